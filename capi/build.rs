@@ -0,0 +1,22 @@
+//! Build script for the C ABI.
+//!
+//! Generates the C header for `gql_parser::capi`, whose source is
+//! `../src/capi`.
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=../src/capi");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let config = cbindgen::Config::from_file("cbindgen.toml").expect("invalid cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("../src/capi/mod.rs")
+        .generate()
+        .expect("failed to generate the C header")
+        .write_to_file(out_dir.join("gql_parser.h"));
+}
@@ -0,0 +1,263 @@
+/* C ABI of the gql_parser crate. */
+
+#ifndef GQL_PARSER_H
+#define GQL_PARSER_H
+
+/* Generated by cbindgen from src/capi; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Parent index of the root node.
+ */
+#define GQL_NO_PARENT (size_t)~0
+
+/**
+ * Severity of a diagnostic.
+ */
+typedef enum GqlSeverity {
+  GQL_SEVERITY_ERROR,
+  GQL_SEVERITY_WARNING,
+  GQL_SEVERITY_NOTE,
+} GqlSeverity;
+
+/**
+ * The outcome of parsing a query, owning its AST and diagnostics.
+ */
+typedef struct GqlResult GqlResult;
+
+/**
+ * A source range with its start and end positions.
+ */
+typedef struct GqlSpan {
+  /**
+   * Byte offset of the first character.
+   */
+  size_t start;
+  /**
+   * Byte offset past the last character.
+   */
+  size_t end;
+  size_t start_line;
+  size_t start_column;
+  size_t end_line;
+  size_t end_column;
+} GqlSpan;
+
+/**
+ * A node of the flattened AST.
+ *
+ * Nodes are stored in pre-order, so children follow their parent and a
+ * node's subtree ends at the next node with the same or smaller depth.
+ */
+typedef struct GqlNode {
+  /**
+   * The AST type or expression variant, such as `NodePattern` or
+   * `Comparison`.
+   */
+  const char *kind;
+  /**
+   * The variable of element patterns and LET bindings, the alias of
+   * return items, the name of simple labels and of referenced variables,
+   * parameters and properties; null otherwise.
+   */
+  const char *detail;
+  struct GqlSpan span;
+  /**
+   * Index of the enclosing node, or `GQL_NO_PARENT` for the program.
+   */
+  size_t parent;
+  /**
+   * Nesting depth, 0 for the program.
+   */
+  size_t depth;
+} GqlNode;
+
+/**
+ * A diagnostic reported by the parser or the validator.
+ */
+typedef struct GqlDiagnostic {
+  enum GqlSeverity severity;
+  /**
+   * Diagnostic code, or null.
+   */
+  const char *code;
+  const char *message;
+  /**
+   * Suggested fix, or null.
+   */
+  const char *help;
+  /**
+   * Whether the diagnostic has a label; `span` is zeroed otherwise.
+   */
+  bool has_span;
+  /**
+   * Span of the primary label, or of the first label if none is primary.
+   */
+  struct GqlSpan span;
+  size_t label_count;
+  size_t note_count;
+} GqlDiagnostic;
+
+/**
+ * A labeled span of a diagnostic.
+ */
+typedef struct GqlLabel {
+  /**
+   * Whether the label marks the cause of the diagnostic rather than
+   * related context.
+   */
+  bool primary;
+  /**
+   * Label text, possibly empty.
+   */
+  const char *message;
+  struct GqlSpan span;
+} GqlLabel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns the library version as a static string.
+ */
+const char *gql_version(void);
+
+/**
+ * Parses GQL source text.
+ *
+ * Returns a result to release with `gql_result_free`, or null if `source`
+ * is null with a non-zero `length` or the parser failed unexpectedly.
+ * Source text that is not UTF-8 yields a result with one error.
+ *
+ * # Safety
+ *
+ * `source` must be null or point to `length` readable bytes; it does not
+ * need to be NUL-terminated.
+ */
+struct GqlResult *gql_parse(const char *source, size_t length);
+
+/**
+ * Parses GQL source text and validates it semantically if it parsed
+ * without diagnostics, like `gql_parse` otherwise.
+ *
+ * # Safety
+ *
+ * `source` must be null or point to `length` readable bytes; it does not
+ * need to be NUL-terminated.
+ */
+struct GqlResult *gql_parse_and_validate(const char *source, size_t length);
+
+/**
+ * Releases a result and every string obtained from it.
+ *
+ * # Safety
+ *
+ * `result` must be null or a result returned by this library that has not
+ * been freed yet.
+ */
+void gql_result_free(struct GqlResult *result);
+
+/**
+ * Returns whether parsing produced an AST, which it may do despite errors
+ * by recovering at statement boundaries.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+bool gql_result_has_ast(const struct GqlResult *result);
+
+/**
+ * Returns whether any diagnostic is an error.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+bool gql_result_has_errors(const struct GqlResult *result);
+
+/**
+ * Returns the flattened AST as a JSON object, or null without an AST.
+ *
+ * The object has a `nodes` array holding the nodes in pre-order, each with
+ * the fields `kind`, `detail`, `start`, `end`, `parent` and `depth` of
+ * `GqlNode`; `detail` and `parent` may be `null`.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+const char *gql_result_ast_json(const struct GqlResult *result);
+
+/**
+ * Returns the number of nodes in the flattened AST.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+size_t gql_result_node_count(const struct GqlResult *result);
+
+/**
+ * Writes the node at `index` to `out`, returning false if there is none.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result, and `out` null or writable.
+ */
+bool gql_result_node(const struct GqlResult *result, size_t index, struct GqlNode *out);
+
+/**
+ * Returns the number of diagnostics.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+size_t gql_result_diagnostic_count(const struct GqlResult *result);
+
+/**
+ * Writes the diagnostic at `index` to `out`, returning false if there is
+ * none.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result, and `out` null or writable.
+ */
+bool gql_result_diagnostic(const struct GqlResult *result, size_t index, struct GqlDiagnostic *out);
+
+/**
+ * Writes label `label` of diagnostic `diagnostic` to `out`, returning
+ * false if there is none.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result, and `out` null or writable.
+ */
+bool gql_result_diagnostic_label(const struct GqlResult *result,
+                                 size_t diagnostic,
+                                 size_t label,
+                                 struct GqlLabel *out);
+
+/**
+ * Returns note `note` of diagnostic `diagnostic`, or null if there is none.
+ *
+ * # Safety
+ *
+ * `result` must be null or a live result.
+ */
+const char *gql_result_diagnostic_note(const struct GqlResult *result,
+                                       size_t diagnostic,
+                                       size_t note);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GQL_PARSER_H */
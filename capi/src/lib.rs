@@ -0,0 +1,7 @@
+//! C ABI of the `gql_parser` crate.
+//!
+//! Re-exports [`gql_parser::capi`] and builds it as a static and a shared
+//! library for C and C++ programs, with the header
+//! `capi/include/gql_parser.h`.
+
+pub use gql_parser::capi::*;
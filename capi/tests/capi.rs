@@ -0,0 +1,128 @@
+//! C ABI integration tests
+//!
+//! Queries are run through `tests/driver/driver.c`, a C program that reads
+//! results through the ABI alone; the tests compare what it prints. The
+//! driver is built by the `gql_capi_driver` dev-dependency, so only tests
+//! compile it.
+
+use std::ffi::{CStr, c_char, c_int};
+
+use gql_capi_driver::{gql_capi_driver_check_null_handling, gql_capi_driver_run};
+use gql_parser::capi::GQL_NO_PARENT;
+
+fn drive(source: &[u8], validate: bool) -> String {
+    let mut output = vec![0 as c_char; 64 * 1024];
+    // SAFETY: the source and output buffers are valid for their lengths.
+    let status = unsafe {
+        gql_capi_driver_run(
+            source.as_ptr().cast(),
+            source.len(),
+            c_int::from(validate),
+            output.as_mut_ptr(),
+            output.len(),
+        )
+    };
+    assert_eq!(status, 0, "the driver failed or truncated its output");
+    // SAFETY: the driver NUL-terminates the output.
+    let output = unsafe { CStr::from_ptr(output.as_ptr()) };
+    output.to_str().expect("driver output is UTF-8").to_string()
+}
+
+#[test]
+fn test_checked_in_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/gql_parser.h"));
+    let checked_in = include_str!("../include/gql_parser.h");
+    assert!(
+        generated == checked_in,
+        "include/gql_parser.h is stale; copy the header generated in OUT_DIR over it"
+    );
+    assert_eq!(GQL_NO_PARENT, usize::MAX);
+}
+
+#[test]
+fn test_driver_walks_flattened_ast() {
+    let output = drive(
+        b"MATCH (n:Person)-[e:KNOWS]->(m) WHERE n.age > $min RETURN m.name AS name",
+        false,
+    );
+    let (tree, json) = output.split_once("json ").expect("expected AST JSON");
+    assert_eq!(
+        tree,
+        "\
+ast=1 errors=0
+Program [0,72)
+  QueryStatement [0,68)
+    LinearQuery [0,68)
+      MatchStatement [0,50)
+        GraphPattern [6,50)
+          PathPattern [6,31)
+            NodePattern n [6,16)
+              LabelExpression Person [9,15)
+            EdgePattern e [16,28)
+              LabelExpression KNOWS [20,25)
+            NodePattern m [28,31)
+          Comparison [38,50)
+            PropertyReference age [38,43)
+              VariableReference n [38,39)
+            ParameterReference min [46,50)
+      ReturnStatement [51,72)
+        ReturnItem name [58,72)
+          PropertyReference name [58,64)
+            VariableReference m [58,59)
+"
+    );
+    assert!(json.starts_with(
+        r#"{"nodes":[{"kind":"Program","detail":null,"start":0,"end":72,"parent":null,"depth":0},"#
+    ));
+    assert!(json.contains(
+        r#"{"kind":"NodePattern","detail":"n","start":6,"end":16,"parent":5,"depth":6}"#
+    ));
+}
+
+#[test]
+fn test_driver_reports_syntax_errors_with_positions() {
+    let output = drive(b"MATCH (n:Person)\nRETURN n.name AS", false);
+    assert!(output.starts_with("ast=1 errors=1\n"), "{output}");
+    assert!(
+        output.contains(
+            "\nerror: Expected alias after AS in RETURN item @ 2:15-2:17\n  \
+             primary [31,33) 2:15-2:17 AS must be followed by an identifier\n"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_driver_validates_semantics() {
+    let source = b"MATCH (n:Person) RETURN m";
+    assert!(drive(source, false).starts_with("ast=1 errors=0\n"));
+
+    let output = drive(source, true);
+    assert!(output.starts_with("ast=1 errors=1\n"), "{output}");
+    assert!(
+        output.contains(
+            "\nerror: Undefined variable 'm' @ 1:25-1:26\n  \
+             primary [24,25) 1:25-1:26 variable not defined\n"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_driver_rejects_invalid_utf8() {
+    let output = drive(b"MATCH (n) RETURN '\xff'", false);
+    assert_eq!(
+        output,
+        "\
+ast=0 errors=1
+error: source is not valid UTF-8 @ 1:19-1:19
+  primary [18,18) 1:19-1:19 invalid byte sequence
+"
+    );
+}
+
+#[test]
+fn test_driver_handles_null_and_out_of_range_arguments() {
+    // SAFETY: the check takes no arguments.
+    assert_eq!(unsafe { gql_capi_driver_check_null_handling() }, 0);
+}
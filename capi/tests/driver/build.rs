@@ -0,0 +1,14 @@
+//! Compiles the C driver against the checked-in header, which
+//! `tests/capi.rs` keeps in sync with the generated one.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=driver.c");
+    println!("cargo:rerun-if-changed=../../include/gql_parser.h");
+
+    cc::Build::new()
+        .file("driver.c")
+        .include("../../include")
+        .warnings_into_errors(true)
+        .compile("gql_capi_driver");
+}
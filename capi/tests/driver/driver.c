@@ -0,0 +1,182 @@
+/*
+ * C driver for the capi integration tests.
+ *
+ * Parses a query through the C ABI and prints the flattened AST, the
+ * diagnostics and the AST JSON into a buffer that the Rust tests compare.
+ */
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "gql_parser.h"
+
+typedef struct Output {
+    char *data;
+    size_t capacity;
+    size_t length;
+    int truncated;
+} Output;
+
+static void print(Output *output, const char *format, ...) {
+    va_list args;
+    size_t available = output->capacity - output->length;
+    int written;
+
+    va_start(args, format);
+    written = vsnprintf(output->data + output->length, available, format, args);
+    va_end(args);
+    if (written < 0 || (size_t)written >= available) {
+        output->length = output->capacity - 1;
+        output->truncated = 1;
+    } else {
+        output->length += (size_t)written;
+    }
+}
+
+static void print_span(Output *output, GqlSpan span) {
+    print(output, "%zu:%zu-%zu:%zu", span.start_line, span.start_column, span.end_line,
+          span.end_column);
+}
+
+static const char *severity_name(GqlSeverity severity) {
+    switch (severity) {
+    case GQL_SEVERITY_ERROR:
+        return "error";
+    case GQL_SEVERITY_WARNING:
+        return "warning";
+    case GQL_SEVERITY_NOTE:
+        return "note";
+    }
+    return "unknown";
+}
+
+static void print_nodes(Output *output, const GqlResult *result) {
+    size_t count = gql_result_node_count(result);
+    size_t index;
+    GqlNode node;
+    GqlNode parent;
+
+    for (index = 0; index < count; index++) {
+        if (!gql_result_node(result, index, &node)) {
+            print(output, "missing node %zu\n", index);
+            return;
+        }
+        if (node.parent == GQL_NO_PARENT ? node.depth != 0
+                                         : node.parent >= index ||
+                                               !gql_result_node(result, node.parent, &parent) ||
+                                               parent.depth + 1 != node.depth) {
+            print(output, "inconsistent parent for node %zu\n", index);
+        }
+        print(output, "%*s%s", (int)(node.depth * 2), "", node.kind);
+        if (node.detail != NULL) {
+            print(output, " %s", node.detail);
+        }
+        print(output, " [%zu,%zu)\n", node.span.start, node.span.end);
+    }
+    if (gql_result_node(result, count, &node)) {
+        print(output, "node past the end\n");
+    }
+}
+
+static void print_diagnostics(Output *output, const GqlResult *result) {
+    size_t count = gql_result_diagnostic_count(result);
+    size_t index;
+    size_t item;
+    GqlDiagnostic diagnostic;
+    GqlLabel label;
+
+    for (index = 0; index < count; index++) {
+        if (!gql_result_diagnostic(result, index, &diagnostic)) {
+            print(output, "missing diagnostic %zu\n", index);
+            return;
+        }
+        print(output, "%s", severity_name(diagnostic.severity));
+        if (diagnostic.code != NULL) {
+            print(output, "[%s]", diagnostic.code);
+        }
+        print(output, ": %s", diagnostic.message);
+        if (diagnostic.has_span) {
+            print(output, " @ ");
+            print_span(output, diagnostic.span);
+        }
+        print(output, "\n");
+        for (item = 0; item < diagnostic.label_count; item++) {
+            if (gql_result_diagnostic_label(result, index, item, &label)) {
+                print(output, "  %s [%zu,%zu) ", label.primary ? "primary" : "secondary",
+                      label.span.start, label.span.end);
+                print_span(output, label.span);
+                print(output, " %s\n", label.message);
+            }
+        }
+        if (diagnostic.help != NULL) {
+            print(output, "  help: %s\n", diagnostic.help);
+        }
+        for (item = 0; item < diagnostic.note_count; item++) {
+            print(output, "  note: %s\n", gql_result_diagnostic_note(result, index, item));
+        }
+    }
+}
+
+/*
+ * Parses `length` bytes of `source`, validating them if `validate` is set,
+ * and prints the result to `output`. Returns 0 on success, 1 if the output
+ * was truncated and -1 if no result was returned.
+ */
+int gql_capi_driver_run(const char *source, size_t length, int validate, char *output,
+                        size_t capacity) {
+    Output out = {output, capacity, 0, 0};
+    GqlResult *result;
+    const char *json;
+
+    output[0] = '\0';
+    result = validate ? gql_parse_and_validate(source, length) : gql_parse(source, length);
+    if (result == NULL) {
+        return -1;
+    }
+    print(&out, "ast=%d errors=%d\n", gql_result_has_ast(result),
+          gql_result_has_errors(result));
+    print_nodes(&out, result);
+    print_diagnostics(&out, result);
+    json = gql_result_ast_json(result);
+    if (json != NULL) {
+        print(&out, "json %s\n", json);
+    }
+    gql_result_free(result);
+    return out.truncated;
+}
+
+/*
+ * Checks that accessors tolerate null results and out-of-range indices.
+ * Returns the number of failed checks.
+ */
+int gql_capi_driver_check_null_handling(void) {
+    int failures = 0;
+    GqlNode node;
+    GqlDiagnostic diagnostic;
+    GqlLabel label;
+    GqlResult *result;
+
+    failures += gql_parse(NULL, 4) != NULL;
+    failures += gql_result_has_ast(NULL);
+    failures += gql_result_has_errors(NULL);
+    failures += gql_result_ast_json(NULL) != NULL;
+    failures += gql_result_node_count(NULL) != 0;
+    failures += gql_result_node(NULL, 0, &node);
+    failures += gql_result_diagnostic_count(NULL) != 0;
+    failures += gql_result_diagnostic(NULL, 0, &diagnostic);
+    failures += gql_result_diagnostic_label(NULL, 0, 0, &label);
+    failures += gql_result_diagnostic_note(NULL, 0, 0) != NULL;
+    gql_result_free(NULL);
+
+    result = gql_parse(NULL, 0);
+    failures += result == NULL;
+    failures += !gql_result_has_ast(result);
+    failures += gql_result_node(result, 0, NULL);
+    failures += gql_result_diagnostic(result, 0, &diagnostic);
+    failures += gql_result_diagnostic_label(result, 0, 0, &label);
+    gql_result_free(result);
+
+    failures += strlen(gql_version()) == 0;
+    return failures;
+}
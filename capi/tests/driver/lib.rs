@@ -0,0 +1,25 @@
+//! Entry points of `driver.c`, a C program that reads parse results
+//! through the C ABI alone.
+//!
+//! The driver calls the `gql_*` functions of the C ABI, so it links only
+//! into binaries that also link `gql_parser` with the `capi` feature, such
+//! as the tests of `gql_parser-capi`.
+
+use std::ffi::{c_char, c_int};
+
+unsafe extern "C" {
+    /// Parses `length` bytes of `source`, validating them if `validate` is
+    /// set, and prints the result to `output`. Returns 0 on success, 1 if
+    /// the output was truncated and -1 if no result was returned.
+    pub fn gql_capi_driver_run(
+        source: *const c_char,
+        length: usize,
+        validate: c_int,
+        output: *mut c_char,
+        capacity: usize,
+    ) -> c_int;
+
+    /// Checks that accessors tolerate null results and out-of-range
+    /// indices. Returns the number of failed checks.
+    pub fn gql_capi_driver_check_null_handling() -> c_int;
+}
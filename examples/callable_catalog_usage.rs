@@ -15,8 +15,9 @@
 use gql_parser::semantic::callable::{
     CallableKind, resolve_builtin_signatures, list_builtin_callables,
     CallableSignature, CallableValidator, DefaultCallableValidator,
-    Nullability, ParameterSignature, Volatility,
+    Nullability, ParameterSignature, SignatureType, Volatility,
 };
+use gql_parser::ir::type_table::Type;
 use gql_parser::semantic::metadata_provider::MockMetadataProvider;
 use gql_parser::semantic::SemanticValidator;
 
@@ -120,12 +121,12 @@ fn main() {
             "distance",
             CallableKind::Function,
             vec![
-                ParameterSignature::required("lat1", "FLOAT"),
-                ParameterSignature::required("lon1", "FLOAT"),
-                ParameterSignature::required("lat2", "FLOAT"),
-                ParameterSignature::required("lon2", "FLOAT"),
+                ParameterSignature::required("lat1", Type::Float),
+                ParameterSignature::required("lon1", Type::Float),
+                ParameterSignature::required("lat2", Type::Float),
+                ParameterSignature::required("lon2", Type::Float),
             ],
-            Some("FLOAT"),
+            Some(Type::Float),
         )
         .with_volatility(Volatility::Immutable)
         .with_nullability(Nullability::NullOnNullInput),
@@ -138,11 +139,11 @@ fn main() {
             "log_event",
             CallableKind::Procedure,
             vec![
-                ParameterSignature::required("level", "STRING"),
-                ParameterSignature::required("message", "STRING"),
-                ParameterSignature::optional("details", "STRING"),
+                ParameterSignature::required("level", Type::String),
+                ParameterSignature::required("message", Type::String),
+                ParameterSignature::optional("details", Type::String),
             ],
-            None::<String>, // Procedures don't return values
+            None::<SignatureType>, // Procedures don't return values
         )
         .with_volatility(Volatility::Volatile),
     );
@@ -154,10 +155,10 @@ fn main() {
             "join_strings",
             CallableKind::Function,
             vec![
-                ParameterSignature::required("separator", "STRING"),
-                ParameterSignature::variadic("strings", "STRING"),
+                ParameterSignature::required("separator", Type::String),
+                ParameterSignature::variadic("strings", Type::String),
             ],
-            Some("STRING"),
+            Some(Type::String),
         )
         .with_volatility(Volatility::Immutable),
     );
@@ -0,0 +1,202 @@
+"""Tests for the Python bindings.
+
+Run against a locally built wheel:
+
+    maturin build --release
+    pip install target/wheels/gql_parser-*.whl pytest
+    pytest
+"""
+
+import json
+
+import pytest
+
+import gql_parser
+from gql_parser import (
+    ConstraintMeta,
+    PatternInfo,
+    PropertyMeta,
+    QueryInfo,
+    SchemaSnapshot,
+    SchemaSnapshotBuilder,
+    VariableDependencyGraph,
+    parse,
+    parse_and_validate,
+)
+
+QUERY = "MATCH (n:Person)-[e:KNOWS]->(m) WHERE n.age > $min RETURN m.name AS name"
+
+
+def social_schema():
+    return (
+        SchemaSnapshotBuilder()
+        .with_node_type(
+            "Person",
+            lambda person: person.add_property(PropertyMeta.string("name", required=True))
+            .add_property(PropertyMeta.int("age"))
+            .add_constraint(ConstraintMeta.primary_key(["name"])),
+        )
+        .with_edge_type("KNOWS", lambda knows: knows.connecting("Person", "Person"))
+        .build()
+    )
+
+
+def test_module_exposes_version():
+    assert gql_parser.__version__
+
+
+def test_parse_returns_program_and_statements():
+    result = parse(QUERY)
+    assert not result.has_errors
+    assert result.diagnostics == []
+
+    program = result.ast
+    assert len(program) == 1
+    statement = program[0]
+    assert statement.kind == "QueryStatement"
+    assert statement.span == (0, 68)
+    assert [s.kind for s in program.statements] == ["QueryStatement"]
+    with pytest.raises(IndexError):
+        program[1]
+
+
+def test_to_dict_nests_the_flattened_ast():
+    tree = parse(QUERY).ast.to_dict()
+    assert tree["kind"] == "Program"
+    assert tree["span"] == (0, 72)
+
+    def find(node, kind):
+        if node["kind"] == kind:
+            yield node
+        for child in node["children"]:
+            yield from find(child, kind)
+
+    nodes = list(find(tree, "NodePattern"))
+    assert [(node["detail"], node["span"]) for node in nodes] == [("n", (6, 16)), ("m", (28, 31))]
+    assert nodes[0]["children"][0] == {
+        "kind": "LabelExpression",
+        "detail": "Person",
+        "span": (9, 15),
+        "children": [],
+    }
+    assert [node["detail"] for node in find(tree, "ReturnItem")] == ["name"]
+
+    statement = parse(QUERY).ast[0].to_dict()
+    assert statement["kind"] == "QueryStatement"
+    assert statement["children"][0]["kind"] == "LinearQuery"
+    json.dumps(statement)
+
+
+def test_syntax_errors_are_diags_with_positions():
+    result = parse("MATCH (n:Person)\nRETURN n.name AS")
+    assert result.has_errors
+    [diag] = result.diagnostics
+    assert diag.severity == "error"
+    assert diag.message == "Expected alias after AS in RETURN item"
+    [label] = diag.labels
+    assert label.primary
+    assert label.message == "AS must be followed by an identifier"
+    assert label.span == (31, 33)
+    assert label.start == (2, 15)
+    assert label.end == (2, 17)
+    assert str(diag) == "error: Expected alias after AS in RETURN item"
+
+
+def test_parse_and_validate_reports_semantic_errors():
+    assert not parse("MATCH (n:Person) RETURN m").has_errors
+
+    result = parse_and_validate("MATCH (n:Person) RETURN m")
+    [diag] = result.diagnostics
+    assert diag.message == "Undefined variable 'm'"
+    assert diag.labels[0].span == (24, 25)
+    assert parse_and_validate("MATCH (n:Person) RETURN n").diagnostics == []
+
+
+def test_render_formats_diagnostics():
+    result = parse_and_validate("MATCH (n:Person) RETURN m")
+    assert "^ variable not defined" in result.render()
+    record = json.loads(result.render("json").splitlines()[0])
+    assert record["message"] == "Undefined variable 'm'"
+    assert json.loads(result.render("sarif"))["version"] == "2.1.0"
+    with pytest.raises(ValueError):
+        result.render("xml")
+
+
+def test_schema_builder_mirrors_rust_builder():
+    schema = social_schema()
+    assert isinstance(schema, SchemaSnapshot)
+    assert schema.node_types == ["Person"]
+    assert schema.edge_types == ["KNOWS"]
+    assert "Person" in SchemaSnapshot.example().node_types
+
+    builder = SchemaSnapshotBuilder()
+    builder.build()
+    with pytest.raises(RuntimeError):
+        builder.build()
+
+
+def test_parse_and_validate_checks_the_schema():
+    schema = social_schema()
+    known = parse_and_validate("MATCH (n:Person)-[:KNOWS]->(m) RETURN m.name", schema=schema)
+    assert known.diagnostics == []
+
+    [unknown_label] = parse_and_validate("MATCH (n:Animal) RETURN n", schema=schema).diagnostics
+    assert unknown_label.message == "Unknown label 'Animal'"
+
+    [unknown_property] = parse_and_validate(
+        "MATCH (n:Person) RETURN n.salary", schema=schema
+    ).diagnostics
+    assert "'salary'" in unknown_property.message
+
+    assert parse_and_validate("MATCH (n:Animal) RETURN n").diagnostics == []
+
+
+def test_query_info_describes_clauses():
+    info = QueryInfo.from_ast(parse(QUERY).ast[0])
+    assert [clause.kind for clause in info.clause_sequence] == ["Match", "Return"]
+    assert info.query_shape == {"kind": "linear", "pipeline_id": 0}
+    assert info.graph_pattern_count == 1
+    assert not info.contains_aggregation
+
+    match = info.clause_sequence[0]
+    assert match.clause_id == (0, 0)
+    assert not match.optional
+    assert match.definitions == ["e", "m", "n"]
+    [reference] = match.property_references
+    assert (reference.variable, reference.property, reference.span) == ("n", "age", (38, 43))
+
+    composite = QueryInfo.from_ast(
+        parse("MATCH (n) RETURN n UNION ALL MATCH (m) RETURN m").ast[0]
+    )
+    assert composite.query_shape == {
+        "kind": "composite",
+        "operator": "UNION ALL",
+        "left": {"kind": "linear", "pipeline_id": 0},
+        "right": {"kind": "linear", "pipeline_id": 1},
+    }
+
+
+def test_pattern_info_analyzes_each_graph_pattern():
+    statement = parse("MATCH (a:A|B)-[:R]->(b) MATCH (c) RETURN a").ast[0]
+    first, second = PatternInfo.from_statement(statement)
+    assert (first.node_count, first.edge_count, first.path_count) == (2, 1, 1)
+    assert first.label_expression_complexity == "Boolean"
+    assert first.is_fully_connected
+    assert (second.node_count, second.edge_count) == (1, 0)
+    assert second.label_expression_complexity == "None"
+
+
+def test_variable_dependency_graph_links_definitions_to_uses():
+    graph = VariableDependencyGraph.build(parse("MATCH (n) LET x = n.age RETURN x, y").ast[0])
+    assert graph.unresolved_variables() == ["y"]
+    for edge in graph.edges:
+        definition = graph.definition_points[edge.definition_index]
+        usage = graph.usage_points[edge.usage_index]
+        assert definition.variable == usage.variable == edge.variable
+    definitions = {
+        (edge.variable, graph.definition_points[edge.definition_index].clause_kind)
+        for edge in graph.edges
+    }
+    assert definitions == {("n", "Match"), ("x", "Let")}
+    unresolved = [graph.usage_points[index] for index in graph.unresolved_usage_indices]
+    assert [(usage.variable, usage.clause_kind) for usage in unresolved] == [("y", "Return")]
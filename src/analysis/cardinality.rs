@@ -0,0 +1,1199 @@
+//! Cardinality estimation.
+//!
+//! [`CardinalityEstimator`] annotates a query with estimated row counts,
+//! using the data statistics exposed by a [`StatisticsProvider`]:
+//!
+//! - Node patterns start from the number of nodes matching their label
+//!   expression, reduced by their property specification and WHERE clause.
+//! - Expansions multiply by the average number of matching edges per
+//!   source node, taken from edge counts per endpoint label pair.
+//!   Quantified expansions sum the expected paths over the repetition range.
+//! - WHERE predicates are split into conjuncts, each with a selectivity from
+//!   most common values, histograms and distinct counts.
+//! - Clauses join on shared variables by dividing by the size of the
+//!   variable's domain, in the usual independence-assuming way.
+//!
+//! Missing statistics fall back to fixed default selectivities. Path modes
+//! are ignored; selective path searches cap a path pattern at the requested
+//! number of paths per endpoint pair.
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::analysis::cardinality::CardinalityEstimator;
+//! use gql_parser::parse;
+//! use gql_parser::semantic::statistics_provider::MockStatisticsProvider;
+//!
+//! let program = parse("MATCH (p:Person)-[:LIVES_IN]->(c:City) RETURN c").ast.unwrap();
+//! let statistics = MockStatisticsProvider::example();
+//! let estimate = CardinalityEstimator::new(&statistics)
+//!     .estimate_statement(&program.statements[0])
+//!     .unwrap();
+//! assert_eq!(estimate.rows, 1000.0);
+//! ```
+
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use crate::analysis::const_eval::{ConstEvaluator, Value};
+use crate::analysis::expression_info::ExpressionInfo;
+use crate::analysis::pattern_info::PatternInfo;
+use crate::analysis::query_info::{ClauseId, ClauseKind};
+use crate::ast::expression::{
+    BooleanValue, ComparisonOperator, Literal, LogicalOperator, Predicate,
+};
+use crate::ast::query::{
+    AbbreviatedEdgePattern, EdgeDirection, EdgePattern, ElementPattern,
+    ElementPropertySpecification, GraphPattern, GraphPatternQuantifier, GroupingElement,
+    LabelExpression, LinearQuery, MatchStatement, NodePattern, OptionalOperand,
+    OrderByAndPageStatement, PathFactor, PathPatternExpression, PathPatternPrefix, PathPrimary,
+    PathSearch, PrimitiveQueryStatement, PrimitiveResultStatement, Query, ReturnItemList,
+    SetOperator, SetQuantifier, ShortestPathSearch,
+};
+use crate::ast::{Expression, Span, Statement, UnaryOperator};
+use crate::semantic::schema_catalog::TypeRef;
+use crate::semantic::statistics_provider::{PropertyStatistics, StatisticsProvider};
+
+/// Selectivity of an equality without statistics.
+const DEFAULT_EQUALITY_SELECTIVITY: f64 = 0.1;
+/// Selectivity of a range comparison without statistics.
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+/// Selectivity of IS NULL without statistics.
+const DEFAULT_NULL_SELECTIVITY: f64 = 0.05;
+/// Fraction of elements carrying a label without a count.
+const DEFAULT_LABEL_SELECTIVITY: f64 = 0.1;
+/// Selectivity of any other predicate.
+const DEFAULT_SELECTIVITY: f64 = 0.5;
+/// Assumed length of a list iterated by FOR.
+const DEFAULT_LIST_LENGTH: f64 = 10.0;
+/// Repetitions assumed beyond the lower bound of an unbounded quantifier.
+const UNBOUNDED_REPETITIONS: u32 = 4;
+
+/// Estimate for a node pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeEstimate {
+    /// Node pattern span.
+    pub span: Span,
+    /// Element variable, if declared.
+    pub variable: Option<SmolStr>,
+    /// Number of nodes matching the label expression.
+    pub label_rows: f64,
+    /// Selectivity of the property specification and WHERE clause.
+    pub selectivity: f64,
+    /// Number of nodes matching the whole node pattern.
+    pub rows: f64,
+}
+
+/// Estimate for an edge pattern expanding a path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpansionEstimate {
+    /// Edge pattern span.
+    pub span: Span,
+    /// Element variable, if declared.
+    pub variable: Option<SmolStr>,
+    /// Average number of matching edges per node on the left of the edge
+    /// pattern.
+    pub fan_out: f64,
+    /// Average number of matching edges per node on the right of the edge
+    /// pattern, for expanding against the written direction.
+    pub reverse_fan_out: f64,
+    /// Selectivity of the property specification and WHERE clause.
+    pub selectivity: f64,
+    /// Number of partial paths after the expansion, including any filter on
+    /// the node it reaches.
+    pub rows: f64,
+}
+
+/// Estimate for a conjunct of a WHERE clause or FILTER statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredicateEstimate {
+    /// Conjunct span.
+    pub span: Span,
+    /// Variables and properties the conjunct refers to.
+    pub info: ExpressionInfo,
+    /// Fraction of rows expected to satisfy the conjunct.
+    pub selectivity: f64,
+}
+
+/// Estimate for a graph pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternEstimate {
+    /// Graph pattern span.
+    pub span: Span,
+    /// Structure of the pattern.
+    pub info: PatternInfo,
+    /// Node patterns in source order.
+    pub nodes: Vec<NodeEstimate>,
+    /// Edge patterns in source order.
+    pub expansions: Vec<ExpansionEstimate>,
+    /// Conjuncts of the graph pattern WHERE clause.
+    pub predicates: Vec<PredicateEstimate>,
+    /// Number of matches of the whole pattern.
+    pub rows: f64,
+}
+
+/// Estimate for a clause of a linear query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseEstimate {
+    /// Clause id, as assigned by [`QueryInfo`](crate::analysis::QueryInfo).
+    pub clause_id: ClauseId,
+    /// Clause type.
+    pub kind: ClauseKind,
+    /// Clause span.
+    pub span: Span,
+    /// Graph patterns matched by the clause.
+    pub patterns: Vec<PatternEstimate>,
+    /// Conjuncts of a FILTER condition.
+    pub predicates: Vec<PredicateEstimate>,
+    /// Number of rows in the working table after the clause.
+    pub rows: f64,
+}
+
+/// Estimate for a query.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueryEstimate {
+    /// Clauses of all linear queries in traversal order.
+    pub clauses: Vec<ClauseEstimate>,
+    /// Number of result rows.
+    pub rows: f64,
+}
+
+/// Estimates cardinalities from graph statistics.
+pub struct CardinalityEstimator<'s> {
+    statistics: &'s dyn StatisticsProvider,
+}
+
+/// A variable in scope, with the element type its properties belong to and
+/// the size of its domain.
+#[derive(Debug, Clone)]
+struct Binding {
+    owner: Option<TypeRef>,
+    domain: f64,
+}
+
+type Scope = HashMap<SmolStr, Binding>;
+
+/// Label of the node at the current end of a partial path, if it is known
+/// to carry a single label.
+type Endpoint = Option<SmolStr>;
+
+/// State of a path being estimated left to right.
+#[derive(Debug, Clone, Default)]
+struct PathState {
+    started: bool,
+    rows: f64,
+    start_rows: f64,
+    end_rows: f64,
+    endpoint: Endpoint,
+    /// Edge pattern waiting for the node it leads to.
+    pending: Option<(EdgeCounts, Option<SmolStr>, Span, f64)>,
+}
+
+/// Edge counts of an edge pattern, resolved against the endpoint labels
+/// once the node it leads to is known.
+#[derive(Debug, Clone)]
+struct EdgeCounts {
+    types: Vec<Option<SmolStr>>,
+    type_fraction: f64,
+    forward: bool,
+    reverse: bool,
+}
+
+impl<'s> CardinalityEstimator<'s> {
+    /// Creates an estimator reading statistics from `statistics`.
+    pub fn new(statistics: &'s dyn StatisticsProvider) -> Self {
+        Self { statistics }
+    }
+
+    /// Estimates a query statement; returns `None` for other statements.
+    pub fn estimate_statement(&self, statement: &Statement) -> Option<QueryEstimate> {
+        match statement {
+            Statement::Query(statement) => Some(self.estimate_query(&statement.query)),
+            _ => None,
+        }
+    }
+
+    /// Estimates a query.
+    pub fn estimate_query(&self, query: &Query) -> QueryEstimate {
+        let mut estimate = QueryEstimate::default();
+        let mut next_pipeline_id = 0;
+        estimate.rows = self.query(query, &mut next_pipeline_id, &mut estimate.clauses);
+        estimate
+    }
+
+    /// Estimates a graph pattern on its own.
+    pub fn estimate_pattern(&self, pattern: &GraphPattern) -> PatternEstimate {
+        self.pattern(pattern, &mut Scope::new()).0
+    }
+
+    fn query(&self, query: &Query, next: &mut usize, clauses: &mut Vec<ClauseEstimate>) -> f64 {
+        match query {
+            Query::Linear(linear) => {
+                let pipeline_id = *next;
+                *next += 1;
+                self.linear_query(linear, pipeline_id, clauses)
+            }
+            Query::Composite(composite) => {
+                let left = self.query(&composite.left, next, clauses);
+                let right = self.query(&composite.right, next, clauses);
+                match composite.operator {
+                    SetOperator::Union { .. } => left + right,
+                    SetOperator::Except { .. } => left,
+                    SetOperator::Intersect { .. } => left.min(right),
+                    SetOperator::Otherwise if left >= 1.0 => left,
+                    SetOperator::Otherwise => right,
+                }
+            }
+            Query::Parenthesized(inner, _) => self.query(inner, next, clauses),
+        }
+    }
+
+    fn linear_query(
+        &self,
+        query: &LinearQuery,
+        pipeline_id: usize,
+        clauses: &mut Vec<ClauseEstimate>,
+    ) -> f64 {
+        let mut scope = Scope::new();
+        let mut rows = 1.0;
+        let mut push = |kind, span: &Span, patterns, predicates, rows| {
+            clauses.push(ClauseEstimate {
+                clause_id: ClauseId {
+                    pipeline_id,
+                    position: clauses
+                        .iter()
+                        .filter(|clause| clause.clause_id.pipeline_id == pipeline_id)
+                        .count(),
+                },
+                kind,
+                span: span.clone(),
+                patterns,
+                predicates,
+                rows,
+            });
+        };
+
+        if let Some(use_graph) = &query.use_graph {
+            push(
+                ClauseKind::UseGraph,
+                &use_graph.span,
+                Vec::new(),
+                Vec::new(),
+                rows,
+            );
+        }
+
+        for statement in &query.primitive_statements {
+            let span = statement.span();
+            match statement {
+                PrimitiveQueryStatement::Match(MatchStatement::Simple(statement)) => {
+                    let (pattern, shared) = self.pattern(&statement.pattern, &mut scope);
+                    rows = rows * pattern.rows / shared;
+                    push(
+                        ClauseKind::Match { optional: false },
+                        span,
+                        vec![pattern],
+                        Vec::new(),
+                        rows,
+                    );
+                }
+                PrimitiveQueryStatement::Match(MatchStatement::Optional(statement)) => {
+                    let patterns = match &statement.operand {
+                        OptionalOperand::Match { pattern } => vec![pattern.as_ref()],
+                        OptionalOperand::Block { statements }
+                        | OptionalOperand::ParenthesizedBlock { statements } => {
+                            statements.iter().flat_map(match_patterns).collect()
+                        }
+                    };
+                    let mut matched = rows;
+                    let mut estimates = Vec::new();
+                    for pattern in patterns {
+                        let (pattern, shared) = self.pattern(pattern, &mut scope);
+                        matched = matched * pattern.rows / shared;
+                        estimates.push(pattern);
+                    }
+                    rows = rows.max(matched);
+                    push(
+                        ClauseKind::Match { optional: true },
+                        span,
+                        estimates,
+                        Vec::new(),
+                        rows,
+                    );
+                }
+                PrimitiveQueryStatement::Filter(statement) => {
+                    let predicates = self.predicates(&statement.condition, &scope);
+                    rows *= predicates.iter().map(|p| p.selectivity).product::<f64>();
+                    push(ClauseKind::Filter, span, Vec::new(), predicates, rows);
+                }
+                PrimitiveQueryStatement::Let(statement) => {
+                    for binding in &statement.bindings {
+                        declare(&mut scope, &binding.variable.name, None, rows);
+                    }
+                    push(ClauseKind::Let, span, Vec::new(), Vec::new(), rows);
+                }
+                PrimitiveQueryStatement::For(statement) => {
+                    let length = match &statement.item.collection {
+                        Expression::ListConstructor(items, _) => items.len() as f64,
+                        Expression::Literal(Literal::List(items), _) => items.len() as f64,
+                        _ => DEFAULT_LIST_LENGTH,
+                    };
+                    rows *= length;
+                    declare(
+                        &mut scope,
+                        &statement.item.binding_variable.name,
+                        None,
+                        rows,
+                    );
+                    push(ClauseKind::For, span, Vec::new(), Vec::new(), rows);
+                }
+                PrimitiveQueryStatement::OrderByAndPage(statement) => {
+                    rows = page(statement, rows);
+                    push(
+                        ClauseKind::OrderByAndPage,
+                        span,
+                        Vec::new(),
+                        Vec::new(),
+                        rows,
+                    );
+                }
+                PrimitiveQueryStatement::Call(statement) => {
+                    let kind = ClauseKind::Call {
+                        optional: statement.optional,
+                        inline: matches!(
+                            statement.call,
+                            crate::ast::procedure::ProcedureCall::Inline(_)
+                        ),
+                    };
+                    push(kind, span, Vec::new(), Vec::new(), rows);
+                }
+                PrimitiveQueryStatement::Select(_) => {
+                    push(ClauseKind::Select, span, Vec::new(), Vec::new(), rows);
+                }
+            }
+        }
+
+        match query.result_statement.as_deref() {
+            Some(PrimitiveResultStatement::Return(statement)) => {
+                let items: Vec<&Expression> = match &statement.items {
+                    ReturnItemList::Star => Vec::new(),
+                    ReturnItemList::Items { items } => {
+                        items.iter().map(|item| &item.expression).collect()
+                    }
+                };
+                let aggregated = items
+                    .iter()
+                    .any(|item| ExpressionInfo::analyze(item).contains_aggregate);
+                if let Some(group_by) = &statement.group_by {
+                    let keys: Vec<&Expression> = group_by
+                        .elements
+                        .iter()
+                        .filter_map(|element| match element {
+                            GroupingElement::Expression(expression) => Some(expression),
+                            GroupingElement::EmptyGroupingSet => None,
+                        })
+                        .collect();
+                    rows = self.groups(&keys, &scope, rows);
+                } else if aggregated {
+                    let keys: Vec<&Expression> = items
+                        .iter()
+                        .copied()
+                        .filter(|item| !ExpressionInfo::analyze(item).contains_aggregate)
+                        .collect();
+                    rows = self.groups(&keys, &scope, rows);
+                } else if statement.quantifier == Some(SetQuantifier::Distinct) && !items.is_empty()
+                {
+                    rows = self.groups(&items, &scope, rows);
+                }
+                if let Some(order_by_and_page) = &statement.order_by_and_page {
+                    rows = page(order_by_and_page, rows);
+                }
+                push(
+                    ClauseKind::Return,
+                    &statement.span,
+                    Vec::new(),
+                    Vec::new(),
+                    rows,
+                );
+            }
+            Some(PrimitiveResultStatement::Finish(span)) => {
+                push(ClauseKind::Finish, span, Vec::new(), Vec::new(), rows);
+            }
+            None => {}
+        }
+
+        rows
+    }
+
+    /// Estimates the number of groups formed by `keys`: one without keys,
+    /// otherwise the product of the keys' distinct counts, at most `rows`.
+    fn groups(&self, keys: &[&Expression], scope: &Scope, rows: f64) -> f64 {
+        if keys.is_empty() {
+            return rows.min(1.0);
+        }
+        let distinct: f64 = keys
+            .iter()
+            .map(|key| match key {
+                Expression::VariableReference(name, _) => {
+                    scope.get(name).map_or(rows, |binding| binding.domain)
+                }
+                _ => self
+                    .column_statistics(key, scope)
+                    .map_or(rows, |statistics| statistics.distinct_count as f64),
+            })
+            .product();
+        distinct.min(rows)
+    }
+
+    /// Estimates a graph pattern and adds its variables to `scope`. Also
+    /// returns the divisor for joining it with the rows bound before.
+    fn pattern(&self, pattern: &GraphPattern, scope: &mut Scope) -> (PatternEstimate, f64) {
+        let mut estimate = PatternEstimate {
+            span: pattern.span.clone(),
+            info: PatternInfo::analyze(pattern),
+            nodes: Vec::new(),
+            expansions: Vec::new(),
+            predicates: Vec::new(),
+            rows: 1.0,
+        };
+        // Variables bound by earlier clauses join on the clause level; here
+        // only repeated occurrences within the pattern reduce the estimate.
+        let mut local = Scope::new();
+        for path in &pattern.paths.patterns {
+            let mut state = PathState::default();
+            self.path_expression(&path.expression, &mut state, &mut local, &mut estimate);
+            let mut rows = state.rows;
+            if let Some(PathPatternPrefix::PathSearch(search)) = &path.prefix
+                && let Some(paths) = self.paths_per_endpoints(search)
+            {
+                rows = rows.min(paths * state.start_rows * state.end_rows);
+            }
+            estimate.rows *= rows;
+        }
+        // Joining on variables bound by earlier clauses divides by the size
+        // of their domains.
+        let mut shared = 1.0;
+        for (name, binding) in local {
+            match scope.get(&name) {
+                Some(outer) => shared *= outer.domain.max(1.0),
+                None => {
+                    scope.insert(name, binding);
+                }
+            }
+        }
+        if let Some(where_clause) = &pattern.where_clause {
+            estimate.predicates = self.predicates(&where_clause.condition, scope);
+            estimate.rows *= estimate
+                .predicates
+                .iter()
+                .map(|predicate| predicate.selectivity)
+                .product::<f64>();
+        }
+        (estimate, shared)
+    }
+
+    /// Returns how many paths a selective search keeps per endpoint pair.
+    fn paths_per_endpoints(&self, search: &PathSearch) -> Option<f64> {
+        match search {
+            PathSearch::All(_) => None,
+            PathSearch::Any(_)
+            | PathSearch::Shortest(ShortestPathSearch::AnyShortest { .. })
+            | PathSearch::Shortest(ShortestPathSearch::AllShortest { .. }) => Some(1.0),
+            PathSearch::Shortest(
+                ShortestPathSearch::CountedShortest { count, .. }
+                | ShortestPathSearch::CountedShortestGroups { count, .. },
+            ) => Some(match ConstEvaluator::new().evaluate(count) {
+                Ok(Value::Integer(count)) if count > 0 => count as f64,
+                _ => 1.0,
+            }),
+        }
+    }
+
+    fn path_expression(
+        &self,
+        expression: &PathPatternExpression,
+        state: &mut PathState,
+        scope: &mut Scope,
+        estimate: &mut PatternEstimate,
+    ) {
+        let alternatives: Vec<&PathPatternExpression> = match expression {
+            PathPatternExpression::Term(term) => {
+                for factor in &term.factors {
+                    self.path_factor(factor, state, scope, estimate);
+                }
+                return;
+            }
+            PathPatternExpression::Union { left, right, .. } => vec![left, right],
+            PathPatternExpression::Alternation { alternatives, .. } => {
+                alternatives.iter().collect()
+            }
+        };
+        let initial = state.clone();
+        let mut merged: Option<PathState> = None;
+        for alternative in alternatives {
+            let mut branch = initial.clone();
+            self.path_expression(alternative, &mut branch, scope, estimate);
+            merged = Some(match merged {
+                None => branch,
+                Some(mut merged) => {
+                    merged.rows += branch.rows;
+                    merged.start_rows = merged.start_rows.max(branch.start_rows);
+                    merged.end_rows += branch.end_rows;
+                    if merged.endpoint != branch.endpoint {
+                        merged.endpoint = None;
+                    }
+                    merged
+                }
+            });
+        }
+        if let Some(merged) = merged {
+            *state = merged;
+        }
+    }
+
+    fn path_factor(
+        &self,
+        factor: &PathFactor,
+        state: &mut PathState,
+        scope: &mut Scope,
+        estimate: &mut PatternEstimate,
+    ) {
+        match (&factor.primary, &factor.quantifier) {
+            (PathPrimary::ElementPattern(element), None) => match element.as_ref() {
+                ElementPattern::Node(node) => self.node(node, state, scope, estimate),
+                ElementPattern::Edge(edge) => self.edge(edge, state, scope),
+            },
+            (primary, quantifier) => {
+                // A quantified or nested subpath: estimate one iteration
+                // from the current end of the path, then repeat its growth.
+                let mut inner = PathState {
+                    started: state.started,
+                    rows: if state.started { state.rows } else { 1.0 },
+                    start_rows: state.start_rows,
+                    end_rows: state.end_rows,
+                    endpoint: state.endpoint.clone(),
+                    pending: None,
+                };
+                let base = inner.rows;
+                match primary {
+                    PathPrimary::ElementPattern(element) => match element.as_ref() {
+                        ElementPattern::Node(node) => self.node(node, &mut inner, scope, estimate),
+                        ElementPattern::Edge(edge) => {
+                            self.edge(edge, &mut inner, scope);
+                            let (counts, variable, span, selectivity) =
+                                inner.pending.take().expect("edge pattern is pending");
+                            let fan_out = self.fan_out(&counts, &inner.endpoint, &None);
+                            let reverse_fan_out =
+                                self.reverse_fan_out(&counts, &inner.endpoint, &None);
+                            inner.rows *= fan_out * selectivity;
+                            inner.endpoint = None;
+                            estimate.expansions.push(ExpansionEstimate {
+                                span,
+                                variable,
+                                fan_out,
+                                reverse_fan_out,
+                                selectivity,
+                                rows: inner.rows,
+                            });
+                        }
+                    },
+                    PathPrimary::ParenthesizedExpression(expression) => {
+                        self.path_expression(expression, &mut inner, scope, estimate);
+                    }
+                    PathPrimary::SimplifiedExpression(_) => {
+                        let fan_out = self.statistics.edge_count() as f64
+                            / (self.statistics.node_count() as f64).max(1.0);
+                        inner.rows *= fan_out;
+                        inner.endpoint = None;
+                    }
+                }
+                let Some(quantifier) = quantifier else {
+                    *state = inner;
+                    return;
+                };
+                let (first, growth) = if state.started {
+                    (state.rows, inner.rows / base.max(f64::MIN_POSITIVE))
+                } else {
+                    // The subpath starts the path: its first iteration
+                    // supplies the starting rows.
+                    let first = inner.start_rows.max(1.0);
+                    (first, inner.rows / first)
+                };
+                let (min, max) = repetitions(quantifier);
+                let total: f64 = (min..=max).map(|k| growth.powi(k as i32)).sum();
+                state.rows = first * total;
+                if !state.started {
+                    state.start_rows = inner.start_rows;
+                }
+                state.started = true;
+                state.end_rows = inner.end_rows;
+                state.endpoint = None;
+            }
+        }
+    }
+
+    /// Leaves an edge pattern pending until the node it leads to is known.
+    fn edge(&self, edge: &EdgePattern, state: &mut PathState, scope: &mut Scope) {
+        let counts = self.edge_counts(edge);
+        let (variable, selectivity) = match edge {
+            EdgePattern::Full(full) => {
+                let variable = full.filler.variable.as_ref().map(|v| v.variable.clone());
+                if let Some(name) = &variable {
+                    let owner = edge_labels(edge)
+                        .and_then(single_label)
+                        .map(TypeRef::EdgeType);
+                    declare(scope, name, owner, self.statistics.edge_count() as f64);
+                }
+                let selectivity = self.filter_selectivity(
+                    variable.as_ref(),
+                    full.filler.properties.as_ref(),
+                    full.filler.where_clause.as_ref().map(|w| &w.condition),
+                    scope,
+                );
+                (variable, selectivity)
+            }
+            EdgePattern::Abbreviated(_) => (None, 1.0),
+        };
+        state.pending = Some((counts, variable, edge_span(edge).clone(), selectivity));
+    }
+
+    fn node(
+        &self,
+        node: &NodePattern,
+        state: &mut PathState,
+        scope: &mut Scope,
+        estimate: &mut PatternEstimate,
+    ) {
+        let total = self.statistics.node_count() as f64;
+        let variable = node.variable.as_ref().map(|v| v.variable.clone());
+        let label = node.label_expression.as_ref().and_then(single_label);
+        let label_rows = node.label_expression.as_ref().map_or(total, |labels| {
+            self.label_rows(labels, total, &|label| self.statistics.label_count(label))
+        });
+        let repeated = variable
+            .as_ref()
+            .and_then(|name| scope.get(name))
+            .map(|binding| binding.domain);
+        if let Some(name) = &variable {
+            declare(
+                scope,
+                name,
+                label.clone().map(TypeRef::NodeType),
+                label_rows,
+            );
+        }
+        let selectivity = self.filter_selectivity(
+            variable.as_ref(),
+            node.properties.as_ref(),
+            node.where_clause.as_ref().map(|w| &w.condition),
+            scope,
+        );
+        let rows = label_rows * selectivity;
+        estimate.nodes.push(NodeEstimate {
+            span: node.span.clone(),
+            variable,
+            label_rows,
+            selectivity,
+            rows,
+        });
+
+        let label_fraction = label_rows / total.max(1.0);
+        if let Some((counts, variable, span, edge_selectivity)) = state.pending.take() {
+            let fan_out = self.fan_out(&counts, &state.endpoint, &label);
+            let reverse_fan_out = self.reverse_fan_out(&counts, &state.endpoint, &label);
+            // Edge counts per endpoint label already account for a single
+            // label; other label expressions filter the reached nodes.
+            let reached = if label.is_some() { 1.0 } else { label_fraction };
+            state.rows *= fan_out * edge_selectivity * reached * selectivity;
+            estimate.expansions.push(ExpansionEstimate {
+                span,
+                variable,
+                fan_out,
+                reverse_fan_out,
+                selectivity: edge_selectivity,
+                rows: state.rows,
+            });
+        } else if state.started {
+            // Adjacent node patterns match the same node, whose label may
+            // already be known.
+            if label.is_some() && label != state.endpoint {
+                state.rows *= label_fraction;
+            }
+            state.rows *= selectivity;
+            if label.is_none() {
+                return finish_node(state, repeated, rows);
+            }
+        } else {
+            state.rows = rows;
+            state.start_rows = rows;
+            state.started = true;
+        }
+        state.endpoint = label;
+        finish_node(state, repeated, rows);
+    }
+
+    /// Resolves the edge types and directions an edge pattern matches.
+    fn edge_counts(&self, edge: &EdgePattern) -> EdgeCounts {
+        let direction = match edge {
+            EdgePattern::Full(full) => full.direction,
+            EdgePattern::Abbreviated(AbbreviatedEdgePattern::LeftArrow { .. }) => {
+                EdgeDirection::PointingLeft
+            }
+            EdgePattern::Abbreviated(AbbreviatedEdgePattern::RightArrow { .. }) => {
+                EdgeDirection::PointingRight
+            }
+            EdgePattern::Abbreviated(_) => EdgeDirection::AnyDirection,
+        };
+        let (forward, reverse) = match direction {
+            EdgeDirection::PointingRight => (true, false),
+            EdgeDirection::PointingLeft => (false, true),
+            _ => (true, true),
+        };
+        let labels = edge_labels(edge);
+        let (types, type_fraction) = match labels.map(label_alternatives) {
+            Some(Some(names)) => (names.into_iter().map(Some).collect(), 1.0),
+            Some(None) => {
+                let total = self.statistics.edge_count() as f64;
+                let rows = self.label_rows(labels.expect("labels are present"), total, &|label| {
+                    self.statistics.edge_type_count(Some(label), None, None)
+                });
+                (vec![None], rows / total.max(1.0))
+            }
+            None => (vec![None], 1.0),
+        };
+        EdgeCounts {
+            types,
+            type_fraction,
+            forward,
+            reverse,
+        }
+    }
+
+    /// Returns the number of edges matching `counts` from a node labeled
+    /// `from` to a node labeled `to`, in the pattern's directions.
+    fn edges(&self, counts: &EdgeCounts, from: &Endpoint, to: &Endpoint) -> f64 {
+        let mut edges = 0.0;
+        for edge_type in &counts.types {
+            let mut count = |source: &Endpoint, destination: &Endpoint| {
+                edges += self.edge_type_count(edge_type.as_deref(), source, destination);
+            };
+            if counts.forward {
+                count(from, to);
+            }
+            if counts.reverse {
+                count(to, from);
+            }
+        }
+        edges * counts.type_fraction
+    }
+
+    fn edge_type_count(
+        &self,
+        edge_type: Option<&str>,
+        source: &Endpoint,
+        destination: &Endpoint,
+    ) -> f64 {
+        if let Some(count) =
+            self.statistics
+                .edge_type_count(edge_type, source.as_deref(), destination.as_deref())
+        {
+            return count as f64;
+        }
+        // Scale the count of the edge type by the label fractions of the
+        // endpoints, assuming independence.
+        let total = self.statistics.node_count() as f64;
+        let fraction = |label: &Endpoint| {
+            label.as_deref().map_or(1.0, |label| {
+                self.statistics
+                    .label_count(label)
+                    .map_or(DEFAULT_LABEL_SELECTIVITY, |count| {
+                        count as f64 / total.max(1.0)
+                    })
+            })
+        };
+        let edges = self
+            .statistics
+            .edge_type_count(edge_type, None, None)
+            .map_or(
+                self.statistics.edge_count() as f64 * DEFAULT_LABEL_SELECTIVITY,
+                |c| c as f64,
+            );
+        edges * fraction(source) * fraction(destination)
+    }
+
+    fn domain(&self, endpoint: &Endpoint) -> f64 {
+        let total = self.statistics.node_count() as f64;
+        endpoint
+            .as_deref()
+            .map_or(total, |label| {
+                self.statistics
+                    .label_count(label)
+                    .map_or(total * DEFAULT_LABEL_SELECTIVITY, |count| count as f64)
+            })
+            .max(1.0)
+    }
+
+    fn fan_out(&self, counts: &EdgeCounts, from: &Endpoint, to: &Endpoint) -> f64 {
+        self.edges(counts, from, to) / self.domain(from)
+    }
+
+    fn reverse_fan_out(&self, counts: &EdgeCounts, from: &Endpoint, to: &Endpoint) -> f64 {
+        self.edges(counts, from, to) / self.domain(to)
+    }
+
+    /// Returns the number of elements matching a label expression, given a
+    /// count per label and the total number of elements.
+    fn label_rows(
+        &self,
+        labels: &LabelExpression,
+        total: f64,
+        count: &dyn Fn(&str) -> Option<u64>,
+    ) -> f64 {
+        match labels {
+            LabelExpression::LabelName { name, .. } => {
+                count(name).map_or(total * DEFAULT_LABEL_SELECTIVITY, |count| count as f64)
+            }
+            LabelExpression::Wildcard { .. } => total,
+            LabelExpression::Parenthesized { expression, .. } => {
+                self.label_rows(expression, total, count)
+            }
+            LabelExpression::Negation { operand, .. } => {
+                (total - self.label_rows(operand, total, count)).max(0.0)
+            }
+            LabelExpression::Conjunction { left, right, .. } => {
+                let left = self.label_rows(left, total, count);
+                let right = self.label_rows(right, total, count);
+                left * right / total.max(1.0)
+            }
+            LabelExpression::Disjunction { left, right, .. } => {
+                let left = self.label_rows(left, total, count);
+                let right = self.label_rows(right, total, count);
+                (left + right - left * right / total.max(1.0)).min(total)
+            }
+        }
+    }
+
+    /// Returns the selectivity of an element's property specification and
+    /// WHERE clause.
+    fn filter_selectivity(
+        &self,
+        variable: Option<&SmolStr>,
+        properties: Option<&ElementPropertySpecification>,
+        condition: Option<&Expression>,
+        scope: &Scope,
+    ) -> f64 {
+        let owner = variable
+            .and_then(|name| scope.get(name))
+            .and_then(|binding| binding.owner.as_ref());
+        let mut selectivity = 1.0;
+        for pair in properties.iter().flat_map(|p| &p.properties) {
+            let value = ConstEvaluator::new().evaluate(&pair.value).ok();
+            selectivity *= owner
+                .and_then(|owner| self.statistics.property_statistics(owner, &pair.key))
+                .map_or(DEFAULT_EQUALITY_SELECTIVITY, |statistics| {
+                    statistics.equality_selectivity(value.as_ref())
+                });
+        }
+        if let Some(condition) = condition {
+            selectivity *= self.selectivity(condition, scope);
+        }
+        selectivity
+    }
+
+    /// Splits a condition into conjuncts and estimates each.
+    fn predicates(&self, condition: &Expression, scope: &Scope) -> Vec<PredicateEstimate> {
+        let mut conjuncts = Vec::new();
+        collect_conjuncts(condition, &mut conjuncts);
+        conjuncts
+            .into_iter()
+            .map(|conjunct| PredicateEstimate {
+                span: conjunct.span(),
+                info: ExpressionInfo::analyze(conjunct),
+                selectivity: self.selectivity(conjunct, scope),
+            })
+            .collect()
+    }
+
+    fn selectivity(&self, condition: &Expression, scope: &Scope) -> f64 {
+        match condition {
+            Expression::Parenthesized(inner, _) => self.selectivity(inner, scope),
+            Expression::Literal(Literal::Boolean(BooleanValue::True), _) => 1.0,
+            Expression::Literal(Literal::Boolean(_) | Literal::Null, _) => 0.0,
+            Expression::Unary(UnaryOperator::Not, operand, _) => {
+                1.0 - self.selectivity(operand, scope)
+            }
+            Expression::Logical(operator, left, right, _) => {
+                let left = self.selectivity(left, scope);
+                let right = self.selectivity(right, scope);
+                match operator {
+                    LogicalOperator::And => left * right,
+                    LogicalOperator::Or => left + right - left * right,
+                    LogicalOperator::Xor => left + right - 2.0 * left * right,
+                }
+            }
+            Expression::Comparison(operator, left, right, _) => {
+                self.comparison(*operator, left, right, scope)
+            }
+            Expression::Predicate(Predicate::IsNull(operand, negated, _)) => {
+                let null = self
+                    .column_statistics(operand, scope)
+                    .map_or(DEFAULT_NULL_SELECTIVITY, |statistics| {
+                        statistics.null_fraction
+                    });
+                if *negated { 1.0 - null } else { null }
+            }
+            Expression::Predicate(Predicate::PropertyExists(target, property, _)) => {
+                let owner = match target.as_ref() {
+                    Expression::VariableReference(name, _) => {
+                        scope.get(name).and_then(|binding| binding.owner.as_ref())
+                    }
+                    _ => None,
+                };
+                owner
+                    .and_then(|owner| self.statistics.property_statistics(owner, property))
+                    .map_or(1.0 - DEFAULT_NULL_SELECTIVITY, |statistics| {
+                        1.0 - statistics.null_fraction
+                    })
+            }
+            Expression::Predicate(Predicate::IsLabeled(target, Some(labels), negated, _)) => {
+                let total = self.statistics.node_count() as f64;
+                let labeled = match target.as_ref() {
+                    Expression::VariableReference(name, _) => scope.get(name).map(|binding| {
+                        let matching = self
+                            .label_rows(labels, total, &|label| self.statistics.label_count(label));
+                        (matching / binding.domain.max(1.0)).min(1.0)
+                    }),
+                    _ => None,
+                }
+                .unwrap_or(DEFAULT_LABEL_SELECTIVITY);
+                if *negated { 1.0 - labeled } else { labeled }
+            }
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn comparison(
+        &self,
+        operator: ComparisonOperator,
+        left: &Expression,
+        right: &Expression,
+        scope: &Scope,
+    ) -> f64 {
+        let default = match operator {
+            ComparisonOperator::Eq => DEFAULT_EQUALITY_SELECTIVITY,
+            ComparisonOperator::NotEq => 1.0 - DEFAULT_EQUALITY_SELECTIVITY,
+            _ => DEFAULT_RANGE_SELECTIVITY,
+        };
+        let (statistics, other, operator) = match (
+            self.column_statistics(left, scope),
+            self.column_statistics(right, scope),
+        ) {
+            (Some(left), Some(right)) => {
+                // A join between two properties.
+                let distinct = left.distinct_count.max(right.distinct_count).max(1) as f64;
+                return match operator {
+                    ComparisonOperator::Eq => 1.0 / distinct,
+                    ComparisonOperator::NotEq => 1.0 - 1.0 / distinct,
+                    _ => DEFAULT_RANGE_SELECTIVITY,
+                };
+            }
+            (Some(statistics), None) => (statistics, right, operator),
+            (None, Some(statistics)) => (statistics, left, flip(operator)),
+            (None, None) => return default,
+        };
+        if !ExpressionInfo::analyze(other)
+            .variable_references
+            .is_empty()
+        {
+            return default;
+        }
+        let value = ConstEvaluator::new().evaluate(other).ok();
+        let equal = statistics.equality_selectivity(value.as_ref());
+        let non_null = 1.0 - statistics.null_fraction;
+        let below = value
+            .as_ref()
+            .zip(statistics.histogram.as_ref())
+            .and_then(|(value, histogram)| histogram.fraction_below(value));
+        match (operator, below) {
+            (ComparisonOperator::Eq, _) => equal,
+            (ComparisonOperator::NotEq, _) => (non_null - equal).max(0.0),
+            (ComparisonOperator::Lt | ComparisonOperator::LtEq, Some(below)) => below * non_null,
+            (ComparisonOperator::Gt | ComparisonOperator::GtEq, Some(below)) => {
+                (1.0 - below) * non_null
+            }
+            (_, None) => DEFAULT_RANGE_SELECTIVITY,
+        }
+    }
+
+    /// Returns statistics for `expression` if it is a property of a
+    /// variable whose element type is known.
+    fn column_statistics(
+        &self,
+        expression: &Expression,
+        scope: &Scope,
+    ) -> Option<PropertyStatistics> {
+        let Expression::PropertyReference(target, property, _) = expression else {
+            return None;
+        };
+        let Expression::VariableReference(name, _) = target.as_ref() else {
+            return None;
+        };
+        let owner = scope.get(name)?.owner.as_ref()?;
+        self.statistics.property_statistics(owner, property)
+    }
+}
+
+/// Binds `name` unless it is already in scope.
+fn declare(scope: &mut Scope, name: &SmolStr, owner: Option<TypeRef>, domain: f64) {
+    scope
+        .entry(name.clone())
+        .or_insert(Binding { owner, domain });
+}
+
+/// Finishes a node pattern reaching `rows` nodes, joining with an
+/// earlier occurrence of its variable over `repeated` nodes.
+fn finish_node(state: &mut PathState, repeated: Option<f64>, rows: f64) {
+    if let Some(domain) = repeated {
+        state.rows /= domain.max(1.0);
+    }
+    state.end_rows = rows;
+}
+
+fn match_patterns(statement: &MatchStatement) -> Vec<&GraphPattern> {
+    match statement {
+        MatchStatement::Simple(simple) => vec![&simple.pattern],
+        MatchStatement::Optional(optional) => match &optional.operand {
+            OptionalOperand::Match { pattern } => vec![pattern.as_ref()],
+            OptionalOperand::Block { statements }
+            | OptionalOperand::ParenthesizedBlock { statements } => {
+                statements.iter().flat_map(match_patterns).collect()
+            }
+        },
+    }
+}
+
+fn page(statement: &OrderByAndPageStatement, rows: f64) -> f64 {
+    let count = |expression: &Expression| match ConstEvaluator::new().evaluate(expression) {
+        Ok(Value::Integer(count)) if count >= 0 => Some(count as f64),
+        _ => None,
+    };
+    let mut rows = rows;
+    if let Some(offset) = statement.offset.as_ref().and_then(|o| count(&o.count)) {
+        rows = (rows - offset).max(0.0);
+    }
+    if let Some(limit) = statement.limit.as_ref().and_then(|l| count(&l.count)) {
+        rows = rows.min(limit);
+    }
+    rows
+}
+
+fn repetitions(quantifier: &GraphPatternQuantifier) -> (u32, u32) {
+    let (min, max) = match quantifier {
+        GraphPatternQuantifier::Star { .. } => (0, None),
+        GraphPatternQuantifier::Plus { .. } => (1, None),
+        GraphPatternQuantifier::QuestionMark { .. } => (0, Some(1)),
+        GraphPatternQuantifier::Fixed { count, .. } => (*count, Some(*count)),
+        GraphPatternQuantifier::General { min, max, .. } => (min.unwrap_or(0), *max),
+    };
+    (min, max.unwrap_or(min + UNBOUNDED_REPETITIONS).max(min))
+}
+
+pub(crate) fn collect_conjuncts<'a>(
+    expression: &'a Expression,
+    conjuncts: &mut Vec<&'a Expression>,
+) {
+    match expression {
+        Expression::Logical(LogicalOperator::And, left, right, _) => {
+            collect_conjuncts(left, conjuncts);
+            collect_conjuncts(right, conjuncts);
+        }
+        Expression::Parenthesized(inner, _)
+            if matches!(
+                inner.as_ref(),
+                Expression::Logical(LogicalOperator::And, ..)
+            ) =>
+        {
+            collect_conjuncts(inner, conjuncts);
+        }
+        _ => conjuncts.push(expression),
+    }
+}
+
+fn flip(operator: ComparisonOperator) -> ComparisonOperator {
+    match operator {
+        ComparisonOperator::Lt => ComparisonOperator::Gt,
+        ComparisonOperator::Gt => ComparisonOperator::Lt,
+        ComparisonOperator::LtEq => ComparisonOperator::GtEq,
+        ComparisonOperator::GtEq => ComparisonOperator::LtEq,
+        other => other,
+    }
+}
+
+fn edge_labels(edge: &EdgePattern) -> Option<&LabelExpression> {
+    match edge {
+        EdgePattern::Full(full) => full.filler.label_expression.as_ref(),
+        EdgePattern::Abbreviated(_) => None,
+    }
+}
+
+fn edge_span(edge: &EdgePattern) -> &Span {
+    match edge {
+        EdgePattern::Full(full) => &full.span,
+        EdgePattern::Abbreviated(abbreviated) => abbreviated.span(),
+    }
+}
+
+/// Returns the label of a label expression that is a single label name.
+pub(crate) fn single_label(labels: &LabelExpression) -> Option<SmolStr> {
+    match labels {
+        LabelExpression::LabelName { name, .. } => Some(name.clone()),
+        LabelExpression::Parenthesized { expression, .. } => single_label(expression),
+        _ => None,
+    }
+}
+
+/// Returns the labels of a label expression that is a disjunction of label
+/// names.
+fn label_alternatives(labels: &LabelExpression) -> Option<Vec<SmolStr>> {
+    match labels {
+        LabelExpression::LabelName { name, .. } => Some(vec![name.clone()]),
+        LabelExpression::Parenthesized { expression, .. } => label_alternatives(expression),
+        LabelExpression::Disjunction { left, right, .. } => {
+            let mut names = label_alternatives(left)?;
+            names.extend(label_alternatives(right)?);
+            Some(names)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repetitions_bound_unbounded_quantifiers() {
+        let span = 0..0;
+        assert_eq!(
+            repetitions(&GraphPatternQuantifier::Star { span: span.clone() }),
+            (0, 4)
+        );
+        assert_eq!(
+            repetitions(&GraphPatternQuantifier::Plus { span: span.clone() }),
+            (1, 5)
+        );
+        assert_eq!(
+            repetitions(&GraphPatternQuantifier::General {
+                min: Some(2),
+                max: None,
+                span: span.clone(),
+            }),
+            (2, 6)
+        );
+        assert_eq!(
+            repetitions(&GraphPatternQuantifier::Fixed { count: 3, span }),
+            (3, 3)
+        );
+    }
+}
@@ -0,0 +1,1877 @@
+//! Constant expression evaluation.
+//!
+//! [`ConstEvaluator`] folds expressions built only from literals and
+//! (optionally) bound parameters into [`Value`]s, following GQL semantics:
+//!
+//! - NULL propagates through arithmetic, comparisons and most functions;
+//!   UNKNOWN is the boolean NULL.
+//! - `AND`, `OR`, `XOR` and `NOT` use three-valued logic.
+//! - Integer arithmetic reports overflow instead of wrapping; division by
+//!   zero is an error.
+//! - `CAST` is checked against [`DefaultCastRuleSet`] before converting.
+//!   Conversions to integers truncate toward zero, conversions to a DECIMAL
+//!   scale round half away from zero, and values outside the target's range
+//!   are errors.
+//! - A minus sign directly before an integer literal is part of the literal,
+//!   so `-9223372036854775808` is in range.
+//!
+//! Anything that depends on graph data (variables, properties, aggregates,
+//! subqueries) or on the session (`CURRENT_DATE`) is reported as
+//! [`ConstEvalErrorKind::NotConstant`].
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::analysis::const_eval::{ConstEvaluator, Value};
+//! use gql_parser::parser::expression::parse_expression;
+//! use gql_parser::tokenize;
+//!
+//! let tokens = tokenize("COALESCE(NULL, 2 + 3 * 4)").tokens;
+//! let expr = parse_expression(&tokens).unwrap();
+//! assert_eq!(ConstEvaluator::new().evaluate(&expr), Ok(Value::Integer(14)));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use smol_str::SmolStr;
+
+use crate::ast::expression::{
+    BinaryOperator, BooleanValue, CaseExpression, CastExpression, ComparisonOperator,
+    FunctionCall, FunctionName, Literal, LogicalOperator, Predicate, TrimSpecification,
+    TruthValue, UnaryOperator,
+};
+use crate::ast::literal::{
+    DateValue, DatetimeValue, DecimalValue, DurationValue, LiteralErrorKind, LiteralValue,
+    TimeValue,
+};
+use crate::ast::visit::VisitResult;
+use crate::ast::visit_mut::{VisitMut, walk_expression};
+use crate::ast::types::ValueType;
+use crate::ast::{Expression, Span};
+use crate::diag::Diag;
+use crate::ir::type_table::{DurationQualifier, Type};
+use crate::semantic::type_metadata::{CastRuleSet, DefaultCastRuleSet};
+
+/// A constant value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// NULL, including the boolean UNKNOWN.
+    Null,
+    Boolean(bool),
+    /// Exact integer (`INT`, 64-bit).
+    Integer(i64),
+    /// Exact decimal.
+    Decimal(DecimalValue),
+    /// Approximate numeric.
+    Float(f64),
+    String(SmolStr),
+    Bytes(Vec<u8>),
+    Date(DateValue),
+    Time(TimeValue),
+    Datetime(DatetimeValue),
+    Duration(DurationValue),
+    List(Vec<Value>),
+    Record(Vec<(SmolStr, Value)>),
+}
+
+impl Value {
+    /// Returns true for NULL.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the static type of this value.
+    pub fn type_of(&self) -> Type {
+        match self {
+            Value::Null => Type::Null,
+            Value::Boolean(_) => Type::Boolean,
+            Value::Integer(_) => Type::Int,
+            Value::Decimal(d) => Type::Decimal {
+                precision: Some(d.precision()),
+                scale: Some(d.scale),
+            },
+            Value::Float(_) => Type::Float,
+            Value::String(_) => Type::String,
+            Value::Bytes(_) => Type::Bytes,
+            Value::Date(_) => Type::Date,
+            Value::Time(t) if t.offset.is_some() => Type::ZonedTime,
+            Value::Time(_) => Type::Time,
+            Value::Datetime(dt) if dt.time.offset.is_some() => Type::ZonedDatetime,
+            Value::Datetime(_) => Type::Timestamp,
+            Value::Duration(_) => Type::Duration,
+            Value::List(items) => {
+                let types: Vec<Type> = items.iter().map(Value::type_of).collect();
+                let element = if types.is_empty() {
+                    Type::Any
+                } else {
+                    Type::common_supertype(&types).unwrap_or(Type::Any)
+                };
+                Type::List(Box::new(element))
+            }
+            Value::Record(fields) => Type::Record(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.type_of()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Converts the value back into an expression, if it has a literal form.
+    ///
+    /// Negative numbers become a unary minus over a literal, as the parser
+    /// would produce. NaN and infinite floats have no literal form.
+    pub fn to_expression(&self, span: Span) -> Option<Expression> {
+        let literal = |literal| Some(Expression::Literal(literal, span.clone()));
+        let negated = |literal| {
+            Some(Expression::Unary(
+                UnaryOperator::Minus,
+                Box::new(Expression::Literal(literal, span.clone())),
+                span.clone(),
+            ))
+        };
+
+        match self {
+            Value::Null => literal(Literal::Null),
+            Value::Boolean(true) => literal(Literal::Boolean(BooleanValue::True)),
+            Value::Boolean(false) => literal(Literal::Boolean(BooleanValue::False)),
+            Value::Integer(i) if *i < 0 => {
+                negated(Literal::Integer(i.unsigned_abs().to_string().into()))
+            }
+            Value::Integer(i) => literal(Literal::Integer(i.to_string().into())),
+            Value::Decimal(d) if d.unscaled < 0 => negated(Literal::Float(
+                DecimalValue {
+                    unscaled: -d.unscaled,
+                    scale: d.scale,
+                }
+                .to_string()
+                .into(),
+            )),
+            Value::Decimal(d) => literal(Literal::Float(d.to_string().into())),
+            Value::Float(f) if !f.is_finite() => None,
+            // The exponent keeps the text an approximate numeric literal.
+            Value::Float(f) if *f < 0.0 => negated(Literal::Float(format!("{:e}", -f).into())),
+            Value::Float(f) => literal(Literal::Float(format!("{f:e}").into())),
+            Value::String(s) => literal(Literal::String(s.clone())),
+            Value::Bytes(bytes) => literal(Literal::ByteString(hex(bytes).into())),
+            Value::Date(d) => literal(Literal::Date(d.to_string().into())),
+            Value::Time(t) => literal(Literal::Time(t.to_string().into())),
+            Value::Datetime(dt) => literal(Literal::Datetime(dt.to_string().into())),
+            Value::Duration(d) => literal(Literal::Duration(d.to_string().into())),
+            Value::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| item.to_expression(span.clone()))
+                    .collect::<Option<Vec<_>>>()?;
+                literal(Literal::List(items))
+            }
+            Value::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        Some(crate::ast::expression::RecordField {
+                            name: name.clone(),
+                            value: value.to_expression(span.clone())?,
+                            span: span.clone(),
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                literal(Literal::Record(fields))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("NULL"),
+            Value::Boolean(true) => f.write_str("TRUE"),
+            Value::Boolean(false) => f.write_str("FALSE"),
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Decimal(d) => write!(f, "{d}"),
+            Value::Float(x) => write!(f, "{x:?}"),
+            Value::String(s) => f.write_str(s),
+            Value::Bytes(bytes) => f.write_str(&hex(bytes)),
+            Value::Date(d) => write!(f, "{d}"),
+            Value::Time(t) => write!(f, "{t}"),
+            Value::Datetime(dt) => write!(f, "{dt}"),
+            Value::Duration(d) => write!(f, "{d}"),
+            Value::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            Value::Record(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Why an expression could not be evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstEvalErrorKind {
+    /// The expression depends on data or session state.
+    NotConstant,
+    /// A parameter was referenced but no value was supplied for it.
+    UnboundParameter,
+    /// Integer or decimal arithmetic overflowed, or a float became infinite.
+    Overflow,
+    /// Division or modulo by zero.
+    DivisionByZero,
+    /// An operator or function was applied to values of the wrong type.
+    TypeMismatch,
+    /// A CAST is not allowed or the value does not fit the target type.
+    InvalidCast,
+    /// A function argument is outside the function's domain.
+    InvalidArgument,
+    /// A literal is malformed or out of range.
+    InvalidLiteral,
+}
+
+/// Error produced by constant evaluation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstEvalError {
+    pub kind: ConstEvalErrorKind,
+    pub message: String,
+    /// Span of the sub-expression that failed.
+    pub span: Span,
+}
+
+impl ConstEvalError {
+    fn new(kind: ConstEvalErrorKind, message: impl Into<String>, span: &Span) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            span: span.clone(),
+        }
+    }
+
+    fn not_constant(what: &str, span: &Span) -> Self {
+        Self::new(
+            ConstEvalErrorKind::NotConstant,
+            format!("{what} is not a constant expression"),
+            span,
+        )
+    }
+
+    fn mismatch(message: impl Into<String>, span: &Span) -> Self {
+        Self::new(ConstEvalErrorKind::TypeMismatch, message, span)
+    }
+
+    fn overflow(span: &Span) -> Self {
+        Self::new(ConstEvalErrorKind::Overflow, "numeric overflow", span)
+    }
+
+    fn invalid_argument(message: impl Into<String>, span: &Span) -> Self {
+        Self::new(ConstEvalErrorKind::InvalidArgument, message, span)
+    }
+
+    fn invalid_cast(message: impl Into<String>, span: &Span) -> Self {
+        Self::new(ConstEvalErrorKind::InvalidCast, message, span)
+    }
+
+    /// Returns true if the failure only means the value is not known at
+    /// compile time, as opposed to the expression being erroneous.
+    pub fn is_not_constant(&self) -> bool {
+        matches!(
+            self.kind,
+            ConstEvalErrorKind::NotConstant | ConstEvalErrorKind::UnboundParameter
+        )
+    }
+
+    /// Converts the error into an error diagnostic.
+    pub fn to_diag(&self) -> Diag {
+        Diag::error(self.message.clone()).with_primary_label(self.span.clone(), "evaluated here")
+    }
+}
+
+impl fmt::Display for ConstEvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ConstEvalError {}
+
+type EvalResult = Result<Value, ConstEvalError>;
+
+/// Evaluates constant expressions.
+#[derive(Debug, Clone, Default)]
+pub struct ConstEvaluator<'p> {
+    parameters: Option<&'p HashMap<SmolStr, Value>>,
+    cast_rules: DefaultCastRuleSet,
+}
+
+impl<'p> ConstEvaluator<'p> {
+    /// Creates an evaluator that treats every parameter as non-constant.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an evaluator that substitutes the given parameter values.
+    ///
+    /// Keys are parameter names without the leading `$`. Referencing a
+    /// parameter missing from the map is an
+    /// [`ConstEvalErrorKind::UnboundParameter`] error.
+    pub fn with_parameters(parameters: &'p HashMap<SmolStr, Value>) -> Self {
+        Self {
+            parameters: Some(parameters),
+            cast_rules: DefaultCastRuleSet,
+        }
+    }
+
+    /// Evaluates an expression to a constant value.
+    pub fn evaluate(&self, expr: &Expression) -> EvalResult {
+        match expr {
+            Expression::Literal(literal, span) => self.literal(literal, span),
+            Expression::Parenthesized(inner, _) => self.evaluate(inner),
+            Expression::TypeAnnotation(inner, _, _) => self.evaluate(inner),
+            Expression::ParameterReference(name, span) => match self.parameters {
+                Some(parameters) => parameters.get(name).cloned().ok_or_else(|| {
+                    ConstEvalError::new(
+                        ConstEvalErrorKind::UnboundParameter,
+                        format!("no value bound for parameter ${name}"),
+                        span,
+                    )
+                }),
+                None => Err(ConstEvalError::not_constant(&format!("parameter ${name}"), span)),
+            },
+            // `-9223372036854775808` is only in range once the sign is applied.
+            Expression::Unary(UnaryOperator::Minus, operand, span)
+                if matches!(**operand, Expression::Literal(Literal::Integer(_), _)) =>
+            {
+                negative_integer_literal(operand, span)
+            }
+            Expression::Unary(op, operand, span) => {
+                let value = self.evaluate(operand)?;
+                unary(*op, value, span)
+            }
+            Expression::Binary(op, left, right, span) => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                binary(*op, left, right, span)
+            }
+            Expression::Comparison(op, left, right, span) => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                compare(*op, &left, &right, span)
+            }
+            Expression::Logical(op, left, right, span) => self.logical(*op, left, right, span),
+            Expression::FunctionCall(call) => self.function(call),
+            Expression::Case(case) => self.case(case),
+            Expression::Cast(cast) => self.cast(cast),
+            Expression::ListConstructor(items, _) => Ok(Value::List(
+                items
+                    .iter()
+                    .map(|item| self.evaluate(item))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Expression::RecordConstructor(fields, _) => Ok(Value::Record(
+                fields
+                    .iter()
+                    .map(|field| Ok((field.name.clone(), self.evaluate(&field.value)?)))
+                    .collect::<Result<_, ConstEvalError>>()?,
+            )),
+            Expression::Predicate(predicate) => self.predicate(predicate),
+            Expression::VariableReference(name, span) => {
+                Err(ConstEvalError::not_constant(&format!("variable '{name}'"), span))
+            }
+            Expression::PropertyReference(_, _, span) => {
+                Err(ConstEvalError::not_constant("property reference", span))
+            }
+            other => Err(ConstEvalError::not_constant("expression", &other.span())),
+        }
+    }
+
+    /// Returns true if the expression evaluates without error.
+    pub fn is_constant(&self, expr: &Expression) -> bool {
+        self.evaluate(expr).is_ok()
+    }
+
+    fn literal(&self, literal: &Literal, span: &Span) -> EvalResult {
+        match literal {
+            Literal::List(items) => Ok(Value::List(
+                items
+                    .iter()
+                    .map(|item| self.evaluate(item))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Literal::Record(fields) => Ok(Value::Record(
+                fields
+                    .iter()
+                    .map(|field| Ok((field.name.clone(), self.evaluate(&field.value)?)))
+                    .collect::<Result<_, ConstEvalError>>()?,
+            )),
+            _ => {
+                let value = literal.value().map_err(|err| {
+                    let kind = if err.kind == LiteralErrorKind::Overflow {
+                        ConstEvalErrorKind::Overflow
+                    } else {
+                        ConstEvalErrorKind::InvalidLiteral
+                    };
+                    ConstEvalError::new(kind, err.message, span)
+                })?;
+                from_literal_value(value, span)
+            }
+        }
+    }
+
+    fn logical(
+        &self,
+        op: LogicalOperator,
+        left: &Expression,
+        right: &Expression,
+        span: &Span,
+    ) -> EvalResult {
+        let left = truth(&self.evaluate(left)?, span)?;
+        // FALSE AND x and TRUE OR x are decided without evaluating x.
+        match (op, left) {
+            (LogicalOperator::And, Some(false)) => return Ok(Value::Boolean(false)),
+            (LogicalOperator::Or, Some(true)) => return Ok(Value::Boolean(true)),
+            _ => {}
+        }
+        let right = truth(&self.evaluate(right)?, span)?;
+        let result = match op {
+            LogicalOperator::And => match (left, right) {
+                (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            LogicalOperator::Or => match (left, right) {
+                (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            LogicalOperator::Xor => match (left, right) {
+                (Some(a), Some(b)) => Some(a != b),
+                _ => None,
+            },
+        };
+        Ok(from_truth(result))
+    }
+
+    fn predicate(&self, predicate: &Predicate) -> EvalResult {
+        match predicate {
+            Predicate::IsNull(operand, negated, _) => {
+                let is_null = self.evaluate(operand)?.is_null();
+                Ok(Value::Boolean(is_null != *negated))
+            }
+            Predicate::IsTruthValue(operand, expected, negated, span) => {
+                let actual = truth(&self.evaluate(operand)?, span)?;
+                let expected = match expected {
+                    TruthValue::True => Some(true),
+                    TruthValue::False => Some(false),
+                    TruthValue::Unknown => None,
+                };
+                Ok(Value::Boolean((actual == expected) != *negated))
+            }
+            Predicate::IsTyped(operand, target, negated, _) => {
+                let value = self.evaluate(operand)?;
+                if value.is_null() {
+                    return Ok(Value::Null);
+                }
+                let target = Type::from_value_type(target);
+                let typed = value.type_of().can_coerce_to(&target);
+                Ok(Value::Boolean(typed != *negated))
+            }
+            other => Err(ConstEvalError::not_constant("predicate", &other.span())),
+        }
+    }
+
+    fn case(&self, case: &CaseExpression) -> EvalResult {
+        match case {
+            CaseExpression::Simple(simple) => {
+                let operand = self.evaluate(&simple.operand)?;
+                for clause in &simple.when_clauses {
+                    let candidate = self.evaluate(&clause.when_value)?;
+                    let equal = compare(ComparisonOperator::Eq, &operand, &candidate, &clause.span)?;
+                    if equal == Value::Boolean(true) {
+                        return self.evaluate(&clause.then_result);
+                    }
+                }
+                match &simple.else_clause {
+                    Some(otherwise) => self.evaluate(otherwise),
+                    None => Ok(Value::Null),
+                }
+            }
+            CaseExpression::Searched(searched) => {
+                for clause in &searched.when_clauses {
+                    let condition = self.evaluate(&clause.condition)?;
+                    if truth(&condition, &clause.span)? == Some(true) {
+                        return self.evaluate(&clause.then_result);
+                    }
+                }
+                match &searched.else_clause {
+                    Some(otherwise) => self.evaluate(otherwise),
+                    None => Ok(Value::Null),
+                }
+            }
+        }
+    }
+
+    fn cast(&self, cast: &CastExpression) -> EvalResult {
+        let value = self.evaluate(&cast.operand)?;
+        self.cast_value(value, &cast.target_type, &cast.span)
+    }
+
+    /// Casts an already evaluated value to `target`.
+    pub(crate) fn cast_value(&self, value: Value, target: &ValueType, span: &Span) -> EvalResult {
+        let target = Type::from_value_type(target);
+
+        if value.is_null() {
+            return match target {
+                Type::NotNull(_) => Err(ConstEvalError::invalid_cast(
+                    "cannot cast NULL to a NOT NULL type",
+                    span,
+                )),
+                _ => Ok(Value::Null),
+            };
+        }
+
+        let source = value.type_of();
+        if !self.cast_rules.can_cast(&source, &target) {
+            return Err(ConstEvalError::invalid_cast(
+                format!("cannot cast {} to {}", source.name(), target.name()),
+                span,
+            ));
+        }
+        convert(value, &target, span)
+    }
+
+    fn function(&self, call: &FunctionCall) -> EvalResult {
+        let span = &call.span;
+        let name = &call.name;
+
+        // Lazily evaluated conditionals first.
+        match name {
+            FunctionName::Coalesce => {
+                for argument in &call.arguments {
+                    let value = self.evaluate(argument)?;
+                    if !value.is_null() {
+                        return Ok(value);
+                    }
+                }
+                return Ok(Value::Null);
+            }
+            FunctionName::NullIf => {
+                let [left, right] = self.arguments::<2>(call)?;
+                let equal = compare(ComparisonOperator::Eq, &left, &right, span)?;
+                return Ok(if equal == Value::Boolean(true) {
+                    Value::Null
+                } else {
+                    left
+                });
+            }
+            FunctionName::CurrentDate
+            | FunctionName::CurrentTime
+            | FunctionName::CurrentTimestamp => {
+                return Err(ConstEvalError::not_constant("the current time", span));
+            }
+            FunctionName::ElementId
+            | FunctionName::PathLength
+            | FunctionName::Elements
+            | FunctionName::DurationBetween
+            | FunctionName::Custom(_) => {
+                return Err(ConstEvalError::not_constant("function call", span));
+            }
+            _ => {}
+        }
+
+        let arguments: Vec<Value> = call
+            .arguments
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect::<Result<_, _>>()?;
+
+        // Temporal constructors without arguments read the session clock.
+        if arguments.is_empty()
+            && matches!(
+                name,
+                FunctionName::Date
+                    | FunctionName::Time
+                    | FunctionName::Datetime
+                    | FunctionName::ZonedTime
+                    | FunctionName::ZonedDatetime
+                    | FunctionName::LocalTime
+                    | FunctionName::LocalDatetime
+            )
+        {
+            return Err(ConstEvalError::not_constant("the current time", span));
+        }
+
+        // Apart from the conditionals above, functions return NULL on NULL input.
+        if arguments.iter().any(Value::is_null) {
+            return Ok(Value::Null);
+        }
+
+        call_function(name, &arguments, span)
+    }
+
+    fn arguments<const N: usize>(&self, call: &FunctionCall) -> Result<[Value; N], ConstEvalError> {
+        let values: Vec<Value> = call
+            .arguments
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect::<Result<_, _>>()?;
+        values.try_into().map_err(|values: Vec<Value>| {
+            ConstEvalError::invalid_argument(
+                format!("expected {N} arguments, found {}", values.len()),
+                &call.span,
+            )
+        })
+    }
+}
+
+/// Evaluates a constant expression without parameters.
+pub fn evaluate(expr: &Expression) -> EvalResult {
+    ConstEvaluator::new().evaluate(expr)
+}
+
+/// Replaces constant sub-expressions with their literal values and
+/// simplifies boolean connectives with constant operands.
+///
+/// Simplifications preserve three-valued semantics: `x AND FALSE` becomes
+/// `FALSE` and `x AND TRUE` becomes `x`, but `x OR NULL` is left alone.
+/// Sub-expressions that fail for reasons other than being non-constant (e.g.
+/// division by zero) are left unfolded so the error surfaces at run time.
+pub fn fold_constants(expr: &mut Expression, evaluator: &ConstEvaluator<'_>) {
+    let mut folder = Folder { evaluator };
+    let _ = folder.visit_expression(expr);
+}
+
+struct Folder<'e, 'p> {
+    evaluator: &'e ConstEvaluator<'p>,
+}
+
+impl VisitMut for Folder<'_, '_> {
+    type Break = ();
+
+    fn visit_expression(&mut self, expr: &mut Expression) -> VisitResult<()> {
+        walk_expression(self, expr)?;
+
+        if !matches!(expr, Expression::Literal(..))
+            && let Ok(value) = self.evaluator.evaluate(expr)
+            && let Some(folded) = value.to_expression(expr.span())
+        {
+            *expr = folded;
+            return VisitResult::Continue(());
+        }
+
+        if let Expression::Logical(op @ (LogicalOperator::And | LogicalOperator::Or), left, right, _) =
+            expr
+        {
+            let absorbing = *op == LogicalOperator::Or;
+            let left_truth = constant_truth(left);
+            let right_truth = constant_truth(right);
+            // TRUE OR x = TRUE, FALSE AND x = FALSE.
+            if left_truth == Some(absorbing) {
+                *expr = (**left).clone();
+            } else if right_truth == Some(absorbing) {
+                *expr = (**right).clone();
+            // FALSE OR x = x, TRUE AND x = x.
+            } else if left_truth == Some(!absorbing) {
+                *expr = (**right).clone();
+            } else if right_truth == Some(!absorbing) {
+                *expr = (**left).clone();
+            }
+        }
+
+        VisitResult::Continue(())
+    }
+}
+
+fn constant_truth(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::Literal(Literal::Boolean(BooleanValue::True), _) => Some(true),
+        Expression::Literal(Literal::Boolean(BooleanValue::False), _) => Some(false),
+        _ => None,
+    }
+}
+
+fn from_literal_value(value: LiteralValue, span: &Span) -> EvalResult {
+    Ok(match value {
+        LiteralValue::Boolean(BooleanValue::True) => Value::Boolean(true),
+        LiteralValue::Boolean(BooleanValue::False) => Value::Boolean(false),
+        LiteralValue::Boolean(BooleanValue::Unknown) | LiteralValue::Null => Value::Null,
+        LiteralValue::Integer(i) => Value::Integer(i.as_i64().ok_or_else(|| {
+            ConstEvalError::new(
+                ConstEvalErrorKind::Overflow,
+                format!("integer literal {i} is out of range for INT"),
+                span,
+            )
+        })?),
+        LiteralValue::Decimal(d) => Value::Decimal(d),
+        LiteralValue::Float(f) => Value::Float(f),
+        LiteralValue::String(s) => Value::String(s),
+        LiteralValue::Bytes(b) => Value::Bytes(b),
+        LiteralValue::Date(d) => Value::Date(d),
+        LiteralValue::Time(t) => Value::Time(t),
+        LiteralValue::Datetime(dt) => Value::Datetime(dt),
+        LiteralValue::Duration(d) => Value::Duration(d),
+    })
+}
+
+/// Evaluates a minus sign applied directly to an integer literal.
+fn negative_integer_literal(operand: &Expression, span: &Span) -> EvalResult {
+    let Expression::Literal(literal, literal_span) = operand else {
+        unreachable!("caller matched an integer literal");
+    };
+    let value = literal.value().map_err(|err| {
+        ConstEvalError::new(ConstEvalErrorKind::Overflow, err.message, literal_span)
+    })?;
+    let LiteralValue::Integer(integer) = value else {
+        unreachable!("integer literals decode to integers");
+    };
+    i64::try_from(-integer.as_i128()).map(Value::Integer).map_err(|_| {
+        ConstEvalError::new(
+            ConstEvalErrorKind::Overflow,
+            format!("integer literal -{integer} is out of range for INT"),
+            span,
+        )
+    })
+}
+
+pub(crate) fn truth(value: &Value, span: &Span) -> Result<Option<bool>, ConstEvalError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Boolean(b) => Ok(Some(*b)),
+        other => Err(ConstEvalError::mismatch(
+            format!("expected BOOLEAN, found {}", other.type_of().name()),
+            span,
+        )),
+    }
+}
+
+pub(crate) fn from_truth(truth: Option<bool>) -> Value {
+    truth.map_or(Value::Null, Value::Boolean)
+}
+
+// ============================================================================
+// Arithmetic
+// ============================================================================
+
+/// Numeric operand after promotion to a common representation.
+#[derive(Debug, Clone, Copy)]
+enum Numeric {
+    Int(i64, i64),
+    Decimal(DecimalValue, DecimalValue),
+    Float(f64, f64),
+}
+
+fn as_decimal(value: &Value) -> Option<DecimalValue> {
+    match value {
+        Value::Integer(i) => Some(DecimalValue {
+            unscaled: *i as i128,
+            scale: 0,
+        }),
+        Value::Decimal(d) => Some(*d),
+        _ => None,
+    }
+}
+
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Decimal(d) => Some(d.to_f64()),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+fn promote(left: &Value, right: &Value) -> Option<Numeric> {
+    match (left, right) {
+        (Value::Integer(a), Value::Integer(b)) => Some(Numeric::Int(*a, *b)),
+        (Value::Float(_), _) | (_, Value::Float(_)) => {
+            Some(Numeric::Float(as_float(left)?, as_float(right)?))
+        }
+        _ => Some(Numeric::Decimal(as_decimal(left)?, as_decimal(right)?)),
+    }
+}
+
+fn rescale(value: DecimalValue, scale: u32) -> Option<i128> {
+    let factor = 10i128.checked_pow(scale.checked_sub(value.scale)?)?;
+    value.unscaled.checked_mul(factor)
+}
+
+fn align(a: DecimalValue, b: DecimalValue) -> Option<(i128, i128, u32)> {
+    let scale = a.scale.max(b.scale);
+    Some((rescale(a, scale)?, rescale(b, scale)?, scale))
+}
+
+/// Scale used for the result of decimal division.
+const DECIMAL_DIVISION_SCALE: u32 = 6;
+
+fn checked_float(value: f64, span: &Span) -> EvalResult {
+    if value.is_finite() {
+        Ok(Value::Float(value))
+    } else if value.is_nan() {
+        Err(ConstEvalError::invalid_argument("result is not a number", span))
+    } else {
+        Err(ConstEvalError::overflow(span))
+    }
+}
+
+pub(crate) fn unary(op: UnaryOperator, value: Value, span: &Span) -> EvalResult {
+    match (op, value) {
+        (UnaryOperator::Not, value) => Ok(from_truth(truth(&value, span)?.map(|b| !b))),
+        (_, Value::Null) => Ok(Value::Null),
+        (UnaryOperator::Plus, value @ (Value::Integer(_) | Value::Decimal(_) | Value::Float(_))) => {
+            Ok(value)
+        }
+        (UnaryOperator::Minus, Value::Integer(i)) => i
+            .checked_neg()
+            .map(Value::Integer)
+            .ok_or_else(|| ConstEvalError::overflow(span)),
+        (UnaryOperator::Minus, Value::Decimal(d)) => Ok(Value::Decimal(DecimalValue {
+            unscaled: -d.unscaled,
+            scale: d.scale,
+        })),
+        (UnaryOperator::Minus, Value::Float(f)) => Ok(Value::Float(-f)),
+        (UnaryOperator::Minus, Value::Duration(d)) => Ok(Value::Duration(DurationValue {
+            negative: !d.negative,
+            ..d
+        })),
+        (_, other) => Err(ConstEvalError::mismatch(
+            format!("unary operator cannot be applied to {}", other.type_of().name()),
+            span,
+        )),
+    }
+}
+
+pub(crate) fn binary(op: BinaryOperator, left: Value, right: Value, span: &Span) -> EvalResult {
+    if left.is_null() || right.is_null() {
+        return Ok(Value::Null);
+    }
+
+    if op == BinaryOperator::Concatenate {
+        return match (left, right) {
+            (Value::String(a), Value::String(b)) => Ok(Value::String(format!("{a}{b}").into())),
+            (Value::Bytes(mut a), Value::Bytes(b)) => {
+                a.extend(b);
+                Ok(Value::Bytes(a))
+            }
+            (Value::List(mut a), Value::List(b)) => {
+                a.extend(b);
+                Ok(Value::List(a))
+            }
+            (a, b) => Err(ConstEvalError::mismatch(
+                format!(
+                    "cannot concatenate {} and {}",
+                    a.type_of().name(),
+                    b.type_of().name()
+                ),
+                span,
+            )),
+        };
+    }
+
+    let Some(operands) = promote(&left, &right) else {
+        return Err(ConstEvalError::mismatch(
+            format!(
+                "arithmetic requires numeric operands, found {} and {}",
+                left.type_of().name(),
+                right.type_of().name()
+            ),
+            span,
+        ));
+    };
+
+    let overflow = || ConstEvalError::overflow(span);
+    let division_by_zero =
+        || ConstEvalError::new(ConstEvalErrorKind::DivisionByZero, "division by zero", span);
+
+    match operands {
+        Numeric::Int(a, b) => match op {
+            BinaryOperator::Add => a.checked_add(b).map(Value::Integer).ok_or_else(overflow),
+            BinaryOperator::Subtract => a.checked_sub(b).map(Value::Integer).ok_or_else(overflow),
+            BinaryOperator::Multiply => a.checked_mul(b).map(Value::Integer).ok_or_else(overflow),
+            // Integer division yields an approximate result, matching type inference.
+            BinaryOperator::Divide if b == 0 => Err(division_by_zero()),
+            BinaryOperator::Divide => checked_float(a as f64 / b as f64, span),
+            BinaryOperator::Modulo if b == 0 => Err(division_by_zero()),
+            BinaryOperator::Modulo => a.checked_rem(b).map(Value::Integer).ok_or_else(overflow),
+            BinaryOperator::Concatenate => unreachable!("handled above"),
+        },
+        Numeric::Decimal(a, b) => {
+            let result = match op {
+                BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Modulo => {
+                    let (x, y, scale) = align(a, b).ok_or_else(overflow)?;
+                    let unscaled = match op {
+                        BinaryOperator::Add => x.checked_add(y),
+                        BinaryOperator::Subtract => x.checked_sub(y),
+                        _ if y == 0 => return Err(division_by_zero()),
+                        _ => x.checked_rem(y),
+                    };
+                    DecimalValue {
+                        unscaled: unscaled.ok_or_else(overflow)?,
+                        scale,
+                    }
+                }
+                BinaryOperator::Multiply => DecimalValue {
+                    unscaled: a.unscaled.checked_mul(b.unscaled).ok_or_else(overflow)?,
+                    scale: a.scale + b.scale,
+                },
+                BinaryOperator::Divide => {
+                    if b.unscaled == 0 {
+                        return Err(division_by_zero());
+                    }
+                    let scale = a.scale.max(b.scale).max(DECIMAL_DIVISION_SCALE);
+                    // a / b = (a.unscaled * 10^(scale + b.scale - a.scale)) / b.unscaled
+                    let shift = scale + b.scale - a.scale;
+                    let numerator = 10i128
+                        .checked_pow(shift)
+                        .and_then(|factor| a.unscaled.checked_mul(factor))
+                        .ok_or_else(overflow)?;
+                    DecimalValue {
+                        unscaled: numerator / b.unscaled,
+                        scale,
+                    }
+                }
+                BinaryOperator::Concatenate => unreachable!("handled above"),
+            };
+            Ok(Value::Decimal(result))
+        }
+        Numeric::Float(a, b) => {
+            let result = match op {
+                BinaryOperator::Add => a + b,
+                BinaryOperator::Subtract => a - b,
+                BinaryOperator::Multiply => a * b,
+                BinaryOperator::Divide if b == 0.0 => return Err(division_by_zero()),
+                BinaryOperator::Divide => a / b,
+                BinaryOperator::Modulo if b == 0.0 => return Err(division_by_zero()),
+                BinaryOperator::Modulo => a % b,
+                BinaryOperator::Concatenate => unreachable!("handled above"),
+            };
+            checked_float(result, span)
+        }
+    }
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+pub(crate) fn compare(op: ComparisonOperator, left: &Value, right: &Value, span: &Span) -> EvalResult {
+    let ordering = match order(left, right, span)? {
+        Some(ordering) => ordering,
+        None => return Ok(Value::Null),
+    };
+    let result = match ordering {
+        // Only equality is defined for this pair of values.
+        Comparable::Unordered(equal) => match op {
+            ComparisonOperator::Eq => equal,
+            ComparisonOperator::NotEq => !equal,
+            _ => {
+                return Err(ConstEvalError::mismatch(
+                    format!("{} values cannot be ordered", left.type_of().name()),
+                    span,
+                ));
+            }
+        },
+        Comparable::Ordered(ordering) => match op {
+            ComparisonOperator::Eq => ordering == Ordering::Equal,
+            ComparisonOperator::NotEq => ordering != Ordering::Equal,
+            ComparisonOperator::Lt => ordering == Ordering::Less,
+            ComparisonOperator::Gt => ordering == Ordering::Greater,
+            ComparisonOperator::LtEq => ordering != Ordering::Greater,
+            ComparisonOperator::GtEq => ordering != Ordering::Less,
+        },
+    };
+    Ok(Value::Boolean(result))
+}
+
+enum Comparable {
+    Ordered(Ordering),
+    /// Equality only; `true` if equal.
+    Unordered(bool),
+}
+
+/// Returns the ordering of two values, or `None` if they are NULL, of
+/// different types, or only comparable for equality.
+pub(crate) fn ordering(left: &Value, right: &Value) -> Option<Ordering> {
+    match order(left, right, &(0..0)) {
+        Ok(Some(Comparable::Ordered(ordering))) => Some(ordering),
+        _ => None,
+    }
+}
+
+/// Compares two values; `Ok(None)` means the comparison is UNKNOWN.
+fn order(left: &Value, right: &Value, span: &Span) -> Result<Option<Comparable>, ConstEvalError> {
+    use Comparable::*;
+
+    if left.is_null() || right.is_null() {
+        return Ok(None);
+    }
+
+    let ordered = |ordering: Option<Ordering>| Ok(ordering.map(Ordered));
+
+    match (left, right) {
+        (Value::Boolean(a), Value::Boolean(b)) => ordered(Some(a.cmp(b))),
+        (Value::String(a), Value::String(b)) => ordered(Some(a.cmp(b))),
+        (Value::Bytes(a), Value::Bytes(b)) => ordered(Some(a.cmp(b))),
+        (Value::Date(a), Value::Date(b)) => ordered(Some(a.cmp(b))),
+        (Value::Time(a), Value::Time(b)) if a.offset.is_some() == b.offset.is_some() => {
+            ordered(Some(time_key(a).cmp(&time_key(b))))
+        }
+        (Value::Datetime(a), Value::Datetime(b))
+            if a.time.offset.is_some() == b.time.offset.is_some() =>
+        {
+            ordered(Some(datetime_key(a).cmp(&datetime_key(b))))
+        }
+        (Value::Duration(a), Value::Duration(b)) => Ok(Some(Unordered(a == b))),
+        (Value::List(a), Value::List(b)) => {
+            if a.len() != b.len() {
+                return Ok(Some(Unordered(false)));
+            }
+            let mut unknown = false;
+            for (x, y) in a.iter().zip(b) {
+                match order(x, y, span)? {
+                    None => unknown = true,
+                    Some(Ordered(Ordering::Equal)) | Some(Unordered(true)) => {}
+                    Some(_) => return Ok(Some(Unordered(false))),
+                }
+            }
+            Ok((!unknown).then_some(Unordered(true)))
+        }
+        (Value::Record(a), Value::Record(b)) => {
+            if a.len() != b.len() || a.iter().zip(b).any(|((x, _), (y, _))| x != y) {
+                return Ok(Some(Unordered(false)));
+            }
+            let left = Value::List(a.iter().map(|(_, v)| v.clone()).collect());
+            let right = Value::List(b.iter().map(|(_, v)| v.clone()).collect());
+            order(&left, &right, span)
+        }
+        _ => match promote(left, right) {
+            Some(Numeric::Int(a, b)) => ordered(Some(a.cmp(&b))),
+            Some(Numeric::Decimal(a, b)) => match align(a, b) {
+                Some((x, y, _)) => ordered(Some(x.cmp(&y))),
+                None => ordered(a.to_f64().partial_cmp(&b.to_f64())),
+            },
+            Some(Numeric::Float(a, b)) => ordered(a.partial_cmp(&b)),
+            None => Err(ConstEvalError::mismatch(
+                format!(
+                    "cannot compare {} with {}",
+                    left.type_of().name(),
+                    right.type_of().name()
+                ),
+                span,
+            )),
+        },
+    }
+}
+
+/// Nanoseconds since midnight UTC (or local midnight for local times).
+fn time_key(time: &TimeValue) -> i64 {
+    let local = ((time.hour as i64 * 60 + time.minute as i64) * 60 + time.second as i64)
+        * 1_000_000_000
+        + time.nanosecond as i64;
+    local - time.offset.map_or(0, |o| o.minutes as i64 * 60 * 1_000_000_000)
+}
+
+fn datetime_key(datetime: &DatetimeValue) -> (i64, i64) {
+    // Borrow a day from the date when the offset crosses midnight.
+    const DAY: i64 = 86_400 * 1_000_000_000;
+    let days = days_from_civil(&datetime.date);
+    let nanos = time_key(&datetime.time);
+    (days + nanos.div_euclid(DAY), nanos.rem_euclid(DAY))
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+fn days_from_civil(date: &DateValue) -> i64 {
+    let year = date.year as i64 - if date.month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = date.month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + date.day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+pub(crate) fn call_function(name: &FunctionName, args: &[Value], span: &Span) -> EvalResult {
+    let arity = |expected: &[usize]| {
+        if expected.contains(&args.len()) {
+            Ok(())
+        } else {
+            Err(ConstEvalError::invalid_argument(
+                format!("wrong number of arguments ({}) for function", args.len()),
+                span,
+            ))
+        }
+    };
+
+    match name {
+        // Numeric
+        FunctionName::Abs => {
+            arity(&[1])?;
+            match &args[0] {
+                Value::Integer(i) => i
+                    .checked_abs()
+                    .map(Value::Integer)
+                    .ok_or_else(|| ConstEvalError::overflow(span)),
+                Value::Decimal(d) => Ok(Value::Decimal(DecimalValue {
+                    unscaled: d.unscaled.abs(),
+                    scale: d.scale,
+                })),
+                Value::Float(f) => Ok(Value::Float(f.abs())),
+                other => Err(numeric_expected(other, span)),
+            }
+        }
+        FunctionName::Mod => {
+            arity(&[2])?;
+            binary(BinaryOperator::Modulo, args[0].clone(), args[1].clone(), span)
+        }
+        FunctionName::Floor | FunctionName::Ceil => {
+            arity(&[1])?;
+            let floor = matches!(name, FunctionName::Floor);
+            match &args[0] {
+                Value::Integer(i) => Ok(Value::Integer(*i)),
+                Value::Decimal(d) => {
+                    let divisor = 10i128.pow(d.scale);
+                    let mut whole = d.unscaled.div_euclid(divisor);
+                    if !floor && d.unscaled.rem_euclid(divisor) != 0 {
+                        whole += 1;
+                    }
+                    Ok(Value::Decimal(DecimalValue {
+                        unscaled: whole,
+                        scale: 0,
+                    }))
+                }
+                Value::Float(f) => Ok(Value::Float(if floor { f.floor() } else { f.ceil() })),
+                other => Err(numeric_expected(other, span)),
+            }
+        }
+        FunctionName::Power => {
+            arity(&[2])?;
+            let (base, exponent) = (float_arg(&args[0], span)?, float_arg(&args[1], span)?);
+            checked_float(base.powf(exponent), span)
+        }
+        FunctionName::Log => {
+            arity(&[2])?;
+            let (base, x) = (float_arg(&args[0], span)?, float_arg(&args[1], span)?);
+            if base <= 0.0 || base == 1.0 || x <= 0.0 {
+                return Err(ConstEvalError::invalid_argument(
+                    "LOG requires a positive base other than 1 and a positive argument",
+                    span,
+                ));
+            }
+            checked_float(x.log(base), span)
+        }
+        FunctionName::Atan2 => {
+            arity(&[2])?;
+            checked_float(
+                float_arg(&args[0], span)?.atan2(float_arg(&args[1], span)?),
+                span,
+            )
+        }
+        FunctionName::Sqrt
+        | FunctionName::Exp
+        | FunctionName::Ln
+        | FunctionName::Log10
+        | FunctionName::Sin
+        | FunctionName::Cos
+        | FunctionName::Tan
+        | FunctionName::Cot
+        | FunctionName::Sinh
+        | FunctionName::Cosh
+        | FunctionName::Tanh
+        | FunctionName::Asin
+        | FunctionName::Acos
+        | FunctionName::Atan
+        | FunctionName::Degrees
+        | FunctionName::Radians => {
+            arity(&[1])?;
+            let x = float_arg(&args[0], span)?;
+            let domain_error = || {
+                ConstEvalError::invalid_argument("argument is outside the function's domain", span)
+            };
+            let result = match name {
+                FunctionName::Sqrt if x < 0.0 => return Err(domain_error()),
+                FunctionName::Sqrt => x.sqrt(),
+                FunctionName::Exp => x.exp(),
+                FunctionName::Ln | FunctionName::Log10 if x <= 0.0 => return Err(domain_error()),
+                FunctionName::Ln => x.ln(),
+                FunctionName::Log10 => x.log10(),
+                FunctionName::Sin => x.sin(),
+                FunctionName::Cos => x.cos(),
+                FunctionName::Tan => x.tan(),
+                FunctionName::Cot => 1.0 / x.tan(),
+                FunctionName::Sinh => x.sinh(),
+                FunctionName::Cosh => x.cosh(),
+                FunctionName::Tanh => x.tanh(),
+                FunctionName::Asin | FunctionName::Acos if !(-1.0..=1.0).contains(&x) => {
+                    return Err(domain_error());
+                }
+                FunctionName::Asin => x.asin(),
+                FunctionName::Acos => x.acos(),
+                FunctionName::Atan => x.atan(),
+                FunctionName::Degrees => x.to_degrees(),
+                _ => x.to_radians(),
+            };
+            checked_float(result, span)
+        }
+
+        // String
+        FunctionName::Upper | FunctionName::Lower => {
+            arity(&[1])?;
+            let s = string_arg(&args[0], span)?;
+            Ok(Value::String(
+                if matches!(name, FunctionName::Upper) {
+                    s.to_uppercase()
+                } else {
+                    s.to_lowercase()
+                }
+                .into(),
+            ))
+        }
+        FunctionName::Trim(_) | FunctionName::BTrim | FunctionName::LTrim | FunctionName::RTrim => {
+            arity(&[1, 2])?;
+            let s = string_arg(&args[0], span)?;
+            let chars: Vec<char> = match args.get(1) {
+                Some(set) => string_arg(set, span)?.chars().collect(),
+                None => vec![' '],
+            };
+            let spec = match name {
+                FunctionName::Trim(spec) => *spec,
+                FunctionName::LTrim => TrimSpecification::Leading,
+                FunctionName::RTrim => TrimSpecification::Trailing,
+                _ => TrimSpecification::Both,
+            };
+            let trimmed = match spec {
+                TrimSpecification::Leading => s.trim_start_matches(chars.as_slice()),
+                TrimSpecification::Trailing => s.trim_end_matches(chars.as_slice()),
+                TrimSpecification::Both => s.trim_matches(chars.as_slice()),
+            };
+            Ok(Value::String(trimmed.into()))
+        }
+        FunctionName::Left | FunctionName::Right => {
+            arity(&[2])?;
+            let s = string_arg(&args[0], span)?;
+            let n = length_arg(&args[1], span)?;
+            let count = s.chars().count();
+            let taken: String = if matches!(name, FunctionName::Left) {
+                s.chars().take(n).collect()
+            } else {
+                s.chars().skip(count.saturating_sub(n)).collect()
+            };
+            Ok(Value::String(taken.into()))
+        }
+        FunctionName::Substring => {
+            arity(&[2, 3])?;
+            let s = string_arg(&args[0], span)?;
+            let Value::Integer(start) = args[1] else {
+                return Err(integer_expected(&args[1], span));
+            };
+            // SQL semantics: 1-based start, positions before 1 consume length.
+            let length = match args.get(2) {
+                Some(length) => Some(length_arg(length, span)? as i64),
+                None => None,
+            };
+            let first = start.max(1);
+            let last = match length {
+                Some(length) => start.saturating_add(length),
+                None => i64::MAX,
+            };
+            let taken: String = s
+                .chars()
+                .enumerate()
+                .filter(|(i, _)| {
+                    let position = *i as i64 + 1;
+                    position >= first && position < last
+                })
+                .map(|(_, c)| c)
+                .collect();
+            Ok(Value::String(taken.into()))
+        }
+        FunctionName::Normalize => {
+            arity(&[1, 2])?;
+            let s = string_arg(&args[0], span)?;
+            // ASCII text is invariant under every Unicode normal form.
+            if s.is_ascii() {
+                Ok(Value::String(s.into()))
+            } else {
+                Err(ConstEvalError::not_constant(
+                    "NORMALIZE of non-ASCII text",
+                    span,
+                ))
+            }
+        }
+        FunctionName::CharLength => {
+            arity(&[1])?;
+            Ok(Value::Integer(string_arg(&args[0], span)?.chars().count() as i64))
+        }
+        FunctionName::ByteLength => {
+            arity(&[1])?;
+            match &args[0] {
+                Value::Bytes(bytes) => Ok(Value::Integer(bytes.len() as i64)),
+                Value::String(s) => Ok(Value::Integer(s.len() as i64)),
+                other => Err(ConstEvalError::mismatch(
+                    format!("expected STRING or BYTES, found {}", other.type_of().name()),
+                    span,
+                )),
+            }
+        }
+
+        // Collections
+        FunctionName::Cardinality | FunctionName::Size => {
+            arity(&[1])?;
+            match &args[0] {
+                Value::List(items) => Ok(Value::Integer(items.len() as i64)),
+                Value::Record(fields) => Ok(Value::Integer(fields.len() as i64)),
+                other => Err(ConstEvalError::mismatch(
+                    format!("expected a list, found {}", other.type_of().name()),
+                    span,
+                )),
+            }
+        }
+        FunctionName::TrimList => {
+            arity(&[2])?;
+            let Value::List(items) = &args[0] else {
+                return Err(ConstEvalError::mismatch("TRIM_LIST expects a list", span));
+            };
+            let n = length_arg(&args[1], span)?;
+            if n > items.len() {
+                return Err(ConstEvalError::invalid_argument(
+                    "TRIM_LIST count exceeds list length",
+                    span,
+                ));
+            }
+            Ok(Value::List(items[..items.len() - n].to_vec()))
+        }
+
+        // Temporal constructors from strings
+        FunctionName::Date
+        | FunctionName::Time
+        | FunctionName::Datetime
+        | FunctionName::ZonedTime
+        | FunctionName::ZonedDatetime
+        | FunctionName::LocalTime
+        | FunctionName::LocalDatetime
+        | FunctionName::Duration => {
+            arity(&[1])?;
+            let text: SmolStr = string_arg(&args[0], span)?.into();
+            let target = match name {
+                FunctionName::Date => Type::Date,
+                FunctionName::Time => Type::Any,
+                FunctionName::LocalTime => Type::Time,
+                FunctionName::ZonedTime => Type::ZonedTime,
+                FunctionName::LocalDatetime => Type::Timestamp,
+                FunctionName::ZonedDatetime => Type::ZonedDatetime,
+                FunctionName::Datetime => Type::Any,
+                _ => Type::Duration,
+            };
+            let literal = match name {
+                FunctionName::Date => Literal::Date(text),
+                FunctionName::Time | FunctionName::LocalTime | FunctionName::ZonedTime => {
+                    Literal::Time(text)
+                }
+                FunctionName::Duration => Literal::Duration(text),
+                _ => Literal::Datetime(text),
+            };
+            let value = literal.value().map_err(|err| {
+                ConstEvalError::invalid_argument(err.message, span)
+            })?;
+            let value = from_literal_value(value, span)?;
+            if target == Type::Any {
+                Ok(value)
+            } else {
+                convert(value, &target, span)
+            }
+        }
+
+        _ => Err(ConstEvalError::not_constant("function call", span)),
+    }
+}
+
+fn numeric_expected(value: &Value, span: &Span) -> ConstEvalError {
+    ConstEvalError::mismatch(
+        format!("expected a numeric value, found {}", value.type_of().name()),
+        span,
+    )
+}
+
+fn integer_expected(value: &Value, span: &Span) -> ConstEvalError {
+    ConstEvalError::mismatch(
+        format!("expected an integer, found {}", value.type_of().name()),
+        span,
+    )
+}
+
+fn float_arg(value: &Value, span: &Span) -> Result<f64, ConstEvalError> {
+    as_float(value).ok_or_else(|| numeric_expected(value, span))
+}
+
+fn string_arg<'v>(value: &'v Value, span: &Span) -> Result<&'v str, ConstEvalError> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => Err(ConstEvalError::mismatch(
+            format!("expected STRING, found {}", other.type_of().name()),
+            span,
+        )),
+    }
+}
+
+fn length_arg(value: &Value, span: &Span) -> Result<usize, ConstEvalError> {
+    match value {
+        Value::Integer(n) if *n >= 0 => Ok(*n as usize),
+        Value::Integer(_) => Err(ConstEvalError::invalid_argument(
+            "length must not be negative",
+            span,
+        )),
+        other => Err(integer_expected(other, span)),
+    }
+}
+
+// ============================================================================
+// CAST
+// ============================================================================
+
+fn convert(value: Value, target: &Type, span: &Span) -> EvalResult {
+    let fail = |value: &Value| {
+        ConstEvalError::invalid_cast(
+            format!("cannot cast {value} to {}", target.name()),
+            span,
+        )
+    };
+
+    match target {
+        Type::NotNull(inner) => convert(value, inner, span),
+        Type::Any => Ok(value),
+        Type::String => Ok(Value::String(value.to_string().into())),
+        Type::Boolean => match &value {
+            Value::Boolean(_) => Ok(value),
+            Value::Integer(i) => Ok(Value::Boolean(*i != 0)),
+            Value::String(s) => match s.trim().to_ascii_uppercase().as_str() {
+                "TRUE" => Ok(Value::Boolean(true)),
+                "FALSE" => Ok(Value::Boolean(false)),
+                "UNKNOWN" => Ok(Value::Null),
+                _ => Err(fail(&value)),
+            },
+            _ => Err(fail(&value)),
+        },
+        Type::Int | Type::SignedInt(_) | Type::UnsignedInt(_) => {
+            // Fractional parts are truncated toward zero.
+            let integer = match &value {
+                Value::Integer(i) => *i as i128,
+                Value::Boolean(b) => i128::from(*b),
+                Value::Decimal(d) => {
+                    d.unscaled / 10i128.checked_pow(d.scale).ok_or_else(|| fail(&value))?
+                }
+                Value::Float(f) if f.is_finite() => {
+                    let truncated = f.trunc();
+                    if truncated.abs() >= 2f64.powi(127) {
+                        return Err(ConstEvalError::invalid_cast(
+                            format!("{value} is out of range for {}", target.name()),
+                            span,
+                        ));
+                    }
+                    truncated as i128
+                }
+                Value::String(s) => match Literal::Integer(s.trim().into()).value() {
+                    Ok(LiteralValue::Integer(i)) => i.as_i128(),
+                    _ => return Err(fail(&value)),
+                },
+                _ => return Err(fail(&value)),
+            };
+            let (min, max) = match target {
+                Type::SignedInt(width) => {
+                    let bits = width.bits().min(127);
+                    (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+                }
+                Type::UnsignedInt(width) => (0, (1i128 << width.bits().min(126)) - 1),
+                _ => (i64::MIN as i128, i64::MAX as i128),
+            };
+            if integer < min || integer > max {
+                return Err(ConstEvalError::invalid_cast(
+                    format!("{integer} is out of range for {}", target.name()),
+                    span,
+                ));
+            }
+            i64::try_from(integer)
+                .map(Value::Integer)
+                .map_err(|_| ConstEvalError::overflow(span))
+        }
+        Type::Decimal { precision, scale } => {
+            let decimal = match &value {
+                Value::Integer(_) | Value::Decimal(_) => as_decimal(&value),
+                Value::Float(f) if f.is_finite() => float_to_decimal(*f),
+                Value::String(s) => match Literal::Float(s.trim().into()).value() {
+                    Ok(LiteralValue::Decimal(d)) => Some(d),
+                    _ => match Literal::Integer(s.trim().into()).value() {
+                        Ok(LiteralValue::Integer(i)) => Some(DecimalValue {
+                            unscaled: i.as_i128(),
+                            scale: 0,
+                        }),
+                        _ => None,
+                    },
+                },
+                _ => None,
+            }
+            .ok_or_else(|| fail(&value))?;
+
+            let decimal = match scale {
+                Some(scale) => round_to_scale(decimal, *scale).ok_or_else(|| fail(&value))?,
+                None => decimal,
+            };
+            if let Some(precision) = precision
+                && decimal.precision() > *precision
+            {
+                return Err(ConstEvalError::invalid_cast(
+                    format!("{decimal} does not fit in {}", target.name()),
+                    span,
+                ));
+            }
+            Ok(Value::Decimal(decimal))
+        }
+        Type::Float | Type::SizedFloat(_) => {
+            let float = match &value {
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                other => as_float(other),
+            }
+            .ok_or_else(|| fail(&value))?;
+            let float = match target {
+                Type::SizedFloat(width) if width.bits() <= 32 => float as f32 as f64,
+                _ => float,
+            };
+            checked_float(float, span)
+        }
+        Type::Date
+        | Type::Time
+        | Type::ZonedTime
+        | Type::Timestamp
+        | Type::ZonedDatetime
+        | Type::Duration
+        | Type::QualifiedDuration(_) => {
+            let parsed = match (&value, target) {
+                (Value::String(s), Type::Date) => Literal::Date(s.clone()).value().ok(),
+                (Value::String(s), Type::Time | Type::ZonedTime) => {
+                    Literal::Time(s.clone()).value().ok()
+                }
+                (Value::String(s), Type::Timestamp | Type::ZonedDatetime) => {
+                    Literal::Datetime(s.clone()).value().ok()
+                }
+                (Value::String(s), _) => Literal::Duration(s.clone()).value().ok(),
+                _ => None,
+            };
+            let value = match parsed {
+                Some(parsed) => from_literal_value(parsed, span)?,
+                None => value,
+            };
+            match (value, target) {
+                (v @ Value::Date(_), Type::Date) => Ok(v),
+                (Value::Datetime(dt), Type::Date) => Ok(Value::Date(dt.date)),
+                (Value::Time(t), Type::Time) if t.offset.is_none() => Ok(Value::Time(t)),
+                (Value::Time(t), Type::ZonedTime) if t.offset.is_some() => Ok(Value::Time(t)),
+                (Value::Datetime(dt), Type::Time) if dt.time.offset.is_none() => {
+                    Ok(Value::Time(dt.time))
+                }
+                (Value::Datetime(dt), Type::ZonedTime) if dt.time.offset.is_some() => {
+                    Ok(Value::Time(dt.time))
+                }
+                (Value::Datetime(dt), Type::Timestamp) if dt.time.offset.is_none() => {
+                    Ok(Value::Datetime(dt))
+                }
+                (Value::Datetime(dt), Type::ZonedDatetime) if dt.time.offset.is_some() => {
+                    Ok(Value::Datetime(dt))
+                }
+                (Value::Duration(d), Type::Duration) => Ok(Value::Duration(d)),
+                (Value::Duration(d), Type::QualifiedDuration(DurationQualifier::YearToMonth))
+                    if d.is_year_to_month() =>
+                {
+                    Ok(Value::Duration(d))
+                }
+                (Value::Duration(d), Type::QualifiedDuration(DurationQualifier::DayToSecond))
+                    if d.is_day_to_second() =>
+                {
+                    Ok(Value::Duration(d))
+                }
+                (value, _) => Err(fail(&value)),
+            }
+        }
+        Type::Bytes => match value {
+            v @ Value::Bytes(_) => Ok(v),
+            other => Err(fail(&other)),
+        },
+        Type::List(element) => match value {
+            Value::List(items) => Ok(Value::List(
+                items
+                    .into_iter()
+                    .map(|item| {
+                        if item.is_null() {
+                            Ok(item)
+                        } else {
+                            convert(item, element, span)
+                        }
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            other => Err(fail(&other)),
+        },
+        _ if value.type_of().can_coerce_to(target) => Ok(value),
+        _ => Err(fail(&value)),
+    }
+}
+
+/// Converts a finite float to the shortest decimal that reads back as it.
+fn float_to_decimal(value: f64) -> Option<DecimalValue> {
+    let text = value.abs().to_string();
+    let text = if text.contains('.') { text } else { format!("{text}.0") };
+    match Literal::Float(text.into()).value() {
+        Ok(LiteralValue::Decimal(decimal)) if value.is_sign_negative() => Some(DecimalValue {
+            unscaled: -decimal.unscaled,
+            scale: decimal.scale,
+        }),
+        Ok(LiteralValue::Decimal(decimal)) => Some(decimal),
+        _ => None,
+    }
+}
+
+/// Rounds half away from zero to `scale` digits after the point.
+fn round_to_scale(value: DecimalValue, scale: u32) -> Option<DecimalValue> {
+    if scale >= value.scale {
+        return Some(DecimalValue {
+            unscaled: rescale(value, scale)?,
+            scale,
+        });
+    }
+    let divisor = 10i128.checked_pow(value.scale - scale)?;
+    let quotient = value.unscaled / divisor;
+    let remainder = value.unscaled % divisor;
+    let rounded = if remainder.abs() * 2 >= divisor {
+        quotient + value.unscaled.signum()
+    } else {
+        quotient
+    };
+    Some(DecimalValue {
+        unscaled: rounded,
+        scale,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::expression::parse_expression;
+
+    fn parse(source: &str) -> Expression {
+        let tokens = Lexer::new(source).tokenize().tokens;
+        parse_expression(&tokens).expect("expression should parse")
+    }
+
+    fn eval(source: &str) -> EvalResult {
+        evaluate(&parse(source))
+    }
+
+    fn error_kind(source: &str) -> ConstEvalErrorKind {
+        eval(source).expect_err("evaluation should fail").kind
+    }
+
+    #[test]
+    fn evaluates_arithmetic_with_overflow_checks() {
+        assert_eq!(eval("1 + 2 * 3 - 4"), Ok(Value::Integer(3)));
+        assert_eq!(eval("7 % 3"), Ok(Value::Integer(1)));
+        assert_eq!(eval("-(2)"), Ok(Value::Integer(-2)));
+        assert_eq!(eval("10 / 4"), Ok(Value::Float(2.5)));
+        assert_eq!(
+            eval("1.25 + 1"),
+            Ok(Value::Decimal(DecimalValue { unscaled: 225, scale: 2 }))
+        );
+        assert_eq!(eval("1.5e0 * 2"), Ok(Value::Float(3.0)));
+        assert_eq!(error_kind("9223372036854775807 + 1"), ConstEvalErrorKind::Overflow);
+        assert_eq!(error_kind("1 / 0"), ConstEvalErrorKind::DivisionByZero);
+        assert_eq!(error_kind("1 % 0"), ConstEvalErrorKind::DivisionByZero);
+        assert_eq!(error_kind("'a' + 1"), ConstEvalErrorKind::TypeMismatch);
+        assert_eq!(eval("1 + NULL"), Ok(Value::Null));
+    }
+
+    #[test]
+    fn evaluates_three_valued_logic() {
+        assert_eq!(eval("TRUE AND UNKNOWN"), Ok(Value::Null));
+        assert_eq!(eval("FALSE AND UNKNOWN"), Ok(Value::Boolean(false)));
+        assert_eq!(eval("TRUE OR UNKNOWN"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("FALSE OR NULL"), Ok(Value::Null));
+        assert_eq!(eval("TRUE XOR FALSE"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("TRUE XOR UNKNOWN"), Ok(Value::Null));
+        assert_eq!(eval("NOT UNKNOWN"), Ok(Value::Null));
+        assert_eq!(eval("NULL = NULL"), Ok(Value::Null));
+        assert_eq!(eval("(1 = NULL) IS UNKNOWN"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("NULL IS NOT NULL"), Ok(Value::Boolean(false)));
+        // The right operand is not needed, so its error is never raised.
+        assert_eq!(eval("FALSE AND 1 / 0 = 1"), Ok(Value::Boolean(false)));
+    }
+
+    #[test]
+    fn evaluates_comparisons() {
+        assert_eq!(eval("1 < 1.5"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("2.50 = 2.5"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("'abc' < 'abd'"), Ok(Value::Boolean(true)));
+        assert_eq!(
+            eval("DATE '2024-01-02' > DATE '2024-01-01'"),
+            Ok(Value::Boolean(true))
+        );
+        assert_eq!(
+            eval("ZONED_DATETIME('2024-01-01T01:00:00+01:00') = ZONED_DATETIME('2024-01-01T00:00:00Z')"),
+            Ok(Value::Boolean(true))
+        );
+        assert_eq!(eval("[1, 2] = [1, 2]"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("[1, NULL] = [1, 2]"), Ok(Value::Null));
+        assert_eq!(error_kind("1 < 'a'"), ConstEvalErrorKind::TypeMismatch);
+    }
+
+    #[test]
+    fn evaluates_string_and_numeric_functions() {
+        assert_eq!(eval("UPPER('abc')"), Ok(Value::String("ABC".into())));
+        assert_eq!(eval("TRIM('  x  ')"), Ok(Value::String("x".into())));
+        assert_eq!(eval("LTRIM('xxy', 'x')"), Ok(Value::String("y".into())));
+        assert_eq!(eval("LEFT('hello', 2)"), Ok(Value::String("he".into())));
+        assert_eq!(eval("RIGHT('hello', 3)"), Ok(Value::String("llo".into())));
+        assert_eq!(eval("SUBSTRING('hello', 2, 3)"), Ok(Value::String("ell".into())));
+        assert_eq!(eval("CHAR_LENGTH('héllo')"), Ok(Value::Integer(5)));
+        assert_eq!(eval("BYTE_LENGTH('héllo')"), Ok(Value::Integer(6)));
+        assert_eq!(eval("'a' || 'b'"), Ok(Value::String("ab".into())));
+        assert_eq!(eval("ABS(-3)"), Ok(Value::Integer(3)));
+        assert_eq!(eval("FLOOR(-1.5)"), Ok(Value::Decimal(DecimalValue { unscaled: -2, scale: 0 })));
+        assert_eq!(eval("SQRT(16)"), Ok(Value::Float(4.0)));
+        assert_eq!(error_kind("SQRT(-1)"), ConstEvalErrorKind::InvalidArgument);
+        assert_eq!(eval("UPPER(NULL)"), Ok(Value::Null));
+        assert_eq!(eval("SIZE([1, 2, 3])"), Ok(Value::Integer(3)));
+    }
+
+    #[test]
+    fn evaluates_conditionals() {
+        assert_eq!(eval("COALESCE(NULL, NULL, 3)"), Ok(Value::Integer(3)));
+        assert_eq!(eval("NULLIF(1, 1)"), Ok(Value::Null));
+        assert_eq!(eval("NULLIF(1, 2)"), Ok(Value::Integer(1)));
+        assert_eq!(
+            eval("CASE 2 WHEN 1 THEN 'one' WHEN 2 THEN 'two' ELSE 'many' END"),
+            Ok(Value::String("two".into()))
+        );
+        assert_eq!(
+            eval("CASE WHEN 1 > 2 THEN 'a' WHEN UNKNOWN THEN 'b' END"),
+            Ok(Value::Null)
+        );
+    }
+
+    #[test]
+    fn evaluates_casts_per_cast_rules() {
+        assert_eq!(eval("CAST('42' AS INT)"), Ok(Value::Integer(42)));
+        assert_eq!(eval("CAST(42 AS STRING)"), Ok(Value::String("42".into())));
+        assert_eq!(
+            eval("CAST('1.005' AS DECIMAL(5, 2))"),
+            Ok(Value::Decimal(DecimalValue { unscaled: 101, scale: 2 }))
+        );
+        assert_eq!(eval("CAST('true' AS BOOLEAN)"), Ok(Value::Boolean(true)));
+        assert_eq!(
+            eval("CAST('2024-01-01' AS DATE)"),
+            Ok(Value::Date(DateValue { year: 2024, month: 1, day: 1 }))
+        );
+        assert_eq!(error_kind("CAST(300 AS INT8)"), ConstEvalErrorKind::InvalidCast);
+        assert_eq!(eval("CAST(1.5e0 AS INTEGER)"), Ok(Value::Integer(1)));
+        assert_eq!(error_kind("CAST('x' AS INT)"), ConstEvalErrorKind::InvalidCast);
+        assert_eq!(eval("CAST(NULL AS INT)"), Ok(Value::Null));
+    }
+
+    #[test]
+    fn evaluates_narrowing_and_boolean_casts() {
+        assert_eq!(eval("CAST(3.7 AS INT)"), Ok(Value::Integer(3)));
+        assert_eq!(eval("CAST(-3.7 AS INT)"), Ok(Value::Integer(-3)));
+        assert_eq!(eval("CAST(5 AS INT32)"), Ok(Value::Integer(5)));
+        assert_eq!(eval("CAST(-2.9e0 AS INT16)"), Ok(Value::Integer(-2)));
+        assert_eq!(
+            eval("CAST(10 AS DECIMAL(4, 2))"),
+            Ok(Value::Decimal(DecimalValue { unscaled: 1000, scale: 2 }))
+        );
+        assert_eq!(
+            eval("CAST(2.345e0 AS DECIMAL(4, 2))"),
+            Ok(Value::Decimal(DecimalValue { unscaled: 235, scale: 2 }))
+        );
+        assert_eq!(
+            eval("CAST(-0.5e0 AS DECIMAL(2, 1))"),
+            Ok(Value::Decimal(DecimalValue { unscaled: -5, scale: 1 }))
+        );
+        assert_eq!(eval("CAST(1 AS BOOLEAN)"), Ok(Value::Boolean(true)));
+        assert_eq!(eval("CAST(0 AS BOOLEAN)"), Ok(Value::Boolean(false)));
+        assert_eq!(eval("CAST(TRUE AS INT)"), Ok(Value::Integer(1)));
+
+        assert_eq!(error_kind("CAST(100 AS DECIMAL(4, 2))"), ConstEvalErrorKind::InvalidCast);
+        assert_eq!(error_kind("CAST(1.0e20 AS INT)"), ConstEvalErrorKind::InvalidCast);
+        assert_eq!(error_kind("CAST(40000.5 AS INT16)"), ConstEvalErrorKind::InvalidCast);
+        assert_eq!(error_kind("CAST(DATE '2024-01-01' AS INT)"), ConstEvalErrorKind::InvalidCast);
+    }
+
+    #[test]
+    fn folds_the_sign_into_integer_literals() {
+        assert_eq!(eval("-9223372036854775808"), Ok(Value::Integer(i64::MIN)));
+        assert_eq!(eval("-(5)"), Ok(Value::Integer(-5)));
+        assert_eq!(error_kind("9223372036854775808"), ConstEvalErrorKind::Overflow);
+        assert_eq!(error_kind("-9223372036854775809"), ConstEvalErrorKind::Overflow);
+    }
+
+    #[test]
+    fn parameters_and_non_constants() {
+        assert_eq!(error_kind("$limit + 1"), ConstEvalErrorKind::NotConstant);
+        assert_eq!(error_kind("n.age"), ConstEvalErrorKind::NotConstant);
+        assert_eq!(error_kind("CURRENT_DATE"), ConstEvalErrorKind::NotConstant);
+
+        let parameters = HashMap::from([(SmolStr::new("limit"), Value::Integer(10))]);
+        let evaluator = ConstEvaluator::with_parameters(&parameters);
+        assert_eq!(evaluator.evaluate(&parse("$limit * 2")), Ok(Value::Integer(20)));
+        assert_eq!(
+            evaluator.evaluate(&parse("$other")).unwrap_err().kind,
+            ConstEvalErrorKind::UnboundParameter
+        );
+    }
+
+    #[test]
+    fn folds_constants_and_simplifies_connectives() {
+        let fold = |source: &str| {
+            let mut expr = parse(source);
+            fold_constants(&mut expr, &ConstEvaluator::new());
+            expr
+        };
+
+        let folded = fold("n.age > 10 + 8");
+        let Expression::Comparison(_, _, right, _) = &folded else {
+            panic!("expected comparison, got {folded:?}");
+        };
+        assert!(matches!(right.as_ref(), Expression::Literal(Literal::Integer(v), _) if v == "18"));
+
+        assert!(matches!(fold("n.a = 1 AND 1 = 1"), Expression::Comparison(..)));
+        assert!(matches!(
+            fold("n.a = 1 AND 1 = 2"),
+            Expression::Literal(Literal::Boolean(BooleanValue::False), _)
+        ));
+        assert!(matches!(
+            fold("1 < 2 OR n.a = 1"),
+            Expression::Literal(Literal::Boolean(BooleanValue::True), _)
+        ));
+        assert!(matches!(fold("n.a = 1 OR NULL"), Expression::Logical(..)));
+        assert!(matches!(fold("-(3)"), Expression::Unary(UnaryOperator::Minus, ..)));
+        // Errors are left for run time.
+        assert!(matches!(fold("1 / 0"), Expression::Binary(..)));
+    }
+}
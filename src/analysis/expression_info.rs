@@ -37,6 +37,25 @@ pub enum LiteralInfo {
     Record,
 }
 
+impl From<&Literal> for LiteralInfo {
+    fn from(literal: &Literal) -> Self {
+        match literal {
+            Literal::Boolean(value) => LiteralInfo::Boolean(*value),
+            Literal::Null => LiteralInfo::Null,
+            Literal::Integer(value) => LiteralInfo::Integer(value.clone()),
+            Literal::Float(value) => LiteralInfo::Float(value.clone()),
+            Literal::String(value) => LiteralInfo::String(value.clone()),
+            Literal::ByteString(value) => LiteralInfo::ByteString(value.clone()),
+            Literal::Date(value) => LiteralInfo::Date(value.clone()),
+            Literal::Time(value) => LiteralInfo::Time(value.clone()),
+            Literal::Datetime(value) => LiteralInfo::Datetime(value.clone()),
+            Literal::Duration(value) => LiteralInfo::Duration(value.clone()),
+            Literal::List(_) => LiteralInfo::List,
+            Literal::Record(_) => LiteralInfo::Record,
+        }
+    }
+}
+
 /// Query-planning-oriented metadata for a single expression.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ExpressionInfo {
@@ -72,22 +91,7 @@ impl ExpressionInfoCollector {
     }
 
     fn push_literal(&mut self, literal: &Literal) {
-        let info = match literal {
-            Literal::Boolean(value) => LiteralInfo::Boolean(*value),
-            Literal::Null => LiteralInfo::Null,
-            Literal::Integer(value) => LiteralInfo::Integer(value.clone()),
-            Literal::Float(value) => LiteralInfo::Float(value.clone()),
-            Literal::String(value) => LiteralInfo::String(value.clone()),
-            Literal::ByteString(value) => LiteralInfo::ByteString(value.clone()),
-            Literal::Date(value) => LiteralInfo::Date(value.clone()),
-            Literal::Time(value) => LiteralInfo::Time(value.clone()),
-            Literal::Datetime(value) => LiteralInfo::Datetime(value.clone()),
-            Literal::Duration(value) => LiteralInfo::Duration(value.clone()),
-            Literal::List(_) => LiteralInfo::List,
-            Literal::Record(_) => LiteralInfo::Record,
-        };
-
-        self.info.literals.push(info);
+        self.info.literals.push(LiteralInfo::from(literal));
     }
 
     fn push_function_name(&mut self, name: SmolStr) {
@@ -0,0 +1,946 @@
+//! Join order planning for graph patterns.
+//!
+//! [`JoinGraph`] decomposes a graph pattern into nodes joined by edges:
+//! node patterns sharing a variable become one node, and quantified,
+//! parenthesized or simplified subpaths become a single edge between the
+//! nodes around them. [`JoinOrderPlanner`] then turns each connected
+//! component into a scan followed by expansions, and joins the components.
+//!
+//! Start points are chosen by heuristics, strongest first:
+//!
+//! - variables bound by earlier clauses,
+//! - equalities on every property of a primary key or unique constraint,
+//!   given a schema,
+//! - the fewest estimated rows, given a [`CardinalityEstimator`],
+//! - node patterns with the most property equalities, then with labels.
+//!
+//! Expansions prefer edges closing a cycle, then the smallest estimated
+//! fan-out, then targets the start heuristics favour. With an estimator,
+//! every start candidate is expanded and the plan with the lowest cost — the
+//! sum of the intermediate row counts — is kept.
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::analysis::join_order::{JoinOrderPlanner, PlanStep};
+//! use gql_parser::ast::{QueryStatement, Statement};
+//! use gql_parser::ast::query::{LinearQuery, MatchStatement, PrimitiveQueryStatement, Query};
+//! use gql_parser::parse;
+//!
+//! let program = parse("MATCH (a)-[:KNOWS]->(b:Person {name: 'Ann'}) RETURN a").ast.unwrap();
+//! let Statement::Query(statement) = &program.statements[0] else { unreachable!() };
+//! let Query::Linear(LinearQuery { primitive_statements, .. }) = &statement.query else {
+//!     unreachable!()
+//! };
+//! let PrimitiveQueryStatement::Match(MatchStatement::Simple(matched)) = &primitive_statements[0]
+//! else {
+//!     unreachable!()
+//! };
+//!
+//! let plan = JoinOrderPlanner::new().plan(&matched.pattern);
+//! let PlanStep::Scan { node, .. } = &plan.steps[0] else { unreachable!() };
+//! assert_eq!(plan.graph.nodes[*node].variable.as_deref(), Some("b"));
+//! assert!(matches!(plan.steps[1], PlanStep::Expand { reversed: true, .. }));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use smol_str::SmolStr;
+
+use crate::analysis::cardinality::{
+    CardinalityEstimator, PatternEstimate, collect_conjuncts, single_label,
+};
+use crate::analysis::expression_info::ExpressionInfo;
+use crate::analysis::pattern_info::PatternInfo;
+use crate::ast::expression::ComparisonOperator;
+use crate::ast::query::{
+    AbbreviatedEdgePattern, EdgeDirection, EdgePattern, ElementPattern,
+    ElementPropertySpecification, GraphPattern, NodePattern, PathPatternExpression, PathPrimary,
+    PathTerm,
+};
+use crate::ast::{Expression, Span};
+use crate::semantic::schema_catalog::{ConstraintMeta, SchemaSnapshot, TypeRef};
+use crate::semantic::validator::pattern_validation::pattern_connectivity;
+
+/// A node of a join graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinNode {
+    /// Node variable; `None` for anonymous node patterns.
+    pub variable: Option<SmolStr>,
+    /// Spans of the node patterns describing the node, in source order.
+    /// Empty for the open end of a path that ends with an edge.
+    pub spans: Vec<Span>,
+    /// Label, if a node pattern requires a single label.
+    pub label: Option<SmolStr>,
+    /// True if a node pattern has any label expression.
+    pub labeled: bool,
+    /// Properties compared for equality with a value that does not depend
+    /// on other variables.
+    pub equalities: Vec<SmolStr>,
+}
+
+/// What a join edge stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinEdgeKind {
+    /// A single edge pattern.
+    Edge,
+    /// A quantified, parenthesized or simplified subpath, or a path
+    /// union, matched as a whole.
+    Subpath,
+}
+
+/// An edge of a join graph, connecting two [`JoinNode`]s by index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinEdge {
+    /// Node on the left of the edge as written.
+    pub source: usize,
+    /// Node on the right of the edge as written.
+    pub target: usize,
+    /// Edge variable of a single edge pattern.
+    pub variable: Option<SmolStr>,
+    /// Span of the edge pattern or subpath.
+    pub span: Span,
+    /// Direction as written; `None` for subpaths other than a quantified
+    /// edge.
+    pub direction: Option<EdgeDirection>,
+    /// What the edge stands for.
+    pub kind: JoinEdgeKind,
+}
+
+/// A graph pattern decomposed into nodes and the edges joining them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JoinGraph {
+    /// Nodes, in order of first appearance.
+    pub nodes: Vec<JoinNode>,
+    /// Edges, in source order.
+    pub edges: Vec<JoinEdge>,
+    /// Node indices of each connected component, in order of first
+    /// appearance.
+    pub components: Vec<Vec<usize>>,
+    /// Structural metadata of the pattern.
+    pub info: PatternInfo,
+}
+
+/// Why a scan starts at its node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartReason {
+    /// The node is bound by an earlier clause.
+    Bound,
+    /// The node is looked up by a primary key or unique constraint.
+    KeyLookup {
+        /// Label owning the constraint.
+        label: SmolStr,
+        /// Constrained properties.
+        properties: Vec<SmolStr>,
+    },
+    /// The node has the fewest estimated rows.
+    Estimated {
+        /// Estimated number of matching nodes.
+        rows: f64,
+    },
+    /// The node has a label expression.
+    Label,
+    /// No better start exists; all nodes are scanned.
+    AllNodes,
+}
+
+/// A step of a match plan.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanStep {
+    /// Starts a component by scanning the nodes matching a node.
+    Scan {
+        /// Index of the scanned node.
+        node: usize,
+        /// Why the scan starts there.
+        reason: StartReason,
+    },
+    /// Follows an edge from a node that is already matched.
+    Expand {
+        /// Index of the followed edge.
+        edge: usize,
+        /// Node the expansion starts from.
+        from: usize,
+        /// Node the expansion reaches.
+        to: usize,
+        /// Direction as traversed from `from` to `to`.
+        direction: Option<EdgeDirection>,
+        /// True if the edge is followed against its written order.
+        reversed: bool,
+        /// True if `to` is already matched or bound, so the expansion only
+        /// checks that the edge exists.
+        into: bool,
+    },
+    /// Joins the rows of a component with the rows of the components
+    /// planned before it.
+    Join {
+        /// Index of the joined component.
+        component: usize,
+    },
+}
+
+/// An ordered plan for matching a graph pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchPlan {
+    /// The decomposed pattern.
+    pub graph: JoinGraph,
+    /// Steps in execution order.
+    pub steps: Vec<PlanStep>,
+    /// Estimated result rows, given an estimator.
+    pub rows: Option<f64>,
+    /// Estimated cost as the sum of intermediate rows, given an estimator.
+    pub cost: Option<f64>,
+}
+
+impl JoinGraph {
+    /// Decomposes a graph pattern.
+    pub fn from_pattern(pattern: &GraphPattern) -> Self {
+        let mut builder = GraphBuilder::default();
+        for path in &pattern.paths.patterns {
+            builder.path_expression(&path.expression);
+        }
+        if let Some(where_clause) = &pattern.where_clause {
+            let mut conjuncts = Vec::new();
+            collect_conjuncts(&where_clause.condition, &mut conjuncts);
+            for conjunct in conjuncts {
+                if let Some((variable, property)) = key_equality(conjunct, None)
+                    && let Some(&node) = builder.variables.get(&variable)
+                {
+                    add_equality(&mut builder.graph.nodes[node], property);
+                }
+            }
+        }
+
+        let mut graph = builder.graph;
+        graph.info = PatternInfo::analyze(pattern);
+        graph.components = components(&graph, &builder.variables, pattern);
+        graph
+    }
+
+    /// Returns the component containing `node`.
+    pub fn component_of(&self, node: usize) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|component| component.contains(&node))
+    }
+}
+
+/// Plans the expansion order of graph patterns.
+#[derive(Default)]
+pub struct JoinOrderPlanner<'a> {
+    schema: Option<&'a dyn SchemaSnapshot>,
+    estimator: Option<&'a CardinalityEstimator<'a>>,
+    bound: HashSet<SmolStr>,
+}
+
+impl<'a> JoinOrderPlanner<'a> {
+    /// Creates a planner using heuristics only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses key and unique constraints from `schema` to find lookups.
+    pub fn with_schema(mut self, schema: &'a dyn SchemaSnapshot) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Chooses start points and expansions by estimated cost.
+    pub fn with_estimator(mut self, estimator: &'a CardinalityEstimator<'a>) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Treats `variables` as bound by earlier clauses.
+    pub fn with_bound_variables<I, S>(mut self, variables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<SmolStr>,
+    {
+        self.bound.extend(variables.into_iter().map(Into::into));
+        self
+    }
+
+    /// Plans a graph pattern.
+    pub fn plan(&self, pattern: &GraphPattern) -> MatchPlan {
+        let graph = JoinGraph::from_pattern(pattern);
+        let estimate = self
+            .estimator
+            .map(|estimator| estimator.estimate_pattern(pattern));
+        let context = Context::new(self, &graph, estimate.as_ref());
+
+        let mut plans: Vec<(usize, ComponentPlan)> = graph
+            .components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| (index, context.component(component)))
+            .collect();
+        // Components holding bound variables are cheap to start from; the
+        // others go in order of their estimated rows.
+        plans.sort_by(|(left_index, left), (right_index, right)| {
+            right
+                .bound
+                .cmp(&left.bound)
+                .then_with(|| {
+                    if context.estimated {
+                        left.rows.total_cmp(&right.rows)
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .then_with(|| left_index.cmp(right_index))
+        });
+
+        let mut steps = Vec::new();
+        let mut rows = 1.0;
+        let mut cost = 0.0;
+        for (position, (index, plan)) in plans.into_iter().enumerate() {
+            steps.extend(plan.steps);
+            cost += plan.cost;
+            rows *= plan.rows;
+            if position > 0 {
+                steps.push(PlanStep::Join { component: index });
+                cost += rows;
+            }
+        }
+        if let Some(estimate) = &estimate {
+            rows *= estimate
+                .predicates
+                .iter()
+                .map(|predicate| predicate.selectivity)
+                .product::<f64>();
+        }
+
+        let estimated = context.estimated;
+        MatchPlan {
+            graph,
+            steps,
+            rows: estimated.then_some(rows),
+            cost: estimated.then_some(cost),
+        }
+    }
+}
+
+/// Builds a join graph path by path.
+#[derive(Default)]
+struct GraphBuilder {
+    graph: JoinGraph,
+    variables: HashMap<SmolStr, usize>,
+}
+
+/// An edge or subpath waiting for the node it leads to.
+struct PendingEdge {
+    source: usize,
+    variable: Option<SmolStr>,
+    span: Span,
+    direction: Option<EdgeDirection>,
+    kind: JoinEdgeKind,
+}
+
+impl GraphBuilder {
+    fn path_expression(&mut self, expression: &PathPatternExpression) {
+        match expression {
+            PathPatternExpression::Term(term) => self.path_term(term),
+            _ => {
+                // A union of paths: only its ends are joined on.
+                let source = self.anonymous_node();
+                let target = self.anonymous_node();
+                self.push_edge(
+                    PendingEdge {
+                        source,
+                        variable: None,
+                        span: expression.span().clone(),
+                        direction: None,
+                        kind: JoinEdgeKind::Subpath,
+                    },
+                    target,
+                );
+            }
+        }
+    }
+
+    fn path_term(&mut self, term: &PathTerm) {
+        let mut current: Option<usize> = None;
+        let mut pending: Option<PendingEdge> = None;
+        for factor in &term.factors {
+            if let (PathPrimary::ElementPattern(element), None) =
+                (&factor.primary, &factor.quantifier)
+                && let ElementPattern::Node(node) = element.as_ref()
+            {
+                let index = match (pending.take(), current) {
+                    (Some(edge), _) => {
+                        let index = self.node(node, None);
+                        self.push_edge(edge, index);
+                        index
+                    }
+                    // Adjacent node patterns describe the same node.
+                    (None, Some(previous)) => self.node(node, Some(previous)),
+                    (None, None) => self.node(node, None),
+                };
+                current = Some(index);
+                continue;
+            }
+
+            // An edge or subpath starts from the current end of the path.
+            let source = match (pending.take(), current) {
+                (Some(edge), _) => {
+                    let end = self.anonymous_node();
+                    self.push_edge(edge, end);
+                    end
+                }
+                (None, Some(current)) => current,
+                (None, None) => self.anonymous_node(),
+            };
+            pending = Some(match (&factor.primary, &factor.quantifier) {
+                (PathPrimary::ElementPattern(element), quantifier) => {
+                    let ElementPattern::Edge(edge) = element.as_ref() else {
+                        unreachable!("unquantified node patterns are handled above")
+                    };
+                    PendingEdge {
+                        source,
+                        variable: match (edge, quantifier) {
+                            (EdgePattern::Full(full), None) => {
+                                full.filler.variable.as_ref().map(|v| v.variable.clone())
+                            }
+                            _ => None,
+                        },
+                        span: factor.span.clone(),
+                        direction: Some(edge_direction(edge)),
+                        kind: if quantifier.is_some() {
+                            JoinEdgeKind::Subpath
+                        } else {
+                            JoinEdgeKind::Edge
+                        },
+                    }
+                }
+                _ => PendingEdge {
+                    source,
+                    variable: None,
+                    span: factor.span.clone(),
+                    direction: None,
+                    kind: JoinEdgeKind::Subpath,
+                },
+            });
+            current = None;
+        }
+        if let Some(edge) = pending {
+            let end = self.anonymous_node();
+            self.push_edge(edge, end);
+        }
+    }
+
+    /// Returns the node for a node pattern, merging it into `same` or into
+    /// the node already declaring its variable.
+    fn node(&mut self, pattern: &NodePattern, same: Option<usize>) -> usize {
+        let variable = pattern.variable.as_ref().map(|v| v.variable.clone());
+        let existing = variable
+            .as_ref()
+            .and_then(|name| self.variables.get(name).copied());
+        let index = match (existing, same) {
+            (Some(existing), Some(same)) if existing != same => {
+                // Both nodes are already known: record their identity as a
+                // zero-length subpath.
+                self.graph.edges.push(JoinEdge {
+                    source: same,
+                    target: existing,
+                    variable: None,
+                    span: pattern.span.clone(),
+                    direction: None,
+                    kind: JoinEdgeKind::Subpath,
+                });
+                existing
+            }
+            (Some(index), _) | (None, Some(index)) => index,
+            (None, None) => self.anonymous_node(),
+        };
+
+        let node = &mut self.graph.nodes[index];
+        if let Some(name) = &variable {
+            self.variables.entry(name.clone()).or_insert(index);
+            if node.variable.is_none() {
+                node.variable = Some(name.clone());
+            }
+        }
+        node.spans.push(pattern.span.clone());
+        if let Some(labels) = &pattern.label_expression {
+            node.labeled = true;
+            if node.label.is_none() {
+                node.label = single_label(labels);
+            }
+        }
+        for property in property_equalities(pattern.properties.as_ref()) {
+            add_equality(node, property);
+        }
+        if let Some(where_clause) = &pattern.where_clause {
+            let mut conjuncts = Vec::new();
+            collect_conjuncts(&where_clause.condition, &mut conjuncts);
+            for conjunct in conjuncts {
+                if let Some((_, property)) = key_equality(conjunct, variable.as_ref()) {
+                    add_equality(node, property);
+                }
+            }
+        }
+        index
+    }
+
+    fn anonymous_node(&mut self) -> usize {
+        self.graph.nodes.push(JoinNode {
+            variable: None,
+            spans: Vec::new(),
+            label: None,
+            labeled: false,
+            equalities: Vec::new(),
+        });
+        self.graph.nodes.len() - 1
+    }
+
+    fn push_edge(&mut self, edge: PendingEdge, target: usize) {
+        self.graph.edges.push(JoinEdge {
+            source: edge.source,
+            target,
+            variable: edge.variable,
+            span: edge.span,
+            direction: edge.direction,
+            kind: edge.kind,
+        });
+    }
+}
+
+/// Groups the nodes of `graph` into connected components, joining nodes
+/// connected by an edge or by the variable connectivity of the pattern.
+fn components(
+    graph: &JoinGraph,
+    variables: &HashMap<SmolStr, usize>,
+    pattern: &GraphPattern,
+) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..graph.nodes.len()).collect();
+    fn find(parent: &mut [usize], node: usize) -> usize {
+        let mut root = node;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[node] = root;
+        root
+    }
+    fn union(parent: &mut [usize], left: usize, right: usize) {
+        let left = find(parent, left);
+        let right = find(parent, right);
+        if left != right {
+            parent[left.max(right)] = left.min(right);
+        }
+    }
+
+    for edge in &graph.edges {
+        union(&mut parent, edge.source, edge.target);
+    }
+    let (adjacency, _) = pattern_connectivity(pattern);
+    for (variable, neighbours) in &adjacency {
+        let Some(&node) = variables.get(variable.as_str()) else {
+            continue;
+        };
+        for neighbour in neighbours {
+            if let Some(&other) = variables.get(neighbour.as_str()) {
+                union(&mut parent, node, other);
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut roots: HashMap<usize, usize> = HashMap::new();
+    for node in 0..graph.nodes.len() {
+        let root = find(&mut parent, node);
+        let component = *roots.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[component].push(node);
+    }
+    components
+}
+
+/// A candidate expansion: edge, from, to, and whether it is reversed.
+type Expansion = (usize, usize, usize, bool);
+
+/// A planned component.
+struct ComponentPlan {
+    steps: Vec<PlanStep>,
+    rows: f64,
+    cost: f64,
+    bound: bool,
+}
+
+/// Per-node and per-edge estimates shared by all component plans.
+struct Context<'p> {
+    graph: &'p JoinGraph,
+    estimated: bool,
+    bound: Vec<bool>,
+    keys: Vec<Option<(SmolStr, Vec<SmolStr>)>>,
+    /// Estimated rows, selectivity and label rows of each node.
+    nodes: Vec<Option<(f64, f64, f64)>>,
+    /// Estimated forward and reverse fan-out of each edge.
+    edges: Vec<Option<(f64, f64)>>,
+}
+
+impl<'p> Context<'p> {
+    fn new(
+        planner: &JoinOrderPlanner<'_>,
+        graph: &'p JoinGraph,
+        estimate: Option<&PatternEstimate>,
+    ) -> Self {
+        let bound = graph
+            .nodes
+            .iter()
+            .map(|node| {
+                node.variable
+                    .as_ref()
+                    .is_some_and(|name| planner.bound.contains(name))
+            })
+            .collect();
+        let keys = graph
+            .nodes
+            .iter()
+            .map(|node| planner.schema.and_then(|schema| key_lookup(schema, node)))
+            .collect();
+        let nodes = graph
+            .nodes
+            .iter()
+            .map(|node| {
+                let estimate = estimate?;
+                estimate
+                    .nodes
+                    .iter()
+                    .filter(|candidate| node.spans.contains(&candidate.span))
+                    .map(|candidate| (candidate.rows, candidate.selectivity, candidate.label_rows))
+                    .min_by(|left, right| left.0.total_cmp(&right.0))
+            })
+            .collect();
+        let edges = graph
+            .edges
+            .iter()
+            .map(|edge| {
+                let estimate = estimate?;
+                // A subpath follows every expansion inside it once.
+                let (mut forward, mut reverse) = (1.0, 1.0);
+                for expansion in &estimate.expansions {
+                    if edge.span.start <= expansion.span.start
+                        && expansion.span.end <= edge.span.end
+                    {
+                        forward *= expansion.fan_out * expansion.selectivity;
+                        reverse *= expansion.reverse_fan_out * expansion.selectivity;
+                    }
+                }
+                Some((forward, reverse))
+            })
+            .collect();
+        Self {
+            graph,
+            estimated: estimate.is_some(),
+            bound,
+            keys,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Plans a component from the best start candidate.
+    fn component(&self, component: &[usize]) -> ComponentPlan {
+        let rank = |node: usize| {
+            if self.bound[node] {
+                0
+            } else if self.keys[node].is_some() {
+                1
+            } else {
+                2
+            }
+        };
+        let best = component.iter().map(|&node| rank(node)).min().unwrap_or(2);
+        let candidates: Vec<usize> = component
+            .iter()
+            .copied()
+            .filter(|&node| rank(node) == best)
+            .collect();
+
+        if self.estimated {
+            candidates
+                .iter()
+                .map(|&start| self.expand_from(component, start))
+                .min_by(|left, right| left.cost.total_cmp(&right.cost))
+                .expect("components are not empty")
+        } else {
+            let start = candidates
+                .iter()
+                .copied()
+                .min_by(|&left, &right| self.prefer(left, right))
+                .expect("components are not empty");
+            self.expand_from(component, start)
+        }
+    }
+
+    /// Orders nodes by how attractive they are to start from or expand to
+    /// without estimates.
+    fn prefer(&self, left: usize, right: usize) -> Ordering {
+        let (left_node, right_node) = (&self.graph.nodes[left], &self.graph.nodes[right]);
+        self.bound[right]
+            .cmp(&self.bound[left])
+            .then_with(|| self.keys[right].is_some().cmp(&self.keys[left].is_some()))
+            .then_with(|| right_node.equalities.len().cmp(&left_node.equalities.len()))
+            .then_with(|| right_node.labeled.cmp(&left_node.labeled))
+            .then_with(|| left.cmp(&right))
+    }
+
+    fn start_reason(&self, node: usize) -> StartReason {
+        if self.bound[node] {
+            StartReason::Bound
+        } else if let Some((label, properties)) = &self.keys[node] {
+            StartReason::KeyLookup {
+                label: label.clone(),
+                properties: properties.clone(),
+            }
+        } else if let Some((rows, _, _)) = self.nodes[node] {
+            StartReason::Estimated { rows }
+        } else if self.graph.nodes[node].labeled {
+            StartReason::Label
+        } else {
+            StartReason::AllNodes
+        }
+    }
+
+    /// Greedily expands a component from `start`.
+    fn expand_from(&self, component: &[usize], start: usize) -> ComponentPlan {
+        let mut matched: HashSet<usize> = HashSet::from([start]);
+        let mut used = vec![false; self.graph.edges.len()];
+        let mut rows = if self.bound[start] {
+            1.0
+        } else {
+            self.nodes[start].map_or(1.0, |(rows, _, _)| rows)
+        };
+        let mut cost = rows;
+        let mut steps = vec![PlanStep::Scan {
+            node: start,
+            reason: self.start_reason(start),
+        }];
+
+        loop {
+            let candidates =
+                self.graph
+                    .edges
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, edge)| !used[*index] && component.contains(&edge.source))
+                    .flat_map(|(index, edge)| {
+                        let forward: Option<Expansion> = matched
+                            .contains(&edge.source)
+                            .then_some((index, edge.source, edge.target, false));
+                        let backward: Option<Expansion> = matched
+                            .contains(&edge.target)
+                            .then_some((index, edge.target, edge.source, true));
+                        forward.into_iter().chain(backward)
+                    });
+            let Some((edge, from, to, reversed)) =
+                candidates.min_by(|left, right| self.compare_expansions(*left, *right, &matched))
+            else {
+                break;
+            };
+
+            let into = matched.contains(&to) || self.bound[to];
+            let fan_out = self.fan_out(edge, reversed);
+            let (target_selectivity, target_rows) = self.nodes[to]
+                .map_or((1.0, 1.0), |(_, selectivity, label_rows)| {
+                    (selectivity, label_rows)
+                });
+            rows *= if into {
+                // The target is already fixed: only edges reaching it count.
+                fan_out / target_rows.max(1.0)
+            } else {
+                fan_out * target_selectivity
+            };
+            cost += rows;
+
+            let direction = self.graph.edges[edge].direction;
+            steps.push(PlanStep::Expand {
+                edge,
+                from,
+                to,
+                direction: if reversed {
+                    direction.map(reverse_direction)
+                } else {
+                    direction
+                },
+                reversed,
+                into,
+            });
+            used[edge] = true;
+            matched.insert(to);
+        }
+
+        ComponentPlan {
+            steps,
+            rows,
+            cost,
+            bound: component.iter().any(|&node| self.bound[node]),
+        }
+    }
+
+    /// Estimated fan-out of an edge followed in either direction.
+    fn fan_out(&self, edge: usize, reversed: bool) -> f64 {
+        match self.edges[edge] {
+            Some((_, reverse)) if reversed => reverse,
+            Some((forward, _)) => forward,
+            None => 1.0,
+        }
+    }
+
+    /// Orders candidate expansions.
+    fn compare_expansions(
+        &self,
+        left: Expansion,
+        right: Expansion,
+        matched: &HashSet<usize>,
+    ) -> Ordering {
+        let closes = |(_, _, to, _): Expansion| matched.contains(&to) || self.bound[to];
+        let growth = |(edge, _, to, reversed): Expansion| {
+            self.fan_out(edge, reversed)
+                * self.nodes[to].map_or(1.0, |(_, selectivity, _)| selectivity)
+        };
+        let kind = |(edge, ..): Expansion| self.graph.edges[edge].kind == JoinEdgeKind::Subpath;
+        closes(right)
+            .cmp(&closes(left))
+            .then_with(|| growth(left).total_cmp(&growth(right)))
+            .then_with(|| kind(left).cmp(&kind(right)))
+            .then_with(|| self.prefer(left.2, right.2))
+            .then_with(|| left.3.cmp(&right.3))
+            .then_with(|| left.0.cmp(&right.0))
+    }
+}
+
+/// Returns the label and properties of a key or unique constraint whose
+/// properties are all compared for equality on `node`.
+fn key_lookup(schema: &dyn SchemaSnapshot, node: &JoinNode) -> Option<(SmolStr, Vec<SmolStr>)> {
+    let label = node.label.as_ref()?;
+    schema
+        .constraints(TypeRef::NodeType(label.clone()))
+        .iter()
+        .filter_map(|constraint| match constraint {
+            ConstraintMeta::PrimaryKey { properties } | ConstraintMeta::Unique { properties } => {
+                Some(properties)
+            }
+            _ => None,
+        })
+        .find(|properties| {
+            !properties.is_empty()
+                && properties
+                    .iter()
+                    .all(|property| node.equalities.contains(property))
+        })
+        .map(|properties| (label.clone(), properties.clone()))
+}
+
+/// Returns the properties of a property specification that are set to a
+/// value independent of other variables.
+fn property_equalities(properties: Option<&ElementPropertySpecification>) -> Vec<SmolStr> {
+    properties
+        .map(|properties| {
+            properties
+                .properties
+                .iter()
+                .filter(|pair| {
+                    ExpressionInfo::analyze(&pair.value)
+                        .variable_references
+                        .is_empty()
+                })
+                .map(|pair| pair.key.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Matches `variable.property = value`, either way round, where `value`
+/// does not reference variables. With `variable` given, only that variable
+/// matches.
+fn key_equality(conjunct: &Expression, variable: Option<&SmolStr>) -> Option<(SmolStr, SmolStr)> {
+    let Expression::Comparison(ComparisonOperator::Eq, left, right, _) = conjunct else {
+        return None;
+    };
+    [(left, right), (right, left)]
+        .into_iter()
+        .find_map(|(reference, value)| {
+            let Expression::PropertyReference(target, property, _) = reference.as_ref() else {
+                return None;
+            };
+            let Expression::VariableReference(name, _) = target.as_ref() else {
+                return None;
+            };
+            let independent = ExpressionInfo::analyze(value)
+                .variable_references
+                .is_empty();
+            (independent && variable.is_none_or(|variable| variable == name))
+                .then(|| (name.clone(), property.clone()))
+        })
+}
+
+fn add_equality(node: &mut JoinNode, property: SmolStr) {
+    if !node.equalities.contains(&property) {
+        node.equalities.push(property);
+    }
+}
+
+fn edge_direction(edge: &EdgePattern) -> EdgeDirection {
+    match edge {
+        EdgePattern::Full(full) => full.direction,
+        EdgePattern::Abbreviated(AbbreviatedEdgePattern::LeftArrow { .. }) => {
+            EdgeDirection::PointingLeft
+        }
+        EdgePattern::Abbreviated(AbbreviatedEdgePattern::RightArrow { .. }) => {
+            EdgeDirection::PointingRight
+        }
+        EdgePattern::Abbreviated(AbbreviatedEdgePattern::Undirected { .. }) => {
+            EdgeDirection::Undirected
+        }
+        EdgePattern::Abbreviated(AbbreviatedEdgePattern::AnyDirection { .. }) => {
+            EdgeDirection::AnyDirection
+        }
+    }
+}
+
+/// Returns the direction of an edge followed from right to left.
+fn reverse_direction(direction: EdgeDirection) -> EdgeDirection {
+    match direction {
+        EdgeDirection::PointingLeft => EdgeDirection::PointingRight,
+        EdgeDirection::PointingRight => EdgeDirection::PointingLeft,
+        EdgeDirection::LeftOrUndirected => EdgeDirection::RightOrUndirected,
+        EdgeDirection::RightOrUndirected => EdgeDirection::LeftOrUndirected,
+        symmetric => symmetric,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_direction_swaps_pointing_directions() {
+        for direction in [
+            EdgeDirection::PointingLeft,
+            EdgeDirection::PointingRight,
+            EdgeDirection::Undirected,
+            EdgeDirection::AnyDirected,
+            EdgeDirection::LeftOrUndirected,
+            EdgeDirection::AnyDirection,
+            EdgeDirection::RightOrUndirected,
+        ] {
+            assert_eq!(reverse_direction(reverse_direction(direction)), direction);
+        }
+        assert_eq!(
+            reverse_direction(EdgeDirection::PointingRight),
+            EdgeDirection::PointingLeft
+        );
+        assert_eq!(
+            reverse_direction(EdgeDirection::LeftOrUndirected),
+            EdgeDirection::RightOrUndirected
+        );
+        assert_eq!(
+            reverse_direction(EdgeDirection::AnyDirected),
+            EdgeDirection::AnyDirected
+        );
+    }
+}
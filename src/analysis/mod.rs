@@ -1,13 +1,19 @@
 //! Query analysis APIs for compiler-oriented planning metadata.
 
+pub mod cardinality;
+pub mod const_eval;
 pub mod expression_info;
+pub mod join_order;
+pub mod normalize;
 pub mod pattern_info;
 pub mod query_info;
+pub mod statement_class;
 pub mod variable_dependency;
 
 pub use expression_info::{ExpressionInfo, LiteralInfo, PropertyReference};
 pub use pattern_info::{LabelExpressionComplexity, PatternInfo};
 pub use query_info::{ClauseId, ClauseInfo, ClauseKind, QueryInfo, QueryShape};
+pub use statement_class::{StatementCategory, StatementClass, classify_program};
 pub use variable_dependency::{
     DefineUseEdge, DefinitionPoint, UsagePoint, VariableDependencyGraph,
 };
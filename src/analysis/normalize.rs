@@ -0,0 +1,437 @@
+//! Query normalization and fingerprinting.
+//!
+//! [`normalize`] rewrites a program into a canonical form so that queries
+//! differing only in layout, keyword case, literal values or the order of
+//! `AND` operands share a [`Fingerprint`]:
+//!
+//! - Scalar literals (numbers, strings, byte strings and temporal literals)
+//!   are replaced by positional parameters `$1`, `$2`, ... and returned as
+//!   [`ExtractedLiteral`]s so the caller can bind them. A minus sign in front
+//!   of a numeric literal is lifted with it. `NULL` and boolean literals are
+//!   kept since they usually change the plan.
+//! - Redundant parentheses are dropped; grouping is kept by the tree itself.
+//! - Operands of `AND` chains are sorted when none of them contains a
+//!   subquery or a call to a non-builtin function.
+//! - Keyword case and whitespace never reach the AST, and source spans are
+//!   ignored by the fingerprint.
+//!
+//! Literals inside `VALUE { ... }` subqueries and `EXISTS` graph patterns are
+//! left in place; they still contribute to the fingerprint.
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::analysis::normalize::normalize;
+//! use gql_parser::parse;
+//!
+//! let a = parse("MATCH (n:Person) WHERE n.age > 30 AND n.name = 'Ann' RETURN n")
+//!     .ast
+//!     .unwrap();
+//! let b = parse("match (n:Person)\n where n.name = 'Bob' and n.age > 41\n return n")
+//!     .ast
+//!     .unwrap();
+//!
+//! let (a, b) = (normalize(&a), normalize(&b));
+//! assert_eq!(a.fingerprint, b.fingerprint);
+//! assert_eq!(a.parameters.len(), 2);
+//! ```
+
+use std::fmt;
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use super::expression_info::LiteralInfo;
+use crate::ast::expression::{FunctionName, Literal, LogicalOperator, UnaryOperator};
+use crate::ast::program::Program;
+use crate::ast::spanless::{Spanless, SpanlessKey};
+use crate::ast::visit::{self, Visit};
+use crate::ast::visit_mut::{VisitMut, walk_expression};
+use crate::ast::{Expression, Span};
+
+/// A literal lifted out of a query during normalization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedLiteral {
+    /// Name of the positional parameter that replaced the literal, without `$`.
+    pub parameter: SmolStr,
+    /// The original literal.
+    pub literal: Literal,
+    /// Whether the literal was preceded by a unary minus, which the parameter
+    /// now stands for as well.
+    pub negated: bool,
+    /// Span of the literal, including any minus sign, in the original source.
+    pub span: Span,
+}
+
+impl ExtractedLiteral {
+    /// Returns the literal in [`ExpressionInfo`](super::ExpressionInfo) form.
+    pub fn info(&self) -> LiteralInfo {
+        LiteralInfo::from(&self.literal)
+    }
+}
+
+/// A stable 128-bit hash of a normalized program.
+///
+/// The hash is FNV-1a over a span-free structural encoding of the AST, so it
+/// is stable across processes and platforms for a given crate version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(u128);
+
+impl Fingerprint {
+    /// Returns the full 128-bit value.
+    pub fn as_u128(self) -> u128 {
+        self.0
+    }
+
+    /// Returns a 64-bit value folded from the full fingerprint.
+    pub fn as_u64(self) -> u64 {
+        (self.0 >> 64) as u64 ^ self.0 as u64
+    }
+
+    fn of(program: &Program) -> Self {
+        const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+        const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+        let key = program.spanless_key();
+        let hash = key.as_bytes().iter().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ *byte as u128).wrapping_mul(PRIME)
+        });
+        Fingerprint(hash)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// A normalized program together with the literals lifted out of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedProgram {
+    /// The rewritten program. Spans still refer to the original source.
+    pub program: Program,
+    /// Extracted literals in parameter order (`$1` first).
+    pub parameters: Vec<ExtractedLiteral>,
+    /// Fingerprint of the normalized program.
+    pub fingerprint: Fingerprint,
+}
+
+/// Normalizes a program for plan caching.
+pub fn normalize(program: &Program) -> NormalizedProgram {
+    let mut program = program.clone();
+
+    let _ = Canonicalizer.visit_program(&mut program);
+
+    let mut existing = ParameterCollector::default();
+    let _ = existing.visit_program(&mut program);
+    let mut extractor = LiteralExtractor {
+        next: existing.max_positional + 1,
+        parameters: Vec::new(),
+    };
+    let _ = extractor.visit_program(&mut program);
+
+    let fingerprint = Fingerprint::of(&program);
+    NormalizedProgram {
+        program,
+        parameters: extractor.parameters,
+        fingerprint,
+    }
+}
+
+/// Returns the fingerprint of a program's normalized form.
+pub fn fingerprint(program: &Program) -> Fingerprint {
+    normalize(program).fingerprint
+}
+
+/// Whether a literal is lifted into a parameter.
+fn is_extractable(literal: &Literal) -> bool {
+    !matches!(
+        literal,
+        Literal::Boolean(_) | Literal::Null | Literal::List(_) | Literal::Record(_)
+    )
+}
+
+/// Whether an expression is lifted into a parameter, returning whether it is
+/// a negated numeric literal.
+fn extractable(expression: &Expression) -> Option<bool> {
+    match expression {
+        Expression::Literal(literal, _) if is_extractable(literal) => Some(false),
+        Expression::Unary(UnaryOperator::Minus, operand, _)
+            if matches!(
+                **operand,
+                Expression::Literal(Literal::Integer(_) | Literal::Float(_), _)
+            ) =>
+        {
+            Some(true)
+        }
+        _ => None,
+    }
+}
+
+/// Drops parentheses and sorts the operands of `AND` chains by their
+/// span-free structure.
+struct Canonicalizer;
+
+impl VisitMut for Canonicalizer {
+    type Break = ();
+
+    fn visit_expression(&mut self, expression: &mut Expression) -> ControlFlow<()> {
+        while let Expression::Parenthesized(inner, _) = expression {
+            let inner = std::mem::replace(inner.as_mut(), Expression::Literal(Literal::Null, 0..0));
+            *expression = inner;
+        }
+        walk_expression(self, expression)?;
+
+        if !matches!(expression, Expression::Logical(LogicalOperator::And, ..)) {
+            return ControlFlow::Continue(());
+        }
+
+        let span = expression.span();
+        let mut operands = Vec::new();
+        flatten_conjunction(
+            std::mem::replace(expression, Expression::Literal(Literal::Null, span.clone())),
+            &mut operands,
+        );
+
+        if operands.iter().all(is_reorderable) {
+            let mut keyed: Vec<_> = operands
+                .into_iter()
+                .map(|operand| (operand_key(&operand), operand))
+                .collect();
+            keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+            operands = keyed.into_iter().map(|(_, operand)| operand).collect();
+        }
+
+        let mut operands = operands.into_iter();
+        let first = operands.next().expect("a conjunction has operands");
+        let mut rebuilt = operands.fold(first, |left, right| {
+            let left_span = left.span();
+            let right_span = right.span();
+            let span = left_span.start.min(right_span.start)..left_span.end.max(right_span.end);
+            Expression::Logical(LogicalOperator::And, Box::new(left), Box::new(right), span)
+        });
+        if let Expression::Logical(_, _, _, rebuilt_span) = &mut rebuilt {
+            *rebuilt_span = span;
+        }
+        *expression = rebuilt;
+
+        ControlFlow::Continue(())
+    }
+}
+
+fn flatten_conjunction(expression: Expression, operands: &mut Vec<Expression>) {
+    match expression {
+        Expression::Logical(LogicalOperator::And, left, right, _) => {
+            flatten_conjunction(*left, operands);
+            flatten_conjunction(*right, operands);
+        }
+        other => operands.push(other),
+    }
+}
+
+/// Sort key of an `AND` operand; literal values do not affect the order.
+fn operand_key(operand: &Expression) -> SpanlessKey {
+    let mut masked = operand.clone();
+    let _ = LiteralMasker.visit_expression(&mut masked);
+    masked.spanless_key()
+}
+
+/// Whether an operand may be moved without observable effect.
+fn is_reorderable(operand: &Expression) -> bool {
+    struct Impure;
+
+    impl Visit for Impure {
+        type Break = ();
+
+        fn visit_expression(&mut self, expression: &Expression) -> ControlFlow<()> {
+            match expression {
+                Expression::SubqueryExpression(..) | Expression::Exists(_) => ControlFlow::Break(()),
+                Expression::FunctionCall(call) if matches!(call.name, FunctionName::Custom(_)) => {
+                    ControlFlow::Break(())
+                }
+                _ => visit::walk_expression(self, expression),
+            }
+        }
+    }
+
+    Impure.visit_expression(operand).is_continue()
+}
+
+/// Replaces extractable literals with an anonymous parameter.
+struct LiteralMasker;
+
+impl VisitMut for LiteralMasker {
+    type Break = ();
+
+    fn visit_expression(&mut self, expression: &mut Expression) -> ControlFlow<()> {
+        if extractable(expression).is_some() {
+            let span = expression.span();
+            *expression = Expression::ParameterReference(SmolStr::new_static("?"), span);
+            return ControlFlow::Continue(());
+        }
+        walk_expression(self, expression)
+    }
+}
+
+/// Finds the largest positional parameter already used by the query.
+#[derive(Default)]
+struct ParameterCollector {
+    max_positional: usize,
+}
+
+impl VisitMut for ParameterCollector {
+    type Break = ();
+
+    fn visit_expression(&mut self, expression: &mut Expression) -> ControlFlow<()> {
+        if let Expression::ParameterReference(name, _) = expression
+            && let Ok(position) = name.parse::<usize>()
+        {
+            self.max_positional = self.max_positional.max(position);
+        }
+        walk_expression(self, expression)
+    }
+}
+
+/// Lifts extractable literals into positional parameters.
+struct LiteralExtractor {
+    next: usize,
+    parameters: Vec<ExtractedLiteral>,
+}
+
+impl VisitMut for LiteralExtractor {
+    type Break = ();
+
+    fn visit_expression(&mut self, expression: &mut Expression) -> ControlFlow<()> {
+        if let Some(negated) = extractable(expression) {
+            let parameter = SmolStr::new(self.next.to_string());
+            self.next += 1;
+            let span = expression.span();
+            let replacement = Expression::ParameterReference(parameter.clone(), span.clone());
+            let mut lifted = std::mem::replace(expression, replacement);
+            if let Expression::Unary(_, operand, _) = lifted {
+                lifted = *operand;
+            }
+            let Expression::Literal(literal, _) = lifted else {
+                unreachable!("matched a literal above");
+            };
+            self.parameters.push(ExtractedLiteral {
+                parameter,
+                literal,
+                negated,
+                span,
+            });
+            return ControlFlow::Continue(());
+        }
+        walk_expression(self, expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn normalized(source: &str) -> NormalizedProgram {
+        normalize(&parse(source).ast.expect("source should parse"))
+    }
+
+    fn same_fingerprint(a: &str, b: &str) -> bool {
+        normalized(a).fingerprint == normalized(b).fingerprint
+    }
+
+    #[test]
+    fn layout_case_and_literal_values_do_not_matter() {
+        assert!(same_fingerprint(
+            "MATCH (n:Person) WHERE n.age > 30 RETURN n",
+            "match (n:Person)\n  where n.age > 45\n  return n",
+        ));
+        assert!(same_fingerprint(
+            "MATCH (n {name: 'Ann'}) RETURN n.x + 1",
+            "MATCH (n {name: 'Bob'}) RETURN n.x + 2",
+        ));
+        assert!(!same_fingerprint(
+            "MATCH (n:Person) RETURN n",
+            "MATCH (n:Company) RETURN n",
+        ));
+        assert!(!same_fingerprint(
+            "MATCH (n) WHERE n.ok = TRUE RETURN n",
+            "MATCH (n) WHERE n.ok = FALSE RETURN n",
+        ));
+    }
+
+    #[test]
+    fn literals_become_positional_parameters() {
+        let result = normalized("MATCH (n) WHERE n.name = 'Ann' AND n.born < DATE '2000-01-01' RETURN n");
+        let names: Vec<_> = result.parameters.iter().map(|p| p.parameter.as_str()).collect();
+        assert_eq!(names, ["1", "2"]);
+        assert_eq!(
+            result.parameters[1].info(),
+            LiteralInfo::Date("2000-01-01".into())
+        );
+        assert_eq!(result.parameters[0].literal, Literal::String("Ann".into()));
+
+        let existing = normalized("MATCH (n) WHERE n.a = $2 AND n.b = 7 RETURN n");
+        assert_eq!(existing.parameters[0].parameter, "3");
+    }
+
+    #[test]
+    fn conjunctions_are_sorted() {
+        assert!(same_fingerprint(
+            "MATCH (n) WHERE n.a = 1 AND n.b = 2 AND n.c = 3 RETURN n",
+            "MATCH (n) WHERE n.c = 30 AND (n.a = 10 AND n.b = 20) RETURN n",
+        ));
+        // Parameters follow the sorted order.
+        let result = normalized("MATCH (n) WHERE n.b = 2 AND n.a = 1 RETURN n");
+        let values: Vec<_> = result.parameters.iter().map(|p| p.literal.clone()).collect();
+        assert_eq!(values, [Literal::Integer("1".into()), Literal::Integer("2".into())]);
+
+        assert!(!same_fingerprint(
+            "MATCH (n) WHERE n.a = 1 OR n.b = 2 RETURN n",
+            "MATCH (n) WHERE n.b = 2 OR n.a = 1 RETURN n",
+        ));
+    }
+
+    #[test]
+    fn mutations_are_normalized() {
+        let result = normalized("INSERT (:Person {name: 'Ann', age: 3})");
+        assert_eq!(result.parameters.len(), 2);
+        assert!(same_fingerprint(
+            "MATCH (n) SET n.age = 4 RETURN n",
+            "MATCH (n) SET n.age = 5 RETURN n",
+        ));
+    }
+
+    #[test]
+    fn negative_numbers_become_one_parameter() {
+        assert!(same_fingerprint(
+            "MATCH (n) WHERE n.x > -1 RETURN n",
+            "MATCH (n) WHERE n.x > 1 RETURN n",
+        ));
+        assert!(same_fingerprint(
+            "MATCH (n) WHERE n.a = -2.5 AND n.b = 1 RETURN n",
+            "MATCH (n) WHERE n.b = -3 AND n.a = 4.5 RETURN n",
+        ));
+        let result = normalized("MATCH (n) WHERE n.x > -12 RETURN n");
+        let [parameter] = result.parameters.as_slice() else {
+            panic!("expected one parameter, got {:?}", result.parameters);
+        };
+        assert!(parameter.negated);
+        assert_eq!(parameter.literal, Literal::Integer("12".into()));
+        assert_eq!(parameter.span, 22..25);
+        assert!(!same_fingerprint(
+            "MATCH (n) WHERE n.x > -n.y RETURN n",
+            "MATCH (n) WHERE n.x > n.y RETURN n",
+        ));
+    }
+
+    #[test]
+    fn fingerprints_ignore_spans_but_not_text() {
+        let a = parse("MATCH (n) RETURN 'a 1..2'").ast.unwrap();
+        let b = parse("MATCH  (n)  RETURN  'a 1..2'").ast.unwrap();
+        let c = parse("MATCH (n) RETURN 'a 1..3'").ast.unwrap();
+        assert_eq!(Fingerprint::of(&a), Fingerprint::of(&b));
+        assert_ne!(Fingerprint::of(&a), Fingerprint::of(&c));
+        assert_eq!(Fingerprint(0xab).to_string().len(), 32);
+    }
+}
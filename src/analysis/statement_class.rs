@@ -0,0 +1,393 @@
+//! Statement classification for routing and transaction checks.
+//!
+//! [`StatementClass`] records what a top-level statement does: whether it
+//! only reads, modifies data, modifies the catalog, or controls the session
+//! or transaction, and which procedures and volatile callables it invokes.
+//! Inline `CALL { ... }` bodies and value subqueries are classified together
+//! with the statement that contains them, so a query whose inline call
+//! inserts data is data-modifying.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gql_parser::analysis::StatementClass;
+//!
+//! let class = StatementClass::from_ast(&program.statements[0]);
+//! if class.is_read_only() && !class.calls_volatile {
+//!     route_to_replica();
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use crate::ast::expression::FunctionName;
+use crate::ast::mutation::{
+    InsertEdgePattern, InsertElementPattern, LinearDataModifyingStatement,
+    PrimitiveDataModifyingStatement, SetItem, SimpleDataAccessingStatement,
+    SimpleDataModifyingStatement,
+};
+use crate::ast::procedure::{
+    BindingTableExpression, BindingVariableDefinition, CallProcedureStatement, GraphExpression,
+    ProcedureBody, ProcedureCall, Statement as ProcedureStatement,
+};
+use crate::ast::query::{ElementPropertySpecification, PrimitiveQueryStatement};
+use crate::ast::references::ProcedureReference;
+use crate::ast::visit::{Visit, VisitResult, walk_expression};
+use crate::ast::{Expression, Program, Statement};
+use crate::semantic::callable::{CallableKind, Volatility, resolve_builtin_signatures};
+use crate::semantic::metadata_provider::MetadataProvider;
+
+/// What a statement does, from least to most invasive among the
+/// data-accessing categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatementCategory {
+    /// Reads data without modifying it.
+    ReadOnly,
+    /// Inserts, updates or deletes data, directly or in a nested procedure.
+    DataModifying,
+    /// Creates or drops schemas, graphs, graph types or procedures.
+    CatalogModifying,
+    /// `SESSION SET`, `SESSION RESET` or `SESSION CLOSE`.
+    SessionControl,
+    /// `START TRANSACTION`, `COMMIT` or `ROLLBACK`.
+    TransactionControl,
+}
+
+/// Classification of a single top-level statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementClass {
+    /// What the statement does.
+    pub category: StatementCategory,
+    /// Names of the procedures called by name, including from nested bodies.
+    pub procedures: BTreeSet<SmolStr>,
+    /// Whether the statement calls a volatile function or procedure.
+    ///
+    /// Callables without a known signature are assumed to be volatile.
+    pub calls_volatile: bool,
+}
+
+impl StatementClass {
+    /// Classifies a statement using the built-in callable signatures only.
+    pub fn from_ast(statement: &Statement) -> Self {
+        Self::classify(statement, None)
+    }
+
+    /// Classifies a statement, looking up user-defined callables in
+    /// `provider` to decide whether they are volatile.
+    pub fn with_metadata(statement: &Statement, provider: &dyn MetadataProvider) -> Self {
+        Self::classify(statement, Some(provider))
+    }
+
+    /// Returns whether the statement only reads data.
+    pub fn is_read_only(&self) -> bool {
+        self.category == StatementCategory::ReadOnly
+    }
+
+    /// Returns whether the statement modifies data or the catalog.
+    pub fn is_modifying(&self) -> bool {
+        matches!(
+            self.category,
+            StatementCategory::DataModifying | StatementCategory::CatalogModifying
+        )
+    }
+
+    /// Returns whether the statement calls any procedure by name.
+    pub fn calls_procedures(&self) -> bool {
+        !self.procedures.is_empty()
+    }
+
+    fn classify(statement: &Statement, provider: Option<&dyn MetadataProvider>) -> Self {
+        let mut classifier = Classifier {
+            provider,
+            category: StatementCategory::ReadOnly,
+            procedures: BTreeSet::new(),
+            volatility: Volatility::Immutable,
+        };
+        match statement {
+            Statement::Query(query) => {
+                let _ = classifier.visit_query(&query.query);
+            }
+            Statement::Mutation(mutation) => {
+                classifier.raise(StatementCategory::DataModifying);
+                classifier.data_modifying(&mutation.statement);
+            }
+            Statement::Catalog(_) => classifier.raise(StatementCategory::CatalogModifying),
+            Statement::Session(_) => classifier.category = StatementCategory::SessionControl,
+            Statement::Transaction(_) => {
+                classifier.category = StatementCategory::TransactionControl
+            }
+            Statement::Empty(_) => {}
+        }
+        StatementClass {
+            category: classifier.category,
+            procedures: classifier.procedures,
+            calls_volatile: classifier.volatility == Volatility::Volatile,
+        }
+    }
+}
+
+/// Classifies every statement of a program, in order.
+pub fn classify_program(program: &Program) -> Vec<StatementClass> {
+    program.statements.iter().map(StatementClass::from_ast).collect()
+}
+
+struct Classifier<'p> {
+    provider: Option<&'p dyn MetadataProvider>,
+    category: StatementCategory,
+    procedures: BTreeSet<SmolStr>,
+    volatility: Volatility,
+}
+
+impl Classifier<'_> {
+    fn raise(&mut self, category: StatementCategory) {
+        self.category = self.category.max(category);
+    }
+
+    /// Looks up the volatility of a callable; unknown callables count as
+    /// volatile.
+    fn volatility_of(&self, name: &str, kinds: &[CallableKind]) -> Volatility {
+        let builtin = kinds
+            .iter()
+            .find_map(|kind| resolve_builtin_signatures(name, *kind))
+            .and_then(|signatures| signatures.iter().map(|sig| sig.volatility).max());
+        builtin
+            .or_else(|| {
+                self.provider
+                    .and_then(|provider| provider.lookup_callable(name))
+                    .map(|signature| signature.volatility)
+            })
+            .unwrap_or(Volatility::Volatile)
+    }
+
+    fn call(&mut self, call: &CallProcedureStatement) {
+        match &call.call {
+            ProcedureCall::Inline(inline) => self.body(&inline.specification.body),
+            ProcedureCall::Named(named) => {
+                let name = match &named.procedure {
+                    ProcedureReference::CatalogQualified { name, .. } => &name.name,
+                    ProcedureReference::ReferenceParameter { name, .. } => name,
+                };
+                let volatility =
+                    self.volatility_of(name, &[CallableKind::Procedure, CallableKind::Function]);
+                self.volatility = self.volatility.max(volatility);
+                self.procedures.insert(name.clone());
+                for argument in named.arguments.iter().flat_map(|list| &list.arguments) {
+                    let _ = self.visit_expression(&argument.expression);
+                }
+            }
+        }
+    }
+
+    fn body(&mut self, body: &ProcedureBody) {
+        for definition in body
+            .variable_definitions
+            .iter()
+            .flat_map(|block| &block.definitions)
+        {
+            let expression = match definition {
+                BindingVariableDefinition::Graph(graph) => {
+                    match graph.initializer.as_ref().map(|init| &init.expression) {
+                        Some(GraphExpression::Expression(expression)) => Some(&**expression),
+                        _ => None,
+                    }
+                }
+                BindingVariableDefinition::BindingTable(table) => {
+                    match table.initializer.as_ref().map(|init| &init.expression) {
+                        Some(BindingTableExpression::Expression(expression)) => {
+                            Some(&**expression)
+                        }
+                        _ => None,
+                    }
+                }
+                BindingVariableDefinition::Value(value) => {
+                    value.initializer.as_ref().map(|init| &init.expression)
+                }
+            };
+            if let Some(expression) = expression {
+                let _ = self.visit_expression(expression);
+            }
+        }
+        let block = &body.statements;
+        let statements = block
+            .statements
+            .iter()
+            .chain(block.next_statements.iter().map(|next| &*next.statement));
+        for statement in statements {
+            match statement {
+                ProcedureStatement::CompositeQuery(query) => {
+                    let _ = self.visit_query(query);
+                }
+                ProcedureStatement::LinearCatalogModifying(_) => {
+                    self.raise(StatementCategory::CatalogModifying)
+                }
+                ProcedureStatement::LinearDataModifying(statement) => {
+                    self.raise(StatementCategory::DataModifying);
+                    self.data_modifying(statement);
+                }
+            }
+        }
+    }
+
+    fn data_modifying(&mut self, statement: &LinearDataModifyingStatement) {
+        for step in &statement.statements {
+            match step {
+                SimpleDataAccessingStatement::Query(query) => {
+                    let _ = self.visit_primitive_query_statement(query);
+                }
+                SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Call(
+                    call,
+                )) => self.call(&call.call),
+                SimpleDataAccessingStatement::Modifying(
+                    SimpleDataModifyingStatement::Primitive(primitive),
+                ) => self.primitive(primitive),
+            }
+        }
+        if let Some(result) = &statement.primitive_result_statement {
+            let _ = self.visit_primitive_result_statement(result);
+        }
+    }
+
+    fn primitive(&mut self, statement: &PrimitiveDataModifyingStatement) {
+        match statement {
+            PrimitiveDataModifyingStatement::Insert(insert) => {
+                for element in insert.pattern.paths.iter().flat_map(|path| &path.elements) {
+                    let filler = match element {
+                        InsertElementPattern::Node(node) => node.filler.as_ref(),
+                        InsertElementPattern::Edge(InsertEdgePattern::PointingLeft(edge)) => {
+                            edge.filler.as_ref()
+                        }
+                        InsertElementPattern::Edge(InsertEdgePattern::PointingRight(edge)) => {
+                            edge.filler.as_ref()
+                        }
+                        InsertElementPattern::Edge(InsertEdgePattern::Undirected(edge)) => {
+                            edge.filler.as_ref()
+                        }
+                    };
+                    if let Some(properties) = filler.and_then(|filler| filler.properties.as_ref())
+                    {
+                        self.properties(properties);
+                    }
+                }
+            }
+            PrimitiveDataModifyingStatement::Set(set) => {
+                for item in &set.items.items {
+                    match item {
+                        SetItem::Property(item) => {
+                            let _ = self.visit_expression(&item.value);
+                        }
+                        SetItem::AllProperties(item) => self.properties(&item.properties),
+                        SetItem::Label(_) => {}
+                    }
+                }
+            }
+            PrimitiveDataModifyingStatement::Remove(_) => {}
+            PrimitiveDataModifyingStatement::Delete(delete) => {
+                for item in &delete.items.items {
+                    let _ = self.visit_expression(&item.expression);
+                }
+            }
+        }
+    }
+
+    fn properties(&mut self, properties: &ElementPropertySpecification) {
+        for pair in &properties.properties {
+            let _ = self.visit_expression(&pair.value);
+        }
+    }
+}
+
+impl Visit for Classifier<'_> {
+    type Break = ();
+
+    fn visit_primitive_query_statement(
+        &mut self,
+        statement: &PrimitiveQueryStatement,
+    ) -> VisitResult<()> {
+        if let PrimitiveQueryStatement::Call(call) = statement {
+            self.call(call);
+            return ControlFlow::Continue(());
+        }
+        crate::ast::visit::walk_primitive_query_statement(self, statement)
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> VisitResult<()> {
+        match expression {
+            Expression::FunctionCall(call) => {
+                let volatility = match &call.name {
+                    FunctionName::Custom(name) => {
+                        self.volatility_of(name, &[CallableKind::Function])
+                    }
+                    builtin => self
+                        .volatility_of(builtin.gql_name(), &[CallableKind::Function])
+                        // Built-ins missing from the signature table are
+                        // still part of the language, not user code.
+                        .min(Volatility::Stable),
+                };
+                self.volatility = self.volatility.max(volatility);
+            }
+            Expression::SubqueryExpression(subquery, _) => self.body(&subquery.body),
+            _ => {}
+        }
+        walk_expression(self, expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::statement_class::{StatementCategory, classify_program};
+    use crate::parse;
+
+    fn categories(source: &str) -> Vec<StatementCategory> {
+        let program = parse(source).ast.expect("expected AST");
+        classify_program(&program)
+            .into_iter()
+            .map(|class| class.category)
+            .collect()
+    }
+
+    #[test]
+    fn classifies_top_level_statements() {
+        assert_eq!(
+            categories("START TRANSACTION; MATCH (n) RETURN n; INSERT (:A); COMMIT"),
+            vec![
+                StatementCategory::TransactionControl,
+                StatementCategory::ReadOnly,
+                StatementCategory::DataModifying,
+                StatementCategory::TransactionControl,
+            ]
+        );
+        assert_eq!(
+            categories("CREATE GRAPH g ANY"),
+            vec![StatementCategory::CatalogModifying]
+        );
+        assert_eq!(
+            categories("SESSION SET SCHEMA /s"),
+            vec![StatementCategory::SessionControl]
+        );
+    }
+
+    #[test]
+    fn procedure_calls_are_recorded_from_nested_bodies() {
+        let program = parse("CALL { CALL my_proc(1) RETURN 1 } RETURN 1")
+            .ast
+            .expect("expected AST");
+        let class = &classify_program(&program)[0];
+        assert_eq!(class.category, StatementCategory::ReadOnly);
+        assert!(class.procedures.contains("my_proc"));
+        assert!(class.calls_volatile);
+    }
+
+    #[test]
+    fn unknown_callables_are_volatile() {
+        let program = parse("MATCH (n) RETURN my_fn(n), upper(n.name)")
+            .ast
+            .expect("expected AST");
+        assert!(classify_program(&program)[0].calls_volatile);
+
+        let program = parse("MATCH (n) RETURN upper(n.name)").ast.expect("expected AST");
+        assert!(!classify_program(&program)[0].calls_volatile);
+    }
+}
@@ -269,7 +269,7 @@ pub enum Predicate {
     /// IS [NOT] DIRECTED predicate
     IsDirected(Box<Expression>, bool, Span),
 
-    /// IS [NOT] LABELED [:label] predicate
+    /// `IS [NOT] LABELED [label_expression]` or `element : label_expression` predicate
     IsLabeled(Box<Expression>, Option<LabelExpression>, bool, Span),
 
     /// IS [NOT] TRUE/FALSE/UNKNOWN predicate
@@ -318,12 +318,8 @@ pub enum TruthValue {
     Unknown,
 }
 
-/// Label expression (placeholder for Sprint 8)
-#[derive(Debug, Clone, PartialEq)]
-pub struct LabelExpression {
-    pub label: SmolStr,
-    pub span: Span,
-}
+/// Label expression used by labeled predicates; shared with graph patterns.
+pub use crate::ast::query::LabelExpression;
 
 // ============================================================================
 // EXISTS Expression
@@ -445,6 +441,71 @@ pub enum FunctionName {
     Custom(SmolStr),
 }
 
+impl FunctionName {
+    /// Returns the name as written in GQL, such as `CHAR_LENGTH`; user-defined
+    /// functions keep their own spelling.
+    pub fn gql_name(&self) -> &str {
+        match self {
+            Self::Abs => "ABS",
+            Self::Mod => "MOD",
+            Self::Floor => "FLOOR",
+            Self::Ceil => "CEIL",
+            Self::Sqrt => "SQRT",
+            Self::Power => "POWER",
+            Self::Exp => "EXP",
+            Self::Ln => "LN",
+            Self::Log => "LOG",
+            Self::Log10 => "LOG10",
+            Self::Sin => "SIN",
+            Self::Cos => "COS",
+            Self::Tan => "TAN",
+            Self::Cot => "COT",
+            Self::Sinh => "SINH",
+            Self::Cosh => "COSH",
+            Self::Tanh => "TANH",
+            Self::Asin => "ASIN",
+            Self::Acos => "ACOS",
+            Self::Atan => "ATAN",
+            Self::Atan2 => "ATAN2",
+            Self::Degrees => "DEGREES",
+            Self::Radians => "RADIANS",
+            Self::Upper => "UPPER",
+            Self::Lower => "LOWER",
+            Self::Trim(TrimSpecification::Both) => "TRIM",
+            Self::Trim(TrimSpecification::Leading) | Self::LTrim => "LTRIM",
+            Self::Trim(TrimSpecification::Trailing) | Self::RTrim => "RTRIM",
+            Self::BTrim => "BTRIM",
+            Self::Left => "LEFT",
+            Self::Right => "RIGHT",
+            Self::Normalize => "NORMALIZE",
+            Self::CharLength => "CHAR_LENGTH",
+            Self::ByteLength => "BYTE_LENGTH",
+            Self::Substring => "SUBSTRING",
+            Self::CurrentDate => "CURRENT_DATE",
+            Self::CurrentTime => "CURRENT_TIME",
+            Self::CurrentTimestamp => "CURRENT_TIMESTAMP",
+            Self::Date => "DATE",
+            Self::Time => "TIME",
+            Self::Datetime => "DATETIME",
+            Self::ZonedTime => "ZONED_TIME",
+            Self::ZonedDatetime => "ZONED_DATETIME",
+            Self::LocalTime => "LOCAL_TIME",
+            Self::LocalDatetime => "LOCAL_DATETIME",
+            Self::Duration => "DURATION",
+            Self::DurationBetween => "DURATION_BETWEEN",
+            Self::TrimList => "TRIM_LIST",
+            Self::Elements => "ELEMENTS",
+            Self::Cardinality => "CARDINALITY",
+            Self::Size => "SIZE",
+            Self::PathLength => "PATH_LENGTH",
+            Self::ElementId => "ELEMENT_ID",
+            Self::Coalesce => "COALESCE",
+            Self::NullIf => "NULLIF",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
 /// TRIM specification for TRIM functions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrimSpecification {
@@ -44,7 +44,7 @@
 //! - `propertyTypesSpecification` (Line 1691)
 //! - `labelSetPhrase` (Line 1679)
 
-use crate::ast::{Span, ValueType};
+use crate::ast::{Expression, Span, ValueType};
 use smol_str::SmolStr;
 
 // ============================================================================
@@ -131,28 +131,29 @@ pub struct InheritedTypeReference {
 
 /// Graph-type constraint clause.
 ///
-/// Constraints are captured in parsed form and preserve any raw argument payload
-/// for downstream validation/normalization.
+/// Key, uniqueness and mandatory constraints name properties of the enclosing
+/// node or edge type; CHECK holds a condition over them. Constraints the
+/// parser does not know keep their raw argument payload.
 #[derive(Debug, Clone, PartialEq)]
 pub enum GraphTypeConstraint {
-    /// `... CONSTRAINT KEY (...)`
+    /// `... CONSTRAINT KEY (a, b)` or `... KEY a`
     Key {
-        arguments: Vec<GraphTypeConstraintArgument>,
+        properties: Vec<PropertyName>,
         span: Span,
     },
-    /// `... CONSTRAINT UNIQUE (...)`
+    /// `... CONSTRAINT UNIQUE (a, b)`
     Unique {
-        arguments: Vec<GraphTypeConstraintArgument>,
+        properties: Vec<PropertyName>,
         span: Span,
     },
-    /// `... CONSTRAINT MANDATORY (...)`
+    /// `... CONSTRAINT MANDATORY (a, b)`
     Mandatory {
-        arguments: Vec<GraphTypeConstraintArgument>,
+        properties: Vec<PropertyName>,
         span: Span,
     },
-    /// `... CONSTRAINT CHECK (...)`
+    /// `... CONSTRAINT CHECK (<condition>)`
     Check {
-        arguments: Vec<GraphTypeConstraintArgument>,
+        condition: Box<Expression>,
         span: Span,
     },
     /// `... CONSTRAINT <custom>(...)`
@@ -174,9 +175,30 @@ impl GraphTypeConstraint {
             | GraphTypeConstraint::Custom { span, .. } => span.clone(),
         }
     }
+
+    /// Returns the constraint keyword, such as `KEY` or `CHECK`.
+    pub fn kind_name(&self) -> &str {
+        match self {
+            GraphTypeConstraint::Key { .. } => "KEY",
+            GraphTypeConstraint::Unique { .. } => "UNIQUE",
+            GraphTypeConstraint::Mandatory { .. } => "MANDATORY",
+            GraphTypeConstraint::Check { .. } => "CHECK",
+            GraphTypeConstraint::Custom { name, .. } => name,
+        }
+    }
+
+    /// Returns the properties named by a KEY, UNIQUE or MANDATORY constraint.
+    pub fn properties(&self) -> &[PropertyName] {
+        match self {
+            GraphTypeConstraint::Key { properties, .. }
+            | GraphTypeConstraint::Unique { properties, .. }
+            | GraphTypeConstraint::Mandatory { properties, .. } => properties,
+            GraphTypeConstraint::Check { .. } | GraphTypeConstraint::Custom { .. } => &[],
+        }
+    }
 }
 
-/// Raw argument payload for a graph-type constraint.
+/// Raw argument payload for a custom graph-type constraint.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GraphTypeConstraintArgument {
     /// Normalized token text for the argument segment.
@@ -203,6 +225,8 @@ pub struct GraphTypeConstraintArgument {
 pub struct NodeTypeSpecification {
     /// Whether `ABSTRACT` modifier is present.
     pub is_abstract: bool,
+    /// Node type name (`Person` in `NODE TYPE Person`), if given.
+    pub name: Option<SmolStr>,
     /// Optional inheritance clause.
     pub inheritance: Option<TypeInheritanceClause>,
     /// The node type pattern
@@ -343,6 +367,8 @@ pub struct NodeTypeImpliedContent {
 pub struct EdgeTypeSpecification {
     /// Whether `ABSTRACT` modifier is present.
     pub is_abstract: bool,
+    /// Edge type name (`KNOWS` in `DIRECTED EDGE TYPE KNOWS`), if given.
+    pub name: Option<SmolStr>,
     /// Optional inheritance clause.
     pub inheritance: Option<TypeInheritanceClause>,
     /// The edge type pattern
@@ -602,11 +628,15 @@ pub struct PropertyTypeList {
 
 /// Individual property type definition.
 ///
-/// Syntax: `property_name :: value_type [NOT NULL]`
+/// Syntax: `property_name :: value_type [NOT NULL] [DEFAULT value] [CHECK (condition)]`
+///
+/// `DEFAULT` and `CHECK` are implementation-defined extensions; the condition
+/// refers to properties by bare name.
 ///
 /// Example:
 /// ```gql
 /// name :: STRING NOT NULL
+/// age :: INT DEFAULT 0 CHECK (age >= 0)
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct PropertyType {
@@ -616,6 +646,10 @@ pub struct PropertyType {
     pub value_type: PropertyValueType,
     /// Whether NOT NULL constraint is present
     pub not_null: bool,
+    /// Value given to the property when none is set
+    pub default: Option<Box<Expression>>,
+    /// Condition every value of the property must satisfy
+    pub check: Option<Box<Expression>>,
     /// Source span
     pub span: Span,
 }
@@ -0,0 +1,1006 @@
+//! Decoded literal values.
+//!
+//! The parser keeps numeric and temporal literals as source text so that no
+//! precision is lost before a consumer decides how to represent them.
+//! [`Literal::value`] decodes that text into typed values and reports
+//! malformed or out-of-range literals as [`LiteralError`]s.
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::ast::literal::{DateValue, IntegerValue, LiteralValue};
+//! use gql_parser::ast::Literal;
+//!
+//! let date = Literal::Date("2024-02-29".into()).value().unwrap();
+//! assert_eq!(
+//!     date,
+//!     LiteralValue::Date(DateValue { year: 2024, month: 2, day: 29 })
+//! );
+//!
+//! let int = Literal::Integer("0xFF".into()).value().unwrap();
+//! assert_eq!(int, LiteralValue::Integer(IntegerValue::I64(255)));
+//!
+//! assert!(Literal::Date("2024-13-45".into()).value().is_err());
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use smol_str::SmolStr;
+
+use super::expression::{BooleanValue, Literal};
+
+/// A typed literal value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralValue {
+    /// TRUE, FALSE or UNKNOWN.
+    Boolean(BooleanValue),
+    /// NULL.
+    Null,
+    /// Exact integer.
+    Integer(IntegerValue),
+    /// Exact decimal without an exponent, e.g. `12.50`.
+    Decimal(DecimalValue),
+    /// Approximate numeric with an exponent, e.g. `1.5e3`.
+    Float(f64),
+    /// Character string.
+    String(SmolStr),
+    /// Byte string.
+    Bytes(Vec<u8>),
+    /// DATE literal.
+    Date(DateValue),
+    /// TIME literal, with or without a zone offset.
+    Time(TimeValue),
+    /// DATETIME / TIMESTAMP literal, with or without a zone offset.
+    Datetime(DatetimeValue),
+    /// DURATION literal.
+    Duration(DurationValue),
+}
+
+/// An exact integer, stored in the narrowest representation that holds it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerValue {
+    /// Fits in `i64`.
+    I64(i64),
+    /// Larger than `i64::MAX` but fits in `u64`.
+    U64(u64),
+    /// Larger than `u64::MAX` but fits in `i128`.
+    I128(i128),
+}
+
+impl IntegerValue {
+    /// Returns the value widened to `i128`.
+    pub fn as_i128(self) -> i128 {
+        match self {
+            IntegerValue::I64(v) => v as i128,
+            IntegerValue::U64(v) => v as i128,
+            IntegerValue::I128(v) => v,
+        }
+    }
+
+    /// Returns the value as `i64` if it fits.
+    pub fn as_i64(self) -> Option<i64> {
+        i64::try_from(self.as_i128()).ok()
+    }
+
+    fn from_magnitude(magnitude: u128) -> Option<Self> {
+        if let Ok(v) = i64::try_from(magnitude) {
+            Some(IntegerValue::I64(v))
+        } else if let Ok(v) = u64::try_from(magnitude) {
+            Some(IntegerValue::U64(v))
+        } else {
+            i128::try_from(magnitude).ok().map(IntegerValue::I128)
+        }
+    }
+}
+
+impl fmt::Display for IntegerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_i128())
+    }
+}
+
+/// An exact decimal: `unscaled * 10^-scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalValue {
+    /// All significant digits as an integer.
+    pub unscaled: i128,
+    /// Number of digits after the decimal point.
+    pub scale: u32,
+}
+
+impl DecimalValue {
+    /// Total number of digits, including those after the decimal point.
+    pub fn precision(&self) -> u32 {
+        let digits = self.unscaled.unsigned_abs().checked_ilog10().map_or(1, |d| d + 1);
+        digits.max(self.scale)
+    }
+
+    /// Converts to the nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        self.unscaled as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+impl fmt::Display for DecimalValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.unscaled.unsigned_abs().to_string();
+        let sign = if self.unscaled < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let padded = format!("{digits:0>width$}", width = scale + 1);
+        let (int, frac) = padded.split_at(padded.len() - scale);
+        write!(f, "{sign}{int}.{frac}")
+    }
+}
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateValue {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// A time of day with an optional zone offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeValue {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    /// Offset from UTC; `None` for a local time.
+    pub offset: Option<ZoneOffset>,
+}
+
+/// A zone offset from UTC in minutes (`Z` is zero).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ZoneOffset {
+    pub minutes: i16,
+}
+
+/// A date and time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatetimeValue {
+    pub date: DateValue,
+    pub time: TimeValue,
+}
+
+/// An ISO 8601 duration, e.g. `P1Y2M10DT2H30M1.5S`.
+///
+/// Components are kept as written; no normalisation between units is
+/// performed since months and days have no fixed length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DurationValue {
+    /// Whether the duration was written with a leading `-`.
+    pub negative: bool,
+    pub years: u64,
+    pub months: u64,
+    pub weeks: u64,
+    pub days: u64,
+    pub hours: u64,
+    pub minutes: u64,
+    pub seconds: u64,
+    pub nanoseconds: u32,
+}
+
+impl DurationValue {
+    /// Returns true if only year and month components are present.
+    pub fn is_year_to_month(&self) -> bool {
+        self.weeks == 0
+            && self.days == 0
+            && self.hours == 0
+            && self.minutes == 0
+            && self.seconds == 0
+            && self.nanoseconds == 0
+    }
+
+    /// Returns true if no year or month components are present.
+    pub fn is_day_to_second(&self) -> bool {
+        self.years == 0 && self.months == 0
+    }
+}
+
+impl fmt::Display for DateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}-{:02}-{:02}", -self.year, self.month, self.day)
+        } else {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.minutes == 0 {
+            return f.write_str("Z");
+        }
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.unsigned_abs();
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl fmt::Display for TimeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        write_fraction(f, self.nanosecond)?;
+        if let Some(offset) = self.offset {
+            write!(f, "{offset}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DatetimeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+impl fmt::Display for DurationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+        for (value, unit) in [
+            (self.years, 'Y'),
+            (self.months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ] {
+            if value != 0 {
+                write!(f, "{value}{unit}")?;
+            }
+        }
+        let has_seconds = self.seconds != 0 || self.nanoseconds != 0;
+        if self.hours != 0 || self.minutes != 0 || has_seconds {
+            f.write_str("T")?;
+            if self.hours != 0 {
+                write!(f, "{}H", self.hours)?;
+            }
+            if self.minutes != 0 {
+                write!(f, "{}M", self.minutes)?;
+            }
+            if has_seconds {
+                write!(f, "{}", self.seconds)?;
+                write_fraction(f, self.nanoseconds)?;
+                f.write_str("S")?;
+            }
+        } else if *self == (DurationValue { negative: self.negative, ..Default::default() }) {
+            // An all-zero duration still needs one component.
+            f.write_str("T0S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `.fffffffff` with trailing zeros removed, or nothing for zero.
+fn write_fraction(f: &mut fmt::Formatter<'_>, nanos: u32) -> fmt::Result {
+    if nanos == 0 {
+        return Ok(());
+    }
+    let digits = format!("{nanos:09}");
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+/// Kind of literal decoding failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralErrorKind {
+    /// The text does not follow the literal's syntax.
+    Malformed,
+    /// The text is well formed but a component is out of range
+    /// (e.g. month 13, or a float that overflows to infinity).
+    OutOfRange,
+    /// An integer does not fit in any supported integer type.
+    Overflow,
+    /// List and record literals have no scalar value.
+    NotScalar,
+}
+
+/// Error produced by [`Literal::value`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralError {
+    pub kind: LiteralErrorKind,
+    pub message: String,
+    /// Byte range of the offending part within the literal's stored text,
+    /// when it can be pinned down more precisely than the whole literal.
+    pub range: Option<Range<usize>>,
+}
+
+impl LiteralError {
+    fn new(kind: LiteralErrorKind, message: impl Into<String>, range: Option<Range<usize>>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            range,
+        }
+    }
+
+    fn malformed(message: impl Into<String>, range: Range<usize>) -> Self {
+        Self::new(LiteralErrorKind::Malformed, message, Some(range))
+    }
+
+    fn out_of_range(message: impl Into<String>, range: Range<usize>) -> Self {
+        Self::new(LiteralErrorKind::OutOfRange, message, Some(range))
+    }
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+impl Literal {
+    /// Decodes this literal into a typed value.
+    ///
+    /// Integer, float, byte string and temporal literals are parsed from their
+    /// source text; errors carry the byte range of the offending component
+    /// within that text.
+    pub fn value(&self) -> Result<LiteralValue, LiteralError> {
+        match self {
+            Literal::Boolean(b) => Ok(LiteralValue::Boolean(*b)),
+            Literal::Null => Ok(LiteralValue::Null),
+            Literal::Integer(text) => decode_integer(text).map(LiteralValue::Integer),
+            Literal::Float(text) => decode_float(text),
+            Literal::String(text) => Ok(LiteralValue::String(text.clone())),
+            Literal::ByteString(text) => decode_bytes(text).map(LiteralValue::Bytes),
+            Literal::Date(text) => {
+                let mut cursor = Cursor::new(text);
+                let date = cursor.date()?;
+                cursor.finish("date")?;
+                Ok(LiteralValue::Date(date))
+            }
+            Literal::Time(text) => {
+                let mut cursor = Cursor::new(text);
+                let time = cursor.time()?;
+                cursor.finish("time")?;
+                Ok(LiteralValue::Time(time))
+            }
+            Literal::Datetime(text) => {
+                let mut cursor = Cursor::new(text);
+                let date = cursor.date()?;
+                if !cursor.eat_any(&['T', 't', ' ']) {
+                    return Err(cursor.error("expected 'T' between date and time"));
+                }
+                let time = cursor.time()?;
+                cursor.finish("datetime")?;
+                Ok(LiteralValue::Datetime(DatetimeValue { date, time }))
+            }
+            Literal::Duration(text) => decode_duration(text).map(LiteralValue::Duration),
+            Literal::List(_) | Literal::Record(_) => Err(LiteralError::new(
+                LiteralErrorKind::NotScalar,
+                "list and record literals have no scalar value",
+                None,
+            )),
+        }
+    }
+}
+
+fn decode_integer(text: &str) -> Result<IntegerValue, LiteralError> {
+    let (radix, digits_start) = match text.get(..2) {
+        Some("0x" | "0X") => (16, 2),
+        Some("0o" | "0O") => (8, 2),
+        Some("0b" | "0B") => (2, 2),
+        _ => (10, 0),
+    };
+
+    let mut magnitude: u128 = 0;
+    let mut any_digit = false;
+    for (idx, ch) in text.char_indices().skip(digits_start) {
+        if ch == '_' {
+            continue;
+        }
+        let Some(digit) = ch.to_digit(radix) else {
+            return Err(LiteralError::malformed(
+                format!("invalid digit '{ch}' in integer literal"),
+                idx..idx + ch.len_utf8(),
+            ));
+        };
+        any_digit = true;
+        magnitude = magnitude
+            .checked_mul(radix as u128)
+            .and_then(|m| m.checked_add(digit as u128))
+            .ok_or_else(|| overflow(text))?;
+    }
+
+    if !any_digit {
+        return Err(LiteralError::malformed(
+            "integer literal has no digits",
+            0..text.len(),
+        ));
+    }
+
+    IntegerValue::from_magnitude(magnitude).ok_or_else(|| overflow(text))
+}
+
+fn overflow(text: &str) -> LiteralError {
+    LiteralError::new(
+        LiteralErrorKind::Overflow,
+        format!("integer literal '{text}' does not fit in any supported integer type"),
+        Some(0..text.len()),
+    )
+}
+
+fn decode_float(text: &str) -> Result<LiteralValue, LiteralError> {
+    let cleaned: String = text.chars().filter(|&ch| ch != '_').collect();
+
+    if !cleaned.contains(['e', 'E']) {
+        return decode_decimal(text, &cleaned).map(LiteralValue::Decimal);
+    }
+
+    let value: f64 = cleaned.parse().map_err(|_| {
+        LiteralError::malformed(format!("malformed numeric literal '{text}'"), 0..text.len())
+    })?;
+    if !value.is_finite() {
+        return Err(LiteralError::out_of_range(
+            format!("numeric literal '{text}' is out of range for a floating-point value"),
+            0..text.len(),
+        ));
+    }
+    Ok(LiteralValue::Float(value))
+}
+
+fn decode_decimal(text: &str, cleaned: &str) -> Result<DecimalValue, LiteralError> {
+    let (int_part, frac_part) = cleaned.split_once('.').unwrap_or((cleaned, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(LiteralError::malformed(
+            format!("malformed numeric literal '{text}'"),
+            0..text.len(),
+        ));
+    }
+
+    let mut unscaled: i128 = 0;
+    for ch in int_part.chars().chain(frac_part.chars()) {
+        let Some(digit) = ch.to_digit(10) else {
+            return Err(LiteralError::malformed(
+                format!("malformed numeric literal '{text}'"),
+                0..text.len(),
+            ));
+        };
+        unscaled = unscaled
+            .checked_mul(10)
+            .and_then(|u| u.checked_add(digit as i128))
+            .ok_or_else(|| {
+                LiteralError::out_of_range(
+                    format!("decimal literal '{text}' has too many digits"),
+                    0..text.len(),
+                )
+            })?;
+    }
+
+    Ok(DecimalValue {
+        unscaled,
+        scale: frac_part.len() as u32,
+    })
+}
+
+fn decode_bytes(text: &str) -> Result<Vec<u8>, LiteralError> {
+    let bytes = text.as_bytes();
+    if let Some(idx) = bytes.iter().position(|b| !b.is_ascii_hexdigit()) {
+        let ch = text[idx..].chars().next().unwrap_or('\0');
+        return Err(LiteralError::malformed(
+            format!("invalid hex digit '{ch}' in byte string literal"),
+            idx..idx + ch.len_utf8(),
+        ));
+    }
+    if bytes.len() % 2 != 0 {
+        return Err(LiteralError::malformed(
+            "byte string literal has an odd number of hex digits",
+            0..text.len(),
+        ));
+    }
+
+    Ok(bytes
+        .chunks(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16).unwrap_or(0);
+            let lo = (pair[1] as char).to_digit(16).unwrap_or(0);
+            (hi * 16 + lo) as u8
+        })
+        .collect())
+}
+
+fn decode_duration(text: &str) -> Result<DurationValue, LiteralError> {
+    let mut cursor = Cursor::new(text);
+    let mut duration = DurationValue {
+        negative: cursor.eat('-'),
+        ..DurationValue::default()
+    };
+    if !duration.negative {
+        cursor.eat('+');
+    }
+    if !cursor.eat_any(&['P', 'p']) {
+        return Err(cursor.error("duration must start with 'P'"));
+    }
+
+    // Designators in the order they may appear, before and after 'T'.
+    const DATE_UNITS: [char; 4] = ['Y', 'M', 'W', 'D'];
+    const TIME_UNITS: [char; 3] = ['H', 'M', 'S'];
+
+    let mut in_time = false;
+    let mut next_unit = 0;
+    let mut any_component = false;
+    let mut time_component = false;
+
+    while !cursor.at_end() {
+        if !in_time && cursor.eat_any(&['T', 't']) {
+            in_time = true;
+            next_unit = 0;
+            continue;
+        }
+
+        let start = cursor.pos;
+        let number = cursor.number(u64::MAX).map_err(|error| match error.kind {
+            LiteralErrorKind::OutOfRange => LiteralError::out_of_range(
+                "duration component is out of range",
+                error.range.unwrap_or(start..cursor.pos),
+            ),
+            _ => cursor.error("expected a number or designator in duration"),
+        })?;
+        let fraction = if cursor.peek() == Some('.') {
+            cursor.pos += 1;
+            Some(cursor.fraction()?)
+        } else {
+            None
+        };
+
+        let unit_start = cursor.pos;
+        let Some(unit) = cursor.bump().map(|c| c.to_ascii_uppercase()) else {
+            return Err(cursor.error("missing designator after number in duration"));
+        };
+        let units: &[char] = if in_time { &TIME_UNITS } else { &DATE_UNITS };
+        let Some(offset) = units[next_unit..].iter().position(|&u| u == unit) else {
+            let message = if units.contains(&unit) {
+                format!("duration designator '{unit}' is out of order")
+            } else {
+                format!("invalid duration designator '{unit}'")
+            };
+            return Err(LiteralError::malformed(message, unit_start..cursor.pos));
+        };
+        let index = next_unit + offset;
+        next_unit = index + 1;
+
+        if fraction.is_some() && !(in_time && unit == 'S') {
+            return Err(LiteralError::malformed(
+                "only seconds may have a fractional part in a duration",
+                start..cursor.pos,
+            ));
+        }
+
+        let slot = match (in_time, unit) {
+            (false, 'Y') => &mut duration.years,
+            (false, 'M') => &mut duration.months,
+            (false, 'W') => &mut duration.weeks,
+            (false, _) => &mut duration.days,
+            (true, 'H') => &mut duration.hours,
+            (true, 'M') => &mut duration.minutes,
+            (true, _) => &mut duration.seconds,
+        };
+        *slot = number;
+        if let Some(nanos) = fraction {
+            duration.nanoseconds = nanos;
+        }
+        any_component = true;
+        time_component |= in_time;
+    }
+
+    if in_time && !time_component {
+        return Err(cursor.error("expected a time component after 'T'"));
+    }
+    if !any_component {
+        return Err(LiteralError::malformed(
+            "duration has no components",
+            0..text.len(),
+        ));
+    }
+    Ok(duration)
+}
+
+/// Minimal scanner over literal text that tracks byte positions for errors.
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.eat_any(&[expected])
+    }
+
+    fn eat_any(&mut self, expected: &[char]) -> bool {
+        match self.peek() {
+            Some(ch) if expected.contains(&ch) => {
+                self.pos += ch.len_utf8();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Error pointing at the current character (or the end of the text).
+    fn error(&self, message: impl Into<String>) -> LiteralError {
+        let end = self.peek().map_or(self.pos, |ch| self.pos + ch.len_utf8());
+        LiteralError::malformed(message, self.pos..end)
+    }
+
+    fn finish(&self, what: &str) -> Result<(), LiteralError> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(LiteralError::malformed(
+                format!("unexpected trailing characters in {what} literal"),
+                self.pos..self.text.len(),
+            ))
+        }
+    }
+
+    /// Reads a run of ASCII digits as a number no larger than `max`.
+    fn number(&mut self, max: u64) -> Result<u64, LiteralError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.error("expected digits"));
+        }
+        self.text[start..self.pos]
+            .parse::<u64>()
+            .ok()
+            .filter(|&n| n <= max)
+            .ok_or_else(|| LiteralError::out_of_range("number is too large", start..self.pos))
+    }
+
+    /// Reads exactly `count` digits.
+    fn fixed(&mut self, count: usize, what: &str) -> Result<(u32, Range<usize>), LiteralError> {
+        let start = self.pos;
+        let digits = self.text.get(start..start + count).unwrap_or("");
+        if digits.len() != count || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(LiteralError::malformed(
+                format!("expected {count}-digit {what}"),
+                start..(start + count).min(self.text.len()).max(start),
+            ));
+        }
+        self.pos += count;
+        Ok((digits.parse().unwrap_or(0), start..self.pos))
+    }
+
+    /// Reads fractional-second digits (after the '.') as nanoseconds.
+    fn fraction(&mut self) -> Result<u32, LiteralError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits = &self.text[start..self.pos];
+        if digits.is_empty() {
+            return Err(self.error("expected digits after '.'"));
+        }
+        if digits.len() > 9 {
+            return Err(LiteralError::out_of_range(
+                "fractional seconds are limited to nanosecond precision",
+                start..self.pos,
+            ));
+        }
+        let value: u32 = digits.parse().unwrap_or(0);
+        Ok(value * 10u32.pow(9 - digits.len() as u32))
+    }
+
+    fn date(&mut self) -> Result<DateValue, LiteralError> {
+        let negative = self.eat('-');
+        if !negative {
+            self.eat('+');
+        }
+        let year_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let year_digits = &self.text[year_start..self.pos];
+        if year_digits.len() < 4 {
+            return Err(LiteralError::malformed(
+                "expected 4-digit year",
+                year_start..self.pos.max(year_start + 1).min(self.text.len()),
+            ));
+        }
+        let year: i32 = year_digits.parse().map_err(|_| {
+            LiteralError::out_of_range("year is out of range", year_start..self.pos)
+        })?;
+        let year = if negative { -year } else { year };
+
+        if !self.eat('-') {
+            return Err(self.error("expected '-' after year"));
+        }
+        let (month, month_range) = self.fixed(2, "month")?;
+        if !(1..=12).contains(&month) {
+            return Err(LiteralError::out_of_range(
+                format!("month {month} is out of range (1-12)"),
+                month_range,
+            ));
+        }
+        if !self.eat('-') {
+            return Err(self.error("expected '-' after month"));
+        }
+        let (day, day_range) = self.fixed(2, "day")?;
+        let max_day = days_in_month(year, month);
+        if day == 0 || day > max_day {
+            return Err(LiteralError::out_of_range(
+                format!("day {day} is out of range for {year:04}-{month:02} (1-{max_day})"),
+                day_range,
+            ));
+        }
+
+        Ok(DateValue {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    fn time(&mut self) -> Result<TimeValue, LiteralError> {
+        let (hour, hour_range) = self.fixed(2, "hour")?;
+        if hour > 23 {
+            return Err(LiteralError::out_of_range(
+                format!("hour {hour} is out of range (0-23)"),
+                hour_range,
+            ));
+        }
+        if !self.eat(':') {
+            return Err(self.error("expected ':' after hour"));
+        }
+        let (minute, minute_range) = self.fixed(2, "minute")?;
+        if minute > 59 {
+            return Err(LiteralError::out_of_range(
+                format!("minute {minute} is out of range (0-59)"),
+                minute_range,
+            ));
+        }
+
+        let mut second = 0;
+        let mut nanosecond = 0;
+        if self.eat(':') {
+            let (value, second_range) = self.fixed(2, "second")?;
+            if value > 59 {
+                return Err(LiteralError::out_of_range(
+                    format!("second {value} is out of range (0-59)"),
+                    second_range,
+                ));
+            }
+            second = value;
+            if self.eat('.') {
+                nanosecond = self.fraction()?;
+            }
+        }
+
+        let offset = self.zone_offset()?;
+        Ok(TimeValue {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+            offset,
+        })
+    }
+
+    fn zone_offset(&mut self) -> Result<Option<ZoneOffset>, LiteralError> {
+        if self.eat_any(&['Z', 'z']) {
+            return Ok(Some(ZoneOffset { minutes: 0 }));
+        }
+        let start = self.pos;
+        let sign = match self.peek() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        let (hours, _) = self.fixed(2, "offset hour")?;
+        let minutes = if self.eat(':') {
+            self.fixed(2, "offset minute")?.0
+        } else {
+            0
+        };
+        if minutes > 59 || hours * 60 + minutes > 18 * 60 {
+            return Err(LiteralError::out_of_range(
+                "zone offset is out of range (-18:00 to +18:00)",
+                start..self.pos,
+            ));
+        }
+        Ok(Some(ZoneOffset {
+            minutes: sign * (hours * 60 + minutes) as i16,
+        }))
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_kind(literal: Literal) -> LiteralErrorKind {
+        literal.value().expect_err("literal should be rejected").kind
+    }
+
+    #[test]
+    fn decodes_integers_by_radix_and_width() {
+        let int = |text: &str| Literal::Integer(text.into()).value().unwrap();
+        assert_eq!(int("1_000"), LiteralValue::Integer(IntegerValue::I64(1000)));
+        assert_eq!(int("0o17"), LiteralValue::Integer(IntegerValue::I64(15)));
+        assert_eq!(int("0b1010"), LiteralValue::Integer(IntegerValue::I64(10)));
+        assert_eq!(
+            int("18446744073709551615"),
+            LiteralValue::Integer(IntegerValue::U64(u64::MAX))
+        );
+        assert_eq!(
+            int("99999999999999999999999"),
+            LiteralValue::Integer(IntegerValue::I128(99_999_999_999_999_999_999_999))
+        );
+        assert_eq!(
+            error_kind(Literal::Integer("340282366920938463463374607431768211456".into())),
+            LiteralErrorKind::Overflow
+        );
+    }
+
+    #[test]
+    fn decodes_decimals_and_floats() {
+        let value = Literal::Float("12.50".into()).value().unwrap();
+        let LiteralValue::Decimal(decimal) = value else {
+            panic!("expected decimal, got {value:?}");
+        };
+        assert_eq!(decimal, DecimalValue { unscaled: 1250, scale: 2 });
+        assert_eq!(decimal.precision(), 4);
+        assert_eq!(decimal.to_string(), "12.50");
+        assert_eq!(DecimalValue { unscaled: 5, scale: 3 }.to_string(), "0.005");
+
+        assert_eq!(
+            Literal::Float("1.5e3".into()).value().unwrap(),
+            LiteralValue::Float(1500.0)
+        );
+        assert_eq!(
+            error_kind(Literal::Float("1e400".into())),
+            LiteralErrorKind::OutOfRange
+        );
+    }
+
+    #[test]
+    fn decodes_byte_strings() {
+        assert_eq!(
+            Literal::ByteString("0aFF".into()).value().unwrap(),
+            LiteralValue::Bytes(vec![0x0a, 0xff])
+        );
+        let err = Literal::ByteString("0G".into()).value().unwrap_err();
+        assert_eq!(err.range, Some(1..2));
+    }
+
+    #[test]
+    fn decodes_dates_and_rejects_invalid_components() {
+        assert!(Literal::Date("2024-02-29".into()).value().is_ok());
+
+        let err = Literal::Date("2023-02-29".into()).value().unwrap_err();
+        assert_eq!(err.kind, LiteralErrorKind::OutOfRange);
+        assert_eq!(err.range, Some(8..10));
+
+        let err = Literal::Date("2024-13-45".into()).value().unwrap_err();
+        assert_eq!(err.range, Some(5..7));
+
+        assert_eq!(
+            error_kind(Literal::Date("2024-1-01".into())),
+            LiteralErrorKind::Malformed
+        );
+        assert_eq!(
+            error_kind(Literal::Date("2024-01-01x".into())),
+            LiteralErrorKind::Malformed
+        );
+    }
+
+    #[test]
+    fn decodes_times_and_datetimes_with_offsets() {
+        assert_eq!(
+            Literal::Time("10:30:05.25+05:30".into()).value().unwrap(),
+            LiteralValue::Time(TimeValue {
+                hour: 10,
+                minute: 30,
+                second: 5,
+                nanosecond: 250_000_000,
+                offset: Some(ZoneOffset { minutes: 330 }),
+            })
+        );
+
+        let LiteralValue::Datetime(dt) = Literal::Datetime("2024-01-01T00:00:00Z".into())
+            .value()
+            .unwrap()
+        else {
+            panic!("expected datetime");
+        };
+        assert_eq!(dt.time.offset, Some(ZoneOffset { minutes: 0 }));
+        assert!(Literal::Datetime("2024-01-01 12:00".into()).value().is_ok());
+
+        let err = Literal::Time("24:00".into()).value().unwrap_err();
+        assert_eq!(err.range, Some(0..2));
+        assert!(Literal::Datetime("2024-01-01".into()).value().is_err());
+    }
+
+    #[test]
+    fn temporal_values_display_in_iso_form() {
+        for text in ["2024-02-29", "10:30:05.25+05:30", "2024-01-01T00:00:00Z", "-P1Y2MT0.5S"] {
+            let shown = match Literal::Datetime(text.into()).value() {
+                Ok(LiteralValue::Datetime(v)) => v.to_string(),
+                _ => match Literal::Time(text.into()).value() {
+                    Ok(LiteralValue::Time(v)) => v.to_string(),
+                    _ => match Literal::Date(text.into()).value() {
+                        Ok(LiteralValue::Date(v)) => v.to_string(),
+                        _ => match Literal::Duration(text.into()).value() {
+                            Ok(LiteralValue::Duration(v)) => v.to_string(),
+                            other => panic!("{text} did not decode: {other:?}"),
+                        },
+                    },
+                },
+            };
+            assert_eq!(shown, text);
+        }
+        assert_eq!(DurationValue::default().to_string(), "PT0S");
+    }
+
+    #[test]
+    fn decodes_durations() {
+        assert_eq!(
+            Literal::Duration("P1Y2M10DT2H30M1.5S".into()).value().unwrap(),
+            LiteralValue::Duration(DurationValue {
+                negative: false,
+                years: 1,
+                months: 2,
+                weeks: 0,
+                days: 10,
+                hours: 2,
+                minutes: 30,
+                seconds: 1,
+                nanoseconds: 500_000_000,
+            })
+        );
+        let LiteralValue::Duration(d) = Literal::Duration("-PT5M".into()).value().unwrap() else {
+            panic!("expected duration");
+        };
+        assert!(d.negative && d.is_day_to_second() && d.minutes == 5);
+
+        let err = Literal::Duration("P1Q".into()).value().unwrap_err();
+        assert_eq!(err.range, Some(2..3));
+        assert!(Literal::Duration("P".into()).value().is_err());
+        assert!(Literal::Duration("P1DT".into()).value().is_err());
+        assert!(Literal::Duration("P1D1Y".into()).value().is_err());
+        assert!(Literal::Duration("P1.5D".into()).value().is_err());
+
+        let err = Literal::Duration("P99999999999999999999Y".into()).value().unwrap_err();
+        assert_eq!(err.kind, LiteralErrorKind::OutOfRange);
+        assert_eq!(err.range, Some(1..21));
+    }
+}
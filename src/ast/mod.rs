@@ -3,6 +3,7 @@
 mod catalog;
 pub mod expression;
 pub mod graph_type;
+pub mod literal;
 pub mod mutation;
 pub mod procedure;
 pub mod program;
@@ -10,6 +11,7 @@ pub mod query;
 pub mod references;
 mod session;
 mod span;
+pub(crate) mod spanless;
 mod transaction;
 pub mod types;
 pub mod visit;
@@ -31,10 +31,36 @@ pub enum Statement {
     Empty(Span),
 }
 
+impl Statement {
+    /// Returns the source span of this statement.
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Query(statement) => statement.span.clone(),
+            Statement::Mutation(statement) => statement.span.clone(),
+            Statement::Session(statement) => statement.span.clone(),
+            Statement::Transaction(statement) => statement.span.clone(),
+            Statement::Catalog(statement) => statement.span.clone(),
+            Statement::Empty(span) => span.clone(),
+        }
+    }
+
+    /// Returns whether this statement follows `NEXT`.
+    pub fn follows_next(&self) -> bool {
+        match self {
+            Statement::Query(statement) => statement.follows_next,
+            Statement::Mutation(statement) => statement.follows_next,
+            _ => false,
+        }
+    }
+}
+
 /// Query statement AST node.
 #[derive(Debug, Clone, PartialEq)]
 pub struct QueryStatement {
     pub query: Query,
+    /// Whether the statement follows `NEXT` and so starts from the columns
+    /// returned by the statement before it.
+    pub follows_next: bool,
     pub span: Span,
 }
 
@@ -42,6 +68,9 @@ pub struct QueryStatement {
 #[derive(Debug, Clone, PartialEq)]
 pub struct MutationStatement {
     pub statement: LinearDataModifyingStatement,
+    /// Whether the statement follows `NEXT` and so starts from the columns
+    /// returned by the statement before it.
+    pub follows_next: bool,
     pub span: Span,
 }
 
@@ -97,6 +126,7 @@ mod tests {
                 result_statement: None,
                 span: 0..5,
             }),
+            follows_next: false,
             span: 0..5,
         }));
         assert!(matches!(query, Statement::Query(_)));
@@ -108,6 +138,7 @@ mod tests {
                 primitive_result_statement: None,
                 span: 0..5,
             },
+            follows_next: false,
             span: 0..5,
         }));
         assert!(matches!(mutation, Statement::Mutation(_)));
@@ -1418,6 +1418,7 @@ pub struct HavingClause {
 /// RETURN *
 /// RETURN DISTINCT n.name, n.age
 /// RETURN n.value AS val GROUP BY n.category
+/// RETURN n.name AS name ORDER BY name LIMIT 10
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReturnStatement {
@@ -1427,6 +1428,10 @@ pub struct ReturnStatement {
     pub items: ReturnItemList,
     /// Optional GROUP BY clause.
     pub group_by: Option<GroupByClause>,
+    /// Optional ORDER BY and pagination applied to the returned rows.
+    ///
+    /// Sort keys may refer to the aliases of the return items.
+    pub order_by_and_page: Option<Box<OrderByAndPageStatement>>,
     /// Source span.
     pub span: Span,
 }
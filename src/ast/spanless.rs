@@ -0,0 +1,881 @@
+//! Span-insensitive structural encoding of AST nodes.
+//!
+//! [`Spanless::encode`] writes the structure of a node to a [`SpanlessKey`]:
+//! enum variant names, names and literal text, flags, counts and numbers, in
+//! field order. Source spans are skipped, so two nodes have the same key
+//! exactly when they are equal up to spans. Normalization sorts and
+//! fingerprints by the key, and schema diffs compare constraints with it.
+//!
+//! Every implementation destructures its type without `..`, so a field added
+//! to an AST node does not compile until it is listed here.
+
+use std::ops::Range;
+
+use smol_str::SmolStr;
+
+use super::{
+    catalog, expression, graph_type, mutation, procedure, program, query, references, session,
+    transaction, types,
+};
+use crate::semantic::schema_catalog;
+
+/// An AST node that can be encoded without its source spans.
+pub(crate) trait Spanless {
+    /// Appends the structure of this node to `key`.
+    fn encode(&self, key: &mut SpanlessKey);
+
+    /// Returns the encoded structure of this node.
+    fn spanless_key(&self) -> SpanlessKey {
+        let mut key = SpanlessKey::default();
+        self.encode(&mut key);
+        key
+    }
+
+    /// Returns true if both nodes have the same structure, ignoring spans.
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.spanless_key() == other.spanless_key()
+    }
+}
+
+/// The encoded structure of an AST node.
+///
+/// Keys compare and order byte-wise. Each item is tagged and length-prefixed,
+/// so distinct structures never share an encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct SpanlessKey(Vec<u8>);
+
+impl SpanlessKey {
+    const VARIANT: u8 = 0;
+    const TEXT: u8 = 1;
+    const NUMBER: u8 = 2;
+
+    /// Returns the encoded bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn variant(&mut self, name: &str) {
+        self.0.push(Self::VARIANT);
+        self.bytes(name.as_bytes());
+    }
+
+    fn text(&mut self, text: &str) {
+        self.0.push(Self::TEXT);
+        self.bytes(text.as_bytes());
+    }
+
+    fn number(&mut self, number: u64) {
+        self.0.push(Self::NUMBER);
+        self.0.extend_from_slice(&number.to_be_bytes());
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0
+            .extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+// ============================================================================
+// Leaves and containers
+// ============================================================================
+
+/// Spans are what the encoding leaves out.
+impl Spanless for Range<usize> {
+    fn encode(&self, _: &mut SpanlessKey) {}
+}
+
+impl Spanless for SmolStr {
+    fn encode(&self, key: &mut SpanlessKey) {
+        key.text(self);
+    }
+}
+
+impl Spanless for bool {
+    fn encode(&self, key: &mut SpanlessKey) {
+        key.number(u64::from(*self));
+    }
+}
+
+impl Spanless for u32 {
+    fn encode(&self, key: &mut SpanlessKey) {
+        key.number(u64::from(*self));
+    }
+}
+
+impl<T: Spanless + ?Sized> Spanless for Box<T> {
+    fn encode(&self, key: &mut SpanlessKey) {
+        T::encode(self, key);
+    }
+}
+
+impl<T: Spanless> Spanless for Option<T> {
+    fn encode(&self, key: &mut SpanlessKey) {
+        match self {
+            Some(value) => {
+                key.number(1);
+                value.encode(key);
+            }
+            None => key.number(0),
+        }
+    }
+}
+
+impl<T: Spanless> Spanless for Vec<T> {
+    fn encode(&self, key: &mut SpanlessKey) {
+        key.number(self.len() as u64);
+        for item in self {
+            item.encode(key);
+        }
+    }
+}
+
+/// Implements [`Spanless`] for a struct by encoding its fields, or for an enum
+/// by encoding the variant name and then its fields.
+macro_rules! spanless {
+    (struct $module:ident::$ty:ident { $($field:ident),* $(,)? }) => {
+        impl Spanless for $module::$ty {
+            fn encode(&self, key: &mut SpanlessKey) {
+                let Self { $($field),* } = self;
+                $($field.encode(key);)*
+            }
+        }
+    };
+    (enum $module:ident::$ty:ident {
+        $($variant:ident $(($($tuple:ident),*))? $({ $($named:ident),* })?),* $(,)?
+    }) => {
+        impl Spanless for $module::$ty {
+            fn encode(&self, key: &mut SpanlessKey) {
+                match self {
+                    $(Self::$variant $(($($tuple),*))? $({ $($named),* })? => {
+                        key.variant(stringify!($variant));
+                        $($($tuple.encode(key);)*)?
+                        $($($named.encode(key);)*)?
+                    })*
+                }
+            }
+        }
+    };
+}
+
+// ============================================================================
+// Programs
+// ============================================================================
+
+spanless!(struct program::Program { statements, span });
+spanless!(enum program::Statement {
+    Query(a),
+    Mutation(a),
+    Session(a),
+    Transaction(a),
+    Catalog(a),
+    Empty(a),
+});
+spanless!(struct program::QueryStatement { query, follows_next, span });
+spanless!(struct program::MutationStatement { statement, follows_next, span });
+spanless!(struct program::SessionStatement { command, span });
+spanless!(struct program::TransactionStatement { command, span });
+spanless!(struct program::CatalogStatement { kind, span });
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+spanless!(enum query::Query { Linear(a), Composite(a), Parenthesized(a, b) });
+spanless!(struct query::CompositeQuery { left, operator, right, span });
+spanless!(enum query::SetOperator {
+    Union { quantifier },
+    Except { quantifier },
+    Intersect { quantifier },
+    Otherwise,
+});
+spanless!(enum query::SetQuantifier { All, Distinct });
+spanless!(struct query::LinearQuery { use_graph, primitive_statements, result_statement, span });
+spanless!(enum query::PrimitiveQueryStatement {
+    Match(a),
+    Call(a),
+    Filter(a),
+    Let(a),
+    For(a),
+    OrderByAndPage(a),
+    Select(a),
+});
+spanless!(enum query::PrimitiveResultStatement { Return(a), Finish(a) });
+spanless!(struct query::UseGraphClause { graph, span });
+spanless!(enum query::MatchStatement { Simple(a), Optional(a) });
+spanless!(struct query::SimpleMatchStatement { pattern, span });
+spanless!(struct query::OptionalMatchStatement { operand, span });
+spanless!(enum query::OptionalOperand {
+    Match { pattern },
+    Block { statements },
+    ParenthesizedBlock { statements },
+});
+spanless!(struct query::GraphPattern {
+    match_mode, paths, keep_clause, where_clause, yield_clause, span,
+});
+spanless!(enum query::MatchMode { RepeatableElements, DifferentEdges });
+spanless!(struct query::PathPatternList { patterns, span });
+spanless!(struct query::PathPattern { prefix, expression, variable_declaration, span });
+spanless!(struct query::PathVariableDeclaration { variable, span });
+spanless!(struct query::KeepClause { prefix, span });
+spanless!(struct query::GraphPatternWhereClause { condition, span });
+spanless!(enum query::PathPatternPrefix { PathMode(a), PathSearch(a) });
+spanless!(enum query::PathMode { Walk, Trail, Simple, Acyclic });
+spanless!(enum query::PathSearch { All(a), Any(a), Shortest(a) });
+spanless!(struct query::AllPathSearch { mode, use_paths_keyword, span });
+spanless!(struct query::AnyPathSearch { mode, span });
+spanless!(enum query::ShortestPathSearch {
+    AllShortest { mode, span },
+    AnyShortest { mode, span },
+    CountedShortest { count, mode, use_paths_keyword, span },
+    CountedShortestGroups { count, mode, span },
+});
+spanless!(enum query::PathPatternExpression {
+    Union { left, right, span },
+    Alternation { alternatives, span },
+    Term(a),
+});
+spanless!(struct query::PathTerm { factors, span });
+spanless!(struct query::PathFactor { primary, quantifier, span });
+spanless!(enum query::PathPrimary {
+    ElementPattern(a),
+    ParenthesizedExpression(a),
+    SimplifiedExpression(a),
+});
+spanless!(enum query::GraphPatternQuantifier {
+    Star { span },
+    Plus { span },
+    QuestionMark { span },
+    Fixed { count, span },
+    General { min, max, span },
+});
+spanless!(enum query::ElementPattern { Node(a), Edge(a) });
+spanless!(struct query::NodePattern { variable, label_expression, properties, where_clause, span });
+spanless!(struct query::ElementVariableDeclaration { variable, span });
+spanless!(struct query::ElementPropertySpecification { properties, span });
+spanless!(struct query::PropertyKeyValuePair { key, value, span });
+spanless!(struct query::ElementPatternPredicate { condition, span });
+spanless!(enum query::EdgePattern { Full(a), Abbreviated(a) });
+spanless!(struct query::FullEdgePattern { direction, filler, span });
+spanless!(enum query::EdgeDirection {
+    PointingLeft,
+    PointingRight,
+    Undirected,
+    AnyDirected,
+    LeftOrUndirected,
+    AnyDirection,
+    RightOrUndirected,
+});
+spanless!(struct query::FullEdgePointingFiller {
+    variable, label_expression, properties, where_clause, span,
+});
+spanless!(enum query::AbbreviatedEdgePattern {
+    LeftArrow { span },
+    RightArrow { span },
+    Undirected { span },
+    AnyDirection { span },
+});
+spanless!(enum query::LabelExpression {
+    Negation { operand, span },
+    Conjunction { left, right, span },
+    Disjunction { left, right, span },
+    LabelName { name, span },
+    Wildcard { span },
+    Parenthesized { expression, span },
+});
+spanless!(struct query::IsLabelExpression { expression, span });
+spanless!(struct query::LabelSetSpecification { labels, span });
+spanless!(enum query::LabelSetPhrase { Label, Labels });
+spanless!(struct query::SimplifiedPathPattern { expression, span });
+spanless!(enum query::SimplifiedPathPatternExpression {
+    Contents(a),
+    Union(a),
+    MultisetAlternation(a),
+    Conjunction(a),
+    Concatenation(a),
+    Quantified(a),
+    Questioned(a),
+    DirectionOverride(a),
+    Negation(a),
+});
+spanless!(struct query::SimplifiedContents { labels, span });
+spanless!(struct query::SimplifiedPathUnion { left, right, span });
+spanless!(struct query::SimplifiedMultisetAlternation { alternatives, span });
+spanless!(struct query::SimplifiedConjunction { left, right, span });
+spanless!(struct query::SimplifiedConcatenation { parts, span });
+spanless!(struct query::SimplifiedQuantified { pattern, quantifier, span });
+spanless!(struct query::SimplifiedQuestioned { pattern, span });
+spanless!(struct query::SimplifiedDirectionOverride { pattern, direction, span });
+spanless!(struct query::SimplifiedNegation { pattern, span });
+spanless!(struct query::GraphPatternBindingTable { pattern, yield_clause, span });
+spanless!(struct query::GraphPatternYieldClause { items, span });
+spanless!(struct query::YieldItem { expression, alias, span });
+spanless!(struct query::ParenthesizedPathPatternExpression { expression, span });
+spanless!(struct query::FilterStatement { where_optional, condition, span });
+spanless!(struct query::LetStatement { bindings, span });
+spanless!(struct query::LetVariableDefinition { variable, type_annotation, value, span });
+spanless!(struct query::ForStatement { item, ordinality_or_offset, span });
+spanless!(struct query::ForItem { binding_variable, collection, span });
+spanless!(enum query::ForOrdinalityOrOffset { Ordinality { variable }, Offset { variable } });
+spanless!(struct query::SelectStatement {
+    with_clause, quantifier, select_items, from_clause, where_clause, group_by, having, order_by,
+    offset, limit, span,
+});
+spanless!(struct query::WithClause { recursive, items, span });
+spanless!(struct query::CommonTableExpression { name, columns, query, span });
+spanless!(enum query::SelectItemList { Star, Items { items } });
+spanless!(struct query::SelectItem { expression, alias, span });
+spanless!(enum query::SelectFromClause {
+    GraphMatchList { matches },
+    QuerySpecification { query, alias },
+    GraphAndQuerySpecification { graph, query, alias },
+    SourceList { sources },
+});
+spanless!(enum query::SelectSourceItem {
+    Query { query, alias, span },
+    GraphAndQuery { graph, query, alias, span },
+    Expression { expression, alias, span },
+});
+spanless!(struct query::WhereClause { condition, span });
+spanless!(struct query::HavingClause { condition, span });
+spanless!(struct query::ReturnStatement { quantifier, items, group_by, order_by_and_page, span });
+spanless!(enum query::ReturnItemList { Star, Items { items } });
+spanless!(struct query::ReturnItem { expression, alias, span });
+spanless!(struct query::OrderByAndPageStatement { order_by, offset, limit, span });
+spanless!(struct query::OrderByClause { sort_specifications, span });
+spanless!(struct query::SortSpecification { key, ordering, null_ordering, span });
+spanless!(enum query::OrderingSpecification { Ascending, Descending });
+spanless!(enum query::NullOrdering { NullsFirst, NullsLast });
+spanless!(struct query::LimitClause { count, span });
+spanless!(struct query::OffsetClause { count, use_skip_keyword, span });
+spanless!(struct query::GroupByClause { elements, span });
+spanless!(enum query::GroupingElement { Expression(a), EmptyGroupingSet });
+
+// ============================================================================
+// Expressions
+// ============================================================================
+
+spanless!(enum expression::Expression {
+    Literal(a, b),
+    Unary(a, b, c),
+    Binary(a, b, c, d),
+    Comparison(a, b, c, d),
+    Logical(a, b, c, d),
+    Parenthesized(a, b),
+    PropertyReference(a, b, c),
+    VariableReference(a, b),
+    ParameterReference(a, b),
+    FunctionCall(a),
+    Case(a),
+    Cast(a),
+    AggregateFunction(a),
+    TypeAnnotation(a, b, c),
+    ListConstructor(a, b),
+    RecordConstructor(a, b),
+    PathConstructor(a, b),
+    Exists(a),
+    Predicate(a),
+    GraphExpression(a, b),
+    BindingTableExpression(a, b),
+    SubqueryExpression(a, b),
+});
+spanless!(enum expression::Literal {
+    Boolean(a),
+    Null,
+    Integer(a),
+    Float(a),
+    String(a),
+    ByteString(a),
+    Date(a),
+    Time(a),
+    Datetime(a),
+    Duration(a),
+    List(a),
+    Record(a),
+});
+spanless!(enum expression::BooleanValue { True, False, Unknown });
+spanless!(struct expression::RecordField { name, value, span });
+spanless!(enum expression::UnaryOperator { Plus, Minus, Not });
+spanless!(enum expression::BinaryOperator { Add, Subtract, Multiply, Divide, Modulo, Concatenate });
+spanless!(enum expression::ComparisonOperator { Eq, NotEq, Lt, Gt, LtEq, GtEq });
+spanless!(enum expression::LogicalOperator { And, Or, Xor });
+spanless!(enum expression::Predicate {
+    IsNull(a, b, c),
+    IsTyped(a, b, c, d),
+    IsNormalized(a, b, c),
+    IsDirected(a, b, c),
+    IsLabeled(a, b, c, d),
+    IsTruthValue(a, b, c, d),
+    IsSource(a, b, c, d),
+    IsDestination(a, b, c, d),
+    AllDifferent(a, b),
+    Same(a, b, c),
+    PropertyExists(a, b, c),
+});
+spanless!(enum expression::TruthValue { True, False, Unknown });
+spanless!(struct expression::ExistsExpression { variant, span });
+spanless!(enum expression::ExistsVariant { GraphPattern(a), Subquery(a) });
+spanless!(struct expression::GraphPatternPlaceholder { span });
+spanless!(struct expression::FunctionCall { name, arguments, span });
+spanless!(enum expression::FunctionName {
+    Abs,
+    Mod,
+    Floor,
+    Ceil,
+    Sqrt,
+    Power,
+    Exp,
+    Ln,
+    Log,
+    Log10,
+    Sin,
+    Cos,
+    Tan,
+    Cot,
+    Sinh,
+    Cosh,
+    Tanh,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Degrees,
+    Radians,
+    Upper,
+    Lower,
+    Trim(a),
+    BTrim,
+    LTrim,
+    RTrim,
+    Left,
+    Right,
+    Normalize,
+    CharLength,
+    ByteLength,
+    Substring,
+    CurrentDate,
+    CurrentTime,
+    CurrentTimestamp,
+    Date,
+    Time,
+    Datetime,
+    ZonedTime,
+    ZonedDatetime,
+    LocalTime,
+    LocalDatetime,
+    Duration,
+    DurationBetween,
+    TrimList,
+    Elements,
+    Cardinality,
+    Size,
+    PathLength,
+    ElementId,
+    Coalesce,
+    NullIf,
+    Custom(a),
+});
+spanless!(enum expression::TrimSpecification { Leading, Trailing, Both });
+spanless!(enum expression::CaseExpression { Simple(a), Searched(a) });
+spanless!(struct expression::SimpleCaseExpression { operand, when_clauses, else_clause, span });
+spanless!(struct expression::SimpleWhenClause { when_value, then_result, span });
+spanless!(struct expression::SearchedCaseExpression { when_clauses, else_clause, span });
+spanless!(struct expression::SearchedWhenClause { condition, then_result, span });
+spanless!(struct expression::CastExpression { operand, target_type, span });
+spanless!(enum expression::AggregateFunction {
+    CountStar { span },
+    GeneralSetFunction(a),
+    BinarySetFunction(a),
+});
+spanless!(struct expression::GeneralSetFunction { function_type, quantifier, expression, span });
+spanless!(enum expression::GeneralSetFunctionType {
+    Avg,
+    Count,
+    Max,
+    Min,
+    Sum,
+    CollectList,
+    StddevSamp,
+    StddevPop,
+});
+spanless!(struct expression::BinarySetFunction {
+    function_type, quantifier, inverse_distribution_argument, expression, span,
+});
+spanless!(enum expression::BinarySetFunctionType { PercentileCont, PercentileDisc });
+
+// ============================================================================
+// Data modification
+// ============================================================================
+
+spanless!(struct mutation::LinearDataModifyingStatement {
+    use_graph_clause, statements, primitive_result_statement, span,
+});
+spanless!(enum mutation::SimpleDataAccessingStatement { Query(a), Modifying(a) });
+spanless!(enum mutation::SimpleDataModifyingStatement { Primitive(a), Call(a) });
+spanless!(enum mutation::PrimitiveDataModifyingStatement {
+    Insert(a),
+    Set(a),
+    Remove(a),
+    Delete(a),
+});
+spanless!(struct mutation::InsertStatement { pattern, span });
+spanless!(struct mutation::InsertGraphPattern { paths, span });
+spanless!(struct mutation::InsertPathPattern { elements, span });
+spanless!(enum mutation::InsertElementPattern { Node(a), Edge(a) });
+spanless!(struct mutation::InsertNodePattern { filler, span });
+spanless!(enum mutation::InsertEdgePattern { PointingLeft(a), PointingRight(a), Undirected(a) });
+spanless!(struct mutation::InsertEdgePointingLeft { filler, span });
+spanless!(struct mutation::InsertEdgePointingRight { filler, span });
+spanless!(struct mutation::InsertEdgeUndirected { filler, span });
+spanless!(struct mutation::InsertElementPatternFiller {
+    variable, label_set, use_is_keyword, properties, span,
+});
+spanless!(struct mutation::SetStatement { items, span });
+spanless!(struct mutation::SetItemList { items, span });
+spanless!(enum mutation::SetItem { Property(a), AllProperties(a), Label(a) });
+spanless!(struct mutation::SetPropertyItem { element, property, value, span });
+spanless!(struct mutation::SetAllPropertiesItem { element, properties, span });
+spanless!(struct mutation::SetLabelItem { element, label, use_is_keyword, span });
+spanless!(struct mutation::RemoveStatement { items, span });
+spanless!(struct mutation::RemoveItemList { items, span });
+spanless!(enum mutation::RemoveItem { Property(a), Label(a) });
+spanless!(struct mutation::RemovePropertyItem { element, property, span });
+spanless!(struct mutation::RemoveLabelItem { element, label, use_is_keyword, span });
+spanless!(struct mutation::DeleteStatement { detach_option, items, span });
+spanless!(enum mutation::DetachOption { Detach, NoDetach, Default });
+spanless!(struct mutation::DeleteItemList { items, span });
+spanless!(struct mutation::DeleteItem { expression, span });
+spanless!(struct mutation::CallDataModifyingProcedureStatement { call, span });
+
+// ============================================================================
+// Procedures
+// ============================================================================
+
+spanless!(struct procedure::CallProcedureStatement { optional, call, span });
+spanless!(enum procedure::ProcedureCall { Inline(a), Named(a) });
+spanless!(struct procedure::InlineProcedureCall { variable_scope, specification, span });
+spanless!(struct procedure::VariableScopeClause { variables, span });
+spanless!(struct procedure::NamedProcedureCall { procedure, arguments, yield_clause, span });
+spanless!(struct procedure::ProcedureArgumentList { arguments, span });
+spanless!(struct procedure::ProcedureArgument { expression, span });
+spanless!(struct procedure::YieldClause { items, span });
+spanless!(struct procedure::YieldItemList { items, span });
+spanless!(struct procedure::YieldItem { expression, alias, span });
+spanless!(struct procedure::YieldItemAlias { name, span });
+spanless!(struct procedure::NestedProcedureSpecification { body, span });
+spanless!(struct procedure::NestedDataModifyingProcedureSpecification { body, span });
+spanless!(struct procedure::NestedQuerySpecification { body, span });
+spanless!(struct procedure::ProcedureBody { at_schema, variable_definitions, statements, span });
+spanless!(struct procedure::BindingVariableDefinitionBlock { definitions, span });
+spanless!(enum procedure::BindingVariableDefinition { Graph(a), BindingTable(a), Value(a) });
+spanless!(struct procedure::GraphVariableDefinition {
+    is_property, variable, type_annotation, initializer, span,
+});
+spanless!(struct procedure::GraphInitializer { expression, span });
+spanless!(enum procedure::GraphExpression {
+    VariableReference(a, b),
+    CurrentGraph(a),
+    Expression(a),
+});
+spanless!(struct procedure::BindingTableVariableDefinition {
+    is_binding, variable, type_annotation, initializer, span,
+});
+spanless!(struct procedure::BindingTableInitializer { expression, span });
+spanless!(enum procedure::BindingTableExpression { VariableReference(a, b), Expression(a) });
+spanless!(struct procedure::ValueVariableDefinition {
+    variable, type_annotation, initializer, span,
+});
+spanless!(struct procedure::ValueInitializer { expression, span });
+spanless!(struct procedure::StatementBlock { statements, next_statements, span });
+spanless!(enum procedure::Statement {
+    CompositeQuery(a),
+    LinearCatalogModifying(a),
+    LinearDataModifying(a),
+});
+spanless!(struct procedure::NextStatement { yield_clause, statement, span });
+spanless!(struct procedure::AtSchemaClause { schema, span });
+
+// ============================================================================
+// Catalog statements
+// ============================================================================
+
+spanless!(struct catalog::CreateSchemaStatement { or_replace, if_not_exists, schema, span });
+spanless!(struct catalog::DropSchemaStatement { if_exists, schema, span });
+spanless!(struct catalog::CreateGraphStatement {
+    property, or_replace, if_not_exists, graph, graph_type_spec, span,
+});
+spanless!(enum catalog::GraphTypeSpec {
+    Open { span },
+    Of { graph_type, span },
+    Like { graph, span },
+    AsCopyOf { graph, span },
+});
+spanless!(struct catalog::DropGraphStatement { property, if_exists, graph, span });
+spanless!(struct catalog::CreateGraphTypeStatement {
+    property, or_replace, if_not_exists, graph_type, source, span,
+});
+spanless!(enum catalog::GraphTypeSource {
+    AsCopyOf { graph_type, span },
+    LikeGraph { graph, span },
+    Detailed { specification, span },
+});
+spanless!(struct catalog::DropGraphTypeStatement { property, if_exists, graph_type, span });
+spanless!(struct catalog::CreateProcedureStatement {
+    or_replace, if_not_exists, procedure, specification, span,
+});
+spanless!(struct catalog::DropProcedureStatement { if_exists, procedure, span });
+spanless!(struct catalog::CallCatalogModifyingProcedureStatement { call, span });
+spanless!(enum catalog::CatalogStatementKind {
+    CreateSchema(a),
+    DropSchema(a),
+    CreateGraph(a),
+    DropGraph(a),
+    CreateGraphType(a),
+    DropGraphType(a),
+    CreateProcedure(a),
+    DropProcedure(a),
+    CallCatalogModifyingProcedure(a),
+});
+
+// ============================================================================
+// Session statements
+// ============================================================================
+
+spanless!(enum session::SessionCommand { Set(a), Reset(a), Close(a) });
+spanless!(enum session::SessionSetCommand { Schema(a), Graph(a), TimeZone(a), Parameter(a) });
+spanless!(struct session::SessionSetSchemaClause { schema_reference, span });
+spanless!(struct session::SessionSetGraphClause { property, graph_reference, span });
+spanless!(struct session::SessionSetTimeZoneClause { value, span });
+spanless!(enum session::SessionSetParameterClause {
+    GraphParameter { name, value, span },
+    BindingTableParameter { name, value, span },
+    ValueParameter { name, value, span },
+});
+spanless!(struct session::SessionResetCommand { target, span });
+spanless!(enum session::SessionResetTarget {
+    All,
+    Parameters,
+    Characteristics,
+    Schema,
+    Graph,
+    TimeZone,
+});
+spanless!(struct session::SessionCloseCommand { span });
+
+// ============================================================================
+// Transaction statements
+// ============================================================================
+
+spanless!(enum transaction::TransactionCommand { Start(a), Commit(a), Rollback(a) });
+spanless!(struct transaction::StartTransactionCommand { characteristics, span });
+spanless!(struct transaction::TransactionCharacteristics { modes, span });
+spanless!(enum transaction::TransactionMode { AccessMode(a) });
+spanless!(enum transaction::TransactionAccessMode { ReadOnly, ReadWrite });
+spanless!(struct transaction::CommitCommand { work, span });
+spanless!(struct transaction::RollbackCommand { work, span });
+
+// ============================================================================
+// References
+// ============================================================================
+
+spanless!(enum references::SchemaReference {
+    AbsolutePath { components, span },
+    RelativePath { up_levels, components, span },
+    Identifier { name, span },
+    HomeSchema { span },
+    CurrentSchema { span },
+    Dot { span },
+    ReferenceParameter { name, span },
+});
+spanless!(enum references::GraphReference {
+    CatalogQualified { name, span },
+    Delimited { name, span },
+    HomeGraph { span },
+    HomePropertyGraph { span },
+    CurrentGraph { span },
+    CurrentPropertyGraph { span },
+    ReferenceParameter { name, span },
+});
+spanless!(enum references::GraphTypeReference {
+    CatalogQualified { name, span },
+    ReferenceParameter { name, span },
+});
+spanless!(enum references::BindingTableReference {
+    CatalogQualified { name, span },
+    Delimited { name, span },
+    ReferenceParameter { name, span },
+});
+spanless!(enum references::ProcedureReference {
+    CatalogQualified { name, span },
+    ReferenceParameter { name, span },
+});
+spanless!(struct references::CatalogQualifiedName { parent, name, span });
+spanless!(enum references::CatalogObjectParentReference {
+    Schema { schema, span },
+    Object { name, span },
+});
+spanless!(struct references::BindingVariable { name, span });
+
+// ============================================================================
+// Value types
+// ============================================================================
+
+spanless!(enum types::ValueType { Predefined(a, b), Path(a), List(a), Record(a) });
+spanless!(enum types::PredefinedType {
+    Boolean(a),
+    CharacterString(a),
+    ByteString(a),
+    Numeric(a),
+    Temporal(a),
+    ReferenceValue(a),
+    Immaterial(a),
+});
+spanless!(enum types::BooleanType { Bool, Boolean });
+spanless!(enum types::CharacterStringType { String, Char(a), VarChar(a) });
+spanless!(enum types::ByteStringType { Bytes, Binary(a), VarBinary(a) });
+spanless!(enum types::NumericType { Exact(a), Approximate(a) });
+spanless!(enum types::ExactNumericType { SignedBinary(a), UnsignedBinary(a), Decimal(a) });
+spanless!(enum types::SignedBinaryExactNumericType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Int128,
+    Int256,
+    SmallInt,
+    Int,
+    Integer,
+    BigInt,
+});
+spanless!(enum types::UnsignedBinaryExactNumericType {
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    UInt128,
+    UInt256,
+    USmallInt,
+    UInt,
+    UBigInt,
+});
+spanless!(struct types::DecimalExactNumericType { kind, precision, scale, span });
+spanless!(enum types::DecimalKind { Decimal, Dec });
+spanless!(enum types::ApproximateNumericType {
+    Float16,
+    Float32,
+    Float64,
+    Float128,
+    Float256,
+    Float(a),
+    Real,
+    DoublePrecision,
+});
+spanless!(enum types::TemporalType { Instant(a), Duration(a) });
+spanless!(enum types::TemporalInstantType {
+    ZonedDatetime,
+    LocalDatetime,
+    Date,
+    ZonedTime,
+    LocalTime,
+});
+spanless!(enum types::TemporalDurationType { Duration, DurationYearToMonth, DurationDayToSecond });
+spanless!(enum types::ImmaterialValueType { Null, NullNotNull, Nothing });
+spanless!(enum types::ReferenceValueType { Graph(a), BindingTable(a), Node(a), Edge(a) });
+spanless!(enum types::GraphReferenceValueType {
+    AnyPropertyGraph { not_null, span },
+    PropertyGraph { spec, not_null, span },
+});
+spanless!(struct types::BindingTableReferenceValueType { field_types, not_null, span });
+spanless!(enum types::NodeReferenceValueType {
+    Any { use_vertex, not_null, span },
+    Typed { spec, not_null, span },
+});
+spanless!(enum types::EdgeReferenceValueType {
+    Any { use_relationship, not_null, span },
+    Typed { spec, not_null, span },
+});
+spanless!(struct types::PathValueType { span });
+spanless!(struct types::ListValueType { element_type, syntax_form, span });
+spanless!(enum types::ListSyntaxForm { List, Array, PostfixList, PostfixArray });
+spanless!(enum types::RecordType { AnyRecord { span }, Record { field_types, span } });
+spanless!(struct types::FieldTypesSpecification { fields, span });
+spanless!(struct types::FieldType { field_name, field_type, span });
+spanless!(struct types::TypeAnnotation { operator, type_ref, span });
+spanless!(enum types::TypeAnnotationOperator { DoubleColon, Typed });
+spanless!(struct types::NotNullConstraint { span });
+
+// ============================================================================
+// Graph types
+// ============================================================================
+
+spanless!(struct graph_type::NestedGraphTypeSpecification { body, span });
+spanless!(struct graph_type::GraphTypeSpecificationBody { element_types, span });
+spanless!(struct graph_type::ElementTypeList { types, span });
+spanless!(enum graph_type::ElementTypeSpecification { Node(a), Edge(a) });
+spanless!(struct graph_type::TypeInheritanceClause { parents, span });
+spanless!(struct graph_type::InheritedTypeReference { name, span });
+spanless!(enum graph_type::GraphTypeConstraint {
+    Key { properties, span },
+    Unique { properties, span },
+    Mandatory { properties, span },
+    Check { condition, span },
+    Custom { name, arguments, span },
+});
+spanless!(struct graph_type::GraphTypeConstraintArgument { raw, span });
+spanless!(struct graph_type::NodeTypeSpecification {
+    is_abstract, name, inheritance, pattern, span,
+});
+spanless!(struct graph_type::NodeTypePattern { phrase, span });
+spanless!(struct graph_type::NodeTypePhrase { filler, alias, span });
+spanless!(struct graph_type::LocalNodeTypeAlias { name, span });
+spanless!(struct graph_type::NodeTypeFiller {
+    label_set, property_types, key_label_set, implied_content, constraints, span,
+});
+spanless!(struct graph_type::NodeTypeLabelSet { label_set_phrase, span });
+spanless!(struct graph_type::NodeTypePropertyTypes { specification, span });
+spanless!(struct graph_type::NodeTypeKeyLabelSet { label_set, span });
+spanless!(struct graph_type::NodeTypeImpliedContent { content, span });
+spanless!(struct graph_type::EdgeTypeSpecification {
+    is_abstract, name, inheritance, pattern, span,
+});
+spanless!(enum graph_type::EdgeTypePattern { Directed(a), Undirected(a) });
+spanless!(struct graph_type::EdgeTypePatternDirected { left_endpoint, arc, right_endpoint, span });
+spanless!(struct graph_type::EdgeTypePatternUndirected {
+    left_endpoint, arc, right_endpoint, span,
+});
+spanless!(enum graph_type::DirectedArcType { PointingRight(a), PointingLeft(a) });
+spanless!(struct graph_type::ArcTypePointingRight { filler, span });
+spanless!(struct graph_type::ArcTypePointingLeft { filler, span });
+spanless!(struct graph_type::ArcTypeUndirected { filler, span });
+spanless!(struct graph_type::EdgeTypeFiller { phrase, constraints, span });
+spanless!(struct graph_type::EdgeTypePhrase {
+    edge_kind, filler_content, endpoint_pair_phrase, span,
+});
+spanless!(struct graph_type::EdgeTypePhraseContent { label_set, property_types, span });
+spanless!(struct graph_type::EdgeTypeLabelSet { label_set_phrase, span });
+spanless!(struct graph_type::EdgeTypePropertyTypes { specification, span });
+spanless!(enum graph_type::EdgeKind { Directed, Undirected, Inferred });
+spanless!(struct graph_type::EndpointPairPhrase { endpoint_pair, span });
+spanless!(struct graph_type::EndpointPair { source, destination, span });
+spanless!(struct graph_type::NodeTypeReference { node_type, span });
+spanless!(struct graph_type::PropertyTypesSpecification { property_types, span });
+spanless!(struct graph_type::PropertyTypeList { types, span });
+spanless!(struct graph_type::PropertyType { name, value_type, not_null, default, check, span });
+spanless!(struct graph_type::PropertyName { name, span });
+spanless!(struct graph_type::PropertyValueType { value_type, span });
+spanless!(enum graph_type::LabelSetPhrase { Label(a), Labels(a), IsLabelSet(a) });
+spanless!(struct graph_type::LabelSetSpecification { labels, span });
+spanless!(struct graph_type::LabelName { name, span });
+
+// ============================================================================
+// Catalog constraints
+// ============================================================================
+
+spanless!(enum schema_catalog::ConstraintMeta {
+    PrimaryKey { properties },
+    Unique { properties },
+    ForeignKey { properties, references, referenced_properties },
+    Mandatory { properties },
+    Check { expression },
+});
+spanless!(enum schema_catalog::TypeRef { NodeType(a), EdgeType(a) });
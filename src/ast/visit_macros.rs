@@ -121,6 +121,17 @@ pub trait $trait_name {
     fn visit_expression(&mut self, expression: $($ref)+ Expression) -> VisitResult<Self::Break> {
         walk_expression(self, expression)
     }
+
+    fn visit_mutation(
+        &mut self,
+        statement: $($ref)+ LinearDataModifyingStatement,
+    ) -> VisitResult<Self::Break> {
+        walk_mutation(self, statement)
+    }
+
+    fn visit_procedure_body(&mut self, body: $($ref)+ ProcedureBody) -> VisitResult<Self::Break> {
+        walk_procedure_body(self, body)
+    }
 }
 
 pub fn walk_program<V: $trait_name + ?Sized>(
@@ -138,13 +149,282 @@ pub fn walk_statement<V: $trait_name + ?Sized>(
     visitor: &mut V,
     statement: $($ref)+ Statement,
 ) -> VisitResult<V::Break> {
-    if let Statement::Query(query_statement) = statement {
-        return visitor.visit_query_statement(query_statement);
+    match statement {
+        Statement::Query(query_statement) => visitor.visit_query_statement(query_statement),
+        Statement::Mutation(mutation_statement) => {
+            visitor.visit_mutation($($ref)+ mutation_statement.statement)
+        }
+        Statement::Session(session_statement) => {
+            walk_session_command(visitor, $($ref)+ session_statement.command)
+        }
+        Statement::Catalog(catalog_statement) => {
+            walk_catalog_statement(visitor, $($ref)+ catalog_statement.kind)
+        }
+        Statement::Transaction(_) | Statement::Empty(_) => ControlFlow::Continue(()),
+    }
+}
+
+/// Walks the value expressions of a session command.
+fn walk_session_command<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    command: $($ref)+ SessionCommand,
+) -> VisitResult<V::Break> {
+    match command {
+        SessionCommand::Set(SessionSetCommand::TimeZone(clause)) => {
+            visitor.visit_expression($($ref)+ clause.value)
+        }
+        SessionCommand::Set(SessionSetCommand::Parameter(
+            SessionSetParameterClause::GraphParameter { value, .. }
+            | SessionSetParameterClause::BindingTableParameter { value, .. }
+            | SessionSetParameterClause::ValueParameter { value, .. },
+        )) => visitor.visit_expression(value),
+        SessionCommand::Set(_) | SessionCommand::Reset(_) | SessionCommand::Close(_) => {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Walks the procedure bodies, CALL arguments and graph type expressions of a
+/// catalog statement.
+fn walk_catalog_statement<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    statement: $($ref)+ CatalogStatementKind,
+) -> VisitResult<V::Break> {
+    match statement {
+        CatalogStatementKind::CreateProcedure(create) => {
+            visitor.visit_procedure_body($($ref)+ create.specification.body)
+        }
+        CatalogStatementKind::CallCatalogModifyingProcedure(call) => {
+            walk_call_procedure_statement(visitor, $($ref)+ call.call)
+        }
+        CatalogStatementKind::CreateGraphType(create) => match $($ref)+ create.source {
+            Some(GraphTypeSource::Detailed { specification, .. }) => {
+                walk_graph_type_specification(visitor, specification)
+            }
+            _ => ControlFlow::Continue(()),
+        },
+        _ => ControlFlow::Continue(()),
+    }
+}
+
+/// Walks the property DEFAULT values and CHECK conditions of a graph type.
+///
+/// These expressions refer to properties by bare name, as variable references.
+fn walk_graph_type_specification<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    specification: $($ref)+ NestedGraphTypeSpecification,
+) -> VisitResult<V::Break> {
+    for element in $($ref)+ specification.body.element_types.types {
+        match element {
+            ElementTypeSpecification::Node(node) => {
+                if let Some(NodeTypeFiller { property_types, constraints, .. }) =
+                    $($ref)+ node.pattern.phrase.filler
+                {
+                    if let Some(property_types) = property_types {
+                        try_visit!(walk_property_types(visitor, $($ref)+ property_types.specification));
+                    }
+                    try_visit!(walk_graph_type_constraints(visitor, constraints));
+                }
+            }
+            ElementTypeSpecification::Edge(edge) => {
+                let filler = match $($ref)+ edge.pattern {
+                    EdgeTypePattern::Directed(directed) => match $($ref)+ directed.arc {
+                        DirectedArcType::PointingRight(arc) => $($ref)+ arc.filler,
+                        DirectedArcType::PointingLeft(arc) => $($ref)+ arc.filler,
+                    },
+                    EdgeTypePattern::Undirected(undirected) => $($ref)+ undirected.arc.filler,
+                };
+                if let Some(EdgeTypeFiller { phrase, constraints, .. }) = filler {
+                    if let Some(content) = $($ref)+ phrase.filler_content
+                        && let Some(property_types) = $($ref)+ content.property_types
+                    {
+                        try_visit!(walk_property_types(visitor, $($ref)+ property_types.specification));
+                    }
+                    try_visit!(walk_graph_type_constraints(visitor, constraints));
+                }
+            }
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn walk_property_types<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    specification: $($ref)+ PropertyTypesSpecification,
+) -> VisitResult<V::Break> {
+    let Some(list) = $($ref)+ specification.property_types else {
+        return ControlFlow::Continue(());
+    };
+    for property in $($ref)+ list.types {
+        if let Some(default) = $($ref)+ property.default {
+            try_visit!(visitor.visit_expression(default));
+        }
+        if let Some(check) = $($ref)+ property.check {
+            try_visit!(visitor.visit_expression(check));
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn walk_graph_type_constraints<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    constraints: $($ref)+ Vec<GraphTypeConstraint>,
+) -> VisitResult<V::Break> {
+    for constraint in constraints {
+        if let GraphTypeConstraint::Check { condition, .. } = constraint {
+            try_visit!(visitor.visit_expression(condition));
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+/// Walks a data-modifying statement.
+pub fn walk_mutation<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    statement: $($ref)+ LinearDataModifyingStatement,
+) -> VisitResult<V::Break> {
+    if let Some(use_graph) = $($ref)+ statement.use_graph_clause {
+        try_visit!(visitor.visit_expression($($ref)+ use_graph.graph));
+    }
+    for statement in $($ref)+ statement.statements {
+        match statement {
+            SimpleDataAccessingStatement::Query(query) => {
+                try_visit!(visitor.visit_primitive_query_statement(query));
+            }
+            SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Primitive(
+                primitive,
+            )) => try_visit!(walk_primitive_data_modifying_statement(visitor, primitive)),
+            SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Call(call)) => {
+                try_visit!(walk_call_procedure_statement(visitor, $($ref)+ call.call));
+            }
+        }
+    }
+    if let Some(result) = $($ref)+ statement.primitive_result_statement {
+        try_visit!(visitor.visit_primitive_result_statement(result));
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Walks the value expressions of an INSERT, SET or DELETE statement.
+fn walk_primitive_data_modifying_statement<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    statement: $($ref)+ PrimitiveDataModifyingStatement,
+) -> VisitResult<V::Break> {
+    match statement {
+        PrimitiveDataModifyingStatement::Insert(insert) => {
+            for path in $($ref)+ insert.pattern.paths {
+                for element in $($ref)+ path.elements {
+                    let filler = match element {
+                        InsertElementPattern::Node(node) => $($ref)+ node.filler,
+                        InsertElementPattern::Edge(InsertEdgePattern::PointingLeft(edge)) => {
+                            $($ref)+ edge.filler
+                        }
+                        InsertElementPattern::Edge(InsertEdgePattern::PointingRight(edge)) => {
+                            $($ref)+ edge.filler
+                        }
+                        InsertElementPattern::Edge(InsertEdgePattern::Undirected(edge)) => {
+                            $($ref)+ edge.filler
+                        }
+                    };
+                    let Some(filler) = filler else {
+                        continue;
+                    };
+                    if let Some(properties) = $($ref)+ filler.properties {
+                        for pair in $($ref)+ properties.properties {
+                            try_visit!(visitor.visit_expression($($ref)+ pair.value));
+                        }
+                    }
+                }
+            }
+        }
+        PrimitiveDataModifyingStatement::Set(set) => {
+            for item in $($ref)+ set.items.items {
+                match item {
+                    SetItem::Property(property) => {
+                        try_visit!(visitor.visit_expression($($ref)+ property.value));
+                    }
+                    SetItem::AllProperties(all) => {
+                        for pair in $($ref)+ all.properties.properties {
+                            try_visit!(visitor.visit_expression($($ref)+ pair.value));
+                        }
+                    }
+                    SetItem::Label(_) => {}
+                }
+            }
+        }
+        PrimitiveDataModifyingStatement::Delete(delete) => {
+            for item in $($ref)+ delete.items.items {
+                try_visit!(visitor.visit_expression($($ref)+ item.expression));
+            }
+        }
+        PrimitiveDataModifyingStatement::Remove(_) => {}
     }
 
     ControlFlow::Continue(())
 }
 
+/// Walks a procedure body: its variable initializers and statement block.
+pub fn walk_procedure_body<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    body: $($ref)+ ProcedureBody,
+) -> VisitResult<V::Break> {
+    if let Some(block) = $($ref)+ body.variable_definitions {
+        for definition in $($ref)+ block.definitions {
+            let expression = match definition {
+                BindingVariableDefinition::Graph(graph) => match $($ref)+ graph.initializer {
+                    Some(GraphInitializer {
+                        expression: GraphExpression::Expression(expression),
+                        ..
+                    }) => Some(expression.$agg_access()),
+                    _ => None,
+                },
+                BindingVariableDefinition::BindingTable(table) => match $($ref)+ table.initializer {
+                    Some(BindingTableInitializer {
+                        expression: BindingTableExpression::Expression(expression),
+                        ..
+                    }) => Some(expression.$agg_access()),
+                    _ => None,
+                },
+                BindingVariableDefinition::Value(value) => ($($ref)+ value.initializer)
+                    .$agg_access()
+                    .map(|initializer| $($ref)+ initializer.expression),
+            };
+            if let Some(expression) = expression {
+                try_visit!(visitor.visit_expression(expression));
+            }
+        }
+    }
+
+    for statement in $($ref)+ body.statements.statements {
+        try_visit!(walk_procedure_statement(visitor, statement));
+    }
+    for next in $($ref)+ body.statements.next_statements {
+        if let Some(yield_clause) = $($ref)+ next.yield_clause {
+            for item in $($ref)+ yield_clause.items.items {
+                try_visit!(visitor.visit_expression($($ref)+ item.expression));
+            }
+        }
+        try_visit!(walk_procedure_statement(visitor, $($ref)+ next.statement));
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Walks a statement of a procedure body.
+fn walk_procedure_statement<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    statement: $($ref)+ ProcedureStatement,
+) -> VisitResult<V::Break> {
+    match statement {
+        ProcedureStatement::CompositeQuery(query) => visitor.visit_query(query),
+        ProcedureStatement::LinearCatalogModifying(catalog) => {
+            walk_catalog_statement(visitor, catalog)
+        }
+        ProcedureStatement::LinearDataModifying(mutation) => visitor.visit_mutation(mutation),
+    }
+}
+
 /// Walks a query statement.
 pub fn walk_query_statement<V: $trait_name + ?Sized>(
     visitor: &mut V,
@@ -197,23 +477,31 @@ pub fn walk_primitive_query_statement<V: $trait_name + ?Sized>(
         PrimitiveQueryStatement::Let(let_statement) => visitor.visit_let_statement(let_statement),
         PrimitiveQueryStatement::For(for_statement) => visitor.visit_for_statement(for_statement),
         PrimitiveQueryStatement::OrderByAndPage(order_by_and_page) => {
-            if let Some(order_by) = $($ref)+ order_by_and_page.order_by {
-                for sort in $($ref)+ order_by.sort_specifications {
-                    try_visit!(visitor.visit_expression($($ref)+ sort.key));
-                }
-            }
-            if let Some(offset) = $($ref)+ order_by_and_page.offset {
-                try_visit!(visitor.visit_expression($($ref)+ offset.count));
-            }
-            if let Some(limit) = $($ref)+ order_by_and_page.limit {
-                try_visit!(visitor.visit_expression($($ref)+ limit.count));
-            }
-            ControlFlow::Continue(())
+            walk_order_by_and_page_statement(visitor, order_by_and_page)
         }
         PrimitiveQueryStatement::Select(select) => visitor.visit_select_statement(select),
     }
 }
 
+/// Walks an ORDER BY and pagination statement.
+pub fn walk_order_by_and_page_statement<V: $trait_name + ?Sized>(
+    visitor: &mut V,
+    statement: $($ref)+ OrderByAndPageStatement,
+) -> VisitResult<V::Break> {
+    if let Some(order_by) = $($ref)+ statement.order_by {
+        for sort in $($ref)+ order_by.sort_specifications {
+            try_visit!(visitor.visit_expression($($ref)+ sort.key));
+        }
+    }
+    if let Some(offset) = $($ref)+ statement.offset {
+        try_visit!(visitor.visit_expression($($ref)+ offset.count));
+    }
+    if let Some(limit) = $($ref)+ statement.limit {
+        try_visit!(visitor.visit_expression($($ref)+ limit.count));
+    }
+    ControlFlow::Continue(())
+}
+
 /// Walks a primitive result statement.
 pub fn walk_primitive_result_statement<V: $trait_name + ?Sized>(
     visitor: &mut V,
@@ -546,6 +834,10 @@ pub fn walk_return_statement<V: $trait_name + ?Sized>(
         }
     }
 
+    if let Some(order_by_and_page) = $($ref)+ statement.order_by_and_page {
+        try_visit!(walk_order_by_and_page_statement(visitor, order_by_and_page));
+    }
+
     ControlFlow::Continue(())
 }
 
@@ -654,7 +946,13 @@ fn walk_predicate<V: $trait_name + ?Sized>(
         | Predicate::IsDirected(expression, _, _)
         | Predicate::IsTruthValue(expression, _, _, _)
         | Predicate::PropertyExists(expression, _, _) => visitor.visit_expression(expression),
-        Predicate::IsLabeled(expression, _, _, _) => visitor.visit_expression(expression),
+        Predicate::IsLabeled(expression, label, _, _) => {
+            try_visit!(visitor.visit_expression(expression));
+            if let Some(label) = label {
+                try_visit!(visitor.visit_label_expression(label));
+            }
+            ControlFlow::Continue(())
+        }
         Predicate::IsSource(source, target, _, _)
         | Predicate::IsDestination(source, target, _, _) => {
             try_visit!(visitor.visit_expression(source));
@@ -699,7 +997,9 @@ fn walk_call_procedure_statement<V: $trait_name + ?Sized>(
     call: $($ref)+ CallProcedureStatement,
 ) -> VisitResult<V::Break> {
     match $($ref)+ call.call {
-        ProcedureCall::Inline(_) => ControlFlow::Continue(()),
+        ProcedureCall::Inline(inline) => {
+            visitor.visit_procedure_body($($ref)+ inline.specification.body)
+        }
         ProcedureCall::Named(named) => {
             if let Some(arguments) = $($ref)+ named.arguments {
                 for argument in $($ref)+ arguments.arguments {
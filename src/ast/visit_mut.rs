@@ -3,16 +3,32 @@
 use std::ops::ControlFlow;
 
 use crate::ast::Expression;
+use crate::ast::catalog::{CatalogStatementKind, GraphTypeSource};
 use crate::ast::expression::{CaseExpression, ExistsVariant, Literal, Predicate};
-use crate::ast::procedure::{CallProcedureStatement, ProcedureCall};
+use crate::ast::graph_type::{
+    DirectedArcType, EdgeTypeFiller, EdgeTypePattern, ElementTypeSpecification,
+    GraphTypeConstraint, NestedGraphTypeSpecification, NodeTypeFiller, PropertyTypesSpecification,
+};
+use crate::ast::mutation::{
+    InsertEdgePattern, InsertElementPattern, LinearDataModifyingStatement,
+    PrimitiveDataModifyingStatement, SetItem, SimpleDataAccessingStatement,
+    SimpleDataModifyingStatement,
+};
+use crate::ast::procedure::{
+    BindingTableExpression, BindingTableInitializer, BindingVariableDefinition,
+    CallProcedureStatement, GraphExpression, GraphInitializer, ProcedureBody, ProcedureCall,
+    Statement as ProcedureStatement,
+};
 use crate::ast::program::{Program, QueryStatement, Statement};
 use crate::ast::query::{
     EdgePattern, ElementPattern, FilterStatement, ForStatement, GraphPattern, GroupingElement,
     LabelExpression, LetStatement, LetVariableDefinition, LinearQuery, MatchStatement, NodePattern,
-    PathFactor, PathPattern, PathPatternExpression, PathPrimary, PrimitiveQueryStatement,
-    PrimitiveResultStatement, Query, ReturnItem, ReturnItemList, ReturnStatement, SelectFromClause,
-    SelectItemList, SelectSourceItem, SelectStatement, SimplifiedPathPatternExpression,
+    OrderByAndPageStatement, PathFactor, PathPattern, PathPatternExpression, PathPrimary,
+    PrimitiveQueryStatement, PrimitiveResultStatement, Query, ReturnItem, ReturnItemList,
+    ReturnStatement, SelectFromClause, SelectItemList, SelectSourceItem, SelectStatement,
+    SimplifiedPathPatternExpression,
 };
+use crate::ast::session::{SessionCommand, SessionSetCommand, SessionSetParameterClause};
 
 use super::visit::VisitResult;
 use super::visit_macros::define_visit_api;
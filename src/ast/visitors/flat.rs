@@ -0,0 +1,382 @@
+//! Flattened AST for the language bindings.
+//!
+//! The typed AST has no stable layout across the FFI boundary, so the C ABI
+//! exposes a pre-order node table instead, and the Python bindings nest it
+//! into dictionaries. Each node records its kind, an optional kind-specific
+//! detail, its span and the index of its parent. The table covers the nodes
+//! reached by [`Visit`]: the query structure down to patterns and
+//! expressions, with other statements as single nodes.
+
+use std::ffi::CStr;
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use crate::ast::program::{Program, Statement};
+use crate::ast::query::{
+    EdgePattern, FilterStatement, ForStatement, GraphPattern, LabelExpression, LetStatement,
+    LetVariableDefinition, MatchStatement, NodePattern, PathPattern, PrimitiveQueryStatement,
+    PrimitiveResultStatement, Query, ReturnItem, ReturnStatement, SelectStatement,
+};
+use crate::ast::visit::{
+    Visit, walk_edge_pattern, walk_expression, walk_filter_statement, walk_for_statement,
+    walk_graph_pattern, walk_label_expression, walk_let_binding, walk_let_statement,
+    walk_match_statement, walk_node_pattern, walk_path_pattern, walk_primitive_query_statement,
+    walk_primitive_result_statement, walk_program, walk_query, walk_return_item,
+    walk_return_statement, walk_select_statement, walk_statement,
+};
+use crate::ast::{Expression, Span};
+#[cfg(feature = "capi")]
+use crate::diag::json::{Object, array};
+
+/// A node of the flattened AST.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FlatNode {
+    /// The AST type or expression variant, such as `NodePattern`.
+    pub kind: &'static CStr,
+    /// The variable of element patterns and LET bindings, the alias of
+    /// return items, the name of simple labels and of referenced variables,
+    /// parameters and properties.
+    pub detail: Option<SmolStr>,
+    pub span: Span,
+    /// Index of the enclosing node; `None` for the program.
+    pub parent: Option<usize>,
+    pub depth: usize,
+}
+
+/// Flattens `program` into a pre-order node table.
+pub(crate) fn flatten(program: &Program) -> Vec<FlatNode> {
+    let mut flattener = Flattener::default();
+    let _ = flattener.visit_program(program);
+    flattener.nodes
+}
+
+/// Flattens a single statement, which becomes the root of the table.
+#[cfg(feature = "python")]
+pub(crate) fn flatten_statement(statement: &Statement) -> Vec<FlatNode> {
+    let mut flattener = Flattener::default();
+    let _ = flattener.visit_statement(statement);
+    flattener.nodes
+}
+
+/// Renders a node table as a JSON object with a `nodes` array.
+#[cfg(feature = "capi")]
+pub(crate) fn to_json(nodes: &[FlatNode]) -> String {
+    let nodes = nodes.iter().map(|node| {
+        let mut object = Object::new();
+        object.string("kind", &node.kind.to_string_lossy());
+        object.optional_string("detail", node.detail.as_deref());
+        object.field("start", &node.span.start.to_string());
+        object.field("end", &node.span.end.to_string());
+        object.field(
+            "parent",
+            &node
+                .parent
+                .map_or_else(|| "null".to_string(), |parent| parent.to_string()),
+        );
+        object.field("depth", &node.depth.to_string());
+        object.finish()
+    });
+    let mut object = Object::new();
+    object.field("nodes", &array(nodes));
+    object.finish()
+}
+
+#[derive(Default)]
+struct Flattener {
+    nodes: Vec<FlatNode>,
+    stack: Vec<usize>,
+}
+
+impl Flattener {
+    /// Records a node and walks its children beneath it.
+    fn enter(
+        &mut self,
+        kind: &'static CStr,
+        detail: Option<&str>,
+        span: Span,
+        walk: impl FnOnce(&mut Self) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let index = self.nodes.len();
+        self.nodes.push(FlatNode {
+            kind,
+            detail: detail.map(SmolStr::from),
+            span,
+            parent: self.stack.last().copied(),
+            depth: self.stack.len(),
+        });
+        self.stack.push(index);
+        let flow = walk(self);
+        self.stack.pop();
+        flow
+    }
+}
+
+impl Visit for Flattener {
+    type Break = ();
+
+    fn visit_program(&mut self, program: &Program) -> ControlFlow<Self::Break> {
+        self.enter(c"Program", None, program.span.clone(), |v| {
+            walk_program(v, program)
+        })
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<Self::Break> {
+        let (kind, span) = match statement {
+            Statement::Query(statement) => (c"QueryStatement", &statement.span),
+            Statement::Mutation(statement) => (c"MutationStatement", &statement.span),
+            Statement::Session(statement) => (c"SessionStatement", &statement.span),
+            Statement::Transaction(statement) => (c"TransactionStatement", &statement.span),
+            Statement::Catalog(statement) => (c"CatalogStatement", &statement.span),
+            Statement::Empty(span) => (c"EmptyStatement", span),
+        };
+        self.enter(kind, None, span.clone(), |v| walk_statement(v, statement))
+    }
+
+    fn visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        let kind = match query {
+            Query::Linear(_) => c"LinearQuery",
+            Query::Composite(_) => c"CompositeQuery",
+            Query::Parenthesized(..) => c"ParenthesizedQuery",
+        };
+        self.enter(kind, None, query.span().clone(), |v| walk_query(v, query))
+    }
+
+    // Statements with their own visit method are recorded there.
+    fn visit_primitive_query_statement(
+        &mut self,
+        statement: &PrimitiveQueryStatement,
+    ) -> ControlFlow<Self::Break> {
+        let kind = match statement {
+            PrimitiveQueryStatement::Call(_) => c"CallStatement",
+            PrimitiveQueryStatement::OrderByAndPage(_) => c"OrderByAndPageStatement",
+            _ => return walk_primitive_query_statement(self, statement),
+        };
+        self.enter(kind, None, statement.span().clone(), |v| {
+            walk_primitive_query_statement(v, statement)
+        })
+    }
+
+    fn visit_primitive_result_statement(
+        &mut self,
+        statement: &PrimitiveResultStatement,
+    ) -> ControlFlow<Self::Break> {
+        match statement {
+            PrimitiveResultStatement::Return(_) => walk_primitive_result_statement(self, statement),
+            PrimitiveResultStatement::Finish(span) => {
+                self.enter(c"FinishStatement", None, span.clone(), |_| {
+                    ControlFlow::Continue(())
+                })
+            }
+        }
+    }
+
+    fn visit_match_statement(&mut self, statement: &MatchStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"MatchStatement", None, statement.span().clone(), |v| {
+            walk_match_statement(v, statement)
+        })
+    }
+
+    fn visit_graph_pattern(&mut self, pattern: &GraphPattern) -> ControlFlow<Self::Break> {
+        self.enter(c"GraphPattern", None, pattern.span.clone(), |v| {
+            walk_graph_pattern(v, pattern)
+        })
+    }
+
+    fn visit_path_pattern(&mut self, pattern: &PathPattern) -> ControlFlow<Self::Break> {
+        let variable = pattern
+            .variable_declaration
+            .as_ref()
+            .map(|declaration| declaration.variable.as_str());
+        self.enter(c"PathPattern", variable, pattern.span.clone(), |v| {
+            walk_path_pattern(v, pattern)
+        })
+    }
+
+    fn visit_node_pattern(&mut self, pattern: &NodePattern) -> ControlFlow<Self::Break> {
+        let variable = pattern
+            .variable
+            .as_ref()
+            .map(|declaration| declaration.variable.as_str());
+        self.enter(c"NodePattern", variable, pattern.span.clone(), |v| {
+            walk_node_pattern(v, pattern)
+        })
+    }
+
+    fn visit_edge_pattern(&mut self, pattern: &EdgePattern) -> ControlFlow<Self::Break> {
+        let (variable, span) = match pattern {
+            EdgePattern::Full(edge) => (
+                edge.filler
+                    .variable
+                    .as_ref()
+                    .map(|declaration| declaration.variable.as_str()),
+                edge.span.clone(),
+            ),
+            EdgePattern::Abbreviated(edge) => (None, edge.span().clone()),
+        };
+        self.enter(c"EdgePattern", variable, span, |v| {
+            walk_edge_pattern(v, pattern)
+        })
+    }
+
+    fn visit_label_expression(&mut self, expression: &LabelExpression) -> ControlFlow<Self::Break> {
+        let name = match expression {
+            LabelExpression::LabelName { name, .. } => Some(name.as_str()),
+            _ => None,
+        };
+        self.enter(c"LabelExpression", name, expression.span().clone(), |v| {
+            walk_label_expression(v, expression)
+        })
+    }
+
+    fn visit_filter_statement(&mut self, statement: &FilterStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"FilterStatement", None, statement.span.clone(), |v| {
+            walk_filter_statement(v, statement)
+        })
+    }
+
+    fn visit_let_statement(&mut self, statement: &LetStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"LetStatement", None, statement.span.clone(), |v| {
+            walk_let_statement(v, statement)
+        })
+    }
+
+    fn visit_let_binding(&mut self, binding: &LetVariableDefinition) -> ControlFlow<Self::Break> {
+        let variable = Some(binding.variable.name.as_str());
+        self.enter(c"LetBinding", variable, binding.span.clone(), |v| {
+            walk_let_binding(v, binding)
+        })
+    }
+
+    fn visit_for_statement(&mut self, statement: &ForStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"ForStatement", None, statement.span.clone(), |v| {
+            walk_for_statement(v, statement)
+        })
+    }
+
+    fn visit_select_statement(&mut self, statement: &SelectStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"SelectStatement", None, statement.span.clone(), |v| {
+            walk_select_statement(v, statement)
+        })
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) -> ControlFlow<Self::Break> {
+        self.enter(c"ReturnStatement", None, statement.span.clone(), |v| {
+            walk_return_statement(v, statement)
+        })
+    }
+
+    fn visit_return_item(&mut self, item: &ReturnItem) -> ControlFlow<Self::Break> {
+        self.enter(
+            c"ReturnItem",
+            item.alias.as_deref(),
+            item.span.clone(),
+            |v| walk_return_item(v, item),
+        )
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> ControlFlow<Self::Break> {
+        let (kind, detail) = expression_kind(expression);
+        self.enter(kind, detail, expression.span(), |v| {
+            walk_expression(v, expression)
+        })
+    }
+}
+
+/// Returns the variant name of an expression and the name it refers to.
+fn expression_kind(expression: &Expression) -> (&'static CStr, Option<&str>) {
+    match expression {
+        Expression::Literal(..) => (c"Literal", None),
+        Expression::Unary(..) => (c"Unary", None),
+        Expression::Binary(..) => (c"Binary", None),
+        Expression::Comparison(..) => (c"Comparison", None),
+        Expression::Logical(..) => (c"Logical", None),
+        Expression::Parenthesized(..) => (c"Parenthesized", None),
+        Expression::PropertyReference(_, property, _) => {
+            (c"PropertyReference", Some(property.as_str()))
+        }
+        Expression::VariableReference(name, _) => (c"VariableReference", Some(name.as_str())),
+        Expression::ParameterReference(name, _) => (c"ParameterReference", Some(name.as_str())),
+        Expression::FunctionCall(_) => (c"FunctionCall", None),
+        Expression::Case(_) => (c"Case", None),
+        Expression::Cast(_) => (c"Cast", None),
+        Expression::AggregateFunction(_) => (c"AggregateFunction", None),
+        Expression::TypeAnnotation(..) => (c"TypeAnnotation", None),
+        Expression::ListConstructor(..) => (c"ListConstructor", None),
+        Expression::RecordConstructor(..) => (c"RecordConstructor", None),
+        Expression::PathConstructor(..) => (c"PathConstructor", None),
+        Expression::Exists(_) => (c"Exists", None),
+        Expression::Predicate(_) => (c"Predicate", None),
+        Expression::GraphExpression(..) => (c"GraphExpression", None),
+        Expression::BindingTableExpression(..) => (c"BindingTableExpression", None),
+        Expression::SubqueryExpression(..) => (c"SubqueryExpression", None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn flatten_links_nodes_to_their_parents() {
+        let source = "MATCH (n:Person)-[e]->(m) RETURN n.name AS name";
+        let program = parse(source).ast.expect("expected AST");
+        let nodes = flatten(&program);
+        let describe: Vec<String> = nodes
+            .iter()
+            .map(|node| {
+                let kind = node.kind.to_str().unwrap();
+                let indent = "  ".repeat(node.depth);
+                match &node.detail {
+                    Some(detail) => format!("{indent}{kind} {detail}"),
+                    None => format!("{indent}{kind}"),
+                }
+            })
+            .collect();
+        assert_eq!(
+            describe,
+            [
+                "Program",
+                "  QueryStatement",
+                "    LinearQuery",
+                "      MatchStatement",
+                "        GraphPattern",
+                "          PathPattern",
+                "            NodePattern n",
+                "              LabelExpression Person",
+                "            EdgePattern e",
+                "            NodePattern m",
+                "      ReturnStatement",
+                "        ReturnItem name",
+                "          PropertyReference name",
+                "            VariableReference n",
+            ]
+        );
+        for (index, node) in nodes.iter().enumerate().skip(1) {
+            let parent = &nodes[node.parent.unwrap()];
+            assert!(node.parent.unwrap() < index);
+            assert_eq!(parent.depth + 1, node.depth);
+        }
+        assert_eq!(&source[nodes[6].span.clone()], "(n:Person)");
+    }
+
+    #[cfg(feature = "capi")]
+    #[test]
+    fn to_json_renders_the_node_table() {
+        let program = parse("MATCH (n:Person)-[e]->(m) RETURN n.name AS name")
+            .ast
+            .expect("expected AST");
+        let nodes = flatten(&program);
+        let json = to_json(&nodes[..2]);
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"nodes":[{"kind":"Program","detail":null,"start":0,"end":47,"#,
+                r#""parent":null,"depth":0},"#,
+                r#"{"kind":"QueryStatement","detail":null,"start":0,"end":43,"#,
+                r#""parent":0,"depth":1}]}"#,
+            )
+        );
+    }
+}
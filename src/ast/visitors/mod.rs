@@ -1,6 +1,8 @@
 //! Ready-to-use AST visitors.
 
 pub mod collecting;
+#[cfg(any(feature = "capi", feature = "python"))]
+pub(crate) mod flat;
 pub mod span;
 pub mod variable;
 
@@ -69,15 +69,7 @@ impl Visit for SpanCollector {
     }
 
     fn visit_statement(&mut self, statement: &Statement) -> ControlFlow<Self::Break> {
-        let span = match statement {
-            Statement::Query(statement) => statement.span.clone(),
-            Statement::Mutation(statement) => statement.span.clone(),
-            Statement::Session(statement) => statement.span.clone(),
-            Statement::Transaction(statement) => statement.span.clone(),
-            Statement::Catalog(statement) => statement.span.clone(),
-            Statement::Empty(span) => span.clone(),
-        };
-        self.push_span(span);
+        self.push_span(statement.span());
         walk_statement(self, statement)
     }
 
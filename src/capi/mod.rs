@@ -0,0 +1,480 @@
+//! C ABI for embedding the parser in C and C++ programs.
+//!
+//! Enabled by the `capi` feature. The `gql_parser-capi` package in `capi/`
+//! builds the static and shared libraries:
+//!
+//! ```text
+//! cargo build --release -p gql_parser-capi
+//! ```
+//!
+//! Link `target/release/libgql_parser_capi.a` (or the shared library) and
+//! include `capi/include/gql_parser.h`, which cbindgen generates from this
+//! module. Parsing returns an opaque [`GqlResult`] that owns a flattened AST
+//! and the diagnostics; C callers read them through index-based accessors
+//! or fetch the AST as JSON. Returned strings are NUL-terminated UTF-8 and
+//! stay valid until the result is released with [`gql_result_free`].
+//!
+//! Spans are byte offsets into the source text, with 1-based lines and
+//! columns counting characters. Accessors accept null results and
+//! out-of-range indices, returning zero, `false` or null.
+
+use std::ffi::{CString, c_char};
+use std::panic;
+use std::ptr;
+use std::slice;
+
+use crate::ast::visitors::flat::{self, FlatNode};
+use crate::ast::{Program, Span};
+use crate::diag::{Diag, DiagSeverity, LabelRole, SourceFile};
+use crate::semantic::SemanticValidator;
+
+/// Parent index of the root node.
+// Spelled as a cast for cbindgen, which cannot evaluate `usize::MAX`.
+#[allow(clippy::unnecessary_cast)]
+pub const GQL_NO_PARENT: usize = !0 as usize;
+
+/// Severity of a diagnostic.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GqlSeverity {
+    Error,
+    Warning,
+    Note,
+}
+
+/// A source range with its start and end positions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GqlSpan {
+    /// Byte offset of the first character.
+    pub start: usize,
+    /// Byte offset past the last character.
+    pub end: usize,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// A node of the flattened AST.
+///
+/// Nodes are stored in pre-order, so children follow their parent and a
+/// node's subtree ends at the next node with the same or smaller depth.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct GqlNode {
+    /// The AST type or expression variant, such as `NodePattern` or
+    /// `Comparison`.
+    pub kind: *const c_char,
+    /// The variable of element patterns and LET bindings, the alias of
+    /// return items, the name of simple labels and of referenced variables,
+    /// parameters and properties; null otherwise.
+    pub detail: *const c_char,
+    pub span: GqlSpan,
+    /// Index of the enclosing node, or `GQL_NO_PARENT` for the program.
+    pub parent: usize,
+    /// Nesting depth, 0 for the program.
+    pub depth: usize,
+}
+
+/// A diagnostic reported by the parser or the validator.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct GqlDiagnostic {
+    pub severity: GqlSeverity,
+    /// Diagnostic code, or null.
+    pub code: *const c_char,
+    pub message: *const c_char,
+    /// Suggested fix, or null.
+    pub help: *const c_char,
+    /// Whether the diagnostic has a label; `span` is zeroed otherwise.
+    pub has_span: bool,
+    /// Span of the primary label, or of the first label if none is primary.
+    pub span: GqlSpan,
+    pub label_count: usize,
+    pub note_count: usize,
+}
+
+/// A labeled span of a diagnostic.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct GqlLabel {
+    /// Whether the label marks the cause of the diagnostic rather than
+    /// related context.
+    pub primary: bool,
+    /// Label text, possibly empty.
+    pub message: *const c_char,
+    pub span: GqlSpan,
+}
+
+/// The outcome of parsing a query, owning its AST and diagnostics.
+pub struct GqlResult {
+    source: SourceFile,
+    has_ast: bool,
+    nodes: Vec<Node>,
+    ast_json: Option<CString>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A flattened node with its strings converted for C.
+struct Node {
+    node: FlatNode,
+    detail: Option<CString>,
+}
+
+/// A diagnostic with its strings converted for C.
+struct Diagnostic {
+    diag: Diag,
+    message: CString,
+    code: Option<CString>,
+    help: Option<CString>,
+    labels: Vec<CString>,
+    notes: Vec<CString>,
+}
+
+impl GqlResult {
+    fn new(source: SourceFile, ast: Option<&Program>, diagnostics: Vec<Diag>) -> Self {
+        let nodes = ast.map(flat::flatten).unwrap_or_default();
+        let ast_json = ast.map(|_| c_string(&flat::to_json(&nodes)));
+        let nodes = nodes
+            .into_iter()
+            .map(|node| Node {
+                detail: node.detail.as_deref().map(c_string),
+                node,
+            })
+            .collect();
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diag| Diagnostic {
+                message: c_string(&diag.message),
+                code: diag.code.as_deref().map(c_string),
+                help: diag.help.as_deref().map(c_string),
+                labels: diag
+                    .labels
+                    .iter()
+                    .map(|label| c_string(&label.message))
+                    .collect(),
+                notes: diag.notes.iter().map(|note| c_string(note)).collect(),
+                diag,
+            })
+            .collect();
+        Self {
+            source,
+            has_ast: ast.is_some(),
+            nodes,
+            ast_json,
+            diagnostics,
+        }
+    }
+
+    fn span(&self, span: &Span) -> GqlSpan {
+        let span = self.source.clamp_span(span);
+        let start = self.source.line_column(span.start);
+        let end = self.source.line_column(span.end);
+        GqlSpan {
+            start: span.start,
+            end: span.end,
+            start_line: start.line,
+            start_column: start.column,
+            end_line: end.line,
+            end_column: end.column,
+        }
+    }
+}
+
+/// Converts text to a C string, dropping interior NUL characters.
+fn c_string(text: &str) -> CString {
+    CString::new(text.replace('\0', "")).expect("NUL characters were removed")
+}
+
+fn c_ptr(text: Option<&CString>) -> *const c_char {
+    text.map_or(ptr::null(), |text| text.as_ptr())
+}
+
+/// Parses `text`, running semantic validation if requested and parsing
+/// reported nothing.
+fn run(text: &str, validate: bool) -> GqlResult {
+    let validator = validate.then(SemanticValidator::new);
+    let (ast, diagnostics) = crate::parse_to_diagnostics(text, validator.as_ref());
+    GqlResult::new(SourceFile::new(text), ast.as_ref(), diagnostics)
+}
+
+/// Reads the source text and runs the parser behind a panic guard.
+///
+/// # Safety
+///
+/// `source` must be null or point to `length` readable bytes.
+unsafe fn create(source: *const c_char, length: usize, validate: bool) -> *mut GqlResult {
+    let bytes = if length == 0 {
+        &[][..]
+    } else if source.is_null() {
+        return ptr::null_mut();
+    } else {
+        // SAFETY: the caller guarantees `length` readable bytes.
+        unsafe { slice::from_raw_parts(source.cast::<u8>(), length) }
+    };
+    let result = panic::catch_unwind(|| match std::str::from_utf8(bytes) {
+        Ok(text) => run(text, validate),
+        Err(error) => {
+            let valid = error.valid_up_to();
+            let text = std::str::from_utf8(&bytes[..valid]).unwrap_or_default();
+            let diag = Diag::error("source is not valid UTF-8")
+                .with_primary_label(valid..valid, "invalid byte sequence");
+            GqlResult::new(SourceFile::new(text), None, vec![diag])
+        }
+    });
+    result.map_or(ptr::null_mut(), |result| Box::into_raw(Box::new(result)))
+}
+
+/// Returns the library version as a static string.
+#[unsafe(no_mangle)]
+pub extern "C" fn gql_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Parses GQL source text.
+///
+/// Returns a result to release with `gql_result_free`, or null if `source`
+/// is null with a non-zero `length` or the parser failed unexpectedly.
+/// Source text that is not UTF-8 yields a result with one error.
+///
+/// # Safety
+///
+/// `source` must be null or point to `length` readable bytes; it does not
+/// need to be NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_parse(source: *const c_char, length: usize) -> *mut GqlResult {
+    // SAFETY: forwarded from the caller.
+    unsafe { create(source, length, false) }
+}
+
+/// Parses GQL source text and validates it semantically if it parsed
+/// without diagnostics, like `gql_parse` otherwise.
+///
+/// # Safety
+///
+/// `source` must be null or point to `length` readable bytes; it does not
+/// need to be NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_parse_and_validate(
+    source: *const c_char,
+    length: usize,
+) -> *mut GqlResult {
+    // SAFETY: forwarded from the caller.
+    unsafe { create(source, length, true) }
+}
+
+/// Releases a result and every string obtained from it.
+///
+/// # Safety
+///
+/// `result` must be null or a result returned by this library that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_free(result: *mut GqlResult) {
+    if !result.is_null() {
+        // SAFETY: the caller passes ownership of a live result.
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+/// Returns whether parsing produced an AST, which it may do despite errors
+/// by recovering at statement boundaries.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_has_ast(result: *const GqlResult) -> bool {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }.is_some_and(|result| result.has_ast)
+}
+
+/// Returns whether any diagnostic is an error.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_has_errors(result: *const GqlResult) -> bool {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }.is_some_and(|result| {
+        result
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.diag.severity == DiagSeverity::Error)
+    })
+}
+
+/// Returns the flattened AST as a JSON object, or null without an AST.
+///
+/// The object has a `nodes` array holding the nodes in pre-order, each with
+/// the fields `kind`, `detail`, `start`, `end`, `parent` and `depth` of
+/// `GqlNode`; `detail` and `parent` may be `null`.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_ast_json(result: *const GqlResult) -> *const c_char {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }.map_or(ptr::null(), |result| c_ptr(result.ast_json.as_ref()))
+}
+
+/// Returns the number of nodes in the flattened AST.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_node_count(result: *const GqlResult) -> usize {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }.map_or(0, |result| result.nodes.len())
+}
+
+/// Writes the node at `index` to `out`, returning false if there is none.
+///
+/// # Safety
+///
+/// `result` must be null or a live result, and `out` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_node(
+    result: *const GqlResult,
+    index: usize,
+    out: *mut GqlNode,
+) -> bool {
+    // SAFETY: the caller passes a live result or null.
+    let Some(result) = (unsafe { result.as_ref() }) else {
+        return false;
+    };
+    let Some(Node { node, detail }) = result.nodes.get(index) else {
+        return false;
+    };
+    if out.is_null() {
+        return false;
+    }
+    let node = GqlNode {
+        kind: node.kind.as_ptr(),
+        detail: c_ptr(detail.as_ref()),
+        span: result.span(&node.span),
+        parent: node.parent.unwrap_or(GQL_NO_PARENT),
+        depth: node.depth,
+    };
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { out.write(node) };
+    true
+}
+
+/// Returns the number of diagnostics.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_diagnostic_count(result: *const GqlResult) -> usize {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }.map_or(0, |result| result.diagnostics.len())
+}
+
+/// Writes the diagnostic at `index` to `out`, returning false if there is
+/// none.
+///
+/// # Safety
+///
+/// `result` must be null or a live result, and `out` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_diagnostic(
+    result: *const GqlResult,
+    index: usize,
+    out: *mut GqlDiagnostic,
+) -> bool {
+    // SAFETY: the caller passes a live result or null.
+    let Some(result) = (unsafe { result.as_ref() }) else {
+        return false;
+    };
+    let Some(diagnostic) = result.diagnostics.get(index) else {
+        return false;
+    };
+    if out.is_null() {
+        return false;
+    }
+    let diag = &diagnostic.diag;
+    let label = diag
+        .labels
+        .iter()
+        .find(|label| label.role == LabelRole::Primary)
+        .or(diag.labels.first());
+    let value = GqlDiagnostic {
+        severity: match diag.severity {
+            DiagSeverity::Error => GqlSeverity::Error,
+            DiagSeverity::Warning => GqlSeverity::Warning,
+            DiagSeverity::Note => GqlSeverity::Note,
+        },
+        code: c_ptr(diagnostic.code.as_ref()),
+        message: diagnostic.message.as_ptr(),
+        help: c_ptr(diagnostic.help.as_ref()),
+        has_span: label.is_some(),
+        span: label.map_or_else(GqlSpan::default, |label| result.span(&label.span)),
+        label_count: diag.labels.len(),
+        note_count: diag.notes.len(),
+    };
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { out.write(value) };
+    true
+}
+
+/// Writes label `label` of diagnostic `diagnostic` to `out`, returning
+/// false if there is none.
+///
+/// # Safety
+///
+/// `result` must be null or a live result, and `out` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_diagnostic_label(
+    result: *const GqlResult,
+    diagnostic: usize,
+    label: usize,
+    out: *mut GqlLabel,
+) -> bool {
+    // SAFETY: the caller passes a live result or null.
+    let Some(result) = (unsafe { result.as_ref() }) else {
+        return false;
+    };
+    let Some(diagnostic) = result.diagnostics.get(diagnostic) else {
+        return false;
+    };
+    let (Some(value), Some(message)) = (
+        diagnostic.diag.labels.get(label),
+        diagnostic.labels.get(label),
+    ) else {
+        return false;
+    };
+    if out.is_null() {
+        return false;
+    }
+    let value = GqlLabel {
+        primary: value.role == LabelRole::Primary,
+        message: message.as_ptr(),
+        span: result.span(&value.span),
+    };
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { out.write(value) };
+    true
+}
+
+/// Returns note `note` of diagnostic `diagnostic`, or null if there is none.
+///
+/// # Safety
+///
+/// `result` must be null or a live result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gql_result_diagnostic_note(
+    result: *const GqlResult,
+    diagnostic: usize,
+    note: usize,
+) -> *const c_char {
+    // SAFETY: the caller passes a live result or null.
+    unsafe { result.as_ref() }
+        .and_then(|result| result.diagnostics.get(diagnostic))
+        .map_or(ptr::null(), |diagnostic| c_ptr(diagnostic.notes.get(note)))
+}
@@ -0,0 +1,201 @@
+//! Line-delimited JSON diagnostic output.
+
+use super::source_map::resolve;
+use super::{Diag, LabelRole, SourceFile, SourceLookup};
+
+/// Renders diagnostics as JSON lines, one object per diagnostic.
+///
+/// Each object has the fields `severity`, `code`, `message`, `source` (the
+/// name given to [`SourceFile::with_name`]), `labels`, `help` and `notes`;
+/// absent values are `null`. Each label has a `role`, a `message`, the
+/// `source` it points into, and `start` and `end` positions with a byte
+/// `offset` and a 1-based `line` and `column`:
+///
+/// ```
+/// use gql_parser::diag::{Diag, SourceFile, render_json_lines};
+///
+/// let source = SourceFile::with_name("MATCH (n) RETURN m", "query.gql");
+/// let diag = Diag::error("undefined variable").with_primary_label(17..18, "not bound");
+/// let output = render_json_lines(&[diag], &source);
+/// assert!(output.starts_with(r#"{"severity":"error","code":null,"message":"undefined"#));
+/// assert!(output.contains(r#""start":{"offset":17,"line":1,"column":18}"#));
+/// assert!(output.ends_with("}\n"));
+/// ```
+pub fn render_json_lines<S: SourceLookup + ?Sized>(diagnostics: &[Diag], sources: &S) -> String {
+    let mut output = String::new();
+    for diag in diagnostics {
+        output.push_str(&diagnostic(diag, sources));
+        output.push('\n');
+    }
+    output
+}
+
+fn diagnostic<S: SourceLookup + ?Sized>(diag: &Diag, sources: &S) -> String {
+    let empty = SourceFile::new("");
+    let source = resolve(sources, diag.file, &empty);
+    let labels = diag.labels.iter().map(|label| {
+        let file = resolve(sources, label.file.or(diag.file), &empty);
+        let span = file.clamp_span(&label.span);
+        let mut object = Object::new();
+        object.string(
+            "role",
+            match label.role {
+                LabelRole::Primary => "primary",
+                LabelRole::Secondary => "secondary",
+            },
+        );
+        object.string("message", &label.message);
+        object.optional_string("source", file.name());
+        object.field("start", &position(file, span.start));
+        object.field("end", &position(file, span.end));
+        object.finish()
+    });
+
+    let mut object = Object::new();
+    object.string("severity", &diag.severity.to_string());
+    object.optional_string("code", diag.code.as_deref());
+    object.string("message", &diag.message);
+    object.optional_string("source", source.name());
+    object.field("labels", &array(labels));
+    object.optional_string("help", diag.help.as_deref());
+    object.field("notes", &array(diag.notes.iter().map(|note| string(note))));
+    object.finish()
+}
+
+fn position(source: &SourceFile, offset: usize) -> String {
+    let location = source.line_column(offset);
+    let mut object = Object::new();
+    object.field("offset", &offset.to_string());
+    object.field("line", &location.line.to_string());
+    object.field("column", &location.column.to_string());
+    object.finish()
+}
+
+/// A JSON object written field by field.
+pub(crate) struct Object {
+    output: String,
+}
+
+impl Object {
+    pub(crate) fn new() -> Self {
+        Self {
+            output: String::from("{"),
+        }
+    }
+
+    /// Adds a field whose value is already JSON.
+    pub(crate) fn field(&mut self, key: &str, value: &str) {
+        if self.output.len() > 1 {
+            self.output.push(',');
+        }
+        self.output.push_str(&string(key));
+        self.output.push(':');
+        self.output.push_str(value);
+    }
+
+    pub(crate) fn string(&mut self, key: &str, value: &str) {
+        self.field(key, &string(value));
+    }
+
+    /// Adds a string field, or `null` if `value` is `None`.
+    pub(crate) fn optional_string(&mut self, key: &str, value: Option<&str>) {
+        self.field(key, &value.map_or_else(|| "null".to_string(), string));
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        self.output.push('}');
+        self.output
+    }
+}
+
+/// Joins JSON values into an array.
+pub(crate) fn array(values: impl IntoIterator<Item = String>) -> String {
+    let values: Vec<String> = values.into_iter().collect();
+    format!("[{}]", values.join(","))
+}
+
+/// Quotes and escapes a JSON string.
+pub(crate) fn string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if u32::from(c) < 0x20 => output.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diag::{FileSpan, SourceMap};
+
+    #[test]
+    fn string_escapes_quotes_and_control_characters() {
+        assert_eq!(string("plain"), r#""plain""#);
+        assert_eq!(string("a \"b\" \\ c"), r#""a \"b\" \\ c""#);
+        assert_eq!(string("line\nnext\ttab\u{1}"), r#""line\nnext\ttab\u0001""#);
+        assert_eq!(string("é"), "\"é\"");
+    }
+
+    #[test]
+    fn render_json_lines_writes_one_object_per_diagnostic() {
+        let source = SourceFile::new("MATCH (n)\nRETURN m");
+        let diagnostics = vec![
+            Diag::error("undefined variable")
+                .with_code("E100")
+                .with_primary_label(17..18, "not bound")
+                .with_secondary_label(7..8, "did you mean `n`?")
+                .with_help("bind `m` in MATCH")
+                .with_note("variables are scoped to the query"),
+            Diag::warning("unused variable"),
+        ];
+        let output = render_json_lines(&diagnostics, &source);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            concat!(
+                r#"{"severity":"error","code":"E100","message":"undefined variable","#,
+                r#""source":null,"labels":[{"role":"primary","message":"not bound","source":null,"#,
+                r#""start":{"offset":17,"line":2,"column":8},"#,
+                r#""end":{"offset":18,"line":2,"column":9}},"#,
+                r#"{"role":"secondary","message":"did you mean `n`?","source":null,"#,
+                r#""start":{"offset":7,"line":1,"column":8},"#,
+                r#""end":{"offset":8,"line":1,"column":9}}],"#,
+                r#""help":"bind `m` in MATCH","notes":["variables are scoped to the query"]}"#,
+            )
+        );
+        assert_eq!(
+            lines[1],
+            concat!(
+                r#"{"severity":"warning","code":null,"message":"unused variable","#,
+                r#""source":null,"labels":[],"help":null,"notes":[]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn render_json_lines_names_the_file_of_each_label() {
+        let mut sources = SourceMap::new();
+        let query = sources.add_file("query.gql", "MATCH (p:Person) RETURN p");
+        let schema = sources.add_file("schema.gql", "NODE Person");
+        let diag = Diag::error("unknown label")
+            .with_file(query)
+            .with_primary_label(9..15, "here")
+            .with_secondary_label_in(FileSpan::new(schema, 5..11), "declared");
+        let output = render_json_lines(&[diag], &sources);
+        assert!(output.contains(r#""message":"unknown label","source":"query.gql""#));
+        assert!(output.contains(
+            r#""message":"declared","source":"schema.gql","start":{"offset":5,"line":1,"column":6}"#
+        ));
+    }
+}
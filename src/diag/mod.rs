@@ -4,6 +4,16 @@ use crate::ast::Span;
 use miette::{Diagnostic, LabeledSpan, Report, Severity};
 use std::fmt;
 
+pub(crate) mod json;
+mod sarif;
+mod source_map;
+mod text;
+
+pub use json::render_json_lines;
+pub use sarif::{SarifLog, render_sarif};
+pub use source_map::{FileId, FileSpan, SourceLookup, SourceMap};
+pub use text::render_text;
+
 /// Severity level for a diagnostic.
 ///
 /// This covers the full taxonomy required for syntax-phase diagnostics:
@@ -41,7 +51,8 @@ pub enum LabelRole {
 /// A labeled span within a diagnostic.
 ///
 /// Each label associates a span with explanatory text and indicates
-/// whether it's the primary focus or a supporting context.
+/// whether it's the primary focus or a supporting context. A label may
+/// point into another file of a [`SourceMap`] than its diagnostic.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiagLabel {
     /// The span this label refers to.
@@ -50,6 +61,8 @@ pub struct DiagLabel {
     pub message: String,
     /// Whether this is a primary or secondary label.
     pub role: LabelRole,
+    /// The file the span belongs to; `None` for the diagnostic's file.
+    pub file: Option<FileId>,
 }
 
 impl DiagLabel {
@@ -59,6 +72,7 @@ impl DiagLabel {
             span,
             message: message.into(),
             role: LabelRole::Primary,
+            file: None,
         }
     }
 
@@ -68,8 +82,15 @@ impl DiagLabel {
             span,
             message: message.into(),
             role: LabelRole::Secondary,
+            file: None,
         }
     }
+
+    /// Points this label into `file`.
+    pub fn in_file(mut self, file: FileId) -> Self {
+        self.file = Some(file);
+        self
+    }
 }
 
 /// A structured diagnostic message.
@@ -91,6 +112,9 @@ pub struct Diag {
     pub notes: Vec<String>,
     /// Optional diagnostic code (e.g., "E0001" or "syntax::unclosed_string").
     pub code: Option<String>,
+    /// The file the diagnostic is reported in, when rendered against a
+    /// [`SourceMap`]; `None` for its first file.
+    pub file: Option<FileId>,
 }
 
 impl Diag {
@@ -103,6 +127,7 @@ impl Diag {
             help: None,
             notes: Vec::new(),
             code: None,
+            file: None,
         }
     }
 
@@ -156,6 +181,26 @@ impl Diag {
         self.code = Some(code.into());
         self
     }
+
+    /// Sets the file the diagnostic is reported in.
+    pub fn with_file(mut self, file: FileId) -> Self {
+        self.file = Some(file);
+        self
+    }
+
+    /// Adds a primary label pointing into a specific file.
+    pub fn with_primary_label_in(mut self, span: FileSpan, message: impl Into<String>) -> Self {
+        self.labels
+            .push(DiagLabel::primary(span.span, message).in_file(span.file));
+        self
+    }
+
+    /// Adds a secondary label pointing into a specific file.
+    pub fn with_secondary_label_in(mut self, span: FileSpan, message: impl Into<String>) -> Self {
+        self.labels
+            .push(DiagLabel::secondary(span.span, message).in_file(span.file));
+        self
+    }
 }
 
 /// A wrapper around source text for diagnostic rendering.
@@ -169,13 +214,29 @@ pub struct SourceFile {
     content: String,
     /// Optional filename for display purposes.
     name: Option<String>,
+    /// Byte offsets at which each line starts.
+    line_starts: Vec<usize>,
+}
+
+/// A 1-based line and column in a source file.
+///
+/// Columns count Unicode scalar values, so a multi-byte character occupies
+/// a single column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineColumn {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
 }
 
 impl SourceFile {
     /// Creates a new source file from the given content.
     pub fn new(content: impl Into<String>) -> Self {
+        let content = content.into();
         Self {
-            content: content.into(),
+            line_starts: line_starts(&content),
+            content,
             name: None,
         }
     }
@@ -183,8 +244,8 @@ impl SourceFile {
     /// Creates a new source file with a name.
     pub fn with_name(content: impl Into<String>, name: impl Into<String>) -> Self {
         Self {
-            content: content.into(),
             name: Some(name.into()),
+            ..Self::new(content)
         }
     }
 
@@ -210,6 +271,44 @@ impl SourceFile {
         let end = span.end.min(len).max(start);
         start..end
     }
+
+    /// Returns the line and column of a byte offset, clamped to the source.
+    ///
+    /// Offsets inside a multi-byte character resolve to that character.
+    pub fn line_column(&self, offset: usize) -> LineColumn {
+        let mut offset = offset.min(self.content.len());
+        while !self.content.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let index = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let start = self.line_starts[index];
+        LineColumn {
+            line: index + 1,
+            column: self.content[start..offset].chars().count() + 1,
+        }
+    }
+
+    /// Returns the text of a 1-based line, without its line terminator.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.content.len());
+        Some(self.content[start..end].trim_end_matches(['\n', '\r']))
+    }
+
+    /// Returns the number of lines in this source.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+}
+
+fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
 }
 
 /// Converts internal diagnostics to miette Reports with source context.
@@ -217,7 +316,12 @@ impl SourceFile {
 /// This function provides the bridge from our internal diagnostic model
 /// to miette's rich error reporting. It safely handles invalid spans and
 /// preserves all diagnostic information (labels, help, notes, severity).
-pub fn convert_diagnostics_to_reports(diagnostics: &[Diag], source: &SourceFile) -> Vec<Report> {
+///
+/// `source` is either a single [`SourceFile`] or a [`SourceMap`].
+pub fn convert_diagnostics_to_reports<S: SourceLookup + ?Sized>(
+    diagnostics: &[Diag],
+    source: &S,
+) -> Vec<Report> {
     diagnostics
         .iter()
         .map(|diag| convert_diag_to_report(diag, source))
@@ -232,8 +336,12 @@ pub fn convert_diagnostics_to_reports(diagnostics: &[Diag], source: &SourceFile)
 /// - Attaching help text and notes
 /// - Including diagnostic codes
 /// - Safely handling out-of-bounds spans
-pub fn convert_diag_to_report(diag: &Diag, source: &SourceFile) -> Report {
-    let diagnostic = build_diagnostic(diag, source);
+/// - Showing labels in other files of a [`SourceMap`] as related
+///   diagnostics with their own source context
+pub fn convert_diag_to_report<S: SourceLookup + ?Sized>(diag: &Diag, sources: &S) -> Report {
+    let empty = SourceFile::new("");
+    let source = source_map::resolve(sources, diag.file, &empty);
+    let diagnostic = build_diagnostic(diag, sources);
 
     // Create the report with source context
     let mut report = Report::new(diagnostic);
@@ -249,11 +357,16 @@ pub fn convert_diag_to_report(diag: &Diag, source: &SourceFile) -> Report {
     report
 }
 
-fn build_diagnostic(diag: &Diag, source: &SourceFile) -> BuiltDiagnostic {
-    // Build the labels first
+fn build_diagnostic<S: SourceLookup + ?Sized>(diag: &Diag, sources: &S) -> BuiltDiagnostic {
+    let empty = SourceFile::new("");
+    let source = source_map::resolve(sources, diag.file, &empty);
+
+    // Build the labels first, grouping those in other files by file
     let mut labels = Vec::new();
+    let mut locations: Vec<(&SourceFile, LocationDiagnostic)> = Vec::new();
     for label in &diag.labels {
-        let clamped_span = source.clamp_span(&label.span);
+        let file = source_map::resolve(sources, label.file.or(diag.file), &empty);
+        let clamped_span = file.clamp_span(&label.span);
         let span = (clamped_span.start, clamped_span.end - clamped_span.start);
         let labeled_span = match label.role {
             LabelRole::Primary => {
@@ -261,7 +374,18 @@ fn build_diagnostic(diag: &Diag, source: &SourceFile) -> BuiltDiagnostic {
             }
             LabelRole::Secondary => LabeledSpan::new_with_span(Some(label.message.clone()), span),
         };
-        labels.push(labeled_span);
+        if std::ptr::eq(file, source) {
+            labels.push(labeled_span);
+        } else if let Some((_, location)) = locations
+            .iter_mut()
+            .find(|(other, _)| std::ptr::eq(*other, file))
+        {
+            location.labels.push(labeled_span);
+        } else {
+            let mut location = LocationDiagnostic::new(file);
+            location.labels.push(labeled_span);
+            locations.push((file, location));
+        }
     }
 
     // Create the diagnostic struct
@@ -275,6 +399,10 @@ fn build_diagnostic(diag: &Diag, source: &SourceFile) -> BuiltDiagnostic {
         code: diag.code.clone(),
         help: diag.help.clone(),
         labels,
+        locations: locations
+            .into_iter()
+            .map(|(_, location)| location)
+            .collect(),
         related: diag
             .notes
             .iter()
@@ -292,9 +420,52 @@ struct BuiltDiagnostic {
     code: Option<String>,
     help: Option<String>,
     labels: Vec<LabeledSpan>,
+    locations: Vec<LocationDiagnostic>,
     related: Vec<NoteDiagnostic>,
 }
 
+/// Labels pointing into a file other than the diagnostic's, shown with the
+/// source of that file.
+#[derive(Debug)]
+struct LocationDiagnostic {
+    name: String,
+    labels: Vec<LabeledSpan>,
+    source_code: miette::NamedSource<String>,
+}
+
+impl LocationDiagnostic {
+    fn new(file: &SourceFile) -> Self {
+        let name = file.name().unwrap_or("<source>").to_string();
+        Self {
+            source_code: miette::NamedSource::new(&name, file.content().to_string()),
+            name,
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for LocationDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl std::error::Error for LocationDiagnostic {}
+
+impl Diagnostic for LocationDiagnostic {
+    fn severity(&self) -> Option<Severity> {
+        Some(Severity::Advice)
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.source_code)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.clone().into_iter()))
+    }
+}
+
 #[derive(Debug)]
 struct NoteDiagnostic {
     message: String,
@@ -347,12 +518,12 @@ impl Diagnostic for BuiltDiagnostic {
     }
 
     fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
-        if self.related.is_empty() {
+        if self.locations.is_empty() && self.related.is_empty() {
             None
         } else {
-            Some(Box::new(
-                self.related.iter().map(|diag| diag as &dyn Diagnostic),
-            ))
+            let locations = self.locations.iter().map(|diag| diag as &dyn Diagnostic);
+            let notes = self.related.iter().map(|diag| diag as &dyn Diagnostic);
+            Some(Box::new(locations.chain(notes)))
         }
     }
 }
@@ -456,6 +627,24 @@ mod tests {
         assert_eq!(src.clamp_span(&(10..20)), 5..5);
     }
 
+    #[test]
+    fn source_file_line_column() {
+        let src = SourceFile::new("MATCH (n)\r\nRETURN é, n\n");
+        assert_eq!(src.line_count(), 3);
+        assert_eq!(src.line_column(0), LineColumn { line: 1, column: 1 });
+        assert_eq!(src.line_column(6), LineColumn { line: 1, column: 7 });
+        assert_eq!(src.line_column(11), LineColumn { line: 2, column: 1 });
+        // `é` is two bytes but one column.
+        assert_eq!(src.line_column(20), LineColumn { line: 2, column: 9 });
+        assert_eq!(src.line_column(19), LineColumn { line: 2, column: 8 });
+        assert_eq!(src.line_column(100), LineColumn { line: 3, column: 1 });
+        assert_eq!(src.line(1), Some("MATCH (n)"));
+        assert_eq!(src.line(2), Some("RETURN é, n"));
+        assert_eq!(src.line(3), Some(""));
+        assert_eq!(src.line(0), None);
+        assert_eq!(src.line(4), None);
+    }
+
     #[test]
     fn diag_warning_and_note() {
         let warn = Diag::warning("deprecated syntax");
@@ -563,6 +752,44 @@ mod tests {
         assert_eq!(related[0].severity(), Some(Severity::Advice));
     }
 
+    #[test]
+    fn convert_shows_labels_in_other_files_with_their_source() {
+        let mut sources = SourceMap::new();
+        let schema = sources.add_file("schema.gql", "NODE Person (name STRING)");
+        let query = sources.add_file("query.gql", "MATCH (p:Person) RETURN p.nickname");
+        let diag = Diag::error("unknown property")
+            .with_file(query)
+            .with_primary_label(26..34, "not declared")
+            .with_secondary_label_in(FileSpan::new(schema, 5..11), "declared here")
+            .with_note("properties come from the graph type");
+
+        let built = build_diagnostic(&diag, &sources);
+        assert_eq!(built.labels.len(), 1);
+        assert_eq!(built.labels[0].offset(), 26);
+        let related = built.related().unwrap().collect::<Vec<_>>();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].to_string(), "schema.gql");
+        let labels = related[0].labels().unwrap().collect::<Vec<_>>();
+        assert_eq!(labels[0].offset(), 5);
+        assert_eq!(labels[0].label(), Some("declared here"));
+        assert!(related[0].source_code().is_some());
+        assert_eq!(
+            related[1].to_string(),
+            "properties come from the graph type"
+        );
+
+        let mut rendered = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(
+                &mut rendered,
+                convert_diag_to_report(&diag, &sources).as_ref(),
+            )
+            .unwrap();
+        assert!(rendered.contains("query.gql"), "{rendered}");
+        assert!(rendered.contains("schema.gql"), "{rendered}");
+        assert!(rendered.contains("declared here"), "{rendered}");
+    }
+
     #[test]
     fn convert_with_invalid_span() {
         let source = SourceFile::new("short");
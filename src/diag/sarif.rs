@@ -0,0 +1,284 @@
+//! SARIF 2.1.0 diagnostic output for code scanning tools.
+
+use super::json::{Object, array, string};
+use super::source_map::resolve;
+use super::{Diag, DiagLabel, DiagSeverity, LabelRole, SourceFile, SourceLookup};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A SARIF 2.1.0 log collecting diagnostics from any number of files.
+///
+/// The log has a single run whose rules are the distinct diagnostic codes.
+/// Primary labels become result locations and secondary labels related
+/// locations; help and notes are kept in the result's property bag. Columns
+/// count Unicode code points.
+///
+/// ```
+/// use gql_parser::diag::{Diag, SarifLog, SourceFile};
+///
+/// let mut log = SarifLog::new();
+/// for (name, text) in [("a.gql", "MATCH (n) RETURN m"), ("b.gql", "RETURN 1")] {
+///     let source = SourceFile::with_name(text, name);
+///     let diagnostics = if name == "a.gql" {
+///         vec![Diag::error("undefined variable").with_primary_label(17..18, "not bound")]
+///     } else {
+///         Vec::new()
+///     };
+///     log.add(&diagnostics, &source);
+/// }
+/// let sarif = log.render();
+/// assert!(sarif.contains(r#""version":"2.1.0""#));
+/// assert!(sarif.contains(r#""artifactLocation":{"uri":"a.gql"}"#));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SarifLog {
+    rules: Vec<String>,
+    results: Vec<String>,
+}
+
+impl SarifLog {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the diagnostics reported for `sources`, either a single
+    /// [`SourceFile`] or a [`SourceMap`](super::SourceMap).
+    pub fn add<S: SourceLookup + ?Sized>(&mut self, diagnostics: &[Diag], sources: &S) {
+        for diag in diagnostics {
+            let result = self.result(diag, sources);
+            self.results.push(result);
+        }
+    }
+
+    /// Renders the log as a SARIF JSON document.
+    pub fn render(&self) -> String {
+        let rules = self.rules.iter().map(|code| {
+            let mut rule = Object::new();
+            rule.string("id", code);
+            rule.finish()
+        });
+        let mut driver = Object::new();
+        driver.string("name", env!("CARGO_PKG_NAME"));
+        driver.string("version", env!("CARGO_PKG_VERSION"));
+        driver.field("rules", &array(rules));
+        let mut tool = Object::new();
+        tool.field("driver", &driver.finish());
+
+        let mut run = Object::new();
+        run.field("tool", &tool.finish());
+        run.string("columnKind", "unicodeCodePoints");
+        run.field("results", &array(self.results.iter().cloned()));
+
+        let mut log = Object::new();
+        log.string("$schema", SCHEMA);
+        log.string("version", "2.1.0");
+        log.field("runs", &array([run.finish()]));
+        log.finish()
+    }
+
+    fn result<S: SourceLookup + ?Sized>(&mut self, diag: &Diag, sources: &S) -> String {
+        let empty = SourceFile::new("");
+        let source = resolve(sources, diag.file, &empty);
+        let file = |label: &DiagLabel| resolve(sources, label.file.or(diag.file), &empty);
+        let mut result = Object::new();
+        if let Some(code) = &diag.code {
+            let index = match self.rules.iter().position(|rule| rule == code) {
+                Some(index) => index,
+                None => {
+                    self.rules.push(code.clone());
+                    self.rules.len() - 1
+                }
+            };
+            result.string("ruleId", code);
+            result.field("ruleIndex", &index.to_string());
+        }
+        result.string(
+            "level",
+            match diag.severity {
+                DiagSeverity::Error => "error",
+                DiagSeverity::Warning => "warning",
+                DiagSeverity::Note => "note",
+            },
+        );
+        result.field("message", &message(&diag.message));
+
+        let (primary, secondary): (Vec<&DiagLabel>, Vec<&DiagLabel>) = diag
+            .labels
+            .iter()
+            .partition(|label| label.role == LabelRole::Primary);
+        let mut locations: Vec<String> = primary
+            .iter()
+            .map(|label| location(file(label), Some(label), None))
+            .collect();
+        if locations.is_empty() && source.name().is_some() {
+            locations.push(location(source, None, None));
+        }
+        if !locations.is_empty() {
+            result.field("locations", &array(locations));
+        }
+        if !secondary.is_empty() {
+            let related = secondary
+                .iter()
+                .enumerate()
+                .map(|(id, label)| location(file(label), Some(label), Some(id)));
+            result.field("relatedLocations", &array(related));
+        }
+
+        if diag.help.is_some() || !diag.notes.is_empty() {
+            let mut properties = Object::new();
+            if let Some(help) = &diag.help {
+                properties.string("help", help);
+            }
+            if !diag.notes.is_empty() {
+                properties.field("notes", &array(diag.notes.iter().map(|note| string(note))));
+            }
+            result.field("properties", &properties.finish());
+        }
+        result.finish()
+    }
+}
+
+/// Renders diagnostics as a SARIF JSON document.
+pub fn render_sarif<S: SourceLookup + ?Sized>(diagnostics: &[Diag], sources: &S) -> String {
+    let mut log = SarifLog::new();
+    log.add(diagnostics, sources);
+    log.render()
+}
+
+fn message(text: &str) -> String {
+    let mut message = Object::new();
+    message.string("text", text);
+    message.finish()
+}
+
+/// Renders a location in `source`, covering a label's span if given.
+fn location(source: &SourceFile, label: Option<&DiagLabel>, id: Option<usize>) -> String {
+    let mut physical = Object::new();
+    if let Some(name) = source.name() {
+        let mut artifact = Object::new();
+        artifact.string("uri", &uri(name));
+        physical.field("artifactLocation", &artifact.finish());
+    }
+    if let Some(label) = label {
+        let span = source.clamp_span(&label.span);
+        let start = source.line_column(span.start);
+        let end = source.line_column(span.end);
+        let mut region = Object::new();
+        region.field("startLine", &start.line.to_string());
+        region.field("startColumn", &start.column.to_string());
+        region.field("endLine", &end.line.to_string());
+        region.field("endColumn", &end.column.to_string());
+        physical.field("region", &region.finish());
+    }
+
+    let mut location = Object::new();
+    if let Some(id) = id {
+        location.field("id", &id.to_string());
+    }
+    location.field("physicalLocation", &physical.finish());
+    if let Some(label) = label.filter(|label| !label.message.is_empty()) {
+        location.field("message", &message(&label.message));
+    }
+    location.finish()
+}
+
+/// Converts a file name to a relative URI reference, percent-encoding
+/// characters outside the unreserved set and path separators.
+fn uri(name: &str) -> String {
+    let mut uri = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(char::from(byte))
+            }
+            byte => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diag::{FileSpan, SourceMap};
+
+    #[test]
+    fn uri_encodes_reserved_characters() {
+        assert_eq!(uri("queries/a.gql"), "queries/a.gql");
+        assert_eq!(uri("queries\\my query#1.gql"), "queries/my%20query%231.gql");
+        assert_eq!(uri("é.gql"), "%C3%A9.gql");
+    }
+
+    #[test]
+    fn render_sarif_maps_labels_to_locations() {
+        let source = SourceFile::with_name("MATCH (n)\nRETURN m", "query.gql");
+        let diagnostics = vec![
+            Diag::error("undefined variable")
+                .with_code("E100")
+                .with_primary_label(17..18, "not bound")
+                .with_secondary_label(7..8, "similar name")
+                .with_help("bind `m` in MATCH"),
+            Diag::warning("unused variable")
+                .with_code("W200")
+                .with_note("remove it"),
+            Diag::error("another undefined variable").with_code("E100"),
+        ];
+        let sarif = render_sarif(&diagnostics, &source);
+        assert_eq!(
+            sarif,
+            concat!(
+                r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","#,
+                r#""version":"2.1.0","runs":[{"tool":{"driver":{"name":"gql_parser","#,
+                r#""version":""#,
+                env!("CARGO_PKG_VERSION"),
+                r#"","rules":[{"id":"E100"},{"id":"W200"}]}},"#,
+                r#""columnKind":"unicodeCodePoints","results":["#,
+                r#"{"ruleId":"E100","ruleIndex":0,"level":"error","#,
+                r#""message":{"text":"undefined variable"},"#,
+                r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"query.gql"},"#,
+                r#""region":{"startLine":2,"startColumn":8,"endLine":2,"endColumn":9}},"#,
+                r#""message":{"text":"not bound"}}],"#,
+                r#""relatedLocations":[{"id":0,"physicalLocation":{"#,
+                r#""artifactLocation":{"uri":"query.gql"},"#,
+                r#""region":{"startLine":1,"startColumn":8,"endLine":1,"endColumn":9}},"#,
+                r#""message":{"text":"similar name"}}],"#,
+                r#""properties":{"help":"bind `m` in MATCH"}},"#,
+                r#"{"ruleId":"W200","ruleIndex":1,"level":"warning","#,
+                r#""message":{"text":"unused variable"},"#,
+                r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"query.gql"}}}],"#,
+                r#""properties":{"notes":["remove it"]}},"#,
+                r#"{"ruleId":"E100","ruleIndex":0,"level":"error","#,
+                r#""message":{"text":"another undefined variable"},"#,
+                r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"query.gql"}}}]}"#,
+                r#"]}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn sarif_log_points_related_locations_into_other_files() {
+        let mut sources = SourceMap::new();
+        let schema = sources.add_file("schema.gql", "NODE Person");
+        let query = sources.add_file("queries/q 1.gql", "MATCH (p:Person) RETURN p");
+        let diagnostics = vec![
+            Diag::error("unknown label")
+                .with_file(query)
+                .with_primary_label(9..15, "here")
+                .with_secondary_label_in(FileSpan::new(schema, 5..11), "declared"),
+        ];
+        let mut log = SarifLog::new();
+        log.add(&diagnostics, &sources);
+        let sarif = log.render();
+        assert!(sarif.contains(concat!(
+            r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"queries/q%201.gql"},"#,
+            r#""region":{"startLine":1,"startColumn":10,"endLine":1,"endColumn":16}}"#,
+        )));
+        assert!(sarif.contains(concat!(
+            r#""relatedLocations":[{"id":0,"physicalLocation":{"#,
+            r#""artifactLocation":{"uri":"schema.gql"},"#,
+            r#""region":{"startLine":1,"startColumn":6,"endLine":1,"endColumn":12}}"#,
+        )));
+    }
+}
@@ -0,0 +1,174 @@
+//! Multi-file source database.
+
+use super::SourceFile;
+use crate::ast::Span;
+
+/// Identifies a file in a [`SourceMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+
+impl FileId {
+    /// Returns the position of the file in its source map.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A span within a specific file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileSpan {
+    /// The file the span belongs to.
+    pub file: FileId,
+    /// Byte range within the file.
+    pub span: Span,
+}
+
+impl FileSpan {
+    /// Creates a span within `file`.
+    pub fn new(file: FileId, span: Span) -> Self {
+        Self { file, span }
+    }
+}
+
+/// A collection of source files that diagnostics can point into.
+///
+/// Scripts, procedure libraries and schema DDL are added as separate
+/// files; labels referring to a [`FileId`] are rendered against the file
+/// they belong to, so one diagnostic can show a query site next to the
+/// declaration it conflicts with.
+///
+/// ```
+/// use gql_parser::diag::{Diag, FileSpan, SourceMap, render_text};
+///
+/// let mut sources = SourceMap::new();
+/// let query = sources.add_file("query.gql", "MATCH (p:Person) RETURN p.nickname");
+/// let schema = sources.add_file("schema.gql", "NODE Person (name STRING)");
+/// let diag = Diag::error("unknown property `nickname`")
+///     .with_file(query)
+///     .with_primary_label(26..34, "not declared on Person")
+///     .with_secondary_label_in(FileSpan::new(schema, 0..25), "Person declared here");
+/// let text = render_text(&[diag], &sources);
+/// assert!(text.contains("--> query.gql:1:27"));
+/// assert!(text.contains("::: schema.gql:1:1"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    /// Creates an empty source map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source file and returns its ID.
+    pub fn add(&mut self, file: SourceFile) -> FileId {
+        let id = FileId(u32::try_from(self.files.len()).expect("too many source files"));
+        self.files.push(file);
+        id
+    }
+
+    /// Adds a named source file and returns its ID.
+    pub fn add_file(&mut self, name: impl Into<String>, content: impl Into<String>) -> FileId {
+        self.add(SourceFile::with_name(content, name))
+    }
+
+    /// Returns the file with the given ID.
+    pub fn get(&self, file: FileId) -> Option<&SourceFile> {
+        self.files.get(file.index())
+    }
+
+    /// Returns the ID of the first file with the given name.
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.iter()
+            .find(|(_, file)| file.name() == Some(name))
+            .map(|(id, _)| id)
+    }
+
+    /// Iterates over the files in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (FileId(index as u32), file))
+    }
+
+    /// Returns the number of files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns true if no files were added.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Resolves the source files that diagnostics and their labels refer to.
+///
+/// Renderers accept either a single [`SourceFile`], which serves every
+/// label, or a [`SourceMap`].
+pub trait SourceLookup {
+    /// Returns the source of `file`; `None` asks for the default source.
+    fn source(&self, file: Option<FileId>) -> Option<&SourceFile>;
+}
+
+impl SourceLookup for SourceFile {
+    fn source(&self, _file: Option<FileId>) -> Option<&SourceFile> {
+        Some(self)
+    }
+}
+
+impl SourceLookup for SourceMap {
+    /// Resolves to the file with the given ID, or to the first file.
+    fn source(&self, file: Option<FileId>) -> Option<&SourceFile> {
+        match file {
+            Some(file) => self.get(file),
+            None => self.files.first(),
+        }
+    }
+}
+
+/// Resolves `file`, falling back to the default source and then to
+/// `fallback` for unknown files and empty source maps.
+pub(super) fn resolve<'a, S: SourceLookup + ?Sized>(
+    sources: &'a S,
+    file: Option<FileId>,
+    fallback: &'a SourceFile,
+) -> &'a SourceFile {
+    sources
+        .source(file)
+        .or_else(|| sources.source(None))
+        .unwrap_or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_map_assigns_ids_in_order() {
+        let mut sources = SourceMap::new();
+        assert!(sources.is_empty());
+        let query = sources.add_file("query.gql", "MATCH (n) RETURN n");
+        let schema = sources.add(SourceFile::new("NODE Person"));
+        assert_eq!((query.index(), schema.index()), (0, 1));
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources.get(schema).unwrap().content(), "NODE Person");
+        assert_eq!(sources.find("query.gql"), Some(query));
+        assert_eq!(sources.find("schema.gql"), None);
+        assert_eq!(
+            sources.source(None).unwrap().name(),
+            Some("query.gql"),
+            "the first file is the default"
+        );
+
+        let fallback = SourceFile::new("");
+        let other = SourceMap::new().add_file("elsewhere.gql", "");
+        assert!(std::ptr::eq(
+            resolve(&SourceMap::new(), Some(other), &fallback),
+            &fallback
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! Plain text diagnostic output.
+
+use std::fmt::Write;
+
+use super::source_map::resolve;
+use super::{Diag, DiagLabel, LabelRole, LineColumn, SourceFile, SourceLookup};
+
+/// Renders diagnostics as plain text without ANSI escapes, for logs.
+///
+/// The layout follows the usual compiler style: a header with severity,
+/// code and message, the location of the primary label, each labeled line
+/// with `^` under primary and `-` under secondary spans, then help and
+/// notes. Labels in other files of a [`SourceMap`](super::SourceMap) are
+/// shown under their own `:::` location line. Diagnostics are separated by
+/// blank lines.
+///
+/// ```
+/// use gql_parser::diag::{Diag, SourceFile, render_text};
+///
+/// let source = SourceFile::with_name("MATCH (n) RETURN m", "query.gql");
+/// let diag = Diag::error("undefined variable")
+///     .with_code("E100")
+///     .with_primary_label(17..18, "not bound")
+///     .with_help("bind `m` in MATCH");
+/// assert_eq!(
+///     render_text(&[diag], &source),
+///     "error[E100]: undefined variable\n \
+///      --> query.gql:1:18\n  \
+///       |\n\
+///      1 | MATCH (n) RETURN m\n  \
+///       |                  ^ not bound\n  \
+///       |\n  \
+///       = help: bind `m` in MATCH\n",
+/// );
+/// ```
+pub fn render_text<S: SourceLookup + ?Sized>(diagnostics: &[Diag], sources: &S) -> String {
+    let mut output = String::new();
+    for (index, diag) in diagnostics.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        diagnostic(&mut output, diag, sources);
+    }
+    output
+}
+
+/// A label resolved to its file, with the line and columns it underlines.
+struct Resolved<'a> {
+    file: &'a SourceFile,
+    start: LineColumn,
+    end: LineColumn,
+    label: &'a DiagLabel,
+}
+
+fn diagnostic<S: SourceLookup + ?Sized>(output: &mut String, diag: &Diag, sources: &S) {
+    output.push_str(&diag.severity.to_string());
+    if let Some(code) = &diag.code {
+        let _ = write!(output, "[{code}]");
+    }
+    let _ = writeln!(output, ": {}", diag.message);
+
+    // Group labels by file, starting with the file of the primary label,
+    // and order them by position within each file.
+    let empty = SourceFile::new("");
+    let mut labels: Vec<&DiagLabel> = diag.labels.iter().collect();
+    labels.sort_by_key(|label| label.role != LabelRole::Primary);
+    let mut groups: Vec<Vec<Resolved<'_>>> = Vec::new();
+    for label in labels {
+        let file = resolve(sources, label.file.or(diag.file), &empty);
+        let span = file.clamp_span(&label.span);
+        let resolved = Resolved {
+            file,
+            start: file.line_column(span.start),
+            end: file.line_column(span.end),
+            label,
+        };
+        match groups
+            .iter_mut()
+            .find(|group| std::ptr::eq(group[0].file, file))
+        {
+            Some(group) => group.push(resolved),
+            None => groups.push(vec![resolved]),
+        }
+    }
+    let width = groups
+        .iter()
+        .flatten()
+        .map(|resolved| resolved.start.line.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let gutter = " ".repeat(width);
+
+    for (index, group) in groups.iter_mut().enumerate() {
+        if index > 0 {
+            let _ = writeln!(output, "{gutter} |");
+        }
+        let first = &group[0];
+        let arrow = if index == 0 { "-->" } else { ":::" };
+        let _ = write!(output, "{gutter}{arrow} ");
+        if let Some(name) = first.file.name() {
+            let _ = write!(output, "{name}:");
+        }
+        let _ = writeln!(output, "{}:{}", first.start.line, first.start.column);
+
+        group.sort_by_key(|resolved| resolved.start);
+        let _ = writeln!(output, "{gutter} |");
+        let mut previous_line = None;
+        for Resolved {
+            file,
+            start,
+            end,
+            label,
+        } in group.iter()
+        {
+            let text = file.line(start.line).unwrap_or_default();
+            if previous_line != Some(start.line) {
+                let _ = writeln!(output, "{:>width$} | {text}", start.line);
+                previous_line = Some(start.line);
+            }
+            // Spans running past their first line are underlined to its end.
+            let last = if end.line == start.line {
+                end.column
+            } else {
+                text.chars().count() + 1
+            };
+            let marker = match label.role {
+                LabelRole::Primary => "^",
+                LabelRole::Secondary => "-",
+            };
+            let underline = marker.repeat(last.saturating_sub(start.column).max(1));
+            let padding = " ".repeat(start.column - 1);
+            let line = format!("{gutter} | {padding}{underline} {}", label.message);
+            let _ = writeln!(output, "{}", line.trim_end());
+        }
+    }
+
+    if diag.help.is_some() || !diag.notes.is_empty() {
+        if !groups.is_empty() {
+            let _ = writeln!(output, "{gutter} |");
+        }
+        if let Some(help) = &diag.help {
+            let _ = writeln!(output, "{gutter} = help: {help}");
+        }
+        for note in &diag.notes {
+            let _ = writeln!(output, "{gutter} = note: {note}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diag::{FileSpan, SourceMap};
+
+    #[test]
+    fn render_text_orders_labels_by_line() {
+        let source = SourceFile::new("MATCH (n)\nRETURN m,\n  n.name");
+        let diagnostics = vec![
+            Diag::error("undefined variable")
+                .with_primary_label(17..18, "not bound")
+                .with_secondary_label(7..8, "did you mean `n`?")
+                .with_note("variables are scoped to the query"),
+            Diag::warning("long span").with_primary_label(10..25, ""),
+            Diag::note("no labels").with_help("nothing to point at"),
+        ];
+        assert_eq!(
+            render_text(&diagnostics, &source),
+            "\
+error: undefined variable
+ --> 2:8
+  |
+1 | MATCH (n)
+  |        - did you mean `n`?
+2 | RETURN m,
+  |        ^ not bound
+  |
+  = note: variables are scoped to the query
+
+warning: long span
+ --> 2:1
+  |
+2 | RETURN m,
+  | ^^^^^^^^^
+
+note: no labels
+ = help: nothing to point at
+"
+        );
+    }
+
+    #[test]
+    fn render_text_shows_each_file_under_its_own_location() {
+        let mut sources = SourceMap::new();
+        let query = sources.add_file("query.gql", "MATCH (p:Person)\nRETURN p.nickname");
+        let schema = sources.add(SourceFile::new(
+            "CREATE GRAPH TYPE social {\n  NODE Person\n}",
+        ));
+        let diag = Diag::error("unknown property")
+            .with_file(query)
+            .with_secondary_label_in(FileSpan::new(schema, 29..40), "Person declared here")
+            .with_primary_label(26..34, "not declared on Person")
+            .with_secondary_label(9..15, "");
+        assert_eq!(
+            render_text(&[diag], &sources),
+            "\
+error: unknown property
+ --> query.gql:2:10
+  |
+1 | MATCH (p:Person)
+  |          ------
+2 | RETURN p.nickname
+  |          ^^^^^^^^ not declared on Person
+  |
+ ::: 2:3
+  |
+2 |   NODE Person
+  |   ----------- Person declared here
+"
+        );
+    }
+}
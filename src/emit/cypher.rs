@@ -0,0 +1,1681 @@
+//! openCypher emitter.
+//!
+//! Renders GQL programs as openCypher text for Neo4j-compatible stores. The
+//! two languages share most of their pattern and expression syntax; the
+//! emitter rewrites the statements Cypher spells differently:
+//!
+//! | GQL | openCypher |
+//! |---|---|
+//! | `INSERT (a:A&B)-[:R]->(b)` | `CREATE (a:A:B)-[:R]->(b)` |
+//! | `LET x = expr` | `WITH *, expr AS x` |
+//! | `FILTER cond` | `WITH * WHERE cond` |
+//! | `FOR x IN list` | `UNWIND list AS x` |
+//! | `-[:R]->{1,3}` | `-[:R*1..3]->` |
+//! | `((a)-[:R]->(b)){2,}` | `((a)-[:R]->(b)){2,}` |
+//! | `p = ANY SHORTEST (a)-[:R]->+(b)` | `p = shortestPath((a)-[:R*1..]->(b))` |
+//! | `x IS TRUE` | `coalesce(x, false)` |
+//! | `UPPER`, `COLLECT_LIST`, `PATH_LENGTH`, ... | `toUpper`, `collect`, `length`, ... |
+//!
+//! Label expressions use the Neo4j 5 forms `:A|B`, `:A&B`, `:!A` and `:%`,
+//! and quantified path patterns need Neo4j 5.9 or later. Element `WHERE`
+//! clauses move into the `WHERE` of their MATCH, except inside quantified
+//! path patterns where they stay on the element.
+//!
+//! Cypher matches every MATCH under relationship isomorphism, which GQL
+//! spells `DIFFERENT EDGES`, while GQL defaults to `REPEATABLE ELEMENTS`.
+//! Both match modes are written as a plain MATCH, so a pattern with several
+//! edges, such as `(a)-[]->(b)-[]->(c)`, drops the matches that bind one
+//! relationship twice. Path modes, multiset alternation, `NULLS FIRST` and
+//! other constructs without a Cypher counterpart are reported.
+
+use crate::analysis::ExpressionInfo;
+use crate::ast::Span;
+use crate::ast::expression::{
+    AggregateFunction, BinaryOperator, BinarySetFunctionType, BooleanValue, CaseExpression,
+    ComparisonOperator, Expression, FunctionCall, FunctionName, GeneralSetFunctionType, Literal,
+    LogicalOperator, Predicate, RecordField, TrimSpecification, TruthValue, UnaryOperator,
+};
+use crate::ast::mutation::{
+    DeleteStatement, DetachOption, InsertEdgePattern, InsertElementPattern,
+    InsertElementPatternFiller, InsertStatement, LinearDataModifyingStatement,
+    PrimitiveDataModifyingStatement, RemoveItem, RemoveStatement, SetItem, SetStatement,
+    SimpleDataAccessingStatement, SimpleDataModifyingStatement,
+};
+use crate::ast::procedure::{CallProcedureStatement, ProcedureCall};
+use crate::ast::program::{Program, Statement};
+use crate::ast::query::{
+    AbbreviatedEdgePattern, EdgeDirection, EdgePattern, ElementPattern, ElementPatternPredicate,
+    ElementPropertySpecification, ElementVariableDeclaration, ForOrdinalityOrOffset, GraphPattern,
+    GraphPatternQuantifier, GroupingElement, LabelExpression, LinearQuery, MatchStatement,
+    NullOrdering, OptionalOperand, OrderByAndPageStatement, OrderingSpecification, PathFactor,
+    PathPattern, PathPatternExpression, PathPatternPrefix, PathPrimary, PathSearch,
+    PrimitiveQueryStatement, PrimitiveResultStatement, Query, ReturnItemList, ReturnStatement,
+    SetOperator, SetQuantifier, ShortestPathSearch, UseGraphClause,
+};
+use crate::ast::references::{CatalogObjectParentReference, ProcedureReference};
+use crate::ast::types::{
+    BooleanType, CharacterStringType, ExactNumericType, NumericType, PredefinedType,
+    TemporalInstantType, TemporalType, ValueType,
+};
+use crate::diag::Diag;
+
+use super::gql::{
+    ADDITIVE, AND, COMPARISON, IS, MULTIPLICATIVE, NOT, POSTFIX, PRIMARY, UNARY, precedence,
+    string_literal,
+};
+use super::{EmitResult, unsupported};
+
+const TARGET: &str = "openCypher";
+
+/// Words that must be quoted to be used as Cypher identifiers.
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "ASCENDING",
+    "BY",
+    "CALL",
+    "CASE",
+    "CONTAINS",
+    "CREATE",
+    "DELETE",
+    "DESC",
+    "DESCENDING",
+    "DETACH",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "ENDS",
+    "EXISTS",
+    "FALSE",
+    "IN",
+    "IS",
+    "LIMIT",
+    "MATCH",
+    "MERGE",
+    "NOT",
+    "NULL",
+    "ON",
+    "OPTIONAL",
+    "OR",
+    "ORDER",
+    "REMOVE",
+    "RETURN",
+    "SET",
+    "SKIP",
+    "STARTS",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNWIND",
+    "USE",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "XOR",
+    "YIELD",
+];
+
+/// Renders every statement of `program`, separated by `;`.
+///
+/// A statement after `NEXT` continues the one before it: that statement's
+/// RETURN becomes a `WITH` carrying its columns. Other statements are
+/// separated by `;`.
+pub fn program(program: &Program) -> EmitResult {
+    let statements: Vec<&Statement> = program
+        .statements
+        .iter()
+        .filter(|statement| !matches!(statement, Statement::Empty(_)))
+        .collect();
+    let mut writer = CypherWriter::default();
+    let mut carried = false;
+    for (index, statement) in statements.iter().enumerate() {
+        let carries = statements
+            .get(index + 1)
+            .is_some_and(|next| next.follows_next())
+            && writer.hands_on(statement);
+        let text = writer.capture(|writer| {
+            writer.continued = carries;
+            writer.statement(statement);
+            writer.continued = false;
+        });
+        if index > 0 {
+            writer.push(if carried { "\n" } else { ";\n" });
+        }
+        writer.push(&text);
+        carried = carries;
+        writer.bound = carries;
+    }
+    writer.finish()
+}
+
+/// Renders a single statement.
+pub fn statement(statement: &Statement) -> EmitResult {
+    let mut writer = CypherWriter::default();
+    writer.statement(statement);
+    writer.finish()
+}
+
+/// Renders a query.
+pub fn query(query: &Query) -> EmitResult {
+    let mut writer = CypherWriter::default();
+    writer.query(query);
+    writer.finish()
+}
+
+/// Renders a value expression.
+pub fn expression(expression: &Expression) -> EmitResult {
+    let mut writer = CypherWriter::default();
+    writer.expression(expression, 0);
+    writer.finish()
+}
+
+/// Quotes `name` with backticks unless it can be written bare.
+pub fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let regular = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    let reserved = RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(name));
+    if regular && !reserved {
+        return name.to_string();
+    }
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Accumulates Cypher text and the diagnostics for constructs it cannot
+/// render.
+#[derive(Default)]
+struct CypherWriter {
+    out: String,
+    diagnostics: Vec<Diag>,
+    /// Whether the current query has bound any variables yet.
+    bound: bool,
+    /// Whether the statement's RETURN hands its columns to a statement
+    /// after `NEXT`, and so is written as `WITH`.
+    continued: bool,
+    /// Whether an updating clause was written since the last `WITH`.
+    updated: bool,
+    /// Nesting depth of quantified path patterns.
+    depth: usize,
+}
+
+impl CypherWriter {
+    fn finish(self) -> EmitResult {
+        if self.diagnostics.is_empty() {
+            Ok(self.out)
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn list<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.push(", ");
+            }
+            write(self, item);
+        }
+    }
+
+    /// Runs `write` and returns what it wrote instead of appending it.
+    fn capture(&mut self, write: impl FnOnce(&mut Self)) -> String {
+        let outer = std::mem::take(&mut self.out);
+        write(self);
+        std::mem::replace(&mut self.out, outer)
+    }
+
+    fn unsupported(&mut self, construct: &str, span: &Span) {
+        self.diagnostics
+            .push(unsupported(construct, TARGET, span.clone()));
+    }
+
+    /// Starts a clause on a new line.
+    fn clause(&mut self, keyword: &str) {
+        if !self.out.is_empty() {
+            self.push("\n");
+        }
+        self.push(keyword);
+    }
+
+    /// Starts a reading clause, which Cypher only allows after an updating
+    /// clause once a `WITH` separates them.
+    fn reading_clause(&mut self, keyword: &str) {
+        if self.updated {
+            self.clause("WITH *");
+            self.updated = false;
+        }
+        self.clause(keyword);
+    }
+
+    /// Starts a `WITH` clause that keeps the variables in scope. Cypher has
+    /// no empty projection, so `construct` is reported when none are bound.
+    fn with_all(&mut self, construct: &str, span: &Span) {
+        if !self.bound {
+            self.unsupported(&format!("{construct} before any variable is bound"), span);
+        }
+        self.clause("WITH *");
+        self.updated = false;
+    }
+
+    // ========================================================================
+    // Statements
+    // ========================================================================
+
+    /// Whether `statement` has a RETURN to hand to a statement after `NEXT`,
+    /// reporting a result Cypher cannot continue with `WITH`.
+    fn hands_on(&mut self, statement: &Statement) -> bool {
+        fn query_hands_on(writer: &mut CypherWriter, query: &Query) -> bool {
+            match query {
+                Query::Linear(linear) => matches!(
+                    linear.result_statement.as_deref(),
+                    Some(PrimitiveResultStatement::Return(_))
+                ),
+                Query::Parenthesized(inner, _) => query_hands_on(writer, inner),
+                Query::Composite(composite) => {
+                    writer.unsupported("Set operation before NEXT", &composite.span);
+                    false
+                }
+            }
+        }
+        match statement {
+            Statement::Query(statement) => query_hands_on(self, &statement.query),
+            Statement::Mutation(statement) => matches!(
+                statement.statement.primitive_result_statement,
+                Some(PrimitiveResultStatement::Return(_))
+            ),
+            _ => false,
+        }
+    }
+
+    /// Writes a statement. `bound` is already set when it continues the
+    /// statement before `NEXT`, whose columns are then in scope.
+    fn statement(&mut self, statement: &Statement) {
+        self.updated = false;
+        match statement {
+            Statement::Query(statement) => self.query(&statement.query),
+            Statement::Mutation(statement) => self.mutation(&statement.statement),
+            Statement::Session(statement) => self.unsupported("Session command", &statement.span),
+            Statement::Transaction(statement) => {
+                self.unsupported("Transaction command", &statement.span)
+            }
+            Statement::Catalog(statement) => self.unsupported("Catalog statement", &statement.span),
+            Statement::Empty(_) => {}
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        match query {
+            Query::Linear(linear) => self.linear_query(linear),
+            Query::Parenthesized(inner, _) => self.query(inner),
+            Query::Composite(composite) => {
+                let keyword = match composite.operator {
+                    SetOperator::Union {
+                        quantifier: SetQuantifier::All,
+                    } => "UNION ALL",
+                    SetOperator::Union { .. } => "UNION",
+                    SetOperator::Except { .. } => {
+                        return self.unsupported("EXCEPT", &composite.span);
+                    }
+                    SetOperator::Intersect { .. } => {
+                        return self.unsupported("INTERSECT", &composite.span);
+                    }
+                    SetOperator::Otherwise => {
+                        return self.unsupported("OTHERWISE", &composite.span);
+                    }
+                };
+                self.query(&composite.left);
+                self.clause(keyword);
+                self.bound = false;
+                self.updated = false;
+                self.query(&composite.right);
+            }
+        }
+    }
+
+    fn linear_query(&mut self, query: &LinearQuery) {
+        if let Some(use_graph) = &query.use_graph {
+            self.use_graph(use_graph);
+        }
+        for statement in &query.primitive_statements {
+            self.query_statement(statement);
+        }
+        match query.result_statement.as_deref() {
+            Some(PrimitiveResultStatement::Return(ret)) => self.return_statement(ret),
+            Some(PrimitiveResultStatement::Finish(span)) => self.diagnostics.push(
+                unsupported("FINISH", TARGET, span.clone())
+                    .with_help("a Cypher query without updates must end with RETURN"),
+            ),
+            None => self.unsupported("Query without RETURN", &query.span),
+        }
+    }
+
+    fn mutation(&mut self, statement: &LinearDataModifyingStatement) {
+        if let Some(use_graph) = &statement.use_graph_clause {
+            self.use_graph(use_graph);
+        }
+        for statement in &statement.statements {
+            match statement {
+                SimpleDataAccessingStatement::Query(statement) => self.query_statement(statement),
+                SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Call(
+                    call,
+                )) => self.call(&call.call),
+                SimpleDataAccessingStatement::Modifying(
+                    SimpleDataModifyingStatement::Primitive(statement),
+                ) => match statement {
+                    PrimitiveDataModifyingStatement::Insert(insert) => self.insert(insert),
+                    PrimitiveDataModifyingStatement::Set(set) => self.set(set),
+                    PrimitiveDataModifyingStatement::Remove(remove) => self.remove(remove),
+                    PrimitiveDataModifyingStatement::Delete(delete) => self.delete(delete),
+                },
+            }
+        }
+        // FINISH is implied by a Cypher query that ends in an update.
+        if let Some(PrimitiveResultStatement::Return(ret)) = &statement.primitive_result_statement {
+            self.return_statement(ret);
+        }
+    }
+
+    fn use_graph(&mut self, use_graph: &UseGraphClause) {
+        match &use_graph.graph {
+            Expression::VariableReference(name, _) => {
+                self.clause("USE ");
+                self.push(&identifier(name));
+            }
+            _ => self.unsupported("Computed USE graph", &use_graph.span),
+        }
+    }
+
+    fn query_statement(&mut self, statement: &PrimitiveQueryStatement) {
+        match statement {
+            PrimitiveQueryStatement::Match(MatchStatement::Simple(simple)) => {
+                self.match_clause("MATCH ", &simple.pattern)
+            }
+            PrimitiveQueryStatement::Match(MatchStatement::Optional(optional)) => {
+                match &optional.operand {
+                    OptionalOperand::Match { pattern } => {
+                        self.match_clause("OPTIONAL MATCH ", pattern)
+                    }
+                    OptionalOperand::Block { .. } | OptionalOperand::ParenthesizedBlock { .. } => {
+                        self.unsupported("OPTIONAL match block", &optional.span)
+                    }
+                }
+            }
+            PrimitiveQueryStatement::Filter(filter) => {
+                self.with_all("FILTER", &filter.span);
+                self.push(" WHERE ");
+                self.expression(&filter.condition, 0);
+            }
+            PrimitiveQueryStatement::Let(statement) => {
+                self.clause(if self.bound { "WITH *, " } else { "WITH " });
+                self.updated = false;
+                self.list(&statement.bindings, |writer, binding| {
+                    if binding.type_annotation.is_some() {
+                        writer.unsupported("Typed LET variable", &binding.span);
+                    }
+                    writer.expression(&binding.value, 0);
+                    writer.push(" AS ");
+                    writer.push(&identifier(&binding.variable.name));
+                });
+                self.bound = true;
+            }
+            PrimitiveQueryStatement::For(statement) => {
+                match &statement.ordinality_or_offset {
+                    Some(ForOrdinalityOrOffset::Ordinality { .. }) => {
+                        self.unsupported("FOR WITH ORDINALITY", &statement.span)
+                    }
+                    Some(ForOrdinalityOrOffset::Offset { .. }) => {
+                        self.unsupported("FOR WITH OFFSET", &statement.span)
+                    }
+                    None => {}
+                }
+                self.reading_clause("UNWIND ");
+                self.expression(&statement.item.collection, 0);
+                self.push(" AS ");
+                self.push(&identifier(&statement.item.binding_variable.name));
+                self.bound = true;
+            }
+            PrimitiveQueryStatement::OrderByAndPage(statement) => {
+                self.with_all("ORDER BY", &statement.span);
+                self.order_by_and_page(statement);
+            }
+            PrimitiveQueryStatement::Call(call) => self.call(call),
+            PrimitiveQueryStatement::Select(statement) => {
+                self.unsupported("SELECT statement", &statement.span)
+            }
+        }
+    }
+
+    fn call(&mut self, call: &CallProcedureStatement) {
+        if call.optional {
+            self.unsupported("OPTIONAL CALL", &call.span);
+        }
+        let named = match &call.call {
+            ProcedureCall::Named(named) => named,
+            ProcedureCall::Inline(inline) => {
+                return self.unsupported("Inline procedure call", &inline.span);
+            }
+        };
+        let name = match &named.procedure {
+            ProcedureReference::CatalogQualified { name, .. } => {
+                let mut parts = vec![identifier(&name.name)];
+                let mut parent = name.parent.as_ref();
+                while let Some(reference) = parent {
+                    match reference {
+                        CatalogObjectParentReference::Object { name, .. } => {
+                            parts.push(identifier(&name.name));
+                            parent = name.parent.as_ref();
+                        }
+                        CatalogObjectParentReference::Schema { span, .. } => {
+                            self.unsupported("Schema-qualified procedure", span);
+                            parent = None;
+                        }
+                    }
+                }
+                parts.reverse();
+                parts.join(".")
+            }
+            ProcedureReference::ReferenceParameter { span, .. } => {
+                return self.unsupported("Procedure reference parameter", span);
+            }
+        };
+        self.reading_clause("CALL ");
+        self.push(&name);
+        self.push("(");
+        if let Some(arguments) = &named.arguments {
+            self.list(&arguments.arguments, |writer, argument| {
+                writer.expression(&argument.expression, 0)
+            });
+        }
+        self.push(")");
+        if let Some(yield_clause) = &named.yield_clause {
+            self.push(" YIELD ");
+            self.list(&yield_clause.items.items, |writer, item| {
+                writer.expression(&item.expression, 0);
+                if let Some(alias) = &item.alias {
+                    writer.push(" AS ");
+                    writer.push(&identifier(&alias.name));
+                }
+            });
+            self.bound = true;
+        }
+    }
+
+    fn return_statement(&mut self, ret: &ReturnStatement) {
+        let continued = std::mem::take(&mut self.continued);
+        if continued {
+            self.clause("WITH ");
+            self.updated = false;
+        } else {
+            self.clause("RETURN ");
+        }
+        if ret.quantifier == Some(SetQuantifier::Distinct) {
+            self.push("DISTINCT ");
+        }
+        match &ret.items {
+            ReturnItemList::Star => self.push("*"),
+            ReturnItemList::Items { items } => {
+                self.list(items, |writer, item| {
+                    if continued
+                        && item.alias.is_none()
+                        && !matches!(item.expression, Expression::VariableReference(..))
+                    {
+                        writer.diagnostics.push(
+                            unsupported("Unnamed column before NEXT", TARGET, item.span.clone())
+                                .with_help("name the column with AS to use it after NEXT"),
+                        );
+                    }
+                    writer.expression(&item.expression, 0);
+                    if let Some(alias) = &item.alias {
+                        writer.push(" AS ");
+                        writer.push(&identifier(alias));
+                    }
+                });
+                self.group_by(ret);
+            }
+        }
+        if let Some(page) = &ret.order_by_and_page {
+            self.order_by_and_page(page);
+        }
+    }
+
+    /// Checks that an explicit GROUP BY matches the implicit grouping of
+    /// Cypher, which groups by every returned item that is not aggregated.
+    fn group_by(&mut self, ret: &ReturnStatement) {
+        let (Some(group_by), ReturnItemList::Items { items }) = (&ret.group_by, &ret.items) else {
+            return;
+        };
+        let mut returned = Vec::new();
+        for item in items {
+            if ExpressionInfo::analyze(&item.expression).contains_aggregate {
+                continue;
+            }
+            returned.push(self.capture(|writer| writer.expression(&item.expression, 0)));
+            if let Some(alias) = &item.alias {
+                returned.push(identifier(alias));
+            }
+        }
+        let mut grouped = 0;
+        for element in &group_by.elements {
+            let GroupingElement::Expression(key) = element else {
+                continue;
+            };
+            let key = self.capture(|writer| writer.expression(key, 0));
+            if returned.contains(&key) {
+                grouped += 1;
+            } else {
+                grouped = usize::MAX;
+                break;
+            }
+        }
+        let keys = items
+            .iter()
+            .filter(|item| !ExpressionInfo::analyze(&item.expression).contains_aggregate)
+            .count();
+        if grouped != keys {
+            self.diagnostics.push(
+                unsupported("GROUP BY", TARGET, group_by.span.clone()).with_help(
+                    "Cypher groups by the returned items that are not aggregates; \
+                     return exactly the grouping keys",
+                ),
+            );
+        }
+    }
+
+    fn order_by_and_page(&mut self, page: &OrderByAndPageStatement) {
+        if let Some(order_by) = &page.order_by {
+            self.clause("ORDER BY ");
+            self.list(&order_by.sort_specifications, |writer, sort| {
+                writer.expression(&sort.key, 0);
+                if sort.ordering == Some(OrderingSpecification::Descending) {
+                    writer.push(" DESC");
+                }
+                if let Some(null_ordering) = sort.null_ordering {
+                    let construct = match null_ordering {
+                        NullOrdering::NullsFirst => "NULLS FIRST",
+                        NullOrdering::NullsLast => "NULLS LAST",
+                    };
+                    writer.unsupported(construct, &sort.span);
+                }
+            });
+        }
+        if let Some(offset) = &page.offset {
+            self.clause("SKIP ");
+            self.expression(&offset.count, 0);
+        }
+        if let Some(limit) = &page.limit {
+            self.clause("LIMIT ");
+            self.expression(&limit.count, 0);
+        }
+    }
+
+    // ========================================================================
+    // Updates
+    // ========================================================================
+
+    fn insert(&mut self, insert: &InsertStatement) {
+        self.clause("CREATE ");
+        self.list(&insert.pattern.paths, |writer, path| {
+            for element in &path.elements {
+                match element {
+                    InsertElementPattern::Node(node) => {
+                        writer.push("(");
+                        if let Some(filler) = &node.filler {
+                            writer.insert_filler(filler, false);
+                        }
+                        writer.push(")");
+                    }
+                    InsertElementPattern::Edge(edge) => {
+                        let (left, right, filler) = match edge {
+                            InsertEdgePattern::PointingLeft(edge) => ("<-[", "]-", &edge.filler),
+                            InsertEdgePattern::PointingRight(edge) => ("-[", "]->", &edge.filler),
+                            InsertEdgePattern::Undirected(edge) => {
+                                writer.unsupported("Undirected edge", &edge.span);
+                                continue;
+                            }
+                        };
+                        let labels = filler
+                            .as_ref()
+                            .and_then(|filler| filler.label_set.as_ref())
+                            .map_or(0, |set| set.labels.len());
+                        if labels != 1 {
+                            writer.diagnostics.push(
+                                unsupported(
+                                    "Inserted edge without exactly one label",
+                                    TARGET,
+                                    edge.span().clone(),
+                                )
+                                .with_help("a Cypher relationship has exactly one type"),
+                            );
+                        }
+                        writer.push(left);
+                        if let Some(filler) = filler {
+                            writer.insert_filler(filler, true);
+                        }
+                        writer.push(right);
+                    }
+                }
+            }
+        });
+        self.bound = true;
+        self.updated = true;
+    }
+
+    fn insert_filler(&mut self, filler: &InsertElementPatternFiller, edge: bool) {
+        if let Some(variable) = &filler.variable {
+            self.push(&identifier(&variable.variable));
+        }
+        if let Some(label_set) = &filler.label_set {
+            if edge {
+                // Only the first label is written; more were reported.
+                if let Some(label) = label_set.labels.first() {
+                    self.push(":");
+                    self.push(&identifier(label));
+                }
+            } else {
+                for label in &label_set.labels {
+                    self.push(":");
+                    self.push(&identifier(label));
+                }
+            }
+        }
+        if let Some(properties) = &filler.properties {
+            if !self.out.ends_with(['(', '[']) {
+                self.push(" ");
+            }
+            self.properties(properties);
+        }
+    }
+
+    fn set(&mut self, set: &SetStatement) {
+        self.clause("SET ");
+        self.list(&set.items.items, |writer, item| match item {
+            SetItem::Property(item) => {
+                writer.push(&identifier(&item.element));
+                writer.push(".");
+                writer.push(&identifier(&item.property));
+                writer.push(" = ");
+                writer.expression(&item.value, 0);
+            }
+            SetItem::AllProperties(item) => {
+                writer.push(&identifier(&item.element));
+                writer.push(" = ");
+                writer.properties(&item.properties);
+            }
+            SetItem::Label(item) => {
+                writer.push(&identifier(&item.element));
+                writer.push(":");
+                writer.push(&identifier(&item.label));
+            }
+        });
+        self.updated = true;
+    }
+
+    fn remove(&mut self, remove: &RemoveStatement) {
+        self.clause("REMOVE ");
+        self.list(&remove.items.items, |writer, item| match item {
+            RemoveItem::Property(item) => {
+                writer.push(&identifier(&item.element));
+                writer.push(".");
+                writer.push(&identifier(&item.property));
+            }
+            RemoveItem::Label(item) => {
+                writer.push(&identifier(&item.element));
+                writer.push(":");
+                writer.push(&identifier(&item.label));
+            }
+        });
+        self.updated = true;
+    }
+
+    fn delete(&mut self, delete: &DeleteStatement) {
+        // GQL deletes without DETACH by default, as Cypher does.
+        self.clause(match delete.detach_option {
+            DetachOption::Detach => "DETACH DELETE ",
+            DetachOption::NoDetach | DetachOption::Default => "DELETE ",
+        });
+        self.list(&delete.items.items, |writer, item| {
+            writer.expression(&item.expression, 0)
+        });
+        self.updated = true;
+    }
+
+    // ========================================================================
+    // Patterns
+    // ========================================================================
+
+    fn match_clause(&mut self, keyword: &str, pattern: &GraphPattern) {
+        if let Some(keep) = &pattern.keep_clause {
+            self.unsupported("KEEP clause", &keep.span);
+        }
+        if let Some(yield_clause) = &pattern.yield_clause {
+            self.unsupported("YIELD clause", &yield_clause.span);
+        }
+        self.reading_clause(keyword);
+        self.list(&pattern.paths.patterns, |writer, path| {
+            writer.path_pattern(path)
+        });
+
+        let mut conditions = Vec::new();
+        for path in &pattern.paths.patterns {
+            collect_element_conditions(&path.expression, &mut conditions);
+        }
+        conditions.extend(pattern.where_clause.as_ref().map(|w| &w.condition));
+        if !conditions.is_empty() {
+            self.clause("WHERE ");
+            let min = if conditions.len() > 1 { AND } else { 0 };
+            for (index, condition) in conditions.iter().enumerate() {
+                if index > 0 {
+                    self.push(" AND ");
+                }
+                self.expression(condition, min);
+            }
+        }
+        self.bound = true;
+    }
+
+    fn path_pattern(&mut self, pattern: &PathPattern) {
+        if let Some(declaration) = &pattern.variable_declaration {
+            self.push(&identifier(&declaration.variable));
+            self.push(" = ");
+        }
+        let function = match &pattern.prefix {
+            None => None,
+            Some(PathPatternPrefix::PathMode(_)) => {
+                self.path_mode(pattern);
+                None
+            }
+            Some(PathPatternPrefix::PathSearch(search)) => match search {
+                PathSearch::All(all) => {
+                    if all.mode.is_some() {
+                        self.path_mode(pattern);
+                    }
+                    None
+                }
+                PathSearch::Shortest(ShortestPathSearch::AnyShortest { mode, span }) => self
+                    .shortest(pattern, mode.is_some(), span)
+                    .then_some("shortestPath("),
+                PathSearch::Shortest(ShortestPathSearch::AllShortest { mode, span }) => self
+                    .shortest(pattern, mode.is_some(), span)
+                    .then_some("allShortestPaths("),
+                PathSearch::Any(any) => {
+                    self.unsupported("ANY path search", &any.span);
+                    None
+                }
+                PathSearch::Shortest(
+                    ShortestPathSearch::CountedShortest { span, .. }
+                    | ShortestPathSearch::CountedShortestGroups { span, .. },
+                ) => {
+                    self.unsupported("Counted shortest path search", span);
+                    None
+                }
+            },
+        };
+        if let Some(function) = function {
+            self.push(function);
+        }
+        self.path_expression(&pattern.expression);
+        if function.is_some() {
+            self.push(")");
+        }
+    }
+
+    fn path_mode(&mut self, pattern: &PathPattern) {
+        self.diagnostics.push(
+            unsupported("Path mode", TARGET, pattern.span.clone())
+                .with_help("Cypher paths follow DIFFERENT EDGES semantics and take no path mode"),
+        );
+    }
+
+    /// Checks that a shortest path search can be written with Cypher's
+    /// shortest path functions.
+    fn shortest(&mut self, pattern: &PathPattern, moded: bool, span: &Span) -> bool {
+        if moded {
+            self.path_mode(pattern);
+            return false;
+        }
+        if !is_single_edge(&pattern.expression) {
+            self.diagnostics.push(
+                unsupported(
+                    "Shortest path search over this pattern",
+                    TARGET,
+                    span.clone(),
+                )
+                .with_help("Cypher searches shortest paths over a single relationship"),
+            );
+            return false;
+        }
+        true
+    }
+
+    fn path_expression(&mut self, expression: &PathPatternExpression) {
+        match expression {
+            PathPatternExpression::Term(term) => {
+                for (index, factor) in term.factors.iter().enumerate() {
+                    // Quantified path patterns are set apart from their
+                    // neighbours for readability.
+                    if index > 0 && (is_group(factor) || is_group(&term.factors[index - 1])) {
+                        self.push(" ");
+                    }
+                    self.path_factor(factor);
+                }
+            }
+            PathPatternExpression::Union { span, .. } => {
+                self.unsupported("Path pattern union", span)
+            }
+            PathPatternExpression::Alternation { span, .. } => {
+                self.unsupported("Multiset alternation", span)
+            }
+        }
+    }
+
+    fn path_factor(&mut self, factor: &PathFactor) {
+        let quantifier = factor.quantifier.as_ref();
+        match &factor.primary {
+            PathPrimary::ElementPattern(element) => match &**element {
+                ElementPattern::Node(node) => {
+                    if quantifier.is_some() {
+                        self.unsupported("Quantified node pattern", &factor.span);
+                    }
+                    self.push("(");
+                    self.element_filler(
+                        node.variable.as_ref(),
+                        node.label_expression.as_ref(),
+                        node.properties.as_ref(),
+                        node.where_clause.as_ref(),
+                    );
+                    self.push(")");
+                }
+                ElementPattern::Edge(EdgePattern::Full(edge)) => {
+                    let Some((left, right)) = edge_delimiters(edge.direction) else {
+                        self.unsupported("Undirected edge pattern", &edge.span);
+                        return;
+                    };
+                    let filler = &edge.filler;
+                    let var_length = filler.where_clause.is_none()
+                        && filler
+                            .label_expression
+                            .as_ref()
+                            .is_none_or(is_type_disjunction);
+                    if let Some(quantifier) = quantifier
+                        && !var_length
+                    {
+                        // Quantify a one-relationship path pattern instead.
+                        self.push("(()");
+                        self.depth += 1;
+                        self.push(left);
+                        self.element_filler(
+                            filler.variable.as_ref(),
+                            filler.label_expression.as_ref(),
+                            filler.properties.as_ref(),
+                            filler.where_clause.as_ref(),
+                        );
+                        self.push(right);
+                        self.depth -= 1;
+                        self.push("())");
+                        self.group_quantifier(quantifier);
+                        return;
+                    }
+                    self.push(left);
+                    let mut separator = "";
+                    if let Some(variable) = &filler.variable {
+                        self.push(&identifier(&variable.variable));
+                    }
+                    if let Some(label) = &filler.label_expression {
+                        self.push(":");
+                        self.label_expression(label);
+                    }
+                    if let Some(quantifier) = quantifier {
+                        self.push(&var_length_quantifier(quantifier));
+                    }
+                    if !self.out.ends_with('[') {
+                        separator = " ";
+                    }
+                    if let Some(properties) = &filler.properties {
+                        self.push(separator);
+                        self.properties(properties);
+                    }
+                    self.element_where(filler.where_clause.as_ref());
+                    self.push(right);
+                }
+                ElementPattern::Edge(EdgePattern::Abbreviated(edge)) => {
+                    let (left, right) = match edge {
+                        AbbreviatedEdgePattern::LeftArrow { .. } => ("<-", "-"),
+                        AbbreviatedEdgePattern::RightArrow { .. } => ("-", "->"),
+                        AbbreviatedEdgePattern::AnyDirection { .. } => ("-", "-"),
+                        AbbreviatedEdgePattern::Undirected { span } => {
+                            return self.unsupported("Undirected edge pattern", span);
+                        }
+                    };
+                    self.push(left);
+                    if let Some(quantifier) = quantifier {
+                        self.push("[");
+                        self.push(&var_length_quantifier(quantifier));
+                        self.push("]");
+                    }
+                    self.push(right);
+                }
+            },
+            PathPrimary::ParenthesizedExpression(expression) => {
+                let Some(quantifier) = quantifier else {
+                    self.path_expression(expression);
+                    return;
+                };
+                if !has_boundary_nodes(expression) {
+                    self.diagnostics.push(
+                        unsupported(
+                            "Quantified path pattern without boundary nodes",
+                            TARGET,
+                            factor.span.clone(),
+                        )
+                        .with_help("start and end the quantified pattern with a node"),
+                    );
+                }
+                self.push("(");
+                self.depth += 1;
+                self.path_expression(expression);
+                self.depth -= 1;
+                self.push(")");
+                self.group_quantifier(quantifier);
+            }
+            PathPrimary::SimplifiedExpression(_) => {
+                self.unsupported("Simplified path pattern", &factor.span)
+            }
+        }
+    }
+
+    /// Writes the inside of a node pattern, or of an edge pattern that is
+    /// not variable-length.
+    fn element_filler(
+        &mut self,
+        variable: Option<&ElementVariableDeclaration>,
+        label: Option<&LabelExpression>,
+        properties: Option<&ElementPropertySpecification>,
+        predicate: Option<&ElementPatternPredicate>,
+    ) {
+        let mut separator = "";
+        if let Some(variable) = variable {
+            self.push(&identifier(&variable.variable));
+            separator = " ";
+        }
+        if let Some(label) = label {
+            self.push(":");
+            self.label_expression(label);
+            separator = " ";
+        }
+        if let Some(properties) = properties {
+            self.push(separator);
+            self.properties(properties);
+        }
+        self.element_where(predicate);
+    }
+
+    /// Writes an element `WHERE` inside a quantified path pattern; at the
+    /// top level it was moved to the MATCH.
+    fn element_where(&mut self, predicate: Option<&ElementPatternPredicate>) {
+        if let Some(predicate) = predicate
+            && self.depth > 0
+        {
+            if !self.out.ends_with(['(', '[']) {
+                self.push(" ");
+            }
+            self.push("WHERE ");
+            self.expression(&predicate.condition, 0);
+        }
+    }
+
+    fn properties(&mut self, properties: &ElementPropertySpecification) {
+        self.push("{");
+        self.list(&properties.properties, |writer, pair| {
+            writer.push(&identifier(&pair.key));
+            writer.push(": ");
+            writer.expression(&pair.value, 0);
+        });
+        self.push("}");
+    }
+
+    fn group_quantifier(&mut self, quantifier: &GraphPatternQuantifier) {
+        match quantifier {
+            GraphPatternQuantifier::Star { .. } => self.push("*"),
+            GraphPatternQuantifier::Plus { .. } => self.push("+"),
+            GraphPatternQuantifier::QuestionMark { .. } => self.push("{0,1}"),
+            GraphPatternQuantifier::Fixed { count, .. } => self.push(&format!("{{{count}}}")),
+            GraphPatternQuantifier::General { min, max, .. } => {
+                let max = max.map(|max| max.to_string()).unwrap_or_default();
+                self.push(&format!("{{{},{max}}}", min.unwrap_or(0)));
+            }
+        }
+    }
+
+    fn label_expression(&mut self, label: &LabelExpression) {
+        fn strength(label: &LabelExpression) -> u8 {
+            match label {
+                LabelExpression::Disjunction { .. } => 1,
+                LabelExpression::Conjunction { .. } => 2,
+                LabelExpression::Negation { .. } => 3,
+                _ => 4,
+            }
+        }
+        let operand = |writer: &mut Self, label: &LabelExpression, min: u8| {
+            if strength(label) < min {
+                writer.push("(");
+                writer.label_expression(label);
+                writer.push(")");
+            } else {
+                writer.label_expression(label);
+            }
+        };
+        match label {
+            LabelExpression::Negation { operand: inner, .. } => {
+                self.push("!");
+                operand(self, inner, 3);
+            }
+            LabelExpression::Conjunction { left, right, .. } => {
+                operand(self, left, 2);
+                self.push("&");
+                operand(self, right, 3);
+            }
+            LabelExpression::Disjunction { left, right, .. } => {
+                operand(self, left, 1);
+                self.push("|");
+                operand(self, right, 2);
+            }
+            LabelExpression::LabelName { name, .. } => self.push(&identifier(name)),
+            LabelExpression::Wildcard { .. } => self.push("%"),
+            LabelExpression::Parenthesized { expression, .. } => {
+                self.push("(");
+                self.label_expression(expression);
+                self.push(")");
+            }
+        }
+    }
+
+    // ========================================================================
+    // Expressions
+    // ========================================================================
+
+    /// Writes `expression`, parenthesized if it binds looser than `min`.
+    fn expression(&mut self, expression: &Expression, min: u8) {
+        let own = cypher_precedence(expression);
+        if own < min {
+            self.push("(");
+            self.expression(expression, 0);
+            self.push(")");
+            return;
+        }
+        match expression {
+            Expression::Literal(literal, span) => self.literal(literal, span),
+            Expression::Unary(operator, operand, _) => {
+                self.push(match operator {
+                    UnaryOperator::Plus => "+",
+                    UnaryOperator::Minus => "-",
+                    UnaryOperator::Not => "NOT ",
+                });
+                // Keep `- -x` readable as two operators.
+                let min = if matches!(**operand, Expression::Unary(..)) && own == UNARY {
+                    PRIMARY
+                } else {
+                    own
+                };
+                self.expression(operand, min);
+            }
+            Expression::Binary(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    BinaryOperator::Add | BinaryOperator::Concatenate => " + ",
+                    BinaryOperator::Subtract => " - ",
+                    BinaryOperator::Multiply => " * ",
+                    BinaryOperator::Divide => " / ",
+                    BinaryOperator::Modulo => " % ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Comparison(operator, left, right, _) => {
+                self.expression(left, own + 1);
+                self.push(match operator {
+                    ComparisonOperator::Eq => " = ",
+                    ComparisonOperator::NotEq => " <> ",
+                    ComparisonOperator::Lt => " < ",
+                    ComparisonOperator::Gt => " > ",
+                    ComparisonOperator::LtEq => " <= ",
+                    ComparisonOperator::GtEq => " >= ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Logical(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    LogicalOperator::And => " AND ",
+                    LogicalOperator::Or => " OR ",
+                    LogicalOperator::Xor => " XOR ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Parenthesized(inner, _) => {
+                self.push("(");
+                self.expression(inner, 0);
+                self.push(")");
+            }
+            Expression::PropertyReference(base, property, _) => {
+                self.expression(base, POSTFIX);
+                self.push(".");
+                self.push(&identifier(property));
+            }
+            Expression::VariableReference(name, _) => self.push(&identifier(name)),
+            Expression::ParameterReference(name, _) => {
+                self.push("$");
+                self.push(name);
+            }
+            Expression::FunctionCall(call) => self.function_call(call),
+            Expression::Case(case) => self.case(case),
+            Expression::Cast(cast) => {
+                let Some(function) = conversion_function(&cast.target_type) else {
+                    self.unsupported("Cast target type", &cast.span);
+                    return;
+                };
+                self.push(function);
+                self.push("(");
+                self.expression(&cast.operand, 0);
+                self.push(")");
+            }
+            Expression::AggregateFunction(aggregate) => self.aggregate(aggregate),
+            Expression::Predicate(predicate) => self.predicate(predicate),
+            Expression::TypeAnnotation(_, _, span) => self.unsupported("Type annotation", span),
+            Expression::ListConstructor(elements, _) => self.list_value(elements),
+            Expression::RecordConstructor(fields, _) => self.map(fields),
+            Expression::PathConstructor(_, span) => self.unsupported("Path value", span),
+            Expression::Exists(exists) => self.unsupported("EXISTS", &exists.span),
+            Expression::GraphExpression(_, span) => self.unsupported("Graph expression", span),
+            Expression::BindingTableExpression(_, span) => {
+                self.unsupported("Binding table expression", span)
+            }
+            Expression::SubqueryExpression(_, span) => self.unsupported("VALUE subquery", span),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal, span: &Span) {
+        match literal {
+            Literal::Boolean(BooleanValue::True) => self.push("TRUE"),
+            Literal::Boolean(BooleanValue::False) => self.push("FALSE"),
+            // Cypher has no separate unknown truth value.
+            Literal::Boolean(BooleanValue::Unknown) | Literal::Null => self.push("NULL"),
+            Literal::Integer(value) | Literal::Float(value) => self.push(value),
+            Literal::String(value) => self.push(&string_literal(value)),
+            Literal::ByteString(_) => self.unsupported("Byte string literal", span),
+            Literal::Date(value) => self.temporal("date", value),
+            Literal::Time(value) => self.temporal("time", value),
+            Literal::Datetime(value) => self.temporal("datetime", value),
+            Literal::Duration(value) => self.temporal("duration", value),
+            Literal::List(elements) => self.list_value(elements),
+            Literal::Record(fields) => self.map(fields),
+        }
+    }
+
+    fn temporal(&mut self, function: &str, value: &str) {
+        self.push(function);
+        self.push("(");
+        self.push(&string_literal(value));
+        self.push(")");
+    }
+
+    fn list_value(&mut self, elements: &[Expression]) {
+        self.push("[");
+        self.list(elements, |writer, element| writer.expression(element, 0));
+        self.push("]");
+    }
+
+    fn map(&mut self, fields: &[RecordField]) {
+        self.push("{");
+        self.list(fields, |writer, field| {
+            writer.push(&identifier(&field.name));
+            writer.push(": ");
+            writer.expression(&field.value, 0);
+        });
+        self.push("}");
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) {
+        let arguments = &call.arguments;
+        let name = match &call.name {
+            FunctionName::CurrentDate => return self.push("date()"),
+            FunctionName::CurrentTime => return self.push("time()"),
+            FunctionName::CurrentTimestamp => return self.push("datetime()"),
+            FunctionName::Mod | FunctionName::Power if arguments.len() == 2 => {
+                let (operator, own) = match call.name {
+                    FunctionName::Mod => (" % ", MULTIPLICATIVE),
+                    _ => (" ^ ", UNARY),
+                };
+                self.push("(");
+                self.expression(&arguments[0], own);
+                self.push(operator);
+                self.expression(&arguments[1], own + 1);
+                self.push(")");
+                return;
+            }
+            FunctionName::NullIf if arguments.len() == 2 => {
+                self.push("CASE WHEN ");
+                self.expression(&arguments[0], COMPARISON + 1);
+                self.push(" = ");
+                self.expression(&arguments[1], COMPARISON + 1);
+                self.push(" THEN NULL ELSE ");
+                self.expression(&arguments[0], 0);
+                self.push(" END");
+                return;
+            }
+            FunctionName::Trim(_) | FunctionName::LTrim | FunctionName::RTrim
+                if arguments.len() != 1 =>
+            {
+                self.unsupported("Trimming characters other than spaces", &call.span);
+                return;
+            }
+            FunctionName::Abs => "abs",
+            FunctionName::Floor => "floor",
+            FunctionName::Ceil => "ceil",
+            FunctionName::Sqrt => "sqrt",
+            FunctionName::Exp => "exp",
+            FunctionName::Ln => "log",
+            FunctionName::Log10 => "log10",
+            FunctionName::Sin => "sin",
+            FunctionName::Cos => "cos",
+            FunctionName::Tan => "tan",
+            FunctionName::Cot => "cot",
+            FunctionName::Asin => "asin",
+            FunctionName::Acos => "acos",
+            FunctionName::Atan => "atan",
+            FunctionName::Atan2 => "atan2",
+            FunctionName::Degrees => "degrees",
+            FunctionName::Radians => "radians",
+            FunctionName::Upper => "toUpper",
+            FunctionName::Lower => "toLower",
+            FunctionName::Trim(TrimSpecification::Both) => "trim",
+            FunctionName::Trim(TrimSpecification::Leading) | FunctionName::LTrim => "ltrim",
+            FunctionName::Trim(TrimSpecification::Trailing) | FunctionName::RTrim => "rtrim",
+            FunctionName::Left => "left",
+            FunctionName::Right => "right",
+            FunctionName::Normalize => "normalize",
+            FunctionName::CharLength | FunctionName::Cardinality | FunctionName::Size => "size",
+            FunctionName::PathLength => "length",
+            FunctionName::ElementId => "elementId",
+            FunctionName::Coalesce => "coalesce",
+            FunctionName::Date => "date",
+            FunctionName::Time | FunctionName::ZonedTime => "time",
+            FunctionName::Datetime | FunctionName::ZonedDatetime => "datetime",
+            FunctionName::LocalTime => "localtime",
+            FunctionName::LocalDatetime => "localdatetime",
+            FunctionName::Duration => "duration",
+            FunctionName::DurationBetween => "duration.between",
+            FunctionName::Custom(name) => &identifier(name),
+            name => {
+                let construct = format!("{} function", name.gql_name());
+                self.unsupported(&construct, &call.span);
+                return;
+            }
+        };
+        self.push(name);
+        self.push("(");
+        self.list(arguments, |writer, argument| writer.expression(argument, 0));
+        self.push(")");
+    }
+
+    fn case(&mut self, case: &CaseExpression) {
+        self.push("CASE");
+        let else_clause = match case {
+            CaseExpression::Simple(simple) => {
+                self.push(" ");
+                self.expression(&simple.operand, 0);
+                for when in &simple.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.when_value, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &simple.else_clause
+            }
+            CaseExpression::Searched(searched) => {
+                for when in &searched.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.condition, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &searched.else_clause
+            }
+        };
+        if let Some(else_result) = else_clause {
+            self.push(" ELSE ");
+            self.expression(else_result, 0);
+        }
+        self.push(" END");
+    }
+
+    fn aggregate(&mut self, aggregate: &AggregateFunction) {
+        match aggregate {
+            AggregateFunction::CountStar { .. } => self.push("count(*)"),
+            AggregateFunction::GeneralSetFunction(function) => {
+                self.push(match function.function_type {
+                    GeneralSetFunctionType::Avg => "avg(",
+                    GeneralSetFunctionType::Count => "count(",
+                    GeneralSetFunctionType::Max => "max(",
+                    GeneralSetFunctionType::Min => "min(",
+                    GeneralSetFunctionType::Sum => "sum(",
+                    GeneralSetFunctionType::CollectList => "collect(",
+                    GeneralSetFunctionType::StddevSamp => "stDev(",
+                    GeneralSetFunctionType::StddevPop => "stDevP(",
+                });
+                if function.quantifier == Some(SetQuantifier::Distinct) {
+                    self.push("DISTINCT ");
+                }
+                self.expression(&function.expression, 0);
+                self.push(")");
+            }
+            AggregateFunction::BinarySetFunction(function) => {
+                self.push(match function.function_type {
+                    BinarySetFunctionType::PercentileCont => "percentileCont(",
+                    BinarySetFunctionType::PercentileDisc => "percentileDisc(",
+                });
+                if function.quantifier == Some(SetQuantifier::Distinct) {
+                    self.push("DISTINCT ");
+                }
+                self.expression(&function.inverse_distribution_argument, 0);
+                self.push(", ");
+                self.expression(&function.expression, 0);
+                self.push(")");
+            }
+        }
+    }
+
+    fn predicate(&mut self, predicate: &Predicate) {
+        // Cypher binds IS NULL tighter than comparisons, unlike GQL.
+        match predicate {
+            Predicate::IsNull(operand, negated, _) => {
+                self.expression(operand, ADDITIVE);
+                self.push(if *negated { " IS NOT NULL" } else { " IS NULL" });
+            }
+            Predicate::IsTruthValue(operand, TruthValue::Unknown, negated, _) => {
+                self.expression(operand, ADDITIVE);
+                self.push(if *negated { " IS NOT NULL" } else { " IS NULL" });
+            }
+            Predicate::IsTruthValue(operand, value, negated, _) => {
+                // A null operand is neither true nor false.
+                let expected = *value == TruthValue::True;
+                if expected == *negated {
+                    self.push("NOT ");
+                }
+                self.push("coalesce(");
+                self.expression(operand, 0);
+                self.push(if expected { ", false)" } else { ", true)" });
+            }
+            Predicate::IsSource(operand, edge, negated, _)
+            | Predicate::IsDestination(operand, edge, negated, _) => {
+                self.push(match predicate {
+                    Predicate::IsSource(..) => "startNode(",
+                    _ => "endNode(",
+                });
+                self.expression(edge, 0);
+                self.push(if *negated { ") <> " } else { ") = " });
+                self.expression(operand, COMPARISON + 1);
+            }
+            Predicate::Same(left, right, _) => {
+                self.expression(left, COMPARISON + 1);
+                self.push(" = ");
+                self.expression(right, COMPARISON + 1);
+            }
+            Predicate::AllDifferent(elements, _) => {
+                let mut first = true;
+                for (index, left) in elements.iter().enumerate() {
+                    for right in &elements[index + 1..] {
+                        if !first {
+                            self.push(" AND ");
+                        }
+                        first = false;
+                        self.expression(left, COMPARISON + 1);
+                        self.push(" <> ");
+                        self.expression(right, COMPARISON + 1);
+                    }
+                }
+                if first {
+                    self.push("TRUE");
+                }
+            }
+            Predicate::PropertyExists(element, property, _) => {
+                self.expression(element, POSTFIX);
+                self.push(".");
+                self.push(&identifier(property));
+                self.push(" IS NOT NULL");
+            }
+            Predicate::IsLabeled(operand, Some(label), negated, _) => {
+                if *negated {
+                    self.push("NOT ");
+                }
+                self.expression(operand, POSTFIX);
+                self.push(":");
+                self.label_expression(label);
+            }
+            Predicate::IsLabeled(_, None, _, span) => self.unsupported("IS LABELED", span),
+            Predicate::IsNormalized(_, _, span) => self.unsupported("IS NORMALIZED", span),
+            Predicate::IsTyped(_, _, _, span) => self.unsupported("IS TYPED", span),
+            Predicate::IsDirected(_, _, span) => self.unsupported("IS DIRECTED", span),
+        }
+    }
+}
+
+/// Binding strength of the Cypher text written for `expression`.
+fn cypher_precedence(expression: &Expression) -> u8 {
+    match expression {
+        // Written with `+`.
+        Expression::Binary(BinaryOperator::Concatenate, ..) => ADDITIVE,
+        Expression::Predicate(predicate) => match predicate {
+            Predicate::IsTruthValue(_, TruthValue::Unknown, ..) => IS,
+            Predicate::IsTruthValue(..) | Predicate::IsLabeled(_, _, true, _) => NOT,
+            Predicate::IsSource(..) | Predicate::IsDestination(..) | Predicate::Same(..) => {
+                COMPARISON
+            }
+            Predicate::AllDifferent(elements, _) if elements.len() > 2 => AND,
+            Predicate::AllDifferent(..) => COMPARISON,
+            _ => IS,
+        },
+        _ => precedence(expression),
+    }
+}
+
+/// Opening and closing delimiters of a full edge pattern. Cypher
+/// relationships are always directed, so an edge in either direction is
+/// written as one in any direction.
+fn edge_delimiters(direction: EdgeDirection) -> Option<(&'static str, &'static str)> {
+    match direction {
+        EdgeDirection::PointingLeft => Some(("<-[", "]-")),
+        EdgeDirection::PointingRight => Some(("-[", "]->")),
+        EdgeDirection::AnyDirected | EdgeDirection::AnyDirection => Some(("-[", "]-")),
+        EdgeDirection::Undirected
+        | EdgeDirection::LeftOrUndirected
+        | EdgeDirection::RightOrUndirected => None,
+    }
+}
+
+/// The `*min..max` range of a variable-length relationship. Cypher's bare
+/// `*` means at least one hop, so lower bounds are always written.
+fn var_length_quantifier(quantifier: &GraphPatternQuantifier) -> String {
+    match quantifier {
+        GraphPatternQuantifier::Star { .. } => "*0..".to_string(),
+        GraphPatternQuantifier::Plus { .. } => "*1..".to_string(),
+        GraphPatternQuantifier::QuestionMark { .. } => "*0..1".to_string(),
+        GraphPatternQuantifier::Fixed { count, .. } => format!("*{count}"),
+        GraphPatternQuantifier::General { min, max, .. } => {
+            let max = max.map(|max| max.to_string()).unwrap_or_default();
+            format!("*{}..{max}", min.unwrap_or(0))
+        }
+    }
+}
+
+/// Whether `label` is a relationship type or a disjunction of types, the
+/// only label expressions a variable-length relationship accepts.
+fn is_type_disjunction(label: &LabelExpression) -> bool {
+    match label {
+        LabelExpression::LabelName { .. } => true,
+        LabelExpression::Disjunction { left, right, .. } => {
+            is_type_disjunction(left) && is_type_disjunction(right)
+        }
+        LabelExpression::Parenthesized { expression, .. } => is_type_disjunction(expression),
+        _ => false,
+    }
+}
+
+/// Whether `factor` is written as a quantified path pattern.
+fn is_group(factor: &PathFactor) -> bool {
+    factor.quantifier.is_some()
+        && match &factor.primary {
+            PathPrimary::ParenthesizedExpression(_) => true,
+            PathPrimary::ElementPattern(element) => match &**element {
+                ElementPattern::Edge(EdgePattern::Full(edge)) => {
+                    edge.filler.where_clause.is_some()
+                        || !edge
+                            .filler
+                            .label_expression
+                            .as_ref()
+                            .is_none_or(is_type_disjunction)
+                }
+                _ => false,
+            },
+            PathPrimary::SimplifiedExpression(_) => false,
+        }
+}
+
+/// Whether a path starts and ends with a node pattern, as a Cypher
+/// quantified path pattern must.
+fn has_boundary_nodes(expression: &PathPatternExpression) -> bool {
+    let PathPatternExpression::Term(term) = expression else {
+        return true;
+    };
+    let is_node = |factor: Option<&PathFactor>| {
+        factor.is_some_and(|factor| match &factor.primary {
+            PathPrimary::ElementPattern(element) => matches!(**element, ElementPattern::Node(_)),
+            PathPrimary::ParenthesizedExpression(_) => true,
+            PathPrimary::SimplifiedExpression(_) => false,
+        })
+    };
+    is_node(term.factors.first()) && is_node(term.factors.last())
+}
+
+/// Whether a path is a single edge between two nodes.
+fn is_single_edge(expression: &PathPatternExpression) -> bool {
+    let PathPatternExpression::Term(term) = expression else {
+        return false;
+    };
+    let kinds: Vec<Option<bool>> = term
+        .factors
+        .iter()
+        .map(|factor| match &factor.primary {
+            PathPrimary::ElementPattern(element) => {
+                Some(matches!(**element, ElementPattern::Edge(_)))
+            }
+            _ => None,
+        })
+        .collect();
+    kinds == [Some(false), Some(true), Some(false)]
+}
+
+/// Collects the element `WHERE` conditions outside quantified path patterns,
+/// which move into the `WHERE` of the MATCH.
+fn collect_element_conditions<'a>(
+    expression: &'a PathPatternExpression,
+    conditions: &mut Vec<&'a Expression>,
+) {
+    let PathPatternExpression::Term(term) = expression else {
+        return;
+    };
+    for factor in &term.factors {
+        match &factor.primary {
+            PathPrimary::ElementPattern(element) => {
+                let predicate = match &**element {
+                    ElementPattern::Node(node) => node.where_clause.as_ref(),
+                    ElementPattern::Edge(EdgePattern::Full(edge)) if !is_group(factor) => {
+                        edge.filler.where_clause.as_ref()
+                    }
+                    ElementPattern::Edge(_) => None,
+                };
+                conditions.extend(predicate.map(|predicate| &predicate.condition));
+            }
+            PathPrimary::ParenthesizedExpression(inner) if factor.quantifier.is_none() => {
+                collect_element_conditions(inner, conditions)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The Cypher conversion function for a cast to `value_type`, if there is
+/// one.
+fn conversion_function(value_type: &ValueType) -> Option<&'static str> {
+    let ValueType::Predefined(predefined, _) = value_type else {
+        return None;
+    };
+    let function = match predefined {
+        PredefinedType::Boolean(BooleanType::Bool | BooleanType::Boolean) => "toBoolean",
+        PredefinedType::CharacterString(
+            CharacterStringType::String | CharacterStringType::VarChar(None),
+        ) => "toString",
+        PredefinedType::Numeric(NumericType::Exact(
+            ExactNumericType::SignedBinary(_) | ExactNumericType::UnsignedBinary(_),
+        )) => "toInteger",
+        PredefinedType::Numeric(NumericType::Approximate(_)) => "toFloat",
+        PredefinedType::Temporal(TemporalType::Instant(instant)) => match instant {
+            TemporalInstantType::Date => "date",
+            TemporalInstantType::LocalTime => "localtime",
+            TemporalInstantType::ZonedTime => "time",
+            TemporalInstantType::LocalDatetime => "localdatetime",
+            TemporalInstantType::ZonedDatetime => "datetime",
+        },
+        _ => return None,
+    };
+    Some(function)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn render(source: &str) -> EmitResult {
+        let program = parse(source).ast.expect("program should parse");
+        super::program(&program)
+    }
+
+    #[test]
+    fn test_quantified_edges_become_variable_length() {
+        let cypher = render(
+            "MATCH DIFFERENT EDGES (a)-[e:KNOWS|LIKES]->{2,5}(b)-[:R]->*(c)<-[]-{,3}(d) \
+             RETURN d",
+        )
+        .unwrap();
+        assert!(
+            cypher.starts_with(
+                "MATCH (a)-[e:KNOWS|LIKES*2..5]->(b)-[:R*0..]->(c)<-[*0..3]-(d)\n"
+            ),
+            "{cypher}"
+        );
+    }
+
+    #[test]
+    fn test_operators_follow_cypher_precedence() {
+        let cypher = render("MATCH (a) RETURN a.s || (a.t || a.u), (a.x = 1) IS NULL").unwrap();
+        assert!(
+            cypher.ends_with("RETURN a.s + (a.t + a.u), (a.x = 1) IS NULL"),
+            "{cypher}"
+        );
+    }
+
+    #[test]
+    fn test_identifiers_are_quoted_when_needed() {
+        assert_eq!(identifier("name"), "name");
+        assert_eq!(identifier("match"), "`match`");
+        assert_eq!(identifier("a`b c"), "`a``b c`");
+    }
+}
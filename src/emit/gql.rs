@@ -0,0 +1,1187 @@
+//! GQL text emitter.
+//!
+//! Renders expressions, value types and graph type definitions as GQL. The
+//! output is canonical rather than a copy of the original source: keywords
+//! are upper case, synonyms are normalized (`VERTEX` becomes `NODE`) and
+//! parentheses are added only where operator precedence requires them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gql_parser::emit::gql;
+//!
+//! let text = gql::create_graph_type(&statement)?;
+//! ```
+
+use crate::ast::expression::{
+    AggregateFunction, BinaryOperator, BooleanValue, CaseExpression, ComparisonOperator,
+    ExistsVariant, Expression, Literal, LogicalOperator, Predicate, RecordField, TruthValue,
+    UnaryOperator,
+};
+use crate::ast::graph_type::{
+    DirectedArcType, EdgeKind, EdgeTypeFiller, EdgeTypePattern, EdgeTypePhraseContent,
+    EdgeTypeSpecification, ElementTypeSpecification, GraphTypeConstraint, LabelSetPhrase,
+    LabelSetSpecification, NestedGraphTypeSpecification, NodeTypeFiller, NodeTypePattern,
+    NodeTypeSpecification, PropertyTypesSpecification, TypeInheritanceClause,
+};
+use crate::ast::query::{LabelExpression, SetQuantifier};
+use crate::ast::references::{GraphReference, GraphTypeReference};
+use crate::ast::types::{
+    ApproximateNumericType, BooleanType, ByteStringType, CharacterStringType, DecimalKind,
+    EdgeReferenceValueType, ExactNumericType, FieldTypesSpecification, GraphReferenceValueType,
+    ImmaterialValueType, ListSyntaxForm, NodeReferenceValueType, NumericType, PredefinedType,
+    RecordType, ReferenceValueType, SignedBinaryExactNumericType, TemporalDurationType,
+    TemporalInstantType, TemporalType, TypeAnnotationOperator, UnsignedBinaryExactNumericType,
+    ValueType,
+};
+use crate::ast::{CreateGraphTypeStatement, GraphTypeSource};
+use crate::diag::Diag;
+use crate::lexer::keywords::{classify_keyword, is_keyword};
+
+use super::{EmitResult, unsupported};
+
+/// Renders an expression.
+pub fn expression(expression: &Expression) -> EmitResult {
+    let mut writer = GqlWriter::default();
+    writer.expression(expression, 0);
+    writer.finish()
+}
+
+/// Renders a value type, such as `LIST<INT64 NOT NULL>`.
+pub fn value_type(value_type: &ValueType) -> EmitResult {
+    let mut writer = GqlWriter::default();
+    writer.value_type(value_type);
+    writer.finish()
+}
+
+/// Renders a graph type body, one element type per line.
+pub fn graph_type_specification(specification: &NestedGraphTypeSpecification) -> EmitResult {
+    let mut writer = GqlWriter::default();
+    writer.graph_type(specification);
+    writer.finish()
+}
+
+/// Renders a `CREATE GRAPH TYPE` statement.
+pub fn create_graph_type(statement: &CreateGraphTypeStatement) -> EmitResult {
+    let mut writer = GqlWriter::default();
+    writer.create_graph_type(statement);
+    writer.finish()
+}
+
+/// Quotes `name` as a delimited identifier unless it can be written bare.
+pub fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let regular = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if regular && !is_keyword(name) && classify_keyword(name).is_none() {
+        return name.to_string();
+    }
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('`');
+    for ch in name.chars() {
+        match ch {
+            '`' => quoted.push_str("\\`"),
+            '\\' => quoted.push_str("\\\\"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('`');
+    quoted
+}
+
+/// Quotes `value` as a character string literal.
+pub fn string_literal(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for ch in value.chars() {
+        match ch {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// Binding strength of expression forms, loosest first, following the
+// expression parser.
+pub(super) const OR: u8 = 1;
+pub(super) const XOR: u8 = 2;
+pub(super) const AND: u8 = 3;
+pub(super) const NOT: u8 = 4;
+pub(super) const IS: u8 = 5;
+pub(super) const COMPARISON: u8 = 6;
+pub(super) const CONCATENATION: u8 = 7;
+pub(super) const ADDITIVE: u8 = 8;
+pub(super) const MULTIPLICATIVE: u8 = 9;
+pub(super) const UNARY: u8 = 10;
+pub(super) const POSTFIX: u8 = 11;
+pub(super) const PRIMARY: u8 = 12;
+
+pub(super) fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::Logical(LogicalOperator::Or, ..) => OR,
+        Expression::Logical(LogicalOperator::Xor, ..) => XOR,
+        Expression::Logical(LogicalOperator::And, ..) => AND,
+        Expression::Unary(UnaryOperator::Not, ..) => NOT,
+        Expression::Unary(..) => UNARY,
+        Expression::Predicate(
+            Predicate::AllDifferent(..) | Predicate::Same(..) | Predicate::PropertyExists(..),
+        ) => PRIMARY,
+        Expression::Predicate(_) => IS,
+        Expression::Comparison(..) => COMPARISON,
+        Expression::Binary(BinaryOperator::Concatenate, ..) => CONCATENATION,
+        Expression::Binary(BinaryOperator::Add | BinaryOperator::Subtract, ..) => ADDITIVE,
+        Expression::Binary(..) => MULTIPLICATIVE,
+        Expression::PropertyReference(..) | Expression::TypeAnnotation(..) => POSTFIX,
+        _ => PRIMARY,
+    }
+}
+
+/// Accumulates GQL text and the diagnostics for constructs it cannot render.
+#[derive(Default)]
+pub(crate) struct GqlWriter {
+    out: String,
+    diagnostics: Vec<Diag>,
+}
+
+impl GqlWriter {
+    pub(crate) fn finish(self) -> EmitResult {
+        if self.diagnostics.is_empty() {
+            Ok(self.out)
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn list<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.push(", ");
+            }
+            write(self, item);
+        }
+    }
+
+    // ========================================================================
+    // Expressions
+    // ========================================================================
+
+    /// Writes `expression`, parenthesized if it binds looser than `min`.
+    pub(crate) fn expression(&mut self, expression: &Expression, min: u8) {
+        let own = precedence(expression);
+        if own < min {
+            self.push("(");
+            self.expression(expression, 0);
+            self.push(")");
+            return;
+        }
+        match expression {
+            Expression::Literal(literal, _) => self.literal(literal),
+            Expression::Unary(operator, operand, _) => {
+                self.push(match operator {
+                    UnaryOperator::Plus => "+",
+                    UnaryOperator::Minus => "-",
+                    UnaryOperator::Not => "NOT ",
+                });
+                // Keep `- -x` from turning into the comment `--x`.
+                let min = if matches!(**operand, Expression::Unary(..)) && own == UNARY {
+                    PRIMARY
+                } else {
+                    own
+                };
+                self.expression(operand, min);
+            }
+            Expression::Binary(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    BinaryOperator::Add => " + ",
+                    BinaryOperator::Subtract => " - ",
+                    BinaryOperator::Multiply => " * ",
+                    BinaryOperator::Divide => " / ",
+                    BinaryOperator::Modulo => " % ",
+                    BinaryOperator::Concatenate => " || ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Comparison(operator, left, right, _) => {
+                self.expression(left, own + 1);
+                self.push(match operator {
+                    ComparisonOperator::Eq => " = ",
+                    ComparisonOperator::NotEq => " <> ",
+                    ComparisonOperator::Lt => " < ",
+                    ComparisonOperator::Gt => " > ",
+                    ComparisonOperator::LtEq => " <= ",
+                    ComparisonOperator::GtEq => " >= ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Logical(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    LogicalOperator::And => " AND ",
+                    LogicalOperator::Or => " OR ",
+                    LogicalOperator::Xor => " XOR ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Parenthesized(inner, _) => {
+                self.push("(");
+                self.expression(inner, 0);
+                self.push(")");
+            }
+            Expression::PropertyReference(base, property, _) => {
+                self.expression(base, POSTFIX);
+                self.push(".");
+                self.push(&identifier(property));
+            }
+            Expression::VariableReference(name, _) => self.push(&identifier(name)),
+            Expression::ParameterReference(name, _) => {
+                self.push("$");
+                self.push(name);
+            }
+            Expression::FunctionCall(call) => {
+                self.push(call.name.gql_name());
+                self.push("(");
+                self.list(&call.arguments, |writer, argument| {
+                    writer.expression(argument, 0)
+                });
+                self.push(")");
+            }
+            Expression::Case(case) => self.case(case),
+            Expression::Cast(cast) => {
+                self.push("CAST(");
+                self.expression(&cast.operand, 0);
+                self.push(" AS ");
+                self.value_type(&cast.target_type);
+                self.push(")");
+            }
+            Expression::AggregateFunction(aggregate) => self.aggregate(aggregate),
+            Expression::TypeAnnotation(operand, annotation, _) => {
+                self.expression(operand, POSTFIX);
+                self.push(match annotation.operator {
+                    TypeAnnotationOperator::DoubleColon => " :: ",
+                    TypeAnnotationOperator::Typed => " TYPED ",
+                });
+                self.value_type(&annotation.type_ref);
+            }
+            Expression::ListConstructor(elements, _) => {
+                self.push("[");
+                self.list(elements, |writer, element| writer.expression(element, 0));
+                self.push("]");
+            }
+            Expression::RecordConstructor(fields, _) => {
+                self.push("RECORD ");
+                self.record_fields(fields);
+            }
+            Expression::PathConstructor(elements, _) => {
+                self.push("PATH[");
+                self.list(elements, |writer, element| writer.expression(element, 0));
+                self.push("]");
+            }
+            Expression::Exists(exists) => match &exists.variant {
+                ExistsVariant::Subquery(inner) => {
+                    self.push("EXISTS (");
+                    self.expression(inner, 0);
+                    self.push(")");
+                }
+                ExistsVariant::GraphPattern(_) => self.diagnostics.push(unsupported(
+                    "EXISTS graph pattern",
+                    "GQL",
+                    exists.span.clone(),
+                )),
+            },
+            Expression::Predicate(predicate) => self.predicate(predicate),
+            Expression::GraphExpression(inner, _) => {
+                self.push("GRAPH ");
+                self.expression(inner, UNARY);
+            }
+            Expression::BindingTableExpression(inner, span) => {
+                if matches!(**inner, Expression::SubqueryExpression(..)) {
+                    self.diagnostics.push(unsupported(
+                        "BINDING TABLE subquery",
+                        "GQL",
+                        span.clone(),
+                    ));
+                } else {
+                    self.push("BINDING TABLE ");
+                    self.expression(inner, UNARY);
+                }
+            }
+            Expression::SubqueryExpression(_, span) => {
+                self.diagnostics
+                    .push(unsupported("VALUE subquery", "GQL", span.clone()));
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::Boolean(BooleanValue::True) => self.push("TRUE"),
+            Literal::Boolean(BooleanValue::False) => self.push("FALSE"),
+            Literal::Boolean(BooleanValue::Unknown) => self.push("UNKNOWN"),
+            Literal::Null => self.push("NULL"),
+            Literal::Integer(value) | Literal::Float(value) => self.push(value),
+            Literal::String(value) => self.push(&string_literal(value)),
+            Literal::ByteString(value) => {
+                self.push("X");
+                self.push(&string_literal(value));
+            }
+            Literal::Date(value) => self.typed_string("DATE", value),
+            Literal::Time(value) => self.typed_string("TIME", value),
+            Literal::Datetime(value) => self.typed_string("DATETIME", value),
+            Literal::Duration(value) => self.typed_string("DURATION", value),
+            Literal::List(elements) => {
+                self.push("[");
+                self.list(elements, |writer, element| writer.expression(element, 0));
+                self.push("]");
+            }
+            Literal::Record(fields) => self.record_fields(fields),
+        }
+    }
+
+    fn typed_string(&mut self, keyword: &str, value: &str) {
+        self.push(keyword);
+        self.push(" ");
+        self.push(&string_literal(value));
+    }
+
+    fn record_fields(&mut self, fields: &[RecordField]) {
+        self.push("{");
+        self.list(fields, |writer, field| {
+            writer.push(&identifier(&field.name));
+            writer.push(": ");
+            writer.expression(&field.value, 0);
+        });
+        self.push("}");
+    }
+
+    fn case(&mut self, case: &CaseExpression) {
+        self.push("CASE");
+        let else_clause = match case {
+            CaseExpression::Simple(simple) => {
+                self.push(" ");
+                self.expression(&simple.operand, 0);
+                for when in &simple.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.when_value, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &simple.else_clause
+            }
+            CaseExpression::Searched(searched) => {
+                for when in &searched.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.condition, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &searched.else_clause
+            }
+        };
+        if let Some(else_result) = else_clause {
+            self.push(" ELSE ");
+            self.expression(else_result, 0);
+        }
+        self.push(" END");
+    }
+
+    fn aggregate(&mut self, aggregate: &AggregateFunction) {
+        match aggregate {
+            AggregateFunction::CountStar { .. } => self.push("COUNT(*)"),
+            AggregateFunction::GeneralSetFunction(function) => {
+                self.push(&function.function_type.to_string());
+                self.push("(");
+                self.set_quantifier(function.quantifier);
+                self.expression(&function.expression, 0);
+                self.push(")");
+            }
+            AggregateFunction::BinarySetFunction(function) => {
+                self.push(&function.function_type.to_string());
+                self.push("(");
+                self.set_quantifier(function.quantifier);
+                self.expression(&function.inverse_distribution_argument, 0);
+                self.push(", ");
+                self.expression(&function.expression, 0);
+                self.push(")");
+            }
+        }
+    }
+
+    fn set_quantifier(&mut self, quantifier: Option<SetQuantifier>) {
+        match quantifier {
+            Some(SetQuantifier::Distinct) => self.push("DISTINCT "),
+            Some(SetQuantifier::All) => self.push("ALL "),
+            None => {}
+        }
+    }
+
+    fn predicate(&mut self, predicate: &Predicate) {
+        let is = |writer: &mut Self, operand: &Expression, negated: bool| {
+            writer.expression(operand, IS);
+            writer.push(if negated { " IS NOT " } else { " IS " });
+        };
+        match predicate {
+            Predicate::IsNull(operand, negated, _) => {
+                is(self, operand, *negated);
+                self.push("NULL");
+            }
+            Predicate::IsTyped(operand, value_type, negated, _) => {
+                is(self, operand, *negated);
+                self.push("TYPED ");
+                self.value_type(value_type);
+            }
+            Predicate::IsNormalized(operand, negated, _) => {
+                is(self, operand, *negated);
+                self.push("NORMALIZED");
+            }
+            Predicate::IsDirected(operand, negated, _) => {
+                is(self, operand, *negated);
+                self.push("DIRECTED");
+            }
+            Predicate::IsLabeled(operand, label, negated, _) => {
+                is(self, operand, *negated);
+                self.push("LABELED");
+                if let Some(label) = label {
+                    self.push(" ");
+                    self.label_expression(label);
+                }
+            }
+            Predicate::IsTruthValue(operand, value, negated, _) => {
+                is(self, operand, *negated);
+                self.push(match value {
+                    TruthValue::True => "TRUE",
+                    TruthValue::False => "FALSE",
+                    TruthValue::Unknown => "UNKNOWN",
+                });
+            }
+            Predicate::IsSource(operand, edge, negated, _) => {
+                is(self, operand, *negated);
+                self.push("SOURCE OF ");
+                self.expression(edge, COMPARISON);
+            }
+            Predicate::IsDestination(operand, edge, negated, _) => {
+                is(self, operand, *negated);
+                self.push("DESTINATION OF ");
+                self.expression(edge, COMPARISON);
+            }
+            Predicate::AllDifferent(elements, _) => {
+                self.push("ALL_DIFFERENT(");
+                self.list(elements, |writer, element| writer.expression(element, 0));
+                self.push(")");
+            }
+            Predicate::Same(left, right, _) => {
+                self.push("SAME(");
+                self.expression(left, 0);
+                self.push(", ");
+                self.expression(right, 0);
+                self.push(")");
+            }
+            Predicate::PropertyExists(element, property, _) => {
+                self.push("PROPERTY_EXISTS(");
+                self.expression(element, 0);
+                self.push(", ");
+                self.push(&identifier(property));
+                self.push(")");
+            }
+        }
+    }
+
+    /// Writes a label expression; `|` binds looser than `&`, which binds
+    /// looser than `!`.
+    pub(crate) fn label_expression(&mut self, label: &LabelExpression) {
+        fn strength(label: &LabelExpression) -> u8 {
+            match label {
+                LabelExpression::Disjunction { .. } => 1,
+                LabelExpression::Conjunction { .. } => 2,
+                LabelExpression::Negation { .. } => 3,
+                _ => 4,
+            }
+        }
+        let operand = |writer: &mut Self, label: &LabelExpression, min: u8| {
+            if strength(label) < min {
+                writer.push("(");
+                writer.label_expression(label);
+                writer.push(")");
+            } else {
+                writer.label_expression(label);
+            }
+        };
+        match label {
+            LabelExpression::Negation { operand: inner, .. } => {
+                self.push("!");
+                operand(self, inner, 3);
+            }
+            LabelExpression::Conjunction { left, right, .. } => {
+                operand(self, left, 2);
+                self.push("&");
+                operand(self, right, 3);
+            }
+            LabelExpression::Disjunction { left, right, .. } => {
+                operand(self, left, 1);
+                self.push("|");
+                operand(self, right, 2);
+            }
+            LabelExpression::LabelName { name, .. } => self.push(&identifier(name)),
+            LabelExpression::Wildcard { .. } => self.push("%"),
+            LabelExpression::Parenthesized { expression, .. } => {
+                self.push("(");
+                self.label_expression(expression);
+                self.push(")");
+            }
+        }
+    }
+
+    // ========================================================================
+    // Value types
+    // ========================================================================
+
+    pub(crate) fn value_type(&mut self, value_type: &ValueType) {
+        match value_type {
+            ValueType::Predefined(predefined, _) => self.predefined_type(predefined),
+            ValueType::Path(_) => self.push("PATH"),
+            ValueType::List(list) => match list.syntax_form {
+                ListSyntaxForm::List | ListSyntaxForm::Array => {
+                    self.push(if list.syntax_form == ListSyntaxForm::List {
+                        "LIST<"
+                    } else {
+                        "ARRAY<"
+                    });
+                    self.value_type(&list.element_type);
+                    self.push(">");
+                }
+                ListSyntaxForm::PostfixList | ListSyntaxForm::PostfixArray => {
+                    self.value_type(&list.element_type);
+                    self.push(if list.syntax_form == ListSyntaxForm::PostfixList {
+                        " LIST"
+                    } else {
+                        " ARRAY"
+                    });
+                }
+            },
+            ValueType::Record(RecordType::AnyRecord { .. }) => self.push("ANY RECORD"),
+            ValueType::Record(RecordType::Record { field_types, .. }) => {
+                self.push("RECORD ");
+                self.field_types(field_types);
+            }
+        }
+    }
+
+    fn field_types(&mut self, field_types: &FieldTypesSpecification) {
+        self.push("{");
+        self.list(&field_types.fields, |writer, field| {
+            writer.push(&identifier(&field.field_name));
+            writer.push(" :: ");
+            writer.value_type(&field.field_type);
+        });
+        self.push("}");
+    }
+
+    fn predefined_type(&mut self, predefined: &PredefinedType) {
+        let name = match predefined {
+            PredefinedType::Boolean(BooleanType::Bool) => "BOOL".to_string(),
+            PredefinedType::Boolean(BooleanType::Boolean) => "BOOLEAN".to_string(),
+            PredefinedType::CharacterString(string) => match string {
+                CharacterStringType::String => "STRING".to_string(),
+                CharacterStringType::Char(length) => sized("CHAR", *length),
+                CharacterStringType::VarChar(length) => sized("VARCHAR", *length),
+            },
+            PredefinedType::ByteString(bytes) => match bytes {
+                ByteStringType::Bytes => "BYTES".to_string(),
+                ByteStringType::Binary(length) => sized("BINARY", *length),
+                ByteStringType::VarBinary(length) => sized("VARBINARY", *length),
+            },
+            PredefinedType::Numeric(numeric) => numeric_type(numeric),
+            PredefinedType::Temporal(temporal) => match temporal {
+                TemporalType::Instant(instant) => match instant {
+                    TemporalInstantType::ZonedDatetime => "ZONED DATETIME",
+                    TemporalInstantType::LocalDatetime => "LOCAL DATETIME",
+                    TemporalInstantType::Date => "DATE",
+                    TemporalInstantType::ZonedTime => "ZONED TIME",
+                    TemporalInstantType::LocalTime => "LOCAL TIME",
+                }
+                .to_string(),
+                TemporalType::Duration(duration) => match duration {
+                    TemporalDurationType::Duration => "DURATION",
+                    TemporalDurationType::DurationYearToMonth => "DURATION YEAR TO MONTH",
+                    TemporalDurationType::DurationDayToSecond => "DURATION DAY TO SECOND",
+                }
+                .to_string(),
+            },
+            PredefinedType::Immaterial(immaterial) => match immaterial {
+                ImmaterialValueType::Null => "NULL",
+                ImmaterialValueType::NullNotNull => "NULL NOT NULL",
+                ImmaterialValueType::Nothing => "NOTHING",
+            }
+            .to_string(),
+            PredefinedType::ReferenceValue(reference) => {
+                self.reference_type(reference);
+                return;
+            }
+        };
+        self.push(&name);
+    }
+
+    fn reference_type(&mut self, reference: &ReferenceValueType) {
+        let not_null = match reference {
+            ReferenceValueType::Graph(GraphReferenceValueType::AnyPropertyGraph {
+                not_null,
+                ..
+            }) => {
+                self.push("ANY PROPERTY GRAPH");
+                *not_null
+            }
+            ReferenceValueType::Graph(GraphReferenceValueType::PropertyGraph {
+                spec,
+                not_null,
+                ..
+            }) => {
+                self.push("PROPERTY GRAPH ");
+                self.graph_type(spec);
+                *not_null
+            }
+            ReferenceValueType::BindingTable(table) => {
+                self.push("BINDING TABLE");
+                if let Some(field_types) = &table.field_types {
+                    self.push(" ");
+                    self.field_types(field_types);
+                }
+                table.not_null
+            }
+            ReferenceValueType::Node(NodeReferenceValueType::Any { not_null, .. }) => {
+                self.push("ANY NODE");
+                *not_null
+            }
+            ReferenceValueType::Node(NodeReferenceValueType::Typed { spec, not_null, .. }) => {
+                self.node_type(spec);
+                *not_null
+            }
+            ReferenceValueType::Edge(EdgeReferenceValueType::Any { not_null, .. }) => {
+                self.push("ANY EDGE");
+                *not_null
+            }
+            ReferenceValueType::Edge(EdgeReferenceValueType::Typed { spec, not_null, .. }) => {
+                self.edge_type(spec);
+                *not_null
+            }
+        };
+        if not_null {
+            self.push(" NOT NULL");
+        }
+    }
+
+    // ========================================================================
+    // Graph types
+    // ========================================================================
+
+    fn create_graph_type(&mut self, statement: &CreateGraphTypeStatement) {
+        self.push("CREATE ");
+        if statement.or_replace {
+            self.push("OR REPLACE ");
+        }
+        if statement.property {
+            self.push("PROPERTY ");
+        }
+        self.push("GRAPH TYPE ");
+        if statement.if_not_exists {
+            self.push("IF NOT EXISTS ");
+        }
+        self.graph_type_reference(&statement.graph_type);
+        match &statement.source {
+            Some(GraphTypeSource::Detailed { specification, .. }) => {
+                self.push(" AS ");
+                self.graph_type(specification);
+            }
+            Some(GraphTypeSource::AsCopyOf { graph_type, .. }) => {
+                self.push(" AS COPY OF ");
+                self.graph_type_reference(graph_type);
+            }
+            Some(GraphTypeSource::LikeGraph { graph, span }) => {
+                self.push(" LIKE ");
+                match graph {
+                    GraphReference::CatalogQualified { name, .. } if name.parent.is_none() => {
+                        self.push(&name.name)
+                    }
+                    GraphReference::HomeGraph { .. } => self.push("HOME_GRAPH"),
+                    GraphReference::HomePropertyGraph { .. } => self.push("HOME_PROPERTY_GRAPH"),
+                    GraphReference::ReferenceParameter { name, .. } => {
+                        self.push("$$");
+                        self.push(name);
+                    }
+                    _ => self.diagnostics.push(unsupported(
+                        "this graph reference",
+                        "GQL",
+                        span.clone(),
+                    )),
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn graph_type_reference(&mut self, reference: &GraphTypeReference) {
+        match reference {
+            GraphTypeReference::CatalogQualified { name, span } => {
+                if name.parent.is_some() {
+                    self.diagnostics.push(unsupported(
+                        "a qualified graph type name",
+                        "GQL",
+                        span.clone(),
+                    ));
+                }
+                self.push(&name.name);
+            }
+            GraphTypeReference::ReferenceParameter { name, .. } => {
+                self.push("$$");
+                self.push(name);
+            }
+        }
+    }
+
+    pub(crate) fn graph_type(&mut self, specification: &NestedGraphTypeSpecification) {
+        self.push("{\n");
+        for (index, element) in specification.body.element_types.types.iter().enumerate() {
+            if index > 0 {
+                self.push(",\n");
+            }
+            self.push("  ");
+            match element {
+                ElementTypeSpecification::Node(node) => self.node_type(node),
+                ElementTypeSpecification::Edge(edge) => self.edge_type(edge),
+            }
+        }
+        self.push("\n}");
+    }
+
+    fn node_type(&mut self, node: &NodeTypeSpecification) {
+        if node.is_abstract {
+            self.push("ABSTRACT ");
+        }
+        self.push("NODE TYPE");
+        if let Some(name) = &node.name {
+            self.push(" ");
+            self.push(&identifier(name));
+        }
+        self.inheritance(node.inheritance.as_ref());
+
+        let phrase = &node.pattern.phrase;
+        match &phrase.filler {
+            Some(filler) => {
+                self.node_filler(filler);
+                if let Some(alias) = &phrase.alias {
+                    self.push(" AS ");
+                    self.push(&identifier(&alias.name));
+                }
+            }
+            // `NODE TYPE AS x` does not parse; the pattern form carries the alias.
+            None if node.name.is_none() => {
+                self.push(" (");
+                if let Some(alias) = &phrase.alias {
+                    self.push(&identifier(&alias.name));
+                }
+                self.push(")");
+            }
+            None => {
+                if let Some(alias) = &phrase.alias {
+                    self.push(" AS ");
+                    self.push(&identifier(&alias.name));
+                }
+            }
+        }
+    }
+
+    fn node_filler(&mut self, filler: &NodeTypeFiller) {
+        if let Some(label_set) = &filler.label_set {
+            self.push(" ");
+            self.label_set_phrase(&label_set.label_set_phrase);
+        }
+        if let Some(property_types) = &filler.property_types {
+            self.push(" ");
+            self.property_types(&property_types.specification);
+        }
+        if let Some(key) = &filler.key_label_set {
+            self.push(" KEY ");
+            self.label_set(&key.label_set);
+        }
+        self.constraints(&filler.constraints);
+    }
+
+    fn edge_type(&mut self, edge: &EdgeTypeSpecification) {
+        let (left, right) = match &edge.pattern {
+            EdgeTypePattern::Directed(directed) => {
+                (&directed.left_endpoint, &directed.right_endpoint)
+            }
+            EdgeTypePattern::Undirected(undirected) => {
+                (&undirected.left_endpoint, &undirected.right_endpoint)
+            }
+        };
+        let filler = match &edge.pattern {
+            EdgeTypePattern::Directed(directed) => match &directed.arc {
+                DirectedArcType::PointingRight(arc) => arc.filler.as_ref(),
+                DirectedArcType::PointingLeft(arc) => arc.filler.as_ref(),
+            },
+            EdgeTypePattern::Undirected(undirected) => undirected.arc.filler.as_ref(),
+        };
+
+        // The phrase form repeats its endpoints in the phrase; the visual
+        // form (`(a)-[...]->(b)`) leaves them empty there.
+        let phrase_form = filler.is_some_and(|filler| {
+            let pair = &filler.phrase.endpoint_pair_phrase.endpoint_pair;
+            pair.source.node_type == *left && pair.destination.node_type == *right
+        });
+        match filler {
+            Some(filler) if phrase_form => self.edge_phrase(edge, filler, left, right),
+            _ => self.edge_visual(edge, filler, left, right),
+        }
+    }
+
+    fn edge_phrase(
+        &mut self,
+        edge: &EdgeTypeSpecification,
+        filler: &EdgeTypeFiller,
+        source: &NodeTypePattern,
+        destination: &NodeTypePattern,
+    ) {
+        if edge.is_abstract {
+            self.push("ABSTRACT ");
+        }
+        let directed = match &edge.pattern {
+            EdgeTypePattern::Undirected(_) => Some(false),
+            EdgeTypePattern::Directed(_) if filler.phrase.edge_kind == EdgeKind::Inferred => None,
+            EdgeTypePattern::Directed(_) => Some(true),
+        };
+        match directed {
+            Some(true) => self.push("DIRECTED "),
+            Some(false) => self.push("UNDIRECTED "),
+            None => {}
+        }
+        self.push("EDGE TYPE");
+        if let Some(name) = &edge.name {
+            self.push(" ");
+            self.push(&identifier(name));
+        }
+        self.inheritance(edge.inheritance.as_ref());
+        if let Some(content) = &filler.phrase.filler_content {
+            self.edge_content(content);
+        }
+        self.constraints(&filler.constraints);
+        self.push(" CONNECTING (");
+        self.endpoint(source);
+        self.push(" TO ");
+        self.endpoint(destination);
+        self.push(")");
+    }
+
+    fn edge_visual(
+        &mut self,
+        edge: &EdgeTypeSpecification,
+        filler: Option<&EdgeTypeFiller>,
+        left: &NodeTypePattern,
+        right: &NodeTypePattern,
+    ) {
+        if edge.is_abstract {
+            self.push("ABSTRACT ");
+        }
+        self.node_pattern(left);
+        self.push(match &edge.pattern {
+            EdgeTypePattern::Directed(directed) => match directed.arc {
+                DirectedArcType::PointingRight(_) => "-[",
+                DirectedArcType::PointingLeft(_) => "<-[",
+            },
+            EdgeTypePattern::Undirected(_) => "~[",
+        });
+        if let Some(filler) = filler {
+            let start = self.out.len();
+            if let Some(content) = &filler.phrase.filler_content {
+                self.edge_content(content);
+            }
+            self.constraints(&filler.constraints);
+            // Drop the separator written before the first part.
+            if self.out[start..].starts_with(' ') {
+                self.out.remove(start);
+            }
+        }
+        self.push(match &edge.pattern {
+            EdgeTypePattern::Directed(directed) => match directed.arc {
+                DirectedArcType::PointingRight(_) => "]->",
+                DirectedArcType::PointingLeft(_) => "]-",
+            },
+            EdgeTypePattern::Undirected(_) => "]~",
+        });
+        self.node_pattern(right);
+    }
+
+    fn edge_content(&mut self, content: &EdgeTypePhraseContent) {
+        if let Some(label_set) = &content.label_set {
+            self.push(" ");
+            self.label_set_phrase(&label_set.label_set_phrase);
+        }
+        if let Some(property_types) = &content.property_types {
+            self.push(" ");
+            self.property_types(&property_types.specification);
+        }
+    }
+
+    /// Writes an endpoint of `CONNECTING (... TO ...)`: a bare node type
+    /// reference, or a parenthesized node type pattern.
+    fn endpoint(&mut self, endpoint: &NodeTypePattern) {
+        match (&endpoint.phrase.alias, &endpoint.phrase.filler) {
+            (Some(alias), None) => self.push(&identifier(&alias.name)),
+            _ => self.node_pattern(endpoint),
+        }
+    }
+
+    fn node_pattern(&mut self, pattern: &NodeTypePattern) {
+        self.push("(");
+        if let Some(alias) = &pattern.phrase.alias {
+            self.push(&identifier(&alias.name));
+        }
+        if let Some(filler) = &pattern.phrase.filler {
+            let start = self.out.len();
+            self.node_filler(filler);
+            if pattern.phrase.alias.is_none() && self.out[start..].starts_with(' ') {
+                self.out.remove(start);
+            }
+        }
+        self.push(")");
+    }
+
+    fn inheritance(&mut self, inheritance: Option<&TypeInheritanceClause>) {
+        if let Some(clause) = inheritance {
+            self.push(" INHERITS ");
+            self.list(&clause.parents, |writer, parent| writer.push(&identifier(&parent.name)));
+        }
+    }
+
+    fn label_set_phrase(&mut self, phrase: &LabelSetPhrase) {
+        match phrase {
+            LabelSetPhrase::Label(label) => {
+                self.push("LABEL ");
+                self.push(&identifier(&label.name));
+            }
+            LabelSetPhrase::Labels(set) => {
+                self.push("LABELS ");
+                self.label_set(set);
+            }
+            LabelSetPhrase::IsLabelSet(set) => {
+                self.push("IS ");
+                self.label_set(set);
+            }
+        }
+    }
+
+    fn label_set(&mut self, set: &LabelSetSpecification) {
+        for (index, label) in set.labels.iter().enumerate() {
+            if index > 0 {
+                self.push(" & ");
+            }
+            self.push(&identifier(&label.name));
+        }
+    }
+
+    fn property_types(&mut self, specification: &PropertyTypesSpecification) {
+        let Some(list) = &specification.property_types else {
+            self.push("{}");
+            return;
+        };
+        self.push("{ ");
+        self.list(&list.types, |writer, property| {
+            writer.push(&identifier(&property.name.name));
+            writer.push(" :: ");
+            writer.value_type(&property.value_type.value_type);
+            if property.not_null {
+                writer.push(" NOT NULL");
+            }
+            if let Some(default) = &property.default {
+                writer.push(" DEFAULT ");
+                writer.expression(default, 0);
+            }
+            if let Some(check) = &property.check {
+                writer.push(" CHECK (");
+                writer.expression(check, 0);
+                writer.push(")");
+            }
+        });
+        self.push(" }");
+    }
+
+    fn constraints(&mut self, constraints: &[GraphTypeConstraint]) {
+        for constraint in constraints {
+            self.push(" CONSTRAINT ");
+            match constraint {
+                GraphTypeConstraint::Key { properties, .. }
+                | GraphTypeConstraint::Unique { properties, .. }
+                | GraphTypeConstraint::Mandatory { properties, .. } => {
+                    self.push(constraint.kind_name());
+                    self.push(" (");
+                    self.list(properties, |writer, property| writer.push(&identifier(&property.name)));
+                    self.push(")");
+                }
+                GraphTypeConstraint::Check { condition, .. } => {
+                    self.push("CHECK (");
+                    self.expression(condition, 0);
+                    self.push(")");
+                }
+                GraphTypeConstraint::Custom {
+                    name, arguments, ..
+                } => {
+                    self.push(name);
+                    self.push(" (");
+                    self.list(arguments, |writer, argument| writer.push(&argument.raw));
+                    self.push(")");
+                }
+            }
+        }
+    }
+}
+
+fn sized(name: &str, length: Option<u32>) -> String {
+    match length {
+        Some(length) => format!("{name}({length})"),
+        None => name.to_string(),
+    }
+}
+
+fn numeric_type(numeric: &NumericType) -> String {
+    match numeric {
+        NumericType::Exact(ExactNumericType::SignedBinary(signed)) => match signed {
+            SignedBinaryExactNumericType::Int8 => "INT8",
+            SignedBinaryExactNumericType::Int16 => "INT16",
+            SignedBinaryExactNumericType::Int32 => "INT32",
+            SignedBinaryExactNumericType::Int64 => "INT64",
+            SignedBinaryExactNumericType::Int128 => "INT128",
+            SignedBinaryExactNumericType::Int256 => "INT256",
+            SignedBinaryExactNumericType::SmallInt => "SMALLINT",
+            SignedBinaryExactNumericType::Int => "INT",
+            SignedBinaryExactNumericType::Integer => "INTEGER",
+            SignedBinaryExactNumericType::BigInt => "BIGINT",
+        }
+        .to_string(),
+        NumericType::Exact(ExactNumericType::UnsignedBinary(unsigned)) => match unsigned {
+            UnsignedBinaryExactNumericType::UInt8 => "UINT8",
+            UnsignedBinaryExactNumericType::UInt16 => "UINT16",
+            UnsignedBinaryExactNumericType::UInt32 => "UINT32",
+            UnsignedBinaryExactNumericType::UInt64 => "UINT64",
+            UnsignedBinaryExactNumericType::UInt128 => "UINT128",
+            UnsignedBinaryExactNumericType::UInt256 => "UINT256",
+            UnsignedBinaryExactNumericType::USmallInt => "USMALLINT",
+            UnsignedBinaryExactNumericType::UInt => "UINT",
+            UnsignedBinaryExactNumericType::UBigInt => "UBIGINT",
+        }
+        .to_string(),
+        NumericType::Exact(ExactNumericType::Decimal(decimal)) => {
+            let name = match decimal.kind {
+                DecimalKind::Decimal => "DECIMAL",
+                DecimalKind::Dec => "DEC",
+            };
+            match (decimal.precision, decimal.scale) {
+                (Some(precision), Some(scale)) => format!("{name}({precision}, {scale})"),
+                (Some(precision), None) => format!("{name}({precision})"),
+                _ => name.to_string(),
+            }
+        }
+        NumericType::Approximate(approximate) => match approximate {
+            ApproximateNumericType::Float16 => "FLOAT16".to_string(),
+            ApproximateNumericType::Float32 => "FLOAT32".to_string(),
+            ApproximateNumericType::Float64 => "FLOAT64".to_string(),
+            ApproximateNumericType::Float128 => "FLOAT128".to_string(),
+            ApproximateNumericType::Float256 => "FLOAT256".to_string(),
+            ApproximateNumericType::Float(precision) => sized("FLOAT", *precision),
+            ApproximateNumericType::Real => "REAL".to_string(),
+            ApproximateNumericType::DoublePrecision => "DOUBLE PRECISION".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{CatalogStatementKind, Statement};
+    use crate::parse;
+
+    fn round_trip(source: &str) -> String {
+        let program = parse(&format!("RETURN {source}"))
+            .ast
+            .expect("parse should succeed");
+        let Statement::Query(query) = &program.statements[0] else {
+            panic!("expected query");
+        };
+        let mut expressions = Vec::new();
+        let _ = crate::ast::visit::Visit::visit_query(
+            &mut ExpressionCollector(&mut expressions),
+            &query.query,
+        );
+        expression(&expressions[0]).expect("expression should render")
+    }
+
+    struct ExpressionCollector<'a>(&'a mut Vec<Expression>);
+
+    impl crate::ast::visit::Visit for ExpressionCollector<'_> {
+        type Break = ();
+
+        fn visit_expression(&mut self, expression: &Expression) -> std::ops::ControlFlow<()> {
+            self.0.push(expression.clone());
+            std::ops::ControlFlow::Break(())
+        }
+    }
+
+    #[test]
+    fn test_expressions_render_canonically() {
+        assert_eq!(round_trip("a.x+1 * 2"), "a.x + 1 * 2");
+        assert_eq!(round_trip("(a + 1) * 2"), "(a + 1) * 2");
+        assert_eq!(
+            round_trip("not a and b or c xor d"),
+            "NOT a AND b OR c XOR d"
+        );
+        assert_eq!(round_trip("'it\\'s' || x"), "'it\\'s' || x");
+        assert_eq!(round_trip("n IS NOT NULL"), "n IS NOT NULL");
+        assert_eq!(round_trip("date '2024-01-01'"), "DATE '2024-01-01'");
+        assert_eq!(round_trip("cast(x AS int64)"), "CAST(x AS INT64)");
+        assert_eq!(
+            round_trip("case when x > 1 then 'a' else 'b' end"),
+            "CASE WHEN x > 1 THEN 'a' ELSE 'b' END"
+        );
+    }
+
+    #[test]
+    fn test_missing_parentheses_are_added() {
+        let one = || Box::new(Expression::Literal(Literal::Integer("1".into()), 0..0));
+        let sum = Expression::Binary(BinaryOperator::Add, one(), one(), 0..0);
+        let product = Expression::Binary(BinaryOperator::Multiply, Box::new(sum), one(), 0..0);
+        assert_eq!(expression(&product).unwrap(), "(1 + 1) * 1");
+    }
+
+    #[test]
+    fn test_identifiers_are_quoted_when_needed() {
+        assert_eq!(identifier("name"), "name");
+        assert_eq!(identifier("my var"), "`my var`");
+        assert_eq!(identifier("MATCH"), "`MATCH`");
+    }
+
+    #[test]
+    fn test_create_graph_type_round_trips() {
+        let source = "CREATE GRAPH TYPE g AS {\n  \
+            NODE TYPE Person LABEL Person { age :: INT32, name :: STRING NOT NULL } \
+            CONSTRAINT KEY (name) CONSTRAINT CHECK (age >= 0),\n  \
+            DIRECTED EDGE TYPE KNOWS LABEL KNOWS { since :: DATE } CONNECTING (Person TO Person)\n}";
+        let program = parse(source).ast.expect("parse should succeed");
+        let Statement::Catalog(catalog) = &program.statements[0] else {
+            panic!("expected catalog statement");
+        };
+        let CatalogStatementKind::CreateGraphType(statement) = &catalog.kind else {
+            panic!("expected CREATE GRAPH TYPE");
+        };
+        assert_eq!(create_graph_type(statement).unwrap(), source);
+    }
+}
@@ -0,0 +1,26 @@
+//! Source text emitters.
+//!
+//! Emitters turn AST nodes back into query text. [`gql`] renders GQL that
+//! parses back into an equivalent AST; it is used to export schemas and to
+//! print rewritten queries. [`sql_pgq`] translates linear queries into
+//! SQL/PGQ `GRAPH_TABLE` queries for relational engines, and [`cypher`]
+//! renders programs as openCypher for Neo4j-compatible stores.
+//!
+//! Emitters report constructs they cannot express as [`Diag`]s instead of
+//! producing partial text.
+
+pub mod cypher;
+pub mod gql;
+pub mod sql_pgq;
+
+use crate::ast::Span;
+use crate::diag::Diag;
+
+/// Rendered text, or the diagnostics explaining why it could not be rendered.
+pub type EmitResult = Result<String, Vec<Diag>>;
+
+/// Reports a construct that cannot be expressed in the target language.
+pub(crate) fn unsupported(construct: &str, target: &str, span: Span) -> Diag {
+    Diag::error(format!("{construct} cannot be rendered as {target}"))
+        .with_primary_label(span, "unsupported construct")
+}
@@ -0,0 +1,1258 @@
+//! SQL/PGQ emitter.
+//!
+//! Renders a linear GQL query as an SQL:2023 query over `GRAPH_TABLE`. The
+//! MATCH patterns and their WHERE conditions go inside the graph table
+//! almost unchanged, since SQL/PGQ shares the GQL pattern language. The
+//! RETURN items become the `COLUMNS` of the graph table, and grouping,
+//! ordering and paging are applied by the enclosing `SELECT`:
+//!
+//! ```text
+//! MATCH (a:Person)-[:KNOWS]->{1,2}(b:Person)
+//! WHERE a.age > 30
+//! RETURN b.name AS name, count(*) AS n ORDER BY n DESC LIMIT 10
+//! ```
+//!
+//! becomes
+//!
+//! ```text
+//! SELECT name, COUNT(*) AS n
+//! FROM GRAPH_TABLE (social
+//!   MATCH (a IS Person)-[IS KNOWS]->{1,2}(b IS Person)
+//!   WHERE a.age > 30
+//!   COLUMNS (b.name AS name)
+//! )
+//! GROUP BY name
+//! ORDER BY n DESC
+//! FETCH FIRST 10 ROWS ONLY
+//! ```
+//!
+//! Queries are expected to have passed semantic validation. Constructs
+//! without an SQL/PGQ counterpart, such as OPTIONAL MATCH, unbounded
+//! quantifiers or list values, are reported rather than approximated.
+//! Parameters are written as `:name` host parameters.
+
+use smol_str::SmolStr;
+
+use crate::analysis::ExpressionInfo;
+use crate::ast::Span;
+use crate::ast::expression::{
+    AggregateFunction, BinaryOperator, BinarySetFunctionType, BooleanValue, CaseExpression,
+    ComparisonOperator, Expression, FunctionCall, FunctionName, GeneralSetFunctionType, Literal,
+    LogicalOperator, Predicate, TrimSpecification, TruthValue, UnaryOperator,
+};
+use crate::ast::query::{
+    AbbreviatedEdgePattern, EdgeDirection, EdgePattern, ElementPattern, ElementPatternPredicate,
+    ElementPropertySpecification, ElementVariableDeclaration, GraphPatternQuantifier,
+    GroupingElement, LabelExpression, LinearQuery, MatchMode, MatchStatement, NullOrdering,
+    OrderByAndPageStatement, OrderingSpecification, PathFactor, PathMode, PathPattern,
+    PathPatternExpression, PathPatternPrefix, PathPrimary, PathSearch, PrimitiveQueryStatement,
+    PrimitiveResultStatement, Query, ReturnItemList, ReturnStatement, SetQuantifier,
+    ShortestPathSearch,
+};
+use crate::ast::types::{
+    ApproximateNumericType, BooleanType, ByteStringType, CharacterStringType, ExactNumericType,
+    NumericType, PredefinedType, SignedBinaryExactNumericType, TemporalInstantType, TemporalType,
+    ValueType,
+};
+use crate::diag::Diag;
+use crate::lexer::keywords::{classify_keyword, is_keyword};
+
+use super::gql::{AND, COMPARISON, IS, POSTFIX, PRIMARY, UNARY, precedence};
+use super::{EmitResult, unsupported};
+
+const TARGET: &str = "SQL/PGQ";
+
+/// Renders `query` as a `SELECT` over `GRAPH_TABLE`.
+///
+/// The graph table reads `graph` unless the query names its own graph with
+/// `USE`. `graph` is written as given, so it may be a qualified name.
+pub fn linear_query(query: &LinearQuery, graph: &str) -> EmitResult {
+    let mut writer = SqlWriter::default();
+    writer.linear_query(query, graph);
+    writer.finish()
+}
+
+/// Renders a query; only linear queries have an SQL/PGQ form.
+pub fn query(query: &Query, graph: &str) -> EmitResult {
+    match query {
+        Query::Linear(linear) => linear_query(linear, graph),
+        Query::Parenthesized(inner, _) => self::query(inner, graph),
+        Query::Composite(composite) => Err(vec![unsupported(
+            "Composite query",
+            TARGET,
+            composite.span.clone(),
+        )]),
+    }
+}
+
+/// Quotes `name` as an SQL delimited identifier unless it can be written
+/// bare.
+///
+/// GQL reserved words are quoted as well; they cover the SQL reserved words
+/// that can appear in a graph table query.
+pub fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let regular = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if regular && !is_keyword(name) && classify_keyword(name).is_none() {
+        return name.to_string();
+    }
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quotes `value` as an SQL character string literal.
+pub fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Where an expression is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Scope {
+    /// Inside `GRAPH_TABLE`, where the element variables are bound.
+    #[default]
+    Graph,
+    /// In the enclosing `SELECT`, which only sees the graph table columns.
+    Table,
+}
+
+/// Accumulates SQL text and the diagnostics for constructs it cannot render.
+#[derive(Default)]
+struct SqlWriter {
+    out: String,
+    diagnostics: Vec<Diag>,
+    scope: Scope,
+    /// Element variables declared by the MATCH patterns.
+    elements: Vec<SmolStr>,
+    /// `COLUMNS` of the graph table as (graph expression, column name).
+    columns: Vec<(String, String)>,
+    /// Names of the result columns, which sort keys may refer to.
+    outputs: Vec<SmolStr>,
+}
+
+impl SqlWriter {
+    fn finish(self) -> EmitResult {
+        if self.diagnostics.is_empty() {
+            Ok(self.out)
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn list<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.push(", ");
+            }
+            write(self, item);
+        }
+    }
+
+    /// Runs `write` and returns what it wrote instead of appending it.
+    fn capture(&mut self, write: impl FnOnce(&mut Self)) -> String {
+        let outer = std::mem::take(&mut self.out);
+        write(self);
+        std::mem::replace(&mut self.out, outer)
+    }
+
+    fn unsupported(&mut self, construct: &str, span: &Span) {
+        self.diagnostics
+            .push(unsupported(construct, TARGET, span.clone()));
+    }
+
+    // ========================================================================
+    // Query structure
+    // ========================================================================
+
+    fn linear_query(&mut self, query: &LinearQuery, default_graph: &str) {
+        let graph = match &query.use_graph {
+            None => default_graph.to_string(),
+            Some(use_graph) => match &use_graph.graph {
+                Expression::VariableReference(name, _) => identifier(name),
+                _ => {
+                    self.unsupported("Computed USE graph", &use_graph.span);
+                    String::new()
+                }
+            },
+        };
+
+        let mut patterns: Vec<&PathPattern> = Vec::new();
+        let mut conditions: Vec<&Expression> = Vec::new();
+        for statement in &query.primitive_statements {
+            match statement {
+                PrimitiveQueryStatement::Match(MatchStatement::Simple(simple)) => {
+                    let pattern = &simple.pattern;
+                    if pattern.match_mode == Some(MatchMode::DifferentEdges) {
+                        self.unsupported("DIFFERENT EDGES match mode", &pattern.span);
+                    }
+                    if let Some(keep) = &pattern.keep_clause {
+                        self.unsupported("KEEP clause", &keep.span);
+                    }
+                    if let Some(yield_clause) = &pattern.yield_clause {
+                        self.unsupported("YIELD clause", &yield_clause.span);
+                    }
+                    patterns.extend(&pattern.paths.patterns);
+                    conditions.extend(pattern.where_clause.as_ref().map(|w| &w.condition));
+                }
+                PrimitiveQueryStatement::Match(MatchStatement::Optional(optional)) => {
+                    self.unsupported("OPTIONAL MATCH", &optional.span);
+                }
+                PrimitiveQueryStatement::Filter(filter) => conditions.push(&filter.condition),
+                PrimitiveQueryStatement::Call(call) => self.unsupported("CALL", &call.span),
+                PrimitiveQueryStatement::Let(statement) => self.unsupported("LET", &statement.span),
+                PrimitiveQueryStatement::For(statement) => self.unsupported("FOR", &statement.span),
+                PrimitiveQueryStatement::OrderByAndPage(statement) => self.diagnostics.push(
+                    unsupported("ORDER BY before RETURN", TARGET, statement.span.clone())
+                        .with_help("write ORDER BY, OFFSET and LIMIT after RETURN"),
+                ),
+                PrimitiveQueryStatement::Select(statement) => {
+                    self.unsupported("SELECT statement", &statement.span)
+                }
+            }
+        }
+        if patterns.is_empty() {
+            self.unsupported("Query without MATCH", &query.span);
+        }
+        let ret = match query.result_statement.as_deref() {
+            Some(PrimitiveResultStatement::Return(ret)) => ret,
+            Some(PrimitiveResultStatement::Finish(span)) => {
+                self.unsupported("FINISH", span);
+                return;
+            }
+            None => {
+                self.unsupported("Query without RETURN", &query.span);
+                return;
+            }
+        };
+        for pattern in &patterns {
+            collect_elements(&pattern.expression, &mut self.elements);
+        }
+
+        // The enclosing SELECT is written first: the graph expressions it
+        // needs become columns of the graph table.
+        self.scope = Scope::Table;
+        let select = self.capture(|writer| writer.select_list(ret));
+        let group_by = self.capture(|writer| writer.group_by(ret));
+        let order_by = ret
+            .order_by_and_page
+            .as_ref()
+            .map(|page| self.capture(|writer| writer.order_by_and_page(page)));
+
+        self.scope = Scope::Graph;
+        let paths = self.capture(|writer| {
+            writer.list(&patterns, |writer, pattern| writer.path_pattern(pattern))
+        });
+        let condition = self.capture(|writer| {
+            let min = if conditions.len() > 1 { AND } else { 0 };
+            for (index, condition) in conditions.iter().enumerate() {
+                if index > 0 {
+                    writer.push(" AND ");
+                }
+                writer.expression(condition, min);
+            }
+        });
+
+        self.push("SELECT ");
+        self.push(&select);
+        self.push("\nFROM GRAPH_TABLE (");
+        self.push(&graph);
+        self.push("\n  MATCH ");
+        self.push(&paths);
+        if !condition.is_empty() {
+            self.push("\n  WHERE ");
+            self.push(&condition);
+        }
+        // A graph table has at least one column. When every result column
+        // is an aggregate that needs no graph value, such as COUNT(*), each
+        // match contributes a constant row.
+        if self.columns.is_empty() {
+            let name = self.fresh_name("one", &self.outputs.clone()).to_string();
+            self.columns.push(("1".to_string(), name));
+        }
+        self.push("\n  COLUMNS (");
+        let columns = std::mem::take(&mut self.columns);
+        self.list(&columns, |writer, (expression, name)| {
+            writer.push(expression);
+            writer.push(" AS ");
+            writer.push(&identifier(name));
+        });
+        self.push(")\n)");
+        if !group_by.is_empty() {
+            self.push("\nGROUP BY ");
+            self.push(&group_by);
+        }
+        if let Some(order_by) = order_by {
+            self.push(&order_by);
+        }
+    }
+
+    fn select_list(&mut self, ret: &ReturnStatement) {
+        let ReturnItemList::Items { items } = &ret.items else {
+            self.unsupported("RETURN *", &ret.span);
+            return;
+        };
+        if ret.quantifier == Some(SetQuantifier::Distinct) {
+            self.push("DISTINCT ");
+        }
+        // Result column names are fixed first so that the columns added for
+        // aggregate arguments do not take an alias.
+        let mut outputs = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let name = match (&item.alias, &item.expression) {
+                (Some(alias), _) => alias.clone(),
+                (None, Expression::PropertyReference(_, property, _)) => property.clone(),
+                (None, Expression::VariableReference(name, _)) => name.clone(),
+                (None, _) => SmolStr::new(format!("col{}", index + 1)),
+            };
+            outputs.push(self.fresh_name(&name, &outputs));
+        }
+        self.outputs = outputs;
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.push(", ");
+            }
+            let name = self.outputs[index].clone();
+            if ExpressionInfo::analyze(&item.expression).contains_aggregate {
+                self.expression(&item.expression, 0);
+                self.push(" AS ");
+                self.push(&identifier(&name));
+            } else {
+                // Per-row values are computed inside the graph table.
+                self.scope = Scope::Graph;
+                let column = self.capture(|writer| writer.projected(&item.expression));
+                self.scope = Scope::Table;
+                self.columns.push((column, name.to_string()));
+                self.push(&identifier(&name));
+            }
+        }
+    }
+
+    fn group_by(&mut self, ret: &ReturnStatement) {
+        let ReturnItemList::Items { items } = &ret.items else {
+            return;
+        };
+        if let Some(group_by) = &ret.group_by {
+            let keys: Vec<&Expression> = group_by
+                .elements
+                .iter()
+                .filter_map(|element| match element {
+                    GroupingElement::Expression(expression) => Some(expression),
+                    GroupingElement::EmptyGroupingSet => None,
+                })
+                .collect();
+            self.list(&keys, |writer, key| writer.sort_key(key));
+            return;
+        }
+        // GQL groups implicitly by the items that are not aggregated.
+        let aggregated: Vec<bool> = items
+            .iter()
+            .map(|item| ExpressionInfo::analyze(&item.expression).contains_aggregate)
+            .collect();
+        if !aggregated.contains(&true) {
+            return;
+        }
+        let keys: Vec<SmolStr> = self
+            .outputs
+            .iter()
+            .zip(&aggregated)
+            .filter(|(_, aggregated)| !**aggregated)
+            .map(|(name, _)| name.clone())
+            .collect();
+        self.list(&keys, |writer, name| writer.push(&identifier(name)));
+    }
+
+    fn order_by_and_page(&mut self, page: &OrderByAndPageStatement) {
+        if let Some(order_by) = &page.order_by {
+            self.push("\nORDER BY ");
+            self.list(&order_by.sort_specifications, |writer, sort| {
+                writer.sort_key(&sort.key);
+                if sort.ordering == Some(OrderingSpecification::Descending) {
+                    writer.push(" DESC");
+                }
+                match sort.null_ordering {
+                    Some(NullOrdering::NullsFirst) => writer.push(" NULLS FIRST"),
+                    Some(NullOrdering::NullsLast) => writer.push(" NULLS LAST"),
+                    None => {}
+                }
+            });
+        }
+        if let Some(offset) = &page.offset {
+            self.push("\nOFFSET ");
+            self.expression(&offset.count, PRIMARY);
+            self.push(" ROWS");
+        }
+        if let Some(limit) = &page.limit {
+            self.push("\nFETCH FIRST ");
+            self.expression(&limit.count, PRIMARY);
+            self.push(" ROWS ONLY");
+        }
+    }
+
+    /// Writes a sort or grouping key, which may name a result column.
+    fn sort_key(&mut self, key: &Expression) {
+        match key {
+            Expression::VariableReference(name, _) if self.outputs.contains(name) => {
+                self.push(&identifier(name))
+            }
+            _ => self.expression(key, 0),
+        }
+    }
+
+    /// Writes a graph table column value; whole elements are not values.
+    fn projected(&mut self, expression: &Expression) {
+        match expression {
+            Expression::VariableReference(name, span) if self.elements.contains(name) => {
+                self.diagnostics.push(
+                    unsupported("Graph element value", TARGET, span.clone())
+                        .with_help("return the element's properties instead"),
+                );
+            }
+            _ => self.expression(expression, 0),
+        }
+    }
+
+    /// Returns the graph table column holding `expression`, adding it if
+    /// needed.
+    fn column(&mut self, expression: &Expression, name: &str) -> String {
+        let scope = std::mem::replace(&mut self.scope, Scope::Graph);
+        let text = self.capture(|writer| writer.expression(expression, 0));
+        self.scope = scope;
+        if let Some((_, name)) = self.columns.iter().find(|(column, _)| *column == text) {
+            return name.clone();
+        }
+        let name = self.fresh_name(name, &self.outputs.clone()).to_string();
+        self.columns.push((text, name.clone()));
+        name
+    }
+
+    /// Returns `name`, suffixed if a column or result column already has it.
+    fn fresh_name(&self, name: &str, taken: &[SmolStr]) -> SmolStr {
+        let used = |candidate: &str| {
+            taken.iter().any(|name| name == candidate)
+                || self.columns.iter().any(|(_, name)| name == candidate)
+        };
+        if !used(name) {
+            return SmolStr::new(name);
+        }
+        (2..)
+            .map(|suffix| format!("{name}_{suffix}"))
+            .find(|candidate| !used(candidate))
+            .map(SmolStr::new)
+            .unwrap_or_default()
+    }
+
+    // ========================================================================
+    // Patterns
+    // ========================================================================
+
+    fn path_pattern(&mut self, pattern: &PathPattern) {
+        if let Some(declaration) = &pattern.variable_declaration {
+            self.unsupported("Path variable", &declaration.span);
+        }
+        if let Some(prefix) = &pattern.prefix {
+            self.path_prefix(prefix);
+            self.push(" ");
+        }
+        self.path_expression(&pattern.expression);
+    }
+
+    fn path_prefix(&mut self, prefix: &PathPatternPrefix) {
+        let (search, mode) = match prefix {
+            PathPatternPrefix::PathMode(mode) => (None, Some(*mode)),
+            PathPatternPrefix::PathSearch(PathSearch::All(all)) => (Some("ALL"), all.mode),
+            PathPatternPrefix::PathSearch(PathSearch::Any(any)) => (Some("ANY"), any.mode),
+            PathPatternPrefix::PathSearch(PathSearch::Shortest(shortest)) => match shortest {
+                ShortestPathSearch::AllShortest { mode, .. } => (Some("ALL SHORTEST"), *mode),
+                ShortestPathSearch::AnyShortest { mode, .. } => (Some("ANY SHORTEST"), *mode),
+                ShortestPathSearch::CountedShortest { count, mode, .. } => {
+                    self.push("SHORTEST ");
+                    self.expression(count, PRIMARY);
+                    (None, *mode)
+                }
+                ShortestPathSearch::CountedShortestGroups { count, mode, .. } => {
+                    self.push("SHORTEST ");
+                    self.expression(count, PRIMARY);
+                    self.push(" GROUPS");
+                    (None, *mode)
+                }
+            },
+        };
+        let mode = mode.map(|mode| match mode {
+            PathMode::Walk => "WALK",
+            PathMode::Trail => "TRAIL",
+            PathMode::Simple => "SIMPLE",
+            PathMode::Acyclic => "ACYCLIC",
+        });
+        let counted = search.is_none() && !matches!(prefix, PathPatternPrefix::PathMode(_));
+        let words: Vec<&str> = search.into_iter().chain(mode).collect();
+        if counted && !words.is_empty() {
+            self.push(" ");
+        }
+        self.push(&words.join(" "));
+    }
+
+    fn path_expression(&mut self, expression: &PathPatternExpression) {
+        match expression {
+            PathPatternExpression::Term(term) => {
+                for factor in &term.factors {
+                    self.path_factor(factor);
+                }
+            }
+            PathPatternExpression::Union { left, right, .. } => {
+                self.path_expression(left);
+                self.push(" | ");
+                self.path_expression(right);
+            }
+            PathPatternExpression::Alternation { alternatives, .. } => {
+                for (index, alternative) in alternatives.iter().enumerate() {
+                    if index > 0 {
+                        self.push(" |+| ");
+                    }
+                    self.path_expression(alternative);
+                }
+            }
+        }
+    }
+
+    fn path_factor(&mut self, factor: &PathFactor) {
+        match &factor.primary {
+            PathPrimary::ElementPattern(element) => match &**element {
+                ElementPattern::Node(node) => {
+                    self.push("(");
+                    self.element_filler(
+                        node.variable.as_ref(),
+                        node.label_expression.as_ref(),
+                        node.properties.as_ref(),
+                        node.where_clause.as_ref(),
+                        &node.span,
+                    );
+                    self.push(")");
+                }
+                ElementPattern::Edge(EdgePattern::Full(edge)) => {
+                    let Some((left, right)) = edge_delimiters(edge.direction) else {
+                        self.unsupported("Undirected edge pattern", &edge.span);
+                        return;
+                    };
+                    self.push(left);
+                    let filler = &edge.filler;
+                    self.element_filler(
+                        filler.variable.as_ref(),
+                        filler.label_expression.as_ref(),
+                        filler.properties.as_ref(),
+                        filler.where_clause.as_ref(),
+                        &filler.span,
+                    );
+                    self.push(right);
+                }
+                ElementPattern::Edge(EdgePattern::Abbreviated(edge)) => match edge {
+                    AbbreviatedEdgePattern::LeftArrow { .. } => self.push("<-"),
+                    AbbreviatedEdgePattern::RightArrow { .. } => self.push("->"),
+                    AbbreviatedEdgePattern::AnyDirection { .. } => self.push("-"),
+                    AbbreviatedEdgePattern::Undirected { span } => {
+                        self.unsupported("Undirected edge pattern", span)
+                    }
+                },
+            },
+            PathPrimary::ParenthesizedExpression(expression) => {
+                self.push("(");
+                self.path_expression(expression);
+                self.push(")");
+            }
+            PathPrimary::SimplifiedExpression(_) => {
+                self.unsupported("Simplified path pattern", &factor.span)
+            }
+        }
+        if let Some(quantifier) = &factor.quantifier {
+            self.quantifier(quantifier);
+        }
+    }
+
+    /// Writes the inside of a node or edge pattern. Property specifications
+    /// become equality conditions in the element's WHERE clause.
+    fn element_filler(
+        &mut self,
+        variable: Option<&ElementVariableDeclaration>,
+        label: Option<&LabelExpression>,
+        properties: Option<&ElementPropertySpecification>,
+        predicate: Option<&ElementPatternPredicate>,
+        span: &Span,
+    ) {
+        let mut separator = "";
+        if let Some(variable) = variable {
+            self.push(&identifier(&variable.variable));
+            separator = " ";
+        }
+        if let Some(label) = label {
+            self.push(separator);
+            self.push("IS ");
+            self.label_expression(label);
+            separator = " ";
+        }
+        let properties = properties.map_or(&[][..], |spec| &spec.properties[..]);
+        if properties.is_empty() && predicate.is_none() {
+            return;
+        }
+        self.push(separator);
+        self.push("WHERE ");
+        if !properties.is_empty() {
+            let Some(variable) = variable else {
+                self.diagnostics.push(
+                    unsupported(
+                        "Property specification on an anonymous element",
+                        TARGET,
+                        span.clone(),
+                    )
+                    .with_help("bind the element to a variable"),
+                );
+                return;
+            };
+            for (index, pair) in properties.iter().enumerate() {
+                if index > 0 {
+                    self.push(" AND ");
+                }
+                self.push(&identifier(&variable.variable));
+                self.push(".");
+                self.push(&identifier(&pair.key));
+                self.push(" = ");
+                self.expression(&pair.value, COMPARISON + 1);
+            }
+        }
+        if let Some(predicate) = predicate {
+            let min = if properties.is_empty() { 0 } else { AND };
+            if !properties.is_empty() {
+                self.push(" AND ");
+            }
+            self.expression(&predicate.condition, min);
+        }
+    }
+
+    fn quantifier(&mut self, quantifier: &GraphPatternQuantifier) {
+        match quantifier {
+            GraphPatternQuantifier::QuestionMark { .. } => self.push("?"),
+            GraphPatternQuantifier::Fixed { count, .. } => self.push(&format!("{{{count}}}")),
+            GraphPatternQuantifier::General {
+                min,
+                max: Some(max),
+                ..
+            } => self.push(&format!("{{{},{max}}}", min.unwrap_or(0))),
+            GraphPatternQuantifier::Star { span }
+            | GraphPatternQuantifier::Plus { span }
+            | GraphPatternQuantifier::General { span, .. } => self.diagnostics.push(
+                unsupported("Unbounded quantifier", TARGET, span.clone())
+                    .with_help("give the quantifier an upper bound, such as {1,10}"),
+            ),
+        }
+    }
+
+    fn label_expression(&mut self, label: &LabelExpression) {
+        fn strength(label: &LabelExpression) -> u8 {
+            match label {
+                LabelExpression::Disjunction { .. } => 1,
+                LabelExpression::Conjunction { .. } => 2,
+                LabelExpression::Negation { .. } => 3,
+                _ => 4,
+            }
+        }
+        let operand = |writer: &mut Self, label: &LabelExpression, min: u8| {
+            if strength(label) < min {
+                writer.push("(");
+                writer.label_expression(label);
+                writer.push(")");
+            } else {
+                writer.label_expression(label);
+            }
+        };
+        match label {
+            LabelExpression::Negation { operand: inner, .. } => {
+                self.push("!");
+                operand(self, inner, 3);
+            }
+            LabelExpression::Conjunction { left, right, .. } => {
+                operand(self, left, 2);
+                self.push("&");
+                operand(self, right, 3);
+            }
+            LabelExpression::Disjunction { left, right, .. } => {
+                operand(self, left, 1);
+                self.push("|");
+                operand(self, right, 2);
+            }
+            LabelExpression::LabelName { name, .. } => self.push(&identifier(name)),
+            LabelExpression::Wildcard { .. } => self.push("%"),
+            LabelExpression::Parenthesized { expression, .. } => {
+                self.push("(");
+                self.label_expression(expression);
+                self.push(")");
+            }
+        }
+    }
+
+    // ========================================================================
+    // Expressions
+    // ========================================================================
+
+    /// Writes `expression`, parenthesized if it binds looser than `min`.
+    fn expression(&mut self, expression: &Expression, min: u8) {
+        let own = match expression {
+            // Written as MOD(a, b).
+            Expression::Binary(BinaryOperator::Modulo, ..) => PRIMARY,
+            _ => precedence(expression),
+        };
+        if own < min {
+            self.push("(");
+            self.expression(expression, 0);
+            self.push(")");
+            return;
+        }
+        match expression {
+            Expression::Literal(literal, span) => self.literal(literal, span),
+            Expression::Unary(operator, operand, _) => {
+                self.push(match operator {
+                    UnaryOperator::Plus => "+",
+                    UnaryOperator::Minus => "-",
+                    UnaryOperator::Not => "NOT ",
+                });
+                // Keep `- -x` from turning into the comment `--x`.
+                let min = if matches!(**operand, Expression::Unary(..)) && own == UNARY {
+                    PRIMARY
+                } else {
+                    own
+                };
+                self.expression(operand, min);
+            }
+            Expression::Binary(BinaryOperator::Modulo, left, right, _) => {
+                self.push("MOD(");
+                self.expression(left, 0);
+                self.push(", ");
+                self.expression(right, 0);
+                self.push(")");
+            }
+            Expression::Binary(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    BinaryOperator::Add => " + ",
+                    BinaryOperator::Subtract => " - ",
+                    BinaryOperator::Multiply => " * ",
+                    BinaryOperator::Divide => " / ",
+                    BinaryOperator::Concatenate => " || ",
+                    BinaryOperator::Modulo => unreachable!("written as MOD"),
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Comparison(operator, left, right, _) => {
+                self.expression(left, own + 1);
+                self.push(match operator {
+                    ComparisonOperator::Eq => " = ",
+                    ComparisonOperator::NotEq => " <> ",
+                    ComparisonOperator::Lt => " < ",
+                    ComparisonOperator::Gt => " > ",
+                    ComparisonOperator::LtEq => " <= ",
+                    ComparisonOperator::GtEq => " >= ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Logical(LogicalOperator::Xor, _, _, span) => self.unsupported("XOR", span),
+            Expression::Logical(operator, left, right, _) => {
+                self.expression(left, own);
+                self.push(match operator {
+                    LogicalOperator::And => " AND ",
+                    _ => " OR ",
+                });
+                self.expression(right, own + 1);
+            }
+            Expression::Parenthesized(inner, _) => {
+                self.push("(");
+                self.expression(inner, 0);
+                self.push(")");
+            }
+            Expression::PropertyReference(base, property, _) => match &**base {
+                Expression::VariableReference(variable, _)
+                    if self.scope == Scope::Table && self.elements.contains(variable) =>
+                {
+                    let name = format!("{variable}_{property}");
+                    let column = self.column(expression, &name);
+                    self.push(&identifier(&column));
+                }
+                _ => {
+                    self.expression(base, POSTFIX);
+                    self.push(".");
+                    self.push(&identifier(property));
+                }
+            },
+            Expression::VariableReference(name, span) => {
+                if self.scope == Scope::Table && self.elements.contains(name) {
+                    self.diagnostics.push(
+                        unsupported("Graph element value", TARGET, span.clone())
+                            .with_help("use the element's properties instead"),
+                    );
+                } else {
+                    self.push(&identifier(name));
+                }
+            }
+            Expression::ParameterReference(name, _) => {
+                self.push(":");
+                self.push(name);
+            }
+            Expression::FunctionCall(call) => self.function_call(call),
+            Expression::Case(case) => self.case(case),
+            Expression::Cast(cast) => {
+                let Some(target) = sql_type(&cast.target_type) else {
+                    self.unsupported("Cast target type", &cast.span);
+                    return;
+                };
+                self.push("CAST(");
+                self.expression(&cast.operand, 0);
+                self.push(" AS ");
+                self.push(&target);
+                self.push(")");
+            }
+            Expression::AggregateFunction(aggregate) => self.aggregate(aggregate),
+            Expression::Predicate(predicate) => self.predicate(predicate),
+            Expression::TypeAnnotation(_, _, span) => self.unsupported("Type annotation", span),
+            Expression::ListConstructor(_, span) => self.unsupported("List value", span),
+            Expression::RecordConstructor(_, span) => self.unsupported("Record value", span),
+            Expression::PathConstructor(_, span) => self.unsupported("Path value", span),
+            Expression::Exists(exists) => self.unsupported("EXISTS", &exists.span),
+            Expression::GraphExpression(_, span) => self.unsupported("Graph expression", span),
+            Expression::BindingTableExpression(_, span) => {
+                self.unsupported("Binding table expression", span)
+            }
+            Expression::SubqueryExpression(_, span) => self.unsupported("VALUE subquery", span),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal, span: &Span) {
+        match literal {
+            Literal::Boolean(BooleanValue::True) => self.push("TRUE"),
+            Literal::Boolean(BooleanValue::False) => self.push("FALSE"),
+            Literal::Boolean(BooleanValue::Unknown) => self.push("UNKNOWN"),
+            Literal::Null => self.push("NULL"),
+            Literal::Integer(value) | Literal::Float(value) => self.push(value),
+            Literal::String(value) => self.push(&string_literal(value)),
+            Literal::ByteString(value) => {
+                self.push("X");
+                self.push(&string_literal(value));
+            }
+            Literal::Date(value) => self.push(&format!("DATE {}", string_literal(value))),
+            Literal::Time(value) => self.push(&format!("TIME {}", string_literal(value))),
+            Literal::Datetime(value) => self.push(&format!("TIMESTAMP {}", string_literal(value))),
+            Literal::Duration(_) => self.unsupported("Duration literal", span),
+            Literal::List(_) => self.unsupported("List value", span),
+            Literal::Record(_) => self.unsupported("Record value", span),
+        }
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) {
+        let arguments = &call.arguments;
+        let name = match &call.name {
+            FunctionName::CurrentDate => return self.push("CURRENT_DATE"),
+            FunctionName::CurrentTime => return self.push("CURRENT_TIME"),
+            FunctionName::CurrentTimestamp => return self.push("CURRENT_TIMESTAMP"),
+            FunctionName::Trim(TrimSpecification::Leading)
+            | FunctionName::LTrim
+            | FunctionName::Trim(TrimSpecification::Trailing)
+            | FunctionName::RTrim
+                if !arguments.is_empty() =>
+            {
+                let side = match call.name {
+                    FunctionName::Trim(TrimSpecification::Leading) | FunctionName::LTrim => {
+                        "LEADING"
+                    }
+                    _ => "TRAILING",
+                };
+                self.push("TRIM(");
+                self.push(side);
+                self.push(" ");
+                if let Some(characters) = arguments.get(1) {
+                    self.expression(characters, 0);
+                    self.push(" ");
+                }
+                self.push("FROM ");
+                self.expression(&arguments[0], 0);
+                self.push(")");
+                return;
+            }
+            FunctionName::Substring if (2..=3).contains(&arguments.len()) => {
+                self.push("SUBSTRING(");
+                self.expression(&arguments[0], 0);
+                self.push(" FROM ");
+                self.expression(&arguments[1], 0);
+                if let Some(length) = arguments.get(2) {
+                    self.push(" FOR ");
+                    self.expression(length, 0);
+                }
+                self.push(")");
+                return;
+            }
+            FunctionName::Date
+            | FunctionName::Time
+            | FunctionName::LocalTime
+            | FunctionName::ZonedTime
+            | FunctionName::Datetime
+            | FunctionName::LocalDatetime
+            | FunctionName::ZonedDatetime
+                if arguments.len() == 1 =>
+            {
+                self.push("CAST(");
+                self.expression(&arguments[0], 0);
+                self.push(match call.name {
+                    FunctionName::Date => " AS DATE)",
+                    FunctionName::Time | FunctionName::LocalTime => " AS TIME)",
+                    FunctionName::ZonedTime => " AS TIME WITH TIME ZONE)",
+                    FunctionName::Datetime | FunctionName::LocalDatetime => " AS TIMESTAMP)",
+                    _ => " AS TIMESTAMP WITH TIME ZONE)",
+                });
+                return;
+            }
+            FunctionName::Abs
+            | FunctionName::Mod
+            | FunctionName::Floor
+            | FunctionName::Ceil
+            | FunctionName::Sqrt
+            | FunctionName::Power
+            | FunctionName::Exp
+            | FunctionName::Ln
+            | FunctionName::Log
+            | FunctionName::Log10
+            | FunctionName::Sin
+            | FunctionName::Cos
+            | FunctionName::Tan
+            | FunctionName::Sinh
+            | FunctionName::Cosh
+            | FunctionName::Tanh
+            | FunctionName::Asin
+            | FunctionName::Acos
+            | FunctionName::Atan
+            | FunctionName::Upper
+            | FunctionName::Lower
+            | FunctionName::Trim(TrimSpecification::Both)
+            | FunctionName::Normalize
+            | FunctionName::CharLength
+            | FunctionName::Cardinality
+            | FunctionName::Coalesce
+            | FunctionName::NullIf => call.name.gql_name().to_string(),
+            FunctionName::ByteLength => "OCTET_LENGTH".to_string(),
+            FunctionName::Custom(name) => identifier(name),
+            name => {
+                let construct = format!("{} function", name.gql_name());
+                self.unsupported(&construct, &call.span);
+                return;
+            }
+        };
+        self.push(&name);
+        self.push("(");
+        self.list(arguments, |writer, argument| writer.expression(argument, 0));
+        self.push(")");
+    }
+
+    fn case(&mut self, case: &CaseExpression) {
+        self.push("CASE");
+        let else_clause = match case {
+            CaseExpression::Simple(simple) => {
+                self.push(" ");
+                self.expression(&simple.operand, 0);
+                for when in &simple.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.when_value, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &simple.else_clause
+            }
+            CaseExpression::Searched(searched) => {
+                for when in &searched.when_clauses {
+                    self.push(" WHEN ");
+                    self.expression(&when.condition, 0);
+                    self.push(" THEN ");
+                    self.expression(&when.then_result, 0);
+                }
+                &searched.else_clause
+            }
+        };
+        if let Some(else_result) = else_clause {
+            self.push(" ELSE ");
+            self.expression(else_result, 0);
+        }
+        self.push(" END");
+    }
+
+    fn aggregate(&mut self, aggregate: &AggregateFunction) {
+        match aggregate {
+            AggregateFunction::CountStar { .. } => self.push("COUNT(*)"),
+            AggregateFunction::GeneralSetFunction(function) => {
+                // Without OPTIONAL MATCH an element variable is never null,
+                // so counting it counts rows.
+                if function.function_type == GeneralSetFunctionType::Count
+                    && function.quantifier != Some(SetQuantifier::Distinct)
+                    && matches!(
+                        &*function.expression,
+                        Expression::VariableReference(name, _) if self.elements.contains(name)
+                    )
+                {
+                    self.push("COUNT(*)");
+                    return;
+                }
+                self.push(match function.function_type {
+                    GeneralSetFunctionType::Avg => "AVG",
+                    GeneralSetFunctionType::Count => "COUNT",
+                    GeneralSetFunctionType::Max => "MAX",
+                    GeneralSetFunctionType::Min => "MIN",
+                    GeneralSetFunctionType::Sum => "SUM",
+                    GeneralSetFunctionType::CollectList => "ARRAY_AGG",
+                    GeneralSetFunctionType::StddevSamp => "STDDEV_SAMP",
+                    GeneralSetFunctionType::StddevPop => "STDDEV_POP",
+                });
+                self.push("(");
+                self.set_quantifier(function.quantifier);
+                self.expression(&function.expression, 0);
+                self.push(")");
+            }
+            AggregateFunction::BinarySetFunction(function) => {
+                self.push(match function.function_type {
+                    BinarySetFunctionType::PercentileCont => "PERCENTILE_CONT(",
+                    BinarySetFunctionType::PercentileDisc => "PERCENTILE_DISC(",
+                });
+                self.expression(&function.expression, 0);
+                self.push(") WITHIN GROUP (ORDER BY ");
+                self.expression(&function.inverse_distribution_argument, 0);
+                self.push(")");
+            }
+        }
+    }
+
+    fn set_quantifier(&mut self, quantifier: Option<SetQuantifier>) {
+        match quantifier {
+            Some(SetQuantifier::Distinct) => self.push("DISTINCT "),
+            Some(SetQuantifier::All) => self.push("ALL "),
+            None => {}
+        }
+    }
+
+    fn predicate(&mut self, predicate: &Predicate) {
+        let is = |writer: &mut Self, operand: &Expression, negated: bool| {
+            writer.expression(operand, IS);
+            writer.push(if negated { " IS NOT " } else { " IS " });
+        };
+        match predicate {
+            Predicate::IsNull(operand, negated, _) => {
+                is(self, operand, *negated);
+                self.push("NULL");
+            }
+            Predicate::IsNormalized(operand, negated, _) => {
+                is(self, operand, *negated);
+                self.push("NORMALIZED");
+            }
+            Predicate::IsTruthValue(operand, value, negated, _) => {
+                is(self, operand, *negated);
+                self.push(match value {
+                    TruthValue::True => "TRUE",
+                    TruthValue::False => "FALSE",
+                    TruthValue::Unknown => "UNKNOWN",
+                });
+            }
+            Predicate::IsSource(operand, edge, negated, _) => {
+                is(self, operand, *negated);
+                self.push("SOURCE OF ");
+                self.expression(edge, COMPARISON);
+            }
+            Predicate::IsDestination(operand, edge, negated, _) => {
+                is(self, operand, *negated);
+                self.push("DESTINATION OF ");
+                self.expression(edge, COMPARISON);
+            }
+            Predicate::AllDifferent(elements, _) => {
+                self.push("ALL_DIFFERENT(");
+                self.list(elements, |writer, element| writer.expression(element, 0));
+                self.push(")");
+            }
+            Predicate::Same(left, right, _) => {
+                self.push("SAME(");
+                self.expression(left, 0);
+                self.push(", ");
+                self.expression(right, 0);
+                self.push(")");
+            }
+            Predicate::PropertyExists(element, property, _) => {
+                self.push("PROPERTY_EXISTS(");
+                self.expression(element, 0);
+                self.push(", ");
+                self.push(&identifier(property));
+                self.push(")");
+            }
+            Predicate::IsTyped(_, _, _, span) => self.unsupported("IS TYPED", span),
+            Predicate::IsDirected(_, _, span) => self.unsupported("IS DIRECTED", span),
+            Predicate::IsLabeled(_, _, _, span) => self.unsupported("IS LABELED", span),
+        }
+    }
+}
+
+/// Opening and closing delimiters of a full edge pattern; SQL/PGQ edges
+/// are always directed.
+fn edge_delimiters(direction: EdgeDirection) -> Option<(&'static str, &'static str)> {
+    match direction {
+        EdgeDirection::PointingLeft => Some(("<-[", "]-")),
+        EdgeDirection::PointingRight => Some(("-[", "]->")),
+        EdgeDirection::AnyDirected => Some(("<-[", "]->")),
+        EdgeDirection::AnyDirection => Some(("-[", "]-")),
+        EdgeDirection::Undirected
+        | EdgeDirection::LeftOrUndirected
+        | EdgeDirection::RightOrUndirected => None,
+    }
+}
+
+/// Adds the element variables declared in `expression` to `elements`.
+fn collect_elements(expression: &PathPatternExpression, elements: &mut Vec<SmolStr>) {
+    let factors = match expression {
+        PathPatternExpression::Term(term) => &term.factors,
+        PathPatternExpression::Union { left, right, .. } => {
+            collect_elements(left, elements);
+            collect_elements(right, elements);
+            return;
+        }
+        PathPatternExpression::Alternation { alternatives, .. } => {
+            for alternative in alternatives {
+                collect_elements(alternative, elements);
+            }
+            return;
+        }
+    };
+    for factor in factors {
+        let variable = match &factor.primary {
+            PathPrimary::ElementPattern(element) => match &**element {
+                ElementPattern::Node(node) => node.variable.as_ref(),
+                ElementPattern::Edge(EdgePattern::Full(edge)) => edge.filler.variable.as_ref(),
+                ElementPattern::Edge(EdgePattern::Abbreviated(_)) => None,
+            },
+            PathPrimary::ParenthesizedExpression(inner) => {
+                collect_elements(inner, elements);
+                None
+            }
+            PathPrimary::SimplifiedExpression(_) => None,
+        };
+        if let Some(variable) = variable
+            && !elements.contains(&variable.variable)
+        {
+            elements.push(variable.variable.clone());
+        }
+    }
+}
+
+/// The SQL spelling of a GQL predefined type, if it has one.
+fn sql_type(value_type: &ValueType) -> Option<String> {
+    let ValueType::Predefined(predefined, _) = value_type else {
+        return None;
+    };
+    let sized = |name: &str, length: Option<u32>| match length {
+        Some(length) => format!("{name}({length})"),
+        None => name.to_string(),
+    };
+    let name = match predefined {
+        PredefinedType::Boolean(BooleanType::Bool | BooleanType::Boolean) => "BOOLEAN".into(),
+        PredefinedType::CharacterString(string) => match string {
+            CharacterStringType::String => "VARCHAR".into(),
+            CharacterStringType::Char(length) => sized("CHAR", *length),
+            CharacterStringType::VarChar(length) => sized("VARCHAR", *length),
+        },
+        PredefinedType::ByteString(bytes) => match bytes {
+            ByteStringType::Bytes => "VARBINARY".into(),
+            ByteStringType::Binary(length) => sized("BINARY", *length),
+            ByteStringType::VarBinary(length) => sized("VARBINARY", *length),
+        },
+        PredefinedType::Numeric(NumericType::Exact(ExactNumericType::SignedBinary(signed))) => {
+            match signed {
+                SignedBinaryExactNumericType::Int8
+                | SignedBinaryExactNumericType::Int16
+                | SignedBinaryExactNumericType::SmallInt => "SMALLINT",
+                SignedBinaryExactNumericType::Int32
+                | SignedBinaryExactNumericType::Int
+                | SignedBinaryExactNumericType::Integer => "INTEGER",
+                SignedBinaryExactNumericType::Int64 | SignedBinaryExactNumericType::BigInt => {
+                    "BIGINT"
+                }
+                _ => return None,
+            }
+            .into()
+        }
+        PredefinedType::Numeric(NumericType::Exact(ExactNumericType::Decimal(decimal))) => {
+            match (decimal.precision, decimal.scale) {
+                (Some(precision), Some(scale)) => format!("DECIMAL({precision}, {scale})"),
+                (Some(precision), None) => format!("DECIMAL({precision})"),
+                _ => "DECIMAL".into(),
+            }
+        }
+        PredefinedType::Numeric(NumericType::Approximate(approximate)) => match approximate {
+            ApproximateNumericType::Float32 | ApproximateNumericType::Real => "REAL".into(),
+            ApproximateNumericType::Float64 | ApproximateNumericType::DoublePrecision => {
+                "DOUBLE PRECISION".into()
+            }
+            ApproximateNumericType::Float(precision) => sized("FLOAT", *precision),
+            _ => return None,
+        },
+        PredefinedType::Temporal(TemporalType::Instant(instant)) => match instant {
+            TemporalInstantType::Date => "DATE",
+            TemporalInstantType::LocalTime => "TIME",
+            TemporalInstantType::ZonedTime => "TIME WITH TIME ZONE",
+            TemporalInstantType::LocalDatetime => "TIMESTAMP",
+            TemporalInstantType::ZonedDatetime => "TIMESTAMP WITH TIME ZONE",
+        }
+        .into(),
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Statement;
+    use crate::parse;
+
+    fn render(source: &str) -> EmitResult {
+        let program = parse(source).ast.expect("query should parse");
+        let Statement::Query(statement) = &program.statements[0] else {
+            panic!("expected a query statement");
+        };
+        query(&statement.query, "g")
+    }
+
+    #[test]
+    fn test_pattern_elements_render_as_pgq() {
+        let sql = render(
+            "MATCH (a:Person {name: 'Ann'})-[e:KNOWS|LIKES WHERE e.since > 2000]->(b) \
+             RETURN b.name",
+        )
+        .unwrap();
+        assert!(
+            sql.contains(
+                "MATCH (a IS Person WHERE a.name = 'Ann')\
+                 -[e IS KNOWS|LIKES WHERE e.since > 2000]->(b)"
+            ),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_sql_literals_and_operators() {
+        let sql = render("MATCH (a) WHERE a.x % 2 = 0 AND a.s = 'it\\'s' RETURN a.x").unwrap();
+        assert!(
+            sql.contains("WHERE MOD(a.x, 2) = 0 AND a.s = 'it''s'"),
+            "{sql}"
+        );
+        assert_eq!(identifier("select"), "\"select\"");
+        assert_eq!(identifier("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_whole_elements_are_not_columns() {
+        let diagnostics = render("MATCH (a) RETURN a").unwrap_err();
+        assert_eq!(
+            diagnostics[0].message,
+            "Graph element value cannot be rendered as SQL/PGQ"
+        );
+    }
+}
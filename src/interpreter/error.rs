@@ -0,0 +1,103 @@
+//! Errors raised while executing a program.
+
+use std::fmt;
+
+use crate::analysis::const_eval::{ConstEvalError, ConstEvalErrorKind};
+use crate::ast::Span;
+use crate::diag::Diag;
+
+/// Why execution failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeErrorKind {
+    /// An expression could not be evaluated (overflow, division by zero,
+    /// type mismatch, missing parameter, ...).
+    Evaluation(ConstEvalErrorKind),
+    /// A variable was referenced before being bound.
+    UnboundVariable,
+    /// A path pattern matches infinitely many paths.
+    InfinitePaths,
+    /// A modification would leave the graph inconsistent, such as deleting a
+    /// node that still has edges without DETACH.
+    ConstraintViolation,
+    /// A graph element was used after being deleted.
+    DeletedElement,
+    /// The construct is valid GQL but the interpreter does not execute it.
+    Unsupported,
+}
+
+/// Error produced by the interpreter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub message: String,
+    /// Span of the construct that failed.
+    pub span: Span,
+}
+
+impl RuntimeError {
+    pub(crate) fn new(kind: RuntimeErrorKind, message: impl Into<String>, span: &Span) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            span: span.clone(),
+        }
+    }
+
+    pub(crate) fn mismatch(message: impl Into<String>, span: &Span) -> Self {
+        Self::new(
+            RuntimeErrorKind::Evaluation(ConstEvalErrorKind::TypeMismatch),
+            message,
+            span,
+        )
+    }
+
+    pub(crate) fn unsupported(what: &str, span: &Span) -> Self {
+        Self::new(
+            RuntimeErrorKind::Unsupported,
+            format!("{what} is not supported by the interpreter"),
+            span,
+        )
+    }
+
+    /// Converts the error into an error diagnostic.
+    pub fn to_diag(&self) -> Diag {
+        Diag::error(self.message.clone()).with_primary_label(self.span.clone(), "failed here")
+    }
+}
+
+impl From<ConstEvalError> for RuntimeError {
+    fn from(error: ConstEvalError) -> Self {
+        // Everything is known at run time, so the only remaining "not
+        // constant" failures are operations the constant evaluator lacks.
+        let kind = match error.kind {
+            ConstEvalErrorKind::NotConstant => RuntimeErrorKind::Unsupported,
+            kind => RuntimeErrorKind::Evaluation(kind),
+        };
+        Self {
+            kind,
+            message: error.message,
+            span: error.span,
+        }
+    }
+}
+
+impl From<Box<Diag>> for RuntimeError {
+    /// Wraps a compilation diagnostic, such as a path pattern the automaton
+    /// builder rejects.
+    fn from(diag: Box<Diag>) -> Self {
+        let span = diag.labels.first().map_or(0..0, |label| label.span.clone());
+        Self {
+            kind: RuntimeErrorKind::Unsupported,
+            message: diag.message,
+            span,
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RuntimeError {}
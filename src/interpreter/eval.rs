@@ -0,0 +1,979 @@
+//! Expression evaluation against bound rows.
+//!
+//! Scalar operators and functions are delegated to the constant evaluator;
+//! this module adds variables, parameters, properties, graph element
+//! predicates and aggregates. Aggregates are vertical (over the rows of a
+//! group) unless their argument refers to a group variable, in which case
+//! they aggregate horizontally over the elements the group variable bound
+//! in a single row.
+
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use super::error::{RuntimeError, RuntimeErrorKind};
+use super::graph::{Edge, EdgeId, Node, NodeId, Path, PropertyGraph};
+use super::value::{Value, not_distinct, sort_order};
+use crate::analysis::ExpressionInfo;
+use crate::analysis::const_eval::{self, ConstEvalErrorKind, ConstEvaluator, Value as Constant};
+use crate::ast::expression::{
+    AggregateFunction, BinaryOperator, BinarySetFunctionType, CaseExpression, CastExpression,
+    ComparisonOperator, FunctionCall, FunctionName, GeneralSetFunctionType, Literal,
+    LogicalOperator, Predicate, TruthValue,
+};
+use crate::ast::query::SetQuantifier;
+use crate::ast::{Expression, Span};
+use crate::emit::gql;
+use crate::ir::type_table::Type;
+use crate::pattern::automaton::label_expression_matches;
+
+type EvalResult = Result<Value, RuntimeError>;
+
+/// Variable bindings of one row of a working table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Row {
+    bindings: Vec<(SmolStr, Value)>,
+    /// Bindings that are group variables of a quantified path pattern.
+    groups: Vec<SmolStr>,
+}
+
+impl Row {
+    pub(crate) fn get(&self, name: &str) -> Option<&Value> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| bound == name)
+            .map(|(_, value)| value)
+    }
+
+    pub(crate) fn bindings(&self) -> &[(SmolStr, Value)] {
+        &self.bindings
+    }
+
+    /// Binds a singleton variable, replacing any previous binding.
+    pub(crate) fn bind(&mut self, name: SmolStr, value: Value) {
+        self.groups.retain(|group| *group != name);
+        match self.bindings.iter_mut().find(|(bound, _)| *bound == name) {
+            Some((_, bound)) => *bound = value,
+            None => self.bindings.push((name, value)),
+        }
+    }
+
+    /// Binds a group variable to the elements it matched.
+    pub(crate) fn bind_group(&mut self, name: SmolStr, elements: Vec<Value>) {
+        self.bind(name.clone(), Value::List(elements));
+        self.groups.push(name);
+    }
+
+    pub(crate) fn is_group(&self, name: &str) -> bool {
+        self.groups.iter().any(|group| group == name)
+    }
+
+    /// Returns a copy in which each group variable is bound to the element
+    /// it matched last. This is how element predicates inside a quantifier
+    /// see the iteration being matched.
+    pub(crate) fn current_iteration(&self) -> Row {
+        let mut row = Row {
+            bindings: self.bindings.clone(),
+            groups: Vec::new(),
+        };
+        for (name, value) in &mut row.bindings {
+            if self.is_group(name)
+                && let Value::List(elements) = value
+            {
+                *value = elements.last().cloned().unwrap_or(Value::Null);
+            }
+        }
+        row
+    }
+}
+
+/// Names the column produced by `expression`: the alias, else the variable
+/// it references, else its GQL text.
+pub(crate) fn column_name(expression: &Expression, alias: Option<&SmolStr>) -> SmolStr {
+    match (alias, expression) {
+        (Some(alias), _) => alias.clone(),
+        (None, Expression::VariableReference(name, _)) => name.clone(),
+        (None, expression) => gql::expression(expression)
+            .map_or_else(|_| SmolStr::new_static("?column?"), SmolStr::from),
+    }
+}
+
+/// What an expression is evaluated against.
+#[derive(Clone, Copy)]
+struct Scope<'r> {
+    row: &'r Row,
+    /// Rows of the current group, if aggregates are allowed.
+    group: Option<&'r [Row]>,
+}
+
+/// Evaluates expressions over a graph.
+pub(crate) struct Evaluator<'a> {
+    graph: &'a PropertyGraph,
+    parameters: &'a HashMap<SmolStr, Value>,
+    constants: ConstEvaluator<'static>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub(crate) fn new(graph: &'a PropertyGraph, parameters: &'a HashMap<SmolStr, Value>) -> Self {
+        Self {
+            graph,
+            parameters,
+            constants: ConstEvaluator::new(),
+        }
+    }
+
+    pub(crate) fn graph(&self) -> &'a PropertyGraph {
+        self.graph
+    }
+
+    /// Evaluates an expression over one row.
+    pub(crate) fn evaluate(&self, expr: &Expression, row: &Row) -> EvalResult {
+        self.eval(expr, Scope { row, group: None })
+    }
+
+    /// Evaluates an expression whose aggregates range over `group`. Other
+    /// references are resolved in `row`.
+    pub(crate) fn evaluate_grouped(
+        &self,
+        expr: &Expression,
+        row: &Row,
+        group: &[Row],
+    ) -> EvalResult {
+        self.eval(
+            expr,
+            Scope {
+                row,
+                group: Some(group),
+            },
+        )
+    }
+
+    /// Evaluates a search condition; UNKNOWN counts as false.
+    pub(crate) fn condition(&self, expr: &Expression, row: &Row) -> Result<bool, RuntimeError> {
+        let value = self.evaluate(expr, row)?;
+        Ok(truth(&value, &expr.span())? == Some(true))
+    }
+
+    /// Evaluates an OFFSET, LIMIT or SHORTEST count.
+    pub(crate) fn count(&self, expr: &Expression, row: &Row) -> Result<usize, RuntimeError> {
+        match self.evaluate(expr, row)? {
+            Value::Integer(count) if count >= 0 => Ok(count as usize),
+            other => Err(RuntimeError::new(
+                RuntimeErrorKind::Evaluation(ConstEvalErrorKind::InvalidArgument),
+                format!(
+                    "expected a non-negative integer, found {}",
+                    describe(&other)
+                ),
+                &expr.span(),
+            )),
+        }
+    }
+
+    pub(crate) fn node(&self, id: NodeId, span: &Span) -> Result<&'a Node, RuntimeError> {
+        self.graph.node(id).ok_or_else(|| deleted(span))
+    }
+
+    pub(crate) fn edge(&self, id: EdgeId, span: &Span) -> Result<&'a Edge, RuntimeError> {
+        self.graph.edge(id).ok_or_else(|| deleted(span))
+    }
+
+    fn eval(&self, expr: &Expression, scope: Scope<'_>) -> EvalResult {
+        match expr {
+            Expression::Literal(Literal::List(items), _)
+            | Expression::ListConstructor(items, _) => {
+                Ok(Value::List(self.eval_all(items, scope)?))
+            }
+            Expression::Literal(Literal::Record(fields), _)
+            | Expression::RecordConstructor(fields, _) => Ok(Value::Record(
+                fields
+                    .iter()
+                    .map(|field| Ok((field.name.clone(), self.eval(&field.value, scope)?)))
+                    .collect::<Result<_, RuntimeError>>()?,
+            )),
+            Expression::Literal(..) => Ok(self.constants.evaluate(expr)?.into()),
+            Expression::Parenthesized(inner, _) | Expression::TypeAnnotation(inner, _, _) => {
+                self.eval(inner, scope)
+            }
+            Expression::VariableReference(name, span) => {
+                scope.row.get(name).cloned().ok_or_else(|| {
+                    RuntimeError::new(
+                        RuntimeErrorKind::UnboundVariable,
+                        format!("variable '{name}' is not bound"),
+                        span,
+                    )
+                })
+            }
+            Expression::ParameterReference(name, span) => {
+                self.parameters.get(name).cloned().ok_or_else(|| {
+                    RuntimeError::new(
+                        RuntimeErrorKind::Evaluation(ConstEvalErrorKind::UnboundParameter),
+                        format!("no value bound for parameter ${name}"),
+                        span,
+                    )
+                })
+            }
+            Expression::PropertyReference(object, name, span) => {
+                let object = self.eval(object, scope)?;
+                self.property(&object, name, span)
+            }
+            Expression::Unary(op, operand, span) => {
+                let operand = constant(self.eval(operand, scope)?, span)?;
+                Ok(const_eval::unary(*op, operand, span)?.into())
+            }
+            Expression::Binary(op, left, right, span) => {
+                let left = self.eval(left, scope)?;
+                let right = self.eval(right, scope)?;
+                match (op, left, right) {
+                    // Lists of graph elements have no constant form.
+                    (BinaryOperator::Concatenate, Value::List(mut a), Value::List(b)) => {
+                        a.extend(b);
+                        Ok(Value::List(a))
+                    }
+                    (_, left, right) => Ok(const_eval::binary(
+                        *op,
+                        constant(left, span)?,
+                        constant(right, span)?,
+                        span,
+                    )?
+                    .into()),
+                }
+            }
+            Expression::Comparison(op, left, right, span) => {
+                let left = self.eval(left, scope)?;
+                let right = self.eval(right, scope)?;
+                compare(*op, &left, &right, span)
+            }
+            Expression::Logical(op, left, right, span) => {
+                self.logical(*op, left, right, span, scope)
+            }
+            Expression::FunctionCall(call) => self.function(call, scope),
+            Expression::Case(case) => self.case(case, scope),
+            Expression::Cast(cast) => self.cast(cast, scope),
+            Expression::AggregateFunction(aggregate) => self.aggregate(aggregate, scope),
+            Expression::PathConstructor(items, span) => {
+                let items = self.eval_all(items, scope)?;
+                self.path(items, span)
+            }
+            Expression::Predicate(predicate) => self.predicate(predicate, scope),
+            Expression::Exists(exists) => Err(RuntimeError::unsupported("EXISTS", &exists.span)),
+            Expression::GraphExpression(_, span) => {
+                Err(RuntimeError::unsupported("a graph expression", span))
+            }
+            Expression::BindingTableExpression(_, span) => Err(RuntimeError::unsupported(
+                "a binding table expression",
+                span,
+            )),
+            Expression::SubqueryExpression(_, span) => {
+                Err(RuntimeError::unsupported("a value subquery", span))
+            }
+        }
+    }
+
+    fn eval_all(&self, items: &[Expression], scope: Scope<'_>) -> Result<Vec<Value>, RuntimeError> {
+        items.iter().map(|item| self.eval(item, scope)).collect()
+    }
+
+    fn property(&self, object: &Value, name: &SmolStr, span: &Span) -> EvalResult {
+        let value = match object {
+            Value::Null => None,
+            Value::Node(id) => self.node(*id, span)?.properties.get(name),
+            Value::Edge(id) => self.edge(*id, span)?.properties.get(name),
+            Value::Record(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            other => {
+                return Err(RuntimeError::mismatch(
+                    format!("cannot read property '{name}' of {}", describe(other)),
+                    span,
+                ));
+            }
+        };
+        Ok(value.cloned().unwrap_or(Value::Null))
+    }
+
+    fn logical(
+        &self,
+        op: LogicalOperator,
+        left: &Expression,
+        right: &Expression,
+        span: &Span,
+        scope: Scope<'_>,
+    ) -> EvalResult {
+        let left = truth(&self.eval(left, scope)?, span)?;
+        match (op, left) {
+            (LogicalOperator::And, Some(false)) => return Ok(Value::Boolean(false)),
+            (LogicalOperator::Or, Some(true)) => return Ok(Value::Boolean(true)),
+            _ => {}
+        }
+        let right = truth(&self.eval(right, scope)?, span)?;
+        let result = match op {
+            LogicalOperator::And => match (left, right) {
+                (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            LogicalOperator::Or => match (left, right) {
+                (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            LogicalOperator::Xor => match (left, right) {
+                (Some(a), Some(b)) => Some(a != b),
+                _ => None,
+            },
+        };
+        Ok(from_truth(result))
+    }
+
+    fn function(&self, call: &FunctionCall, scope: Scope<'_>) -> EvalResult {
+        let span = &call.span;
+        match &call.name {
+            FunctionName::Coalesce => {
+                for argument in &call.arguments {
+                    let value = self.eval(argument, scope)?;
+                    if !value.is_null() {
+                        return Ok(value);
+                    }
+                }
+                return Ok(Value::Null);
+            }
+            FunctionName::CurrentDate
+            | FunctionName::CurrentTime
+            | FunctionName::CurrentTimestamp => {
+                return Err(RuntimeError::unsupported("reading the session clock", span));
+            }
+            FunctionName::Custom(name) => {
+                return Err(RuntimeError::unsupported(&format!("function {name}"), span));
+            }
+            _ => {}
+        }
+
+        let arguments = self.eval_all(&call.arguments, scope)?;
+        if call.name == FunctionName::NullIf {
+            let [left, right] =
+                <[Value; 2]>::try_from(arguments).map_err(|_| arity_error(&call.name, span))?;
+            let equal = compare(ComparisonOperator::Eq, &left, &right, span)?;
+            return Ok(if equal == Value::Boolean(true) {
+                Value::Null
+            } else {
+                left
+            });
+        }
+        // Temporal constructors without arguments read the session clock.
+        if arguments.is_empty()
+            && matches!(
+                call.name,
+                FunctionName::Date
+                    | FunctionName::Time
+                    | FunctionName::Datetime
+                    | FunctionName::ZonedTime
+                    | FunctionName::ZonedDatetime
+                    | FunctionName::LocalTime
+                    | FunctionName::LocalDatetime
+            )
+        {
+            return Err(RuntimeError::unsupported("reading the session clock", span));
+        }
+        if arguments.iter().any(Value::is_null) {
+            return Ok(Value::Null);
+        }
+
+        match (&call.name, arguments.as_slice()) {
+            (FunctionName::ElementId, [Value::Node(id)]) => {
+                self.node(*id, span)?;
+                Ok(Value::String(format!("n{}", id.index()).into()))
+            }
+            (FunctionName::ElementId, [Value::Edge(id)]) => {
+                self.edge(*id, span)?;
+                Ok(Value::String(format!("e{}", id.index()).into()))
+            }
+            (FunctionName::PathLength, [Value::Path(path)]) => {
+                Ok(Value::Integer(path.len() as i64))
+            }
+            (FunctionName::Elements, [Value::Path(path)]) => {
+                let mut elements = vec![Value::Node(path.nodes()[0])];
+                for (edge, node) in path.edges().iter().zip(&path.nodes()[1..]) {
+                    elements.push(Value::Edge(*edge));
+                    elements.push(Value::Node(*node));
+                }
+                Ok(Value::List(elements))
+            }
+            (FunctionName::Size | FunctionName::Cardinality, [Value::List(items)]) => {
+                Ok(Value::Integer(items.len() as i64))
+            }
+            (
+                FunctionName::ElementId | FunctionName::PathLength | FunctionName::Elements,
+                [other],
+            ) => Err(RuntimeError::mismatch(
+                format!(
+                    "{} cannot be applied to {}",
+                    call.name.gql_name(),
+                    describe(other)
+                ),
+                span,
+            )),
+            (FunctionName::ElementId | FunctionName::PathLength | FunctionName::Elements, _) => {
+                Err(arity_error(&call.name, span))
+            }
+            (FunctionName::DurationBetween, _) => {
+                Err(RuntimeError::unsupported("DURATION_BETWEEN", span))
+            }
+            (name, _) => {
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| constant(argument, span))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(const_eval::call_function(name, &arguments, span)?.into())
+            }
+        }
+    }
+
+    fn case(&self, case: &CaseExpression, scope: Scope<'_>) -> EvalResult {
+        match case {
+            CaseExpression::Simple(simple) => {
+                let operand = self.eval(&simple.operand, scope)?;
+                for clause in &simple.when_clauses {
+                    let candidate = self.eval(&clause.when_value, scope)?;
+                    let equal =
+                        compare(ComparisonOperator::Eq, &operand, &candidate, &clause.span)?;
+                    if equal == Value::Boolean(true) {
+                        return self.eval(&clause.then_result, scope);
+                    }
+                }
+                match &simple.else_clause {
+                    Some(otherwise) => self.eval(otherwise, scope),
+                    None => Ok(Value::Null),
+                }
+            }
+            CaseExpression::Searched(searched) => {
+                for clause in &searched.when_clauses {
+                    let condition = self.eval(&clause.condition, scope)?;
+                    if truth(&condition, &clause.span)? == Some(true) {
+                        return self.eval(&clause.then_result, scope);
+                    }
+                }
+                match &searched.else_clause {
+                    Some(otherwise) => self.eval(otherwise, scope),
+                    None => Ok(Value::Null),
+                }
+            }
+        }
+    }
+
+    fn cast(&self, cast: &CastExpression, scope: Scope<'_>) -> EvalResult {
+        let value = self.eval(&cast.operand, scope)?;
+        if let Some(constant) = value.to_constant() {
+            return Ok(self
+                .constants
+                .cast_value(constant, &cast.target_type, &cast.span)?
+                .into());
+        }
+        let target = Type::from_value_type(&cast.target_type);
+        if has_element_type(&value, &target) {
+            Ok(value)
+        } else {
+            Err(RuntimeError::new(
+                RuntimeErrorKind::Evaluation(ConstEvalErrorKind::InvalidCast),
+                format!("cannot cast {} to {}", describe(&value), target.name()),
+                &cast.span,
+            ))
+        }
+    }
+
+    fn path(&self, items: Vec<Value>, span: &Span) -> EvalResult {
+        if items.iter().any(Value::is_null) {
+            return Ok(Value::Null);
+        }
+        let malformed = || {
+            RuntimeError::mismatch(
+                "PATH elements must alternate nodes and edges connecting them",
+                span,
+            )
+        };
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            match (index % 2, item) {
+                (0, Value::Node(id)) => {
+                    self.node(*id, span)?;
+                    nodes.push(*id);
+                }
+                (1, Value::Edge(id)) => edges.push(*id),
+                _ => return Err(malformed()),
+            }
+        }
+        if nodes.len() != edges.len() + 1 {
+            return Err(malformed());
+        }
+        for (index, id) in edges.iter().enumerate() {
+            let edge = self.edge(*id, span)?;
+            let (a, b) = (nodes[index], nodes[index + 1]);
+            if !((edge.source == a && edge.target == b) || (edge.source == b && edge.target == a)) {
+                return Err(malformed());
+            }
+        }
+        Ok(Value::Path(Path::new(nodes, edges)))
+    }
+
+    fn predicate(&self, predicate: &Predicate, scope: Scope<'_>) -> EvalResult {
+        match predicate {
+            Predicate::IsNull(operand, negated, _) => {
+                let is_null = self.eval(operand, scope)?.is_null();
+                Ok(Value::Boolean(is_null != *negated))
+            }
+            Predicate::IsTyped(operand, target, negated, _) => {
+                let value = self.eval(operand, scope)?;
+                let target = Type::from_value_type(target);
+                let typed = match value.to_constant() {
+                    Some(Constant::Null) => return Ok(Value::Null),
+                    Some(constant) => constant.type_of().can_coerce_to(&target),
+                    None => has_element_type(&value, &target),
+                };
+                Ok(Value::Boolean(typed != *negated))
+            }
+            Predicate::IsNormalized(_, _, span) => {
+                Err(RuntimeError::unsupported("IS NORMALIZED", span))
+            }
+            Predicate::IsDirected(operand, negated, span) => match self.eval(operand, scope)? {
+                Value::Null => Ok(Value::Null),
+                Value::Edge(id) => Ok(Value::Boolean(self.edge(id, span)?.directed != *negated)),
+                other => Err(element_expected("an edge", &other, span)),
+            },
+            Predicate::IsLabeled(operand, expression, negated, span) => {
+                let labels = match self.eval(operand, scope)? {
+                    Value::Null => return Ok(Value::Null),
+                    Value::Node(id) => &self.node(id, span)?.labels,
+                    Value::Edge(id) => &self.edge(id, span)?.labels,
+                    other => return Err(element_expected("a node or edge", &other, span)),
+                };
+                let labeled = match expression {
+                    Some(expression) => label_expression_matches(expression, labels),
+                    None => !labels.is_empty(),
+                };
+                Ok(Value::Boolean(labeled != *negated))
+            }
+            Predicate::IsTruthValue(operand, expected, negated, span) => {
+                let actual = truth(&self.eval(operand, scope)?, span)?;
+                let expected = match expected {
+                    TruthValue::True => Some(true),
+                    TruthValue::False => Some(false),
+                    TruthValue::Unknown => None,
+                };
+                Ok(Value::Boolean((actual == expected) != *negated))
+            }
+            Predicate::IsSource(node, edge, negated, span)
+            | Predicate::IsDestination(node, edge, negated, span) => {
+                let node = self.eval(node, scope)?;
+                let edge = self.eval(edge, scope)?;
+                let (node, edge) = match (node, edge) {
+                    (Value::Null, _) | (_, Value::Null) => return Ok(Value::Null),
+                    (Value::Node(node), Value::Edge(edge)) => (node, self.edge(edge, span)?),
+                    (Value::Node(_), other) => {
+                        return Err(element_expected("an edge", &other, span));
+                    }
+                    (other, _) => return Err(element_expected("a node", &other, span)),
+                };
+                let endpoint = match predicate {
+                    Predicate::IsSource(..) => edge.source,
+                    _ => edge.target,
+                };
+                Ok(Value::Boolean(
+                    (edge.directed && endpoint == node) != *negated,
+                ))
+            }
+            Predicate::AllDifferent(items, span) => {
+                let items = self.eval_all(items, scope)?;
+                if items.iter().any(Value::is_null) {
+                    return Ok(Value::Null);
+                }
+                for item in &items {
+                    if !matches!(item, Value::Node(_) | Value::Edge(_)) {
+                        return Err(element_expected("a node or edge", item, span));
+                    }
+                }
+                let different = items
+                    .iter()
+                    .enumerate()
+                    .all(|(i, item)| items[i + 1..].iter().all(|other| other != item));
+                Ok(Value::Boolean(different))
+            }
+            Predicate::Same(left, right, span) => {
+                let left = self.eval(left, scope)?;
+                let right = self.eval(right, scope)?;
+                match (&left, &right) {
+                    (Value::Null, _) | (_, Value::Null) => Ok(Value::Null),
+                    (Value::Node(_) | Value::Edge(_), Value::Node(_) | Value::Edge(_)) => {
+                        Ok(Value::Boolean(left == right))
+                    }
+                    (Value::Node(_) | Value::Edge(_), other) | (other, _) => {
+                        Err(element_expected("a node or edge", other, span))
+                    }
+                }
+            }
+            Predicate::PropertyExists(operand, name, span) => {
+                let properties = match self.eval(operand, scope)? {
+                    Value::Null => return Ok(Value::Null),
+                    Value::Node(id) => &self.node(id, span)?.properties,
+                    Value::Edge(id) => &self.edge(id, span)?.properties,
+                    other => return Err(element_expected("a node or edge", &other, span)),
+                };
+                Ok(Value::Boolean(properties.contains_key(name)))
+            }
+        }
+    }
+
+    fn aggregate(&self, aggregate: &AggregateFunction, scope: Scope<'_>) -> EvalResult {
+        let span = &aggregate.span();
+        let argument = match aggregate {
+            AggregateFunction::CountStar { .. } => None,
+            AggregateFunction::GeneralSetFunction(function) => Some(&*function.expression),
+            AggregateFunction::BinarySetFunction(function) => {
+                Some(&*function.inverse_distribution_argument)
+            }
+        };
+
+        let groups: Vec<SmolStr> = argument
+            .map(|argument| {
+                ExpressionInfo::analyze(argument)
+                    .variable_references
+                    .into_iter()
+                    .filter(|name| scope.row.is_group(name))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut values = Vec::new();
+        if let Some(argument) = argument.filter(|_| !groups.is_empty()) {
+            // Horizontal aggregation over the elements of group variables.
+            let lists: Vec<(&SmolStr, &[Value])> = groups
+                .iter()
+                .map(|name| match scope.row.get(name) {
+                    Some(Value::List(items)) => (name, items.as_slice()),
+                    _ => (name, &[][..]),
+                })
+                .collect();
+            let len = lists
+                .iter()
+                .map(|(_, items)| items.len())
+                .max()
+                .unwrap_or(0);
+            for index in 0..len {
+                let mut row = scope.row.clone();
+                for (name, items) in &lists {
+                    let item = items.get(index).cloned().unwrap_or(Value::Null);
+                    row.bind((*name).clone(), item);
+                }
+                values.push(self.evaluate(argument, &row)?);
+            }
+        } else if let Some(group) = scope.group {
+            match argument {
+                Some(argument) => {
+                    for row in group {
+                        values.push(self.evaluate(argument, row)?);
+                    }
+                }
+                None => return Ok(Value::Integer(group.len() as i64)),
+            }
+        } else {
+            return Err(RuntimeError::unsupported(
+                "an aggregate function outside RETURN that does not range over a group variable",
+                span,
+            ));
+        }
+
+        match aggregate {
+            AggregateFunction::CountStar { .. } => unreachable!("COUNT(*) has no argument"),
+            AggregateFunction::GeneralSetFunction(function) => {
+                set_function(function.function_type, function.quantifier, values, span)
+            }
+            AggregateFunction::BinarySetFunction(function) => {
+                let row = scope
+                    .group
+                    .and_then(|group| group.first())
+                    .unwrap_or(scope.row);
+                let fraction = match constant(self.evaluate(&function.expression, row)?, span)? {
+                    Constant::Integer(i) => i as f64,
+                    Constant::Decimal(d) => d.to_f64(),
+                    Constant::Float(f) => f,
+                    other => {
+                        return Err(RuntimeError::mismatch(
+                            format!(
+                                "percentile must be numeric, found {}",
+                                other.type_of().name()
+                            ),
+                            span,
+                        ));
+                    }
+                };
+                percentile(
+                    function.function_type,
+                    function.quantifier,
+                    fraction,
+                    values,
+                    span,
+                )
+            }
+        }
+    }
+}
+
+/// Compares two values with three-valued logic. Graph elements are equal
+/// only to themselves and cannot be ordered.
+pub(crate) fn compare(
+    op: ComparisonOperator,
+    left: &Value,
+    right: &Value,
+    span: &Span,
+) -> EvalResult {
+    if let (Some(a), Some(b)) = (left.to_constant(), right.to_constant()) {
+        return Ok(const_eval::compare(op, &a, &b, span)?.into());
+    }
+    match op {
+        ComparisonOperator::Eq => Ok(from_truth(equal(left, right, span)?)),
+        ComparisonOperator::NotEq => Ok(from_truth(equal(left, right, span)?.map(|eq| !eq))),
+        _ => Err(RuntimeError::mismatch(
+            format!("{} values cannot be ordered", describe(left)),
+            span,
+        )),
+    }
+}
+
+fn equal(left: &Value, right: &Value, span: &Span) -> Result<Option<bool>, RuntimeError> {
+    match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => Ok(None),
+        (Value::List(a), Value::List(b)) => {
+            if a.len() != b.len() {
+                return Ok(Some(false));
+            }
+            let mut unknown = false;
+            for (x, y) in a.iter().zip(b) {
+                match equal(x, y, span)? {
+                    Some(false) => return Ok(Some(false)),
+                    None => unknown = true,
+                    Some(true) => {}
+                }
+            }
+            Ok((!unknown).then_some(true))
+        }
+        (Value::Record(a), Value::Record(b)) => {
+            if a.len() != b.len() || a.iter().zip(b).any(|((m, _), (n, _))| m != n) {
+                return Ok(Some(false));
+            }
+            let a = Value::List(a.iter().map(|(_, value)| value.clone()).collect());
+            let b = Value::List(b.iter().map(|(_, value)| value.clone()).collect());
+            equal(&a, &b, span)
+        }
+        _ => match compare(ComparisonOperator::Eq, left, right, span) {
+            Ok(Value::Boolean(eq)) => Ok(Some(eq)),
+            Ok(_) => Ok(None),
+            Err(_) if left.to_constant().is_none() || right.to_constant().is_none() => {
+                Ok(Some(left == right))
+            }
+            Err(error) => Err(error),
+        },
+    }
+}
+
+fn set_function(
+    function: GeneralSetFunctionType,
+    quantifier: Option<SetQuantifier>,
+    values: Vec<Value>,
+    span: &Span,
+) -> EvalResult {
+    let values = set_values(quantifier, values);
+    if function == GeneralSetFunctionType::Count {
+        return Ok(Value::Integer(values.len() as i64));
+    }
+    if function == GeneralSetFunctionType::CollectList {
+        return Ok(Value::List(values));
+    }
+    let Some(first) = values.first().cloned() else {
+        return Ok(Value::Null);
+    };
+
+    match function {
+        GeneralSetFunctionType::Sum | GeneralSetFunctionType::Avg => {
+            let mut sum = constant(first, span)?;
+            for value in &values[1..] {
+                let value = constant(value.clone(), span)?;
+                sum = const_eval::binary(BinaryOperator::Add, sum, value, span)?;
+            }
+            if function == GeneralSetFunctionType::Avg {
+                let count = Constant::Integer(values.len() as i64);
+                sum = const_eval::binary(BinaryOperator::Divide, sum, count, span)?;
+            }
+            Ok(sum.into())
+        }
+        GeneralSetFunctionType::Max => Ok(values
+            .into_iter()
+            .reduce(|max, value| {
+                if sort_order(&value, &max).is_gt() {
+                    value
+                } else {
+                    max
+                }
+            })
+            .unwrap_or(Value::Null)),
+        GeneralSetFunctionType::Min => Ok(values
+            .into_iter()
+            .reduce(|min, value| {
+                if sort_order(&value, &min).is_lt() {
+                    value
+                } else {
+                    min
+                }
+            })
+            .unwrap_or(Value::Null)),
+        GeneralSetFunctionType::StddevSamp | GeneralSetFunctionType::StddevPop => {
+            let numbers = numbers(&values, span)?;
+            let n = numbers.len() as f64;
+            let divisor = if function == GeneralSetFunctionType::StddevSamp {
+                n - 1.0
+            } else {
+                n
+            };
+            if divisor <= 0.0 {
+                return Ok(Value::Null);
+            }
+            let mean = numbers.iter().sum::<f64>() / n;
+            let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / divisor;
+            Ok(Value::Float(variance.sqrt()))
+        }
+        GeneralSetFunctionType::Count | GeneralSetFunctionType::CollectList => {
+            unreachable!("handled above")
+        }
+    }
+}
+
+fn percentile(
+    function: BinarySetFunctionType,
+    quantifier: Option<SetQuantifier>,
+    fraction: f64,
+    values: Vec<Value>,
+    span: &Span,
+) -> EvalResult {
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(RuntimeError::new(
+            RuntimeErrorKind::Evaluation(ConstEvalErrorKind::InvalidArgument),
+            format!("percentile {fraction} is outside [0, 1]"),
+            span,
+        ));
+    }
+    let mut values = set_values(quantifier, values);
+    if values.is_empty() {
+        return Ok(Value::Null);
+    }
+    values.sort_by(sort_order);
+    match function {
+        BinarySetFunctionType::PercentileDisc => {
+            // The first value whose cumulative distribution reaches the fraction.
+            let position = (fraction * values.len() as f64).ceil() as usize;
+            Ok(values.swap_remove(position.saturating_sub(1)))
+        }
+        BinarySetFunctionType::PercentileCont => {
+            let numbers = numbers(&values, span)?;
+            let position = fraction * (numbers.len() - 1) as f64;
+            let lower = position.floor() as usize;
+            let upper = position.ceil() as usize;
+            let weight = position - lower as f64;
+            Ok(Value::Float(
+                numbers[lower] + (numbers[upper] - numbers[lower]) * weight,
+            ))
+        }
+    }
+}
+
+/// Drops NULLs and, for DISTINCT, duplicates from the input of a set
+/// function.
+fn set_values(quantifier: Option<SetQuantifier>, values: Vec<Value>) -> Vec<Value> {
+    let mut kept: Vec<Value> = Vec::with_capacity(values.len());
+    for value in values {
+        if value.is_null()
+            || (quantifier == Some(SetQuantifier::Distinct)
+                && kept.iter().any(|seen| not_distinct(seen, &value)))
+        {
+            continue;
+        }
+        kept.push(value);
+    }
+    kept
+}
+
+fn numbers(values: &[Value], span: &Span) -> Result<Vec<f64>, RuntimeError> {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Integer(i) => Ok(*i as f64),
+            Value::Decimal(d) => Ok(d.to_f64()),
+            Value::Float(f) => Ok(*f),
+            other => Err(RuntimeError::mismatch(
+                format!("expected a numeric value, found {}", describe(other)),
+                span,
+            )),
+        })
+        .collect()
+}
+
+fn has_element_type(value: &Value, target: &Type) -> bool {
+    let target = target.without_not_null();
+    match value {
+        Value::Node(_) => target.is_node() || *target == Type::Any,
+        Value::Edge(_) => target.is_edge() || *target == Type::Any,
+        Value::Path(_) => target.is_path() || *target == Type::Any,
+        _ => false,
+    }
+}
+
+/// Converts a value for a scalar operator.
+fn constant(value: Value, span: &Span) -> Result<Constant, RuntimeError> {
+    value.to_constant().ok_or_else(|| {
+        RuntimeError::mismatch(
+            format!("expected a value, found {}", describe(&value)),
+            span,
+        )
+    })
+}
+
+pub(crate) fn truth(value: &Value, span: &Span) -> Result<Option<bool>, RuntimeError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Boolean(b) => Ok(Some(*b)),
+        other => Err(RuntimeError::mismatch(
+            format!("expected BOOLEAN, found {}", describe(other)),
+            span,
+        )),
+    }
+}
+
+fn from_truth(truth: Option<bool>) -> Value {
+    truth.map_or(Value::Null, Value::Boolean)
+}
+
+/// Names the type of a value for error messages.
+pub(crate) fn describe(value: &Value) -> String {
+    value.type_of().name()
+}
+
+fn element_expected(expected: &str, found: &Value, span: &Span) -> RuntimeError {
+    RuntimeError::mismatch(
+        format!("expected {expected}, found {}", describe(found)),
+        span,
+    )
+}
+
+fn arity_error(name: &FunctionName, span: &Span) -> RuntimeError {
+    RuntimeError::new(
+        RuntimeErrorKind::Evaluation(ConstEvalErrorKind::InvalidArgument),
+        format!("wrong number of arguments for {}", name.gql_name()),
+        span,
+    )
+}
+
+pub(crate) fn deleted(span: &Span) -> RuntimeError {
+    RuntimeError::new(
+        RuntimeErrorKind::DeletedElement,
+        "graph element was deleted",
+        span,
+    )
+}
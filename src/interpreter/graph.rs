@@ -0,0 +1,371 @@
+//! In-memory property graph.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use smol_str::SmolStr;
+
+use super::value::Value;
+use crate::pattern::Traversal;
+
+/// Identifier of a node in a [`PropertyGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns the position of the node in insertion order.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Identifier of an edge in a [`PropertyGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    /// Returns the position of the edge in insertion order.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A node: a label set and properties.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Node {
+    /// Labels, sorted and without duplicates.
+    pub labels: Vec<SmolStr>,
+    pub properties: BTreeMap<SmolStr, Value>,
+}
+
+/// An edge between two nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    /// Source of a directed edge; either endpoint of an undirected one.
+    pub source: NodeId,
+    /// Target of a directed edge; the other endpoint of an undirected one.
+    pub target: NodeId,
+    pub directed: bool,
+    /// Labels, sorted and without duplicates.
+    pub labels: Vec<SmolStr>,
+    pub properties: BTreeMap<SmolStr, Value>,
+}
+
+/// A path: a node followed by edge-node pairs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    nodes: Vec<NodeId>,
+    edges: Vec<EdgeId>,
+}
+
+impl Path {
+    /// Creates a path. `nodes` must have exactly one more entry than
+    /// `edges`, and each edge must connect the nodes around it.
+    pub fn new(nodes: Vec<NodeId>, edges: Vec<EdgeId>) -> Self {
+        debug_assert_eq!(nodes.len(), edges.len() + 1);
+        Path { nodes, edges }
+    }
+
+    /// Returns the nodes of the path in order.
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+
+    /// Returns the edges of the path in order.
+    pub fn edges(&self) -> &[EdgeId] {
+        &self.edges
+    }
+
+    /// Returns the number of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns true for a path of a single node.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.nodes[0].0)?;
+        for (edge, node) in self.edges.iter().zip(&self.nodes[1..]) {
+            write!(f, "-[{}]-({})", edge.0, node.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// One way of leaving a node over an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub edge: EdgeId,
+    /// Node reached over the edge.
+    pub node: NodeId,
+    pub traversal: Traversal,
+}
+
+/// A mutable property graph held in memory.
+///
+/// Identifiers are never reused: removing an element leaves a hole, so
+/// identifiers held by query results stay unambiguous.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropertyGraph {
+    nodes: Vec<Option<Node>>,
+    edges: Vec<Option<Edge>>,
+    /// Edges incident to each node; a self-loop is listed once.
+    incident: Vec<Vec<EdgeId>>,
+}
+
+impl PropertyGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node and returns its identifier.
+    pub fn add_node<L, K>(
+        &mut self,
+        labels: impl IntoIterator<Item = L>,
+        properties: impl IntoIterator<Item = (K, Value)>,
+    ) -> NodeId
+    where
+        L: Into<SmolStr>,
+        K: Into<SmolStr>,
+    {
+        self.nodes.push(Some(Node {
+            labels: label_set(labels),
+            properties: property_map(properties),
+        }));
+        self.incident.push(Vec::new());
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Adds a directed edge from `source` to `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either endpoint is not in the graph.
+    pub fn add_edge<L, K>(
+        &mut self,
+        source: NodeId,
+        target: NodeId,
+        labels: impl IntoIterator<Item = L>,
+        properties: impl IntoIterator<Item = (K, Value)>,
+    ) -> EdgeId
+    where
+        L: Into<SmolStr>,
+        K: Into<SmolStr>,
+    {
+        self.insert_edge(
+            source,
+            target,
+            true,
+            label_set(labels),
+            property_map(properties),
+        )
+    }
+
+    /// Adds an undirected edge between `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either endpoint is not in the graph.
+    pub fn add_undirected_edge<L, K>(
+        &mut self,
+        a: NodeId,
+        b: NodeId,
+        labels: impl IntoIterator<Item = L>,
+        properties: impl IntoIterator<Item = (K, Value)>,
+    ) -> EdgeId
+    where
+        L: Into<SmolStr>,
+        K: Into<SmolStr>,
+    {
+        self.insert_edge(a, b, false, label_set(labels), property_map(properties))
+    }
+
+    fn insert_edge(
+        &mut self,
+        source: NodeId,
+        target: NodeId,
+        directed: bool,
+        labels: Vec<SmolStr>,
+        properties: BTreeMap<SmolStr, Value>,
+    ) -> EdgeId {
+        assert!(
+            self.node(source).is_some(),
+            "edge source is not in the graph"
+        );
+        assert!(
+            self.node(target).is_some(),
+            "edge target is not in the graph"
+        );
+        let id = EdgeId(self.edges.len());
+        self.edges.push(Some(Edge {
+            source,
+            target,
+            directed,
+            labels,
+            properties,
+        }));
+        self.incident[source.0].push(id);
+        if target != source {
+            self.incident[target.0].push(id);
+        }
+        id
+    }
+
+    /// Returns a node, or `None` if it was removed.
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id.0)?.as_ref()
+    }
+
+    /// Returns a node for modification.
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(id.0)?.as_mut()
+    }
+
+    /// Returns an edge, or `None` if it was removed.
+    pub fn edge(&self, id: EdgeId) -> Option<&Edge> {
+        self.edges.get(id.0)?.as_ref()
+    }
+
+    /// Returns an edge for modification.
+    pub fn edge_mut(&mut self, id: EdgeId) -> Option<&mut Edge> {
+        self.edges.get_mut(id.0)?.as_mut()
+    }
+
+    /// Iterates over the nodes in insertion order.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &Node)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| Some((NodeId(index), node.as_ref()?)))
+    }
+
+    /// Iterates over the edges in insertion order.
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, &Edge)> {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter_map(|(index, edge)| Some((EdgeId(index), edge.as_ref()?)))
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes().count()
+    }
+
+    /// Returns the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+
+    /// Returns the edges incident to a node.
+    pub fn incident_edges(&self, node: NodeId) -> &[EdgeId] {
+        self.incident.get(node.0).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the ways of leaving `node` over one edge. A directed
+    /// self-loop can be traversed both forward and backward.
+    pub fn steps(&self, node: NodeId) -> Vec<Step> {
+        let mut steps = Vec::new();
+        for &id in self.incident_edges(node) {
+            let Some(edge) = self.edge(id) else { continue };
+            if !edge.directed {
+                let other = if edge.source == node {
+                    edge.target
+                } else {
+                    edge.source
+                };
+                steps.push(Step {
+                    edge: id,
+                    node: other,
+                    traversal: Traversal::Undirected,
+                });
+                continue;
+            }
+            if edge.source == node {
+                steps.push(Step {
+                    edge: id,
+                    node: edge.target,
+                    traversal: Traversal::Forward,
+                });
+            }
+            if edge.target == node {
+                steps.push(Step {
+                    edge: id,
+                    node: edge.source,
+                    traversal: Traversal::Backward,
+                });
+            }
+        }
+        steps
+    }
+
+    /// Removes an edge and returns it.
+    pub fn remove_edge(&mut self, id: EdgeId) -> Option<Edge> {
+        let edge = self.edges.get_mut(id.0)?.take()?;
+        for endpoint in [edge.source, edge.target] {
+            self.incident[endpoint.0].retain(|&incident| incident != id);
+        }
+        Some(edge)
+    }
+
+    /// Removes a node together with its incident edges and returns it.
+    pub fn remove_node(&mut self, id: NodeId) -> Option<Node> {
+        let node = self.nodes.get_mut(id.0)?.take()?;
+        for edge in std::mem::take(&mut self.incident[id.0]) {
+            self.remove_edge(edge);
+        }
+        Some(node)
+    }
+}
+
+fn label_set<L: Into<SmolStr>>(labels: impl IntoIterator<Item = L>) -> Vec<SmolStr> {
+    let mut labels: Vec<SmolStr> = labels.into_iter().map(Into::into).collect();
+    labels.sort();
+    labels.dedup();
+    labels
+}
+
+/// Builds a property map, dropping NULL values: an absent property and a
+/// NULL property are indistinguishable.
+fn property_map<K: Into<SmolStr>>(
+    properties: impl IntoIterator<Item = (K, Value)>,
+) -> BTreeMap<SmolStr, Value> {
+    properties
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| (key.into(), value))
+        .collect()
+}
+
+/// Inserts `label` into a sorted label set.
+pub(crate) fn add_label(labels: &mut Vec<SmolStr>, label: &SmolStr) {
+    if let Err(position) = labels.binary_search(label) {
+        labels.insert(position, label.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removing_a_node_detaches_its_edges() {
+        let mut graph = PropertyGraph::new();
+        let a = graph.add_node(["Person"], [("name", Value::from("Ann"))]);
+        let b = graph.add_node(["Person"], [("name", Value::from("Bob"))]);
+        let knows = graph.add_edge(a, b, ["KNOWS"], Vec::<(&str, Value)>::new());
+        let loops = graph.add_edge(b, b, ["SELF"], Vec::<(&str, Value)>::new());
+
+        assert_eq!(graph.steps(b).len(), 3);
+        assert!(graph.remove_node(b).is_some());
+        assert!(graph.edge(knows).is_none() && graph.edge(loops).is_none());
+        assert!(graph.incident_edges(a).is_empty());
+        assert_eq!((graph.node_count(), graph.edge_count()), (1, 0));
+    }
+}
@@ -0,0 +1,729 @@
+//! Graph pattern matching.
+//!
+//! Each path pattern is compiled into an [`Nfa`] and matched by a
+//! breadth-first search over the product of the graph and the automaton, so
+//! paths come out in order of length. Path modes prune routes as they grow;
+//! selective searches pick paths per pair of endpoints once the search is
+//! exhausted. Under WALK a selective search only extends a route if no
+//! earlier route reached the same node and automaton state with the same
+//! bindings often enough to make it redundant, which keeps the search
+//! finite on cyclic graphs.
+//!
+//! A KEEP clause is applied as the default prefix of every path pattern.
+
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use super::error::{RuntimeError, RuntimeErrorKind};
+use super::eval::{Evaluator, Row, column_name, compare};
+use super::graph::{EdgeId, NodeId, Path};
+use super::value::Value;
+use crate::ast::expression::ComparisonOperator;
+use crate::ast::query::{
+    GraphPattern, MatchMode, PathMode, PathPattern, PathPatternExpression, PathPatternPrefix,
+    PathPrimary, PathSearch, ShortestPathSearch,
+};
+use crate::ast::{Expression, Span};
+use crate::ir::symbol_table::DegreeOfReference;
+use crate::pattern::Nfa;
+use crate::pattern::automaton::{ElementTest, StateId, Symbol, direction_allows};
+
+type MatchResult<T> = Result<T, RuntimeError>;
+
+/// A graph pattern prepared for matching.
+pub(crate) struct CompiledPattern<'p> {
+    pattern: &'p GraphPattern,
+    paths: Vec<PathPlan<'p>>,
+}
+
+impl<'p> CompiledPattern<'p> {
+    pub(crate) fn compile(pattern: &'p GraphPattern) -> MatchResult<Self> {
+        let keep = pattern.keep_clause.as_ref().map(|keep| &keep.prefix);
+        let paths = pattern
+            .paths
+            .patterns
+            .iter()
+            .map(|path| PathPlan::compile(path, keep))
+            .collect::<MatchResult<_>>()?;
+        Ok(Self { pattern, paths })
+    }
+
+    /// Returns the variables a match adds to its input row, with whether
+    /// they are group variables.
+    pub(crate) fn variables(&self) -> Vec<(SmolStr, bool)> {
+        let mut declared: Vec<(SmolStr, bool)> = Vec::new();
+        for path in &self.paths {
+            let path_variable = path.variable.iter().map(|name| (name.clone(), false));
+            for (name, group) in path.variables.iter().cloned().chain(path_variable) {
+                if !declared.iter().any(|(seen, _)| *seen == name) {
+                    declared.push((name, group));
+                }
+            }
+        }
+        let Some(yield_clause) = &self.pattern.yield_clause else {
+            return declared;
+        };
+        yield_clause
+            .items
+            .iter()
+            .map(|item| {
+                let group = match &item.expression {
+                    Expression::VariableReference(name, _) => declared
+                        .iter()
+                        .any(|(declared, group)| *group && declared == name),
+                    _ => false,
+                };
+                (column_name(&item.expression, item.alias.as_ref()), group)
+            })
+            .collect()
+    }
+
+    /// Returns the rows extending `input` with each match of the pattern.
+    pub(crate) fn matches(&self, evaluator: &Evaluator<'_>, input: &Row) -> MatchResult<Vec<Row>> {
+        let different_edges = self.pattern.match_mode == Some(MatchMode::DifferentEdges);
+        let mut partial: Vec<(Row, Vec<EdgeId>)> = vec![(input.clone(), Vec::new())];
+        for path in &self.paths {
+            let mut joined = Vec::new();
+            for (row, edges) in partial {
+                for (row, matched) in path.run(evaluator, &row, different_edges)? {
+                    if different_edges && matched.edges().iter().any(|edge| edges.contains(edge)) {
+                        continue;
+                    }
+                    let mut edges = edges.clone();
+                    edges.extend_from_slice(matched.edges());
+                    joined.push((row, edges));
+                }
+            }
+            partial = joined;
+        }
+
+        let mut rows = Vec::new();
+        for (row, _) in partial {
+            if let Some(where_clause) = &self.pattern.where_clause
+                && !evaluator.condition(&where_clause.condition, &row)?
+            {
+                continue;
+            }
+            rows.push(match &self.pattern.yield_clause {
+                Some(yield_clause) => {
+                    let mut yielded = input.clone();
+                    for item in &yield_clause.items {
+                        let name = column_name(&item.expression, item.alias.as_ref());
+                        let value = evaluator.evaluate(&item.expression, &row)?;
+                        match (&item.expression, value) {
+                            (Expression::VariableReference(variable, _), Value::List(elements))
+                                if row.is_group(variable) =>
+                            {
+                                yielded.bind_group(name, elements)
+                            }
+                            (_, value) => yielded.bind(name, value),
+                        }
+                    }
+                    yielded
+                }
+                None => row,
+            });
+        }
+        Ok(rows)
+    }
+}
+
+/// Path search of a path pattern, before its counts are evaluated.
+#[derive(Clone, Copy)]
+enum Search<'p> {
+    All,
+    /// ANY and ANY SHORTEST: one shortest path per pair of endpoints.
+    AnyShortest,
+    AllShortest,
+    Shortest(&'p Expression),
+    ShortestGroups(&'p Expression),
+}
+
+/// Which matches of a path pattern are kept, per pair of endpoints.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    All,
+    /// The first `k` paths in order of length.
+    Shortest(usize),
+    /// The paths of the `k` smallest lengths.
+    Groups(usize),
+}
+
+struct PathPlan<'p> {
+    nfa: Nfa,
+    mode: PathMode,
+    search: Search<'p>,
+    /// Path variable.
+    variable: Option<SmolStr>,
+    /// Element variables, with whether they are group variables.
+    variables: Vec<(SmolStr, bool)>,
+    /// Set for path pattern unions, which do not produce duplicates.
+    distinct: bool,
+    span: &'p Span,
+}
+
+impl<'p> PathPlan<'p> {
+    fn compile(path: &'p PathPattern, keep: Option<&'p PathPatternPrefix>) -> MatchResult<Self> {
+        let nfa = Nfa::compile(&path.expression)?;
+        let mut variables: Vec<(SmolStr, bool)> = Vec::new();
+        for state in nfa.states() {
+            if let Some(binding) = &state.binding
+                && !variables.iter().any(|(name, _)| *name == binding.variable)
+            {
+                let group = binding.degree == DegreeOfReference::Group;
+                variables.push((binding.variable.clone(), group));
+            }
+        }
+
+        let (mut mode, mut search) = prefix_parts(path.prefix.as_ref());
+        if let Some((keep_mode, keep_search)) = keep.map(|keep| prefix_parts(Some(keep))) {
+            mode = mode.or(keep_mode);
+            search = search.or(keep_search);
+        }
+        Ok(Self {
+            nfa,
+            mode: mode.unwrap_or_default(),
+            search: search.unwrap_or(Search::All),
+            variable: path
+                .variable_declaration
+                .as_ref()
+                .map(|declaration| declaration.variable.clone()),
+            variables,
+            distinct: contains_union(&path.expression),
+            span: &path.span,
+        })
+    }
+
+    /// Returns the matches of the path pattern that are compatible with
+    /// `input`, each with the row it binds and the path it matched.
+    fn run(
+        &self,
+        evaluator: &Evaluator<'_>,
+        input: &Row,
+        different_edges: bool,
+    ) -> MatchResult<Vec<(Row, Path)>> {
+        let selection = match self.search {
+            Search::All => Selection::All,
+            Search::AnyShortest => Selection::Shortest(1),
+            Search::AllShortest => Selection::Groups(1),
+            Search::Shortest(count) => Selection::Shortest(evaluator.count(count, input)?),
+            Search::ShortestGroups(count) => Selection::Groups(evaluator.count(count, input)?),
+        };
+        let unrestricted = self.mode == PathMode::Walk && !different_edges;
+        let graph = evaluator.graph();
+
+        let mut frontier: Vec<Route<'_>> = graph
+            .nodes()
+            .map(|(node, _)| Route::new(node, self.nfa.start(), input))
+            .collect();
+        let mut arrivals: HashMap<ArrivalKey, Vec<usize>> = HashMap::new();
+        let mut found = Vec::new();
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for route in frontier {
+                for route in self.closure(evaluator, route)? {
+                    if route.state == self.nfa.accept() {
+                        found.push(route.clone());
+                    }
+                    let edges: Vec<_> = self
+                        .nfa
+                        .state(route.state)
+                        .transitions
+                        .iter()
+                        .filter_map(|transition| match &transition.symbol {
+                            Symbol::Edge(direction, test) => {
+                                Some((*direction, test, transition.target))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    if edges.is_empty() || route.closed {
+                        continue;
+                    }
+
+                    let node = route.node();
+                    let mut route = route;
+                    if unrestricted {
+                        if selection == Selection::All {
+                            // A route that returns to the same node in the
+                            // same state can repeat that cycle forever.
+                            if route.history.contains(&(node, route.state)) {
+                                return Err(RuntimeError::new(
+                                    RuntimeErrorKind::InfinitePaths,
+                                    "path pattern matches infinitely many walks",
+                                    self.span,
+                                ));
+                            }
+                            route.history.push((node, route.state));
+                        } else if !admit(&mut arrivals, &route, selection) {
+                            continue;
+                        }
+                    }
+
+                    for step in graph.steps(node) {
+                        let Some(edge) = graph.edge(step.edge) else {
+                            continue;
+                        };
+                        for &(direction, test, target) in &edges {
+                            if !direction_allows(direction, step.traversal)
+                                || !test.matches_labels(&edge.labels)
+                            {
+                                continue;
+                            }
+                            let revisits = route.nodes.contains(&step.node);
+                            let allowed = match self.mode {
+                                PathMode::Walk => true,
+                                PathMode::Trail => !route.edges.contains(&step.edge),
+                                PathMode::Acyclic => !revisits,
+                                PathMode::Simple => !revisits || step.node == route.nodes[0],
+                            };
+                            if !allowed || (different_edges && route.edges.contains(&step.edge)) {
+                                continue;
+                            }
+                            let mut extended = route.clone();
+                            extended.edges.push(step.edge);
+                            extended.nodes.push(step.node);
+                            extended.closed = self.mode == PathMode::Simple && revisits;
+                            extended.entered = vec![target];
+                            extended.state = target;
+                            if self.enter(evaluator, &mut extended, Value::Edge(step.edge), test)? {
+                                next.push(extended);
+                            }
+                        }
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut matches = Vec::new();
+        for route in found {
+            if let Some(matched) = self.finish(evaluator, route)? {
+                if self.distinct && matches.contains(&matched) {
+                    continue;
+                }
+                matches.push(matched);
+            }
+        }
+        Ok(select(selection, matches))
+    }
+
+    /// Returns the routes reachable from `route` without leaving its node:
+    /// over ε-transitions and node tests the node satisfies.
+    fn closure<'n>(
+        &'n self,
+        evaluator: &Evaluator<'_>,
+        route: Route<'n>,
+    ) -> MatchResult<Vec<Route<'n>>> {
+        let node = route.node();
+        let labels = &evaluator.node(node, self.span)?.labels;
+        let mut reached = Vec::new();
+        let mut pending = vec![route];
+        while let Some(route) = pending.pop() {
+            for transition in self.nfa.state(route.state).transitions.iter().rev() {
+                let test = match &transition.symbol {
+                    Symbol::Epsilon => None,
+                    Symbol::Node(test) if test.matches_labels(labels) => Some(test),
+                    Symbol::Node(_) | Symbol::Edge(..) => continue,
+                };
+                // Re-entering a state without moving would repeat the same
+                // node tests forever.
+                if route.entered.contains(&transition.target) {
+                    continue;
+                }
+                let mut next = route.clone();
+                next.state = transition.target;
+                next.entered.push(transition.target);
+                if let Some(test) = test
+                    && !self.enter(evaluator, &mut next, Value::Node(node), test)?
+                {
+                    continue;
+                }
+                pending.push(next);
+            }
+            reached.push(route);
+        }
+        Ok(reached)
+    }
+
+    /// Binds `element` to the variable of the state `route` just entered and
+    /// checks the element's predicates. Predicates that refer to variables
+    /// bound later in the path are checked when the match is complete.
+    fn enter<'n>(
+        &self,
+        evaluator: &Evaluator<'_>,
+        route: &mut Route<'n>,
+        element: Value,
+        test: &'n ElementTest,
+    ) -> MatchResult<bool> {
+        let binding = self.nfa.state(route.state).binding.as_ref();
+        if let Some(binding) = binding {
+            let name = &binding.variable;
+            if binding.degree == DegreeOfReference::Group {
+                let mut elements = match route.row.get(name) {
+                    Some(Value::List(elements)) if route.row.is_group(name) => elements.clone(),
+                    _ => Vec::new(),
+                };
+                elements.push(element.clone());
+                route.row.bind_group(name.clone(), elements);
+            } else {
+                match route.row.get(name) {
+                    Some(bound) if *bound != element => return Ok(false),
+                    Some(_) => {}
+                    None => route.row.bind(name.clone(), element.clone()),
+                }
+            }
+        }
+        if !test.has_predicate() {
+            return Ok(true);
+        }
+
+        let variable = binding.map(|binding| binding.variable.clone());
+        let mut row = route.row.current_iteration();
+        if let Some(variable) = &variable {
+            row.bind(variable.clone(), element.clone());
+        }
+        match satisfies(evaluator, test, &element, &row) {
+            Err(error) if error.kind == RuntimeErrorKind::UnboundVariable => {
+                route.deferred.push(Deferred {
+                    test,
+                    element,
+                    variable,
+                });
+                Ok(true)
+            }
+            result => result,
+        }
+    }
+
+    /// Completes the bindings of an accepted route and checks its deferred
+    /// predicates.
+    fn finish(
+        &self,
+        evaluator: &Evaluator<'_>,
+        route: Route<'_>,
+    ) -> MatchResult<Option<(Row, Path)>> {
+        let mut row = route.row;
+        for (name, group) in &self.variables {
+            if row.get(name).is_none() {
+                if *group {
+                    row.bind_group(name.clone(), Vec::new());
+                } else {
+                    row.bind(name.clone(), Value::Null);
+                }
+            }
+        }
+        let path = Path::new(route.nodes, route.edges);
+        if let Some(variable) = &self.variable {
+            row.bind(variable.clone(), Value::Path(path.clone()));
+        }
+        for deferred in &route.deferred {
+            let mut view = row.clone();
+            if let Some(variable) = &deferred.variable {
+                view.bind(variable.clone(), deferred.element.clone());
+            }
+            if !satisfies(evaluator, deferred.test, &deferred.element, &view)? {
+                return Ok(None);
+            }
+        }
+        Ok(Some((row, path)))
+    }
+}
+
+/// A partial match: a path through the graph and a run of the automaton
+/// over it.
+#[derive(Clone)]
+struct Route<'n> {
+    state: StateId,
+    nodes: Vec<NodeId>,
+    edges: Vec<EdgeId>,
+    row: Row,
+    deferred: Vec<Deferred<'n>>,
+    /// States entered since the last edge.
+    entered: Vec<StateId>,
+    /// Set once a SIMPLE path returns to its first node.
+    closed: bool,
+    /// Node and state at each edge taken, to detect unbounded walks.
+    history: Vec<(NodeId, StateId)>,
+}
+
+impl Route<'_> {
+    fn new(node: NodeId, state: StateId, input: &Row) -> Self {
+        Route {
+            state,
+            nodes: vec![node],
+            edges: Vec::new(),
+            row: input.clone(),
+            deferred: Vec::new(),
+            entered: vec![state],
+            closed: false,
+            history: Vec::new(),
+        }
+    }
+
+    fn node(&self) -> NodeId {
+        *self.nodes.last().expect("a route starts at a node")
+    }
+}
+
+/// An element predicate waiting for variables bound later in the path.
+#[derive(Clone)]
+struct Deferred<'n> {
+    test: &'n ElementTest,
+    element: Value,
+    variable: Option<SmolStr>,
+}
+
+/// Identifies routes with the same future: the same start node, current
+/// node and automaton state, and the same singleton element bindings.
+type ArrivalKey = (NodeId, NodeId, StateId, Vec<(SmolStr, ElementKey)>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ElementKey {
+    Node(NodeId),
+    Edge(EdgeId),
+}
+
+/// Records the arrival of `route` and returns false if enough routes with
+/// the same future already arrived for `selection` to ignore it.
+fn admit(
+    arrivals: &mut HashMap<ArrivalKey, Vec<usize>>,
+    route: &Route<'_>,
+    selection: Selection,
+) -> bool {
+    let bindings = route
+        .row
+        .bindings()
+        .iter()
+        .filter_map(|(name, value)| match value {
+            Value::Node(node) => Some((name.clone(), ElementKey::Node(*node))),
+            Value::Edge(edge) => Some((name.clone(), ElementKey::Edge(*edge))),
+            _ => None,
+        })
+        .collect();
+    let key = (route.nodes[0], route.node(), route.state, bindings);
+    let lengths = arrivals.entry(key).or_default();
+    let length = route.edges.len();
+    let admitted = match selection {
+        Selection::All => true,
+        Selection::Shortest(k) => lengths.len() < k,
+        Selection::Groups(k) => within_groups(lengths, length, k),
+    };
+    if admitted {
+        lengths.push(length);
+    }
+    admitted
+}
+
+/// Keeps the selected matches of each pair of endpoints. `matches` is in
+/// order of length.
+fn select(selection: Selection, matches: Vec<(Row, Path)>) -> Vec<(Row, Path)> {
+    if selection == Selection::All {
+        return matches;
+    }
+    let mut partitions: HashMap<(NodeId, NodeId), Vec<usize>> = HashMap::new();
+    let mut kept = Vec::new();
+    for (row, path) in matches {
+        let endpoints = (
+            path.nodes()[0],
+            *path.nodes().last().expect("paths are not empty"),
+        );
+        let lengths = partitions.entry(endpoints).or_default();
+        let keep = match selection {
+            Selection::All => true,
+            Selection::Shortest(k) => lengths.len() < k,
+            Selection::Groups(k) => within_groups(lengths, path.len(), k),
+        };
+        if keep {
+            lengths.push(path.len());
+            kept.push((row, path));
+        }
+    }
+    kept
+}
+
+/// Returns true if `length` is among the `k` smallest lengths, given the
+/// lengths seen so far in ascending order.
+fn within_groups(lengths: &[usize], length: usize, k: usize) -> bool {
+    let mut distinct = lengths.to_vec();
+    distinct.dedup();
+    distinct.contains(&length) || distinct.len() < k
+}
+
+/// Checks the property specification and WHERE clause of an element test.
+fn satisfies(
+    evaluator: &Evaluator<'_>,
+    test: &ElementTest,
+    element: &Value,
+    row: &Row,
+) -> MatchResult<bool> {
+    for pair in &test.properties {
+        let properties = match element {
+            Value::Node(node) => &evaluator.node(*node, &pair.span)?.properties,
+            Value::Edge(edge) => &evaluator.edge(*edge, &pair.span)?.properties,
+            _ => unreachable!("element tests apply to nodes and edges"),
+        };
+        let actual = properties.get(&pair.key).cloned().unwrap_or(Value::Null);
+        let expected = evaluator.evaluate(&pair.value, row)?;
+        let equal = compare(ComparisonOperator::Eq, &actual, &expected, &pair.span)?;
+        if equal != Value::Boolean(true) {
+            return Ok(false);
+        }
+    }
+    match &test.condition {
+        Some(condition) => evaluator.condition(condition, row),
+        None => Ok(true),
+    }
+}
+
+fn prefix_parts(prefix: Option<&PathPatternPrefix>) -> (Option<PathMode>, Option<Search<'_>>) {
+    match prefix {
+        None => (None, None),
+        Some(PathPatternPrefix::PathMode(mode)) => (Some(*mode), None),
+        Some(PathPatternPrefix::PathSearch(PathSearch::All(all))) => (all.mode, Some(Search::All)),
+        Some(PathPatternPrefix::PathSearch(PathSearch::Any(any))) => {
+            (any.mode, Some(Search::AnyShortest))
+        }
+        Some(PathPatternPrefix::PathSearch(PathSearch::Shortest(shortest))) => match shortest {
+            ShortestPathSearch::AllShortest { mode, .. } => (*mode, Some(Search::AllShortest)),
+            ShortestPathSearch::AnyShortest { mode, .. } => (*mode, Some(Search::AnyShortest)),
+            ShortestPathSearch::CountedShortest { count, mode, .. } => {
+                (*mode, Some(Search::Shortest(count)))
+            }
+            ShortestPathSearch::CountedShortestGroups { count, mode, .. } => {
+                (*mode, Some(Search::ShortestGroups(count)))
+            }
+        },
+    }
+}
+
+/// Returns true if the expression contains a path pattern union (`|`).
+fn contains_union(expression: &PathPatternExpression) -> bool {
+    match expression {
+        PathPatternExpression::Union { .. } => true,
+        PathPatternExpression::Alternation { alternatives, .. } => {
+            alternatives.iter().any(contains_union)
+        }
+        PathPatternExpression::Term(term) => term.factors.iter().any(|factor| {
+            matches!(&factor.primary, PathPrimary::ParenthesizedExpression(inner)
+                if contains_union(inner))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Statement;
+    use crate::ast::query::{MatchStatement, PrimitiveQueryStatement, Query};
+    use crate::interpreter::graph::PropertyGraph;
+
+    fn pattern(source: &str) -> GraphPattern {
+        let result = crate::parse(&format!("MATCH {source} RETURN 1"));
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let program = result.ast.expect("pattern should parse");
+        let Statement::Query(statement) = &program.statements[0] else {
+            panic!("expected query statement");
+        };
+        let Query::Linear(query) = &statement.query else {
+            panic!("expected linear query");
+        };
+        let PrimitiveQueryStatement::Match(MatchStatement::Simple(simple)) =
+            &query.primitive_statements[0]
+        else {
+            panic!("expected MATCH");
+        };
+        simple.pattern.clone()
+    }
+
+    /// Returns the length of path `p` in each match of `source`.
+    fn path_lengths(graph: &PropertyGraph, source: &str) -> Vec<usize> {
+        let pattern = pattern(source);
+        let compiled = CompiledPattern::compile(&pattern).expect("pattern should compile");
+        let parameters = HashMap::new();
+        let evaluator = Evaluator::new(graph, &parameters);
+        compiled
+            .matches(&evaluator, &Row::default())
+            .expect("pattern should match")
+            .iter()
+            .map(|row| match row.get("p") {
+                Some(Value::Path(path)) => path.len(),
+                other => panic!("expected a path, found {other:?}"),
+            })
+            .collect()
+    }
+
+    /// Ann knows Bob and Cat, and Bob knows Cat.
+    fn triangle() -> PropertyGraph {
+        let mut graph = PropertyGraph::new();
+        let no_properties = Vec::<(&str, Value)>::new;
+        let ann = graph.add_node(["Person"], no_properties());
+        let bob = graph.add_node(["Person"], no_properties());
+        let cat = graph.add_node(["Person"], no_properties());
+        graph.add_edge(ann, bob, ["KNOWS"], no_properties());
+        graph.add_edge(bob, cat, ["KNOWS"], no_properties());
+        graph.add_edge(ann, cat, ["KNOWS"], no_properties());
+        graph
+    }
+
+    #[test]
+    fn test_paths_are_enumerated_in_order_of_length() {
+        let graph = triangle();
+        assert_eq!(
+            path_lengths(&graph, "p = (x)-[:KNOWS]->{1,2}(y)"),
+            vec![1, 1, 1, 2]
+        );
+        assert_eq!(path_lengths(&graph, "p = (x)<-[:KNOWS]-{2}(y)"), vec![2]);
+    }
+
+    #[test]
+    fn test_selective_searches_pick_paths_per_endpoint_pair() {
+        let graph = triangle();
+        // Ann reaches Cat directly and through Bob
+        assert_eq!(
+            path_lengths(&graph, "p = ANY SHORTEST (x)-[:KNOWS]->{1,2}(y)"),
+            vec![1, 1, 1]
+        );
+        assert_eq!(
+            path_lengths(&graph, "p = ALL SHORTEST (x)-[:KNOWS]->{1,2}(y)"),
+            vec![1, 1, 1]
+        );
+        assert_eq!(
+            path_lengths(&graph, "p = SHORTEST 2 PATHS (x)-[:KNOWS]->{1,2}(y)"),
+            vec![1, 1, 1, 2]
+        );
+        assert_eq!(
+            path_lengths(&graph, "p = SHORTEST 1 PATH GROUPS (x)-[:KNOWS]->{1,2}(y)"),
+            vec![1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_walk_searches_terminate_on_cycles() {
+        let mut graph = PropertyGraph::new();
+        let no_properties = Vec::<(&str, Value)>::new;
+        let ann = graph.add_node(["Person"], no_properties());
+        let bob = graph.add_node(["Person"], no_properties());
+        graph.add_edge(ann, bob, ["KNOWS"], no_properties());
+        graph.add_edge(bob, ann, ["KNOWS"], no_properties());
+
+        // One shortest walk from each node to each node, including itself
+        assert_eq!(
+            path_lengths(&graph, "p = ANY SHORTEST (x)-[:KNOWS]->+(y)"),
+            vec![1, 1, 2, 2]
+        );
+        // Under TRAIL no edge repeats, so the unbounded search ends anyway
+        assert_eq!(
+            path_lengths(&graph, "p = TRAIL (x)-[:KNOWS]->+(y)"),
+            vec![1, 1, 2, 2]
+        );
+    }
+
+    #[test]
+    fn test_within_groups_counts_distinct_lengths() {
+        assert!(within_groups(&[1, 1, 2], 2, 2));
+        assert!(within_groups(&[1, 1], 3, 2));
+        assert!(!within_groups(&[1, 1, 2], 3, 2));
+    }
+}
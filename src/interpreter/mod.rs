@@ -0,0 +1,178 @@
+//! Reference interpreter for GQL programs.
+//!
+//! [`Interpreter`] executes a validated program against an in-memory
+//! [`PropertyGraph`]. It is written to be obviously correct rather than
+//! fast: path patterns are matched by exhaustive search over the graph and
+//! the pattern automaton, working tables are plain vectors, and every
+//! data-modifying statement copies the graph so a failure can be undone. It
+//! is meant as a test oracle for real engines and for executable examples.
+//!
+//! Supported are MATCH with all path modes and path searches, OPTIONAL
+//! MATCH, FILTER, LET, FOR, RETURN with aggregation and GROUP BY, ORDER BY,
+//! OFFSET and LIMIT, set operations, NEXT, INSERT, SET, REMOVE, DELETE and
+//! transactions. Procedure calls, SELECT, catalog statements, subqueries and
+//! functions that read the session clock report
+//! [`RuntimeErrorKind::Unsupported`].
+//!
+//! # Example
+//!
+//! ```
+//! use gql_parser::interpreter::{Interpreter, PropertyGraph, Value};
+//! use gql_parser::parse_and_validate;
+//!
+//! let mut graph = PropertyGraph::new();
+//! let ann = graph.add_node(["Person"], [("name", Value::from("Ann"))]);
+//! let bob = graph.add_node(["Person"], [("name", Value::from("Bob"))]);
+//! graph.add_edge(ann, bob, ["KNOWS"], Vec::<(&str, Value)>::new());
+//!
+//! let ir = parse_and_validate("MATCH (a)-[:KNOWS]->(b) RETURN b.name AS friend")
+//!     .ir
+//!     .unwrap();
+//! let results = Interpreter::new(&mut graph).execute(&ir).unwrap();
+//! assert_eq!(results[0].get(0, "friend"), Some(&Value::from("Bob")));
+//! ```
+
+mod error;
+mod eval;
+pub mod graph;
+mod matching;
+mod mutation;
+mod query;
+pub mod value;
+
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+pub use error::{RuntimeError, RuntimeErrorKind};
+pub use graph::{Edge, EdgeId, Node, NodeId, Path, PropertyGraph};
+pub use value::Value;
+
+use crate::ast::TransactionCommand;
+use crate::ast::program::Statement;
+use crate::ir::IR;
+use crate::ir::type_table::Type;
+use eval::Evaluator;
+use query::WorkingTable;
+
+/// A column of a [`BindingTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: SmolStr,
+    /// Static type of the column, from the validator's type table where
+    /// available.
+    pub ty: Type,
+}
+
+/// Result of a query or data-modifying statement.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BindingTable {
+    pub columns: Vec<Column>,
+    /// Rows, each with one value per column.
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl BindingTable {
+    /// Returns the position of the column named `name`.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+
+    /// Returns the value of column `name` in row `row`.
+    pub fn get(&self, row: usize, name: &str) -> Option<&Value> {
+        self.rows.get(row)?.get(self.column(name)?)
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Executes programs against a property graph.
+#[derive(Debug)]
+pub struct Interpreter<'g> {
+    graph: &'g mut PropertyGraph,
+    parameters: HashMap<SmolStr, Value>,
+    /// Graph as it was when the open transaction started.
+    transaction: Option<PropertyGraph>,
+}
+
+impl<'g> Interpreter<'g> {
+    /// Creates an interpreter that reads and modifies `graph`.
+    pub fn new(graph: &'g mut PropertyGraph) -> Self {
+        Self {
+            graph,
+            parameters: HashMap::new(),
+            transaction: None,
+        }
+    }
+
+    /// Binds a value to the parameter `$name`.
+    pub fn with_parameter(mut self, name: impl Into<SmolStr>, value: impl Into<Value>) -> Self {
+        self.parameters.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns the graph.
+    pub fn graph(&self) -> &PropertyGraph {
+        self.graph
+    }
+
+    /// Executes the statements of a program in order and returns one table
+    /// per query or data-modifying statement. A statement after `NEXT`
+    /// starts from the rows returned by the statement before it.
+    ///
+    /// A statement that fails has no effect on the graph; statements before
+    /// it keep theirs. A transaction left open stays open for the next call.
+    pub fn execute(&mut self, ir: &IR) -> Result<Vec<BindingTable>, RuntimeError> {
+        let types = ir.type_table();
+        let mut results = Vec::new();
+        for statement in &ir.program().statements {
+            let input = match results.last() {
+                Some(previous) if statement.follows_next() => WorkingTable::next(previous),
+                _ => WorkingTable::unit(),
+            };
+            match statement {
+                Statement::Query(statement) => {
+                    let evaluator = Evaluator::new(self.graph, &self.parameters);
+                    results.push(query::query(&evaluator, types, &statement.query, &input)?);
+                }
+                Statement::Mutation(statement) => {
+                    results.push(mutation::data_modifying(
+                        self.graph,
+                        &self.parameters,
+                        types,
+                        &statement.statement,
+                        input,
+                    )?);
+                }
+                Statement::Transaction(statement) => match &statement.command {
+                    TransactionCommand::Start(_) => {
+                        self.transaction = Some(self.graph.clone());
+                    }
+                    TransactionCommand::Commit(_) => self.transaction = None,
+                    TransactionCommand::Rollback(_) => {
+                        if let Some(snapshot) = self.transaction.take() {
+                            *self.graph = snapshot;
+                        }
+                    }
+                },
+                // There is a single graph and no session state to change.
+                Statement::Session(_) | Statement::Empty(_) => {}
+                Statement::Catalog(statement) => {
+                    return Err(RuntimeError::unsupported(
+                        "a catalog statement",
+                        &statement.span,
+                    ));
+                }
+            }
+        }
+        Ok(results)
+    }
+}
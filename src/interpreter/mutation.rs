@@ -0,0 +1,422 @@
+//! Data-modifying statements.
+//!
+//! A data-modifying statement runs like a linear query whose statements may
+//! also change the graph. Expressions are evaluated against the graph as it
+//! is before each change, and a statement that fails leaves the graph as it
+//! was before the statement started.
+
+use std::collections::{BTreeMap, HashMap};
+
+use smol_str::SmolStr;
+
+use super::BindingTable;
+use super::error::{RuntimeError, RuntimeErrorKind};
+use super::eval::{Evaluator, Row, deleted};
+use super::graph::{EdgeId, NodeId, PropertyGraph, add_label};
+use super::query::{self, WorkingTable};
+use super::value::Value;
+use crate::ast::Span;
+use crate::ast::mutation::{
+    DeleteStatement, DetachOption, InsertEdgePattern, InsertElementPattern,
+    InsertElementPatternFiller, InsertStatement, LinearDataModifyingStatement,
+    PrimitiveDataModifyingStatement, RemoveItem, SetItem, SimpleDataAccessingStatement,
+    SimpleDataModifyingStatement,
+};
+use crate::ast::query::ElementPropertySpecification;
+use crate::ir::type_table::TypeTable;
+
+type MutationResult<T> = Result<T, RuntimeError>;
+
+/// Executes a data-modifying statement over `input` and returns its result
+/// table.
+pub(crate) fn data_modifying(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    types: &TypeTable,
+    statement: &LinearDataModifyingStatement,
+    input: WorkingTable,
+) -> MutationResult<BindingTable> {
+    let snapshot = graph.clone();
+    let result = run(graph, parameters, types, statement, input);
+    if result.is_err() {
+        *graph = snapshot;
+    }
+    result
+}
+
+fn run(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    types: &TypeTable,
+    statement: &LinearDataModifyingStatement,
+    mut table: WorkingTable,
+) -> MutationResult<BindingTable> {
+    for statement in &statement.statements {
+        table = match statement {
+            SimpleDataAccessingStatement::Query(statement) => {
+                query::statement(&Evaluator::new(graph, parameters), statement, table)?
+            }
+            SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Primitive(
+                statement,
+            )) => modify(graph, parameters, statement, table)?,
+            SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Call(call)) => {
+                return Err(RuntimeError::unsupported("CALL", &call.span));
+            }
+        };
+    }
+    let evaluator = Evaluator::new(graph, parameters);
+    query::result(
+        &evaluator,
+        types,
+        statement.primitive_result_statement.as_ref(),
+        table,
+    )
+}
+
+fn modify(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    statement: &PrimitiveDataModifyingStatement,
+    mut table: WorkingTable,
+) -> MutationResult<WorkingTable> {
+    match statement {
+        PrimitiveDataModifyingStatement::Insert(insert) => {
+            for row in &mut table.rows {
+                self::insert(graph, parameters, insert, row)?;
+            }
+            for path in &insert.pattern.paths {
+                for element in &path.elements {
+                    if let Some(variable) = filler(element).and_then(|f| f.variable.as_ref()) {
+                        if !table
+                            .variables
+                            .iter()
+                            .any(|(name, _)| *name == variable.variable)
+                        {
+                            table.variables.push((variable.variable.clone(), false));
+                        }
+                    }
+                }
+            }
+        }
+        PrimitiveDataModifyingStatement::Set(set) => {
+            for row in &table.rows {
+                for item in &set.items.items {
+                    set_item(graph, parameters, item, row)?;
+                }
+            }
+        }
+        PrimitiveDataModifyingStatement::Remove(remove) => {
+            for row in &table.rows {
+                for item in &remove.items.items {
+                    remove_item(graph, item, row)?;
+                }
+            }
+        }
+        PrimitiveDataModifyingStatement::Delete(delete) => {
+            self::delete(graph, parameters, delete, &table.rows)?;
+        }
+    }
+    Ok(table)
+}
+
+fn insert(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    insert: &InsertStatement,
+    row: &mut Row,
+) -> MutationResult<()> {
+    for path in &insert.pattern.paths {
+        let mut previous: Option<NodeId> = None;
+        let mut pending: Option<&InsertEdgePattern> = None;
+        for element in &path.elements {
+            match element {
+                InsertElementPattern::Node(node) => {
+                    let id = insert_node(graph, parameters, node.filler.as_ref(), &node.span, row)?;
+                    if let (Some(edge), Some(source)) = (pending.take(), previous) {
+                        insert_edge(graph, parameters, edge, source, id, row)?;
+                    }
+                    previous = Some(id);
+                }
+                InsertElementPattern::Edge(edge) => pending = Some(edge),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the node an insert node pattern refers to: the node its variable
+/// is bound to, or a new node.
+fn insert_node(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    filler: Option<&InsertElementPatternFiller>,
+    span: &Span,
+    row: &mut Row,
+) -> MutationResult<NodeId> {
+    let variable = filler.and_then(|filler| filler.variable.as_ref());
+    if let Some(variable) = variable {
+        match row.get(&variable.variable) {
+            Some(Value::Node(id)) => {
+                graph.node(*id).ok_or_else(|| deleted(span))?;
+                return Ok(*id);
+            }
+            Some(other) => {
+                return Err(RuntimeError::mismatch(
+                    format!(
+                        "cannot insert at '{}', which is bound to {}",
+                        variable.variable,
+                        other.type_of().name()
+                    ),
+                    span,
+                ));
+            }
+            None => {}
+        }
+    }
+
+    let (labels, properties) = element_contents(graph, parameters, filler, row)?;
+    let id = graph.add_node(labels, properties);
+    if let Some(variable) = variable {
+        row.bind(variable.variable.clone(), Value::Node(id));
+    }
+    Ok(id)
+}
+
+fn insert_edge(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    edge: &InsertEdgePattern,
+    left: NodeId,
+    right: NodeId,
+    row: &mut Row,
+) -> MutationResult<EdgeId> {
+    let filler = match edge {
+        InsertEdgePattern::PointingLeft(edge) => edge.filler.as_ref(),
+        InsertEdgePattern::PointingRight(edge) => edge.filler.as_ref(),
+        InsertEdgePattern::Undirected(edge) => edge.filler.as_ref(),
+    };
+    let (labels, properties) = element_contents(graph, parameters, filler, row)?;
+    let id = match edge {
+        InsertEdgePattern::PointingLeft(_) => graph.add_edge(right, left, labels, properties),
+        InsertEdgePattern::PointingRight(_) => graph.add_edge(left, right, labels, properties),
+        InsertEdgePattern::Undirected(_) => {
+            graph.add_undirected_edge(left, right, labels, properties)
+        }
+    };
+    if let Some(variable) = filler.and_then(|filler| filler.variable.as_ref()) {
+        row.bind(variable.variable.clone(), Value::Edge(id));
+    }
+    Ok(id)
+}
+
+/// Evaluates the labels and properties of an inserted element.
+fn element_contents(
+    graph: &PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    filler: Option<&InsertElementPatternFiller>,
+    row: &Row,
+) -> MutationResult<(Vec<SmolStr>, BTreeMap<SmolStr, Value>)> {
+    let Some(filler) = filler else {
+        return Ok((Vec::new(), BTreeMap::new()));
+    };
+    let labels = filler
+        .label_set
+        .as_ref()
+        .map(|label_set| label_set.labels.clone())
+        .unwrap_or_default();
+    let properties = match &filler.properties {
+        Some(properties) => property_values(graph, parameters, properties, row)?,
+        None => BTreeMap::new(),
+    };
+    Ok((labels, properties))
+}
+
+fn property_values(
+    graph: &PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    specification: &ElementPropertySpecification,
+    row: &Row,
+) -> MutationResult<BTreeMap<SmolStr, Value>> {
+    let evaluator = Evaluator::new(graph, parameters);
+    let mut properties = BTreeMap::new();
+    for pair in &specification.properties {
+        let value = property_value(&evaluator, &pair.value, row)?;
+        if !value.is_null() {
+            properties.insert(pair.key.clone(), value);
+        }
+    }
+    Ok(properties)
+}
+
+/// Evaluates a value to be stored as a property. Properties hold values,
+/// not references to graph elements.
+fn property_value(
+    evaluator: &Evaluator<'_>,
+    expression: &crate::ast::Expression,
+    row: &Row,
+) -> MutationResult<Value> {
+    let value = evaluator.evaluate(expression, row)?;
+    if value.to_constant().is_none() {
+        return Err(RuntimeError::mismatch(
+            format!("a property cannot hold {}", value.type_of().name()),
+            &expression.span(),
+        ));
+    }
+    Ok(value)
+}
+
+/// Returns the node or edge bound to `variable`, or `None` if it is NULL.
+fn target(row: &Row, variable: &SmolStr, span: &Span) -> MutationResult<Option<Value>> {
+    match row.get(variable) {
+        None => Err(RuntimeError::new(
+            RuntimeErrorKind::UnboundVariable,
+            format!("variable '{variable}' is not bound"),
+            span,
+        )),
+        Some(Value::Null) => Ok(None),
+        Some(value @ (Value::Node(_) | Value::Edge(_))) => Ok(Some(value.clone())),
+        Some(other) => Err(RuntimeError::mismatch(
+            format!("expected a node or edge, found {}", other.type_of().name()),
+            span,
+        )),
+    }
+}
+
+/// Returns the labels and properties of a node or edge for modification.
+fn contents<'g>(
+    graph: &'g mut PropertyGraph,
+    element: &Value,
+    span: &Span,
+) -> MutationResult<(&'g mut Vec<SmolStr>, &'g mut BTreeMap<SmolStr, Value>)> {
+    match element {
+        Value::Node(id) => {
+            let node = graph.node_mut(*id).ok_or_else(|| deleted(span))?;
+            Ok((&mut node.labels, &mut node.properties))
+        }
+        Value::Edge(id) => {
+            let edge = graph.edge_mut(*id).ok_or_else(|| deleted(span))?;
+            Ok((&mut edge.labels, &mut edge.properties))
+        }
+        _ => unreachable!("targets are nodes or edges"),
+    }
+}
+
+fn set_item(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    item: &SetItem,
+    row: &Row,
+) -> MutationResult<()> {
+    match item {
+        SetItem::Property(item) => {
+            let Some(element) = target(row, &item.element, &item.span)? else {
+                return Ok(());
+            };
+            let value = property_value(&Evaluator::new(graph, parameters), &item.value, row)?;
+            let (_, properties) = contents(graph, &element, &item.span)?;
+            if value.is_null() {
+                properties.remove(&item.property);
+            } else {
+                properties.insert(item.property.clone(), value);
+            }
+        }
+        SetItem::AllProperties(item) => {
+            let Some(element) = target(row, &item.element, &item.span)? else {
+                return Ok(());
+            };
+            let values = property_values(graph, parameters, &item.properties, row)?;
+            let (_, properties) = contents(graph, &element, &item.span)?;
+            *properties = values;
+        }
+        SetItem::Label(item) => {
+            let Some(element) = target(row, &item.element, &item.span)? else {
+                return Ok(());
+            };
+            let (labels, _) = contents(graph, &element, &item.span)?;
+            add_label(labels, &item.label);
+        }
+    }
+    Ok(())
+}
+
+fn remove_item(graph: &mut PropertyGraph, item: &RemoveItem, row: &Row) -> MutationResult<()> {
+    match item {
+        RemoveItem::Property(item) => {
+            if let Some(element) = target(row, &item.element, &item.span)? {
+                contents(graph, &element, &item.span)?
+                    .1
+                    .remove(&item.property);
+            }
+        }
+        RemoveItem::Label(item) => {
+            if let Some(element) = target(row, &item.element, &item.span)? {
+                contents(graph, &element, &item.span)?
+                    .0
+                    .retain(|label| *label != item.label);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deletes the elements the items evaluate to in any row. Edges go first, so
+/// a node can be deleted together with all of its edges without DETACH.
+fn delete(
+    graph: &mut PropertyGraph,
+    parameters: &HashMap<SmolStr, Value>,
+    delete: &DeleteStatement,
+    rows: &[Row],
+) -> MutationResult<()> {
+    let mut nodes: Vec<(NodeId, &Span)> = Vec::new();
+    let mut edges: Vec<EdgeId> = Vec::new();
+    let evaluator = Evaluator::new(graph, parameters);
+    for row in rows {
+        for item in &delete.items.items {
+            match evaluator.evaluate(&item.expression, row)? {
+                Value::Null => {}
+                Value::Node(id) => {
+                    evaluator.node(id, &item.span)?;
+                    if !nodes.iter().any(|(node, _)| *node == id) {
+                        nodes.push((id, &item.span));
+                    }
+                }
+                Value::Edge(id) => {
+                    evaluator.edge(id, &item.span)?;
+                    if !edges.contains(&id) {
+                        edges.push(id);
+                    }
+                }
+                other => {
+                    return Err(RuntimeError::mismatch(
+                        format!("cannot delete {}", other.type_of().name()),
+                        &item.span,
+                    ));
+                }
+            }
+        }
+    }
+
+    for edge in edges {
+        graph.remove_edge(edge);
+    }
+    for (node, span) in nodes {
+        if delete.detach_option != DetachOption::Detach && !graph.incident_edges(node).is_empty() {
+            return Err(RuntimeError::new(
+                RuntimeErrorKind::ConstraintViolation,
+                "cannot delete a node that still has edges; use DETACH DELETE",
+                span,
+            ));
+        }
+        graph.remove_node(node);
+    }
+    Ok(())
+}
+
+fn filler(element: &InsertElementPattern) -> Option<&InsertElementPatternFiller> {
+    match element {
+        InsertElementPattern::Node(node) => node.filler.as_ref(),
+        InsertElementPattern::Edge(InsertEdgePattern::PointingLeft(edge)) => edge.filler.as_ref(),
+        InsertElementPattern::Edge(InsertEdgePattern::PointingRight(edge)) => edge.filler.as_ref(),
+        InsertElementPattern::Edge(InsertEdgePattern::Undirected(edge)) => edge.filler.as_ref(),
+    }
+}
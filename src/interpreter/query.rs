@@ -0,0 +1,813 @@
+//! Query execution.
+//!
+//! A linear query threads a working table through its statements, starting
+//! from a single row without bindings, or after `NEXT` from the rows the
+//! statement before returned, and its result statement turns the final
+//! working table into a [`BindingTable`]. Composite queries combine the
+//! tables of their operands.
+
+use std::cmp::Ordering;
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use super::error::RuntimeError;
+use super::eval::{Evaluator, Row, column_name};
+use super::matching::CompiledPattern;
+use super::value::{Value, rows_not_distinct, sort_order};
+use super::{BindingTable, Column};
+use crate::ast::Expression;
+use crate::ast::query::{
+    ForOrdinalityOrOffset, GraphPattern, GroupingElement, LinearQuery, MatchStatement,
+    NullOrdering, OptionalOperand, OrderByAndPageStatement, OrderingSpecification,
+    PrimitiveQueryStatement, PrimitiveResultStatement, Query, ReturnItemList, ReturnStatement,
+    SetOperator, SetQuantifier, SortSpecification,
+};
+use crate::ast::visit::{Visit, walk_expression};
+use crate::ir::type_table::{Type, TypeTable};
+
+type QueryResult<T> = Result<T, RuntimeError>;
+
+/// Rows flowing between the statements of a linear query.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WorkingTable {
+    /// Variables in order of declaration, with whether they are group
+    /// variables.
+    pub(crate) variables: Vec<(SmolStr, bool)>,
+    pub(crate) rows: Vec<Row>,
+}
+
+impl WorkingTable {
+    /// The table a query starts from: one row without bindings.
+    pub(crate) fn unit() -> Self {
+        WorkingTable {
+            variables: Vec::new(),
+            rows: vec![Row::default()],
+        }
+    }
+
+    /// The table a statement after `NEXT` starts from: the columns returned
+    /// by the statement before it, or the unit table if it returned none.
+    pub(crate) fn next(previous: &BindingTable) -> Self {
+        if previous.columns.is_empty() {
+            return Self::unit();
+        }
+        let rows = previous
+            .rows
+            .iter()
+            .map(|values| {
+                let mut row = Row::default();
+                for (column, value) in previous.columns.iter().zip(values) {
+                    row.bind(column.name.clone(), value.clone());
+                }
+                row
+            })
+            .collect();
+        WorkingTable {
+            variables: previous
+                .columns
+                .iter()
+                .map(|column| (column.name.clone(), false))
+                .collect(),
+            rows,
+        }
+    }
+
+    fn declare(&mut self, name: &SmolStr, group: bool) {
+        match self
+            .variables
+            .iter_mut()
+            .find(|(declared, _)| declared == name)
+        {
+            Some((_, declared_group)) => *declared_group = group,
+            None => self.variables.push((name.clone(), group)),
+        }
+    }
+}
+
+/// Executes a query over `input` and returns its result table.
+pub(crate) fn query(
+    evaluator: &Evaluator<'_>,
+    types: &TypeTable,
+    query: &Query,
+    input: &WorkingTable,
+) -> QueryResult<BindingTable> {
+    match query {
+        Query::Linear(linear) => linear_query(evaluator, types, linear, input),
+        Query::Parenthesized(inner, _) => self::query(evaluator, types, inner, input),
+        Query::Composite(composite) => {
+            let left = self::query(evaluator, types, &composite.left, input)?;
+            // OTHERWISE only evaluates its right operand if needed.
+            if composite.operator == SetOperator::Otherwise && !left.is_empty() {
+                return Ok(left);
+            }
+            let right = self::query(evaluator, types, &composite.right, input)?;
+            Ok(set_operation(&composite.operator, left, right))
+        }
+    }
+}
+
+fn linear_query(
+    evaluator: &Evaluator<'_>,
+    types: &TypeTable,
+    query: &LinearQuery,
+    input: &WorkingTable,
+) -> QueryResult<BindingTable> {
+    let mut table = input.clone();
+    for statement in &query.primitive_statements {
+        table = self::statement(evaluator, statement, table)?;
+    }
+    result(evaluator, types, query.result_statement.as_deref(), table)
+}
+
+/// Executes one query statement over a working table.
+pub(crate) fn statement(
+    evaluator: &Evaluator<'_>,
+    statement: &PrimitiveQueryStatement,
+    mut table: WorkingTable,
+) -> QueryResult<WorkingTable> {
+    match statement {
+        PrimitiveQueryStatement::Match(statement) => match_statement(evaluator, statement, table),
+        PrimitiveQueryStatement::Filter(filter) => {
+            let mut rows = Vec::new();
+            for row in table.rows {
+                if evaluator.condition(&filter.condition, &row)? {
+                    rows.push(row);
+                }
+            }
+            table.rows = rows;
+            Ok(table)
+        }
+        PrimitiveQueryStatement::Let(statement) => {
+            for binding in &statement.bindings {
+                for row in &mut table.rows {
+                    let value = evaluator.evaluate(&binding.value, row)?;
+                    row.bind(binding.variable.name.clone(), value);
+                }
+                table.declare(&binding.variable.name, false);
+            }
+            Ok(table)
+        }
+        PrimitiveQueryStatement::For(statement) => {
+            let item = &statement.item;
+            let mut rows = Vec::new();
+            for row in &table.rows {
+                let elements = match evaluator.evaluate(&item.collection, row)? {
+                    Value::Null => Vec::new(),
+                    Value::List(elements) => elements,
+                    other => {
+                        return Err(RuntimeError::mismatch(
+                            format!("FOR expects a list, found {}", other.type_of().name()),
+                            &item.collection.span(),
+                        ));
+                    }
+                };
+                for (index, element) in elements.into_iter().enumerate() {
+                    let mut row = row.clone();
+                    row.bind(item.binding_variable.name.clone(), element);
+                    match &statement.ordinality_or_offset {
+                        Some(ForOrdinalityOrOffset::Ordinality { variable }) => {
+                            row.bind(variable.name.clone(), Value::Integer(index as i64 + 1));
+                        }
+                        Some(ForOrdinalityOrOffset::Offset { variable }) => {
+                            row.bind(variable.name.clone(), Value::Integer(index as i64));
+                        }
+                        None => {}
+                    }
+                    rows.push(row);
+                }
+            }
+            table.rows = rows;
+            table.declare(&item.binding_variable.name, false);
+            if let Some(
+                ForOrdinalityOrOffset::Ordinality { variable }
+                | ForOrdinalityOrOffset::Offset { variable },
+            ) = &statement.ordinality_or_offset
+            {
+                table.declare(&variable.name, false);
+            }
+            Ok(table)
+        }
+        PrimitiveQueryStatement::OrderByAndPage(statement) => {
+            let rows = std::mem::take(&mut table.rows)
+                .into_iter()
+                .map(|row| (row, None, ()))
+                .collect();
+            table.rows = order_and_page(evaluator, statement, rows)?
+                .into_iter()
+                .map(|(row, _, _)| row)
+                .collect();
+            Ok(table)
+        }
+        PrimitiveQueryStatement::Call(call) => Err(RuntimeError::unsupported("CALL", &call.span)),
+        PrimitiveQueryStatement::Select(_) => {
+            Err(RuntimeError::unsupported("SELECT", statement.span()))
+        }
+    }
+}
+
+fn match_statement(
+    evaluator: &Evaluator<'_>,
+    statement: &MatchStatement,
+    mut table: WorkingTable,
+) -> QueryResult<WorkingTable> {
+    match statement {
+        MatchStatement::Simple(simple) => {
+            let pattern = CompiledPattern::compile(&simple.pattern)?;
+            let mut rows = Vec::new();
+            for row in &table.rows {
+                rows.extend(pattern.matches(evaluator, row)?);
+            }
+            table.rows = rows;
+            for (name, group) in pattern.variables() {
+                table.declare(&name, group);
+            }
+            Ok(table)
+        }
+        MatchStatement::Optional(optional) => {
+            let statements = match &optional.operand {
+                OptionalOperand::Match { pattern } => {
+                    return optional_match(
+                        evaluator,
+                        std::slice::from_ref(pattern.as_ref()),
+                        table,
+                    );
+                }
+                OptionalOperand::Block { statements }
+                | OptionalOperand::ParenthesizedBlock { statements } => statements,
+            };
+            let mut variables = Vec::new();
+            collect_variables(statements, &mut variables)?;
+            let mut rows = Vec::new();
+            for row in &table.rows {
+                let mut block = WorkingTable {
+                    variables: table.variables.clone(),
+                    rows: vec![row.clone()],
+                };
+                for statement in statements {
+                    block = match_statement(evaluator, statement, block)?;
+                }
+                if block.rows.is_empty() {
+                    rows.push(pad(row, &variables));
+                } else {
+                    rows.extend(block.rows);
+                }
+            }
+            table.rows = rows;
+            for (name, group) in variables {
+                table.declare(&name, group);
+            }
+            Ok(table)
+        }
+    }
+}
+
+fn optional_match(
+    evaluator: &Evaluator<'_>,
+    patterns: &[GraphPattern],
+    mut table: WorkingTable,
+) -> QueryResult<WorkingTable> {
+    let pattern = CompiledPattern::compile(&patterns[0])?;
+    let variables = pattern.variables();
+    let mut rows = Vec::new();
+    for row in &table.rows {
+        let matches = pattern.matches(evaluator, row)?;
+        if matches.is_empty() {
+            rows.push(pad(row, &variables));
+        } else {
+            rows.extend(matches);
+        }
+    }
+    table.rows = rows;
+    for (name, group) in variables {
+        table.declare(&name, group);
+    }
+    Ok(table)
+}
+
+/// Binds the variables an OPTIONAL operand would have bound to NULL.
+fn pad(row: &Row, variables: &[(SmolStr, bool)]) -> Row {
+    let mut row = row.clone();
+    for (name, _) in variables {
+        if row.get(name).is_none() {
+            row.bind(name.clone(), Value::Null);
+        }
+    }
+    row
+}
+
+fn collect_variables(
+    statements: &[MatchStatement],
+    variables: &mut Vec<(SmolStr, bool)>,
+) -> QueryResult<()> {
+    for statement in statements {
+        match statement {
+            MatchStatement::Simple(simple) => {
+                variables.extend(CompiledPattern::compile(&simple.pattern)?.variables());
+            }
+            MatchStatement::Optional(optional) => match &optional.operand {
+                OptionalOperand::Match { pattern } => {
+                    variables.extend(CompiledPattern::compile(pattern)?.variables());
+                }
+                OptionalOperand::Block { statements }
+                | OptionalOperand::ParenthesizedBlock { statements } => {
+                    collect_variables(statements, variables)?;
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Turns the final working table of a linear query into its result.
+pub(crate) fn result(
+    evaluator: &Evaluator<'_>,
+    types: &TypeTable,
+    statement: Option<&PrimitiveResultStatement>,
+    table: WorkingTable,
+) -> QueryResult<BindingTable> {
+    match statement {
+        Some(PrimitiveResultStatement::Return(statement)) => {
+            return_statement(evaluator, types, statement, table)
+        }
+        Some(PrimitiveResultStatement::Finish(_)) | None => Ok(BindingTable::default()),
+    }
+}
+
+/// An entry to sort: the row sort keys are evaluated against, the rows of
+/// its group if grouped, and a payload.
+type Sorted<T> = (Row, Option<Vec<Row>>, T);
+
+fn return_statement(
+    evaluator: &Evaluator<'_>,
+    types: &TypeTable,
+    statement: &ReturnStatement,
+    table: WorkingTable,
+) -> QueryResult<BindingTable> {
+    let items: Vec<(SmolStr, Option<&Expression>)> = match &statement.items {
+        ReturnItemList::Star => table
+            .variables
+            .iter()
+            .map(|(name, _)| (name.clone(), None))
+            .collect(),
+        ReturnItemList::Items { items } => items
+            .iter()
+            .map(|item| {
+                let name = column_name(&item.expression, item.alias.as_ref());
+                (name, Some(&item.expression))
+            })
+            .collect(),
+    };
+
+    let is_group = |name: &str| {
+        table
+            .variables
+            .iter()
+            .any(|(declared, group)| *group && declared == name)
+    };
+    let grouped = statement.group_by.is_some()
+        || items.iter().any(|(_, expression)| {
+            expression.is_some_and(|e| has_vertical_aggregate(e, &is_group))
+        });
+
+    let mut projected: Vec<Sorted<Vec<Value>>> = Vec::new();
+    if grouped {
+        let keys: Vec<&Expression> = match &statement.group_by {
+            Some(group_by) => group_by
+                .elements
+                .iter()
+                .filter_map(|element| match element {
+                    GroupingElement::Expression(expression) => {
+                        Some(grouping_key(expression, &table.variables, &items))
+                    }
+                    GroupingElement::EmptyGroupingSet => None,
+                })
+                .collect(),
+            // Without GROUP BY, the items without aggregates are the keys.
+            None => items
+                .iter()
+                .filter_map(|(_, expression)| *expression)
+                .filter(|expression| !has_vertical_aggregate(expression, &is_group))
+                .collect(),
+        };
+        let mut groups: Vec<(Vec<Value>, Vec<Row>)> = Vec::new();
+        for row in table.rows {
+            let key = keys
+                .iter()
+                .map(|key| evaluator.evaluate(key, &row))
+                .collect::<QueryResult<Vec<_>>>()?;
+            match groups
+                .iter_mut()
+                .find(|(seen, _)| rows_not_distinct(seen, &key))
+            {
+                Some((_, rows)) => rows.push(row),
+                None => groups.push((key, vec![row])),
+            }
+        }
+        // Aggregating an empty table without grouping keys yields one row.
+        if groups.is_empty() && keys.is_empty() {
+            groups.push((Vec::new(), Vec::new()));
+        }
+        for (_, rows) in groups {
+            let representative = rows.first().cloned().unwrap_or_default();
+            let mut values = Vec::with_capacity(items.len());
+            for (name, expression) in &items {
+                values.push(match expression {
+                    Some(expression) => {
+                        evaluator.evaluate_grouped(expression, &representative, &rows)?
+                    }
+                    None => representative.get(name).cloned().unwrap_or(Value::Null),
+                });
+            }
+            let context = with_columns(representative, &items, &values);
+            projected.push((context, Some(rows), values));
+        }
+    } else {
+        for row in table.rows {
+            let mut values = Vec::with_capacity(items.len());
+            for (name, expression) in &items {
+                values.push(match expression {
+                    Some(expression) => evaluator.evaluate(expression, &row)?,
+                    None => row.get(name).cloned().unwrap_or(Value::Null),
+                });
+            }
+            let context = with_columns(row, &items, &values);
+            projected.push((context, None, values));
+        }
+    }
+
+    if statement.quantifier == Some(SetQuantifier::Distinct) {
+        let mut distinct: Vec<Sorted<Vec<Value>>> = Vec::new();
+        for entry in projected {
+            if !distinct
+                .iter()
+                .any(|(_, _, seen)| rows_not_distinct(seen, &entry.2))
+            {
+                distinct.push(entry);
+            }
+        }
+        projected = distinct;
+    }
+
+    if let Some(order_by_and_page) = &statement.order_by_and_page {
+        projected = order_and_page(evaluator, order_by_and_page, projected)?;
+    }
+    let projected: Vec<Vec<Value>> = projected.into_iter().map(|(_, _, values)| values).collect();
+
+    let columns = items
+        .iter()
+        .enumerate()
+        .map(|(index, (name, expression))| Column {
+            name: name.clone(),
+            ty: column_type(types, *expression, projected.iter().map(|row| &row[index])),
+        })
+        .collect();
+    Ok(BindingTable {
+        columns,
+        rows: projected,
+    })
+}
+
+/// Binds the projected columns by name, so ORDER BY can refer to aliases.
+fn with_columns(mut row: Row, items: &[(SmolStr, Option<&Expression>)], values: &[Value]) -> Row {
+    for ((name, _), value) in items.iter().zip(values) {
+        row.bind(name.clone(), value.clone());
+    }
+    row
+}
+
+/// Resolves a grouping key that names a return item alias rather than a
+/// variable of the working table to the item's expression.
+fn grouping_key<'a>(
+    key: &'a Expression,
+    variables: &[(SmolStr, bool)],
+    items: &[(SmolStr, Option<&'a Expression>)],
+) -> &'a Expression {
+    let Expression::VariableReference(name, _) = key else {
+        return key;
+    };
+    if variables.iter().any(|(declared, _)| declared == name) {
+        return key;
+    }
+    items
+        .iter()
+        .find_map(|(alias, expression)| expression.filter(|_| alias == name))
+        .unwrap_or(key)
+}
+
+/// Sorts `rows` and applies OFFSET and LIMIT. Sort keys are evaluated
+/// against the row of each entry, with aggregates ranging over its group if
+/// it has one; the payload travels along.
+fn order_and_page<T>(
+    evaluator: &Evaluator<'_>,
+    statement: &OrderByAndPageStatement,
+    rows: Vec<Sorted<T>>,
+) -> QueryResult<Vec<Sorted<T>>> {
+    let mut rows = rows;
+    if let Some(order_by) = &statement.order_by {
+        let specifications = &order_by.sort_specifications;
+        let mut keyed = Vec::with_capacity(rows.len());
+        for entry in rows {
+            let (row, group, _) = &entry;
+            let keys = specifications
+                .iter()
+                .map(|specification| match group {
+                    Some(group) => evaluator.evaluate_grouped(&specification.key, row, group),
+                    None => evaluator.evaluate(&specification.key, row),
+                })
+                .collect::<QueryResult<Vec<_>>>()?;
+            keyed.push((keys, entry));
+        }
+        keyed.sort_by(|(a, _), (b, _)| {
+            specifications
+                .iter()
+                .zip(a.iter().zip(b))
+                .map(|(specification, (a, b))| compare_keys(specification, a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        rows = keyed.into_iter().map(|(_, row)| row).collect();
+    }
+
+    let unit = Row::default();
+    let offset = match &statement.offset {
+        Some(offset) => evaluator.count(&offset.count, &unit)?,
+        None => 0,
+    };
+    let limit = match &statement.limit {
+        Some(limit) => evaluator.count(&limit.count, &unit)?,
+        None => usize::MAX,
+    };
+    Ok(rows.into_iter().skip(offset).take(limit).collect())
+}
+
+/// Orders two sort keys. NULL sorts after every other value unless NULLS
+/// FIRST is given, and the direction applies to non-NULL values only.
+fn compare_keys(specification: &SortSpecification, a: &Value, b: &Value) -> Ordering {
+    let descending = specification.ordering == Some(OrderingSpecification::Descending);
+    let nulls_first = match specification.null_ordering {
+        Some(NullOrdering::NullsFirst) => true,
+        Some(NullOrdering::NullsLast) => false,
+        None => descending,
+    };
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) if nulls_first => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if nulls_first => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) if descending => sort_order(b, a),
+        (false, false) => sort_order(a, b),
+    }
+}
+
+/// Returns true if `expression` aggregates over the rows of a group rather
+/// than over the elements of a group variable.
+fn has_vertical_aggregate(expression: &Expression, is_group: &dyn Fn(&str) -> bool) -> bool {
+    struct Finder<'f> {
+        is_group: &'f dyn Fn(&str) -> bool,
+    }
+
+    impl Visit for Finder<'_> {
+        type Break = ();
+
+        fn visit_expression(&mut self, expression: &Expression) -> ControlFlow<Self::Break> {
+            if let Expression::AggregateFunction(_) = expression {
+                let info = crate::analysis::ExpressionInfo::analyze(expression);
+                if !info
+                    .variable_references
+                    .iter()
+                    .any(|name| (self.is_group)(name))
+                {
+                    return ControlFlow::Break(());
+                }
+                return ControlFlow::Continue(());
+            }
+            walk_expression(self, expression)
+        }
+    }
+
+    Finder { is_group }.visit_expression(expression).is_break()
+}
+
+/// Infers the type of a result column: from the type table where the
+/// validator recorded one, otherwise from the values.
+fn column_type<'v>(
+    types: &TypeTable,
+    expression: Option<&Expression>,
+    values: impl Iterator<Item = &'v Value>,
+) -> Type {
+    let recorded = expression.and_then(|expression| types.get_type_by_span(&expression.span()));
+    if let Some(recorded) = recorded.filter(|ty| **ty != Type::Any) {
+        return recorded.clone();
+    }
+    let observed: Vec<Type> = values
+        .filter(|value| !value.is_null())
+        .map(Value::type_of)
+        .collect();
+    Type::common_supertype(&observed).unwrap_or(Type::Any)
+}
+
+/// Combines the results of the operands of a composite query. Rows of the
+/// right operand are aligned to the columns of the left by name.
+fn set_operation(operator: &SetOperator, left: BindingTable, right: BindingTable) -> BindingTable {
+    let positions: Vec<Option<usize>> = left
+        .columns
+        .iter()
+        .map(|column| right.column(&column.name))
+        .collect();
+    let aligned: Vec<Vec<Value>> = right
+        .rows
+        .iter()
+        .map(|row| {
+            positions
+                .iter()
+                .enumerate()
+                .map(|(index, position)| {
+                    row.get(position.unwrap_or(index))
+                        .cloned()
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        })
+        .collect();
+    let columns =
+        left.columns
+            .iter()
+            .zip(&positions)
+            .map(|(column, position)| {
+                let right_type = position.map(|position| right.columns[position].ty.clone());
+                let ty = match right_type {
+                    Some(right_type) => Type::common_supertype(&[column.ty.clone(), right_type])
+                        .unwrap_or(Type::Any),
+                    None => column.ty.clone(),
+                };
+                Column {
+                    name: column.name.clone(),
+                    ty,
+                }
+            })
+            .collect();
+
+    let contains = |rows: &[Vec<Value>], row: &[Value]| {
+        rows.iter()
+            .position(|candidate| rows_not_distinct(candidate, row))
+    };
+    let rows = match operator {
+        SetOperator::Union { quantifier } => {
+            let mut rows = left.rows;
+            rows.extend(aligned);
+            if *quantifier == SetQuantifier::Distinct {
+                rows = distinct(rows);
+            }
+            rows
+        }
+        SetOperator::Except { quantifier } => match quantifier {
+            SetQuantifier::All => {
+                let mut remaining = aligned;
+                let mut rows = Vec::new();
+                for row in left.rows {
+                    match contains(&remaining, &row) {
+                        Some(index) => {
+                            remaining.swap_remove(index);
+                        }
+                        None => rows.push(row),
+                    }
+                }
+                rows
+            }
+            SetQuantifier::Distinct => distinct(left.rows)
+                .into_iter()
+                .filter(|row| contains(&aligned, row).is_none())
+                .collect(),
+        },
+        SetOperator::Intersect { quantifier } => match quantifier {
+            SetQuantifier::All => {
+                let mut remaining = aligned;
+                let mut rows = Vec::new();
+                for row in left.rows {
+                    if let Some(index) = contains(&remaining, &row) {
+                        remaining.swap_remove(index);
+                        rows.push(row);
+                    }
+                }
+                rows
+            }
+            SetQuantifier::Distinct => distinct(left.rows)
+                .into_iter()
+                .filter(|row| contains(&aligned, row).is_some())
+                .collect(),
+        },
+        SetOperator::Otherwise => {
+            if left.rows.is_empty() {
+                aligned
+            } else {
+                left.rows
+            }
+        }
+    };
+    BindingTable { columns, rows }
+}
+
+fn distinct(rows: Vec<Vec<Value>>) -> Vec<Vec<Value>> {
+    let mut kept: Vec<Vec<Value>> = Vec::new();
+    for row in rows {
+        if !kept.iter().any(|seen| rows_not_distinct(seen, &row)) {
+            kept.push(row);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(columns: [(&str, Type); 2], rows: Vec<Vec<Value>>) -> BindingTable {
+        BindingTable {
+            columns: columns
+                .into_iter()
+                .map(|(name, ty)| Column {
+                    name: name.into(),
+                    ty,
+                })
+                .collect(),
+            rows,
+        }
+    }
+
+    fn row(a: i64, b: &str) -> Vec<Value> {
+        vec![Value::from(a), Value::from(b)]
+    }
+
+    #[test]
+    fn test_set_operations_align_right_columns_by_name() {
+        let left = table(
+            [("a", Type::Int), ("b", Type::String)],
+            vec![row(1, "x"), row(1, "x"), row(2, "y")],
+        );
+        let right = table(
+            [("b", Type::String), ("a", Type::Int)],
+            vec![vec![Value::from("x"), Value::from(1)]],
+        );
+        let combine = |operator| set_operation(&operator, left.clone(), right.clone()).rows;
+
+        assert_eq!(
+            combine(SetOperator::Union {
+                quantifier: SetQuantifier::All
+            }),
+            vec![row(1, "x"), row(1, "x"), row(2, "y"), row(1, "x")]
+        );
+        assert_eq!(
+            combine(SetOperator::Union {
+                quantifier: SetQuantifier::Distinct
+            }),
+            vec![row(1, "x"), row(2, "y")]
+        );
+        assert_eq!(
+            combine(SetOperator::Except {
+                quantifier: SetQuantifier::All
+            }),
+            vec![row(1, "x"), row(2, "y")]
+        );
+        assert_eq!(
+            combine(SetOperator::Except {
+                quantifier: SetQuantifier::Distinct
+            }),
+            vec![row(2, "y")]
+        );
+        assert_eq!(
+            combine(SetOperator::Intersect {
+                quantifier: SetQuantifier::All
+            }),
+            vec![row(1, "x")]
+        );
+        assert_eq!(combine(SetOperator::Otherwise), left.rows);
+
+        let empty = BindingTable {
+            rows: Vec::new(),
+            ..left
+        };
+        let result = set_operation(&SetOperator::Otherwise, empty, right);
+        assert_eq!(result.rows, vec![row(1, "x")]);
+    }
+
+    #[test]
+    fn test_working_table_declares_each_variable_once() {
+        let mut table = WorkingTable::unit();
+        table.declare(&"n".into(), false);
+        table.declare(&"e".into(), false);
+        table.declare(&"e".into(), true);
+        assert_eq!(
+            table.variables,
+            vec![("n".into(), false), ("e".into(), true)]
+        );
+        assert_eq!(table.rows, vec![Row::default()]);
+
+        let mut bound = Row::default();
+        bound.bind("n".into(), Value::from(1));
+        let padded = pad(&bound, &table.variables);
+        assert_eq!(padded.get("n"), Some(&Value::from(1)));
+        assert_eq!(padded.get("e"), Some(&Value::Null));
+    }
+}
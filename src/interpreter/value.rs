@@ -0,0 +1,295 @@
+//! Runtime values.
+//!
+//! [`Value`] extends the constant values of [`const_eval`] with references to
+//! graph elements. Operators on scalar values are delegated to the constant
+//! evaluator so that both agree on NULL propagation, overflow and casts.
+//!
+//! [`const_eval`]: crate::analysis::const_eval
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use smol_str::SmolStr;
+
+use super::graph::{EdgeId, NodeId, Path};
+use crate::analysis::const_eval::{self, Value as Constant};
+use crate::ast::literal::{DateValue, DatetimeValue, DecimalValue, DurationValue, TimeValue};
+use crate::ir::type_table::Type;
+
+/// A value produced by the interpreter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// NULL, including the boolean UNKNOWN.
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Decimal(DecimalValue),
+    Float(f64),
+    String(SmolStr),
+    Bytes(Vec<u8>),
+    Date(DateValue),
+    Time(TimeValue),
+    Datetime(DatetimeValue),
+    Duration(DurationValue),
+    List(Vec<Value>),
+    Record(Vec<(SmolStr, Value)>),
+    Node(NodeId),
+    Edge(EdgeId),
+    Path(Path),
+}
+
+impl Value {
+    /// Returns true for NULL.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Converts the value to a constant value, or returns `None` if it
+    /// contains a graph element.
+    pub fn to_constant(&self) -> Option<Constant> {
+        Some(match self {
+            Value::Null => Constant::Null,
+            Value::Boolean(b) => Constant::Boolean(*b),
+            Value::Integer(i) => Constant::Integer(*i),
+            Value::Decimal(d) => Constant::Decimal(*d),
+            Value::Float(f) => Constant::Float(*f),
+            Value::String(s) => Constant::String(s.clone()),
+            Value::Bytes(b) => Constant::Bytes(b.clone()),
+            Value::Date(d) => Constant::Date(*d),
+            Value::Time(t) => Constant::Time(*t),
+            Value::Datetime(dt) => Constant::Datetime(*dt),
+            Value::Duration(d) => Constant::Duration(*d),
+            Value::List(items) => Constant::List(
+                items
+                    .iter()
+                    .map(Value::to_constant)
+                    .collect::<Option<_>>()?,
+            ),
+            Value::Record(fields) => Constant::Record(
+                fields
+                    .iter()
+                    .map(|(name, value)| Some((name.clone(), value.to_constant()?)))
+                    .collect::<Option<_>>()?,
+            ),
+            Value::Node(_) | Value::Edge(_) | Value::Path(_) => return None,
+        })
+    }
+
+    /// Returns the static type of this value. Element types carry no labels.
+    pub fn type_of(&self) -> Type {
+        match self {
+            Value::Node(_) => Type::Node(None),
+            Value::Edge(_) => Type::Edge(None),
+            Value::Path(_) => Type::Path,
+            Value::List(items) => {
+                let types: Vec<Type> = items.iter().map(Value::type_of).collect();
+                let element = Type::common_supertype(&types).unwrap_or(Type::Any);
+                Type::List(Box::new(element))
+            }
+            Value::Record(fields) => Type::Record(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.type_of()))
+                    .collect(),
+            ),
+            other => other
+                .to_constant()
+                .map_or(Type::Any, |constant| constant.type_of()),
+        }
+    }
+}
+
+impl From<Constant> for Value {
+    fn from(value: Constant) -> Self {
+        match value {
+            Constant::Null => Value::Null,
+            Constant::Boolean(b) => Value::Boolean(b),
+            Constant::Integer(i) => Value::Integer(i),
+            Constant::Decimal(d) => Value::Decimal(d),
+            Constant::Float(f) => Value::Float(f),
+            Constant::String(s) => Value::String(s),
+            Constant::Bytes(b) => Value::Bytes(b),
+            Constant::Date(d) => Value::Date(d),
+            Constant::Time(t) => Value::Time(t),
+            Constant::Datetime(dt) => Value::Datetime(dt),
+            Constant::Duration(d) => Value::Duration(d),
+            Constant::List(items) => Value::List(items.into_iter().map(Value::from).collect()),
+            Constant::Record(fields) => Value::Record(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.into())
+    }
+}
+
+impl From<SmolStr> for Value {
+    fn from(value: SmolStr) -> Self {
+        Value::String(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Node(id) => write!(f, "({})", id.index()),
+            Value::Edge(id) => write!(f, "[{}]", id.index()),
+            Value::Path(path) => write!(f, "{path}"),
+            Value::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            Value::Record(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                f.write_str("}")
+            }
+            other => match other.to_constant() {
+                Some(constant) => write!(f, "{constant}"),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+/// Returns true if two values are not distinct: NULLs are not distinct
+/// from each other, and other values are not distinct if they are equal.
+/// This is the equivalence used by DISTINCT, GROUP BY and set operators.
+pub(crate) fn not_distinct(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Null, Value::Null) => true,
+        (Value::Null, _) | (_, Value::Null) => false,
+        (Value::Node(a), Value::Node(b)) => a == b,
+        (Value::Edge(a), Value::Edge(b)) => a == b,
+        (Value::Path(a), Value::Path(b)) => a == b,
+        (Value::List(a), Value::List(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| not_distinct(x, y))
+        }
+        (Value::Record(a), Value::Record(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|((m, x), (n, y))| m == n && not_distinct(x, y))
+        }
+        _ => match (left.to_constant(), right.to_constant()) {
+            (Some(a), Some(b)) => a == b || const_eval::ordering(&a, &b) == Some(Ordering::Equal),
+            _ => false,
+        },
+    }
+}
+
+/// Returns true if two rows of values are not distinct.
+pub(crate) fn rows_not_distinct(left: &[Value], right: &[Value]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).all(|(x, y)| not_distinct(x, y))
+}
+
+/// Orders two non-NULL values for sorting. Comparable values use their
+/// natural order; otherwise values are ordered by kind, so that sorting a
+/// mixed column is still deterministic.
+pub(crate) fn sort_order(left: &Value, right: &Value) -> Ordering {
+    if let (Some(a), Some(b)) = (left.to_constant(), right.to_constant())
+        && let Some(ordering) = const_eval::ordering(&a, &b)
+    {
+        return ordering;
+    }
+    match (left, right) {
+        (Value::Node(a), Value::Node(b)) => a.cmp(b),
+        (Value::Edge(a), Value::Edge(b)) => a.cmp(b),
+        (Value::Path(a), Value::Path(b)) => a
+            .nodes()
+            .cmp(b.nodes())
+            .then_with(|| a.edges().cmp(b.edges())),
+        (Value::List(a), Value::List(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(x, y)| sort_order(x, y))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => kind_rank(left).cmp(&kind_rank(right)),
+    }
+}
+
+fn kind_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Boolean(_) => 1,
+        Value::Integer(_) | Value::Decimal(_) | Value::Float(_) => 2,
+        Value::String(_) => 3,
+        Value::Bytes(_) => 4,
+        Value::Date(_) => 5,
+        Value::Time(_) => 6,
+        Value::Datetime(_) => 7,
+        Value::Duration(_) => 8,
+        Value::List(_) => 9,
+        Value::Record(_) => 10,
+        Value::Node(_) => 11,
+        Value::Edge(_) => 12,
+        Value::Path(_) => 13,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_distinct_and_sort_order() {
+        assert!(not_distinct(&Value::Null, &Value::Null));
+        assert!(!not_distinct(&Value::Null, &Value::from(1)));
+        assert!(not_distinct(&Value::from(1), &Value::Float(1.0)));
+        assert!(rows_not_distinct(
+            &[Value::from("a"), Value::Null],
+            &[Value::from("a"), Value::Null]
+        ));
+        assert_eq!(
+            sort_order(&Value::from(2), &Value::Float(1.5)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            sort_order(&Value::from(true), &Value::from("x")),
+            Ordering::Less
+        );
+    }
+}
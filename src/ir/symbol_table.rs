@@ -1,6 +1,7 @@
 //! Symbol table for tracking variable bindings and scopes.
 
 use crate::ast::Span;
+use crate::ir::type_table::Type;
 use std::collections::HashMap;
 
 /// Unique identifier for a scope.
@@ -95,6 +96,29 @@ pub enum SymbolKind {
 
     /// Parameter variable.
     Parameter,
+
+    /// Variable bound from a procedure result column by CALL.
+    YieldVariable,
+
+    /// Computed result column of the statement before `NEXT` (e.g. `c` in
+    /// `RETURN count(*) AS c NEXT FILTER c > 1`).
+    ResultColumn,
+}
+
+/// Degree of reference of a binding variable.
+///
+/// Element variables declared inside a quantified path primary, such as `e`
+/// in `((a)-[e]->(b)){1,5}`, bind one element per iteration. Within the
+/// quantifier they are singletons; outside it they are group variables that
+/// denote the list of elements bound across all iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DegreeOfReference {
+    /// Binds a single value.
+    #[default]
+    Singleton,
+
+    /// Binds a list of elements, one per iteration of a quantifier.
+    Group,
 }
 
 /// Symbol representing a variable binding.
@@ -111,6 +135,13 @@ pub struct Symbol {
 
     /// Scope where the symbol is defined.
     pub scope: ScopeId,
+
+    /// Type declared for the symbol, when known at binding time (e.g. from a
+    /// procedure's result columns).
+    pub declared_type: Option<Type>,
+
+    /// Degree of reference outside any quantifier the symbol is declared in.
+    pub degree: DegreeOfReference,
 }
 
 impl Symbol {
@@ -121,8 +152,15 @@ impl Symbol {
             kind,
             declared_at,
             scope,
+            declared_type: None,
+            degree: DegreeOfReference::Singleton,
         }
     }
+
+    /// Returns true if this symbol is a group variable.
+    pub fn is_group(&self) -> bool {
+        self.degree == DegreeOfReference::Group
+    }
 }
 
 /// Symbol table tracking variable bindings and scopes.
@@ -179,17 +217,54 @@ impl SymbolTable {
     ///
     /// Returns a reference to the newly added symbol.
     pub fn define(&mut self, name: String, kind: SymbolKind, declared_at: Span) -> &Symbol {
-        let symbol = Symbol::new(name.clone(), kind, declared_at, self.current_scope);
+        self.define_typed(name, kind, declared_at, None)
+    }
 
-        // Add to scope
-        self.scopes[self.current_scope.0].add_symbol(name.clone());
+    /// Defines a symbol in the current scope with the type declared for it,
+    /// if one is known at binding time.
+    pub fn define_typed(
+        &mut self,
+        name: String,
+        kind: SymbolKind,
+        declared_at: Span,
+        declared_type: Option<Type>,
+    ) -> &Symbol {
+        let mut symbol = Symbol::new(name, kind, declared_at, self.current_scope);
+        symbol.declared_type = declared_type;
+        self.insert(symbol)
+    }
 
-        // Add to symbols map
-        let symbols_for_name = self.symbols.entry(name).or_default();
-        symbols_for_name.push(symbol);
+    /// Defines a group variable in the current scope.
+    ///
+    /// The symbol is typed as a list of `element_type`, which is how the
+    /// variable is seen outside its quantifier.
+    pub fn define_group(&mut self, name: String, declared_at: Span, element_type: Type) -> &Symbol {
+        let mut symbol = Symbol::new(
+            name,
+            SymbolKind::BindingVariable,
+            declared_at,
+            self.current_scope,
+        );
+        symbol.declared_type = Some(Type::List(Box::new(element_type)));
+        symbol.degree = DegreeOfReference::Group;
+        self.insert(symbol)
+    }
+
+    /// Defines `symbol` again in the current scope under `name`, keeping its
+    /// kind, declared type, and degree of reference.
+    pub fn define_alias(&mut self, symbol: &Symbol, name: String, declared_at: Span) -> &Symbol {
+        let mut alias = symbol.clone();
+        alias.name = name;
+        alias.declared_at = declared_at;
+        alias.scope = self.current_scope;
+        self.insert(alias)
+    }
 
-        // Return reference to the newly added symbol (last element in the vector)
-        // SAFETY: We just pushed an element, so the vector is guaranteed non-empty
+    /// Adds a symbol to its scope and returns a reference to it.
+    fn insert(&mut self, symbol: Symbol) -> &Symbol {
+        self.scopes[symbol.scope.0].add_symbol(symbol.name.clone());
+        let symbols_for_name = self.symbols.entry(symbol.name.clone()).or_default();
+        symbols_for_name.push(symbol);
         symbols_for_name.last().expect("vector is non-empty after push")
     }
 
@@ -235,6 +310,13 @@ impl SymbolTable {
             .filter(move |s| s.scope == current_scope)
     }
 
+    /// Returns all symbols across all scopes, grouped by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Symbol])> {
+        self.symbols
+            .iter()
+            .map(|(name, symbols)| (name.as_str(), symbols.as_slice()))
+    }
+
     /// Returns all symbols with a given name across all scopes.
     pub fn lookup_all(&self, name: &str) -> Option<&[Symbol]> {
         self.symbols.get(name).map(|v| v.as_slice())
@@ -307,4 +389,36 @@ mod tests {
         let symbol = st.lookup("n").unwrap();
         assert_eq!(symbol.declared_at, 2..3);
     }
+
+    #[test]
+    fn test_symbol_table_group_variables() {
+        let mut st = SymbolTable::new();
+
+        st.define("a".to_string(), SymbolKind::BindingVariable, 0..1);
+        st.define_group("e".to_string(), 2..3, Type::Edge(None));
+
+        assert!(!st.lookup("a").unwrap().is_group());
+        let group = st.lookup("e").unwrap();
+        assert!(group.is_group());
+        assert_eq!(
+            group.declared_type,
+            Some(Type::List(Box::new(Type::Edge(None))))
+        );
+    }
+
+    #[test]
+    fn test_symbol_table_aliases_keep_the_binding() {
+        let mut st = SymbolTable::new();
+
+        st.define_group("e".to_string(), 0..1, Type::Edge(None));
+        let source = st.lookup("e").unwrap().clone();
+        let inner = st.push_scope(ScopeKind::Query);
+        st.define_alias(&source, "edges".to_string(), 5..10);
+
+        let alias = st.lookup("edges").unwrap();
+        assert_eq!(alias.scope, inner);
+        assert_eq!(alias.declared_at, 5..10);
+        assert_eq!(alias.kind, SymbolKind::BindingVariable);
+        assert!(alias.is_group());
+    }
 }
@@ -1,5 +1,15 @@
 //! Type table for tracking expression types.
 
+use crate::ast::graph_type::{
+    DirectedArcType, EdgeTypeFiller, EdgeTypePattern, ElementTypeSpecification, LabelSetPhrase,
+};
+use crate::ast::types::{
+    ApproximateNumericType, EdgeReferenceValueType, EdgeTypeSpecification, ExactNumericType,
+    FieldTypesSpecification, GraphReferenceValueType, ImmaterialValueType, NestedGraphTypeSpecification,
+    NodeReferenceValueType, NodeTypeSpecification, NumericType, PredefinedType, RecordType,
+    ReferenceValueType, SignedBinaryExactNumericType, TemporalDurationType, TemporalInstantType,
+    TemporalType, UnsignedBinaryExactNumericType, ValueType,
+};
 use std::collections::HashMap;
 
 /// Unique identifier for an expression node in the AST.
@@ -18,37 +28,141 @@ impl ExprId {
     }
 }
 
+/// Bit width of a binary exact numeric or approximate numeric type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NumericWidth {
+    /// 8 bits.
+    W8,
+    /// 16 bits.
+    W16,
+    /// 32 bits.
+    W32,
+    /// 64 bits.
+    W64,
+    /// 128 bits.
+    W128,
+    /// 256 bits.
+    W256,
+}
+
+impl NumericWidth {
+    /// Returns the width in bits.
+    pub fn bits(self) -> u16 {
+        match self {
+            NumericWidth::W8 => 8,
+            NumericWidth::W16 => 16,
+            NumericWidth::W32 => 32,
+            NumericWidth::W64 => 64,
+            NumericWidth::W128 => 128,
+            NumericWidth::W256 => 256,
+        }
+    }
+
+    /// Returns the next wider width, if any.
+    pub fn wider(self) -> Option<NumericWidth> {
+        match self {
+            NumericWidth::W8 => Some(NumericWidth::W16),
+            NumericWidth::W16 => Some(NumericWidth::W32),
+            NumericWidth::W32 => Some(NumericWidth::W64),
+            NumericWidth::W64 => Some(NumericWidth::W128),
+            NumericWidth::W128 => Some(NumericWidth::W256),
+            NumericWidth::W256 => None,
+        }
+    }
+
+    /// Number of decimal digits needed to hold every value of a binary integer of this width.
+    fn decimal_digits(self, signed: bool) -> u32 {
+        match (self, signed) {
+            (NumericWidth::W8, _) => 3,
+            (NumericWidth::W16, _) => 5,
+            (NumericWidth::W32, _) => 10,
+            (NumericWidth::W64, true) => 19,
+            (NumericWidth::W64, false) => 20,
+            (NumericWidth::W128, _) => 39,
+            (NumericWidth::W256, true) => 77,
+            (NumericWidth::W256, false) => 78,
+        }
+    }
+}
+
+/// Qualifier of a duration type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationQualifier {
+    /// DURATION YEAR TO MONTH.
+    YearToMonth,
+    /// DURATION DAY TO SECOND.
+    DayToSecond,
+}
+
 /// GQL type representation.
+///
+/// The default forms produced by literals (`Int`, `Float`, `Time`, `Timestamp`,
+/// `Duration`) are kept as dedicated variants; the sized and qualified variants
+/// preserve the precision declared in a type annotation, CAST target or schema.
+/// Use [`Type::signed`], [`Type::unsigned`] and [`Type::approximate`] to build
+/// numeric types so that the 64-bit defaults stay canonical.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Type {
-    /// Integer type.
+    /// Signed integer of the default width (INT, INTEGER, INT64, BIGINT).
     Int,
 
-    /// Floating-point type.
+    /// Signed binary exact numeric of a non-default width (INT8, SMALLINT, INT32, ...).
+    SignedInt(NumericWidth),
+
+    /// Unsigned binary exact numeric (UINT8, ..., UINT256).
+    UnsignedInt(NumericWidth),
+
+    /// Decimal exact numeric with optional precision and scale.
+    Decimal {
+        /// Total number of digits.
+        precision: Option<u32>,
+        /// Digits after the decimal point.
+        scale: Option<u32>,
+    },
+
+    /// Floating-point type of the default width (FLOAT, FLOAT64, DOUBLE PRECISION).
     Float,
 
+    /// Approximate numeric of a non-default width (FLOAT16, REAL, FLOAT128, ...).
+    SizedFloat(NumericWidth),
+
     /// String type.
     String,
 
+    /// Byte string type (BYTES, BINARY, VARBINARY).
+    Bytes,
+
     /// Boolean type.
     Boolean,
 
     /// Date type.
     Date,
 
-    /// Time type.
+    /// Local time type (TIME, LOCAL TIME).
     Time,
 
-    /// Timestamp type.
+    /// Zoned time type (ZONED TIME, TIME WITH TIME ZONE).
+    ZonedTime,
+
+    /// Local datetime type (TIMESTAMP, LOCAL DATETIME).
     Timestamp,
 
+    /// Zoned datetime type (ZONED DATETIME, TIMESTAMP WITH TIME ZONE).
+    ZonedDatetime,
+
     /// Duration type.
     Duration,
 
+    /// Duration restricted to year-month or day-second components.
+    QualifiedDuration(DurationQualifier),
+
     /// Node type (optionally with labels).
+    ///
+    /// `None` is an open node reference; `Some(labels)` is closed to nodes
+    /// carrying one of the listed labels.
     Node(Option<Vec<String>>),
 
-    /// Edge type (optionally with labels).
+    /// Edge type (optionally with labels), open or closed like [`Type::Node`].
     Edge(Option<Vec<String>>),
 
     /// Path type.
@@ -60,65 +174,155 @@ pub enum Type {
     /// Record type with field names and types.
     Record(Vec<(String, Type)>),
 
+    /// Record of unknown shape (ANY RECORD).
+    AnyRecord,
+
+    /// Property graph reference; `Some` lists the node and edge types of a closed graph type.
+    Graph(Option<Vec<Type>>),
+
+    /// Binding table reference; `Some` lists the declared columns.
+    BindingTable(Option<Vec<(String, Type)>>),
+
+    /// Non-nullable form of the inner type.
+    NotNull(Box<Type>),
+
     /// Union of multiple types.
     Union(Vec<Type>),
 
     /// Null type.
     Null,
 
+    /// The empty type (NOTHING); coercible to every type.
+    Nothing,
+
     /// Any type (unknown or dynamic).
     Any,
 }
 
 impl Type {
-    /// Returns true if this type is numeric (Int or Float).
+    /// Builds a signed integer type, keeping 64 bits as [`Type::Int`].
+    pub fn signed(width: NumericWidth) -> Type {
+        match width {
+            NumericWidth::W64 => Type::Int,
+            other => Type::SignedInt(other),
+        }
+    }
+
+    /// Builds an unsigned integer type.
+    pub fn unsigned(width: NumericWidth) -> Type {
+        Type::UnsignedInt(width)
+    }
+
+    /// Builds an approximate numeric type, keeping 64 bits as [`Type::Float`].
+    pub fn approximate(width: NumericWidth) -> Type {
+        match width {
+            NumericWidth::W64 => Type::Float,
+            other => Type::SizedFloat(other),
+        }
+    }
+
+    /// Wraps a type as NOT NULL, collapsing redundant wrappers.
+    pub fn not_null(ty: Type) -> Type {
+        match ty {
+            Type::NotNull(_) | Type::Any | Type::Nothing => ty,
+            Type::Null => Type::Nothing,
+            other => Type::NotNull(Box::new(other)),
+        }
+    }
+
+    /// Returns the type with any NOT NULL wrapper removed.
+    pub fn without_not_null(&self) -> &Type {
+        match self {
+            Type::NotNull(inner) => inner,
+            other => other,
+        }
+    }
+
+    /// Returns true if values of this type may be null.
+    pub fn is_nullable(&self) -> bool {
+        !matches!(self, Type::NotNull(_) | Type::Nothing)
+    }
+
+    /// Returns true if this type is numeric (exact or approximate).
     pub fn is_numeric(&self) -> bool {
-        matches!(self, Type::Int | Type::Float)
+        self.is_exact_numeric() || self.is_approximate_numeric()
     }
 
-    /// Returns true if this type is comparable.
-    pub fn is_comparable(&self) -> bool {
+    /// Returns true if this type is an exact numeric type.
+    pub fn is_exact_numeric(&self) -> bool {
         matches!(
-            self,
-            Type::Int
-                | Type::Float
-                | Type::String
-                | Type::Boolean
-                | Type::Date
+            self.without_not_null(),
+            Type::Int | Type::SignedInt(_) | Type::UnsignedInt(_) | Type::Decimal { .. }
+        )
+    }
+
+    /// Returns true if this type is an approximate numeric type.
+    pub fn is_approximate_numeric(&self) -> bool {
+        matches!(self.without_not_null(), Type::Float | Type::SizedFloat(_))
+    }
+
+    /// Returns true if this type is a temporal instant or duration.
+    pub fn is_temporal(&self) -> bool {
+        matches!(
+            self.without_not_null(),
+            Type::Date
                 | Type::Time
+                | Type::ZonedTime
                 | Type::Timestamp
+                | Type::ZonedDatetime
                 | Type::Duration
+                | Type::QualifiedDuration(_)
         )
     }
 
+    /// Returns true if this type is comparable.
+    pub fn is_comparable(&self) -> bool {
+        self.is_numeric()
+            || self.is_temporal()
+            || matches!(
+                self.without_not_null(),
+                Type::String | Type::Bytes | Type::Boolean
+            )
+    }
+
     /// Returns true if this type is a boolean.
     pub fn is_boolean(&self) -> bool {
-        matches!(self, Type::Boolean)
+        matches!(self.without_not_null(), Type::Boolean)
     }
 
     /// Returns true if this type is a string.
     pub fn is_string(&self) -> bool {
-        matches!(self, Type::String)
+        matches!(self.without_not_null(), Type::String)
+    }
+
+    /// Returns true if this type is a byte string.
+    pub fn is_bytes(&self) -> bool {
+        matches!(self.without_not_null(), Type::Bytes)
     }
 
     /// Returns true if this type is a node.
     pub fn is_node(&self) -> bool {
-        matches!(self, Type::Node(_))
+        matches!(self.without_not_null(), Type::Node(_))
     }
 
     /// Returns true if this type is an edge.
     pub fn is_edge(&self) -> bool {
-        matches!(self, Type::Edge(_))
+        matches!(self.without_not_null(), Type::Edge(_))
     }
 
     /// Returns true if this type is a path.
     pub fn is_path(&self) -> bool {
-        matches!(self, Type::Path)
+        matches!(self.without_not_null(), Type::Path)
     }
 
     /// Returns true if this type is a list.
     pub fn is_list(&self) -> bool {
-        matches!(self, Type::List(_))
+        matches!(self.without_not_null(), Type::List(_))
+    }
+
+    /// Returns true if this type is a record.
+    pub fn is_record(&self) -> bool {
+        matches!(self.without_not_null(), Type::Record(_) | Type::AnyRecord)
     }
 
     /// Returns true if this type is null.
@@ -128,83 +332,627 @@ impl Type {
 
     /// Returns true if this type is compatible with another type for assignment/comparison.
     pub fn is_compatible_with(&self, other: &Type) -> bool {
+        let (this, other) = (self.without_not_null(), other.without_not_null());
+
         // Same types are compatible
-        if self == other {
+        if this == other {
             return true;
         }
 
         // Any type is compatible with everything
-        if matches!(self, Type::Any) || matches!(other, Type::Any) {
+        if matches!(this, Type::Any) || matches!(other, Type::Any) {
             return true;
         }
 
-        // Int can be promoted to Float
-        if matches!(self, Type::Int) && matches!(other, Type::Float) {
-            return true;
-        }
-        if matches!(self, Type::Float) && matches!(other, Type::Int) {
+        // Numeric types are mutually comparable
+        if this.is_numeric() && other.is_numeric() {
             return true;
         }
 
         // Null is compatible with any type
-        if matches!(self, Type::Null) || matches!(other, Type::Null) {
+        if matches!(this, Type::Null | Type::Nothing) || matches!(other, Type::Null | Type::Nothing)
+        {
             return true;
         }
 
         // Union types
-        if let Type::Union(types) = self {
+        if let Type::Union(types) = this {
             return types.iter().any(|t| t.is_compatible_with(other));
         }
         if let Type::Union(types) = other {
-            return types.iter().any(|t| self.is_compatible_with(t));
+            return types.iter().any(|t| this.is_compatible_with(t));
         }
 
         // Node/Edge types with different labels
-        match (self, other) {
+        match (this, other) {
             (Type::Node(_), Type::Node(_)) => return true,
             (Type::Edge(_), Type::Edge(_)) => return true,
             _ => {}
         }
 
-        false
+        Type::supertype(this, other).is_some()
+    }
+
+    /// Returns true if a value of this type can be implicitly coerced to `target`.
+    pub fn can_coerce_to(&self, target: &Type) -> bool {
+        self.coercion_cost(target).is_some()
+    }
+
+    /// Returns the cost of implicitly coercing this type to `target`.
+    ///
+    /// `Some(0)` means the types are identical; larger values indicate a less
+    /// direct conversion (widening, exact to approximate, exact to FLOAT16,
+    /// dynamic typing).
+    /// `None` means no implicit coercion exists.
+    pub fn coercion_cost(&self, target: &Type) -> Option<u32> {
+        if self == target {
+            return Some(0);
+        }
+
+        match (self, target) {
+            (Type::Any, _) | (_, Type::Any) => Some(4),
+            (Type::Nothing, _) => Some(1),
+            (Type::Null, target) => target.is_nullable().then_some(1),
+
+            (Type::NotNull(inner), Type::NotNull(target_inner)) => {
+                inner.coercion_cost(target_inner)
+            }
+            (Type::NotNull(inner), target) => inner.coercion_cost(target),
+            // Nullability of the source is only known at runtime.
+            (source, Type::NotNull(target_inner)) => {
+                source.coercion_cost(target_inner).map(|cost| cost + 1)
+            }
+
+            (Type::Union(members), target) => members
+                .iter()
+                .map(|m| m.coercion_cost(target))
+                .try_fold(0, |acc, cost| cost.map(|c| acc.max(c))),
+            (source, Type::Union(members)) => {
+                members.iter().filter_map(|m| source.coercion_cost(m)).min()
+            }
+
+            (source, target) if source.is_numeric() && target.is_numeric() => {
+                numeric_coercion_cost(source, target)
+            }
+
+            (Type::QualifiedDuration(_), Type::Duration) => Some(1),
+
+            (Type::Node(Some(_)), Type::Node(None)) | (Type::Edge(Some(_)), Type::Edge(None)) => {
+                Some(1)
+            }
+            (Type::Node(Some(from)), Type::Node(Some(to)))
+            | (Type::Edge(Some(from)), Type::Edge(Some(to))) => {
+                from.iter().all(|label| to.contains(label)).then_some(1)
+            }
+
+            (Type::List(from), Type::List(to)) => from.coercion_cost(to),
+
+            (Type::Record(_), Type::AnyRecord) => Some(1),
+            (Type::Record(from), Type::Record(to)) => fields_coercion_cost(from, to),
+
+            (Type::Graph(Some(_)), Type::Graph(None)) => Some(1),
+
+            (Type::BindingTable(Some(_)), Type::BindingTable(None)) => Some(1),
+            (Type::BindingTable(Some(from)), Type::BindingTable(Some(to))) => {
+                fields_coercion_cost(from, to)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Returns the most specific common supertype of two types, if one exists.
+    ///
+    /// Follows the ISO GQL result-type rules: exact numerics widen to an exact
+    /// type that holds both operands, any approximate operand makes the result
+    /// approximate, null is absorbed, closed element types merge their labels,
+    /// and the result is NOT NULL only if both inputs are.
+    pub fn supertype(a: &Type, b: &Type) -> Option<Type> {
+        if a == b {
+            return Some(a.clone());
+        }
+
+        match (a, b) {
+            (Type::Any, _) | (_, Type::Any) => Some(Type::Any),
+            (Type::Nothing, other) | (other, Type::Nothing) => Some(other.clone()),
+            (Type::Null, other) | (other, Type::Null) => Some(other.without_not_null().clone()),
+
+            (Type::NotNull(x), Type::NotNull(y)) => Type::supertype(x, y).map(Type::not_null),
+            (Type::NotNull(x), other) | (other, Type::NotNull(x)) => Type::supertype(x, other),
+
+            (Type::Union(members), other) | (other, Type::Union(members)) => {
+                let mut all = members.clone();
+                all.push(other.clone());
+                Type::common_supertype(&all)
+            }
+
+            (x, y) if x.is_numeric() && y.is_numeric() => numeric_supertype(x, y),
+
+            (Type::QualifiedDuration(_), Type::QualifiedDuration(_))
+            | (Type::QualifiedDuration(_), Type::Duration)
+            | (Type::Duration, Type::QualifiedDuration(_)) => Some(Type::Duration),
+
+            (Type::Node(x), Type::Node(y)) => Some(Type::Node(merge_labels(x, y))),
+            (Type::Edge(x), Type::Edge(y)) => Some(Type::Edge(merge_labels(x, y))),
+
+            (Type::List(x), Type::List(y)) => {
+                Type::supertype(x, y).map(|elem| Type::List(Box::new(elem)))
+            }
+
+            (Type::Record(x), Type::Record(y)) => {
+                Some(merge_fields(x, y).map_or(Type::AnyRecord, Type::Record))
+            }
+            (Type::Record(_), Type::AnyRecord) | (Type::AnyRecord, Type::Record(_)) => {
+                Some(Type::AnyRecord)
+            }
+
+            (Type::Graph(x), Type::Graph(y)) => Some(Type::Graph(match (x, y) {
+                (Some(x), Some(y)) => {
+                    let mut merged = x.clone();
+                    for ty in y {
+                        if !merged.contains(ty) {
+                            merged.push(ty.clone());
+                        }
+                    }
+                    Some(merged)
+                }
+                _ => None,
+            })),
+
+            (Type::BindingTable(x), Type::BindingTable(y)) => Some(Type::BindingTable(
+                match (x, y) {
+                    (Some(x), Some(y)) => merge_fields(x, y),
+                    _ => None,
+                },
+            )),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the common supertype of all given types, if one exists.
+    pub fn common_supertype(types: &[Type]) -> Option<Type> {
+        let mut flattened = Vec::new();
+        for ty in types {
+            match ty {
+                Type::Union(members) => flattened.extend(members.iter().cloned()),
+                other => flattened.push(other.clone()),
+            }
+        }
+
+        let mut iter = flattened.into_iter();
+        let first = iter.next()?;
+        iter.try_fold(first, |acc, ty| Type::supertype(&acc, &ty))
+    }
+
+    /// Converts an AST value type into its type representation.
+    pub fn from_value_type(value_type: &ValueType) -> Type {
+        match value_type {
+            ValueType::Predefined(predefined, _) => from_predefined_type(predefined),
+            ValueType::Path(_) => Type::Path,
+            ValueType::List(list) => {
+                Type::List(Box::new(Type::from_value_type(&list.element_type)))
+            }
+            ValueType::Record(RecordType::AnyRecord { .. }) => Type::AnyRecord,
+            ValueType::Record(RecordType::Record { field_types, .. }) => {
+                Type::Record(fields_from_specification(field_types))
+            }
+        }
     }
 
     /// Returns a human-readable name for this type.
     pub fn name(&self) -> String {
         match self {
             Type::Int => "Int".to_string(),
+            Type::SignedInt(width) => format!("Int{}", width.bits()),
+            Type::UnsignedInt(width) => format!("UInt{}", width.bits()),
+            Type::Decimal {
+                precision: None, ..
+            } => "Decimal".to_string(),
+            Type::Decimal {
+                precision: Some(p),
+                scale: None,
+            } => format!("Decimal({})", p),
+            Type::Decimal {
+                precision: Some(p),
+                scale: Some(s),
+            } => format!("Decimal({}, {})", p, s),
             Type::Float => "Float".to_string(),
+            Type::SizedFloat(width) => format!("Float{}", width.bits()),
             Type::String => "String".to_string(),
+            Type::Bytes => "Bytes".to_string(),
             Type::Boolean => "Boolean".to_string(),
             Type::Date => "Date".to_string(),
             Type::Time => "Time".to_string(),
+            Type::ZonedTime => "ZonedTime".to_string(),
             Type::Timestamp => "Timestamp".to_string(),
+            Type::ZonedDatetime => "ZonedDatetime".to_string(),
             Type::Duration => "Duration".to_string(),
+            Type::QualifiedDuration(DurationQualifier::YearToMonth) => {
+                "Duration(YearToMonth)".to_string()
+            }
+            Type::QualifiedDuration(DurationQualifier::DayToSecond) => {
+                "Duration(DayToSecond)".to_string()
+            }
             Type::Node(Some(labels)) => format!("Node:{}", labels.join("|")),
             Type::Node(None) => "Node".to_string(),
             Type::Edge(Some(labels)) => format!("Edge:{}", labels.join("|")),
             Type::Edge(None) => "Edge".to_string(),
             Type::Path => "Path".to_string(),
             Type::List(elem_type) => format!("List<{}>", elem_type.name()),
-            Type::Record(fields) => {
-                let field_strs: Vec<_> = fields
-                    .iter()
-                    .map(|(name, ty)| format!("{}: {}", name, ty.name()))
-                    .collect();
-                format!("Record<{}>", field_strs.join(", "))
+            Type::Record(fields) => format!("Record<{}>", fields_name(fields)),
+            Type::AnyRecord => "AnyRecord".to_string(),
+            Type::Graph(None) => "Graph".to_string(),
+            Type::Graph(Some(types)) => {
+                let type_names: Vec<_> = types.iter().map(|t| t.name()).collect();
+                format!("Graph<{}>", type_names.join(", "))
             }
+            Type::BindingTable(None) => "BindingTable".to_string(),
+            Type::BindingTable(Some(fields)) => format!("BindingTable<{}>", fields_name(fields)),
+            Type::NotNull(inner) => format!("{} NOT NULL", inner.name()),
             Type::Union(types) => {
                 let type_names: Vec<_> = types.iter().map(|t| t.name()).collect();
                 format!("Union<{}>", type_names.join(", "))
             }
             Type::Null => "Null".to_string(),
+            Type::Nothing => "Nothing".to_string(),
             Type::Any => "Any".to_string(),
         }
     }
 }
 
+/// Classification of a numeric type used by the coercion and supertype rules.
+enum NumericClass {
+    Binary { signed: bool, width: NumericWidth },
+    Decimal { precision: Option<u32>, scale: u32 },
+    Approximate(NumericWidth),
+}
+
+fn numeric_class(ty: &Type) -> Option<NumericClass> {
+    match ty.without_not_null() {
+        Type::Int => Some(NumericClass::Binary {
+            signed: true,
+            width: NumericWidth::W64,
+        }),
+        Type::SignedInt(width) => Some(NumericClass::Binary {
+            signed: true,
+            width: *width,
+        }),
+        Type::UnsignedInt(width) => Some(NumericClass::Binary {
+            signed: false,
+            width: *width,
+        }),
+        Type::Decimal { precision, scale } => Some(NumericClass::Decimal {
+            precision: *precision,
+            scale: scale.unwrap_or(0),
+        }),
+        Type::Float => Some(NumericClass::Approximate(NumericWidth::W64)),
+        Type::SizedFloat(width) => Some(NumericClass::Approximate(*width)),
+        _ => None,
+    }
+}
+
+/// Integral digits and scale of an exact numeric, `None` digits meaning unbounded.
+fn exact_digits(class: &NumericClass) -> Option<(Option<u32>, u32)> {
+    match class {
+        NumericClass::Binary { signed, width } => {
+            Some((Some(width.decimal_digits(*signed)), 0))
+        }
+        NumericClass::Decimal { precision, scale } => {
+            Some((precision.map(|p| p.saturating_sub(*scale)), *scale))
+        }
+        NumericClass::Approximate(_) => None,
+    }
+}
+
+/// Cost of an implicit numeric coercion that usually loses precision.
+///
+/// Ranked above every other numeric coercion but below dynamic typing, so an
+/// overload taking a wider type is always preferred.
+const LOSSY_COERCION_COST: u32 = 3;
+
+fn numeric_coercion_cost(source: &Type, target: &Type) -> Option<u32> {
+    let (from, to) = (numeric_class(source)?, numeric_class(target)?);
+    match (&from, &to) {
+        (
+            NumericClass::Binary {
+                signed: from_signed,
+                width: from_width,
+            },
+            NumericClass::Binary {
+                signed: to_signed,
+                width: to_width,
+            },
+        ) => {
+            let fits = match (from_signed, to_signed) {
+                (true, true) | (false, false) => from_width <= to_width,
+                (false, true) => from_width < to_width,
+                (true, false) => false,
+            };
+            fits.then_some(1)
+        }
+        (_, NumericClass::Decimal { .. }) | (NumericClass::Decimal { .. }, NumericClass::Binary { .. }) => {
+            let (from_int, from_scale) = exact_digits(&from)?;
+            let (to_int, to_scale) = exact_digits(&to)?;
+            let int_fits = match (from_int, to_int) {
+                (_, None) => true,
+                (Some(from_int), Some(to_int)) => from_int <= to_int,
+                (None, Some(_)) => false,
+            };
+            (int_fits && from_scale <= to_scale).then_some(1)
+        }
+        (NumericClass::Approximate(from_width), NumericClass::Approximate(to_width)) => {
+            (from_width <= to_width).then_some(1)
+        }
+        // A half-precision significand cannot even hold every 16-bit integer.
+        (_, NumericClass::Approximate(NumericWidth::W8 | NumericWidth::W16)) => {
+            Some(LOSSY_COERCION_COST)
+        }
+        (_, NumericClass::Approximate(_)) => Some(2),
+        (NumericClass::Approximate(_), _) => None,
+    }
+}
+
+fn numeric_supertype(a: &Type, b: &Type) -> Option<Type> {
+    let (x, y) = (numeric_class(a)?, numeric_class(b)?);
+    match (&x, &y) {
+        (NumericClass::Approximate(w1), NumericClass::Approximate(w2)) => {
+            Some(Type::approximate(*w1.max(w2)))
+        }
+        (NumericClass::Approximate(w), _) | (_, NumericClass::Approximate(w)) => {
+            Some(Type::approximate((*w).max(NumericWidth::W64)))
+        }
+        (
+            NumericClass::Binary {
+                signed: s1,
+                width: w1,
+            },
+            NumericClass::Binary {
+                signed: s2,
+                width: w2,
+            },
+        ) => match (s1, s2) {
+            (true, true) => Some(Type::signed(*w1.max(w2))),
+            (false, false) => Some(Type::unsigned(*w1.max(w2))),
+            _ => {
+                let (signed_width, unsigned_width) = if *s1 { (*w1, *w2) } else { (*w2, *w1) };
+                if signed_width > unsigned_width {
+                    Some(Type::signed(signed_width))
+                } else if let Some(wider) = unsigned_width.wider() {
+                    Some(Type::signed(wider))
+                } else {
+                    Some(Type::Decimal {
+                        precision: None,
+                        scale: Some(0),
+                    })
+                }
+            }
+        },
+        _ => {
+            let (int1, scale1) = exact_digits(&x)?;
+            let (int2, scale2) = exact_digits(&y)?;
+            let scale = scale1.max(scale2);
+            let precision = match (int1, int2) {
+                (Some(i1), Some(i2)) => Some(i1.max(i2) + scale),
+                _ => None,
+            };
+            Some(Type::Decimal {
+                precision,
+                scale: Some(scale),
+            })
+        }
+    }
+}
+
+fn merge_labels(x: &Option<Vec<String>>, y: &Option<Vec<String>>) -> Option<Vec<String>> {
+    match (x, y) {
+        (Some(x), Some(y)) => {
+            let mut merged = x.clone();
+            for label in y {
+                if !merged.contains(label) {
+                    merged.push(label.clone());
+                }
+            }
+            Some(merged)
+        }
+        _ => None,
+    }
+}
+
+/// Merges two field lists with the same names, returning `None` if the shapes differ.
+fn merge_fields(x: &[(String, Type)], y: &[(String, Type)]) -> Option<Vec<(String, Type)>> {
+    if x.len() != y.len() {
+        return None;
+    }
+    x.iter()
+        .map(|(name, ty)| {
+            let (_, other) = y.iter().find(|(other_name, _)| other_name == name)?;
+            Some((name.clone(), Type::supertype(ty, other)?))
+        })
+        .collect()
+}
+
+fn fields_coercion_cost(from: &[(String, Type)], to: &[(String, Type)]) -> Option<u32> {
+    if from.len() != to.len() {
+        return None;
+    }
+    from.iter().try_fold(0, |acc, (name, ty)| {
+        let (_, target) = to.iter().find(|(target_name, _)| target_name == name)?;
+        Some(acc.max(ty.coercion_cost(target)?))
+    })
+}
+
+fn fields_name(fields: &[(String, Type)]) -> String {
+    let field_strs: Vec<_> = fields
+        .iter()
+        .map(|(name, ty)| format!("{}: {}", name, ty.name()))
+        .collect();
+    field_strs.join(", ")
+}
+
+fn fields_from_specification(spec: &FieldTypesSpecification) -> Vec<(String, Type)> {
+    spec.fields
+        .iter()
+        .map(|field| {
+            (
+                field.field_name.to_string(),
+                Type::from_value_type(&field.field_type),
+            )
+        })
+        .collect()
+}
+
+fn with_not_null(ty: Type, not_null: bool) -> Type {
+    if not_null { Type::not_null(ty) } else { ty }
+}
+
+fn from_predefined_type(predefined: &PredefinedType) -> Type {
+    match predefined {
+        PredefinedType::Boolean(_) => Type::Boolean,
+        PredefinedType::CharacterString(_) => Type::String,
+        PredefinedType::ByteString(_) => Type::Bytes,
+        PredefinedType::Numeric(NumericType::Exact(exact)) => match exact {
+            ExactNumericType::SignedBinary(signed) => Type::signed(match signed {
+                SignedBinaryExactNumericType::Int8 => NumericWidth::W8,
+                SignedBinaryExactNumericType::Int16 | SignedBinaryExactNumericType::SmallInt => {
+                    NumericWidth::W16
+                }
+                SignedBinaryExactNumericType::Int32 => NumericWidth::W32,
+                SignedBinaryExactNumericType::Int64
+                | SignedBinaryExactNumericType::Int
+                | SignedBinaryExactNumericType::Integer
+                | SignedBinaryExactNumericType::BigInt => NumericWidth::W64,
+                SignedBinaryExactNumericType::Int128 => NumericWidth::W128,
+                SignedBinaryExactNumericType::Int256 => NumericWidth::W256,
+            }),
+            ExactNumericType::UnsignedBinary(unsigned) => Type::unsigned(match unsigned {
+                UnsignedBinaryExactNumericType::UInt8 => NumericWidth::W8,
+                UnsignedBinaryExactNumericType::UInt16
+                | UnsignedBinaryExactNumericType::USmallInt => NumericWidth::W16,
+                UnsignedBinaryExactNumericType::UInt32 => NumericWidth::W32,
+                UnsignedBinaryExactNumericType::UInt64
+                | UnsignedBinaryExactNumericType::UInt
+                | UnsignedBinaryExactNumericType::UBigInt => NumericWidth::W64,
+                UnsignedBinaryExactNumericType::UInt128 => NumericWidth::W128,
+                UnsignedBinaryExactNumericType::UInt256 => NumericWidth::W256,
+            }),
+            ExactNumericType::Decimal(decimal) => Type::Decimal {
+                precision: decimal.precision,
+                scale: decimal.scale,
+            },
+        },
+        PredefinedType::Numeric(NumericType::Approximate(approx)) => {
+            Type::approximate(match approx {
+                ApproximateNumericType::Float16 => NumericWidth::W16,
+                ApproximateNumericType::Float32 | ApproximateNumericType::Real => NumericWidth::W32,
+                ApproximateNumericType::Float64
+                | ApproximateNumericType::Float(None)
+                | ApproximateNumericType::DoublePrecision => NumericWidth::W64,
+                ApproximateNumericType::Float128 => NumericWidth::W128,
+                ApproximateNumericType::Float256 => NumericWidth::W256,
+                // Binary precision of the IEEE 754 interchange formats.
+                ApproximateNumericType::Float(Some(p)) => match p {
+                    0..=11 => NumericWidth::W16,
+                    12..=24 => NumericWidth::W32,
+                    25..=53 => NumericWidth::W64,
+                    54..=113 => NumericWidth::W128,
+                    _ => NumericWidth::W256,
+                },
+            })
+        }
+        PredefinedType::Temporal(TemporalType::Instant(instant)) => match instant {
+            TemporalInstantType::Date => Type::Date,
+            TemporalInstantType::LocalTime => Type::Time,
+            TemporalInstantType::ZonedTime => Type::ZonedTime,
+            TemporalInstantType::LocalDatetime => Type::Timestamp,
+            TemporalInstantType::ZonedDatetime => Type::ZonedDatetime,
+        },
+        PredefinedType::Temporal(TemporalType::Duration(duration)) => match duration {
+            TemporalDurationType::Duration => Type::Duration,
+            TemporalDurationType::DurationYearToMonth => {
+                Type::QualifiedDuration(DurationQualifier::YearToMonth)
+            }
+            TemporalDurationType::DurationDayToSecond => {
+                Type::QualifiedDuration(DurationQualifier::DayToSecond)
+            }
+        },
+        PredefinedType::ReferenceValue(reference) => from_reference_type(reference),
+        PredefinedType::Immaterial(ImmaterialValueType::Null) => Type::Null,
+        PredefinedType::Immaterial(
+            ImmaterialValueType::NullNotNull | ImmaterialValueType::Nothing,
+        ) => Type::Nothing,
+    }
+}
+
+fn from_reference_type(reference: &ReferenceValueType) -> Type {
+    match reference {
+        ReferenceValueType::Graph(GraphReferenceValueType::AnyPropertyGraph { not_null, .. }) => {
+            with_not_null(Type::Graph(None), *not_null)
+        }
+        ReferenceValueType::Graph(GraphReferenceValueType::PropertyGraph {
+            spec,
+            not_null,
+            ..
+        }) => with_not_null(Type::Graph(Some(graph_element_types(spec))), *not_null),
+        ReferenceValueType::BindingTable(table) => with_not_null(
+            Type::BindingTable(table.field_types.as_ref().map(fields_from_specification)),
+            table.not_null,
+        ),
+        ReferenceValueType::Node(NodeReferenceValueType::Any { not_null, .. }) => {
+            with_not_null(Type::Node(None), *not_null)
+        }
+        ReferenceValueType::Node(NodeReferenceValueType::Typed { spec, not_null, .. }) => {
+            with_not_null(Type::Node(node_spec_labels(spec)), *not_null)
+        }
+        ReferenceValueType::Edge(EdgeReferenceValueType::Any { not_null, .. }) => {
+            with_not_null(Type::Edge(None), *not_null)
+        }
+        ReferenceValueType::Edge(EdgeReferenceValueType::Typed { spec, not_null, .. }) => {
+            with_not_null(Type::Edge(edge_spec_labels(spec)), *not_null)
+        }
+    }
+}
+
+fn graph_element_types(spec: &NestedGraphTypeSpecification) -> Vec<Type> {
+    spec.body
+        .element_types
+        .types
+        .iter()
+        .map(|element| match element {
+            ElementTypeSpecification::Node(node) => Type::Node(node_spec_labels(node)),
+            ElementTypeSpecification::Edge(edge) => Type::Edge(edge_spec_labels(edge)),
+        })
+        .collect()
+}
+
+fn label_set_labels(phrase: &LabelSetPhrase) -> Vec<String> {
+    match phrase {
+        LabelSetPhrase::Label(label) => vec![label.name.to_string()],
+        LabelSetPhrase::Labels(set) | LabelSetPhrase::IsLabelSet(set) => {
+            set.labels.iter().map(|label| label.name.to_string()).collect()
+        }
+    }
+}
+
+fn node_spec_labels(spec: &NodeTypeSpecification) -> Option<Vec<String>> {
+    let label_set = spec.pattern.phrase.filler.as_ref()?.label_set.as_ref()?;
+    Some(label_set_labels(&label_set.label_set_phrase))
+}
+
+fn edge_spec_labels(spec: &EdgeTypeSpecification) -> Option<Vec<String>> {
+    let filler: &EdgeTypeFiller = match &spec.pattern {
+        EdgeTypePattern::Directed(directed) => match &directed.arc {
+            DirectedArcType::PointingRight(arc) => arc.filler.as_ref()?,
+            DirectedArcType::PointingLeft(arc) => arc.filler.as_ref()?,
+        },
+        EdgeTypePattern::Undirected(undirected) => undirected.arc.filler.as_ref()?,
+    };
+    let label_set = filler.phrase.filler_content.as_ref()?.label_set.as_ref()?;
+    Some(label_set_labels(&label_set.label_set_phrase))
+}
+
 /// Type constraint for an expression.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TypeConstraint {
     /// Must be a numeric type.
     Numeric,
@@ -234,6 +982,23 @@ pub enum TypeConstraint {
     Exact(Type),
 }
 
+impl TypeConstraint {
+    /// Returns true if `ty` satisfies this constraint.
+    pub fn is_satisfied_by(&self, ty: &Type) -> bool {
+        match self {
+            TypeConstraint::Numeric => ty.is_numeric(),
+            TypeConstraint::Comparable => ty.is_comparable(),
+            TypeConstraint::Boolean => ty.is_boolean(),
+            TypeConstraint::String => ty.is_string(),
+            TypeConstraint::Node => ty.is_node(),
+            TypeConstraint::Edge => ty.is_edge(),
+            TypeConstraint::GraphElement => ty.is_node() || ty.is_edge(),
+            TypeConstraint::List => ty.is_list(),
+            TypeConstraint::Exact(expected) => ty.is_compatible_with(expected),
+        }
+    }
+}
+
 /// Type table tracking expression types and constraints.
 #[derive(Debug, Clone)]
 pub struct TypeTable {
@@ -313,17 +1078,9 @@ impl TypeTable {
             None => return true,
         };
 
-        constraints.iter().all(|constraint| match constraint {
-            TypeConstraint::Numeric => ty.is_numeric(),
-            TypeConstraint::Comparable => ty.is_comparable(),
-            TypeConstraint::Boolean => ty.is_boolean(),
-            TypeConstraint::String => ty.is_string(),
-            TypeConstraint::Node => ty.is_node(),
-            TypeConstraint::Edge => ty.is_edge(),
-            TypeConstraint::GraphElement => ty.is_node() || ty.is_edge(),
-            TypeConstraint::List => ty.is_list(),
-            TypeConstraint::Exact(expected) => ty.is_compatible_with(expected),
-        })
+        constraints
+            .iter()
+            .all(|constraint| constraint.is_satisfied_by(ty))
     }
 }
 
@@ -347,6 +1104,113 @@ mod tests {
         assert!(Type::Null.is_compatible_with(&Type::Int));
     }
 
+    #[test]
+    fn test_numeric_coercion() {
+        assert!(Type::SignedInt(NumericWidth::W8).can_coerce_to(&Type::Int));
+        assert!(!Type::Int.can_coerce_to(&Type::SignedInt(NumericWidth::W32)));
+        assert!(Type::UnsignedInt(NumericWidth::W32).can_coerce_to(&Type::Int));
+        assert!(!Type::UnsignedInt(NumericWidth::W64).can_coerce_to(&Type::Int));
+        assert!(!Type::Int.can_coerce_to(&Type::UnsignedInt(NumericWidth::W64)));
+        assert!(Type::Int.can_coerce_to(&Type::Float));
+        assert!(!Type::Float.can_coerce_to(&Type::Int));
+        assert!(Type::SizedFloat(NumericWidth::W32).can_coerce_to(&Type::Float));
+
+        let dec_10_2 = Type::Decimal {
+            precision: Some(10),
+            scale: Some(2),
+        };
+        let dec_5_1 = Type::Decimal {
+            precision: Some(5),
+            scale: Some(1),
+        };
+        assert!(dec_5_1.can_coerce_to(&dec_10_2));
+        assert!(!dec_10_2.can_coerce_to(&dec_5_1));
+        assert!(Type::SignedInt(NumericWidth::W16).can_coerce_to(&dec_10_2));
+        assert!(!Type::Int.can_coerce_to(&dec_10_2));
+
+        // Exact widening is preferred over conversion to approximate
+        assert!(
+            Type::SignedInt(NumericWidth::W32).coercion_cost(&Type::Int)
+                < Type::SignedInt(NumericWidth::W32).coercion_cost(&Type::Float)
+        );
+
+        // FLOAT16 is a lossy target for exact values
+        let float16 = Type::SizedFloat(NumericWidth::W16);
+        assert!(Type::Int.coercion_cost(&Type::Float) < Type::Int.coercion_cost(&float16));
+        assert!(Type::Int.coercion_cost(&float16) < Type::Int.coercion_cost(&Type::Any));
+    }
+
+    #[test]
+    fn test_numeric_supertype() {
+        assert_eq!(
+            Type::supertype(&Type::SignedInt(NumericWidth::W8), &Type::SignedInt(NumericWidth::W32)),
+            Some(Type::SignedInt(NumericWidth::W32))
+        );
+        assert_eq!(
+            Type::supertype(&Type::UnsignedInt(NumericWidth::W32), &Type::SignedInt(NumericWidth::W32)),
+            Some(Type::Int)
+        );
+        assert_eq!(Type::supertype(&Type::Int, &Type::Float), Some(Type::Float));
+        assert_eq!(
+            Type::supertype(
+                &Type::Decimal {
+                    precision: Some(5),
+                    scale: Some(2)
+                },
+                &Type::Decimal {
+                    precision: Some(8),
+                    scale: Some(4)
+                }
+            ),
+            Some(Type::Decimal {
+                precision: Some(8),
+                scale: Some(4)
+            })
+        );
+        assert_eq!(
+            Type::supertype(
+                &Type::SignedInt(NumericWidth::W16),
+                &Type::Decimal {
+                    precision: Some(4),
+                    scale: Some(2)
+                }
+            ),
+            Some(Type::Decimal {
+                precision: Some(7),
+                scale: Some(2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_supertype_nullability_and_references() {
+        assert_eq!(Type::supertype(&Type::Null, &Type::not_null(Type::Int)), Some(Type::Int));
+        assert_eq!(
+            Type::supertype(&Type::not_null(Type::Int), &Type::not_null(Type::Float)),
+            Some(Type::not_null(Type::Float))
+        );
+        assert_eq!(
+            Type::supertype(
+                &Type::Node(Some(vec!["A".into()])),
+                &Type::Node(Some(vec!["B".into()]))
+            ),
+            Some(Type::Node(Some(vec!["A".into(), "B".into()])))
+        );
+        assert_eq!(Type::supertype(&Type::Node(None), &Type::Edge(None)), None);
+        assert_eq!(Type::supertype(&Type::Time, &Type::ZonedTime), None);
+        assert_eq!(
+            Type::supertype(
+                &Type::Record(vec![("a".into(), Type::Int)]),
+                &Type::Record(vec![("b".into(), Type::Int)])
+            ),
+            Some(Type::AnyRecord)
+        );
+        assert!(Type::Node(Some(vec!["A".into()])).can_coerce_to(&Type::Node(None)));
+        assert!(!Type::Node(None).can_coerce_to(&Type::Node(Some(vec!["A".into()]))));
+        assert!(!Type::Null.can_coerce_to(&Type::Nothing));
+        assert!(Type::Nothing.can_coerce_to(&Type::Int));
+    }
+
     #[test]
     fn test_type_table() {
         let mut tt = TypeTable::new();
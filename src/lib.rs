@@ -43,10 +43,17 @@ use miette::Report;
 
 pub mod analysis;
 pub mod ast;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod diag;
+pub mod emit;
+pub mod interpreter;
 pub mod ir;
 pub mod lexer;
 pub mod parser;
+pub mod pattern;
+#[cfg(feature = "python")]
+pub mod python;
 pub mod semantic;
 
 // Re-export syntax span primitives.
@@ -85,6 +92,30 @@ pub fn parse(source: &str) -> ParseResult {
         .parse()
 }
 
+/// Parses openCypher source text into the GQL AST.
+///
+/// Cypher is lexed with the GQL lexer and lowered to the GQL statements with
+/// the same meaning (`CREATE` to `INSERT`, `UNWIND` to `FOR`, `WITH` to
+/// `RETURN ... NEXT`, variable-length relationships to quantifiers, ...), so
+/// the result can go through the same validator as GQL input. Constructs with
+/// no GQL equivalent, such as `MERGE`, are reported as diagnostics.
+///
+/// # Example
+///
+/// ```
+/// use gql_parser::parse_cypher;
+///
+/// let result = parse_cypher("MATCH (n:Person) UNWIND n.tags AS tag RETURN tag");
+/// assert!(result.ast.is_some());
+/// assert!(result.diagnostics.is_empty());
+/// ```
+pub fn parse_cypher(source: &str) -> ParseResult {
+    let lex_result = tokenize(source);
+    Parser::new(lex_result.tokens, source)
+        .with_lexer_diagnostics(lex_result.diagnostics)
+        .parse_cypher()
+}
+
 /// Result of parsing and semantic validation with rendered diagnostics.
 #[derive(Debug)]
 pub struct ParseAndValidateResult {
@@ -194,6 +225,23 @@ fn parse_and_validate_internal(
     }
 }
 
+/// Parses `source` into an AST and unrendered diagnostics for the language
+/// bindings, validating the AST with `validator` when it parsed cleanly.
+#[cfg(any(feature = "capi", feature = "python"))]
+pub(crate) fn parse_to_diagnostics(
+    source: &str,
+    validator: Option<&SemanticValidator<'_>>,
+) -> (Option<ast::Program>, Vec<Diag>) {
+    let lex_result = tokenize(source);
+    let (ast, mut diagnostics) = Parser::new(lex_result.tokens, source)
+        .with_lexer_diagnostics(lex_result.diagnostics)
+        .parse_with_diagnostics();
+    if let (Some(validator), Some(program), true) = (validator, &ast, diagnostics.is_empty()) {
+        diagnostics = validator.validate(program).diagnostics;
+    }
+    (ast, diagnostics)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,813 @@
+//! openCypher compatibility front-end.
+//!
+//! Cypher text is lexed with the GQL lexer and lowered token by token into
+//! the GQL spelling of the same statement, which the regular program parser
+//! then turns into a [`Program`](crate::ast::Program). Lowered tokens keep the
+//! spans of the Cypher tokens they replace, so diagnostics from parsing and
+//! validation point into the Cypher source.
+//!
+//! | openCypher | GQL |
+//! |---|---|
+//! | `CREATE (a)-[:R]->(b)` | `INSERT (a)-[:R]->(b)` |
+//! | `UNWIND list AS x` | `FOR x IN list` |
+//! | `WITH a, count(b) AS c WHERE c > 1` | `RETURN a, count(b) AS c NEXT FILTER c > 1` |
+//! | `(a)-[:R*1..3]->(b)` | `(a)-[:R]->{1,3}(b)` |
+//! | `(a)-->(b)`, `(a)<--(b)`, `(a)--(b)` | `(a)->(b)`, `(a)<-(b)`, `(a)-(b)` |
+//! | `(n:A:B)`, `[:R\|:S]` | `(n:A&B)`, `[:R\|S]` |
+//! | `shortestPath(...)`, `allShortestPaths(...)` | `ANY SHORTEST ...`, `ALL SHORTEST ...` |
+//! | `exists(n.p)`, `exists((n)-->())` | `(n.p IS NOT NULL)`, `EXISTS { (n)->() }` |
+//! | `collect`, `toUpper`, `toLower`, `id`, `length`, `stDev`, ... | `COLLECT_LIST`, `UPPER`, `LOWER`, `ELEMENT_ID`, `PATH_LENGTH`, `STDDEV_SAMP`, ... |
+//!
+//! As with GQL input, `NEXT` starts a new statement of the program.
+//!
+//! Cypher matches patterns under relationship isomorphism, so a `MATCH` with
+//! more than one relationship or a variable-length relationship is lowered
+//! with the `DIFFERENT EDGES` match mode.
+//!
+//! Constructs without a GQL equivalent (`MERGE`, `FOREACH`, list
+//! comprehensions, `STARTS WITH`, ...) are reported on the Cypher source, and
+//! the statement containing them is left out of the program.
+
+use smol_str::SmolStr;
+
+use crate::ast::Span;
+use crate::diag::Diag;
+use crate::lexer::keywords::lookup_keyword;
+use crate::lexer::token::{Token, TokenKind};
+
+/// Lowers Cypher tokens into the equivalent GQL tokens.
+///
+/// `tokens` must end with an EOF token.
+pub(super) fn lower_cypher_tokens(tokens: &[Token]) -> (Vec<Token>, Vec<Diag>) {
+    let mut lowering = Lowering {
+        tokens,
+        pos: 0,
+        out: Vec::with_capacity(tokens.len()),
+        diags: Vec::new(),
+        closers: Vec::new(),
+        open_with: Vec::new(),
+        pending_quantifier: None,
+        consumed: Vec::new(),
+        statement_start: 0,
+        statement_unsupported: false,
+    };
+    while lowering.pos < tokens.len() {
+        lowering.step();
+    }
+    (lowering.out, lowering.diags)
+}
+
+/// What to emit when an open delimiter closes.
+enum Closer {
+    /// The closing token itself.
+    Verbatim,
+    /// A relationship pattern, with the quantifier lowered from its
+    /// `*min..max` range.
+    Relationship(Option<Vec<Token>>),
+    /// The `}` of `EXISTS { ... }` lowered from `exists((pattern))`.
+    PatternExists,
+    /// `IS NOT NULL)` of the predicate lowered from `exists(expr)`.
+    PropertyExists,
+    /// Nothing; the parentheses of `shortestPath(...)` are dropped.
+    Dropped,
+}
+
+struct Lowering<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    out: Vec<Token>,
+    diags: Vec<Diag>,
+    closers: Vec<Closer>,
+    /// Nesting depths of `WITH` clauses lowered to `RETURN` that still need
+    /// their `NEXT`.
+    open_with: Vec<usize>,
+    /// Quantifier to emit after the token that ends a relationship pattern.
+    pending_quantifier: Option<Vec<Token>>,
+    /// Positions already emitted out of order, such as the variable of
+    /// `UNWIND ... AS x`.
+    consumed: Vec<usize>,
+    /// Start of the current statement in `out`.
+    statement_start: usize,
+    /// Whether the current statement uses a construct without a GQL
+    /// equivalent.
+    statement_unsupported: bool,
+}
+
+impl Lowering<'_> {
+    fn step(&mut self) {
+        if self.consumed.contains(&self.pos) {
+            self.pos += 1;
+            return;
+        }
+        let tokens = self.tokens;
+        let token = &tokens[self.pos];
+        let depth = self.closers.len();
+
+        if self.open_with.last() == Some(&depth) {
+            if token.kind == TokenKind::Where {
+                self.open_with.pop();
+                self.push(TokenKind::Next, token.span.start..token.span.start);
+                self.push(TokenKind::Filter, token.span.clone());
+                self.pos += 1;
+                return;
+            }
+            if self.is_clause_start(self.pos) {
+                self.open_with.pop();
+                self.push(TokenKind::Next, token.span.start..token.span.start);
+            } else if is_closing(&token.kind)
+                || matches!(token.kind, TokenKind::Semicolon | TokenKind::Eof)
+            {
+                self.open_with.pop();
+            }
+        }
+
+        match &token.kind {
+            TokenKind::Semicolon | TokenKind::Eof => self.end_statement(),
+            TokenKind::Match => {
+                self.copy();
+                if self.needs_relationship_isomorphism() {
+                    let at = token.span.end..token.span.end;
+                    self.push(TokenKind::Different, at.clone());
+                    self.push(TokenKind::NonReservedKeyword("EDGES".into()), at);
+                }
+            }
+            TokenKind::With => {
+                self.push(TokenKind::Return, token.span.clone());
+                self.open_with.push(depth);
+                self.pos += 1;
+            }
+            TokenKind::Create => self.lower_create(),
+            TokenKind::Drop => {
+                let construct = format!("DROP {}", self.word_at(self.pos + 1));
+                self.unsupported(&construct, token.span.clone(), None);
+                self.skip_clause();
+            }
+            TokenKind::Identifier(word) => self.lower_word(word),
+            TokenKind::Exists if self.kind_at(self.pos + 1) == Some(&TokenKind::LParen) => {
+                self.lower_exists()
+            }
+            TokenKind::LBracket => self.open_bracket(),
+            TokenKind::LBrace if self.kind_at(self.pos + 1) == Some(&TokenKind::Dot) => {
+                self.unsupported("a map projection", token.span.clone(), None);
+                self.closers.push(Closer::Verbatim);
+                self.copy();
+            }
+            TokenKind::LParen | TokenKind::LBrace => {
+                self.closers.push(Closer::Verbatim);
+                self.copy();
+            }
+            TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => self.close(),
+            TokenKind::Star if matches!(self.closers.last(), Some(Closer::Relationship(None))) => {
+                self.lower_range()
+            }
+            TokenKind::Colon => self.lower_colon(),
+            TokenKind::Minus | TokenKind::LeftArrow => self.lower_abbreviated_edge(),
+            TokenKind::In => self.report_in(),
+            TokenKind::DotDot => {
+                self.unsupported("list slicing", token.span.clone(), None);
+                self.copy();
+            }
+            TokenKind::Plus if self.adjacent(TokenKind::Eq) => {
+                self.unsupported(
+                    "SET +=",
+                    token.span.start..tokens[self.pos + 1].span.end,
+                    Some("set each property with SET n.property = value"),
+                );
+                self.copy();
+            }
+            TokenKind::Eq if self.adjacent(TokenKind::Tilde) => {
+                self.unsupported(
+                    "regular expression matching (=~)",
+                    token.span.start..tokens[self.pos + 1].span.end,
+                    None,
+                );
+                self.copy();
+            }
+            _ => self.copy(),
+        }
+    }
+
+    fn end_statement(&mut self) {
+        let token = self.tokens[self.pos].clone();
+        self.closers.clear();
+        self.open_with.clear();
+        self.pending_quantifier = None;
+        let unsupported = std::mem::take(&mut self.statement_unsupported);
+        if unsupported {
+            self.out.truncate(self.statement_start);
+        }
+        if token.kind == TokenKind::Eof || !unsupported {
+            self.out.push(token);
+        }
+        self.statement_start = self.out.len();
+        self.pos += 1;
+    }
+
+    /// `CREATE pattern` becomes `INSERT pattern`; schema commands such as
+    /// `CREATE INDEX` have no counterpart.
+    fn lower_create(&mut self) {
+        let span = self.tokens[self.pos].span.clone();
+        match self.kind_at(self.pos + 1) {
+            Some(TokenKind::LParen) => {
+                self.push(TokenKind::Insert, span);
+                self.pos += 1;
+            }
+            Some(TokenKind::Identifier(_))
+                if self.kind_at(self.pos + 2) == Some(&TokenKind::Eq) =>
+            {
+                self.unsupported(
+                    "a path variable in CREATE",
+                    span.start..self.tokens[self.pos + 2].span.end,
+                    None,
+                );
+                self.copy();
+            }
+            _ => {
+                let construct = format!("CREATE {}", self.word_at(self.pos + 1));
+                self.unsupported(&construct, span, None);
+                self.skip_clause();
+            }
+        }
+    }
+
+    fn lower_word(&mut self, word: &SmolStr) {
+        let tokens = self.tokens;
+        let span = tokens[self.pos].span.clone();
+        let next = self.kind_at(self.pos + 1);
+        match word.to_ascii_lowercase().as_str() {
+            "unwind" => self.lower_unwind(),
+            "merge" => {
+                self.unsupported(
+                    "MERGE",
+                    span,
+                    Some("MATCH the pattern first and INSERT it in a separate statement"),
+                );
+                self.skip_clause();
+            }
+            "foreach" => {
+                self.unsupported(
+                    "FOREACH",
+                    span,
+                    Some("iterate over the list with FOR before the update"),
+                );
+                self.skip_clause();
+            }
+            "load" if next.is_some_and(|kind| word_matches(kind, "csv")) => {
+                self.unsupported("LOAD CSV", span.start..tokens[self.pos + 1].span.end, None);
+                self.skip_clause();
+            }
+            "using" => {
+                self.unsupported("a planner hint (USING)", span, None);
+                self.skip_clause();
+            }
+            "starts" | "ends" if next == Some(&TokenKind::With) => {
+                let construct = format!("{} WITH", word.to_ascii_uppercase());
+                let span = span.start..tokens[self.pos + 1].span.end;
+                self.unsupported(&construct, span, None);
+                self.copy();
+                self.copy();
+            }
+            "contains" => {
+                self.unsupported("CONTAINS", span, None);
+                self.copy();
+            }
+            "shortestpath" | "allshortestpaths" if next == Some(&TokenKind::LParen) => {
+                let selector = if word.eq_ignore_ascii_case("shortestpath") {
+                    TokenKind::Any
+                } else {
+                    TokenKind::All
+                };
+                self.push(selector, span.clone());
+                self.push(TokenKind::Shortest, span);
+                self.closers.push(Closer::Dropped);
+                self.pos += 2;
+            }
+            lower if next == Some(&TokenKind::LParen) => {
+                let name = match lower {
+                    "collect" => "COLLECT_LIST",
+                    "stdev" => "STDDEV_SAMP",
+                    "stdevp" => "STDDEV_POP",
+                    "percentilecont" => "PERCENTILE_CONT",
+                    "percentiledisc" => "PERCENTILE_DISC",
+                    "toupper" => "UPPER",
+                    "tolower" => "LOWER",
+                    "id" | "elementid" => "ELEMENT_ID",
+                    "length" => "PATH_LENGTH",
+                    _ => {
+                        self.copy();
+                        return;
+                    }
+                };
+                let kind =
+                    lookup_keyword(name).unwrap_or_else(|| TokenKind::Identifier(name.into()));
+                self.push(kind, span);
+                self.pos += 1;
+            }
+            _ => self.copy(),
+        }
+    }
+
+    /// `UNWIND expr AS x` becomes `FOR x IN expr`.
+    fn lower_unwind(&mut self) {
+        let tokens = self.tokens;
+        let span = tokens[self.pos].span.clone();
+        let alias = self.find_at_clause_depth(self.pos + 1, |kind| *kind == TokenKind::As);
+        let variable = alias.and_then(|pos| tokens.get(pos + 1)).filter(|token| {
+            matches!(
+                token.kind,
+                TokenKind::Identifier(_) | TokenKind::DelimitedIdentifier(_)
+            )
+        });
+        let (Some(alias), Some(variable)) = (alias, variable) else {
+            self.diags.push(
+                Diag::error("expected AS and a variable after the UNWIND expression")
+                    .with_primary_label(span, "UNWIND needs a variable")
+                    .with_code("P_CYPHER"),
+            );
+            self.statement_unsupported = true;
+            self.copy();
+            return;
+        };
+        self.push(TokenKind::For, span);
+        self.out.push(variable.clone());
+        self.push(TokenKind::In, tokens[alias].span.clone());
+        self.consumed.extend([alias, alias + 1]);
+        self.pos += 1;
+    }
+
+    /// `exists((pattern))` becomes `EXISTS { pattern }` and `exists(expr)`
+    /// becomes `(expr IS NOT NULL)`.
+    fn lower_exists(&mut self) {
+        let tokens = self.tokens;
+        let open = tokens[self.pos + 1].span.clone();
+        if self.kind_at(self.pos + 2) == Some(&TokenKind::LParen) {
+            self.push(TokenKind::Exists, tokens[self.pos].span.clone());
+            self.push(TokenKind::LBrace, open);
+            self.closers.push(Closer::PatternExists);
+        } else {
+            self.push(TokenKind::LParen, open);
+            self.closers.push(Closer::PropertyExists);
+        }
+        self.pos += 2;
+    }
+
+    fn open_bracket(&mut self) {
+        if self.is_relationship_open(self.pos) {
+            self.closers.push(Closer::Relationship(None));
+        } else {
+            if self.kind_at(self.pos + 1) == Some(&TokenKind::LParen)
+                && let Some(close) = self.matching_close(self.pos + 1)
+                && matches!(
+                    self.kind_at(close + 1),
+                    Some(TokenKind::Minus | TokenKind::LeftArrow | TokenKind::Arrow)
+                )
+            {
+                let span = self.tokens[self.pos].span.clone();
+                self.unsupported("a pattern comprehension", span, None);
+            }
+            self.closers.push(Closer::Verbatim);
+        }
+        self.copy();
+    }
+
+    fn close(&mut self) {
+        let span = self.tokens[self.pos].span.clone();
+        match self.closers.pop() {
+            None | Some(Closer::Verbatim) | Some(Closer::Relationship(None)) => self.copy(),
+            Some(Closer::Relationship(Some(quantifier))) => {
+                self.copy();
+                self.pending_quantifier = Some(quantifier);
+            }
+            Some(Closer::PatternExists) => {
+                self.push(TokenKind::RBrace, span);
+                self.pos += 1;
+            }
+            Some(Closer::PropertyExists) => {
+                let at = span.start..span.start;
+                self.push(TokenKind::Is, at.clone());
+                self.push(TokenKind::Not, at.clone());
+                self.push(TokenKind::Null, at);
+                self.push(TokenKind::RParen, span);
+                self.pos += 1;
+            }
+            Some(Closer::Dropped) => self.pos += 1,
+        }
+    }
+
+    /// Lowers the `*`, `*n`, `*n..m`, `*..m` and `*n..` ranges of a
+    /// variable-length relationship to a GQL quantifier.
+    fn lower_range(&mut self) {
+        let tokens = self.tokens;
+        let star = tokens[self.pos].span.clone();
+        let mut cursor = self.pos + 1;
+        let integer = |cursor: usize| match tokens.get(cursor) {
+            Some(
+                token @ Token {
+                    kind: TokenKind::IntegerLiteral(_),
+                    ..
+                },
+            ) => Some(token.clone()),
+            _ => None,
+        };
+
+        let min = integer(cursor);
+        if min.is_some() {
+            cursor += 1;
+        }
+        let range = tokens.get(cursor).map(|token| &token.kind) == Some(&TokenKind::DotDot);
+        let max = if range {
+            cursor += 1;
+            let max = integer(cursor);
+            if max.is_some() {
+                cursor += 1;
+            }
+            max
+        } else {
+            None
+        };
+
+        let end = tokens[cursor - 1].span.end;
+        let brace = |kind| Token::new(kind, star.clone());
+        let quantifier = match (min, range, max) {
+            (None, false, _) => vec![Token::new(TokenKind::Plus, star.start..end)],
+            (Some(exact), false, _) => {
+                vec![brace(TokenKind::LBrace), exact, brace(TokenKind::RBrace)]
+            }
+            (min, true, max) => {
+                // Cypher ranges start at one when the lower bound is omitted.
+                let min = min.unwrap_or_else(|| {
+                    Token::new(TokenKind::IntegerLiteral("1".into()), star.end..star.end)
+                });
+                let mut quantifier = vec![brace(TokenKind::LBrace), min, brace(TokenKind::Comma)];
+                quantifier.extend(max);
+                quantifier.push(brace(TokenKind::RBrace));
+                quantifier
+            }
+        };
+        if let Some(Closer::Relationship(slot)) = self.closers.last_mut() {
+            *slot = Some(quantifier);
+        }
+        self.pos = cursor;
+    }
+
+    /// `:A:B` becomes `:A&B`, and the repeated colon of `[:R|:S]` is dropped.
+    fn lower_colon(&mut self) {
+        let previous = self.pos.checked_sub(1).and_then(|pos| self.kind_at(pos));
+        let before = self.pos.checked_sub(2).and_then(|pos| self.kind_at(pos));
+        match (before, previous) {
+            (_, Some(TokenKind::Pipe)) => self.pos += 1,
+            (Some(TokenKind::Colon), Some(label)) if is_name(label) => {
+                self.push(TokenKind::Ampersand, self.tokens[self.pos].span.clone());
+                self.pos += 1;
+            }
+            _ => self.copy(),
+        }
+    }
+
+    /// `-->`, `<--` and `--` between node patterns become the GQL
+    /// abbreviations `->`, `<-` and `-`.
+    fn lower_abbreviated_edge(&mut self) {
+        let tokens = self.tokens;
+        let token = &tokens[self.pos];
+        let after_node =
+            self.pos.checked_sub(1).and_then(|pos| self.kind_at(pos)) == Some(&TokenKind::RParen);
+        let merged = match (&token.kind, self.kind_at(self.pos + 1)) {
+            (TokenKind::Minus, Some(TokenKind::Minus)) => Some(TokenKind::Minus),
+            (TokenKind::Minus, Some(TokenKind::Arrow)) => Some(TokenKind::Arrow),
+            (TokenKind::LeftArrow, Some(TokenKind::Minus)) => Some(TokenKind::LeftArrow),
+            _ => None,
+        };
+        match merged {
+            Some(kind) if after_node => {
+                self.push(kind, token.span.start..tokens[self.pos + 1].span.end);
+                self.pos += 2;
+            }
+            _ => self.copy(),
+        }
+    }
+
+    fn report_in(&mut self) {
+        let tokens = self.tokens;
+        let span = tokens[self.pos].span.clone();
+        let kind_before = |offset: usize| {
+            self.pos
+                .checked_sub(offset)
+                .and_then(|pos| self.kind_at(pos))
+        };
+        let construct = match (kind_before(1), kind_before(2), kind_before(3)) {
+            (Some(TokenKind::RBrace), _, _) => "CALL { ... } IN TRANSACTIONS",
+            (Some(name), Some(TokenKind::LBracket), _) if is_name(name) => "a list comprehension",
+            (Some(name), Some(TokenKind::LParen), Some(function))
+                if is_name(name) && is_list_predicate(function) =>
+            {
+                "a list predicate (all, any, none, single)"
+            }
+            _ => "the IN list membership predicate",
+        };
+        self.unsupported(construct, span, None);
+        self.copy();
+    }
+
+    /// Skips an unsupported clause, so that nothing inside it is reported
+    /// again.
+    fn skip_clause(&mut self) {
+        let mut depth = 0usize;
+        self.pos += 1;
+        while let Some(kind) = self.kind_at(self.pos) {
+            match kind {
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                kind if is_closing(kind) => match depth.checked_sub(1) {
+                    Some(outer) => depth = outer,
+                    None => return,
+                },
+                TokenKind::Eof => return,
+                TokenKind::Semicolon if depth == 0 => return,
+                // `MERGE ... ON CREATE SET ...` and `ON MATCH SET ...`.
+                kind if depth == 0
+                    && word_matches(kind, "on")
+                    && matches!(
+                        self.kind_at(self.pos + 1),
+                        Some(TokenKind::Create | TokenKind::Match)
+                    ) =>
+                {
+                    self.pos += 1;
+                }
+                _ if depth == 0 && self.is_clause_start(self.pos) => return,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Returns whether the `MATCH` at the current position has more than one
+    /// relationship or a variable-length relationship.
+    fn needs_relationship_isomorphism(&self) -> bool {
+        let tokens = self.tokens;
+        let mut depth = 0usize;
+        let mut relationship_depth = None;
+        let mut relationships = 0usize;
+        let mut cursor = self.pos;
+        while let Some(token) = tokens.get(cursor) {
+            match &token.kind {
+                TokenKind::LBracket => {
+                    if self.is_relationship_open(cursor) {
+                        relationships += 1;
+                        relationship_depth = Some(depth);
+                    }
+                    depth += 1;
+                }
+                TokenKind::LParen | TokenKind::LBrace => depth += 1,
+                kind if is_closing(kind) => {
+                    let Some(outer) = depth.checked_sub(1) else {
+                        break;
+                    };
+                    depth = outer;
+                    if relationship_depth == Some(depth) {
+                        relationship_depth = None;
+                    }
+                }
+                TokenKind::Star if relationship_depth.is_some() => return true,
+                TokenKind::Minus | TokenKind::LeftArrow
+                    if matches!(
+                        (&token.kind, self.kind_at(cursor + 1)),
+                        (TokenKind::Minus, Some(TokenKind::Minus | TokenKind::Arrow))
+                            | (TokenKind::LeftArrow, Some(TokenKind::Minus))
+                    ) && self.kind_at(cursor.wrapping_sub(1)) == Some(&TokenKind::RParen) =>
+                {
+                    relationships += 1;
+                    cursor += 1;
+                }
+                TokenKind::Where | TokenKind::Semicolon | TokenKind::Eof if depth == 0 => break,
+                _ if depth == 0 && cursor > self.pos && self.is_clause_start(cursor) => break,
+                _ => {}
+            }
+            cursor += 1;
+        }
+        relationships > 1
+    }
+
+    /// Finds the first token at the current clause's depth matching
+    /// `predicate`, stopping at the end of the clause.
+    fn find_at_clause_depth(
+        &self,
+        start: usize,
+        predicate: impl Fn(&TokenKind) -> bool,
+    ) -> Option<usize> {
+        let mut depth = 0usize;
+        for (pos, token) in self.tokens.iter().enumerate().skip(start) {
+            match &token.kind {
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                kind if is_closing(kind) => depth = depth.checked_sub(1)?,
+                kind if depth == 0 && predicate(kind) => return Some(pos),
+                TokenKind::Semicolon | TokenKind::Eof => return None,
+                _ if depth == 0 && self.is_clause_start(pos) => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn matching_close(&self, open: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        for (pos, token) in self.tokens.iter().enumerate().skip(open) {
+            match &token.kind {
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                kind if is_closing(kind) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(pos);
+                    }
+                }
+                TokenKind::Eof => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn is_clause_start(&self, pos: usize) -> bool {
+        match self.kind_at(pos) {
+            Some(
+                TokenKind::Match
+                | TokenKind::Optional
+                | TokenKind::With
+                | TokenKind::Return
+                | TokenKind::Create
+                | TokenKind::Set
+                | TokenKind::Remove
+                | TokenKind::Delete
+                | TokenKind::Detach
+                | TokenKind::Call
+                | TokenKind::Union
+                | TokenKind::Drop,
+            ) => true,
+            Some(TokenKind::Identifier(word)) => ["unwind", "merge", "foreach", "load"]
+                .iter()
+                .any(|clause| word.eq_ignore_ascii_case(clause)),
+            _ => false,
+        }
+    }
+
+    fn is_relationship_open(&self, pos: usize) -> bool {
+        self.kind_at(pos) == Some(&TokenKind::LBracket)
+            && matches!(
+                pos.checked_sub(1).and_then(|pos| self.kind_at(pos)),
+                Some(TokenKind::Minus | TokenKind::LeftArrow)
+            )
+    }
+
+    /// Returns whether the next token has `kind` and directly follows the
+    /// current one.
+    fn adjacent(&self, kind: TokenKind) -> bool {
+        self.tokens.get(self.pos + 1).is_some_and(|next| {
+            next.kind == kind && next.span.start == self.tokens[self.pos].span.end
+        })
+    }
+
+    fn kind_at(&self, pos: usize) -> Option<&TokenKind> {
+        self.tokens.get(pos).map(|token| &token.kind)
+    }
+
+    fn word_at(&self, pos: usize) -> String {
+        match self.kind_at(pos) {
+            Some(TokenKind::Identifier(name)) => name.to_ascii_uppercase(),
+            Some(kind) => kind.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn unsupported(&mut self, construct: &str, span: Span, help: Option<&str>) {
+        let mut diag = Diag::error(format!("{construct} has no GQL equivalent"))
+            .with_primary_label(span, "openCypher only")
+            .with_code("P_CYPHER");
+        if let Some(help) = help {
+            diag = diag.with_help(help);
+        }
+        self.diags.push(diag);
+        self.statement_unsupported = true;
+    }
+
+    fn copy(&mut self) {
+        let token = self.tokens[self.pos].clone();
+        self.emit(token);
+        self.pos += 1;
+    }
+
+    fn push(&mut self, kind: TokenKind, span: Span) {
+        self.emit(Token::new(kind, span));
+    }
+
+    fn emit(&mut self, token: Token) {
+        let ends_edge = matches!(token.kind, TokenKind::Minus | TokenKind::Arrow);
+        self.out.push(token);
+        if let Some(quantifier) = self.pending_quantifier.take()
+            && ends_edge
+        {
+            self.out.extend(quantifier);
+        }
+    }
+}
+
+fn is_closing(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace
+    )
+}
+
+fn is_name(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Identifier(_) | TokenKind::DelimitedIdentifier(_)
+    ) || kind.is_keyword()
+}
+
+fn is_list_predicate(kind: &TokenKind) -> bool {
+    matches!(kind, TokenKind::All | TokenKind::Any)
+        || word_matches(kind, "none")
+        || word_matches(kind, "single")
+}
+
+/// Returns whether `kind` is the word `word`, which the GQL lexer may have
+/// classified as an identifier or as a keyword it has no token for.
+fn word_matches(kind: &TokenKind, word: &str) -> bool {
+    match kind {
+        TokenKind::Identifier(name)
+        | TokenKind::ReservedKeyword(name)
+        | TokenKind::PreReservedKeyword(name)
+        | TokenKind::NonReservedKeyword(name) => name.eq_ignore_ascii_case(word),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lower_cypher_tokens;
+    use crate::lexer::token::TokenKind;
+    use crate::tokenize;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .tokens
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    fn assert_lowers_to(cypher: &str, gql: &str) {
+        let (tokens, diagnostics) = lower_cypher_tokens(&tokenize(cypher).tokens);
+        assert!(diagnostics.is_empty(), "{cypher}: {diagnostics:?}");
+        let lowered: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(lowered, kinds(gql), "{cypher}");
+    }
+
+    #[test]
+    fn lowers_clauses() {
+        assert_lowers_to(
+            "UNWIND [1, 2] AS x CREATE (:N {v: x})",
+            "FOR x IN [1, 2] INSERT (:N {v: x})",
+        );
+        assert_lowers_to(
+            "MATCH (n) WITH n, count(*) AS c ORDER BY c LIMIT 3 WHERE c > 1 RETURN n",
+            "MATCH (n) RETURN n, count(*) AS c ORDER BY c LIMIT 3 NEXT FILTER c > 1 RETURN n",
+        );
+        assert_lowers_to(
+            "MATCH (n) WITH n MATCH (n)-->(m) RETURN m",
+            "MATCH (n) RETURN n NEXT MATCH (n)->(m) RETURN m",
+        );
+    }
+
+    #[test]
+    fn lowers_patterns() {
+        assert_lowers_to(
+            "MATCH (a:A:B)-[r:R|:S*2..]->(b)<--(c) RETURN a",
+            "MATCH DIFFERENT EDGES (a:A&B)-[r:R|S]->{2,}(b)<-(c) RETURN a",
+        );
+        assert_lowers_to(
+            "MATCH p = shortestPath((a)-[*..5]-(b)) RETURN p",
+            "MATCH DIFFERENT EDGES p = ANY SHORTEST (a)-[]-{1,5}(b) RETURN p",
+        );
+        assert_lowers_to(
+            "MATCH (a)<-[*]-(b) RETURN a",
+            "MATCH DIFFERENT EDGES (a)<-[]-+(b) RETURN a",
+        );
+    }
+
+    #[test]
+    fn lowers_functions() {
+        assert_lowers_to(
+            "MATCH (n) WHERE exists(n.name) AND exists((n)-->()) RETURN collect(toUpper(n.name))",
+            "MATCH (n) WHERE (n.name IS NOT NULL) AND EXISTS { (n)->() } \
+             RETURN COLLECT_LIST(UPPER(n.name))",
+        );
+    }
+
+    #[test]
+    fn drops_unsupported_statements() {
+        let (tokens, diagnostics) =
+            lower_cypher_tokens(&tokenize("MERGE (n:N); MATCH (n) RETURN n").tokens);
+        assert_eq!(diagnostics.len(), 1);
+        let lowered: Vec<_> = tokens.into_iter().map(|token| token.kind).collect();
+        assert_eq!(lowered, kinds("MATCH (n) RETURN n"));
+    }
+}
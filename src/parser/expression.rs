@@ -16,6 +16,7 @@ use crate::ast::{
 use crate::diag::Diag;
 use crate::lexer::token::{Token, TokenKind};
 use crate::parser::base::{ParseResult, TokenStream};
+use crate::parser::patterns::parse_label_expression;
 use crate::parser::procedure::parse_nested_query_specification;
 use crate::parser::types::parse_value_type_prefix;
 use smol_str::SmolStr;
@@ -96,7 +97,26 @@ impl<'a> ExpressionParser<'a> {
     fn parse_is_expression(&mut self) -> ParseResult<Expression> {
         let mut expr = self.parse_comparison_expression()?;
 
-        while self.stream.check(&TokenKind::Is) {
+        loop {
+            // `n:Label` is shorthand for `n IS LABELED Label` on element variables.
+            if self.stream.check(&TokenKind::Colon)
+                && matches!(expr, Expression::VariableReference(_, _))
+            {
+                self.stream.advance();
+                let label = self.parse_label_expression()?;
+                let span = expr.span().start..label.span().end;
+                expr = Expression::Predicate(Predicate::IsLabeled(
+                    Box::new(expr),
+                    Some(label),
+                    false,
+                    span,
+                ));
+                continue;
+            }
+
+            if !self.stream.check(&TokenKind::Is) {
+                break;
+            }
             self.stream.advance();
 
             let negated = if self.stream.check(&TokenKind::Not) {
@@ -139,14 +159,22 @@ impl<'a> ExpressionParser<'a> {
                 TokenKind::Labeled => {
                     let labeled_span = self.stream.current().span.clone();
                     self.stream.advance();
-                    let label = if self.stream.check(&TokenKind::Colon) {
+                    let label = if self.stream.consume(&TokenKind::Colon)
+                        || matches!(
+                            self.stream.current().kind,
+                            TokenKind::Identifier(_)
+                                | TokenKind::DelimitedIdentifier(_)
+                                | TokenKind::Bang
+                                | TokenKind::Percent
+                                | TokenKind::LParen
+                        ) {
                         Some(self.parse_label_expression()?)
                     } else {
                         None
                     };
                     let end = label
                         .as_ref()
-                        .map_or(labeled_span.end, |label_expr| label_expr.span.end);
+                        .map_or(labeled_span.end, |label_expr| label_expr.span().end);
                     let span = expr.span().start..end;
                     Expression::Predicate(Predicate::IsLabeled(
                         Box::new(expr),
@@ -1240,18 +1268,14 @@ impl<'a> ExpressionParser<'a> {
     }
 
     fn parse_label_expression(&mut self) -> ParseResult<LabelExpression> {
-        self.stream.expect(TokenKind::Colon)?;
-        let label = match &self.stream.current().kind {
-            TokenKind::Identifier(name) | TokenKind::DelimitedIdentifier(name) => {
-                let label = name.clone();
-                self.stream.advance();
-                label
-            }
-            _ => return Err(self.stream.error_here("expected label name")),
-        };
-
-        let span = self.stream.previous_span();
-        Ok(LabelExpression { label, span })
+        let mut pos = self.stream.position();
+        let (label, diags) = parse_label_expression(self.stream.tokens(), &mut pos);
+        self.stream.set_position(pos);
+        match (label, diags.into_iter().next()) {
+            (Some(label), None) => Ok(label),
+            (_, Some(diag)) => Err(Box::new(diag)),
+            (None, None) => Err(self.stream.error_here("expected label expression")),
+        }
     }
 
     fn is_comparison_operator(&self) -> bool {
@@ -1730,6 +1754,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parses_labeled_predicates() {
+        assert!(matches!(
+            parse_expr("n:Person|Org").unwrap(),
+            Expression::Predicate(Predicate::IsLabeled(
+                _,
+                Some(LabelExpression::Disjunction { .. }),
+                false,
+                _
+            ))
+        ));
+        assert!(matches!(
+            parse_expr("n IS LABELED Person").unwrap(),
+            Expression::Predicate(Predicate::IsLabeled(
+                _,
+                Some(LabelExpression::LabelName { .. }),
+                false,
+                _
+            ))
+        ));
+        assert!(matches!(
+            parse_expr("n IS NOT LABELED :!Person").unwrap(),
+            Expression::Predicate(Predicate::IsLabeled(
+                _,
+                Some(LabelExpression::Negation { .. }),
+                true,
+                _
+            ))
+        ));
+        assert!(matches!(
+            parse_expr("n IS LABELED").unwrap(),
+            Expression::Predicate(Predicate::IsLabeled(_, None, false, _))
+        ));
+    }
+
     #[test]
     fn parses_type_annotation_forms() {
         assert!(matches!(
@@ -89,10 +89,13 @@ use crate::ast::{
     PropertyType, PropertyTypeList, PropertyTypesSpecification, PropertyValueType, Span,
     TypeInheritanceClause,
 };
+use crate::ast::expression::Expression;
 use crate::diag::Diag;
 use crate::lexer::token::{Token, TokenKind};
 use crate::parser::base::{ParseResult, TokenStream, merge_spans};
+use crate::parser::expression::parse_expression;
 use crate::parser::types::TypeParser;
+use smol_str::SmolStr;
 
 /// Parser for graph type specifications.
 pub struct GraphTypeParser<'a> {
@@ -232,10 +235,11 @@ impl<'a> GraphTypeParser<'a> {
         is_abstract: bool,
     ) -> ParseResult<NodeTypeSpec> {
         let saved = self.stream.position();
-        if let Ok((pattern, inheritance)) = self.parse_node_type_pattern() {
+        if let Ok((pattern, inheritance, name)) = self.parse_node_type_pattern() {
             let span = pattern.span.clone();
             return Ok(NodeTypeSpec {
                 is_abstract,
+                name,
                 inheritance,
                 pattern,
                 span,
@@ -243,10 +247,11 @@ impl<'a> GraphTypeParser<'a> {
         }
 
         self.stream.set_position(saved);
-        let (phrase, inheritance) = self.parse_node_type_phrase()?;
+        let (phrase, inheritance, name) = self.parse_node_type_phrase()?;
         let span = phrase.span.clone();
         Ok(NodeTypeSpec {
             is_abstract,
+            name,
             inheritance,
             pattern: NodeTypePattern {
                 phrase,
@@ -256,25 +261,27 @@ impl<'a> GraphTypeParser<'a> {
         })
     }
 
-    /// Parses a node type pattern.
+    /// Parses a node type pattern, returning it with its inheritance clause
+    /// and type name.
     fn parse_node_type_pattern(
         &mut self,
-    ) -> ParseResult<(NodeTypePattern, Option<TypeInheritanceClause>)> {
+    ) -> ParseResult<(NodeTypePattern, Option<TypeInheritanceClause>, Option<SmolStr>)> {
         let start_span = self.stream.current().span.clone();
         let mut inheritance = None;
+        let mut type_name = None;
 
         // Optional leading node synonym/type/name prefix.
         if self.stream.consume(&TokenKind::Node) || self.stream.consume(&TokenKind::Vertex) {
             self.stream.consume(&TokenKind::Type);
-            if self.is_regular_identifier_start() {
-                let _ = self.parse_regular_identifier("node type name")?;
+            if self.is_identifier_start() {
+                type_name = Some(self.parse_identifier("node type name")?.0);
             }
             inheritance = self.parse_inheritance_clause_opt()?;
         }
 
         self.stream.expect(TokenKind::LParen)?;
-        let alias = if self.is_regular_identifier_start() {
-            let (name, span) = self.parse_regular_identifier("node type alias")?;
+        let alias = if self.is_identifier_start() {
+            let (name, span) = self.parse_identifier("node type alias")?;
             Some(LocalNodeTypeAlias { name, span })
         } else {
             None
@@ -306,6 +313,7 @@ impl<'a> GraphTypeParser<'a> {
                 phrase,
             },
             inheritance,
+            type_name,
         ))
     }
 
@@ -314,7 +322,7 @@ impl<'a> GraphTypeParser<'a> {
     /// Syntax: `[NODE [TYPE]] [node_type_filler] [AS alias]`
     fn parse_node_type_phrase(
         &mut self,
-    ) -> ParseResult<(NodeTypePhrase, Option<TypeInheritanceClause>)> {
+    ) -> ParseResult<(NodeTypePhrase, Option<TypeInheritanceClause>, Option<SmolStr>)> {
         let start_span = self.stream.current().span.clone();
 
         let has_node_keyword =
@@ -324,18 +332,18 @@ impl<'a> GraphTypeParser<'a> {
         }
         self.stream.consume(&TokenKind::Type);
 
-        let mut has_phrase_name = false;
-        if self.is_regular_identifier_start() {
-            let _ = self.parse_regular_identifier("node type name")?;
-            has_phrase_name = true;
-        }
+        let type_name = if self.is_identifier_start() {
+            Some(self.parse_identifier("node type name")?.0)
+        } else {
+            None
+        };
         let inheritance = self.parse_inheritance_clause_opt()?;
         let filler = if self.is_node_type_filler_start() {
             Some(self.parse_node_type_filler()?)
         } else {
             None
         };
-        if !has_phrase_name && filler.is_none() {
+        if type_name.is_none() && filler.is_none() {
             return Err(self.error_here("expected node type name or node type filler".to_string()));
         }
 
@@ -359,6 +367,7 @@ impl<'a> GraphTypeParser<'a> {
                 span: merge_spans(&start_span, &end_span),
             },
             inheritance,
+            type_name,
         ))
     }
 
@@ -377,7 +386,7 @@ impl<'a> GraphTypeParser<'a> {
 
     /// Parses a local node type alias.
     fn parse_local_node_type_alias(&mut self) -> ParseResult<LocalNodeTypeAlias> {
-        let (name, span) = self.parse_regular_identifier("identifier for node type alias")?;
+        let (name, span) = self.parse_identifier("identifier for node type alias")?;
         Ok(LocalNodeTypeAlias { name, span })
     }
 
@@ -479,7 +488,7 @@ impl<'a> GraphTypeParser<'a> {
         &mut self,
         is_abstract: bool,
     ) -> ParseResult<EdgeTypeSpec> {
-        let (pattern, inheritance) = self.parse_edge_type_pattern()?;
+        let (pattern, inheritance, name) = self.parse_edge_type_pattern()?;
         let span = match &pattern {
             EdgeTypePattern::Directed(d) => d.span.clone(),
             EdgeTypePattern::Undirected(u) => u.span.clone(),
@@ -487,16 +496,18 @@ impl<'a> GraphTypeParser<'a> {
 
         Ok(EdgeTypeSpec {
             is_abstract,
+            name,
             inheritance,
             pattern,
             span,
         })
     }
 
-    /// Parses an edge type pattern (directed or undirected).
+    /// Parses an edge type pattern (directed or undirected), returning it with
+    /// its inheritance clause and type name.
     fn parse_edge_type_pattern(
         &mut self,
-    ) -> ParseResult<(EdgeTypePattern, Option<TypeInheritanceClause>)> {
+    ) -> ParseResult<(EdgeTypePattern, Option<TypeInheritanceClause>, Option<SmolStr>)> {
         // Check for edge type phrase (keywords before pattern)
         if self.stream.check(&TokenKind::Directed)
             || self.stream.check(&TokenKind::Undirected)
@@ -507,13 +518,14 @@ impl<'a> GraphTypeParser<'a> {
         }
 
         // Otherwise, parse visual edge pattern
-        self.parse_edge_type_visual_pattern()
+        let (pattern, inheritance) = self.parse_edge_type_visual_pattern()?;
+        Ok((pattern, inheritance, None))
     }
 
     /// Parses edge type pattern from phrase keywords.
     fn parse_edge_type_phrase_pattern(
         &mut self,
-    ) -> ParseResult<(EdgeTypePattern, Option<TypeInheritanceClause>)> {
+    ) -> ParseResult<(EdgeTypePattern, Option<TypeInheritanceClause>, Option<SmolStr>)> {
         let start_span = self.stream.current().span.clone();
 
         // Parse edge kind
@@ -535,9 +547,11 @@ impl<'a> GraphTypeParser<'a> {
         self.stream.consume(&TokenKind::Type);
 
         // Optional edge type name
-        if self.is_regular_identifier_start() {
-            let _ = self.parse_regular_identifier("edge type name")?;
-        }
+        let type_name = if self.is_identifier_start() {
+            Some(self.parse_identifier("edge type name")?.0)
+        } else {
+            None
+        };
         let inheritance = self.parse_inheritance_clause_opt()?;
 
         // Parse optional phrase content (labels and properties)
@@ -594,6 +608,7 @@ impl<'a> GraphTypeParser<'a> {
                         span: pattern_span,
                     }),
                     inheritance,
+                    type_name.clone(),
                 ))
             }
             EdgeKind::Directed | EdgeKind::Inferred => {
@@ -609,6 +624,7 @@ impl<'a> GraphTypeParser<'a> {
                         span: pattern_span,
                     }),
                     inheritance,
+                    type_name.clone(),
                 ))
             }
         }
@@ -621,7 +637,7 @@ impl<'a> GraphTypeParser<'a> {
         let start_span = self.stream.current().span.clone();
 
         // Parse left endpoint: node_type_pattern
-        let (left_endpoint, _) = self.parse_node_type_pattern()?;
+        let (left_endpoint, _, _) = self.parse_node_type_pattern()?;
 
         // Check for directed or undirected arc
         let is_directed = self.stream.check(&TokenKind::Minus)
@@ -650,7 +666,7 @@ impl<'a> GraphTypeParser<'a> {
                 });
 
                 // Parse right endpoint
-                let (right_endpoint, _) = self.parse_node_type_pattern()?;
+                let (right_endpoint, _, _) = self.parse_node_type_pattern()?;
                 let end_span = right_endpoint.span.clone();
 
                 Ok((
@@ -677,7 +693,7 @@ impl<'a> GraphTypeParser<'a> {
                 });
 
                 // Parse right endpoint
-                let (right_endpoint, _) = self.parse_node_type_pattern()?;
+                let (right_endpoint, _, _) = self.parse_node_type_pattern()?;
                 let end_span = right_endpoint.span.clone();
 
                 Ok((
@@ -702,7 +718,7 @@ impl<'a> GraphTypeParser<'a> {
             };
 
             // Parse right endpoint
-            let (right_endpoint, _) = self.parse_node_type_pattern()?;
+            let (right_endpoint, _, _) = self.parse_node_type_pattern()?;
             let end_span = right_endpoint.span.clone();
 
             Ok((
@@ -911,8 +927,8 @@ impl<'a> GraphTypeParser<'a> {
 
     /// Parses a node type reference.
     fn parse_node_type_reference(&mut self) -> ParseResult<NodeTypeReference> {
-        let node_type = if self.is_regular_identifier_start() {
-            let (name, span) = self.parse_regular_identifier("node type alias")?;
+        let node_type = if self.is_identifier_start() {
+            let (name, span) = self.parse_identifier("node type alias")?;
             NodeTypePattern {
                 phrase: NodeTypePhrase {
                     filler: None,
@@ -993,7 +1009,8 @@ impl<'a> GraphTypeParser<'a> {
 
     /// Parses a property type.
     ///
-    /// Syntax: `property_name [:: | TYPED] value_type [NOT NULL]`
+    /// Syntax:
+    /// `property_name [:: | TYPED] value_type [NOT NULL] [DEFAULT value] [CHECK (condition)]`
     fn parse_property_type(&mut self) -> ParseResult<PropertyType> {
         let name = self.parse_property_name()?;
         let name_span = name.span.clone();
@@ -1013,7 +1030,33 @@ impl<'a> GraphTypeParser<'a> {
             false
         };
 
-        let end_span = if not_null {
+        let default = if self.consume_word("DEFAULT") {
+            Some(Box::new(self.parse_property_default()?))
+        } else {
+            None
+        };
+
+        let check = if self.check_word("CHECK") {
+            let check_span = self.stream.current().span.clone();
+            self.stream.advance();
+            if !self.stream.check(&TokenKind::LParen) {
+                return Err(self.error_here("expected '(' after CHECK".to_string()));
+            }
+            let (segments, end_span) = self.parse_constraint_arguments()?;
+            let [(start, end)] = segments[..] else {
+                return Err(Box::new(
+                    Diag::error("CHECK constraint takes exactly one condition").with_primary_label(
+                        merge_spans(&check_span, &end_span),
+                        "expected a single condition",
+                    ),
+                ));
+            };
+            Some(Box::new(parse_expression(&self.stream.tokens()[start..end])?))
+        } else {
+            None
+        };
+
+        let end_span = if not_null || default.is_some() || check.is_some() {
             self.stream.tokens()[self.stream.position().saturating_sub(1)]
                 .span
                 .clone()
@@ -1025,13 +1068,40 @@ impl<'a> GraphTypeParser<'a> {
             name,
             value_type,
             not_null,
+            default,
+            check,
             span: merge_spans(&name_span, &end_span),
         })
     }
 
+    /// Parses the value of a DEFAULT clause, which extends to the next
+    /// top-level `,`, `}` or CHECK.
+    fn parse_property_default(&mut self) -> ParseResult<Expression> {
+        let start = self.stream.position();
+        let mut depth = 0usize;
+        while !self.stream.check(&TokenKind::Eof) {
+            match self.stream.current().kind {
+                TokenKind::Comma | TokenKind::RBrace if depth == 0 => break,
+                _ if depth == 0 && self.check_word("CHECK") => break,
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                    depth = depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+            self.stream.advance();
+        }
+
+        let end = self.stream.position();
+        if start == end {
+            return Err(self.error_here("expected a value after DEFAULT".to_string()));
+        }
+        parse_expression(&self.stream.tokens()[start..end])
+    }
+
     /// Parses a property name.
     fn parse_property_name(&mut self) -> ParseResult<PropertyName> {
-        let (name, span) = self.parse_regular_identifier("property name identifier")?;
+        let (name, span) = self.parse_identifier("property name identifier")?;
         Ok(PropertyName { name, span })
     }
 
@@ -1117,7 +1187,7 @@ impl<'a> GraphTypeParser<'a> {
 
     /// Parses a label name.
     fn parse_label_name(&mut self) -> ParseResult<LabelName> {
-        let (name, span) = self.parse_regular_identifier("label name identifier")?;
+        let (name, span) = self.parse_identifier("label name identifier")?;
         Ok(LabelName { name, span })
     }
 
@@ -1195,65 +1265,95 @@ impl<'a> GraphTypeParser<'a> {
         }
 
         let (raw_name, name_span) = self.parse_type_reference_identifier("constraint name")?;
-        let mut arguments = if self.stream.check(&TokenKind::LParen) {
-            self.parse_constraint_arguments()?
-        } else {
-            Vec::new()
-        };
+        let upper = raw_name.to_ascii_uppercase();
 
-        // Normalize a standalone KEY constraint with no argument list to a single
-        // synthetic argument if KEY-style label-set syntax follows.
-        if raw_name.eq_ignore_ascii_case("KEY")
-            && arguments.is_empty()
-            && self.is_regular_identifier_start()
-        {
-            let (name, span) = self.parse_regular_identifier("constraint argument")?;
-            arguments.push(GraphTypeConstraintArgument { raw: name, span });
+        // A standalone KEY followed by a bare property name is shorthand for
+        // a single-property key.
+        if upper == "KEY" && !self.stream.check(&TokenKind::LParen) {
+            if !self.is_identifier_start() {
+                return Ok(GraphTypeConstraint::Key {
+                    properties: Vec::new(),
+                    span: merge_spans(&start_span, &name_span),
+                });
+            }
+            let (name, span) = self.parse_identifier("constraint argument")?;
+            return Ok(GraphTypeConstraint::Key {
+                properties: vec![PropertyName {
+                    name,
+                    span: span.clone(),
+                }],
+                span: merge_spans(&start_span, &span),
+            });
         }
 
-        let end_span = arguments
-            .last()
-            .map(|argument| argument.span.clone())
-            .unwrap_or(name_span.clone());
+        let (segments, end_span) = if self.stream.check(&TokenKind::LParen) {
+            self.parse_constraint_arguments()?
+        } else if upper == "CHECK" {
+            return Err(self.error_here("expected '(' after CHECK".to_string()));
+        } else {
+            (Vec::new(), name_span)
+        };
         let span = merge_spans(&start_span, &end_span);
-        let upper = raw_name.to_ascii_uppercase();
 
         Ok(match upper.as_str() {
-            "KEY" => GraphTypeConstraint::Key { arguments, span },
-            "UNIQUE" => GraphTypeConstraint::Unique { arguments, span },
-            "MANDATORY" => GraphTypeConstraint::Mandatory { arguments, span },
-            "CHECK" => GraphTypeConstraint::Check { arguments, span },
+            "KEY" => GraphTypeConstraint::Key {
+                properties: self.build_constraint_properties(&segments)?,
+                span,
+            },
+            "UNIQUE" => GraphTypeConstraint::Unique {
+                properties: self.build_constraint_properties(&segments)?,
+                span,
+            },
+            "MANDATORY" => GraphTypeConstraint::Mandatory {
+                properties: self.build_constraint_properties(&segments)?,
+                span,
+            },
+            "CHECK" => {
+                let [(start, end)] = segments[..] else {
+                    return Err(Box::new(
+                        Diag::error("CHECK constraint takes exactly one condition")
+                            .with_primary_label(span, "expected a single condition"),
+                    ));
+                };
+                let condition = parse_expression(&self.stream.tokens()[start..end])?;
+                GraphTypeConstraint::Check {
+                    condition: Box::new(condition),
+                    span,
+                }
+            }
             _ => GraphTypeConstraint::Custom {
                 name: raw_name,
-                arguments,
+                arguments: segments
+                    .iter()
+                    .map(|&(start, end)| self.build_constraint_argument(start, end))
+                    .collect::<ParseResult<_>>()?,
                 span,
             },
         })
     }
 
-    fn parse_constraint_arguments(&mut self) -> ParseResult<Vec<GraphTypeConstraintArgument>> {
+    /// Parses a parenthesized, comma-separated argument list, returning the
+    /// token range of each argument and the span of the closing parenthesis.
+    fn parse_constraint_arguments(&mut self) -> ParseResult<(Vec<(usize, usize)>, Span)> {
         self.stream.expect(TokenKind::LParen)?;
-        let mut arguments = Vec::new();
+        let mut segments = Vec::new();
         let mut segment_start = self.stream.position();
         let mut depth = 0usize;
 
         while !self.stream.check(&TokenKind::Eof) {
             if depth == 0 && self.stream.check(&TokenKind::RParen) {
                 if segment_start < self.stream.position() {
-                    arguments.push(
-                        self.build_constraint_argument(segment_start, self.stream.position())?,
-                    );
+                    segments.push((segment_start, self.stream.position()));
                 }
+                let end_span = self.stream.current().span.clone();
                 self.stream.advance();
-                return Ok(arguments);
+                return Ok((segments, end_span));
             }
 
             let current_kind = self.stream.current().kind.clone();
             if depth == 0 && matches!(current_kind, TokenKind::Comma) {
                 if segment_start < self.stream.position() {
-                    arguments.push(
-                        self.build_constraint_argument(segment_start, self.stream.position())?,
-                    );
+                    segments.push((segment_start, self.stream.position()));
                 }
                 self.stream.advance();
                 segment_start = self.stream.position();
@@ -1274,6 +1374,45 @@ impl<'a> GraphTypeParser<'a> {
         Err(self.error_here("expected ')' to close constraint argument list".to_string()))
     }
 
+    /// Interprets each argument as a single property name.
+    fn build_constraint_properties(
+        &self,
+        segments: &[(usize, usize)],
+    ) -> ParseResult<Vec<PropertyName>> {
+        segments
+            .iter()
+            .map(|&(start, end)| {
+                let tokens = &self.stream.tokens()[start..end];
+                match tokens {
+                    [token] => match &token.kind {
+                        TokenKind::Identifier(name)
+                        | TokenKind::DelimitedIdentifier(name)
+                        | TokenKind::ReservedKeyword(name)
+                        | TokenKind::PreReservedKeyword(name)
+                        | TokenKind::NonReservedKeyword(name) => Ok(PropertyName {
+                            name: name.clone(),
+                            span: token.span.clone(),
+                        }),
+                        kind if kind.is_keyword() => Ok(PropertyName {
+                            name: kind.to_string().into(),
+                            span: token.span.clone(),
+                        }),
+                        _ => Err(Box::new(
+                            Diag::error("expected property name in constraint")
+                                .with_primary_label(token.span.clone(), "not a property name"),
+                        )),
+                    },
+                    _ => Err(Box::new(
+                        Diag::error("expected property name in constraint").with_primary_label(
+                            merge_spans(&tokens[0].span, &tokens[tokens.len() - 1].span),
+                            "not a property name",
+                        ),
+                    )),
+                }
+            })
+            .collect()
+    }
+
     fn build_constraint_argument(
         &self,
         start: usize,
@@ -1331,13 +1470,15 @@ impl<'a> GraphTypeParser<'a> {
         }
     }
 
-    fn parse_regular_identifier(
+    /// Parses a regular or delimited identifier. Non-reserved keywords are
+    /// accepted as regular identifiers.
+    fn parse_identifier(
         &mut self,
         expected: &str,
     ) -> ParseResult<(smol_str::SmolStr, Span)> {
         let token = self.stream.current().clone();
         match &token.kind {
-            TokenKind::Identifier(name) => {
+            TokenKind::Identifier(name) | TokenKind::DelimitedIdentifier(name) => {
                 self.stream.advance();
                 Ok((name.clone(), token.span))
             }
@@ -1349,8 +1490,11 @@ impl<'a> GraphTypeParser<'a> {
         }
     }
 
-    fn is_regular_identifier_start(&self) -> bool {
-        matches!(self.stream.current().kind, TokenKind::Identifier(_))
+    fn is_identifier_start(&self) -> bool {
+        matches!(
+            self.stream.current().kind,
+            TokenKind::Identifier(_) | TokenKind::DelimitedIdentifier(_)
+        )
             || self
                 .stream
                 .current()
@@ -4,6 +4,7 @@
 //! an AST while preserving diagnostics and recovering at statement boundaries.
 
 pub mod base;
+mod cypher;
 pub mod expression;
 pub mod graph_type;
 pub mod mutation;
@@ -57,7 +58,20 @@ impl<'source> Parser<'source> {
     }
 
     /// Parses the token stream into a GQL program AST.
-    pub fn parse(mut self) -> ParseResult {
+    pub fn parse(self) -> ParseResult {
+        let source = SourceFile::new(self.source);
+        let (ast, diagnostics) = self.parse_with_diagnostics();
+        let reports = convert_diagnostics_to_reports(&diagnostics, &source);
+
+        ParseResult {
+            ast,
+            diagnostics: reports,
+        }
+    }
+
+    /// Parses the token stream, returning diagnostics before they are
+    /// rendered as reports.
+    pub(crate) fn parse_with_diagnostics(mut self) -> (Option<Program>, Vec<Diag>) {
         let (program, parser_diags) =
             program::parse_program_tokens(&self.tokens, self.source.len());
         self.diagnostics.extend(parser_diags);
@@ -70,14 +84,19 @@ impl<'source> Parser<'source> {
         } else {
             Some(program)
         };
+        (ast, self.diagnostics)
+    }
 
-        let source = SourceFile::new(self.source);
-        let reports = convert_diagnostics_to_reports(&self.diagnostics, &source);
-
-        ParseResult {
-            ast,
-            diagnostics: reports,
-        }
+    /// Parses the token stream as openCypher, lowering it to the GQL AST.
+    ///
+    /// Clauses with a direct GQL counterpart are rewritten to it, such as
+    /// `UNWIND` to `FOR` and `WITH` to `RETURN ... NEXT`. Constructs without
+    /// one, such as `MERGE`, are reported and their statements skipped.
+    pub fn parse_cypher(mut self) -> ParseResult {
+        let (tokens, diagnostics) = cypher::lower_cypher_tokens(&self.tokens);
+        self.tokens = tokens;
+        self.diagnostics.extend(diagnostics);
+        self.parse()
     }
 
     /// Merges lexer diagnostics with parser diagnostics.
@@ -54,6 +54,16 @@ pub fn parse_graph_pattern_binding_table(
     (table, parser.diags)
 }
 
+/// Parses a label expression (`A|B`, `A&!B`, `%`, ...) starting at the given position.
+pub fn parse_label_expression(tokens: &[Token], pos: &mut usize) -> ParseResult<LabelExpression> {
+    let mut stream = TokenStream::new(tokens);
+    stream.set_position(*pos);
+    let mut parser = PatternParser::new(stream);
+    let expression = parser.parse_label_expression();
+    *pos = parser.stream.position();
+    (expression, parser.diags)
+}
+
 #[derive(Clone, Copy)]
 enum FillerTerminator {
     RParen,
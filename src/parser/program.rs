@@ -52,19 +52,22 @@ pub(crate) fn parse_program_tokens(tokens: &[Token], source_len: usize) -> (Prog
     let mut statements = Vec::new();
     let mut diagnostics = Vec::new();
     let mut cursor = 0usize;
+    // Whether the last separator seen was NEXT rather than `;`
+    let mut after_next = false;
 
     while cursor < tokens.len() {
         let start_cursor = cursor;
         let mut syntax = classify(&tokens[cursor].kind);
 
-        // Special case: MATCH followed by mutation keywords should be treated as a mutation
-        if syntax == SyntaxToken::QueryStart && is_match_starting_mutation(tokens, cursor) {
+        // Special case: MATCH, FILTER, LET, or FOR followed by mutation keywords is a mutation
+        if syntax == SyntaxToken::QueryStart && is_query_starting_mutation(tokens, cursor) {
             syntax = SyntaxToken::MutationStart;
         }
 
         match syntax {
             SyntaxToken::Eof => break,
             SyntaxToken::Semicolon => {
+                after_next = tokens[cursor].kind == TokenKind::Next;
                 cursor += 1;
             }
             SyntaxToken::Other => {
@@ -85,9 +88,15 @@ pub(crate) fn parse_program_tokens(tokens: &[Token], source_len: usize) -> (Prog
                 let statement_tokens = &tokens[cursor..end];
                 let (statement_opt, mut statement_diags) = parse_statement(class, statement_tokens);
                 append_diags_dedup(&mut diagnostics, &mut statement_diags);
-                if let Some(statement) = statement_opt {
+                if let Some(mut statement) = statement_opt {
+                    match &mut statement {
+                        Statement::Query(query) => query.follows_next = after_next,
+                        Statement::Mutation(mutation) => mutation.follows_next = after_next,
+                        _ => {}
+                    }
                     statements.push(statement);
                 }
+                after_next = false;
                 cursor = end;
             }
         }
@@ -140,10 +149,14 @@ fn classify(kind: &TokenKind) -> SyntaxToken {
     }
 }
 
-/// Checks if a MATCH statement is actually the start of a mutation by looking ahead
-/// for mutation keywords (SET, DELETE, REMOVE) that would make it a linear data-modifying statement.
-fn is_match_starting_mutation(tokens: &[Token], start: usize) -> bool {
-    if !matches!(tokens.get(start).map(|t| &t.kind), Some(TokenKind::Match)) {
+/// Checks if a MATCH, FILTER, LET, or FOR statement is actually the start of a mutation by
+/// looking ahead for mutation keywords (SET, DELETE, REMOVE) that would make it a linear
+/// data-modifying statement.
+fn is_query_starting_mutation(tokens: &[Token], start: usize) -> bool {
+    if !matches!(
+        tokens.get(start).map(|t| &t.kind),
+        Some(TokenKind::Match | TokenKind::Filter | TokenKind::Let | TokenKind::For)
+    ) {
         return false;
     }
 
@@ -288,7 +301,7 @@ fn find_statement_end(tokens: &[Token], start: usize, class: StatementClass) ->
 
     while cursor < tokens.len() {
         let kind = &tokens[cursor].kind;
-        if matches!(kind, TokenKind::Semicolon | TokenKind::Eof) {
+        if is_statement_separator(kind) {
             return cursor;
         }
 
@@ -341,7 +354,11 @@ fn parse_query_statement(tokens: &[Token]) -> StatementParseOutcome {
         Some(query) => {
             let span = query.span().clone();
             (
-                Some(Statement::Query(Box::new(QueryStatement { query, span }))),
+                Some(Statement::Query(Box::new(QueryStatement {
+                    query,
+                    follows_next: false,
+                    span,
+                }))),
                 diags,
             )
         }
@@ -395,6 +412,7 @@ fn parse_mutation_statement(tokens: &[Token]) -> StatementParseOutcome {
             (
                 Some(Statement::Mutation(Box::new(MutationStatement {
                     statement,
+                    follows_next: false,
                     span,
                 }))),
                 diags,
@@ -48,6 +48,18 @@ pub(super) fn parse_order_by_and_page_statement(
         None
     };
 
+    // OFFSET may also follow LIMIT, as in `LIMIT 10 OFFSET 20`
+    let offset = if offset.is_none()
+        && limit.is_some()
+        && (stream.check(&TokenKind::Offset) || stream.check(&TokenKind::Skip))
+    {
+        let (offset_opt, mut offset_diags) = parse_offset_clause(stream);
+        diags.append(&mut offset_diags);
+        offset_opt
+    } else {
+        offset
+    };
+
     if order_by.is_none() && offset.is_none() && limit.is_none() {
         return (None, diags);
     }
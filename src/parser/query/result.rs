@@ -17,7 +17,8 @@ use super::{
 
 // Import functions from sibling modules
 use super::pagination::{
-    parse_group_by_clause, parse_limit_clause, parse_offset_clause, parse_order_by_clause,
+    parse_group_by_clause, parse_limit_clause, parse_offset_clause,
+    parse_order_by_and_page_statement, parse_order_by_clause,
 };
 
 // Import parse_query from parent module for recursive query parsing
@@ -919,6 +920,10 @@ pub(crate) fn parse_return_statement(stream: &mut TokenStream) -> ParseResult<Re
         None
     };
 
+    // Parse optional ORDER BY / OFFSET / LIMIT on the returned rows
+    let (order_by_and_page, mut order_diags) = parse_order_by_and_page_statement(stream);
+    diags.append(&mut order_diags);
+
     let end = stream.previous_span().end;
 
     (
@@ -926,6 +931,7 @@ pub(crate) fn parse_return_statement(stream: &mut TokenStream) -> ParseResult<Re
             quantifier,
             items,
             group_by,
+            order_by_and_page: order_by_and_page.map(Box::new),
             span: start..end,
         }),
         diags,
@@ -0,0 +1,836 @@
+//! Finite automata for path pattern expressions.
+//!
+//! [`Nfa::compile`] turns a [`PathPatternExpression`] into a Thompson-style
+//! NFA whose input is a path: a node, then alternating edges and nodes.
+//!
+//! | Pattern construct         | Automaton                                       |
+//! |---------------------------|-------------------------------------------------|
+//! | `(a:Person WHERE ...)`    | node test on the current node                   |
+//! | `-[e:KNOWS]->`, `<-`, `~` | edge test, moves to the next node               |
+//! | concatenation             | fragments chained by ε-transitions              |
+//! | `\|` and `\|+\|`          | branches joined by ε-transitions                |
+//! | `{m,n}`, `*`, `+`, `?`    | `m` copies, then optional copies or a loop      |
+//!
+//! Node tests do not consume input: they check the node the path is
+//! currently at, so adjacent node patterns such as `(a)(b)` constrain the
+//! same node, and consecutive edge patterns pass through an unconstrained
+//! node. Set alternation (`|`) and multiset alternation (`|+|`) accept the
+//! same paths; they differ only in whether duplicate matches are kept.
+//!
+//! States entered by an element test carry the variable the element binds,
+//! with [`DegreeOfReference::Group`] inside quantifiers. Label tests are
+//! evaluated by the automaton; element predicates (property specifications
+//! and inline `WHERE`) are handed to the caller through
+//! [`Nfa::accepts_with`].
+
+use std::collections::{BTreeSet, HashMap};
+
+use smol_str::SmolStr;
+
+use crate::ast::Span;
+use crate::ast::expression::Expression;
+use crate::ast::query::{
+    AbbreviatedEdgePattern, EdgeDirection, EdgePattern, ElementPattern, GraphPatternQuantifier,
+    LabelExpression, PathFactor, PathPatternExpression, PathPrimary, PropertyKeyValuePair,
+};
+use crate::diag::Diag;
+use crate::ir::symbol_table::DegreeOfReference;
+
+/// Largest quantifier bound that is expanded into copies of its operand.
+pub const MAX_REPETITIONS: u32 = 1024;
+
+/// Identifier of an automaton state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StateId(usize);
+
+impl StateId {
+    /// Returns the raw index of this state.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Direction in which a path traverses an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Traversal {
+    /// From the edge's source to its target.
+    Forward,
+    /// From the edge's target to its source.
+    Backward,
+    /// Along an undirected edge.
+    Undirected,
+}
+
+/// One element of a concrete path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathElement {
+    /// A node with its labels.
+    Node { labels: Vec<SmolStr> },
+    /// An edge with its labels and the direction the path traverses it in.
+    Edge {
+        labels: Vec<SmolStr>,
+        traversal: Traversal,
+    },
+}
+
+impl PathElement {
+    /// Creates a node element.
+    pub fn node<L: Into<SmolStr>>(labels: impl IntoIterator<Item = L>) -> Self {
+        PathElement::Node {
+            labels: labels.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates an edge element.
+    pub fn edge<L: Into<SmolStr>>(
+        labels: impl IntoIterator<Item = L>,
+        traversal: Traversal,
+    ) -> Self {
+        PathElement::Edge {
+            labels: labels.into_iter().map(Into::into).collect(),
+            traversal,
+        }
+    }
+
+    /// Returns the labels of this element.
+    pub fn labels(&self) -> &[SmolStr] {
+        match self {
+            PathElement::Node { labels } | PathElement::Edge { labels, .. } => labels,
+        }
+    }
+}
+
+/// Label test and predicates of one element pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementTest {
+    /// Label expression the element must satisfy, if any.
+    pub labels: Option<LabelExpression>,
+    /// Property values the element must have (`{name: 'Ann'}`).
+    pub properties: Vec<PropertyKeyValuePair>,
+    /// Inline `WHERE` condition.
+    pub condition: Option<Expression>,
+}
+
+impl ElementTest {
+    fn any() -> Self {
+        ElementTest {
+            labels: None,
+            properties: Vec::new(),
+            condition: None,
+        }
+    }
+
+    /// Returns true if an element with `labels` satisfies the label test.
+    pub fn matches_labels(&self, labels: &[SmolStr]) -> bool {
+        self.labels
+            .as_ref()
+            .is_none_or(|expression| label_expression_matches(expression, labels))
+    }
+
+    /// Returns true if the element must also satisfy property or `WHERE`
+    /// predicates.
+    pub fn has_predicate(&self) -> bool {
+        !self.properties.is_empty() || self.condition.is_some()
+    }
+}
+
+/// Input consumed by a transition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbol {
+    /// Consumes nothing.
+    Epsilon,
+    /// Tests the node the path is at, without moving.
+    Node(ElementTest),
+    /// Traverses an edge in one of the directions `direction` allows.
+    Edge(EdgeDirection, ElementTest),
+}
+
+impl Symbol {
+    /// Returns the element test of this symbol, if it has one.
+    pub fn test(&self) -> Option<&ElementTest> {
+        match self {
+            Symbol::Epsilon => None,
+            Symbol::Node(test) | Symbol::Edge(_, test) => Some(test),
+        }
+    }
+}
+
+/// A transition between states.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// Input consumed by the transition.
+    pub symbol: Symbol,
+    /// State entered by the transition.
+    pub target: StateId,
+}
+
+/// Kind of element a variable binds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    /// A node variable.
+    Node,
+    /// An edge variable.
+    Edge,
+}
+
+/// A variable bound when a state is entered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// Variable name.
+    pub variable: SmolStr,
+    /// Kind of element bound.
+    pub kind: ElementKind,
+    /// Group inside a quantifier, singleton otherwise.
+    pub degree: DegreeOfReference,
+}
+
+/// An automaton state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct State {
+    /// Outgoing transitions.
+    pub transitions: Vec<Transition>,
+    /// Variable bound by the element test that enters this state.
+    pub binding: Option<Binding>,
+}
+
+/// Nondeterministic finite automaton for a path pattern expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nfa {
+    states: Vec<State>,
+    start: StateId,
+    accept: StateId,
+}
+
+impl Nfa {
+    /// Compiles a path pattern expression.
+    ///
+    /// Simplified path patterns are not supported, and quantifier bounds above
+    /// [`MAX_REPETITIONS`] are rejected.
+    pub fn compile(expression: &PathPatternExpression) -> Result<Self, Box<Diag>> {
+        let mut builder = Builder { states: Vec::new() };
+        let (start, accept) = builder.expression(expression, false)?;
+        Ok(Nfa {
+            states: builder.states,
+            start,
+            accept,
+        })
+    }
+
+    /// Returns the start state.
+    pub fn start(&self) -> StateId {
+        self.start
+    }
+
+    /// Returns the accepting state.
+    pub fn accept(&self) -> StateId {
+        self.accept
+    }
+
+    /// Returns a state by ID.
+    pub fn state(&self, id: StateId) -> &State {
+        &self.states[id.0]
+    }
+
+    /// Returns all states, indexed by [`StateId::index`].
+    pub fn states(&self) -> &[State] {
+        &self.states
+    }
+
+    /// Returns true if the automaton accepts `path`, treating element
+    /// predicates as satisfied.
+    ///
+    /// A path is a node followed by any number of edge-node pairs; other
+    /// shapes are never accepted.
+    pub fn accepts(&self, path: &[PathElement]) -> bool {
+        self.accepts_with(path, |_, _| true)
+    }
+
+    /// Returns true if the automaton accepts `path`. `predicate` is called
+    /// with an element test that has predicates and the index of the path
+    /// element it applies to, after the element's labels and direction
+    /// matched.
+    pub fn accepts_with(
+        &self,
+        path: &[PathElement],
+        mut predicate: impl FnMut(&ElementTest, usize) -> bool,
+    ) -> bool {
+        if !is_well_formed(path) {
+            return false;
+        }
+
+        let mut current = BTreeSet::from([self.start]);
+        for (index, element) in path.iter().enumerate() {
+            current = self.step(&current, element, |test| {
+                !test.has_predicate() || predicate(test, index)
+            });
+        }
+        current.contains(&self.accept)
+    }
+
+    /// Advances a set of states over one path element. Reading a node closes
+    /// the set over ε-transitions and satisfied node tests; reading an edge
+    /// follows the matching edge transitions.
+    fn step(
+        &self,
+        current: &BTreeSet<StateId>,
+        element: &PathElement,
+        mut predicate: impl FnMut(&ElementTest) -> bool,
+    ) -> BTreeSet<StateId> {
+        match element {
+            PathElement::Node { labels } => {
+                let mut reached = current.clone();
+                let mut pending: Vec<StateId> = current.iter().copied().collect();
+                while let Some(state) = pending.pop() {
+                    for transition in &self.states[state.0].transitions {
+                        let follow = match &transition.symbol {
+                            Symbol::Epsilon => true,
+                            Symbol::Node(test) => test.matches_labels(labels) && predicate(test),
+                            Symbol::Edge(..) => false,
+                        };
+                        if follow && reached.insert(transition.target) {
+                            pending.push(transition.target);
+                        }
+                    }
+                }
+                reached
+            }
+            PathElement::Edge { labels, traversal } => current
+                .iter()
+                .flat_map(|state| &self.states[state.0].transitions)
+                .filter(|transition| match &transition.symbol {
+                    Symbol::Edge(direction, test) => {
+                        direction_allows(*direction, *traversal)
+                            && test.matches_labels(labels)
+                            && predicate(test)
+                    }
+                    _ => false,
+                })
+                .map(|transition| transition.target)
+                .collect(),
+        }
+    }
+
+    /// Returns a DFA for this automaton, built lazily over the concrete path
+    /// elements it reads.
+    pub fn determinize(&self) -> Dfa<'_> {
+        let start = BTreeSet::from([self.start]);
+        Dfa {
+            nfa: self,
+            index: HashMap::from([(start.clone(), 0)]),
+            states: vec![start],
+            transitions: HashMap::new(),
+        }
+    }
+}
+
+/// Identifier of a [`Dfa`] state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DfaStateId(usize);
+
+/// Deterministic automaton obtained from an [`Nfa`] by the subset
+/// construction.
+///
+/// Label tests can overlap, so the alphabet is the set of concrete path
+/// elements; states and transitions are created the first time an element
+/// is read and reused afterwards. Element predicates are treated as
+/// satisfied.
+#[derive(Debug, Clone)]
+pub struct Dfa<'a> {
+    nfa: &'a Nfa,
+    states: Vec<BTreeSet<StateId>>,
+    index: HashMap<BTreeSet<StateId>, usize>,
+    transitions: HashMap<(usize, PathElement), usize>,
+}
+
+impl Dfa<'_> {
+    /// Returns the start state.
+    pub fn start(&self) -> DfaStateId {
+        DfaStateId(0)
+    }
+
+    /// Returns the state reached from `state` by reading `element`.
+    pub fn step(&mut self, state: DfaStateId, element: &PathElement) -> DfaStateId {
+        let key = (state.0, element.clone());
+        if let Some(&target) = self.transitions.get(&key) {
+            return DfaStateId(target);
+        }
+
+        let next = self.nfa.step(&self.states[state.0], element, |_| true);
+        let target = match self.index.get(&next) {
+            Some(&target) => target,
+            None => {
+                self.states.push(next.clone());
+                self.index.insert(next, self.states.len() - 1);
+                self.states.len() - 1
+            }
+        };
+        self.transitions.insert(key, target);
+        DfaStateId(target)
+    }
+
+    /// Returns true if a path that ends in `state` is accepted.
+    pub fn is_accepting(&self, state: DfaStateId) -> bool {
+        self.states[state.0].contains(&self.nfa.accept)
+    }
+
+    /// Returns true if no path through `state` can be accepted.
+    pub fn is_dead(&self, state: DfaStateId) -> bool {
+        self.states[state.0].is_empty()
+    }
+
+    /// Returns true if the automaton accepts `path`.
+    pub fn accepts(&mut self, path: &[PathElement]) -> bool {
+        if !is_well_formed(path) {
+            return false;
+        }
+        let mut state = self.start();
+        for element in path {
+            state = self.step(state, element);
+        }
+        self.is_accepting(state)
+    }
+
+    /// Returns the number of states built so far.
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+}
+
+/// Thompson construction over the pattern AST.
+struct Builder {
+    states: Vec<State>,
+}
+
+impl Builder {
+    fn state(&mut self) -> StateId {
+        self.states.push(State::default());
+        StateId(self.states.len() - 1)
+    }
+
+    fn connect(&mut self, from: StateId, symbol: Symbol, to: StateId) {
+        self.states[from.0]
+            .transitions
+            .push(Transition { symbol, target: to });
+    }
+
+    fn epsilon(&mut self, from: StateId, to: StateId) {
+        self.connect(from, Symbol::Epsilon, to);
+    }
+
+    /// Compiles `expression` into a fragment and returns its entry and exit
+    /// states. `group` is set inside a repeating quantifier.
+    fn expression(
+        &mut self,
+        expression: &PathPatternExpression,
+        group: bool,
+    ) -> Result<(StateId, StateId), Box<Diag>> {
+        match expression {
+            PathPatternExpression::Term(term) => {
+                let start = self.state();
+                let mut end = start;
+                for factor in &term.factors {
+                    let (entry, exit) = self.factor(factor, group)?;
+                    self.epsilon(end, entry);
+                    end = exit;
+                }
+                Ok((start, end))
+            }
+            PathPatternExpression::Union { left, right, .. } => {
+                self.branches([left.as_ref(), right.as_ref()], group)
+            }
+            PathPatternExpression::Alternation { alternatives, .. } => {
+                self.branches(alternatives, group)
+            }
+        }
+    }
+
+    fn branches<'e>(
+        &mut self,
+        branches: impl IntoIterator<Item = &'e PathPatternExpression>,
+        group: bool,
+    ) -> Result<(StateId, StateId), Box<Diag>> {
+        let start = self.state();
+        let end = self.state();
+        for branch in branches {
+            let (entry, exit) = self.expression(branch, group)?;
+            self.epsilon(start, entry);
+            self.epsilon(exit, end);
+        }
+        Ok((start, end))
+    }
+
+    fn factor(
+        &mut self,
+        factor: &PathFactor,
+        group: bool,
+    ) -> Result<(StateId, StateId), Box<Diag>> {
+        let Some(quantifier) = &factor.quantifier else {
+            return self.primary(&factor.primary, group);
+        };
+
+        let (min, max) = bounds(quantifier)?;
+        let group = group || !matches!(quantifier, GraphPatternQuantifier::QuestionMark { .. });
+
+        let start = self.state();
+        let mut current = start;
+        for _ in 0..min {
+            let (entry, exit) = self.primary(&factor.primary, group)?;
+            self.epsilon(current, entry);
+            current = exit;
+        }
+
+        match max {
+            None => {
+                let hub = self.state();
+                self.epsilon(current, hub);
+                let (entry, exit) = self.primary(&factor.primary, group)?;
+                self.epsilon(hub, entry);
+                self.epsilon(exit, hub);
+                Ok((start, hub))
+            }
+            Some(max) => {
+                let end = self.state();
+                for _ in min..max {
+                    let (entry, exit) = self.primary(&factor.primary, group)?;
+                    self.epsilon(current, end);
+                    self.epsilon(current, entry);
+                    current = exit;
+                }
+                self.epsilon(current, end);
+                Ok((start, end))
+            }
+        }
+    }
+
+    fn primary(
+        &mut self,
+        primary: &PathPrimary,
+        group: bool,
+    ) -> Result<(StateId, StateId), Box<Diag>> {
+        match primary {
+            PathPrimary::ElementPattern(element) => Ok(self.element(element, group)),
+            PathPrimary::ParenthesizedExpression(expression) => self.expression(expression, group),
+            PathPrimary::SimplifiedExpression(expression) => Err(Box::new(
+                Diag::error("Simplified path patterns cannot be compiled into an automaton")
+                    .with_primary_label(simplified_span(expression), "simplified path pattern"),
+            )),
+        }
+    }
+
+    fn element(&mut self, element: &ElementPattern, group: bool) -> (StateId, StateId) {
+        let degree = if group {
+            DegreeOfReference::Group
+        } else {
+            DegreeOfReference::Singleton
+        };
+        let binding = |variable: &SmolStr, kind| Binding {
+            variable: variable.clone(),
+            kind,
+            degree,
+        };
+
+        let (symbol, binding) = match element {
+            ElementPattern::Node(node) => (
+                Symbol::Node(ElementTest {
+                    labels: node.label_expression.clone(),
+                    properties: properties(node.properties.as_ref()),
+                    condition: node.where_clause.as_ref().map(|w| w.condition.clone()),
+                }),
+                node.variable
+                    .as_ref()
+                    .map(|v| binding(&v.variable, ElementKind::Node)),
+            ),
+            ElementPattern::Edge(EdgePattern::Full(edge)) => (
+                Symbol::Edge(
+                    edge.direction,
+                    ElementTest {
+                        labels: edge.filler.label_expression.clone(),
+                        properties: properties(edge.filler.properties.as_ref()),
+                        condition: edge
+                            .filler
+                            .where_clause
+                            .as_ref()
+                            .map(|w| w.condition.clone()),
+                    },
+                ),
+                edge.filler
+                    .variable
+                    .as_ref()
+                    .map(|v| binding(&v.variable, ElementKind::Edge)),
+            ),
+            ElementPattern::Edge(EdgePattern::Abbreviated(edge)) => {
+                let direction = match edge {
+                    AbbreviatedEdgePattern::LeftArrow { .. } => EdgeDirection::PointingLeft,
+                    AbbreviatedEdgePattern::RightArrow { .. } => EdgeDirection::PointingRight,
+                    AbbreviatedEdgePattern::Undirected { .. } => EdgeDirection::Undirected,
+                    AbbreviatedEdgePattern::AnyDirection { .. } => EdgeDirection::AnyDirection,
+                };
+                (Symbol::Edge(direction, ElementTest::any()), None)
+            }
+        };
+
+        let start = self.state();
+        let end = self.state();
+        self.connect(start, symbol, end);
+        self.states[end.0].binding = binding;
+        (start, end)
+    }
+}
+
+/// Returns the repetition bounds of a quantifier; `None` is unbounded.
+fn bounds(quantifier: &GraphPatternQuantifier) -> Result<(u32, Option<u32>), Box<Diag>> {
+    let (min, max) = match quantifier {
+        GraphPatternQuantifier::Star { .. } => (0, None),
+        GraphPatternQuantifier::Plus { .. } => (1, None),
+        GraphPatternQuantifier::QuestionMark { .. } => (0, Some(1)),
+        GraphPatternQuantifier::Fixed { count, .. } => (*count, Some(*count)),
+        GraphPatternQuantifier::General { min, max, .. } => (min.unwrap_or(0), *max),
+    };
+
+    let span = quantifier.span().clone();
+    if let Some(max) = max
+        && max < min
+    {
+        return Err(Box::new(
+            Diag::error(format!(
+                "Quantifier upper bound {max} is less than its lower bound {min}"
+            ))
+            .with_primary_label(span, "empty repetition range"),
+        ));
+    }
+    if max.unwrap_or(min) > MAX_REPETITIONS {
+        return Err(Box::new(
+            Diag::error(format!(
+                "Quantifier bound exceeds the automaton limit of {MAX_REPETITIONS}"
+            ))
+            .with_primary_label(span, "too many repetitions")
+            .with_help("use an unbounded quantifier with a selective path search instead"),
+        ));
+    }
+    Ok((min, max))
+}
+
+fn properties(
+    specification: Option<&crate::ast::query::ElementPropertySpecification>,
+) -> Vec<PropertyKeyValuePair> {
+    specification
+        .map(|specification| specification.properties.clone())
+        .unwrap_or_default()
+}
+
+fn simplified_span(expression: &crate::ast::query::SimplifiedPathPatternExpression) -> Span {
+    use crate::ast::query::SimplifiedPathPatternExpression as S;
+    match expression {
+        S::Contents(e) => e.span.clone(),
+        S::Union(e) => e.span.clone(),
+        S::MultisetAlternation(e) => e.span.clone(),
+        S::Conjunction(e) => e.span.clone(),
+        S::Concatenation(e) => e.span.clone(),
+        S::Quantified(e) => e.span.clone(),
+        S::Questioned(e) => e.span.clone(),
+        S::DirectionOverride(e) => e.span.clone(),
+        S::Negation(e) => e.span.clone(),
+    }
+}
+
+/// Returns true if `path` is a node followed by edge-node pairs.
+fn is_well_formed(path: &[PathElement]) -> bool {
+    path.len() % 2 == 1
+        && path
+            .iter()
+            .enumerate()
+            .all(|(index, element)| matches!(element, PathElement::Node { .. }) == (index % 2 == 0))
+}
+
+/// Returns true if an edge pattern with `direction` matches an edge
+/// traversed in `traversal`.
+pub(crate) fn direction_allows(direction: EdgeDirection, traversal: Traversal) -> bool {
+    use EdgeDirection::*;
+    match traversal {
+        Traversal::Forward => {
+            matches!(
+                direction,
+                PointingRight | AnyDirected | AnyDirection | RightOrUndirected
+            )
+        }
+        Traversal::Backward => {
+            matches!(
+                direction,
+                PointingLeft | AnyDirected | AnyDirection | LeftOrUndirected
+            )
+        }
+        Traversal::Undirected => matches!(
+            direction,
+            Undirected | LeftOrUndirected | AnyDirection | RightOrUndirected
+        ),
+    }
+}
+
+/// Evaluates a label expression against an element's label set.
+pub fn label_expression_matches(expression: &LabelExpression, labels: &[SmolStr]) -> bool {
+    match expression {
+        LabelExpression::Negation { operand, .. } => !label_expression_matches(operand, labels),
+        LabelExpression::Conjunction { left, right, .. } => {
+            label_expression_matches(left, labels) && label_expression_matches(right, labels)
+        }
+        LabelExpression::Disjunction { left, right, .. } => {
+            label_expression_matches(left, labels) || label_expression_matches(right, labels)
+        }
+        LabelExpression::LabelName { name, .. } => labels.contains(name),
+        LabelExpression::Wildcard { .. } => !labels.is_empty(),
+        LabelExpression::Parenthesized { expression, .. } => {
+            label_expression_matches(expression, labels)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{PrimitiveQueryStatement, Query, Statement};
+    use crate::parse;
+
+    fn compile(pattern: &str) -> Result<Nfa, Box<Diag>> {
+        let source = format!("MATCH {pattern} RETURN 1");
+        let program = parse(&source).ast.expect("parse");
+        let Statement::Query(statement) = &program.statements[0] else {
+            panic!("expected a query");
+        };
+        let Query::Linear(query) = &statement.query else {
+            panic!("expected a linear query");
+        };
+        let PrimitiveQueryStatement::Match(crate::ast::query::MatchStatement::Simple(simple)) =
+            &query.primitive_statements[0]
+        else {
+            panic!("expected MATCH");
+        };
+        Nfa::compile(&simple.pattern.paths.patterns[0].expression)
+    }
+
+    /// Builds a path from `Label` nodes and `>Label`, `<Label` or `~Label`
+    /// edges; `_` is an element without labels.
+    fn path(spec: &str) -> Vec<PathElement> {
+        spec.split_whitespace()
+            .map(|token| {
+                let (traversal, name) = match token.split_at(1) {
+                    (">", name) => (Some(Traversal::Forward), name),
+                    ("<", name) => (Some(Traversal::Backward), name),
+                    ("~", name) => (Some(Traversal::Undirected), name),
+                    _ => (None, token),
+                };
+                let labels: Vec<&str> = if name == "_" { vec![] } else { vec![name] };
+                match traversal {
+                    Some(traversal) => PathElement::edge(labels, traversal),
+                    None => PathElement::node(labels),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_edge_checks_labels_and_direction() {
+        let nfa = compile("(a:Person)-[e:KNOWS]->(b:Person)").unwrap();
+        assert!(nfa.accepts(&path("Person >KNOWS Person")));
+        assert!(!nfa.accepts(&path("Person <KNOWS Person")));
+        assert!(!nfa.accepts(&path("Person >LIKES Person")));
+        assert!(!nfa.accepts(&path("Robot >KNOWS Person")));
+        assert!(!nfa.accepts(&path("Person >KNOWS")));
+
+        let nfa = compile("(a)<-[:R]-(b)~[:S]~(c)-[:T]-(d)").unwrap();
+        assert!(nfa.accepts(&path("_ <R _ ~S _ >T _")));
+        assert!(nfa.accepts(&path("_ <R _ ~S _ <T _")));
+        assert!(!nfa.accepts(&path("_ >R _ ~S _ >T _")));
+        assert!(!nfa.accepts(&path("_ <R _ >S _ >T _")));
+    }
+
+    #[test]
+    fn test_quantifiers_repeat_their_operand() {
+        let nfa = compile("(a)-[:R]->{2,3}(b)").unwrap();
+        assert!(!nfa.accepts(&path("_ >R _")));
+        assert!(nfa.accepts(&path("_ >R _ >R _")));
+        assert!(nfa.accepts(&path("_ >R _ >R _ >R _")));
+        assert!(!nfa.accepts(&path("_ >R _ >R _ >R _ >R _")));
+
+        let nfa = compile("(a:A) ((x)-[:R]->(y:B))* (b)").unwrap();
+        assert!(nfa.accepts(&path("A")));
+        assert!(nfa.accepts(&path("A >R B >R B")));
+        assert!(!nfa.accepts(&path("A >R B >R C")));
+
+        let nfa = compile("(a)-[:R]->+(b)").unwrap();
+        assert!(!nfa.accepts(&path("_")));
+        assert!(nfa.accepts(&path(&("_ >R ".repeat(20) + "_"))));
+    }
+
+    #[test]
+    fn test_alternation_and_adjacent_nodes() {
+        for pattern in [
+            "(a)-[:R]->(b) | (a)-[:S]->(b)",
+            "(a)-[:R]->(b) |+| (a)-[:S]->(b)",
+        ] {
+            let nfa = compile(pattern).unwrap();
+            assert!(nfa.accepts(&path("_ >R _")), "{pattern}");
+            assert!(nfa.accepts(&path("_ >S _")), "{pattern}");
+            assert!(!nfa.accepts(&path("_ >T _")), "{pattern}");
+        }
+
+        // Adjacent node patterns constrain the same node.
+        let nfa = compile("(a:A)(b:!B)-[:R|S]->(c:%)").unwrap();
+        assert!(nfa.accepts(&path("A >S C")));
+        assert!(!nfa.accepts(&path("B >S C")));
+        assert!(!nfa.accepts(&path("A >S _")));
+    }
+
+    #[test]
+    fn test_states_carry_bindings_and_predicates() {
+        let nfa = compile("(a {name: 'Ann'}) ((x)-[e:R WHERE e.w > 1]->(y))+ (b)").unwrap();
+        let bindings: Vec<(&str, DegreeOfReference)> = nfa
+            .states()
+            .iter()
+            .filter_map(|state| state.binding.as_ref())
+            .map(|binding| (binding.variable.as_str(), binding.degree))
+            .collect();
+        assert!(bindings.contains(&("a", DegreeOfReference::Singleton)));
+        assert!(bindings.contains(&("e", DegreeOfReference::Group)));
+        assert!(bindings.contains(&("y", DegreeOfReference::Group)));
+
+        let input = path("_ >R _ >R _");
+        assert!(nfa.accepts(&input));
+        // Reject the second edge only.
+        assert!(
+            !nfa.accepts_with(&input, |test, index| test.properties.len() == 1
+                || index != 3)
+        );
+        let mut checked = Vec::new();
+        assert!(nfa.accepts_with(&input, |_, index| {
+            checked.push(index);
+            true
+        }));
+        assert!(checked.contains(&0) && checked.contains(&1) && checked.contains(&3));
+    }
+
+    #[test]
+    fn test_dfa_reuses_states() {
+        let nfa = compile("(a)-[:R]->*(b:B)").unwrap();
+        let mut dfa = nfa.determinize();
+        assert!(dfa.accepts(&path("B")));
+        assert!(dfa.accepts(&path("_ >R _ >R B")));
+        let states = dfa.state_count();
+        assert!(dfa.accepts(&path("_ >R _ >R _ >R _ >R B")));
+        assert_eq!(dfa.state_count(), states);
+        assert!(!dfa.accepts(&path("_ >R _ >S B")));
+
+        let dead = dfa.step(dfa.start(), &PathElement::node(["A"]));
+        let dead = dfa.step(dead, &PathElement::edge(["S"], Traversal::Forward));
+        assert!(dfa.is_dead(dead));
+    }
+
+    #[test]
+    fn test_unsupported_patterns_are_reported() {
+        let error = compile("(a)-[:R]->{3,2}(b)").unwrap_err();
+        assert_eq!(
+            error.message,
+            "Quantifier upper bound 2 is less than its lower bound 3"
+        );
+        assert!(compile("(a)-[:R]->{1,5000}(b)").is_err());
+    }
+}
@@ -0,0 +1,8 @@
+//! Executable forms of graph patterns.
+//!
+//! [`automaton`] compiles path pattern expressions into finite automata that
+//! a traversal engine can run as regular path queries.
+
+pub mod automaton;
+
+pub use automaton::{Dfa, Nfa, PathElement, Traversal};
@@ -0,0 +1,411 @@
+//! Python classes for the query analysis APIs.
+//!
+//! Results are converted to plain frozen objects when they are built;
+//! clause kinds, label expression complexity and set operators become
+//! strings, and query shapes become dictionaries.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::analysis::{
+    ClauseId, ClauseInfo, ClauseKind, DefinitionPoint, PatternInfo, PropertyReference, QueryInfo,
+    QueryShape, UsagePoint, VariableDependencyGraph,
+};
+use crate::ast::query::{SetOperator, SetQuantifier};
+use crate::ast::{AstNode, CollectingVisitor, Visit};
+
+use super::{PyStatement, span};
+
+/// Query-level metadata of a statement.
+#[pyclass(name = "QueryInfo", module = "gql_parser", frozen)]
+pub(super) struct PyQueryInfo {
+    #[pyo3(get)]
+    clause_sequence: Vec<PyClauseInfo>,
+    #[pyo3(get)]
+    graph_pattern_count: usize,
+    #[pyo3(get)]
+    contains_aggregation: bool,
+    shape: Option<QueryShape>,
+}
+
+#[pymethods]
+impl PyQueryInfo {
+    /// Builds query metadata from a statement.
+    #[staticmethod]
+    fn from_ast(statement: PyRef<'_, PyStatement>) -> Self {
+        let info = QueryInfo::from_ast(statement.get());
+        Self {
+            clause_sequence: info.clause_sequence.iter().map(PyClauseInfo::new).collect(),
+            graph_pattern_count: info.graph_pattern_count,
+            contains_aggregation: info.contains_aggregation,
+            shape: info.query_shape,
+        }
+    }
+
+    /// The query shape as `{"kind": "linear", "pipeline_id": ...}` or
+    /// `{"kind": "composite", "operator": ..., "left": ..., "right": ...}`,
+    /// or `None` for statements that are not queries.
+    #[getter]
+    fn query_shape<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.shape
+            .as_ref()
+            .map(|shape| shape_to_dict(py, shape))
+            .transpose()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("QueryInfo(clauses={})", self.clause_sequence.len())
+    }
+}
+
+fn shape_to_dict<'py>(py: Python<'py>, shape: &QueryShape) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    match shape {
+        QueryShape::Linear { pipeline_id } => {
+            dict.set_item("kind", "linear")?;
+            dict.set_item("pipeline_id", pipeline_id)?;
+        }
+        QueryShape::Composite {
+            operator,
+            left,
+            right,
+        } => {
+            dict.set_item("kind", "composite")?;
+            dict.set_item("operator", set_operator_name(operator))?;
+            dict.set_item("left", shape_to_dict(py, left)?)?;
+            dict.set_item("right", shape_to_dict(py, right)?)?;
+        }
+    }
+    Ok(dict)
+}
+
+fn set_operator_name(operator: &SetOperator) -> &'static str {
+    use SetQuantifier::All;
+    match operator {
+        SetOperator::Union { quantifier: All } => "UNION ALL",
+        SetOperator::Union { .. } => "UNION DISTINCT",
+        SetOperator::Except { quantifier: All } => "EXCEPT ALL",
+        SetOperator::Except { .. } => "EXCEPT DISTINCT",
+        SetOperator::Intersect { quantifier: All } => "INTERSECT ALL",
+        SetOperator::Intersect { .. } => "INTERSECT DISTINCT",
+        SetOperator::Otherwise => "OTHERWISE",
+    }
+}
+
+/// Metadata of one clause in a linear query pipeline.
+#[pyclass(
+    name = "ClauseInfo",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(super) struct PyClauseInfo {
+    /// `(pipeline_id, position)`.
+    clause_id: (usize, usize),
+    /// The clause kind, such as `"Match"` or `"Return"`.
+    kind: &'static str,
+    /// Set for `OPTIONAL MATCH` and `OPTIONAL CALL`.
+    optional: bool,
+    /// Set for inline procedure calls.
+    inline: bool,
+    span: (usize, usize),
+    definitions: Vec<String>,
+    uses: Vec<String>,
+    property_references: Vec<PyPropertyReference>,
+    contains_aggregate: bool,
+    graph_pattern_count: usize,
+}
+
+impl PyClauseInfo {
+    fn new(info: &ClauseInfo) -> Self {
+        let (kind, optional, inline) = clause_kind(&info.kind);
+        Self {
+            clause_id: clause_id(info.clause_id),
+            kind,
+            optional,
+            inline,
+            span: span(&info.span),
+            definitions: info.definitions.iter().map(ToString::to_string).collect(),
+            uses: info.uses.iter().map(ToString::to_string).collect(),
+            property_references: info
+                .property_references
+                .iter()
+                .map(PyPropertyReference::new)
+                .collect(),
+            contains_aggregate: info.contains_aggregate,
+            graph_pattern_count: info.graph_pattern_count,
+        }
+    }
+}
+
+#[pymethods]
+impl PyClauseInfo {
+    fn __repr__(&self) -> String {
+        format!("ClauseInfo({:?}, span={:?})", self.kind, self.span)
+    }
+}
+
+/// Returns the kind name and the `optional` and `inline` flags.
+fn clause_kind(kind: &ClauseKind) -> (&'static str, bool, bool) {
+    match *kind {
+        ClauseKind::UseGraph => ("UseGraph", false, false),
+        ClauseKind::Match { optional } => ("Match", optional, false),
+        ClauseKind::Call { optional, inline } => ("Call", optional, inline),
+        ClauseKind::Filter => ("Filter", false, false),
+        ClauseKind::Let => ("Let", false, false),
+        ClauseKind::For => ("For", false, false),
+        ClauseKind::OrderByAndPage => ("OrderByAndPage", false, false),
+        ClauseKind::Select => ("Select", false, false),
+        ClauseKind::Return => ("Return", false, false),
+        ClauseKind::Finish => ("Finish", false, false),
+    }
+}
+
+fn clause_id(id: ClauseId) -> (usize, usize) {
+    (id.pipeline_id, id.position)
+}
+
+/// A property access found in a clause.
+#[pyclass(
+    name = "PropertyReference",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(super) struct PyPropertyReference {
+    variable: Option<String>,
+    property: String,
+    span: (usize, usize),
+}
+
+impl PyPropertyReference {
+    fn new(reference: &PropertyReference) -> Self {
+        Self {
+            variable: reference.variable.as_ref().map(ToString::to_string),
+            property: reference.property.to_string(),
+            span: span(&reference.span),
+        }
+    }
+}
+
+#[pymethods]
+impl PyPropertyReference {
+    fn __repr__(&self) -> String {
+        match &self.variable {
+            Some(variable) => format!("PropertyReference({variable}.{})", self.property),
+            None => format!("PropertyReference(.{})", self.property),
+        }
+    }
+}
+
+/// Structural metadata of a graph pattern.
+#[pyclass(name = "PatternInfo", module = "gql_parser", frozen, get_all)]
+pub(super) struct PyPatternInfo {
+    node_count: usize,
+    edge_count: usize,
+    path_count: usize,
+    label_expression_count: usize,
+    /// `"None"`, `"Simple"`, `"Boolean"`, `"Negated"` or `"NestedBoolean"`.
+    label_expression_complexity: String,
+    node_variable_count: usize,
+    connected_component_count: usize,
+    is_fully_connected: bool,
+}
+
+#[pymethods]
+impl PyPatternInfo {
+    /// Analyzes every graph pattern of a statement, in source order.
+    #[staticmethod]
+    fn from_statement(statement: PyRef<'_, PyStatement>) -> Vec<Self> {
+        let mut collector = CollectingVisitor::new(|node: AstNode<'_>| match node {
+            AstNode::GraphPattern(pattern) => Some(Self::new(&PatternInfo::analyze(pattern))),
+            _ => None,
+        });
+        let _ = collector.visit_statement(statement.get());
+        collector.into_items()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PatternInfo(nodes={}, edges={}, paths={})",
+            self.node_count, self.edge_count, self.path_count
+        )
+    }
+}
+
+impl PyPatternInfo {
+    fn new(info: &PatternInfo) -> Self {
+        Self {
+            node_count: info.node_count,
+            edge_count: info.edge_count,
+            path_count: info.path_count,
+            label_expression_count: info.label_expression_count,
+            label_expression_complexity: format!("{:?}", info.label_expression_complexity),
+            node_variable_count: info.node_variable_count,
+            connected_component_count: info.connected_component_count,
+            is_fully_connected: info.is_fully_connected,
+        }
+    }
+}
+
+/// Variable definition and usage points of a statement, linked by edges.
+#[pyclass(name = "VariableDependencyGraph", module = "gql_parser", frozen)]
+pub(super) struct PyVariableDependencyGraph {
+    #[pyo3(get)]
+    definition_points: Vec<PyDefinitionPoint>,
+    #[pyo3(get)]
+    usage_points: Vec<PyUsagePoint>,
+    #[pyo3(get)]
+    edges: Vec<PyDefineUseEdge>,
+    #[pyo3(get)]
+    unresolved_usage_indices: Vec<usize>,
+    unresolved: Vec<String>,
+}
+
+#[pymethods]
+impl PyVariableDependencyGraph {
+    /// Builds the dependency graph of a statement.
+    #[staticmethod]
+    fn build(statement: PyRef<'_, PyStatement>) -> Self {
+        let graph = VariableDependencyGraph::build(statement.get());
+        Self {
+            definition_points: graph
+                .definition_points
+                .iter()
+                .map(PyDefinitionPoint::new)
+                .collect(),
+            usage_points: graph.usage_points.iter().map(PyUsagePoint::new).collect(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|edge| PyDefineUseEdge {
+                    variable: edge.variable.to_string(),
+                    definition_index: edge.definition_index,
+                    usage_index: edge.usage_index,
+                })
+                .collect(),
+            unresolved_usage_indices: graph.unresolved_usage_indices.clone(),
+            unresolved: graph
+                .unresolved_variables()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// Names of the variables used without a reaching definition, sorted.
+    fn unresolved_variables(&self) -> Vec<String> {
+        self.unresolved.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VariableDependencyGraph(definitions={}, usages={}, edges={})",
+            self.definition_points.len(),
+            self.usage_points.len(),
+            self.edges.len()
+        )
+    }
+}
+
+/// A clause that defines a variable.
+#[pyclass(
+    name = "DefinitionPoint",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(super) struct PyDefinitionPoint {
+    variable: String,
+    clause_id: (usize, usize),
+    clause_kind: &'static str,
+    span: (usize, usize),
+}
+
+impl PyDefinitionPoint {
+    fn new(point: &DefinitionPoint) -> Self {
+        Self {
+            variable: point.variable.to_string(),
+            clause_id: clause_id(point.clause_id),
+            clause_kind: clause_kind(&point.clause_kind).0,
+            span: span(&point.span),
+        }
+    }
+}
+
+#[pymethods]
+impl PyDefinitionPoint {
+    fn __repr__(&self) -> String {
+        format!(
+            "DefinitionPoint({:?}, {:?})",
+            self.variable, self.clause_kind
+        )
+    }
+}
+
+/// A clause that uses a variable.
+#[pyclass(
+    name = "UsagePoint",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(super) struct PyUsagePoint {
+    variable: String,
+    clause_id: (usize, usize),
+    clause_kind: &'static str,
+    span: (usize, usize),
+}
+
+impl PyUsagePoint {
+    fn new(point: &UsagePoint) -> Self {
+        Self {
+            variable: point.variable.to_string(),
+            clause_id: clause_id(point.clause_id),
+            clause_kind: clause_kind(&point.clause_kind).0,
+            span: span(&point.span),
+        }
+    }
+}
+
+#[pymethods]
+impl PyUsagePoint {
+    fn __repr__(&self) -> String {
+        format!("UsagePoint({:?}, {:?})", self.variable, self.clause_kind)
+    }
+}
+
+/// Links a usage point to the definition point that reaches it.
+#[pyclass(
+    name = "DefineUseEdge",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(super) struct PyDefineUseEdge {
+    variable: String,
+    /// Index into `definition_points`.
+    definition_index: usize,
+    /// Index into `usage_points`.
+    usage_index: usize,
+}
+
+#[pymethods]
+impl PyDefineUseEdge {
+    fn __repr__(&self) -> String {
+        format!(
+            "DefineUseEdge({:?}, {} -> {})",
+            self.variable, self.definition_index, self.usage_index
+        )
+    }
+}
@@ -0,0 +1,397 @@
+//! Python bindings built with pyo3.
+//!
+//! Enabled by the `python` feature. `pyproject.toml` builds the
+//! `gql_parser` extension module with maturin:
+//!
+//! ```text
+//! maturin build --release
+//! pip install target/wheels/gql_parser-*.whl
+//! ```
+//!
+//! `parse` and `parse_and_validate` return a `ParseResult` holding the
+//! `Program` and its `Diag` objects. Statements convert to nested
+//! dictionaries and feed `QueryInfo`, `PatternInfo` and
+//! `VariableDependencyGraph`; `SchemaSnapshotBuilder` describes the schema
+//! that `parse_and_validate` checks metadata against. Spans are
+//! `(start, end)` byte offsets, with 1-based lines and columns counting
+//! characters.
+
+mod analysis;
+mod schema;
+
+use std::sync::Arc;
+
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::ast::visitors::flat::{self, FlatNode};
+use crate::ast::{Program, Span, Statement};
+use crate::diag::{
+    Diag, DiagSeverity, LabelRole, SourceFile, render_json_lines, render_sarif, render_text,
+};
+use crate::semantic::{MockMetadataProvider, SemanticValidator};
+
+use self::analysis::{
+    PyClauseInfo, PyDefineUseEdge, PyDefinitionPoint, PyPatternInfo, PyPropertyReference,
+    PyQueryInfo, PyUsagePoint, PyVariableDependencyGraph,
+};
+use self::schema::{
+    PyConstraintMeta, PyEdgeTypeBuilder, PyNodeTypeBuilder, PyPropertyMeta, PySchemaSnapshot,
+    PySchemaSnapshotBuilder,
+};
+
+#[pymodule]
+fn gql_parser(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    module.add_function(wrap_pyfunction!(parse_and_validate, module)?)?;
+    module.add_class::<PyParseResult>()?;
+    module.add_class::<PyProgram>()?;
+    module.add_class::<PyStatement>()?;
+    module.add_class::<PyDiag>()?;
+    module.add_class::<PyDiagLabel>()?;
+    module.add_class::<PyQueryInfo>()?;
+    module.add_class::<PyClauseInfo>()?;
+    module.add_class::<PyPropertyReference>()?;
+    module.add_class::<PyPatternInfo>()?;
+    module.add_class::<PyVariableDependencyGraph>()?;
+    module.add_class::<PyDefinitionPoint>()?;
+    module.add_class::<PyUsagePoint>()?;
+    module.add_class::<PyDefineUseEdge>()?;
+    module.add_class::<PyPropertyMeta>()?;
+    module.add_class::<PyConstraintMeta>()?;
+    module.add_class::<PyNodeTypeBuilder>()?;
+    module.add_class::<PyEdgeTypeBuilder>()?;
+    module.add_class::<PySchemaSnapshotBuilder>()?;
+    module.add_class::<PySchemaSnapshot>()?;
+    Ok(())
+}
+
+/// Parses GQL source text.
+#[pyfunction]
+fn parse(py: Python<'_>, source: &str) -> PyParseResult {
+    py.detach(|| PyParseResult::new(source, None))
+}
+
+/// Parses and semantically validates GQL source text.
+///
+/// With a `schema`, labels and properties are also checked against it as
+/// the default graph.
+#[pyfunction]
+#[pyo3(signature = (source, schema = None, strict = false))]
+fn parse_and_validate(
+    py: Python<'_>,
+    source: &str,
+    schema: Option<PyRef<'_, PySchemaSnapshot>>,
+    strict: bool,
+) -> PyResult<PyParseResult> {
+    let mut provider = MockMetadataProvider::new();
+    let mut validator = SemanticValidator::new().with_strict_mode(strict);
+    if let Some(schema) = &schema {
+        provider.add_schema_snapshot("default", schema.snapshot().clone());
+        validator = validator.with_metadata_provider(&provider);
+    }
+    Ok(py.detach(|| PyParseResult::new(source, Some(&validator))))
+}
+
+/// Outcome of `parse` or `parse_and_validate`.
+#[pyclass(name = "ParseResult", module = "gql_parser", frozen)]
+struct PyParseResult {
+    source: SourceFile,
+    program: Option<Arc<Program>>,
+    diagnostics: Vec<Diag>,
+}
+
+impl PyParseResult {
+    fn new(source: &str, validator: Option<&SemanticValidator<'_>>) -> Self {
+        let (program, diagnostics) = crate::parse_to_diagnostics(source, validator);
+        Self {
+            source: SourceFile::new(source),
+            program: program.map(Arc::new),
+            diagnostics,
+        }
+    }
+}
+
+#[pymethods]
+impl PyParseResult {
+    /// The parsed program, or `None` if parsing failed outright.
+    #[getter]
+    fn ast(&self) -> Option<PyProgram> {
+        self.program.as_ref().map(|program| PyProgram {
+            source: Arc::from(self.source.content()),
+            program: Arc::clone(program),
+        })
+    }
+
+    #[getter]
+    fn diagnostics(&self) -> Vec<PyDiag> {
+        self.diagnostics
+            .iter()
+            .map(|diag| PyDiag::new(diag, &self.source))
+            .collect()
+    }
+
+    #[getter]
+    fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diag| diag.severity == DiagSeverity::Error)
+    }
+
+    /// Renders the diagnostics as `"text"`, `"json"` (JSON lines) or `"sarif"`.
+    #[pyo3(signature = (format = "text"))]
+    fn render(&self, format: &str) -> PyResult<String> {
+        match format {
+            "text" => Ok(render_text(&self.diagnostics, &self.source)),
+            "json" => Ok(render_json_lines(&self.diagnostics, &self.source)),
+            "sarif" => Ok(render_sarif(&self.diagnostics, &self.source)),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown diagnostic format '{format}', expected 'text', 'json' or 'sarif'"
+            ))),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ParseResult(ast={}, diagnostics={})",
+            if self.program.is_some() {
+                "Program"
+            } else {
+                "None"
+            },
+            self.diagnostics.len()
+        )
+    }
+}
+
+/// A parsed program.
+#[pyclass(
+    name = "Program",
+    module = "gql_parser",
+    frozen,
+    sequence,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+struct PyProgram {
+    source: Arc<str>,
+    program: Arc<Program>,
+}
+
+#[pymethods]
+impl PyProgram {
+    #[getter]
+    fn span(&self) -> (usize, usize) {
+        span(&self.program.span)
+    }
+
+    #[getter]
+    fn statements(&self) -> Vec<PyStatement> {
+        (0..self.program.statements.len())
+            .map(|index| self.statement(index))
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.program.statements.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<PyStatement> {
+        let len = self.program.statements.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if (0..len).contains(&index) {
+            Ok(self.statement(index as usize))
+        } else {
+            Err(PyIndexError::new_err("statement index out of range"))
+        }
+    }
+
+    /// Converts the program to nested dictionaries with `kind`, `detail`,
+    /// `span` and `children` keys.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        nest(py, &flat::flatten(&self.program))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Program(statements={})", self.program.statements.len())
+    }
+}
+
+impl PyProgram {
+    fn statement(&self, index: usize) -> PyStatement {
+        PyStatement {
+            program: self.clone(),
+            index,
+        }
+    }
+}
+
+/// A top-level statement of a program.
+#[pyclass(name = "Statement", module = "gql_parser", frozen, skip_from_py_object)]
+#[derive(Clone)]
+struct PyStatement {
+    program: PyProgram,
+    index: usize,
+}
+
+impl PyStatement {
+    fn get(&self) -> &Statement {
+        &self.program.program.statements[self.index]
+    }
+}
+
+#[pymethods]
+impl PyStatement {
+    /// The statement type, such as `"QueryStatement"`.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.get() {
+            Statement::Query(_) => "QueryStatement",
+            Statement::Mutation(_) => "MutationStatement",
+            Statement::Session(_) => "SessionStatement",
+            Statement::Transaction(_) => "TransactionStatement",
+            Statement::Catalog(_) => "CatalogStatement",
+            Statement::Empty(_) => "EmptyStatement",
+        }
+    }
+
+    #[getter]
+    fn span(&self) -> (usize, usize) {
+        span(&self.get().span())
+    }
+
+    /// The source text covered by the statement.
+    #[getter]
+    fn text(&self) -> &str {
+        let span = self.get().span();
+        self.program.source.get(span).unwrap_or_default()
+    }
+
+    /// Converts the statement to nested dictionaries, like `Program.to_dict`.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        nest(py, &flat::flatten_statement(self.get()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Statement(kind={:?}, text={:?})", self.kind(), self.text())
+    }
+}
+
+/// Nests a pre-order node table into dictionaries, returning the root.
+fn nest<'py>(py: Python<'py>, nodes: &[FlatNode]) -> PyResult<Bound<'py, PyDict>> {
+    let mut dicts = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let dict = PyDict::new(py);
+        dict.set_item("kind", node.kind.to_string_lossy())?;
+        dict.set_item("detail", node.detail.as_deref())?;
+        dict.set_item("span", span(&node.span))?;
+        dict.set_item("children", PyList::empty(py))?;
+        if let Some(parent) = node.parent {
+            let parent: &Bound<'py, PyDict> = &dicts[parent];
+            let children = parent
+                .get_item("children")?
+                .expect("every node dictionary has children");
+            children.cast::<PyList>()?.append(&dict)?;
+        }
+        dicts.push(dict);
+    }
+    Ok(dicts.into_iter().next().unwrap_or_else(|| PyDict::new(py)))
+}
+
+fn span(span: &Span) -> (usize, usize) {
+    (span.start, span.end)
+}
+
+/// A diagnostic reported by the parser or validator.
+#[pyclass(
+    name = "Diag",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+struct PyDiag {
+    /// `"error"`, `"warning"` or `"note"`.
+    severity: &'static str,
+    message: String,
+    code: Option<String>,
+    help: Option<String>,
+    notes: Vec<String>,
+    labels: Vec<PyDiagLabel>,
+}
+
+impl PyDiag {
+    fn new(diag: &Diag, source: &SourceFile) -> Self {
+        let severity = match diag.severity {
+            DiagSeverity::Error => "error",
+            DiagSeverity::Warning => "warning",
+            DiagSeverity::Note => "note",
+        };
+        let labels = diag
+            .labels
+            .iter()
+            .map(|label| {
+                let start = source.line_column(label.span.start);
+                let end = source.line_column(label.span.end);
+                PyDiagLabel {
+                    primary: label.role == LabelRole::Primary,
+                    message: label.message.clone(),
+                    span: span(&label.span),
+                    start: (start.line, start.column),
+                    end: (end.line, end.column),
+                }
+            })
+            .collect();
+        Self {
+            severity,
+            message: diag.message.clone(),
+            code: diag.code.clone(),
+            help: diag.help.clone(),
+            notes: diag.notes.clone(),
+            labels,
+        }
+    }
+}
+
+#[pymethods]
+impl PyDiag {
+    fn __str__(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}[{code}]: {}", self.severity, self.message),
+            None => format!("{}: {}", self.severity, self.message),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Diag({:?}, {:?})", self.severity, self.message)
+    }
+}
+
+/// A labelled source span of a diagnostic.
+#[pyclass(
+    name = "DiagLabel",
+    module = "gql_parser",
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+struct PyDiagLabel {
+    primary: bool,
+    message: String,
+    span: (usize, usize),
+    /// `(line, column)` of the span start.
+    start: (usize, usize),
+    /// `(line, column)` of the span end.
+    end: (usize, usize),
+}
+
+#[pymethods]
+impl PyDiagLabel {
+    fn __repr__(&self) -> String {
+        format!("DiagLabel({:?}, span={:?})", self.message, self.span)
+    }
+}
@@ -0,0 +1,340 @@
+//! Python classes for describing a schema to validate against.
+//!
+//! The builders mirror [`SchemaSnapshotBuilder`]: methods return the
+//! builder so calls chain, and the type callbacks of `with_node_type` and
+//! `with_edge_type` receive a fresh type builder to fill in.
+
+use std::sync::Arc;
+
+use pyo3::PyClass;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::type_object::PyTypeCheck;
+use smol_str::SmolStr;
+
+use crate::semantic::schema_catalog::{
+    ConstraintMeta, EdgeTypeBuilder, InMemorySchemaSnapshot, NodeTypeBuilder, PropertyMeta,
+    SchemaSnapshotBuilder,
+};
+
+/// A property declared on a node or edge type.
+#[pyclass(name = "PropertyMeta", module = "gql_parser", frozen)]
+pub(super) struct PyPropertyMeta(PropertyMeta);
+
+#[pymethods]
+impl PyPropertyMeta {
+    #[staticmethod]
+    #[pyo3(signature = (name, required = false))]
+    fn string(name: &str, required: bool) -> Self {
+        Self(PropertyMeta::string(name, required))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (name, required = false))]
+    fn int(name: &str, required: bool) -> Self {
+        Self(PropertyMeta::int(name, required))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (name, precision, scale, required = false))]
+    fn decimal(name: &str, precision: u32, scale: u32, required: bool) -> Self {
+        Self(PropertyMeta::decimal(name, required, precision, scale))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (name, required = false))]
+    fn date(name: &str, required: bool) -> Self {
+        Self(PropertyMeta::date(name, required))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (name, required = false))]
+    fn datetime(name: &str, required: bool) -> Self {
+        Self(PropertyMeta::datetime(name, required))
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    #[getter]
+    fn required(&self) -> bool {
+        self.0.required
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PropertyMeta({:?}, {:?})", self.0.name, self.0.value_type)
+    }
+}
+
+/// A key, uniqueness or mandatory constraint over properties of a type.
+#[pyclass(name = "ConstraintMeta", module = "gql_parser", frozen)]
+pub(super) struct PyConstraintMeta(ConstraintMeta);
+
+#[pymethods]
+impl PyConstraintMeta {
+    #[staticmethod]
+    fn primary_key(properties: Vec<String>) -> Self {
+        Self(ConstraintMeta::PrimaryKey {
+            properties: names(properties),
+        })
+    }
+
+    #[staticmethod]
+    fn unique(properties: Vec<String>) -> Self {
+        Self(ConstraintMeta::Unique {
+            properties: names(properties),
+        })
+    }
+
+    #[staticmethod]
+    fn mandatory(properties: Vec<String>) -> Self {
+        Self(ConstraintMeta::Mandatory {
+            properties: names(properties),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ConstraintMeta({:?})", self.0)
+    }
+}
+
+fn names(properties: Vec<String>) -> Vec<SmolStr> {
+    properties.into_iter().map(SmolStr::from).collect()
+}
+
+/// Takes the builder out of `slot`, applies `update` and puts it back.
+fn update<B>(slot: &mut Option<B>, update: impl FnOnce(B) -> B) -> PyResult<()> {
+    let builder = take(slot)?;
+    *slot = Some(update(builder));
+    Ok(())
+}
+
+fn take<B>(slot: &mut Option<B>) -> PyResult<B> {
+    slot.take()
+        .ok_or_else(|| PyRuntimeError::new_err("builder has already been used"))
+}
+
+/// Builds a node type for `SchemaSnapshotBuilder.with_node_type`.
+#[pyclass(name = "NodeTypeBuilder", module = "gql_parser")]
+pub(super) struct PyNodeTypeBuilder(Option<NodeTypeBuilder>);
+
+#[pymethods]
+impl PyNodeTypeBuilder {
+    #[new]
+    fn new(name: &str) -> Self {
+        Self(Some(NodeTypeBuilder::new(name.into())))
+    }
+
+    fn add_property<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        property: PyRef<'_, PyPropertyMeta>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.add_property(property.0.clone())
+        })?;
+        Ok(slf)
+    }
+
+    fn add_constraint<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        constraint: PyRef<'_, PyConstraintMeta>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.add_constraint(constraint.0.clone())
+        })?;
+        Ok(slf)
+    }
+
+    fn add_metadata<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        key: &str,
+        value: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| builder.add_metadata(key, value))?;
+        Ok(slf)
+    }
+}
+
+/// Builds an edge type for `SchemaSnapshotBuilder.with_edge_type`.
+#[pyclass(name = "EdgeTypeBuilder", module = "gql_parser")]
+pub(super) struct PyEdgeTypeBuilder(Option<EdgeTypeBuilder>);
+
+#[pymethods]
+impl PyEdgeTypeBuilder {
+    #[new]
+    fn new(name: &str) -> Self {
+        Self(Some(EdgeTypeBuilder::new(name.into())))
+    }
+
+    fn add_property<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        property: PyRef<'_, PyPropertyMeta>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.add_property(property.0.clone())
+        })?;
+        Ok(slf)
+    }
+
+    fn add_constraint<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        constraint: PyRef<'_, PyConstraintMeta>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.add_constraint(constraint.0.clone())
+        })?;
+        Ok(slf)
+    }
+
+    /// Declares a directed connection from `source` to `destination` nodes.
+    fn connecting<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        source: &str,
+        destination: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.connecting(source, destination)
+        })?;
+        Ok(slf)
+    }
+
+    fn connecting_undirected<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        source: &str,
+        destination: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| {
+            builder.connecting_undirected(source, destination)
+        })?;
+        Ok(slf)
+    }
+
+    fn add_metadata<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        key: &str,
+        value: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        update(&mut slf.0, |builder| builder.add_metadata(key, value))?;
+        Ok(slf)
+    }
+}
+
+/// Builds a `SchemaSnapshot`.
+///
+/// ```python
+/// schema = (
+///     SchemaSnapshotBuilder()
+///     .with_node_type("Person", lambda t: t.add_property(PropertyMeta.string("name", True)))
+///     .with_edge_type("KNOWS", lambda t: t.connecting("Person", "Person"))
+///     .build()
+/// )
+/// ```
+#[pyclass(name = "SchemaSnapshotBuilder", module = "gql_parser")]
+pub(super) struct PySchemaSnapshotBuilder(Option<SchemaSnapshotBuilder>);
+
+#[pymethods]
+impl PySchemaSnapshotBuilder {
+    #[new]
+    fn new() -> Self {
+        Self(Some(SchemaSnapshotBuilder::new()))
+    }
+
+    /// Adds a node type, passing a `NodeTypeBuilder` to `build` if given.
+    #[pyo3(signature = (name, build = None))]
+    fn with_node_type<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        name: &str,
+        build: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let builder = Bound::new(slf.py(), PyNodeTypeBuilder::new(name))?;
+        let builder = call_builder(builder, build)?;
+        let node_type = take(&mut builder.borrow_mut().0)?;
+        update(&mut slf.0, |schema| {
+            schema.with_node_type(name, |_| node_type)
+        })?;
+        Ok(slf)
+    }
+
+    /// Adds an edge type, passing an `EdgeTypeBuilder` to `build` if given.
+    #[pyo3(signature = (name, build = None))]
+    fn with_edge_type<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        name: &str,
+        build: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let builder = Bound::new(slf.py(), PyEdgeTypeBuilder::new(name))?;
+        let builder = call_builder(builder, build)?;
+        let edge_type = take(&mut builder.borrow_mut().0)?;
+        update(&mut slf.0, |schema| {
+            schema.with_edge_type(name, |_| edge_type)
+        })?;
+        Ok(slf)
+    }
+
+    fn build(mut slf: PyRefMut<'_, Self>) -> PyResult<PySchemaSnapshot> {
+        Ok(PySchemaSnapshot(Arc::new(take(&mut slf.0)?.build())))
+    }
+}
+
+/// Calls the type callback, which may return the builder or `None`.
+fn call_builder<'py, T: PyClass + PyTypeCheck>(
+    builder: Bound<'py, T>,
+    build: Option<Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, T>> {
+    let Some(build) = build else {
+        return Ok(builder);
+    };
+    let returned = build.call1((&builder,))?;
+    if returned.is_none() {
+        Ok(builder)
+    } else {
+        Ok(returned.cast_into::<T>()?)
+    }
+}
+
+/// Node and edge types that `parse_and_validate` checks queries against.
+#[pyclass(name = "SchemaSnapshot", module = "gql_parser", frozen)]
+pub(super) struct PySchemaSnapshot(Arc<InMemorySchemaSnapshot>);
+
+impl PySchemaSnapshot {
+    pub(super) fn snapshot(&self) -> &InMemorySchemaSnapshot {
+        &self.0
+    }
+}
+
+#[pymethods]
+impl PySchemaSnapshot {
+    /// An example schema with common types.
+    #[staticmethod]
+    fn example() -> Self {
+        Self(Arc::new(InMemorySchemaSnapshot::example()))
+    }
+
+    /// Names of the node types, sorted.
+    #[getter]
+    fn node_types(&self) -> Vec<String> {
+        sorted(self.0.node_types.keys())
+    }
+
+    /// Names of the edge types, sorted.
+    #[getter]
+    fn edge_types(&self) -> Vec<String> {
+        sorted(self.0.edge_types.keys())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SchemaSnapshot(node_types={:?}, edge_types={:?})",
+            self.node_types(),
+            self.edge_types()
+        )
+    }
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a SmolStr>) -> Vec<String> {
+    let mut names: Vec<String> = names.map(ToString::to_string).collect();
+    names.sort();
+    names
+}
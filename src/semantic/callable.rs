@@ -4,6 +4,12 @@
 //! their signatures, including arity checking, parameter type validation, and
 //! return type inference.
 //!
+//! Parameter and return types are [`SignatureType`]s: concrete [`Type`]s, type
+//! families such as `NUMERIC`, or type variables (`coalesce(T, ...) -> T`).
+//! [`resolve_overload`] picks the signature whose parameters the argument types
+//! coerce to most cheaply (see [`Type::coercion_cost`]) and instantiates its
+//! return type.
+//!
 //! # Architecture
 //!
 //! Built-in functions are resolved directly via `lookup_builtin_callable()` with zero
@@ -17,6 +23,7 @@
 //! - [`list_builtin_callables`]: List all built-in functions by kind
 //! - [`CallableValidator`]: Trait for validating function calls against signatures
 //! - [`DefaultCallableValidator`]: Default arity and signature validation
+//! - [`resolve_overload`]: Select an overload for typed arguments
 //!
 //! # Example
 //!
@@ -36,7 +43,11 @@
 
 use crate::ast::Span;
 use crate::diag::{Diag, DiagLabel, DiagSeverity};
+use crate::ir::type_table::{Type, TypeConstraint};
 use smol_str::SmolStr;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 // ============================================================================
 // Core Types
@@ -155,8 +166,8 @@ pub enum CallableKind {
     AggregateFunction,
 }
 
-/// Volatility classification for callables.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Volatility classification for callables, ordered from least to most volatile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Volatility {
     /// Always returns same result for same inputs (e.g., ABS, FLOOR).
     Immutable,
@@ -196,7 +207,10 @@ pub struct CallableSignature {
     pub parameters: Vec<ParameterSignature>,
 
     /// Return type (None for procedures that don't return values).
-    pub return_type: Option<SmolStr>,
+    pub return_type: Option<SignatureType>,
+
+    /// Result columns of a procedure, in the order they are yielded.
+    pub columns: Vec<ProcedureColumn>,
 
     /// Volatility classification.
     pub volatility: Volatility,
@@ -211,13 +225,14 @@ impl CallableSignature {
         name: impl Into<SmolStr>,
         kind: CallableKind,
         parameters: Vec<ParameterSignature>,
-        return_type: Option<impl Into<SmolStr>>,
+        return_type: Option<impl Into<SignatureType>>,
     ) -> Self {
         Self {
             name: name.into(),
             kind,
             parameters,
             return_type: return_type.map(|s| s.into()),
+            columns: Vec::new(),
             volatility: Volatility::Immutable,
             nullability: Nullability::NullOnNullInput,
         }
@@ -235,6 +250,22 @@ impl CallableSignature {
         self
     }
 
+    /// Sets the result columns of a procedure.
+    pub fn with_columns(mut self, columns: Vec<ProcedureColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Looks up a result column by name.
+    ///
+    /// Matching is case-insensitive, since the parser normalizes identifiers
+    /// that are also keywords (such as `node` or `result`) to uppercase.
+    pub fn column(&self, name: &str) -> Option<&ProcedureColumn> {
+        self.columns
+            .iter()
+            .find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
     /// Returns the minimum number of required arguments.
     pub fn min_arity(&self) -> usize {
         self.parameters
@@ -260,6 +291,95 @@ impl CallableSignature {
             None => arg_count >= min,
         }
     }
+
+    /// Returns the parameter that receives the argument at `index`.
+    ///
+    /// Arguments past the last parameter are received by a trailing variadic
+    /// parameter.
+    pub fn parameter_for(&self, index: usize) -> Option<&ParameterSignature> {
+        self.parameters
+            .get(index)
+            .or_else(|| self.parameters.last().filter(|p| p.variadic))
+    }
+
+    /// Matches argument types against this signature.
+    ///
+    /// Returns the total coercion cost and the instantiated return type, or
+    /// `None` if the arity does not match or an argument cannot be coerced.
+    pub fn bind(&self, arg_types: &[Type]) -> Option<(u32, Option<Type>)> {
+        if !self.matches_arity(arg_types.len()) {
+            return None;
+        }
+
+        let mut bindings = HashMap::new();
+        for (index, arg) in arg_types.iter().enumerate() {
+            self.parameter_for(index)?
+                .param_type
+                .collect_bindings(arg, &mut bindings)?;
+        }
+
+        let mut cost = 0;
+        for (index, arg) in arg_types.iter().enumerate() {
+            cost += self.parameter_for(index)?
+                .param_type
+                .argument_cost(arg, &bindings)?;
+        }
+
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map(|ty| ty.instantiate(&bindings));
+        Some((cost, return_type))
+    }
+}
+
+impl fmt::Display for CallableSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut variables = Vec::new();
+        for param in &self.parameters {
+            param.param_type.collect_variables(&mut variables);
+        }
+        let constrained: Vec<_> = variables
+            .iter()
+            .filter_map(|(name, constraint)| {
+                constraint
+                    .as_ref()
+                    .map(|c| format!("{}: {}", name, constraint_name(c)))
+            })
+            .collect();
+
+        write!(f, "{}", self.name)?;
+        if !constrained.is_empty() {
+            write!(f, "<{}>", constrained.join(", "))?;
+        }
+        write!(f, "(")?;
+        for (index, param) in self.parameters.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", param.name, param.param_type)?;
+            if param.optional {
+                write!(f, "?")?;
+            }
+            if param.variadic {
+                write!(f, "...")?;
+            }
+        }
+        write!(f, ")")?;
+        if let Some(return_type) = &self.return_type {
+            write!(f, " -> {}", return_type)?;
+        }
+        if !self.columns.is_empty() {
+            write!(f, " YIELD ")?;
+            for (index, column) in self.columns.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", column)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Parameter signature for a callable.
@@ -269,7 +389,7 @@ pub struct ParameterSignature {
     pub name: SmolStr,
 
     /// Parameter type.
-    pub param_type: SmolStr,
+    pub param_type: SignatureType,
 
     /// Whether this parameter is optional.
     pub optional: bool,
@@ -280,7 +400,7 @@ pub struct ParameterSignature {
 
 impl ParameterSignature {
     /// Creates a required parameter.
-    pub fn required(name: impl Into<SmolStr>, param_type: impl Into<SmolStr>) -> Self {
+    pub fn required(name: impl Into<SmolStr>, param_type: impl Into<SignatureType>) -> Self {
         Self {
             name: name.into(),
             param_type: param_type.into(),
@@ -290,7 +410,7 @@ impl ParameterSignature {
     }
 
     /// Creates an optional parameter.
-    pub fn optional(name: impl Into<SmolStr>, param_type: impl Into<SmolStr>) -> Self {
+    pub fn optional(name: impl Into<SmolStr>, param_type: impl Into<SignatureType>) -> Self {
         Self {
             name: name.into(),
             param_type: param_type.into(),
@@ -300,7 +420,7 @@ impl ParameterSignature {
     }
 
     /// Creates a variadic parameter.
-    pub fn variadic(name: impl Into<SmolStr>, param_type: impl Into<SmolStr>) -> Self {
+    pub fn variadic(name: impl Into<SmolStr>, param_type: impl Into<SignatureType>) -> Self {
         Self {
             name: name.into(),
             param_type: param_type.into(),
@@ -310,6 +430,306 @@ impl ParameterSignature {
     }
 }
 
+/// A named result column of a procedure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcedureColumn {
+    /// Column name, as referenced in `YIELD`.
+    pub name: SmolStr,
+
+    /// Column type.
+    pub column_type: SignatureType,
+
+    /// Whether the column may contain nulls.
+    pub nullable: bool,
+}
+
+impl ProcedureColumn {
+    /// Creates a nullable column.
+    pub fn new(name: impl Into<SmolStr>, column_type: impl Into<SignatureType>) -> Self {
+        Self {
+            name: name.into(),
+            column_type: column_type.into(),
+            nullable: true,
+        }
+    }
+
+    /// Marks the column as never containing nulls.
+    pub fn not_null(mut self) -> Self {
+        self.nullable = false;
+        self
+    }
+
+    /// Returns the type of values bound from this column.
+    ///
+    /// Type variables in the column type are not bound by a call, so they
+    /// become [`Type::Any`].
+    pub fn value_type(&self) -> Type {
+        let ty = self.column_type.instantiate(&HashMap::new());
+        if self.nullable {
+            ty
+        } else {
+            Type::not_null(ty)
+        }
+    }
+}
+
+impl fmt::Display for ProcedureColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.column_type)?;
+        if !self.nullable {
+            write!(f, " NOT NULL")?;
+        }
+        Ok(())
+    }
+}
+
+/// Type of a callable parameter or result.
+///
+/// Built from a [`Type`], with the constructors below, or parsed from a type
+/// name with [`str::parse`], which rejects names that are not types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureType {
+    /// Accepts any value.
+    Any,
+    /// A concrete type; arguments must implicitly coerce to it.
+    Exact(Type),
+    /// Any type satisfying the constraint, e.g. any numeric type.
+    Constrained(TypeConstraint),
+    /// A type variable. Arguments bound to the same variable are coerced to
+    /// their common supertype, which is also the variable's value in the
+    /// return type.
+    Generic {
+        /// Variable name, e.g. `T`.
+        name: SmolStr,
+        /// Constraint every binding must satisfy.
+        constraint: Option<TypeConstraint>,
+    },
+    /// A list whose elements match the inner type.
+    List(Box<SignatureType>),
+}
+
+impl SignatureType {
+    /// Creates an unconstrained type variable.
+    pub fn generic(name: impl Into<SmolStr>) -> Self {
+        SignatureType::Generic {
+            name: name.into(),
+            constraint: None,
+        }
+    }
+
+    /// Creates a type variable restricted by `constraint`.
+    pub fn constrained_generic(name: impl Into<SmolStr>, constraint: TypeConstraint) -> Self {
+        SignatureType::Generic {
+            name: name.into(),
+            constraint: Some(constraint),
+        }
+    }
+
+    /// Creates a list type.
+    pub fn list(element: impl Into<SignatureType>) -> Self {
+        SignatureType::List(Box::new(element.into()))
+    }
+
+    /// Binds type variables to the argument type; `None` if two arguments
+    /// bound to the same variable have no common supertype.
+    fn collect_bindings(&self, arg: &Type, bindings: &mut HashMap<SmolStr, Type>) -> Option<()> {
+        match self {
+            SignatureType::Generic { name, .. } => {
+                let bound = match bindings.get(name) {
+                    Some(previous) => Type::supertype(previous, arg)?,
+                    None => arg.clone(),
+                };
+                bindings.insert(name.clone(), bound);
+                Some(())
+            }
+            SignatureType::List(element) => match arg.without_not_null() {
+                Type::List(arg_element) => element.collect_bindings(arg_element, bindings),
+                _ => Some(()),
+            },
+            _ => Some(()),
+        }
+    }
+
+    /// Cost of passing an argument of type `arg`, or `None` if it does not fit.
+    fn argument_cost(&self, arg: &Type, bindings: &HashMap<SmolStr, Type>) -> Option<u32> {
+        match self {
+            SignatureType::Any => arg.coercion_cost(&Type::Any),
+            SignatureType::Exact(ty) => arg.coercion_cost(ty),
+            SignatureType::Constrained(constraint) => constraint_cost(constraint, arg),
+            SignatureType::Generic { name, constraint } => {
+                let bound = bindings.get(name).unwrap_or(&Type::Any);
+                if let Some(constraint) = constraint {
+                    constraint_cost(constraint, bound)?;
+                }
+                arg.coercion_cost(bound)
+            }
+            SignatureType::List(element) => match arg.without_not_null() {
+                Type::List(arg_element) => element.argument_cost(arg_element, bindings),
+                Type::Null | Type::Nothing => Some(1),
+                Type::Any => Some(4),
+                _ => None,
+            },
+        }
+    }
+
+    /// Substitutes bound type variables; unbound ones become [`Type::Any`].
+    pub fn instantiate(&self, bindings: &HashMap<SmolStr, Type>) -> Type {
+        match self {
+            SignatureType::Any => Type::Any,
+            SignatureType::Exact(ty) | SignatureType::Constrained(TypeConstraint::Exact(ty)) => {
+                ty.clone()
+            }
+            SignatureType::Constrained(_) => Type::Any,
+            SignatureType::Generic { name, .. } => bindings.get(name).cloned().unwrap_or(Type::Any),
+            SignatureType::List(element) => Type::List(Box::new(element.instantiate(bindings))),
+        }
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<(SmolStr, Option<TypeConstraint>)>) {
+        match self {
+            SignatureType::Generic { name, constraint }
+                if !variables.iter().any(|(n, _)| n == name) =>
+            {
+                variables.push((name.clone(), constraint.clone()));
+            }
+            SignatureType::List(element) => element.collect_variables(variables),
+            _ => {}
+        }
+    }
+}
+
+/// Cost of a value of type `ty` meeting `constraint`.
+fn constraint_cost(constraint: &TypeConstraint, ty: &Type) -> Option<u32> {
+    match ty.without_not_null() {
+        Type::Any => Some(4),
+        Type::Null | Type::Nothing => Some(1),
+        Type::Union(members) => members
+            .iter()
+            .map(|member| constraint_cost(constraint, member))
+            .try_fold(0, |acc, cost| cost.map(|c| acc.max(c))),
+        ty => constraint.is_satisfied_by(ty).then_some(0),
+    }
+}
+
+fn constraint_name(constraint: &TypeConstraint) -> String {
+    match constraint {
+        TypeConstraint::Numeric => "NUMERIC".to_string(),
+        TypeConstraint::Comparable => "COMPARABLE".to_string(),
+        TypeConstraint::Boolean => "BOOLEAN".to_string(),
+        TypeConstraint::String => "STRING".to_string(),
+        TypeConstraint::Node => "NODE".to_string(),
+        TypeConstraint::Edge => "EDGE".to_string(),
+        TypeConstraint::GraphElement => "GRAPH ELEMENT".to_string(),
+        TypeConstraint::List => "LIST".to_string(),
+        TypeConstraint::Exact(ty) => ty.name(),
+    }
+}
+
+impl From<Type> for SignatureType {
+    fn from(ty: Type) -> Self {
+        SignatureType::Exact(ty)
+    }
+}
+
+impl FromStr for SignatureType {
+    type Err = CatalogError;
+
+    /// Parses `"ANY"`, `"NUMERIC"`, `"LIST"`, `"LIST<...>"` or a GQL value
+    /// type such as `"INT"` or `"ZONED DATETIME"`.
+    ///
+    /// Type variables have no textual form here; build them with
+    /// [`SignatureType::generic`].
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let trimmed = name.trim();
+        let upper = trimmed.to_ascii_uppercase();
+        match upper.as_str() {
+            "ANY" => return Ok(SignatureType::Any),
+            "NUMERIC" => return Ok(SignatureType::Constrained(TypeConstraint::Numeric)),
+            "LIST" => return Ok(SignatureType::list(SignatureType::Any)),
+            "DATETIME" => return Ok(SignatureType::Exact(Type::Timestamp)),
+            _ => {}
+        }
+        if let Some(element) = upper
+            .strip_prefix("LIST<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            let offset = "LIST<".len();
+            return trimmed[offset..offset + element.len()]
+                .parse::<SignatureType>()
+                .map(SignatureType::list);
+        }
+
+        let tokens = crate::lexer::tokenize(trimmed).tokens;
+        match crate::parser::types::parse_value_type(&tokens) {
+            Ok(value_type) => Ok(SignatureType::Exact(Type::from_value_type(&value_type))),
+            Err(_) => Err(CatalogError::Other(format!("unknown type name '{trimmed}'"))),
+        }
+    }
+}
+
+impl fmt::Display for SignatureType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureType::Any => write!(f, "ANY"),
+            SignatureType::Exact(ty) => write!(f, "{}", ty.name()),
+            SignatureType::Constrained(constraint) => write!(f, "{}", constraint_name(constraint)),
+            SignatureType::Generic { name, .. } => write!(f, "{}", name),
+            SignatureType::List(element) => write!(f, "LIST<{}>", element),
+        }
+    }
+}
+
+// ============================================================================
+// Overload Resolution
+// ============================================================================
+
+/// The overload selected for a call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedOverload<'s> {
+    /// The selected signature.
+    pub signature: &'s CallableSignature,
+    /// Sum of the argument coercion costs.
+    pub cost: u32,
+    /// Return type with type variables substituted.
+    pub return_type: Option<Type>,
+}
+
+/// Reasons overload resolution can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverloadError {
+    /// No signature accepts the number of arguments.
+    Arity,
+    /// Some signatures accept the number of arguments, but not their types.
+    ArgumentTypes,
+}
+
+/// Selects the overload whose parameters the arguments coerce to most cheaply.
+///
+/// Ties are broken by declaration order, so the preferred overload should be
+/// listed first. Unknown argument types should be passed as [`Type::Any`].
+pub fn resolve_overload<'s>(
+    sigs: &'s [CallableSignature],
+    arg_types: &[Type],
+) -> Result<ResolvedOverload<'s>, OverloadError> {
+    if !sigs.iter().any(|sig| sig.matches_arity(arg_types.len())) {
+        return Err(OverloadError::Arity);
+    }
+
+    let mut best: Option<ResolvedOverload<'s>> = None;
+    for signature in sigs {
+        let Some((cost, return_type)) = signature.bind(arg_types) else {
+            continue;
+        };
+        if best.as_ref().is_none_or(|b| cost < b.cost) {
+            best = Some(ResolvedOverload {
+                signature,
+                cost,
+                return_type,
+            });
+        }
+    }
+    best.ok_or(OverloadError::ArgumentTypes)
+}
 
 // ============================================================================
 // CallableValidator Trait
@@ -339,6 +759,43 @@ pub trait CallableValidator: Send + Sync {
     ///
     /// Returns diagnostics for any validation errors or warnings.
     fn validate_call(&self, call: &CallSite, sigs: &[CallableSignature]) -> Vec<Diag>;
+
+    /// Validates a call site whose argument types are known.
+    ///
+    /// The default implementation runs [`validate_call`](Self::validate_call)
+    /// and then [`resolve_overload`] on the argument types.
+    fn validate_typed_call(
+        &self,
+        call: &CallSite,
+        arg_types: &[Type],
+        sigs: &[CallableSignature],
+    ) -> Vec<Diag> {
+        let diagnostics = self.validate_call(call, sigs);
+        if !diagnostics.is_empty() {
+            return diagnostics;
+        }
+
+        match resolve_overload(sigs, arg_types) {
+            Err(OverloadError::ArgumentTypes) => {
+                let found: Vec<_> = arg_types.iter().map(Type::name).collect();
+                let mut diag = Diag::error(format!(
+                    "{:?} '{}' cannot be applied to arguments of type ({})",
+                    call.kind,
+                    call.name,
+                    found.join(", ")
+                ))
+                .with_label(DiagLabel::primary(
+                    call.span.clone(),
+                    "no overload accepts these argument types",
+                ));
+                for sig in sigs.iter().filter(|sig| sig.matches_arity(arg_types.len())) {
+                    diag = diag.with_note(format!("Candidate: {}", sig));
+                }
+                vec![diag]
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 // ============================================================================
@@ -384,212 +841,212 @@ fn resolve_builtin_function(name: &str) -> Option<Vec<CallableSignature>> {
             "abs" => CallableSignature::new(
                 "abs",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", numeric())],
+                Some(numeric()),
             ),
             "mod" => CallableSignature::new(
                 "mod",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("x", "NUMERIC"),
-                    ParameterSignature::required("y", "NUMERIC"),
+                    ParameterSignature::required("x", numeric()),
+                    ParameterSignature::required("y", numeric()),
                 ],
-                Some("NUMERIC"),
+                Some(numeric()),
             ),
             "floor" => CallableSignature::new(
                 "floor",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", numeric())],
+                Some(numeric()),
             ),
             "ceil" => CallableSignature::new(
                 "ceil",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", numeric())],
+                Some(numeric()),
             ),
             "sqrt" => CallableSignature::new(
                 "sqrt",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "power" => CallableSignature::new(
                 "power",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("base", "NUMERIC"),
-                    ParameterSignature::required("exponent", "NUMERIC"),
+                    ParameterSignature::required("base", any_numeric()),
+                    ParameterSignature::required("exponent", any_numeric()),
                 ],
-                Some("NUMERIC"),
+                Some(Type::Float),
             ),
             "exp" => CallableSignature::new(
                 "exp",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "ln" => CallableSignature::new(
                 "ln",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "log" => CallableSignature::new(
                 "log",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("base", "NUMERIC"),
-                    ParameterSignature::required("x", "NUMERIC"),
+                    ParameterSignature::required("base", any_numeric()),
+                    ParameterSignature::required("x", any_numeric()),
                 ],
-                Some("NUMERIC"),
+                Some(Type::Float),
             ),
             "log10" => CallableSignature::new(
                 "log10",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "sin" | "cos" | "tan" | "asin" | "acos" | "atan" | "cot" | "sinh" | "cosh" | "tanh" => {
                 CallableSignature::new(
                     name,
                     CallableKind::Function,
-                    vec![ParameterSignature::required("x", "NUMERIC")],
-                    Some("NUMERIC"),
+                    vec![ParameterSignature::required("x", any_numeric())],
+                    Some(Type::Float),
                 )
             }
             "atan2" => CallableSignature::new(
                 "atan2",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("y", "NUMERIC"),
-                    ParameterSignature::required("x", "NUMERIC"),
+                    ParameterSignature::required("y", any_numeric()),
+                    ParameterSignature::required("x", any_numeric()),
                 ],
-                Some("NUMERIC"),
+                Some(Type::Float),
             ),
             "degrees" => CallableSignature::new(
                 "degrees",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "radians" => CallableSignature::new(
                 "radians",
                 CallableKind::Function,
-                vec![ParameterSignature::required("x", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("x", any_numeric())],
+                Some(Type::Float),
             ),
             "round" => CallableSignature::new(
                 "round",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("x", "NUMERIC"),
-                    ParameterSignature::optional("decimals", "INT"),
+                    ParameterSignature::required("x", numeric()),
+                    ParameterSignature::optional("decimals", Type::Int),
                 ],
-                Some("NUMERIC"),
+                Some(numeric()),
             ),
 
             // String functions
             "length" => CallableSignature::new(
                 "length",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("INT"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::Int),
             ),
             "substring" => CallableSignature::new(
                 "substring",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("s", "STRING"),
-                    ParameterSignature::required("start", "INT"),
-                    ParameterSignature::optional("length", "INT"),
+                    ParameterSignature::required("s", Type::String),
+                    ParameterSignature::required("start", Type::Int),
+                    ParameterSignature::optional("length", Type::Int),
                 ],
-                Some("STRING"),
+                Some(Type::String),
             ),
             "upper" => CallableSignature::new(
                 "upper",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::String),
             ),
             "lower" => CallableSignature::new(
                 "lower",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::String),
             ),
             "trim" => CallableSignature::new(
                 "trim",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::String),
             ),
             "ltrim" => CallableSignature::new(
                 "ltrim",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::String),
             ),
             "rtrim" => CallableSignature::new(
                 "rtrim",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::String),
             ),
             "replace" => CallableSignature::new(
                 "replace",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("s", "STRING"),
-                    ParameterSignature::required("search", "STRING"),
-                    ParameterSignature::required("replace", "STRING"),
+                    ParameterSignature::required("s", Type::String),
+                    ParameterSignature::required("search", Type::String),
+                    ParameterSignature::required("replace", Type::String),
                 ],
-                Some("STRING"),
+                Some(Type::String),
             ),
             "concat" => CallableSignature::new(
                 "concat",
                 CallableKind::Function,
-                vec![ParameterSignature::variadic("strings", "STRING")],
-                Some("STRING"),
+                vec![ParameterSignature::variadic("strings", Type::String)],
+                Some(Type::String),
             ),
             "left" => CallableSignature::new(
                 "left",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("s", "STRING"),
-                    ParameterSignature::required("n", "INT"),
+                    ParameterSignature::required("s", Type::String),
+                    ParameterSignature::required("n", Type::Int),
                 ],
-                Some("STRING"),
+                Some(Type::String),
             ),
             "right" => CallableSignature::new(
                 "right",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("s", "STRING"),
-                    ParameterSignature::required("n", "INT"),
+                    ParameterSignature::required("s", Type::String),
+                    ParameterSignature::required("n", Type::Int),
                 ],
-                Some("STRING"),
+                Some(Type::String),
             ),
             "normalize" => CallableSignature::new(
                 "normalize",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("s", "STRING"),
-                    ParameterSignature::optional("form", "STRING"),
+                    ParameterSignature::required("s", Type::String),
+                    ParameterSignature::optional("form", Type::String),
                 ],
-                Some("STRING"),
+                Some(Type::String),
             ),
             "char_length" => CallableSignature::new(
                 "char_length",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("INT"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::Int),
             ),
             "byte_length" => CallableSignature::new(
                 "byte_length",
                 CallableKind::Function,
-                vec![ParameterSignature::required("s", "STRING")],
-                Some("INT"),
+                vec![ParameterSignature::required("s", Type::String)],
+                Some(Type::Int),
             ),
 
             // Temporal functions
@@ -597,143 +1054,203 @@ fn resolve_builtin_function(name: &str) -> Option<Vec<CallableSignature>> {
                 "current_date",
                 CallableKind::Function,
                 vec![],
-                Some("DATE"),
+                Some(Type::Date),
             ).with_volatility(Volatility::Stable),
             "current_time" => CallableSignature::new(
                 "current_time",
                 CallableKind::Function,
                 vec![],
-                Some("TIME"),
+                Some(Type::Time),
             ).with_volatility(Volatility::Stable),
             "current_timestamp" => CallableSignature::new(
                 "current_timestamp",
                 CallableKind::Function,
                 vec![],
-                Some("TIMESTAMP"),
+                Some(Type::Timestamp),
             ).with_volatility(Volatility::Stable),
             "now" => CallableSignature::new(
                 "now",
                 CallableKind::Function,
                 vec![],
-                Some("TIMESTAMP"),
+                Some(Type::Timestamp),
             ).with_volatility(Volatility::Stable),
             "date" => CallableSignature::new(
                 "date",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("year", "INT"),
-                    ParameterSignature::required("month", "INT"),
-                    ParameterSignature::required("day", "INT"),
+                    ParameterSignature::required("year", Type::Int),
+                    ParameterSignature::required("month", Type::Int),
+                    ParameterSignature::required("day", Type::Int),
                 ],
-                Some("DATE"),
+                Some(Type::Date),
             ),
             "time" => CallableSignature::new(
                 "time",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("hour", "INT"),
-                    ParameterSignature::required("minute", "INT"),
-                    ParameterSignature::required("second", "INT"),
-                    ParameterSignature::optional("nanosecond", "INT"),
+                    ParameterSignature::required("hour", Type::Int),
+                    ParameterSignature::required("minute", Type::Int),
+                    ParameterSignature::required("second", Type::Int),
+                    ParameterSignature::optional("nanosecond", Type::Int),
                 ],
-                Some("TIME"),
+                Some(Type::Time),
             ),
             "datetime" => CallableSignature::new(
                 "datetime",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("year", "INT"),
-                    ParameterSignature::required("month", "INT"),
-                    ParameterSignature::required("day", "INT"),
-                    ParameterSignature::optional("hour", "INT"),
-                    ParameterSignature::optional("minute", "INT"),
-                    ParameterSignature::optional("second", "INT"),
+                    ParameterSignature::required("year", Type::Int),
+                    ParameterSignature::required("month", Type::Int),
+                    ParameterSignature::required("day", Type::Int),
+                    ParameterSignature::optional("hour", Type::Int),
+                    ParameterSignature::optional("minute", Type::Int),
+                    ParameterSignature::optional("second", Type::Int),
                 ],
-                Some("DATETIME"),
+                Some(Type::Timestamp),
             ),
             "duration" => CallableSignature::new(
                 "duration",
                 CallableKind::Function,
-                vec![ParameterSignature::required("value", "STRING")],
-                Some("DURATION"),
+                vec![ParameterSignature::required("value", Type::String)],
+                Some(Type::Duration),
             ),
             "duration_between" => CallableSignature::new(
                 "duration_between",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("start", "ANY"),
-                    ParameterSignature::required("end", "ANY"),
+                    ParameterSignature::required("start", SignatureType::Any),
+                    ParameterSignature::required("end", SignatureType::Any),
                 ],
-                Some("DURATION"),
+                Some(Type::Duration),
             ),
 
             // List and cardinality functions
             "elements" => CallableSignature::new(
                 "elements",
                 CallableKind::Function,
-                vec![ParameterSignature::required("list", "LIST")],
-                Some("LIST"),
+                vec![ParameterSignature::required("list", SignatureType::list(SignatureType::Any))],
+                Some(SignatureType::list(SignatureType::Any)),
             ),
             "cardinality" => CallableSignature::new(
                 "cardinality",
                 CallableKind::Function,
-                vec![ParameterSignature::required("collection", "ANY")],
-                Some("INT"),
+                vec![ParameterSignature::required("collection", SignatureType::Any)],
+                Some(Type::Int),
             ),
             "size" => CallableSignature::new(
                 "size",
                 CallableKind::Function,
-                vec![ParameterSignature::required("collection", "ANY")],
-                Some("INT"),
+                vec![ParameterSignature::required("collection", SignatureType::Any)],
+                Some(Type::Int),
             ),
             "path_length" => CallableSignature::new(
                 "path_length",
                 CallableKind::Function,
-                vec![ParameterSignature::required("path", "PATH")],
-                Some("INT"),
+                vec![ParameterSignature::required("path", Type::Path)],
+                Some(Type::Int),
             ),
 
             // Graph functions
             "element_id" => CallableSignature::new(
                 "element_id",
                 CallableKind::Function,
-                vec![ParameterSignature::required("element", "ANY")],
-                Some("STRING"),
+                vec![ParameterSignature::required(
+                    "element",
+                    SignatureType::Constrained(TypeConstraint::GraphElement),
+                )],
+                Some(Type::String),
             ),
 
             // Other utility functions
             "coalesce" => CallableSignature::new(
                 "coalesce",
                 CallableKind::Function,
-                vec![ParameterSignature::variadic("exprs", "ANY")],
-                Some("ANY"),
+                vec![ParameterSignature::variadic("exprs", SignatureType::generic("T"))],
+                Some(SignatureType::generic("T")),
             ).with_nullability(Nullability::CalledOnNullInput),
             "nullif" => CallableSignature::new(
                 "nullif",
                 CallableKind::Function,
                 vec![
-                    ParameterSignature::required("expr1", "ANY"),
-                    ParameterSignature::required("expr2", "ANY"),
+                    ParameterSignature::required("expr1", SignatureType::generic("T")),
+                    ParameterSignature::required("expr2", SignatureType::generic("T")),
                 ],
-                Some("ANY"),
+                Some(SignatureType::generic("T")),
             ),
             "type_of" => CallableSignature::new(
                 "type_of",
                 CallableKind::Function,
-                vec![ParameterSignature::required("expr", "ANY")],
-                Some("STRING"),
+                vec![ParameterSignature::required("expr", SignatureType::Any)],
+                Some(Type::String),
             ),
             "collect" => CallableSignature::new(
                 "collect",
                 CallableKind::Function,
-                vec![ParameterSignature::required("expr", "ANY")],
-                Some("LIST<ANY>"),
+                vec![ParameterSignature::required("expr", SignatureType::generic("T"))],
+                Some(SignatureType::list(SignatureType::generic("T"))),
             ),
 
             _ => return None,
         };
 
-    Some(vec![sig])
+    let mut sigs = vec![sig];
+    sigs.extend(builtin_function_overloads(name));
+    Some(sigs)
+}
+
+/// Additional overloads of built-in functions, tried after the primary
+/// signature.
+fn builtin_function_overloads(name: &str) -> Vec<CallableSignature> {
+    let function = |params, return_type| {
+        CallableSignature::new(name, CallableKind::Function, params, Some(return_type))
+    };
+    match name {
+        "length" => vec![function(vec![ParameterSignature::required("p", Type::Path)], Type::Int)],
+        "byte_length" => vec![function(
+            vec![ParameterSignature::required("b", Type::Bytes)],
+            Type::Int,
+        )],
+        "substring" => vec![function(
+            vec![
+                ParameterSignature::required("b", Type::Bytes),
+                ParameterSignature::required("start", Type::Int),
+                ParameterSignature::optional("length", Type::Int),
+            ],
+            Type::Bytes,
+        )],
+        "left" | "right" => vec![function(
+            vec![
+                ParameterSignature::required("b", Type::Bytes),
+                ParameterSignature::required("n", Type::Int),
+            ],
+            Type::Bytes,
+        )],
+        "trim" | "ltrim" | "rtrim" => {
+            vec![function(vec![ParameterSignature::required("b", Type::Bytes)], Type::Bytes)]
+        }
+        "date" => vec![function(vec![ParameterSignature::required("s", Type::String)], Type::Date)],
+        "time" => vec![function(vec![ParameterSignature::required("s", Type::String)], Type::Time)],
+        "datetime" => vec![function(
+            vec![ParameterSignature::required("s", Type::String)],
+            Type::Timestamp,
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// A numeric type variable: the result has the argument's numeric type.
+fn numeric() -> SignatureType {
+    SignatureType::constrained_generic("N", TypeConstraint::Numeric)
+}
+
+/// Any numeric type, when the result type does not depend on it.
+fn any_numeric() -> SignatureType {
+    SignatureType::Constrained(TypeConstraint::Numeric)
+}
+
+/// A comparable type variable, as taken by `min` and `max`.
+fn comparable() -> SignatureType {
+    SignatureType::constrained_generic("T", TypeConstraint::Comparable)
 }
 
 /// Resolves built-in aggregate functions.
@@ -742,50 +1259,50 @@ fn resolve_builtin_aggregate(name: &str) -> Option<Vec<CallableSignature>> {
             "count" => CallableSignature::new(
                 "count",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::optional("expr", "ANY")],
-                Some("INT"),
+                vec![ParameterSignature::optional("expr", SignatureType::Any)],
+                Some(Type::Int),
             ).with_nullability(Nullability::CalledOnNullInput),
             "sum" => CallableSignature::new(
                 "sum",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("expr", numeric())],
+                Some(numeric()),
             ),
             "avg" => CallableSignature::new(
                 "avg",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("expr", any_numeric())],
+                Some(any_numeric()),
             ),
             "min" => CallableSignature::new(
                 "min",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "ANY")],
-                Some("ANY"),
+                vec![ParameterSignature::required("expr", comparable())],
+                Some(comparable()),
             ),
             "max" => CallableSignature::new(
                 "max",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "ANY")],
-                Some("ANY"),
+                vec![ParameterSignature::required("expr", comparable())],
+                Some(comparable()),
             ),
             "collect" => CallableSignature::new(
                 "collect",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "ANY")],
-                Some("LIST<ANY>"),
+                vec![ParameterSignature::required("expr", SignatureType::generic("T"))],
+                Some(SignatureType::list(SignatureType::generic("T"))),
             ),
             "stddev_samp" => CallableSignature::new(
                 "stddev_samp",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("expr", any_numeric())],
+                Some(Type::Float),
             ),
             "stddev_pop" => CallableSignature::new(
                 "stddev_pop",
                 CallableKind::AggregateFunction,
-                vec![ParameterSignature::required("expr", "NUMERIC")],
-                Some("NUMERIC"),
+                vec![ParameterSignature::required("expr", any_numeric())],
+                Some(Type::Float),
             ),
             _ => return None,
         };
@@ -954,7 +1471,7 @@ mod tests {
         // Test LENGTH
         let sig = lookup_builtin_callable("length", CallableKind::Function).unwrap();
         assert_eq!(sig.name, "length");
-        assert_eq!(sig.return_type, Some("INT".into()));
+        assert_eq!(sig.return_type, Some(SignatureType::Exact(Type::Int)));
 
         // Test SUBSTRING (with optional length parameter)
         let sig = lookup_builtin_callable("substring", CallableKind::Function).unwrap();
@@ -973,12 +1490,85 @@ mod tests {
         // Test COUNT
         let sig = lookup_builtin_callable("count", CallableKind::AggregateFunction).unwrap();
         assert_eq!(sig.name, "count");
-        assert_eq!(sig.return_type, Some("INT".into()));
+        assert_eq!(sig.return_type, Some(SignatureType::Exact(Type::Int)));
 
         // Test SUM
         let sig = lookup_builtin_callable("sum", CallableKind::AggregateFunction).unwrap();
         assert_eq!(sig.name, "sum");
-        assert_eq!(sig.return_type, Some("NUMERIC".into()));
+        assert_eq!(
+            sig.return_type,
+            Some(SignatureType::constrained_generic("N", TypeConstraint::Numeric))
+        );
+    }
+
+    #[test]
+    fn test_signature_type_from_str() {
+        let parse = |name: &str| name.parse::<SignatureType>();
+        assert_eq!(parse("INT"), Ok(SignatureType::Exact(Type::Int)));
+        assert_eq!(parse("ANY"), Ok(SignatureType::Any));
+        assert_eq!(
+            parse("NUMERIC"),
+            Ok(SignatureType::Constrained(TypeConstraint::Numeric))
+        );
+        assert_eq!(parse("list<string>"), Ok(SignatureType::list(Type::String)));
+        for unknown in ["T", "MAP", "INTEGR", "VARCHAR2", "LIST<INTEGR>", ""] {
+            assert!(parse(unknown).is_err(), "{unknown:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_resolve_overload_generic_return_type() {
+        let sigs = resolve_builtin_signatures("coalesce", CallableKind::Function).unwrap();
+        let resolved = resolve_overload(&sigs, &[Type::Int, Type::Null, Type::Float]).unwrap();
+        assert_eq!(resolved.return_type, Some(Type::Float));
+
+        let sigs = resolve_builtin_signatures("abs", CallableKind::Function).unwrap();
+        let resolved = resolve_overload(&sigs, &[Type::Int]).unwrap();
+        assert_eq!(resolved.return_type, Some(Type::Int));
+        assert_eq!(resolved.cost, 0);
+        assert_eq!(
+            resolve_overload(&sigs, &[Type::String]),
+            Err(OverloadError::ArgumentTypes)
+        );
+        assert_eq!(resolve_overload(&sigs, &[]), Err(OverloadError::Arity));
+    }
+
+    #[test]
+    fn test_resolve_overload_prefers_cheapest() {
+        let sigs = resolve_builtin_signatures("length", CallableKind::Function).unwrap();
+        let resolved = resolve_overload(&sigs, &[Type::Path]).unwrap();
+        assert_eq!(
+            resolved.signature.parameters[0].param_type,
+            SignatureType::Exact(Type::Path)
+        );
+
+        // Unknown argument types fall back to declaration order.
+        let resolved = resolve_overload(&sigs, &[Type::Any]).unwrap();
+        assert_eq!(
+            resolved.signature.parameters[0].param_type,
+            SignatureType::Exact(Type::String)
+        );
+    }
+
+    #[test]
+    fn test_signature_display() {
+        let sig = lookup_builtin_callable("abs", CallableKind::Function).unwrap();
+        assert_eq!(sig.to_string(), "abs<N: NUMERIC>(x N) -> N");
+    }
+
+    #[test]
+    fn test_procedure_columns() {
+        let sig =
+            CallableSignature::new("p", CallableKind::Procedure, vec![], None::<SignatureType>)
+                .with_columns(vec![
+                    ProcedureColumn::new("id", Type::Int).not_null(),
+                    ProcedureColumn::new("label", Type::String),
+                ]);
+
+        assert_eq!(sig.column("ID").unwrap().value_type(), Type::not_null(Type::Int));
+        assert_eq!(sig.column("label").unwrap().value_type(), Type::String);
+        assert!(sig.column("missing").is_none());
+        assert_eq!(sig.to_string(), "p() YIELD id Int NOT NULL, label String");
     }
 
     #[test]
@@ -1007,8 +1597,8 @@ mod tests {
         let sig = CallableSignature::new(
             "test",
             CallableKind::Function,
-            vec![ParameterSignature::required("x", "INT")],
-            Some("INT"),
+            vec![ParameterSignature::required("x", Type::Int)],
+            Some(Type::Int),
         );
 
         let call = CallSite {
@@ -1025,8 +1615,8 @@ mod tests {
         let sig = CallableSignature::new(
             "test",
             CallableKind::Function,
-            vec![ParameterSignature::required("x", "INT")],
-            Some("INT"),
+            vec![ParameterSignature::required("x", Type::Int)],
+            Some(Type::Int),
         );
 
         let call = CallSite {
@@ -1047,8 +1637,8 @@ mod tests {
         let sig = CallableSignature::new(
             "test",
             CallableKind::Function,
-            vec![ParameterSignature::required("x", "INT")],
-            Some("INT"),
+            vec![ParameterSignature::required("x", Type::Int)],
+            Some(Type::Int),
         );
         assert!(sig.matches_arity(1));
         assert!(!sig.matches_arity(0));
@@ -1059,10 +1649,10 @@ mod tests {
             "test",
             CallableKind::Function,
             vec![
-                ParameterSignature::required("x", "INT"),
-                ParameterSignature::optional("y", "INT"),
+                ParameterSignature::required("x", Type::Int),
+                ParameterSignature::optional("y", Type::Int),
             ],
-            Some("INT"),
+            Some(Type::Int),
         );
         assert!(sig.matches_arity(1));
         assert!(sig.matches_arity(2));
@@ -1073,8 +1663,8 @@ mod tests {
         let sig = CallableSignature::new(
             "test",
             CallableKind::Function,
-            vec![ParameterSignature::variadic("args", "ANY")],
-            Some("ANY"),
+            vec![ParameterSignature::variadic("args", SignatureType::Any)],
+            Some(SignatureType::Any),
         );
         assert!(sig.matches_arity(0)); // variadic can accept 0
         assert!(sig.matches_arity(1));
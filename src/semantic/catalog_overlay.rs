@@ -0,0 +1,793 @@
+//! Catalog overlay applying DDL statements on top of a metadata provider.
+//!
+//! Catalog statements (`CREATE GRAPH TYPE`, `CREATE GRAPH`, `DROP SCHEMA`, ...)
+//! change what later statements of the same program can refer to. The
+//! [`CatalogOverlay`] records those changes in order and answers
+//! [`MetadataProvider`] queries from them first, falling back to the base
+//! provider for everything the program has not touched.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gql_parser::semantic::{CatalogOverlay, MockMetadataProvider};
+//!
+//! let base = MockMetadataProvider::example();
+//! let mut overlay = CatalogOverlay::new(&base);
+//! for statement in &program.statements {
+//!     if let Statement::Catalog(catalog) = statement {
+//!         diagnostics.extend(overlay.apply(&catalog.kind));
+//!     }
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+
+use crate::ast::{
+    CatalogStatementKind, CreateGraphStatement, CreateGraphTypeStatement,
+    CreateProcedureStatement, GraphTypeSource, GraphTypeSpec,
+};
+use crate::ast::expression::Expression;
+use crate::ast::graph_type::{
+    DirectedArcType, EdgeTypePattern, EdgeTypeSpecification, ElementTypeSpecification,
+    GraphTypeConstraint, LabelSetPhrase, NestedGraphTypeSpecification, NodeTypePattern,
+    NodeTypeSpecification, PropertyTypesSpecification, TypeInheritanceClause,
+};
+use crate::ast::procedure::{NestedProcedureSpecification, Statement as ProcedureStatement};
+use crate::ast::query::{PrimitiveResultStatement, Query, ReturnItemList};
+use crate::ast::references::{
+    GraphReference, GraphTypeReference, ProcedureReference, SchemaReference,
+};
+use crate::ast::{Program, Span, types::ValueType};
+use crate::diag::Diag;
+use crate::semantic::callable::{CallableKind, CallableSignature, ProcedureColumn, SignatureType};
+use crate::semantic::diag::{duplicate_catalog_object, unknown_reference};
+use crate::semantic::metadata_provider::MetadataProvider;
+use crate::semantic::schema_catalog::{
+    CatalogError, ConstraintMeta, EdgeEndpoints, EdgeTypeMeta, GraphRef, InMemorySchemaSnapshot,
+    NodeTypeMeta, PropertyConstraint, PropertyMeta, SchemaRef, SchemaSnapshot, SessionContext,
+    TypeRef, VariableTypeContext,
+};
+
+/// State of a catalog object touched by the program.
+#[derive(Clone)]
+enum Entry<T> {
+    /// Created (or replaced) by a catalog statement.
+    Created(T),
+    /// Dropped by a catalog statement.
+    Dropped,
+}
+
+/// A [`MetadataProvider`] reflecting the catalog statements applied so far.
+///
+/// Objects created by [`CatalogOverlay::apply`] shadow the base provider and
+/// dropped objects are hidden from it. Queries about anything else, including
+/// the active graph and schema, are delegated to the base provider.
+///
+/// Names are matched without their catalog path: `/s/g` and `g` refer to the
+/// same graph. Dropping a schema does not drop the objects inside it.
+pub struct CatalogOverlay<'b> {
+    base: &'b dyn MetadataProvider,
+    schemas: HashMap<SmolStr, Entry<()>>,
+    /// Graphs, with the snapshot of their graph type. `None` for open graphs.
+    graphs: BTreeMap<SmolStr, Entry<Option<Arc<dyn SchemaSnapshot>>>>,
+    graph_types: BTreeMap<SmolStr, Entry<Arc<dyn SchemaSnapshot>>>,
+    procedures: HashMap<SmolStr, Entry<CallableSignature>>,
+}
+
+impl<'b> CatalogOverlay<'b> {
+    /// Creates an overlay with no changes over `base`.
+    pub fn new(base: &'b dyn MetadataProvider) -> Self {
+        Self {
+            base,
+            schemas: HashMap::new(),
+            graphs: BTreeMap::new(),
+            graph_types: BTreeMap::new(),
+            procedures: HashMap::new(),
+        }
+    }
+
+    /// Applies one catalog statement.
+    ///
+    /// Returns diagnostics for objects created twice (without `IF NOT EXISTS`
+    /// or `OR REPLACE`), dropped while missing (without `IF EXISTS`), or
+    /// referenced by a definition but unknown. A statement that fails leaves
+    /// the catalog unchanged, except that a graph whose type cannot be
+    /// resolved is still created so later statements can use it.
+    ///
+    /// Objects named by reference parameters or by relative references such
+    /// as `HOME_GRAPH` cannot be resolved statically and are ignored.
+    pub fn apply(&mut self, statement: &CatalogStatementKind) -> Vec<Diag> {
+        let mut diagnostics = Vec::new();
+        match statement {
+            CatalogStatementKind::CreateSchema(stmt) => {
+                if let Some(name) = schema_name(&stmt.schema)
+                    && may_create(
+                        ObjectChange {
+                            kind: "schema",
+                            name: &name,
+                            exists: self.schema_exists(&name),
+                            span: stmt.schema.span(),
+                        },
+                        stmt.or_replace,
+                        stmt.if_not_exists,
+                        &mut diagnostics,
+                    )
+                {
+                    self.schemas.insert(name, Entry::Created(()));
+                }
+            }
+            CatalogStatementKind::DropSchema(stmt) => {
+                if let Some(name) = schema_name(&stmt.schema)
+                    && may_drop(
+                        ObjectChange {
+                            kind: "schema",
+                            name: &name,
+                            exists: self.schema_exists(&name),
+                            span: stmt.schema.span(),
+                        },
+                        stmt.if_exists,
+                        &mut diagnostics,
+                    )
+                {
+                    self.schemas.insert(name, Entry::Dropped);
+                }
+            }
+            CatalogStatementKind::CreateGraph(stmt) => {
+                self.create_graph(stmt, &mut diagnostics);
+            }
+            CatalogStatementKind::DropGraph(stmt) => {
+                if let Some(name) = graph_name(&stmt.graph)
+                    && may_drop(
+                        ObjectChange {
+                            kind: "graph",
+                            name: &name,
+                            exists: self.graph_exists(&name),
+                            span: stmt.graph.span(),
+                        },
+                        stmt.if_exists,
+                        &mut diagnostics,
+                    )
+                {
+                    self.graphs.insert(name, Entry::Dropped);
+                }
+            }
+            CatalogStatementKind::CreateGraphType(stmt) => {
+                self.create_graph_type(stmt, &mut diagnostics);
+            }
+            CatalogStatementKind::DropGraphType(stmt) => {
+                if let Some(name) = graph_type_name(&stmt.graph_type)
+                    && may_drop(
+                        ObjectChange {
+                            kind: "graph type",
+                            name: &name,
+                            exists: self.get_graph_type_snapshot(&name).is_ok(),
+                            span: stmt.graph_type.span(),
+                        },
+                        stmt.if_exists,
+                        &mut diagnostics,
+                    )
+                {
+                    self.graph_types.insert(name, Entry::Dropped);
+                }
+            }
+            CatalogStatementKind::CreateProcedure(stmt) => {
+                self.create_procedure(stmt, &mut diagnostics);
+            }
+            CatalogStatementKind::DropProcedure(stmt) => {
+                if let Some(name) = procedure_name(&stmt.procedure)
+                    && may_drop(
+                        ObjectChange {
+                            kind: "procedure",
+                            name: &name,
+                            exists: self.lookup_callable(&name).is_some(),
+                            span: stmt.procedure.span(),
+                        },
+                        stmt.if_exists,
+                        &mut diagnostics,
+                    )
+                {
+                    self.procedures.insert(name, Entry::Dropped);
+                }
+            }
+            // The effect of a catalog-modifying procedure is not known statically.
+            CatalogStatementKind::CallCatalogModifyingProcedure(_) => {}
+        }
+        diagnostics
+    }
+
+    fn create_graph(&mut self, stmt: &CreateGraphStatement, diagnostics: &mut Vec<Diag>) {
+        let Some(name) = graph_name(&stmt.graph) else {
+            return;
+        };
+        if !may_create(
+            ObjectChange {
+                kind: "graph",
+                name: &name,
+                exists: self.graph_exists(&name),
+                span: stmt.graph.span(),
+            },
+            stmt.or_replace,
+            stmt.if_not_exists,
+            diagnostics,
+        ) {
+            return;
+        }
+
+        let snapshot = match &stmt.graph_type_spec {
+            None | Some(GraphTypeSpec::Open { .. }) => None,
+            Some(GraphTypeSpec::Of { graph_type, .. }) => {
+                graph_type_name(graph_type).and_then(|type_name| {
+                    self.resolve_graph_type(&type_name, graph_type.span(), diagnostics)
+                })
+            }
+            Some(GraphTypeSpec::Like { graph, .. } | GraphTypeSpec::AsCopyOf { graph, .. }) => {
+                self.resolve_graph(graph, diagnostics).flatten()
+            }
+        };
+        self.graphs.insert(name, Entry::Created(snapshot));
+    }
+
+    fn create_graph_type(
+        &mut self,
+        stmt: &CreateGraphTypeStatement,
+        diagnostics: &mut Vec<Diag>,
+    ) {
+        let Some(name) = graph_type_name(&stmt.graph_type) else {
+            return;
+        };
+        if !may_create(
+            ObjectChange {
+                kind: "graph type",
+                name: &name,
+                exists: self.get_graph_type_snapshot(&name).is_ok(),
+                span: stmt.graph_type.span(),
+            },
+            stmt.or_replace,
+            stmt.if_not_exists,
+            diagnostics,
+        ) {
+            return;
+        }
+
+        let snapshot = match &stmt.source {
+            None => Some(Arc::new(InMemorySchemaSnapshot::new()) as Arc<dyn SchemaSnapshot>),
+            Some(GraphTypeSource::Detailed { specification, .. }) => {
+                Some(Arc::new(graph_type_snapshot(specification)) as Arc<dyn SchemaSnapshot>)
+            }
+            Some(GraphTypeSource::AsCopyOf { graph_type, .. }) => graph_type_name(graph_type)
+                .and_then(|source| self.resolve_graph_type(&source, graph_type.span(), diagnostics)),
+            Some(GraphTypeSource::LikeGraph { graph, .. }) => self
+                .resolve_graph(graph, diagnostics)
+                .map(|snapshot| {
+                    snapshot.unwrap_or_else(|| Arc::new(InMemorySchemaSnapshot::new()))
+                }),
+        };
+        if let Some(snapshot) = snapshot {
+            self.graph_types.insert(name, Entry::Created(snapshot));
+        }
+    }
+
+    fn create_procedure(&mut self, stmt: &CreateProcedureStatement, diagnostics: &mut Vec<Diag>) {
+        let Some(name) = procedure_name(&stmt.procedure) else {
+            return;
+        };
+        if !may_create(
+            ObjectChange {
+                kind: "procedure",
+                name: &name,
+                exists: self.lookup_callable(&name).is_some(),
+                span: stmt.procedure.span(),
+            },
+            stmt.or_replace,
+            stmt.if_not_exists,
+            diagnostics,
+        ) {
+            return;
+        }
+
+        let signature = CallableSignature::new(
+            name.clone(),
+            CallableKind::Procedure,
+            vec![],
+            None::<SignatureType>,
+        )
+        .with_columns(result_columns(&stmt.specification));
+        self.procedures.insert(name, Entry::Created(signature));
+    }
+
+    /// Resolves the graph type a definition refers to, reporting it if missing.
+    fn resolve_graph_type(
+        &self,
+        name: &str,
+        span: Span,
+        diagnostics: &mut Vec<Diag>,
+    ) -> Option<Arc<dyn SchemaSnapshot>> {
+        match self.get_graph_type_snapshot(name) {
+            Ok(snapshot) => Some(snapshot),
+            Err(_) => {
+                diagnostics.push(unknown_reference("graph type", name, span));
+                None
+            }
+        }
+    }
+
+    /// Resolves the graph a definition refers to, reporting it if missing.
+    ///
+    /// Returns `Some(None)` for an existing graph without a known snapshot.
+    fn resolve_graph(
+        &self,
+        graph: &GraphReference,
+        diagnostics: &mut Vec<Diag>,
+    ) -> Option<Option<Arc<dyn SchemaSnapshot>>> {
+        let name = graph_name(graph)?;
+        if !self.graph_exists(&name) {
+            diagnostics.push(unknown_reference("graph", &name, graph.span()));
+            return None;
+        }
+        Some(self.get_schema_snapshot(&GraphRef { name }, None).ok())
+    }
+
+    fn schema_exists(&self, name: &str) -> bool {
+        match self.schemas.get(name) {
+            Some(Entry::Created(())) => true,
+            Some(Entry::Dropped) => false,
+            None => self.base.validate_schema_exists(name).is_ok(),
+        }
+    }
+
+    fn graph_exists(&self, name: &str) -> bool {
+        self.validate_graph_exists(name).is_ok()
+    }
+}
+
+impl MetadataProvider for CatalogOverlay<'_> {
+    fn get_schema_snapshot(
+        &self,
+        graph: &GraphRef,
+        schema: Option<&SchemaRef>,
+    ) -> Result<Arc<dyn SchemaSnapshot>, CatalogError> {
+        match self.graphs.get(&graph.name) {
+            Some(Entry::Created(Some(snapshot))) => Ok(snapshot.clone()),
+            Some(Entry::Created(None)) => Err(CatalogError::SnapshotUnavailable {
+                reason: format!("graph '{}' has an open graph type", graph.name).into(),
+            }),
+            Some(Entry::Dropped) => Err(CatalogError::GraphNotFound {
+                graph: graph.name.clone(),
+            }),
+            None => self.base.get_schema_snapshot(graph, schema),
+        }
+    }
+
+    fn resolve_active_graph(&self, session: &SessionContext) -> Result<GraphRef, CatalogError> {
+        self.base.resolve_active_graph(session)
+    }
+
+    fn resolve_active_schema(&self, graph: &GraphRef) -> Result<SchemaRef, CatalogError> {
+        self.base.resolve_active_schema(graph)
+    }
+
+    fn validate_graph_exists(&self, name: &str) -> Result<(), CatalogError> {
+        match self.graphs.get(name) {
+            Some(Entry::Created(_)) => Ok(()),
+            Some(Entry::Dropped) => Err(CatalogError::GraphNotFound { graph: name.into() }),
+            None => self.base.validate_graph_exists(name),
+        }
+    }
+
+    fn validate_schema_exists(&self, name: &str) -> Result<(), CatalogError> {
+        if self.schema_exists(name) {
+            Ok(())
+        } else {
+            Err(CatalogError::SchemaNotFound {
+                schema: name.into(),
+            })
+        }
+    }
+
+    fn get_graph_type_snapshot(
+        &self,
+        name: &str,
+    ) -> Result<Arc<dyn SchemaSnapshot>, CatalogError> {
+        match self.graph_types.get(name) {
+            Some(Entry::Created(snapshot)) => Ok(snapshot.clone()),
+            Some(Entry::Dropped) => Err(CatalogError::GraphTypeNotFound {
+                graph_type: name.into(),
+            }),
+            None => self.base.get_graph_type_snapshot(name),
+        }
+    }
+
+    fn lookup_callable(&self, name: &str) -> Option<CallableSignature> {
+        match self.procedures.get(name) {
+            Some(Entry::Created(signature)) => Some(signature.clone()),
+            Some(Entry::Dropped) => None,
+            None => self.base.lookup_callable(name),
+        }
+    }
+
+    fn validate_callable_invocation(
+        &self,
+        signature: &CallableSignature,
+        args: &[&Expression],
+    ) -> Result<(), String> {
+        self.base.validate_callable_invocation(signature, args)
+    }
+
+    fn get_property_metadata(&self, owner: &TypeRef, property: &str) -> Option<ValueType> {
+        // Graph types the program created shadow the base provider's, in name order
+        let created_graph_types = self.graph_types.values().filter_map(|entry| match entry {
+            Entry::Created(snapshot) => Some(snapshot),
+            Entry::Dropped => None,
+        });
+        let created_graphs = self.graphs.values().filter_map(|entry| match entry {
+            Entry::Created(snapshot) => snapshot.as_ref(),
+            Entry::Dropped => None,
+        });
+        created_graph_types
+            .chain(created_graphs)
+            .find_map(|snapshot| snapshot.property(owner.clone(), property))
+            .map(|meta| meta.value_type.clone())
+            .or_else(|| self.base.get_property_metadata(owner, property))
+    }
+
+    fn get_callable_return_type_metadata(&self, name: &str) -> Option<ValueType> {
+        self.base.get_callable_return_type_metadata(name)
+    }
+
+    fn get_variable_type_metadata(
+        &self,
+        graph: &GraphRef,
+        program: &Program,
+    ) -> Result<VariableTypeContext, CatalogError> {
+        self.base.get_variable_type_metadata(graph, program)
+    }
+}
+
+// ============================================================================
+// Catalog changes
+// ============================================================================
+
+/// A catalog object a CREATE or DROP statement names.
+struct ObjectChange<'a> {
+    /// Kind of object, as named in diagnostics.
+    kind: &'a str,
+    name: &'a str,
+    /// Whether the object exists before the statement.
+    exists: bool,
+    span: Span,
+}
+
+/// Decides whether a CREATE statement takes effect, reporting duplicates.
+fn may_create(
+    object: ObjectChange<'_>,
+    or_replace: bool,
+    if_not_exists: bool,
+    diagnostics: &mut Vec<Diag>,
+) -> bool {
+    if !object.exists || or_replace {
+        return true;
+    }
+    if !if_not_exists {
+        diagnostics.push(duplicate_catalog_object(object.kind, object.name, object.span));
+    }
+    false
+}
+
+/// Decides whether a DROP statement takes effect, reporting missing objects.
+fn may_drop(object: ObjectChange<'_>, if_exists: bool, diagnostics: &mut Vec<Diag>) -> bool {
+    if !object.exists && !if_exists {
+        diagnostics.push(unknown_reference(object.kind, object.name, object.span));
+    }
+    object.exists
+}
+
+// ============================================================================
+// Reference names
+// ============================================================================
+
+fn schema_name(schema: &SchemaReference) -> Option<SmolStr> {
+    match schema {
+        SchemaReference::AbsolutePath { components, .. }
+        | SchemaReference::RelativePath { components, .. } => components.last().cloned(),
+        SchemaReference::Identifier { name, .. } => Some(name.clone()),
+        SchemaReference::HomeSchema { .. }
+        | SchemaReference::CurrentSchema { .. }
+        | SchemaReference::Dot { .. }
+        | SchemaReference::ReferenceParameter { .. } => None,
+    }
+}
+
+fn graph_name(graph: &GraphReference) -> Option<SmolStr> {
+    match graph {
+        GraphReference::CatalogQualified { name, .. } => Some(name.name.clone()),
+        GraphReference::Delimited { name, .. } => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn graph_type_name(graph_type: &GraphTypeReference) -> Option<SmolStr> {
+    match graph_type {
+        GraphTypeReference::CatalogQualified { name, .. } => Some(name.name.clone()),
+        GraphTypeReference::ReferenceParameter { .. } => None,
+    }
+}
+
+fn procedure_name(procedure: &ProcedureReference) -> Option<SmolStr> {
+    match procedure {
+        ProcedureReference::CatalogQualified { name, .. } => Some(name.name.clone()),
+        ProcedureReference::ReferenceParameter { .. } => None,
+    }
+}
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+/// Builds a snapshot from a graph type definition.
+///
+/// Every label of an element type is registered as a type of its own, so
+/// `NODE TYPE Person LABELS Person & Employee` can be matched by either label.
+/// Element types without labels are registered under their type name.
+pub(crate) fn graph_type_snapshot(specification: &NestedGraphTypeSpecification) -> InMemorySchemaSnapshot {
+    let mut snapshot = InMemorySchemaSnapshot::new();
+    for element in &specification.body.element_types.types {
+        match element {
+            ElementTypeSpecification::Node(node) => {
+                let filler = node.pattern.phrase.filler.as_ref();
+                let properties = filler
+                    .and_then(|filler| filler.property_types.as_ref())
+                    .map(|types| property_metas(&types.specification))
+                    .unwrap_or_default();
+                let constraints =
+                    constraint_metas(filler.map_or(&[][..], |filler| &filler.constraints));
+                let parents = parent_refs(node.inheritance.as_ref(), TypeRef::NodeType);
+                for name in node_type_labels(node) {
+                    snapshot.add_node_type(NodeTypeMeta {
+                        name,
+                        properties: properties.clone(),
+                        constraints: constraints.clone(),
+                        parents: parents.clone(),
+                        metadata: HashMap::new(),
+                    });
+                }
+            }
+            ElementTypeSpecification::Edge(edge) => {
+                let filler = edge_filler(edge);
+                let content = filler.and_then(|filler| filler.phrase.filler_content.as_ref());
+                let properties = content
+                    .and_then(|content| content.property_types.as_ref())
+                    .map(|types| property_metas(&types.specification))
+                    .unwrap_or_default();
+                let constraints =
+                    constraint_metas(filler.map_or(&[][..], |filler| &filler.constraints));
+                let parents = parent_refs(edge.inheritance.as_ref(), TypeRef::EdgeType);
+                let endpoints = edge_endpoints(edge);
+                let labels = content
+                    .and_then(|content| content.label_set.as_ref())
+                    .map(|set| labels(&set.label_set_phrase))
+                    .unwrap_or_else(|| edge.name.iter().cloned().collect());
+                for name in labels {
+                    snapshot.add_edge_type(EdgeTypeMeta {
+                        name,
+                        properties: properties.clone(),
+                        constraints: constraints.clone(),
+                        parents: parents.clone(),
+                        endpoints: endpoints.clone(),
+                        metadata: HashMap::new(),
+                    });
+                }
+            }
+        }
+    }
+    snapshot
+}
+
+fn node_type_labels(node: &NodeTypeSpecification) -> Vec<SmolStr> {
+    let phrase = &node.pattern.phrase;
+    if let Some(label_set) = phrase.filler.as_ref().and_then(|filler| filler.label_set.as_ref()) {
+        return labels(&label_set.label_set_phrase);
+    }
+    node.name
+        .clone()
+        .or_else(|| phrase.alias.as_ref().map(|alias| alias.name.clone()))
+        .into_iter()
+        .collect()
+}
+
+fn edge_filler(edge: &EdgeTypeSpecification) -> Option<&crate::ast::graph_type::EdgeTypeFiller> {
+    match &edge.pattern {
+        EdgeTypePattern::Directed(directed) => match &directed.arc {
+            DirectedArcType::PointingRight(arc) => arc.filler.as_ref(),
+            DirectedArcType::PointingLeft(arc) => arc.filler.as_ref(),
+        },
+        EdgeTypePattern::Undirected(undirected) => undirected.arc.filler.as_ref(),
+    }
+}
+
+fn edge_endpoints(edge: &EdgeTypeSpecification) -> Option<EdgeEndpoints> {
+    let (source, destination, directed) = match &edge.pattern {
+        EdgeTypePattern::Directed(directed) => match directed.arc {
+            DirectedArcType::PointingRight(_) => (&directed.left_endpoint, &directed.right_endpoint, true),
+            DirectedArcType::PointingLeft(_) => (&directed.right_endpoint, &directed.left_endpoint, true),
+        },
+        EdgeTypePattern::Undirected(undirected) => {
+            (&undirected.left_endpoint, &undirected.right_endpoint, false)
+        }
+    };
+    Some(EdgeEndpoints {
+        source: endpoint_name(source)?,
+        destination: endpoint_name(destination)?,
+        directed,
+    })
+}
+
+/// Names an endpoint by its first label, or by the node type it refers to
+/// (`Person` in `CONNECTING (Person TO City)`).
+fn endpoint_name(endpoint: &NodeTypePattern) -> Option<SmolStr> {
+    let phrase = &endpoint.phrase;
+    phrase
+        .filler
+        .as_ref()
+        .and_then(|filler| filler.label_set.as_ref())
+        .and_then(|set| labels(&set.label_set_phrase).into_iter().next())
+        .or_else(|| phrase.alias.as_ref().map(|alias| alias.name.clone()))
+}
+
+fn labels(phrase: &LabelSetPhrase) -> Vec<SmolStr> {
+    match phrase {
+        LabelSetPhrase::Label(label) => vec![label.name.clone()],
+        LabelSetPhrase::Labels(set) | LabelSetPhrase::IsLabelSet(set) => {
+            set.labels.iter().map(|label| label.name.clone()).collect()
+        }
+    }
+}
+
+fn property_metas(specification: &PropertyTypesSpecification) -> BTreeMap<SmolStr, PropertyMeta> {
+    specification
+        .property_types
+        .iter()
+        .flat_map(|list| &list.types)
+        .map(|property| {
+            let defaults = property.default.iter().map(|value| PropertyConstraint::Default {
+                value: (**value).clone(),
+            });
+            let checks = property.check.iter().map(|expression| PropertyConstraint::Check {
+                expression: (**expression).clone(),
+            });
+            let meta = PropertyMeta {
+                name: property.name.name.clone(),
+                value_type: property.value_type.value_type.clone(),
+                required: property.not_null,
+                constraints: defaults.chain(checks).collect(),
+            };
+            (meta.name.clone(), meta)
+        })
+        .collect()
+}
+
+fn constraint_metas(constraints: &[GraphTypeConstraint]) -> Vec<ConstraintMeta> {
+    constraints
+        .iter()
+        .filter_map(ConstraintMeta::from_graph_type_constraint)
+        .collect()
+}
+
+fn parent_refs(
+    inheritance: Option<&TypeInheritanceClause>,
+    to_ref: fn(SmolStr) -> TypeRef,
+) -> Vec<TypeRef> {
+    inheritance
+        .map(|clause| clause.parents.iter().map(|parent| to_ref(parent.name.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// Derives the result columns of a procedure from its final RETURN.
+///
+/// Items are named by their alias, variable or property name; unnamed items
+/// and `RETURN *` produce no columns.
+fn result_columns(specification: &NestedProcedureSpecification) -> Vec<ProcedureColumn> {
+    let block = &specification.body.statements;
+    let last = block
+        .next_statements
+        .last()
+        .map(|next| next.statement.as_ref())
+        .or_else(|| block.statements.last());
+    let Some(ProcedureStatement::CompositeQuery(query)) = last else {
+        return Vec::new();
+    };
+    let Query::Linear(linear) = query.as_ref() else {
+        return Vec::new();
+    };
+    let Some(PrimitiveResultStatement::Return(ret)) = linear.result_statement.as_deref() else {
+        return Vec::new();
+    };
+    let ReturnItemList::Items { items } = &ret.items else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            item.alias.clone().or_else(|| match &item.expression {
+                Expression::VariableReference(name, _) => Some(name.clone()),
+                Expression::PropertyReference(_, property, _) => Some(property.clone()),
+                _ => None,
+            })
+        })
+        .map(|name| ProcedureColumn::new(name, SignatureType::Any))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::type_table::Type;
+    use crate::semantic::MockMetadataProvider;
+
+    fn catalog_statements(source: &str) -> Vec<CatalogStatementKind> {
+        let program = crate::parse(source).ast.expect("program should parse");
+        program
+            .statements
+            .into_iter()
+            .filter_map(|statement| match statement {
+                crate::ast::Statement::Catalog(catalog) => Some(catalog.kind),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_graph_type_snapshot_from_definition() {
+        let base = MockMetadataProvider::new();
+        let mut overlay = CatalogOverlay::new(&base);
+        for statement in catalog_statements(
+            "CREATE GRAPH TYPE social AS { \
+               NODE TYPE Person LABEL Person { name :: STRING NOT NULL, age :: INT }, \
+               DIRECTED EDGE TYPE KNOWS { since :: DATE } CONNECTING (Person TO Person) }",
+        ) {
+            assert!(overlay.apply(&statement).is_empty());
+        }
+
+        let snapshot = overlay.get_graph_type_snapshot("social").unwrap();
+        let person = snapshot.node_type("Person").unwrap();
+        assert!(person.properties["name"].required);
+        assert!(!person.properties["age"].required);
+        assert!(snapshot.edge_type("KNOWS").unwrap().properties.contains_key("since"));
+    }
+
+    #[test]
+    fn test_dropped_objects_hide_base() {
+        let base = MockMetadataProvider::example();
+        let mut overlay = CatalogOverlay::new(&base);
+        assert!(overlay.validate_graph_exists("default").is_ok());
+
+        for statement in catalog_statements("DROP GRAPH default") {
+            assert!(overlay.apply(&statement).is_empty());
+        }
+        assert!(overlay.validate_graph_exists("default").is_err());
+        assert!(base.validate_graph_exists("default").is_ok());
+    }
+
+    #[test]
+    fn test_created_graph_types_shadow_base_property_types() {
+        let person = TypeRef::NodeType("Person".into());
+        let mut base = MockMetadataProvider::new();
+        base.add_property_type_metadata(
+            person.clone(),
+            "age",
+            PropertyMeta::string("age", false).value_type,
+        );
+        let mut overlay = CatalogOverlay::new(&base);
+        for statement in
+            catalog_statements("CREATE GRAPH TYPE social AS { NODE TYPE Person { age :: INT } }")
+        {
+            assert!(overlay.apply(&statement).is_empty());
+        }
+
+        let age = overlay.get_property_metadata(&person, "age").unwrap();
+        assert_eq!(Type::from_value_type(&age), Type::Int);
+        assert_eq!(overlay.get_property_metadata(&person, "name"), None);
+    }
+}
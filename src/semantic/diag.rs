@@ -152,3 +152,202 @@ pub fn variable_shadowing(var_name: &str, span: Span, original_span: Span) -> Di
     .with_primary_label(span, "shadows previous declaration")
     .with_secondary_label(original_span, "originally declared here")
 }
+
+/// Creates an invalid property access diagnostic.
+///
+/// `candidates` lists the element types the variable may be bound to at the
+/// point of access; none of them declares `property`.
+pub fn invalid_property_access(
+    var_name: &str,
+    property: &str,
+    candidates: &[&str],
+    span: Span,
+) -> Diag {
+    Diag::error(format!(
+        "Property '{}' does not exist on any possible type of '{}'",
+        property, var_name
+    ))
+    .with_primary_label(span, format!("'{}' not declared", property))
+    .with_note(format!(
+        "'{}' may be bound to: {}",
+        var_name,
+        candidates.join(", ")
+    ))
+}
+
+/// Creates a diagnostic for a label expression that can never match.
+pub fn unsatisfiable_labels(var_name: &str, span: Span) -> Diag {
+    Diag::warning(format!(
+        "No type in the schema satisfies the labels required of '{}'",
+        var_name
+    ))
+    .with_primary_label(span, "label constraints can never match")
+}
+
+/// Creates a malformed or out-of-range literal diagnostic.
+pub fn invalid_literal(literal_kind: &str, message: &str, span: Span) -> Diag {
+    Diag::error(format!("Invalid {} literal: {}", literal_kind, message))
+        .with_primary_label(span, format!("invalid {}", literal_kind))
+}
+
+/// Creates a diagnostic for a LIMIT, OFFSET or SKIP count that is not a
+/// non-negative integer.
+pub fn invalid_page_count(clause: &str, found: &str, span: Span) -> Diag {
+    Diag::error(format!(
+        "{} count must be a non-negative integer, found {}",
+        clause, found
+    ))
+    .with_primary_label(span, format!("invalid {} count", clause))
+}
+
+/// Creates a diagnostic for a YIELD item naming a column the procedure does
+/// not produce.
+pub fn unknown_yield_column(procedure: &str, column: &str, columns: &[&str], span: Span) -> Diag {
+    let diag = Diag::error(format!(
+        "Procedure '{}' has no result column '{}'",
+        procedure, column
+    ))
+    .with_primary_label(span, "unknown column");
+    if columns.is_empty() {
+        diag.with_note(format!("'{}' does not yield any columns", procedure))
+    } else {
+        diag.with_note(format!("available columns: {}", columns.join(", ")))
+    }
+}
+
+/// Creates a diagnostic for two YIELD items binding the same variable.
+pub fn duplicate_yield_binding(var_name: &str, span: Span, original_span: Span) -> Diag {
+    Diag::error(format!("Variable '{}' is yielded more than once", var_name))
+        .with_primary_label(span, "duplicate binding")
+        .with_secondary_label(original_span, "first yielded here")
+}
+
+/// Creates a diagnostic for a CREATE statement naming a catalog object that
+/// already exists.
+pub fn duplicate_catalog_object(object_kind: &str, name: &str, span: Span) -> Diag {
+    Diag::error(format!("{} '{}' already exists", capitalize(object_kind), name))
+        .with_primary_label(span, format!("{} already exists", object_kind))
+        .with_note("use IF NOT EXISTS to skip creation, or OR REPLACE to replace it")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Creates a diagnostic for a graph-type constraint naming a property that
+/// its node or edge type does not declare.
+pub fn unknown_constraint_property(
+    constraint: &str,
+    property: &str,
+    element_type: &str,
+    span: Span,
+) -> Diag {
+    Diag::error(format!(
+        "{} constraint refers to property '{}', which {} does not declare",
+        constraint, property, element_type
+    ))
+    .with_primary_label(span, "undeclared property")
+}
+
+/// Creates a diagnostic for a statement that modifies data or the catalog
+/// inside a READ ONLY transaction.
+pub fn read_only_transaction_violation(kind: &str, span: Span, start_span: Span) -> Diag {
+    Diag::error(format!(
+        "{} statement cannot run in a READ ONLY transaction",
+        capitalize(kind)
+    ))
+    .with_primary_label(span, format!("{} statement", kind))
+    .with_secondary_label(start_span, "transaction started READ ONLY here")
+}
+
+/// Creates a diagnostic for COMMIT or ROLLBACK with no active transaction.
+pub fn inactive_transaction(command: &str, span: Span) -> Diag {
+    Diag::error(format!("{} without an active transaction", command))
+        .with_primary_label(span, "no transaction was started")
+        .with_help("start one with START TRANSACTION")
+}
+
+/// Creates a diagnostic for START TRANSACTION while a transaction is active.
+pub fn nested_transaction(span: Span, outer_span: Span) -> Diag {
+    Diag::error("START TRANSACTION inside an active transaction")
+        .with_primary_label(span, "transactions cannot be nested")
+        .with_secondary_label(outer_span, "active transaction started here")
+}
+
+/// Creates a diagnostic for a session command while a transaction is active.
+pub fn session_command_in_transaction(span: Span, start_span: Span) -> Diag {
+    Diag::error("Session command cannot run inside a transaction")
+        .with_primary_label(span, "session command")
+        .with_secondary_label(start_span, "active transaction started here")
+        .with_help("COMMIT or ROLLBACK before changing session settings")
+}
+
+/// Creates a diagnostic for an unbounded quantifier in a path pattern that is
+/// neither restrictive nor selective, so the set of matching paths is infinite.
+pub fn unbounded_path_quantifier(span: Span) -> Diag {
+    Diag::error("Unbounded quantifier may match infinitely many paths")
+        .with_primary_label(span, "no upper bound")
+        .with_help(
+            "add a restrictive path mode (TRAIL, SIMPLE, ACYCLIC), a selective search \
+             (ANY, ANY SHORTEST, ALL SHORTEST, SHORTEST k), or an upper bound such as {1,10}",
+        )
+}
+
+/// Creates a diagnostic for a quantifier nested inside another quantified
+/// subpattern without a restrictive path mode.
+pub fn nested_path_quantifier(span: Span, outer_span: Span) -> Diag {
+    Diag::error("Nested quantifier requires a restrictive path mode")
+        .with_primary_label(span, "quantifier nested here")
+        .with_secondary_label(outer_span, "inside this quantifier")
+        .with_help("add TRAIL, SIMPLE or ACYCLIC to the path pattern, or use DIFFERENT EDGES")
+}
+
+/// Creates a diagnostic for a selective path pattern in a graph pattern that
+/// also has a KEEP clause.
+pub fn selective_path_with_keep(span: Span, keep_span: Span) -> Diag {
+    Diag::error("Path search prefix cannot be combined with a KEEP clause")
+        .with_primary_label(span, "selective path pattern")
+        .with_secondary_label(keep_span, "KEEP clause selects paths here")
+        .with_help("move the path search into the KEEP clause")
+}
+
+/// Creates a diagnostic for an element variable declared both inside and
+/// outside a quantified path primary.
+pub fn group_singleton_mix(name: &str, span: Span, other_span: Span) -> Diag {
+    Diag::error(format!(
+        "Variable '{}' is declared both as a group variable and as a singleton",
+        name
+    ))
+    .with_primary_label(span, "declared here")
+    .with_secondary_label(other_span, "also declared here")
+    .with_note(
+        "element variables declared inside a quantified path pattern bind a list of elements",
+    )
+}
+
+/// Creates a diagnostic for a group variable used where a single element is
+/// required.
+pub fn group_variable_as_singleton(name: &str, span: Span, declared_at: Span) -> Diag {
+    Diag::error(format!(
+        "Group variable '{}' cannot be used as a single element here",
+        name
+    ))
+    .with_primary_label(span, "refers to a list of elements")
+    .with_secondary_label(declared_at, "declared inside a quantifier here")
+    .with_help("use it inside the quantified pattern, or aggregate it, e.g. sum(e.weight)")
+}
+
+/// Creates a diagnostic for a group variable compared with `=` or `<>`.
+pub fn group_variable_comparison(name: &str, span: Span, declared_at: Span) -> Diag {
+    Diag::error(format!(
+        "Group variable '{}' cannot be compared for equality",
+        name
+    ))
+    .with_primary_label(span, "group variable")
+    .with_secondary_label(declared_at, "declared inside a quantifier here")
+    .with_note("a group variable binds the list of elements matched by every iteration")
+}
@@ -10,7 +10,7 @@ use crate::semantic::schema_catalog::{
 };
 use crate::semantic::callable::CallableSignature;
 use smol_str::SmolStr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Provides all metadata needed for enhanced semantic validation.
@@ -68,6 +68,31 @@ pub trait MetadataProvider: Send + Sync {
     /// Validates that a graph exists (for USE GRAPH validation).
     fn validate_graph_exists(&self, name: &str) -> Result<(), CatalogError>;
 
+    /// Validates that a schema exists (for CREATE/DROP SCHEMA validation).
+    ///
+    /// # Default Implementation
+    ///
+    /// Reports every schema as missing. Override if the catalog exposes schemas.
+    fn validate_schema_exists(&self, name: &str) -> Result<(), CatalogError> {
+        Err(CatalogError::SchemaNotFound { schema: name.into() })
+    }
+
+    /// Gets the element types declared by a named graph type.
+    ///
+    /// Used to resolve `CREATE GRAPH g :: T` and `CREATE GRAPH TYPE ... AS COPY OF T`.
+    ///
+    /// # Default Implementation
+    ///
+    /// Reports every graph type as missing. Override if the catalog exposes graph types.
+    fn get_graph_type_snapshot(
+        &self,
+        name: &str,
+    ) -> Result<Arc<dyn SchemaSnapshot>, CatalogError> {
+        Err(CatalogError::GraphTypeNotFound {
+            graph_type: name.into(),
+        })
+    }
+
     /// Looks up user-defined callable (function/procedure) signature.
     ///
     /// Returns the signature if the UDF exists, None otherwise.
@@ -164,6 +189,8 @@ pub trait MetadataProvider: Send + Sync {
 /// for unit tests, integration tests, and examples.
 pub struct MockMetadataProvider {
     snapshots: HashMap<SmolStr, Arc<crate::semantic::schema_catalog::InMemorySchemaSnapshot>>,
+    graph_types: HashMap<SmolStr, Arc<crate::semantic::schema_catalog::InMemorySchemaSnapshot>>,
+    schemas: HashSet<SmolStr>,
     callables: HashMap<SmolStr, CallableSignature>,
     property_types: HashMap<(TypeRef, SmolStr), ValueType>,
     default_graph: GraphRef,
@@ -175,6 +202,8 @@ impl MockMetadataProvider {
     pub fn new() -> Self {
         Self {
             snapshots: HashMap::new(),
+            graph_types: HashMap::new(),
+            schemas: HashSet::new(),
             callables: HashMap::new(),
             property_types: HashMap::new(),
             default_graph: GraphRef {
@@ -195,6 +224,20 @@ impl MockMetadataProvider {
         self.snapshots.insert(graph.into(), Arc::new(snapshot));
     }
 
+    /// Adds a named graph type.
+    pub fn add_graph_type(
+        &mut self,
+        name: impl Into<SmolStr>,
+        snapshot: crate::semantic::schema_catalog::InMemorySchemaSnapshot,
+    ) {
+        self.graph_types.insert(name.into(), Arc::new(snapshot));
+    }
+
+    /// Adds a schema. The default schema always exists.
+    pub fn add_schema(&mut self, name: impl Into<SmolStr>) {
+        self.schemas.insert(name.into());
+    }
+
     /// Adds a callable (function/procedure) signature.
     pub fn add_callable(&mut self, name: impl Into<SmolStr>, signature: CallableSignature) {
         self.callables.insert(name.into(), signature);
@@ -265,6 +308,11 @@ impl MockMetadataProvider {
             properties: knows_props,
             constraints: vec![],
             parents: vec![],
+            endpoints: Some(EdgeEndpoints {
+                source: "Person".into(),
+                destination: "Person".into(),
+                directed: true,
+            }),
             metadata: std::collections::HashMap::new(),
         });
 
@@ -299,6 +347,11 @@ impl MockMetadataProvider {
             properties: transfer_props,
             constraints: vec![],
             parents: vec![],
+            endpoints: Some(EdgeEndpoints {
+                source: "Account".into(),
+                destination: "Account".into(),
+                directed: true,
+            }),
             metadata: std::collections::HashMap::new(),
         });
 
@@ -453,6 +506,28 @@ impl MetadataProvider for MockMetadataProvider {
         }
     }
 
+    fn validate_schema_exists(&self, name: &str) -> Result<(), CatalogError> {
+        if name == self.default_schema.name || self.schemas.contains(name) {
+            Ok(())
+        } else {
+            Err(CatalogError::SchemaNotFound {
+                schema: name.into(),
+            })
+        }
+    }
+
+    fn get_graph_type_snapshot(
+        &self,
+        name: &str,
+    ) -> Result<Arc<dyn SchemaSnapshot>, CatalogError> {
+        self.graph_types
+            .get(name)
+            .map(|s| s.clone() as Arc<dyn SchemaSnapshot>)
+            .ok_or_else(|| CatalogError::GraphTypeNotFound {
+                graph_type: name.into(),
+            })
+    }
+
     fn lookup_callable(&self, name: &str) -> Option<CallableSignature> {
         // Only return UDFs - built-ins are checked separately by the validator
         self.callables
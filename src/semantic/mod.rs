@@ -54,11 +54,17 @@
 //! ```
 
 pub mod callable;
+pub mod catalog_overlay;
 pub mod diag;
 pub mod metadata_provider;
 pub mod schema_catalog;
+pub mod schema_diff;
+pub mod schema_export;
+pub mod statistics_provider;
 pub mod type_metadata;
 pub mod validator;
 
+pub use catalog_overlay::CatalogOverlay;
 pub use metadata_provider::{MetadataProvider, MockMetadataProvider};
+pub use statistics_provider::{MockStatisticsProvider, StatisticsProvider};
 pub use validator::{SemanticValidator, ValidationConfig};
@@ -15,6 +15,8 @@
 //! For validation with schema metadata, use `MockMetadataProvider` from
 //! the `metadata_provider` module, which implements the `MetadataProvider` trait.
 
+use crate::ast::expression::Expression;
+use crate::ast::graph_type::GraphTypeConstraint;
 use crate::ast::types::ValueType;
 use smol_str::SmolStr;
 use std::collections::{BTreeMap, HashMap};
@@ -84,10 +86,23 @@ pub struct EdgeTypeMeta {
     pub constraints: Vec<ConstraintMeta>,
     /// Parent edge types (for inheritance)
     pub parents: Vec<TypeRef>,
+    /// Node types this edge type connects, if known
+    pub endpoints: Option<EdgeEndpoints>,
     /// Additional metadata
     pub metadata: HashMap<SmolStr, SmolStr>,
 }
 
+/// The node types connected by an edge type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeEndpoints {
+    /// Source node type
+    pub source: SmolStr,
+    /// Destination node type
+    pub destination: SmolStr,
+    /// Whether edges point from source to destination
+    pub directed: bool,
+}
+
 /// Metadata about a property.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PropertyMeta {
@@ -206,10 +221,10 @@ impl PropertyMeta {
 pub enum PropertyConstraint {
     /// UNIQUE constraint
     Unique,
-    /// CHECK constraint with expression (stored as string for now)
-    Check { expression: SmolStr },
-    /// DEFAULT value (stored as string for now)
-    Default { value: SmolStr },
+    /// CHECK constraint with a condition over the property
+    Check { expression: Expression },
+    /// DEFAULT value
+    Default { value: Expression },
 }
 
 /// Schema-level constraints.
@@ -234,6 +249,44 @@ pub enum ConstraintMeta {
         /// Referenced properties
         referenced_properties: Vec<SmolStr>,
     },
+    /// Mandatory constraint: the properties must be present and non-null
+    Mandatory {
+        /// Property names that are mandatory
+        properties: Vec<SmolStr>,
+    },
+    /// CHECK constraint over the properties of the type
+    Check {
+        /// Condition every element of the type must satisfy; properties
+        /// are referenced by bare name
+        expression: Expression,
+    },
+}
+
+impl ConstraintMeta {
+    /// Converts a constraint declared in a graph type.
+    ///
+    /// `KEY` becomes a primary key. Custom constraints have no catalog
+    /// representation and yield `None`.
+    pub fn from_graph_type_constraint(constraint: &GraphTypeConstraint) -> Option<Self> {
+        let names = || {
+            constraint
+                .properties()
+                .iter()
+                .map(|property| property.name.clone())
+                .collect()
+        };
+        match constraint {
+            GraphTypeConstraint::Key { .. } => Some(ConstraintMeta::PrimaryKey { properties: names() }),
+            GraphTypeConstraint::Unique { .. } => Some(ConstraintMeta::Unique { properties: names() }),
+            GraphTypeConstraint::Mandatory { .. } => {
+                Some(ConstraintMeta::Mandatory { properties: names() })
+            }
+            GraphTypeConstraint::Check { condition, .. } => Some(ConstraintMeta::Check {
+                expression: condition.as_ref().clone(),
+            }),
+            GraphTypeConstraint::Custom { .. } => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -252,6 +305,8 @@ pub enum CatalogError {
     GraphNotFound { graph: SmolStr },
     /// The requested schema was not found
     SchemaNotFound { schema: SmolStr },
+    /// The requested graph type was not found
+    GraphTypeNotFound { graph_type: SmolStr },
     /// Invalid snapshot request
     InvalidRequest { reason: SmolStr },
     /// General catalog error
@@ -270,6 +325,9 @@ impl std::fmt::Display for CatalogError {
             CatalogError::SchemaNotFound { schema } => {
                 write!(f, "Schema '{}' not found", schema)
             }
+            CatalogError::GraphTypeNotFound { graph_type } => {
+                write!(f, "Graph type '{}' not found", graph_type)
+            }
             CatalogError::InvalidRequest { reason } => {
                 write!(f, "Invalid catalog request: {}", reason)
             }
@@ -300,6 +358,10 @@ impl CatalogError {
                 Diag::error(format!("Schema '{}' not found", schema))
                     .with_label(DiagLabel::primary(span, "undefined schema"))
             }
+            CatalogError::GraphTypeNotFound { graph_type } => {
+                Diag::error(format!("Graph type '{}' not found", graph_type))
+                    .with_label(DiagLabel::primary(span, "undefined graph type"))
+            }
             CatalogError::InvalidRequest { reason } => {
                 Diag::error(format!("Invalid catalog request: {}", reason))
                     .with_label(DiagLabel::primary(span, reason.as_str()))
@@ -382,6 +444,22 @@ pub trait SchemaSnapshot: Send + Sync {
     ///
     /// Returns an empty slice if the type has no parents.
     fn parents(&self, owner: TypeRef) -> &[TypeRef];
+
+    /// Lists the names of all node types in the snapshot.
+    ///
+    /// Used to enumerate candidate types when narrowing label expressions
+    /// such as `!Person`. The default returns an empty list, which callers
+    /// treat as "the set of node types is unknown".
+    fn node_type_names(&self) -> Vec<SmolStr> {
+        Vec::new()
+    }
+
+    /// Lists the names of all edge types in the snapshot.
+    ///
+    /// See [`SchemaSnapshot::node_type_names`].
+    fn edge_type_names(&self) -> Vec<SmolStr> {
+        Vec::new()
+    }
 }
 
 // ============================================================================
@@ -519,6 +597,11 @@ impl InMemorySchemaSnapshot {
             properties: knows_props,
             constraints: vec![],
             parents: vec![],
+            endpoints: Some(EdgeEndpoints {
+                source: "Person".into(),
+                destination: "Person".into(),
+                directed: true,
+            }),
             metadata: HashMap::new(),
         });
 
@@ -569,6 +652,18 @@ impl SchemaSnapshot for InMemorySchemaSnapshot {
             }
         }
     }
+
+    fn node_type_names(&self) -> Vec<SmolStr> {
+        let mut names: Vec<SmolStr> = self.node_types.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn edge_type_names(&self) -> Vec<SmolStr> {
+        let mut names: Vec<SmolStr> = self.edge_types.keys().cloned().collect();
+        names.sort();
+        names
+    }
 }
 
 // ============================================================================
@@ -708,6 +803,7 @@ pub struct EdgeTypeBuilder {
     properties: BTreeMap<SmolStr, PropertyMeta>,
     constraints: Vec<ConstraintMeta>,
     parents: Vec<TypeRef>,
+    endpoints: Option<EdgeEndpoints>,
     metadata: HashMap<SmolStr, SmolStr>,
 }
 
@@ -719,6 +815,7 @@ impl EdgeTypeBuilder {
             properties: BTreeMap::new(),
             constraints: vec![],
             parents: vec![],
+            endpoints: None,
             metadata: HashMap::new(),
         }
     }
@@ -741,6 +838,30 @@ impl EdgeTypeBuilder {
         self
     }
 
+    /// Sets the node types a directed edge type connects.
+    pub fn connecting(mut self, source: impl Into<SmolStr>, destination: impl Into<SmolStr>) -> Self {
+        self.endpoints = Some(EdgeEndpoints {
+            source: source.into(),
+            destination: destination.into(),
+            directed: true,
+        });
+        self
+    }
+
+    /// Sets the node types an undirected edge type connects.
+    pub fn connecting_undirected(
+        mut self,
+        source: impl Into<SmolStr>,
+        destination: impl Into<SmolStr>,
+    ) -> Self {
+        self.endpoints = Some(EdgeEndpoints {
+            source: source.into(),
+            destination: destination.into(),
+            directed: false,
+        });
+        self
+    }
+
     /// Adds metadata to the edge type.
     pub fn add_metadata(mut self, key: impl Into<SmolStr>, value: impl Into<SmolStr>) -> Self {
         self.metadata.insert(key.into(), value.into());
@@ -754,6 +875,7 @@ impl EdgeTypeBuilder {
             properties: self.properties,
             constraints: self.constraints,
             parents: self.parents,
+            endpoints: self.endpoints,
             metadata: self.metadata,
         }
     }
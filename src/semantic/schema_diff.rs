@@ -0,0 +1,604 @@
+//! Differences between two versions of a schema.
+//!
+//! [`schema_diff`] compares two [`SchemaSnapshot`]s type by type and
+//! classifies every change as compatible or breaking for existing data and
+//! queries. [`graph_type_diff`] does the same for two graph type definitions,
+//! and [`affected_queries`] runs a corpus of saved queries against both
+//! schemas to find the ones the new schema breaks.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gql_parser::semantic::schema_diff::schema_diff;
+//!
+//! let diff = schema_diff(&*old_snapshot, &*new_snapshot);
+//! for change in diff.breaking() {
+//!     eprintln!("breaking: {change}");
+//! }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+
+use crate::ast::graph_type::NestedGraphTypeSpecification;
+use crate::ast::spanless::Spanless;
+use crate::diag::{Diag, DiagSeverity};
+use crate::ir::type_table::Type;
+use crate::semantic::callable::CallableSignature;
+use crate::semantic::catalog_overlay::graph_type_snapshot;
+use crate::semantic::metadata_provider::MetadataProvider;
+use crate::semantic::schema_catalog::{
+    CatalogError, ConstraintMeta, GraphRef, PropertyMeta, SchemaRef, SchemaSnapshot,
+    SessionContext, TypeRef,
+};
+use crate::semantic::validator::SemanticValidator;
+
+/// Whether a schema change keeps existing data and queries valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compatibility {
+    /// Existing data and queries remain valid.
+    Compatible,
+    /// Existing data may violate the new schema, or queries may stop validating.
+    Breaking,
+}
+
+/// How the declared type of a property changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeChange {
+    /// Every old value is still valid (for example INT32 to INT64).
+    Widened,
+    /// Some old values may no longer fit (for example INT64 to INT32).
+    Narrowed,
+    /// The old and new types are unrelated.
+    Incompatible,
+}
+
+/// A single difference between two schemas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// A node or edge type was added.
+    TypeAdded { owner: TypeRef },
+    /// A node or edge type was removed.
+    TypeRemoved { owner: TypeRef },
+    /// A type was removed and an otherwise identical one added under a new name.
+    TypeRenamed { from: TypeRef, to: TypeRef },
+    /// A property was added to a type.
+    PropertyAdded {
+        owner: TypeRef,
+        property: SmolStr,
+        required: bool,
+    },
+    /// A property was removed from a type.
+    PropertyRemoved { owner: TypeRef, property: SmolStr },
+    /// The declared type of a property changed.
+    PropertyTypeChanged {
+        owner: TypeRef,
+        property: SmolStr,
+        from: Type,
+        to: Type,
+        change: TypeChange,
+    },
+    /// A property became required (`required: true`) or optional.
+    RequiredChanged {
+        owner: TypeRef,
+        property: SmolStr,
+        required: bool,
+    },
+    /// A constraint was added to a type.
+    ConstraintAdded {
+        owner: TypeRef,
+        constraint: ConstraintMeta,
+    },
+    /// A constraint was removed from a type.
+    ConstraintRemoved {
+        owner: TypeRef,
+        constraint: ConstraintMeta,
+    },
+    /// A type gained a parent.
+    ParentAdded { owner: TypeRef, parent: TypeRef },
+    /// A type lost a parent.
+    ParentRemoved { owner: TypeRef, parent: TypeRef },
+}
+
+impl SchemaChange {
+    /// Classifies the change.
+    ///
+    /// Additions of types, optional properties and parents, widened property
+    /// types, relaxed requirements and removed constraints are compatible;
+    /// everything else can invalidate stored data or saved queries.
+    pub fn compatibility(&self) -> Compatibility {
+        match self {
+            SchemaChange::TypeAdded { .. }
+            | SchemaChange::PropertyAdded {
+                required: false, ..
+            }
+            | SchemaChange::PropertyTypeChanged {
+                change: TypeChange::Widened,
+                ..
+            }
+            | SchemaChange::RequiredChanged {
+                required: false, ..
+            }
+            | SchemaChange::ConstraintRemoved { .. }
+            | SchemaChange::ParentAdded { .. } => Compatibility::Compatible,
+            _ => Compatibility::Breaking,
+        }
+    }
+
+    /// Returns whether the change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+
+    /// Returns the type the change applies to; for renames, the old type.
+    pub fn owner(&self) -> &TypeRef {
+        match self {
+            SchemaChange::TypeAdded { owner }
+            | SchemaChange::TypeRemoved { owner }
+            | SchemaChange::TypeRenamed { from: owner, .. }
+            | SchemaChange::PropertyAdded { owner, .. }
+            | SchemaChange::PropertyRemoved { owner, .. }
+            | SchemaChange::PropertyTypeChanged { owner, .. }
+            | SchemaChange::RequiredChanged { owner, .. }
+            | SchemaChange::ConstraintAdded { owner, .. }
+            | SchemaChange::ConstraintRemoved { owner, .. }
+            | SchemaChange::ParentAdded { owner, .. }
+            | SchemaChange::ParentRemoved { owner, .. } => owner,
+        }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::TypeAdded { owner } => write!(f, "{} added", describe(owner)),
+            SchemaChange::TypeRemoved { owner } => write!(f, "{} removed", describe(owner)),
+            SchemaChange::TypeRenamed { from, to } => {
+                write!(f, "{} renamed to '{}'", describe(from), type_name(to))
+            }
+            SchemaChange::PropertyAdded {
+                owner,
+                property,
+                required,
+            } => write!(
+                f,
+                "{} property '{}' added to {}",
+                if *required { "required" } else { "optional" },
+                property,
+                describe(owner)
+            ),
+            SchemaChange::PropertyRemoved { owner, property } => {
+                write!(f, "property '{}' removed from {}", property, describe(owner))
+            }
+            SchemaChange::PropertyTypeChanged {
+                owner,
+                property,
+                from,
+                to,
+                change,
+            } => write!(
+                f,
+                "property '{}' of {} {} from {} to {}",
+                property,
+                describe(owner),
+                match change {
+                    TypeChange::Widened => "widened",
+                    TypeChange::Narrowed => "narrowed",
+                    TypeChange::Incompatible => "changed",
+                },
+                from.name(),
+                to.name()
+            ),
+            SchemaChange::RequiredChanged {
+                owner,
+                property,
+                required,
+            } => write!(
+                f,
+                "property '{}' of {} is now {}",
+                property,
+                describe(owner),
+                if *required { "required" } else { "optional" }
+            ),
+            SchemaChange::ConstraintAdded { owner, constraint } => {
+                write!(f, "{} constraint added to {}", constraint_kind(constraint), describe(owner))
+            }
+            SchemaChange::ConstraintRemoved { owner, constraint } => write!(
+                f,
+                "{} constraint removed from {}",
+                constraint_kind(constraint),
+                describe(owner)
+            ),
+            SchemaChange::ParentAdded { owner, parent } => {
+                write!(f, "{} now inherits '{}'", describe(owner), type_name(parent))
+            }
+            SchemaChange::ParentRemoved { owner, parent } => {
+                write!(f, "{} no longer inherits '{}'", describe(owner), type_name(parent))
+            }
+        }
+    }
+}
+
+fn type_name(owner: &TypeRef) -> &str {
+    match owner {
+        TypeRef::NodeType(name) | TypeRef::EdgeType(name) => name,
+    }
+}
+
+fn describe(owner: &TypeRef) -> String {
+    match owner {
+        TypeRef::NodeType(name) => format!("node type '{}'", name),
+        TypeRef::EdgeType(name) => format!("edge type '{}'", name),
+    }
+}
+
+fn constraint_kind(constraint: &ConstraintMeta) -> &'static str {
+    match constraint {
+        ConstraintMeta::PrimaryKey { .. } => "KEY",
+        ConstraintMeta::Unique { .. } => "UNIQUE",
+        ConstraintMeta::ForeignKey { .. } => "FOREIGN KEY",
+        ConstraintMeta::Mandatory { .. } => "MANDATORY",
+        ConstraintMeta::Check { .. } => "CHECK",
+    }
+}
+
+/// The changes between two schemas, ordered by type and then by kind.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDiff {
+    /// All changes.
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Returns whether the schemas are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns whether any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(SchemaChange::is_breaking)
+    }
+
+    /// Iterates over the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+
+    /// Iterates over the compatible changes.
+    pub fn compatible(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|change| !change.is_breaking())
+    }
+}
+
+/// The parts of a node or edge type that are compared.
+struct TypeShape<'a> {
+    properties: &'a BTreeMap<SmolStr, PropertyMeta>,
+    constraints: &'a [ConstraintMeta],
+    parents: &'a [TypeRef],
+}
+
+fn shape<'a>(snapshot: &'a dyn SchemaSnapshot, owner: &TypeRef) -> Option<TypeShape<'a>> {
+    match owner {
+        TypeRef::NodeType(name) => snapshot.node_type(name).map(|meta| TypeShape {
+            properties: &meta.properties,
+            constraints: &meta.constraints,
+            parents: &meta.parents,
+        }),
+        TypeRef::EdgeType(name) => snapshot.edge_type(name).map(|meta| TypeShape {
+            properties: &meta.properties,
+            constraints: &meta.constraints,
+            parents: &meta.parents,
+        }),
+    }
+}
+
+fn owners(snapshot: &dyn SchemaSnapshot) -> BTreeSet<(u8, SmolStr)> {
+    let nodes = snapshot.node_type_names().into_iter().map(|name| (0, name));
+    let edges = snapshot.edge_type_names().into_iter().map(|name| (1, name));
+    nodes.chain(edges).collect()
+}
+
+fn type_ref((kind, name): &(u8, SmolStr)) -> TypeRef {
+    if *kind == 0 {
+        TypeRef::NodeType(name.clone())
+    } else {
+        TypeRef::EdgeType(name.clone())
+    }
+}
+
+/// Compares two schema snapshots.
+///
+/// A type that disappears while a type of the same kind with exactly the
+/// same (non-empty) set of properties appears is reported as a rename.
+pub fn schema_diff(old: &dyn SchemaSnapshot, new: &dyn SchemaSnapshot) -> SchemaDiff {
+    let old_owners = owners(old);
+    let new_owners = owners(new);
+    let mut changes = Vec::new();
+
+    let mut added: Vec<_> = new_owners.difference(&old_owners).cloned().collect();
+    for removed in old_owners.difference(&new_owners) {
+        let from = type_ref(removed);
+        let old_shape = shape(old, &from).expect("listed type exists");
+        let renamed = added.iter().position(|candidate| {
+            candidate.0 == removed.0
+                && shape(new, &type_ref(candidate))
+                    .is_some_and(|new_shape| same_properties(&old_shape, &new_shape))
+        });
+        match renamed {
+            Some(index) => {
+                let to = type_ref(&added.remove(index));
+                changes.push(SchemaChange::TypeRenamed { from, to });
+            }
+            None => changes.push(SchemaChange::TypeRemoved { owner: from }),
+        }
+    }
+    changes.extend(
+        added
+            .iter()
+            .map(|owner| SchemaChange::TypeAdded { owner: type_ref(owner) }),
+    );
+
+    for owner in old_owners.intersection(&new_owners) {
+        let owner = type_ref(owner);
+        let (Some(old_shape), Some(new_shape)) = (shape(old, &owner), shape(new, &owner)) else {
+            continue;
+        };
+        diff_type(&owner, &old_shape, &new_shape, &mut changes);
+    }
+
+    SchemaDiff { changes }
+}
+
+/// Compares two graph type definitions.
+pub fn graph_type_diff(
+    old: &NestedGraphTypeSpecification,
+    new: &NestedGraphTypeSpecification,
+) -> SchemaDiff {
+    schema_diff(&graph_type_snapshot(old), &graph_type_snapshot(new))
+}
+
+fn same_properties(old: &TypeShape, new: &TypeShape) -> bool {
+    !old.properties.is_empty()
+        && old.properties.len() == new.properties.len()
+        && old.properties.iter().all(|(name, property)| {
+            new.properties.get(name).is_some_and(|other| {
+                property_type(property) == property_type(other) && property.required == other.required
+            })
+        })
+}
+
+fn property_type(property: &PropertyMeta) -> Type {
+    Type::from_value_type(&property.value_type)
+        .without_not_null()
+        .clone()
+}
+
+fn diff_type(owner: &TypeRef, old: &TypeShape, new: &TypeShape, changes: &mut Vec<SchemaChange>) {
+    for (name, property) in old.properties {
+        let Some(other) = new.properties.get(name) else {
+            changes.push(SchemaChange::PropertyRemoved {
+                owner: owner.clone(),
+                property: name.clone(),
+            });
+            continue;
+        };
+        let (from, to) = (property_type(property), property_type(other));
+        if from != to {
+            let change = match (from.can_coerce_to(&to), to.can_coerce_to(&from)) {
+                (true, true) => None,
+                (true, false) => Some(TypeChange::Widened),
+                (false, true) => Some(TypeChange::Narrowed),
+                (false, false) => Some(TypeChange::Incompatible),
+            };
+            if let Some(change) = change {
+                changes.push(SchemaChange::PropertyTypeChanged {
+                    owner: owner.clone(),
+                    property: name.clone(),
+                    from,
+                    to,
+                    change,
+                });
+            }
+        }
+        if property.required != other.required {
+            changes.push(SchemaChange::RequiredChanged {
+                owner: owner.clone(),
+                property: name.clone(),
+                required: other.required,
+            });
+        }
+    }
+    for (name, property) in new.properties {
+        if !old.properties.contains_key(name) {
+            changes.push(SchemaChange::PropertyAdded {
+                owner: owner.clone(),
+                property: name.clone(),
+                required: property.required,
+            });
+        }
+    }
+
+    // Constraints compare by structure; CHECK expressions ignore source spans.
+    let contains = |constraints: &[ConstraintMeta], constraint: &ConstraintMeta| {
+        constraints.iter().any(|other| other.spanless_eq(constraint))
+    };
+    for constraint in old.constraints {
+        if !contains(new.constraints, constraint) {
+            changes.push(SchemaChange::ConstraintRemoved {
+                owner: owner.clone(),
+                constraint: constraint.clone(),
+            });
+        }
+    }
+    for constraint in new.constraints {
+        if !contains(old.constraints, constraint) {
+            changes.push(SchemaChange::ConstraintAdded {
+                owner: owner.clone(),
+                constraint: constraint.clone(),
+            });
+        }
+    }
+
+    for parent in old.parents {
+        if !new.parents.contains(parent) {
+            changes.push(SchemaChange::ParentRemoved {
+                owner: owner.clone(),
+                parent: parent.clone(),
+            });
+        }
+    }
+    for parent in new.parents {
+        if !old.parents.contains(parent) {
+            changes.push(SchemaChange::ParentAdded {
+                owner: owner.clone(),
+                parent: parent.clone(),
+            });
+        }
+    }
+}
+
+// ============================================================================
+// Saved queries
+// ============================================================================
+
+/// A saved query that stops validating under the new schema.
+#[derive(Debug, Clone)]
+pub struct QueryImpact<'q> {
+    /// Position of the query in the corpus.
+    pub index: usize,
+    /// Query source.
+    pub source: &'q str,
+    /// Errors reported under the new schema but not under the old one.
+    pub diagnostics: Vec<Diag>,
+}
+
+/// Validates every query against both schemas and returns those that gain
+/// errors under `new`. An error is new unless `old` reports the same message
+/// at the same spans.
+///
+/// Each schema serves every graph, so `USE` clauses do not affect the result.
+/// Queries that do not parse are skipped.
+pub fn affected_queries<'q>(
+    queries: &[&'q str],
+    old: Arc<dyn SchemaSnapshot>,
+    new: Arc<dyn SchemaSnapshot>,
+) -> Vec<QueryImpact<'q>> {
+    let old = SingleSchemaProvider { snapshot: old };
+    let new = SingleSchemaProvider { snapshot: new };
+
+    queries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &source)| {
+            let program = crate::parse(source).ast?;
+            let before = errors(SemanticValidator::new().with_metadata_provider(&old), &program);
+            let diagnostics: Vec<Diag> =
+                errors(SemanticValidator::new().with_metadata_provider(&new), &program)
+                    .into_iter()
+                    .filter(|diag| !before.iter().any(|old| same_error(old, diag)))
+                    .collect();
+            (!diagnostics.is_empty()).then_some(QueryImpact {
+                index,
+                source,
+                diagnostics,
+            })
+        })
+        .collect()
+}
+
+/// Returns true if two errors report the same problem at the same place.
+fn same_error(a: &Diag, b: &Diag) -> bool {
+    let (a_spans, b_spans) = (a.labels.iter(), b.labels.iter());
+    a.message == b.message && a_spans.map(|label| &label.span).eq(b_spans.map(|label| &label.span))
+}
+
+fn errors(validator: SemanticValidator, program: &crate::ast::Program) -> Vec<Diag> {
+    validator
+        .validate(program)
+        .diagnostics
+        .into_iter()
+        .filter(|diag| diag.severity == DiagSeverity::Error)
+        .collect()
+}
+
+/// Serves one snapshot for every graph.
+struct SingleSchemaProvider {
+    snapshot: Arc<dyn SchemaSnapshot>,
+}
+
+impl MetadataProvider for SingleSchemaProvider {
+    fn get_schema_snapshot(
+        &self,
+        _graph: &GraphRef,
+        _schema: Option<&SchemaRef>,
+    ) -> Result<Arc<dyn SchemaSnapshot>, CatalogError> {
+        Ok(self.snapshot.clone())
+    }
+
+    fn resolve_active_graph(&self, session: &SessionContext) -> Result<GraphRef, CatalogError> {
+        Ok(GraphRef {
+            name: session.active_graph.clone().unwrap_or_else(|| "default".into()),
+        })
+    }
+
+    fn resolve_active_schema(&self, _graph: &GraphRef) -> Result<SchemaRef, CatalogError> {
+        Ok(SchemaRef {
+            name: "default".into(),
+        })
+    }
+
+    fn validate_graph_exists(&self, _name: &str) -> Result<(), CatalogError> {
+        Ok(())
+    }
+
+    fn lookup_callable(&self, _name: &str) -> Option<CallableSignature> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::schema_catalog::{InMemorySchemaSnapshot, SchemaSnapshotBuilder};
+
+    fn person(builder: SchemaSnapshotBuilder) -> InMemorySchemaSnapshot {
+        builder
+            .with_node_type("Person", |b| {
+                b.add_property(PropertyMeta::string("name", true))
+                    .add_property(PropertyMeta::int("age", false))
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_no_changes() {
+        let old = person(SchemaSnapshotBuilder::new());
+        let new = person(SchemaSnapshotBuilder::new());
+        assert!(schema_diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_rename_is_detected() {
+        let old = person(SchemaSnapshotBuilder::new());
+        let new = SchemaSnapshotBuilder::new()
+            .with_node_type("Human", |b| {
+                b.add_property(PropertyMeta::string("name", true))
+                    .add_property(PropertyMeta::int("age", false))
+            })
+            .build();
+
+        let diff = schema_diff(&old, &new);
+        assert_eq!(
+            diff.changes,
+            vec![SchemaChange::TypeRenamed {
+                from: TypeRef::NodeType("Person".into()),
+                to: TypeRef::NodeType("Human".into()),
+            }]
+        );
+        assert!(diff.is_breaking());
+    }
+}
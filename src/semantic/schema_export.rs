@@ -0,0 +1,412 @@
+//! Graph type definitions generated from a schema snapshot.
+//!
+//! [`export_graph_type`] turns any [`SchemaSnapshot`] into a canonical
+//! `CREATE GRAPH TYPE` statement, and [`graph_type_ddl`] renders it as text.
+//! Node types come first, then edge types, each sorted by name; properties
+//! are listed by name. Parsing the text and applying it through a
+//! [`CatalogOverlay`](crate::semantic::CatalogOverlay) yields a snapshot
+//! that [`schema_diff`](crate::semantic::schema_diff::schema_diff) reports
+//! as unchanged.
+//!
+//! Graph types have no property-level constraints, so UNIQUE and CHECK
+//! constraints attached to a [`PropertyMeta`] are written as constraints of
+//! the owning type and read back that way. Foreign keys and property
+//! defaults have no graph type syntax at all and are reported as errors.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gql_parser::semantic::schema_catalog::InMemorySchemaSnapshot;
+//! use gql_parser::semantic::schema_export::graph_type_ddl;
+//!
+//! let ddl = graph_type_ddl("social", &InMemorySchemaSnapshot::example())?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use smol_str::SmolStr;
+
+use crate::ast::graph_type::{
+    ArcTypePointingRight, ArcTypeUndirected, DirectedArcType, EdgeKind, EdgeTypeFiller,
+    EdgeTypeLabelSet, EdgeTypePattern, EdgeTypePatternDirected, EdgeTypePatternUndirected,
+    EdgeTypePhrase, EdgeTypePhraseContent, EdgeTypePropertyTypes, EdgeTypeSpecification,
+    ElementTypeList, ElementTypeSpecification, EndpointPair, EndpointPairPhrase,
+    GraphTypeConstraint, GraphTypeSpecificationBody, InheritedTypeReference, LabelName,
+    LabelSetPhrase, LocalNodeTypeAlias, NestedGraphTypeSpecification, NodeTypeFiller,
+    NodeTypeLabelSet, NodeTypePattern, NodeTypePhrase, NodeTypePropertyTypes, NodeTypeReference,
+    NodeTypeSpecification, PropertyName, PropertyType, PropertyTypeList,
+    PropertyTypesSpecification, PropertyValueType, TypeInheritanceClause,
+};
+use crate::ast::references::{CatalogQualifiedName, GraphTypeReference};
+use crate::ast::{CreateGraphTypeStatement, GraphTypeSource, Span};
+use crate::diag::Diag;
+use crate::emit::{EmitResult, gql};
+use crate::semantic::schema_catalog::{
+    ConstraintMeta, EdgeEndpoints, PropertyConstraint, PropertyMeta, SchemaSnapshot, TypeRef,
+};
+
+const SYNTHETIC_SPAN: Span = 0..0;
+
+/// Builds `CREATE GRAPH TYPE name AS { ... }` describing every type in
+/// `snapshot`.
+///
+/// Returns the diagnostics for constraints that cannot be expressed in a
+/// graph type instead of silently dropping them.
+pub fn export_graph_type(
+    name: &str,
+    snapshot: &dyn SchemaSnapshot,
+) -> Result<CreateGraphTypeStatement, Vec<Diag>> {
+    let specification = graph_type_specification(snapshot)?;
+    Ok(CreateGraphTypeStatement {
+        property: false,
+        or_replace: false,
+        if_not_exists: false,
+        graph_type: GraphTypeReference::CatalogQualified {
+            name: CatalogQualifiedName {
+                parent: None,
+                name: name.into(),
+                span: SYNTHETIC_SPAN,
+            },
+            span: SYNTHETIC_SPAN,
+        },
+        source: Some(GraphTypeSource::Detailed {
+            specification,
+            span: SYNTHETIC_SPAN,
+        }),
+        span: SYNTHETIC_SPAN,
+    })
+}
+
+/// Renders the statement built by [`export_graph_type`] as GQL text.
+pub fn graph_type_ddl(name: &str, snapshot: &dyn SchemaSnapshot) -> EmitResult {
+    gql::create_graph_type(&export_graph_type(name, snapshot)?)
+}
+
+/// Builds the `{ ... }` body describing every type in `snapshot`.
+pub fn graph_type_specification(
+    snapshot: &dyn SchemaSnapshot,
+) -> Result<NestedGraphTypeSpecification, Vec<Diag>> {
+    let mut diagnostics = Vec::new();
+    let mut types = Vec::new();
+
+    let mut node_names = snapshot.node_type_names();
+    node_names.sort();
+    for name in node_names {
+        let Some(node) = snapshot.node_type(&name) else {
+            continue;
+        };
+        let owner = TypeRef::NodeType(name.clone());
+        let constraints = constraints(
+            &owner,
+            &node.constraints,
+            &node.properties,
+            &mut diagnostics,
+        );
+        types.push(ElementTypeSpecification::Node(Box::new(
+            NodeTypeSpecification {
+                is_abstract: false,
+                name: Some(name.clone()),
+                inheritance: inheritance(&node.parents),
+                pattern: pattern(NodeTypeFiller {
+                    label_set: Some(NodeTypeLabelSet {
+                        label_set_phrase: label(&name),
+                        span: SYNTHETIC_SPAN,
+                    }),
+                    property_types: property_types(&node.properties).map(|specification| {
+                        NodeTypePropertyTypes {
+                            specification,
+                            span: SYNTHETIC_SPAN,
+                        }
+                    }),
+                    key_label_set: None,
+                    implied_content: None,
+                    constraints,
+                    span: SYNTHETIC_SPAN,
+                }),
+                span: SYNTHETIC_SPAN,
+            },
+        )));
+    }
+
+    let mut edge_names = snapshot.edge_type_names();
+    edge_names.sort();
+    for name in edge_names {
+        let Some(edge) = snapshot.edge_type(&name) else {
+            continue;
+        };
+        let owner = TypeRef::EdgeType(name.clone());
+        let constraints = constraints(
+            &owner,
+            &edge.constraints,
+            &edge.properties,
+            &mut diagnostics,
+        );
+        types.push(ElementTypeSpecification::Edge(Box::new(
+            EdgeTypeSpecification {
+                is_abstract: false,
+                name: Some(name.clone()),
+                inheritance: inheritance(&edge.parents),
+                pattern: edge_pattern(
+                    &name,
+                    edge.endpoints.as_ref(),
+                    &edge.properties,
+                    constraints,
+                ),
+                span: SYNTHETIC_SPAN,
+            },
+        )));
+    }
+
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    Ok(NestedGraphTypeSpecification {
+        body: GraphTypeSpecificationBody {
+            element_types: ElementTypeList {
+                types,
+                span: SYNTHETIC_SPAN,
+            },
+            span: SYNTHETIC_SPAN,
+        },
+        span: SYNTHETIC_SPAN,
+    })
+}
+
+/// Builds `CONNECTING (Source TO Destination)`; unknown endpoints become
+/// the empty pattern `()`, which accepts any node type.
+fn edge_pattern(
+    name: &SmolStr,
+    endpoints: Option<&EdgeEndpoints>,
+    properties: &BTreeMap<SmolStr, PropertyMeta>,
+    constraints: Vec<GraphTypeConstraint>,
+) -> EdgeTypePattern {
+    let endpoint = |name: Option<&SmolStr>| NodeTypePattern {
+        phrase: NodeTypePhrase {
+            filler: None,
+            alias: name.map(|name| LocalNodeTypeAlias {
+                name: name.clone(),
+                span: SYNTHETIC_SPAN,
+            }),
+            span: SYNTHETIC_SPAN,
+        },
+        span: SYNTHETIC_SPAN,
+    };
+    let source = endpoint(endpoints.map(|endpoints| &endpoints.source));
+    let destination = endpoint(endpoints.map(|endpoints| &endpoints.destination));
+    let directed = endpoints.is_none_or(|endpoints| endpoints.directed);
+
+    let filler = EdgeTypeFiller {
+        phrase: EdgeTypePhrase {
+            edge_kind: if directed {
+                EdgeKind::Directed
+            } else {
+                EdgeKind::Undirected
+            },
+            filler_content: Some(EdgeTypePhraseContent {
+                label_set: Some(EdgeTypeLabelSet {
+                    label_set_phrase: label(name),
+                    span: SYNTHETIC_SPAN,
+                }),
+                property_types: property_types(properties).map(|specification| {
+                    EdgeTypePropertyTypes {
+                        specification,
+                        span: SYNTHETIC_SPAN,
+                    }
+                }),
+                span: SYNTHETIC_SPAN,
+            }),
+            endpoint_pair_phrase: EndpointPairPhrase {
+                endpoint_pair: EndpointPair {
+                    source: NodeTypeReference {
+                        node_type: source.clone(),
+                        span: SYNTHETIC_SPAN,
+                    },
+                    destination: NodeTypeReference {
+                        node_type: destination.clone(),
+                        span: SYNTHETIC_SPAN,
+                    },
+                    span: SYNTHETIC_SPAN,
+                },
+                span: SYNTHETIC_SPAN,
+            },
+            span: SYNTHETIC_SPAN,
+        },
+        constraints,
+        span: SYNTHETIC_SPAN,
+    };
+    if directed {
+        EdgeTypePattern::Directed(EdgeTypePatternDirected {
+            left_endpoint: source,
+            arc: DirectedArcType::PointingRight(ArcTypePointingRight {
+                filler: Some(filler),
+                span: SYNTHETIC_SPAN,
+            }),
+            right_endpoint: destination,
+            span: SYNTHETIC_SPAN,
+        })
+    } else {
+        EdgeTypePattern::Undirected(EdgeTypePatternUndirected {
+            left_endpoint: source,
+            arc: ArcTypeUndirected {
+                filler: Some(filler),
+                span: SYNTHETIC_SPAN,
+            },
+            right_endpoint: destination,
+            span: SYNTHETIC_SPAN,
+        })
+    }
+}
+
+fn pattern(filler: NodeTypeFiller) -> NodeTypePattern {
+    NodeTypePattern {
+        phrase: NodeTypePhrase {
+            filler: Some(filler),
+            alias: None,
+            span: SYNTHETIC_SPAN,
+        },
+        span: SYNTHETIC_SPAN,
+    }
+}
+
+fn label(name: &SmolStr) -> LabelSetPhrase {
+    LabelSetPhrase::Label(LabelName {
+        name: name.clone(),
+        span: SYNTHETIC_SPAN,
+    })
+}
+
+fn inheritance(parents: &[TypeRef]) -> Option<TypeInheritanceClause> {
+    if parents.is_empty() {
+        return None;
+    }
+    Some(TypeInheritanceClause {
+        parents: parents
+            .iter()
+            .map(|parent| InheritedTypeReference {
+                name: match parent {
+                    TypeRef::NodeType(name) | TypeRef::EdgeType(name) => name.clone(),
+                },
+                span: SYNTHETIC_SPAN,
+            })
+            .collect(),
+        span: SYNTHETIC_SPAN,
+    })
+}
+
+fn property_types(
+    properties: &BTreeMap<SmolStr, PropertyMeta>,
+) -> Option<PropertyTypesSpecification> {
+    let types: Vec<_> = properties
+        .values()
+        .map(|property| PropertyType {
+            name: property_name(&property.name),
+            value_type: PropertyValueType {
+                value_type: property.value_type.clone(),
+                span: SYNTHETIC_SPAN,
+            },
+            not_null: property.required,
+            default: property.constraints.iter().find_map(|constraint| match constraint {
+                PropertyConstraint::Default { value } => Some(Box::new(value.clone())),
+                _ => None,
+            }),
+            check: property.constraints.iter().find_map(|constraint| match constraint {
+                PropertyConstraint::Check { expression } => Some(Box::new(expression.clone())),
+                _ => None,
+            }),
+            span: SYNTHETIC_SPAN,
+        })
+        .collect();
+    (!types.is_empty()).then_some(PropertyTypesSpecification {
+        property_types: Some(PropertyTypeList {
+            types,
+            span: SYNTHETIC_SPAN,
+        }),
+        span: SYNTHETIC_SPAN,
+    })
+}
+
+fn property_name(name: &SmolStr) -> PropertyName {
+    PropertyName {
+        name: name.clone(),
+        span: SYNTHETIC_SPAN,
+    }
+}
+
+/// Converts the type's constraints, followed by the constraints attached to
+/// individual properties.
+fn constraints(
+    owner: &TypeRef,
+    type_constraints: &[ConstraintMeta],
+    properties: &BTreeMap<SmolStr, PropertyMeta>,
+    diagnostics: &mut Vec<Diag>,
+) -> Vec<GraphTypeConstraint> {
+    let names = |properties: &[SmolStr]| properties.iter().map(property_name).collect();
+    let mut converted = Vec::new();
+    for constraint in type_constraints {
+        converted.push(match constraint {
+            ConstraintMeta::PrimaryKey { properties } => GraphTypeConstraint::Key {
+                properties: names(properties),
+                span: SYNTHETIC_SPAN,
+            },
+            ConstraintMeta::Unique { properties } => GraphTypeConstraint::Unique {
+                properties: names(properties),
+                span: SYNTHETIC_SPAN,
+            },
+            ConstraintMeta::Mandatory { properties } => GraphTypeConstraint::Mandatory {
+                properties: names(properties),
+                span: SYNTHETIC_SPAN,
+            },
+            ConstraintMeta::Check { expression } => GraphTypeConstraint::Check {
+                condition: Box::new(expression.clone()),
+                span: SYNTHETIC_SPAN,
+            },
+            ConstraintMeta::ForeignKey { .. } => {
+                diagnostics.push(Diag::error(format!(
+                    "foreign key on {} cannot be expressed in a graph type",
+                    describe(owner)
+                )));
+                continue;
+            }
+        });
+    }
+    for property in properties.values() {
+        // The first DEFAULT and CHECK are written on the property itself
+        let (mut has_default, mut has_check) = (false, false);
+        for constraint in &property.constraints {
+            converted.push(match constraint {
+                PropertyConstraint::Unique => GraphTypeConstraint::Unique {
+                    properties: vec![property_name(&property.name)],
+                    span: SYNTHETIC_SPAN,
+                },
+                PropertyConstraint::Check { .. } if !has_check => {
+                    has_check = true;
+                    continue;
+                }
+                PropertyConstraint::Check { expression } => GraphTypeConstraint::Check {
+                    condition: Box::new(expression.clone()),
+                    span: SYNTHETIC_SPAN,
+                },
+                PropertyConstraint::Default { .. } if !has_default => {
+                    has_default = true;
+                    continue;
+                }
+                PropertyConstraint::Default { .. } => {
+                    diagnostics.push(Diag::error(format!(
+                        "property '{}' of {} has more than one default value",
+                        property.name,
+                        describe(owner)
+                    )));
+                    continue;
+                }
+            });
+        }
+    }
+    converted
+}
+
+fn describe(owner: &TypeRef) -> String {
+    match owner {
+        TypeRef::NodeType(name) => format!("node type '{name}'"),
+        TypeRef::EdgeType(name) => format!("edge type '{name}'"),
+    }
+}
@@ -0,0 +1,373 @@
+//! Graph statistics for cardinality estimation.
+//!
+//! This module provides the `StatisticsProvider` trait, through which a
+//! database exposes the data statistics that
+//! [`CardinalityEstimator`](crate::analysis::cardinality::CardinalityEstimator)
+//! turns into row-count estimates. It is the planning counterpart of
+//! [`MetadataProvider`](crate::semantic::MetadataProvider): metadata says what
+//! may exist, statistics say how much of it does.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use crate::analysis::const_eval::{self, Value};
+use crate::semantic::schema_catalog::TypeRef;
+
+/// Provides data statistics for cardinality estimation.
+///
+/// Counts may be approximate. Methods returning `Option` report `None` when
+/// the statistic is not maintained, in which case the estimator falls back
+/// to default selectivities.
+pub trait StatisticsProvider: Send + Sync {
+    /// Returns the total number of nodes.
+    fn node_count(&self) -> u64;
+
+    /// Returns the total number of edges.
+    fn edge_count(&self) -> u64;
+
+    /// Returns the number of nodes carrying `label`.
+    fn label_count(&self, label: &str) -> Option<u64>;
+
+    /// Returns the number of edges of `edge_type` whose source carries the
+    /// label `source` and whose destination carries the label
+    /// `destination`. `None` for any of the three matches everything.
+    ///
+    /// Undirected edges are counted once, with their endpoints in either
+    /// order.
+    fn edge_type_count(
+        &self,
+        edge_type: Option<&str>,
+        source: Option<&str>,
+        destination: Option<&str>,
+    ) -> Option<u64>;
+
+    /// Returns statistics on the values of `property` for elements of
+    /// `owner`.
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns None. Override to enable value-based selectivities.
+    fn property_statistics(&self, _owner: &TypeRef, _property: &str) -> Option<PropertyStatistics> {
+        None
+    }
+}
+
+/// Value distribution of a property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyStatistics {
+    /// Number of distinct non-NULL values.
+    pub distinct_count: u64,
+    /// Fraction of elements for which the property is NULL or missing.
+    pub null_fraction: f64,
+    /// Most common values with the fraction of elements holding each.
+    pub most_common_values: Vec<(Value, f64)>,
+    /// Distribution of the non-NULL values.
+    pub histogram: Option<Histogram>,
+}
+
+impl PropertyStatistics {
+    /// Creates statistics for a property with `distinct_count` distinct
+    /// values and no NULLs.
+    pub fn new(distinct_count: u64) -> Self {
+        Self {
+            distinct_count,
+            null_fraction: 0.0,
+            most_common_values: Vec::new(),
+            histogram: None,
+        }
+    }
+
+    /// Sets the fraction of elements for which the property is NULL.
+    pub fn with_null_fraction(mut self, null_fraction: f64) -> Self {
+        self.null_fraction = null_fraction;
+        self
+    }
+
+    /// Records a most common value and its frequency.
+    pub fn with_most_common_value(mut self, value: Value, frequency: f64) -> Self {
+        self.most_common_values.push((value, frequency));
+        self
+    }
+
+    /// Sets the histogram of non-NULL values.
+    pub fn with_histogram(mut self, histogram: Histogram) -> Self {
+        self.histogram = Some(histogram);
+        self
+    }
+
+    /// Returns the fraction of elements whose property equals `value`, or
+    /// equals an unknown value if `value` is `None`.
+    ///
+    /// Values outside the most common list share what the list leaves over
+    /// evenly.
+    pub fn equality_selectivity(&self, value: Option<&Value>) -> f64 {
+        if let Some(value) = value {
+            if value.is_null() {
+                return 0.0;
+            }
+            if let Some((_, frequency)) = self
+                .most_common_values
+                .iter()
+                .find(|(common, _)| same_value(common, value))
+            {
+                return *frequency;
+            }
+            let common: f64 = self.most_common_values.iter().map(|(_, f)| f).sum();
+            let others = self
+                .distinct_count
+                .saturating_sub(self.most_common_values.len() as u64);
+            if others == 0 {
+                return 0.0;
+            }
+            return ((1.0 - self.null_fraction - common) / others as f64).clamp(0.0, 1.0);
+        }
+        if self.distinct_count == 0 {
+            return 0.0;
+        }
+        ((1.0 - self.null_fraction) / self.distinct_count as f64).clamp(0.0, 1.0)
+    }
+}
+
+fn same_value(left: &Value, right: &Value) -> bool {
+    left == right || const_eval::ordering(left, right) == Some(Ordering::Equal)
+}
+
+/// Equi-depth histogram: consecutive bounds delimit buckets holding equal
+/// shares of the non-NULL values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Ascending bucket bounds; `n + 1` bounds describe `n` buckets.
+    pub bounds: Vec<Value>,
+}
+
+impl Histogram {
+    /// Creates a histogram from ascending bucket bounds.
+    pub fn new(bounds: Vec<Value>) -> Self {
+        Self { bounds }
+    }
+
+    /// Returns the fraction of non-NULL values below `value`, or `None` if
+    /// `value` is not comparable with the bounds.
+    ///
+    /// Within a bucket, numeric values are interpolated linearly and other
+    /// values are placed at its middle.
+    pub fn fraction_below(&self, value: &Value) -> Option<f64> {
+        let buckets = self.bounds.len().checked_sub(1).filter(|n| *n > 0)?;
+        let first = self.bounds.first()?;
+        let last = self.bounds.last()?;
+        if const_eval::ordering(value, first)?.is_le() {
+            return Some(0.0);
+        }
+        if const_eval::ordering(value, last)?.is_ge() {
+            return Some(1.0);
+        }
+        let bucket = self
+            .bounds
+            .windows(2)
+            .position(|pair| const_eval::ordering(value, &pair[1]).is_some_and(Ordering::is_lt))?;
+        let (low, high) = (&self.bounds[bucket], &self.bounds[bucket + 1]);
+        let within = match (numeric(low), numeric(high), numeric(value)) {
+            (Some(low), Some(high), Some(value)) if high > low => (value - low) / (high - low),
+            _ => 0.5,
+        };
+        Some((bucket as f64 + within) / buckets as f64)
+    }
+}
+
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Mock Test Double Implementation
+// ============================================================================
+
+/// Mock statistics provider for testing and examples.
+///
+/// **This is a test double** - not for production use.
+#[derive(Debug, Clone, Default)]
+pub struct MockStatisticsProvider {
+    node_count: u64,
+    edge_count: u64,
+    labels: HashMap<SmolStr, u64>,
+    /// Edge counts by edge type, source label and destination label.
+    edge_types: Vec<(SmolStr, SmolStr, SmolStr, u64)>,
+    properties: HashMap<(TypeRef, SmolStr), PropertyStatistics>,
+}
+
+impl MockStatisticsProvider {
+    /// Creates a provider for an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the total node and edge counts.
+    pub fn set_counts(&mut self, node_count: u64, edge_count: u64) {
+        self.node_count = node_count;
+        self.edge_count = edge_count;
+    }
+
+    /// Adds the number of nodes carrying a label.
+    pub fn add_label_count(&mut self, label: impl Into<SmolStr>, count: u64) {
+        self.labels.insert(label.into(), count);
+    }
+
+    /// Adds the number of edges of a type between two endpoint labels.
+    pub fn add_edge_type_count(
+        &mut self,
+        edge_type: impl Into<SmolStr>,
+        source: impl Into<SmolStr>,
+        destination: impl Into<SmolStr>,
+        count: u64,
+    ) {
+        self.edge_types
+            .push((edge_type.into(), source.into(), destination.into(), count));
+    }
+
+    /// Adds statistics for a property.
+    pub fn add_property_statistics(
+        &mut self,
+        owner: TypeRef,
+        property: impl Into<SmolStr>,
+        statistics: PropertyStatistics,
+    ) {
+        self.properties.insert((owner, property.into()), statistics);
+    }
+
+    /// Creates a provider describing a social graph: 1000 `Person` nodes
+    /// knowing five others each and living in one of 50 `City` nodes.
+    pub fn example() -> Self {
+        let mut provider = Self::new();
+        provider.set_counts(1050, 6000);
+        provider.add_label_count("Person", 1000);
+        provider.add_label_count("City", 50);
+        provider.add_edge_type_count("KNOWS", "Person", "Person", 5000);
+        provider.add_edge_type_count("LIVES_IN", "Person", "City", 1000);
+
+        let person = TypeRef::NodeType("Person".into());
+        provider.add_property_statistics(person.clone(), "name", PropertyStatistics::new(1000));
+        provider.add_property_statistics(
+            person,
+            "age",
+            PropertyStatistics::new(80)
+                .with_null_fraction(0.1)
+                .with_histogram(Histogram::new(
+                    [0, 20, 30, 40, 60, 100].map(Value::Integer).to_vec(),
+                )),
+        );
+        provider.add_property_statistics(
+            TypeRef::NodeType("City".into()),
+            "name",
+            PropertyStatistics::new(50).with_most_common_value(Value::String("Paris".into()), 0.2),
+        );
+        provider
+    }
+}
+
+impl StatisticsProvider for MockStatisticsProvider {
+    fn node_count(&self) -> u64 {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> u64 {
+        self.edge_count
+    }
+
+    fn label_count(&self, label: &str) -> Option<u64> {
+        self.labels.get(label).copied()
+    }
+
+    fn edge_type_count(
+        &self,
+        edge_type: Option<&str>,
+        source: Option<&str>,
+        destination: Option<&str>,
+    ) -> Option<u64> {
+        if edge_type.is_none() && source.is_none() && destination.is_none() {
+            return Some(self.edge_count);
+        }
+        let matches = |filter: Option<&str>, name: &SmolStr| filter.is_none_or(|f| f == name);
+        let mut known = false;
+        let mut count = 0;
+        for (ty, from, to, edges) in &self.edge_types {
+            if !matches(edge_type, ty) {
+                continue;
+            }
+            known = true;
+            if matches(source, from) && matches(destination, to) {
+                count += edges;
+            }
+        }
+        known.then_some(count)
+    }
+
+    fn property_statistics(&self, owner: &TypeRef, property: &str) -> Option<PropertyStatistics> {
+        self.properties
+            .get(&(owner.clone(), property.into()))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_edge_type_counts() {
+        let provider = MockStatisticsProvider::example();
+        assert_eq!(provider.edge_type_count(None, None, None), Some(6000));
+        assert_eq!(
+            provider.edge_type_count(Some("KNOWS"), None, None),
+            Some(5000)
+        );
+        assert_eq!(
+            provider.edge_type_count(Some("LIVES_IN"), Some("City"), None),
+            Some(0)
+        );
+        assert_eq!(
+            provider.edge_type_count(None, Some("Person"), Some("City")),
+            Some(1000)
+        );
+        assert_eq!(provider.edge_type_count(Some("WORKS_AT"), None, None), None);
+    }
+
+    #[test]
+    fn test_equality_selectivity() {
+        let statistics = PropertyStatistics::new(11)
+            .with_null_fraction(0.2)
+            .with_most_common_value(Value::Integer(1), 0.3);
+        assert_eq!(
+            statistics.equality_selectivity(Some(&Value::Integer(1))),
+            0.3
+        );
+        assert_eq!(
+            statistics.equality_selectivity(Some(&Value::Float(1.0))),
+            0.3
+        );
+        assert!((statistics.equality_selectivity(Some(&Value::Integer(2))) - 0.05).abs() < 1e-9);
+        assert!((statistics.equality_selectivity(None) - 0.8 / 11.0).abs() < 1e-9);
+        assert_eq!(statistics.equality_selectivity(Some(&Value::Null)), 0.0);
+    }
+
+    #[test]
+    fn test_histogram_fraction_below() {
+        let histogram = Histogram::new([0, 10, 20, 40].map(Value::Integer).to_vec());
+        assert_eq!(histogram.fraction_below(&Value::Integer(-5)), Some(0.0));
+        assert_eq!(histogram.fraction_below(&Value::Integer(15)), Some(0.5));
+        assert_eq!(
+            histogram.fraction_below(&Value::Integer(30)),
+            Some(2.5 / 3.0)
+        );
+        assert_eq!(histogram.fraction_below(&Value::Integer(40)), Some(1.0));
+        assert_eq!(histogram.fraction_below(&Value::String("x".into())), None);
+
+        let names = Histogram::new(["a", "m", "z"].map(|s| Value::String(s.into())).to_vec());
+        assert_eq!(names.fraction_below(&Value::String("c".into())), Some(0.25));
+    }
+}
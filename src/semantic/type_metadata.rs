@@ -280,6 +280,12 @@ pub trait TypeCheckContextProvider: Send + Sync {
 // ============================================================================
 
 /// Default cast rule set with standard SQL/GQL casting rules.
+///
+/// CAST is an explicit conversion and has its own table, wider than the
+/// implicit coercions of [`Type::can_coerce_to`]: any numeric converts to any
+/// other numeric (values out of the target's range fail when converted),
+/// numerics, booleans and temporals convert to and from strings, and booleans
+/// convert to and from integers.
 #[derive(Debug, Clone)]
 pub struct DefaultCastRuleSet;
 
@@ -305,25 +311,34 @@ impl CastRuleSet for DefaultCastRuleSet {
             // Any can cast to anything
             (Type::Any, _) | (_, Type::Any) => true,
 
-            // Null can cast to anything nullable
-            (Type::Null, _) => true,
-
-            // Numeric casts
-            (Type::Int, Type::Float) => true,
-
-            // String to numeric (parse)
-            (Type::String, Type::Int) => true,
-            (Type::String, Type::Float) => true,
-            (Type::String, Type::Boolean) => true,
+            // Null and the empty type can cast to anything
+            (Type::Null | Type::Nothing, _) => true,
 
-            // Anything to string (toString)
-            (_, Type::String) => true,
+            // NOT NULL only constrains the value, not the conversion
+            (Type::NotNull(inner), to) => self.can_cast(inner, to),
+            (from, Type::NotNull(inner)) => self.can_cast(from, inner),
 
             // List element type compatibility
             (Type::List(from_elem), Type::List(to_elem)) => self.can_cast(from_elem, to_elem),
 
-            // Default: no cast
-            _ => false,
+            // Numeric to numeric; narrowing conversions round or truncate and
+            // are range-checked against the target when the value is converted
+            (from, to) if from.is_numeric() && to.is_numeric() => true,
+
+            // Boolean to and from integers (TRUE is 1, FALSE is 0)
+            (Type::Boolean, to) if is_integer(to) => true,
+            (from, Type::Boolean) if is_integer(from) => true,
+
+            // String to numeric, boolean or temporal (parse)
+            (Type::String, to) if to.is_numeric() || to.is_boolean() || to.is_temporal() => true,
+
+            // Numeric, boolean and temporal to string (format)
+            (from, Type::String) if from.is_numeric() || from.is_boolean() || from.is_temporal() => {
+                true
+            }
+
+            // Remaining implicit coercions (closed to open references, durations, ...)
+            (from, to) => from.can_coerce_to(to),
         }
     }
 
@@ -336,6 +351,14 @@ impl CastRuleSet for DefaultCastRuleSet {
     }
 }
 
+/// Returns true for exact numeric types without a fractional part.
+fn is_integer(ty: &Type) -> bool {
+    matches!(
+        ty.without_not_null(),
+        Type::Int | Type::SignedInt(_) | Type::UnsignedInt(_)
+    )
+}
+
 // ============================================================================
 // Mock Implementations
 // ============================================================================
@@ -517,20 +540,55 @@ mod tests {
         // Float -> Double
         assert!(rules.can_cast(&Type::Float, &Type::Float));
 
-        // Not the other way
-        assert!(!rules.can_cast(&Type::Float, &Type::Int));
-        assert!(!rules.can_cast(&Type::Float, &Type::Int));
+        // Approximate to exact truncates
+        assert!(rules.can_cast(&Type::Float, &Type::Int));
+
+        // Booleans convert to and from integers but not floats
+        assert!(rules.can_cast(&Type::Int, &Type::Boolean));
+        assert!(rules.can_cast(&Type::Boolean, &Type::Int));
+        assert!(!rules.can_cast(&Type::Float, &Type::Boolean));
+    }
+
+    #[test]
+    fn test_default_cast_rules_sized_numeric() {
+        use crate::ir::type_table::NumericWidth;
+
+        let rules = DefaultCastRuleSet::new();
+        let decimal = Type::Decimal {
+            precision: Some(10),
+            scale: Some(2),
+        };
+
+        // Widening within exact numerics
+        assert!(rules.can_cast(&Type::SignedInt(NumericWidth::W16), &Type::Int));
+        assert!(rules.can_cast(&Type::UnsignedInt(NumericWidth::W32), &Type::Int));
+        assert!(rules.can_cast(&Type::SignedInt(NumericWidth::W16), &decimal));
+
+        // Narrowing is an explicit cast, range-checked on the value
+        assert!(rules.can_cast(&Type::Int, &Type::SignedInt(NumericWidth::W8)));
+        assert!(rules.can_cast(&Type::Int, &decimal));
+        assert!(rules.can_cast(&Type::Int, &Type::UnsignedInt(NumericWidth::W64)));
+        assert!(rules.can_cast(&decimal, &Type::Int));
+        assert!(rules.can_cast(&Type::Float, &decimal));
+
+        // NOT NULL wrappers are transparent
+        assert!(rules.can_cast(&Type::not_null(Type::Int), &Type::Float));
     }
 
     #[test]
     fn test_default_cast_rules_to_string() {
         let rules = DefaultCastRuleSet::new();
 
-        // Everything can cast to string
+        // Scalars cast to string
         assert!(rules.can_cast(&Type::Int, &Type::String));
         assert!(rules.can_cast(&Type::Float, &Type::String));
         assert!(rules.can_cast(&Type::Boolean, &Type::String));
         assert!(rules.can_cast(&Type::Date, &Type::String));
+
+        // Strings parse back into scalars, but graph elements do not format
+        assert!(rules.can_cast(&Type::String, &Type::ZonedDatetime));
+        assert!(!rules.can_cast(&Type::Node(None), &Type::String));
+        assert!(!rules.can_cast(&Type::Date, &Type::Int));
     }
 
     #[test]
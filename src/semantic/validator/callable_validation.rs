@@ -1,19 +1,27 @@
 //! Callable validation pass (Milestone 4).
 //!
 //! This module validates function and procedure calls against their signatures,
-//! including arity checking and parameter validation.
+//! including arity checking and overload resolution on the argument types
+//! recorded by type inference.
 
+use crate::ast::Span;
 use crate::ast::expression::{
     AggregateFunction, Expression, FunctionCall, FunctionName, GeneralSetFunctionType,
 };
 use crate::ast::procedure::{NamedProcedureCall, ProcedureCall};
-use crate::ast::program::Program;
+use crate::ast::references::ProcedureReference;
+use crate::ast::program::Statement;
 use crate::ast::query::PrimitiveQueryStatement;
-use crate::ast::visit::{
-    Visit, VisitResult, walk_expression, walk_linear_query, walk_primitive_query_statement,
-    walk_program,
-};
+use crate::ast::visit::{Visit, VisitResult, walk_expression, walk_primitive_query_statement};
 use crate::diag::Diag;
+use crate::ir::TypeTable;
+use crate::ir::type_table::Type;
+use crate::semantic::callable::{
+    CallSite, CallableKind, CallableSignature, CallableValidator, DefaultCallableValidator,
+    ProcedureColumn, resolve_builtin_signatures, resolve_overload,
+};
+use crate::semantic::diag::{duplicate_yield_binding, unknown_yield_column};
+use smol_str::SmolStr;
 
 use super::SemanticValidator;
 
@@ -22,60 +30,208 @@ use super::SemanticValidator;
 /// This pass validates:
 /// - Function arity (argument count)
 /// - Aggregate function arity
-/// - Parameter types (if metadata provider provides signatures)
+/// - Argument types against the built-in or catalog signatures
 pub(super) fn run_callable_validation(
     validator: &SemanticValidator,
-    program: &Program,
+    statements: &[Statement],
+    type_table: &TypeTable,
     diagnostics: &mut Vec<Diag>,
 ) {
-    eprintln!("DEBUG: run_callable_validation called");
-    eprintln!(
-        "DEBUG: metadata_provider is_some: {}",
-        validator.metadata_provider.is_some()
-    );
-
     let mut visitor = CallableValidationVisitor {
         validator,
+        type_table,
         diagnostics,
     };
 
-    let _ = walk_program(&mut visitor, program);
-    eprintln!("DEBUG: walk_program completed");
+    for statement in statements {
+        let _ = visitor.visit_statement(statement);
+    }
+}
+
+/// Returns the name of the procedure a named call refers to.
+pub(super) fn procedure_name(call: &NamedProcedureCall) -> &str {
+    match &call.procedure {
+        ProcedureReference::CatalogQualified { name, .. } => &name.name,
+        ProcedureReference::ReferenceParameter { name, .. } => name,
+    }
+}
+
+/// Resolves the signature of the procedure a named call refers to.
+///
+/// Built-in procedures and functions are checked first, then the metadata
+/// provider. Overloads are resolved on `arg_types`, with unknown argument
+/// types passed as [`Type::Any`]; when no overload accepts the arguments the
+/// first signature is returned and the mismatch is left to callable
+/// validation.
+pub(super) fn resolve_procedure(
+    validator: &SemanticValidator,
+    call: &NamedProcedureCall,
+    arg_types: &[Type],
+) -> Option<CallableSignature> {
+    let name = procedure_name(call);
+    let sigs = resolve_builtin_signatures(name, CallableKind::Procedure)
+        .or_else(|| resolve_builtin_signatures(name, CallableKind::Function))
+        .or_else(|| {
+            validator
+                .metadata_provider
+                .and_then(|m| m.lookup_callable(name))
+                .map(|sig| vec![sig])
+        })?;
+    let signature = resolve_overload(&sigs, arg_types).map_or(sigs.first(), |r| Some(r.signature));
+    signature.cloned()
+}
+
+/// A variable bound by a procedure call.
+pub(super) struct YieldBinding {
+    /// Variable name: the alias, or the column name.
+    pub(super) name: SmolStr,
+    /// Type of the column, or [`Type::Any`] if it is unknown.
+    pub(super) ty: Type,
+    /// Span of the binding in the YIELD clause, or of the whole call when
+    /// there is no YIELD clause.
+    pub(super) span: Span,
+}
+
+/// Returns the variables a named procedure call binds.
+///
+/// With a YIELD clause, each item binds its alias or column name; without one,
+/// every result column of `signature` is bound. Under OPTIONAL CALL the
+/// columns may be null regardless of their declared nullability.
+pub(super) fn yield_bindings(
+    signature: Option<&CallableSignature>,
+    call: &NamedProcedureCall,
+    optional: bool,
+) -> Vec<YieldBinding> {
+    let column_type = |column: &ProcedureColumn| {
+        let ty = column.value_type();
+        if optional {
+            ty.without_not_null().clone()
+        } else {
+            ty
+        }
+    };
+
+    let Some(yield_clause) = &call.yield_clause else {
+        return signature
+            .map(|sig| {
+                sig.columns
+                    .iter()
+                    .map(|column| YieldBinding {
+                        name: column.name.clone(),
+                        ty: column_type(column),
+                        span: call.span.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    };
+
+    yield_clause
+        .items
+        .items
+        .iter()
+        .filter_map(|item| {
+            let Expression::VariableReference(column, column_span) = &item.expression else {
+                return None;
+            };
+            let ty = signature
+                .and_then(|sig| sig.column(column))
+                .map_or(Type::Any, column_type);
+            let (name, span) = match &item.alias {
+                Some(alias) => (alias.name.clone(), alias.span.clone()),
+                None => (column.clone(), column_span.clone()),
+            };
+            Some(YieldBinding { name, ty, span })
+        })
+        .collect()
 }
 
 /// Visitor for callable validation.
 struct CallableValidationVisitor<'v, 'm> {
     validator: &'v SemanticValidator<'m>,
+    type_table: &'v TypeTable,
     diagnostics: &'v mut Vec<Diag>,
 }
 
 impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
-    /// Validates a procedure call against the metadata provider.
-    fn validate_procedure_call(&mut self, call: &NamedProcedureCall) {
-        eprintln!("DEBUG: validate_procedure_call called");
-
-        // Get procedure name - extract from ProcedureReference
-        use crate::ast::references::ProcedureReference;
-        let name = match &call.procedure {
-            ProcedureReference::CatalogQualified { name, .. } => &name.name,
-            ProcedureReference::ReferenceParameter { name, .. } => name,
-        };
-
-        eprintln!("DEBUG: Looking up callable: {}", name);
-
-        // Check built-ins first (direct function call - zero cost)
-        use crate::semantic::callable::{CallableKind, lookup_builtin_callable};
-        let signature = lookup_builtin_callable(name, CallableKind::Procedure)
-            .or_else(|| lookup_builtin_callable(name, CallableKind::Function))
-            // Then check metadata provider for UDFs (if configured)
+    /// Looks up the signatures of a callable: built-ins of the given kinds
+    /// first, then the metadata provider.
+    fn signatures(&self, name: &str, kinds: &[CallableKind]) -> Option<Vec<CallableSignature>> {
+        kinds
+            .iter()
+            .find_map(|kind| resolve_builtin_signatures(name, *kind))
             .or_else(|| {
                 self.validator
                     .metadata_provider
                     .and_then(|m| m.lookup_callable(name))
-            });
+                    .map(|sig| vec![sig])
+            })
+    }
+
+    /// Returns the inferred type of each argument, or [`Type::Any`] where
+    /// none was recorded.
+    fn argument_types(&self, args: &[&Expression]) -> Vec<Type> {
+        args.iter()
+            .map(|arg| {
+                self.type_table
+                    .get_type_by_span(&arg.span())
+                    .cloned()
+                    .unwrap_or(Type::Any)
+            })
+            .collect()
+    }
 
-        let Some(signature) = signature else {
-            eprintln!("DEBUG: Callable not found, reporting error");
+    /// Checks a call against its signatures, reporting arity and argument
+    /// type errors.
+    fn check_invocation(
+        &mut self,
+        name: &str,
+        kind: CallableKind,
+        signatures: &[CallableSignature],
+        args: &[&Expression],
+        span: &Span,
+        label: &str,
+    ) {
+        // A metadata provider may apply its own invocation rules
+        if let Some(metadata) = self.validator.metadata_provider {
+            let mut first_error = None;
+            for signature in signatures {
+                match metadata.validate_callable_invocation(signature, args) {
+                    Ok(()) => {
+                        first_error = None;
+                        break;
+                    }
+                    Err(e) => {
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+            if let Some(e) = first_error {
+                self.diagnostics.push(
+                    crate::diag::Diag::new(crate::diag::DiagSeverity::Error, e)
+                        .with_label(crate::diag::DiagLabel::primary(span.clone(), label)),
+                );
+                return;
+            }
+        }
+
+        let arg_types = self.argument_types(args);
+        let call = CallSite {
+            name,
+            kind,
+            arg_count: args.len(),
+            span: span.clone(),
+        };
+        self.diagnostics
+            .extend(DefaultCallableValidator.validate_typed_call(&call, &arg_types, signatures));
+    }
+
+    /// Validates a procedure call against the metadata provider.
+    fn validate_procedure_call(&mut self, call: &NamedProcedureCall) {
+        let name = procedure_name(call);
+        let signatures = self.signatures(name, &[CallableKind::Procedure, CallableKind::Function]);
+
+        let Some(signatures) = signatures else {
             // Only report error if metadata validation is enabled
             if self.validator.config.metadata_validation {
                 self.diagnostics.push(
@@ -92,89 +248,56 @@ impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
             return;
         };
 
-        eprintln!("DEBUG: Callable found: {:?}", signature.name);
-
-        // Validate arity if arguments provided
-        if let Some(arguments) = &call.arguments {
-            let args: Vec<&Expression> =
-                arguments.arguments.iter().map(|a| &a.expression).collect();
+        // Validate arguments if provided
+        let args: Vec<&Expression> = call
+            .arguments
+            .iter()
+            .flat_map(|arguments| arguments.arguments.iter().map(|a| &a.expression))
+            .collect();
+        if call.arguments.is_some() {
+            self.check_invocation(
+                name,
+                CallableKind::Procedure,
+                &signatures,
+                &args,
+                &call.span,
+                "invalid call",
+            );
+        }
 
-            // Use metadata provider for validation if available, otherwise do basic arity check
-            let validation_result = if let Some(metadata) = self.validator.metadata_provider {
-                metadata.validate_callable_invocation(&signature, &args)
-            } else {
-                // Basic arity check
-                let min_arity = signature.min_arity();
-                if args.len() < min_arity {
-                    Err(format!(
-                        "Function '{}' requires at least {} arguments, got {}",
-                        signature.name,
-                        min_arity,
-                        args.len()
-                    ))
-                } else if let Some(max_arity) = signature.max_arity() {
-                    if args.len() > max_arity {
-                        Err(format!(
-                            "Function '{}' accepts at most {} arguments, got {}",
-                            signature.name,
-                            max_arity,
-                            args.len()
-                        ))
-                    } else {
-                        Ok(())
-                    }
-                } else {
-                    Ok(())
+        // Validate YIELD items against the result columns of the selected overload
+        let arg_types = self.argument_types(&args);
+        let signature = resolve_overload(&signatures, &arg_types)
+            .map_or(&signatures[0], |resolved| resolved.signature);
+        if let Some(yield_clause) = &call.yield_clause
+            && signature.kind == CallableKind::Procedure
+        {
+            let columns: Vec<&str> = signature.columns.iter().map(|c| c.name.as_str()).collect();
+            for yield_item in &yield_clause.items.items {
+                if let Expression::VariableReference(column, span) = &yield_item.expression
+                    && signature.column(column).is_none()
+                {
+                    self.diagnostics.push(unknown_yield_column(
+                        name,
+                        column,
+                        &columns,
+                        span.clone(),
+                    ));
                 }
-            };
-
-            if let Err(e) = validation_result {
-                self.diagnostics.push(
-                    crate::diag::Diag::new(crate::diag::DiagSeverity::Error, e.to_string())
-                        .with_label(crate::diag::DiagLabel::primary(
-                            call.span.clone(),
-                            "invalid call",
-                        )),
-                );
             }
         }
 
-        // Validate YIELD clause if present
-        if let Some(yield_clause) = &call.yield_clause {
-            // YIELD validation: check that yielded fields match procedure output
-            // The return_type in CallableSignature represents the output field name(s)
-            if let Some(return_type) = &signature.return_type {
-                // Check each yielded field
-                for yield_item in &yield_clause.items.items {
-                    // Extract the field name from the expression
-                    // In typical cases, it should be a variable reference
-                    use crate::ast::expression::Expression;
-                    let field_name = match &yield_item.expression {
-                        Expression::VariableReference(name, _span) => name,
-                        _ => continue, // Skip validation for non-variable expressions
-                    };
-
-                    // Simple validation: check if field name matches return type
-                    // In a more sophisticated implementation, return_type could be
-                    // a comma-separated list or a structured type
-                    // Note: Use case-insensitive comparison because keywords used as identifiers
-                    // (like 'node', 'result', etc.) are normalized to uppercase by the parser
-                    if !field_name.eq_ignore_ascii_case(return_type.as_str()) {
-                        self.diagnostics.push(
-                            crate::diag::Diag::new(
-                                crate::diag::DiagSeverity::Error,
-                                format!(
-                                    "YIELD field '{}' does not exist in procedure output. Expected '{}'",
-                                    field_name, return_type
-                                ),
-                            )
-                            .with_label(crate::diag::DiagLabel::primary(
-                                yield_item.span.clone(),
-                                "invalid field",
-                            )),
-                        );
-                    }
-                }
+        // Each yielded variable must be bound once
+        let mut bound: Vec<YieldBinding> = Vec::new();
+        for binding in yield_bindings(Some(signature), call, false) {
+            if let Some(first) = bound.iter().find(|b| b.name == binding.name) {
+                self.diagnostics.push(duplicate_yield_binding(
+                    &binding.name,
+                    binding.span.clone(),
+                    first.span.clone(),
+                ));
+            } else {
+                bound.push(binding);
             }
         }
     }
@@ -184,18 +307,10 @@ impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
         // Build callable name
         let name = function_name_to_string(&call.name);
 
-        // Check built-ins first (direct function call - zero cost)
-        use crate::semantic::callable::{CallableKind, lookup_builtin_callable};
-        let signature = lookup_builtin_callable(name, CallableKind::Function)
-            .or_else(|| lookup_builtin_callable(name, CallableKind::AggregateFunction))
-            // Then check metadata provider for UDFs (if configured)
-            .or_else(|| {
-                self.validator
-                    .metadata_provider
-                    .and_then(|m| m.lookup_callable(name))
-            });
+        let signatures =
+            self.signatures(name, &[CallableKind::Function, CallableKind::AggregateFunction]);
 
-        let Some(signature) = signature else {
+        let Some(signatures) = signatures else {
             // Only report error if metadata validation is enabled
             if self.validator.config.metadata_validation {
                 self.diagnostics.push(
@@ -212,96 +327,22 @@ impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
             return;
         };
 
-        // Validate the call
         let args: Vec<&Expression> = call.arguments.iter().collect();
-
-        // Use metadata provider for validation if available, otherwise do basic arity check
-        let validation_result = if let Some(metadata) = self.validator.metadata_provider {
-            metadata.validate_callable_invocation(&signature, &args)
-        } else {
-            // Basic arity check
-            let min_arity = signature.min_arity();
-            if args.len() < min_arity {
-                Err(format!(
-                    "Function '{}' requires at least {} arguments, got {}",
-                    signature.name,
-                    min_arity,
-                    args.len()
-                ))
-            } else if let Some(max_arity) = signature.max_arity() {
-                if args.len() > max_arity {
-                    Err(format!(
-                        "Function '{}' accepts at most {} arguments, got {}",
-                        signature.name,
-                        max_arity,
-                        args.len()
-                    ))
-                } else {
-                    Ok(())
-                }
-            } else {
-                Ok(())
-            }
-        };
-
-        if let Err(e) = validation_result {
-            self.diagnostics.push(
-                crate::diag::Diag::new(crate::diag::DiagSeverity::Error, e.to_string())
-                    .with_label(crate::diag::DiagLabel::primary(
-                        call.span.clone(),
-                        "invalid call",
-                    )),
-            );
-        }
+        self.check_invocation(
+            name,
+            CallableKind::Function,
+            &signatures,
+            &args,
+            &call.span,
+            "invalid call",
+        );
     }
 
     /// Validates an aggregate function call against the metadata provider.
     fn validate_aggregate_function(&mut self, agg: &AggregateFunction) {
-        use crate::semantic::callable::{CallableKind, lookup_builtin_callable};
-
-        match agg {
-            AggregateFunction::CountStar { span } => {
-                // COUNT(*) - special case with 0 arguments
-                // Check built-ins first, then UDFs
-                let signature = lookup_builtin_callable("count", CallableKind::AggregateFunction)
-                    .or_else(|| {
-                        self.validator
-                            .metadata_provider
-                            .and_then(|m| m.lookup_callable("count"))
-                    });
-
-                if let Some(signature) = signature {
-                    let args: Vec<&Expression> = vec![];
-
-                    // Use metadata provider for validation if available, otherwise do basic arity check
-                    let validation_result = if let Some(metadata) = self.validator.metadata_provider
-                    {
-                        metadata.validate_callable_invocation(&signature, &args)
-                    } else {
-                        // Basic arity check
-                        if signature.matches_arity(0) {
-                            Ok(())
-                        } else {
-                            Err(format!(
-                                "Function '{}' does not accept {} arguments",
-                                signature.name, 0
-                            ))
-                        }
-                    };
-
-                    if let Err(e) = validation_result {
-                        self.diagnostics.push(
-                            crate::diag::Diag::new(
-                                crate::diag::DiagSeverity::Error,
-                                e.to_string(),
-                            )
-                            .with_label(
-                                crate::diag::DiagLabel::primary(span.clone(), "invalid aggregate"),
-                            ),
-                        );
-                    }
-                }
-            }
+        let (name, args, span): (&str, Vec<&Expression>, &Span) = match agg {
+            // COUNT(*) - special case with 0 arguments
+            AggregateFunction::CountStar { span } => ("count", vec![], span),
             AggregateFunction::GeneralSetFunction(general_func) => {
                 // Aggregate functions like COUNT, SUM, AVG, etc.
                 let name = match general_func.function_type {
@@ -314,55 +355,27 @@ impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
                     GeneralSetFunctionType::StddevSamp => "stddev_samp",
                     GeneralSetFunctionType::StddevPop => "stddev_pop",
                 };
-
-                // Check built-ins first, then UDFs
-                let signature = lookup_builtin_callable(name, CallableKind::AggregateFunction)
-                    .or_else(|| {
-                        self.validator
-                            .metadata_provider
-                            .and_then(|m| m.lookup_callable(name))
-                    });
-
-                if let Some(signature) = signature {
-                    let args: Vec<&Expression> = vec![general_func.expression.as_ref()];
-
-                    // Use metadata provider for validation if available, otherwise do basic arity check
-                    let validation_result = if let Some(metadata) = self.validator.metadata_provider
-                    {
-                        metadata.validate_callable_invocation(&signature, &args)
-                    } else {
-                        // Basic arity check
-                        if signature.matches_arity(1) {
-                            Ok(())
-                        } else {
-                            Err(format!(
-                                "Function '{}' does not accept {} arguments",
-                                signature.name, 1
-                            ))
-                        }
-                    };
-
-                    if let Err(e) = validation_result {
-                        self.diagnostics.push(
-                            crate::diag::Diag::new(
-                                crate::diag::DiagSeverity::Error,
-                                e.to_string(),
-                            )
-                            .with_label(
-                                crate::diag::DiagLabel::primary(
-                                    general_func.span.clone(),
-                                    "invalid aggregate",
-                                ),
-                            ),
-                        );
-                    }
-                }
-            }
-            AggregateFunction::BinarySetFunction(binary_func) => {
-                // Binary set functions like PERCENTILE_CONT, PERCENTILE_DISC
-                // For now, we skip validation for these as they require special handling
-                let _ = binary_func; // Suppress unused warning
+                (
+                    name,
+                    vec![general_func.expression.as_ref()],
+                    &general_func.span,
+                )
             }
+            // Binary set functions like PERCENTILE_CONT, PERCENTILE_DISC
+            // require special handling and are not validated yet
+            AggregateFunction::BinarySetFunction(_) => return,
+        };
+
+        // Check built-ins first, then UDFs
+        if let Some(signatures) = self.signatures(name, &[CallableKind::AggregateFunction]) {
+            self.check_invocation(
+                name,
+                CallableKind::AggregateFunction,
+                &signatures,
+                &args,
+                span,
+                "invalid aggregate",
+            );
         }
     }
 }
@@ -370,32 +383,14 @@ impl<'v, 'm> CallableValidationVisitor<'v, 'm> {
 impl<'v, 'm> Visit for CallableValidationVisitor<'v, 'm> {
     type Break = ();
 
-    fn visit_linear_query(&mut self, query: &crate::ast::query::LinearQuery) -> VisitResult<()> {
-        eprintln!("DEBUG: visit_linear_query called");
-        eprintln!(
-            "DEBUG: Query type: {}, primitive_statements count: {}",
-            if query.use_graph.is_some() {
-                "Focused"
-            } else {
-                "Ambient"
-            },
-            query.primitive_statements.len()
-        );
-        walk_linear_query(self, query)
-    }
-
     fn visit_primitive_query_statement(
         &mut self,
         statement: &PrimitiveQueryStatement,
     ) -> VisitResult<()> {
-        eprintln!("DEBUG: visit_primitive_query_statement called");
-
         // Check if this is a CALL statement
         if let PrimitiveQueryStatement::Call(call_stmt) = statement {
-            eprintln!("DEBUG: Found CALL statement");
             // Validate procedure call
             if let ProcedureCall::Named(named_call) = &call_stmt.call {
-                eprintln!("DEBUG: Named procedure call");
                 self.validate_procedure_call(named_call);
             }
         }
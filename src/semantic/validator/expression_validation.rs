@@ -6,11 +6,18 @@
 //! - CASE expression type consistency
 //! - Subquery result types
 //! - List operations
+//! - Constant LIMIT / OFFSET / SKIP counts
 
+use crate::analysis::const_eval::{ConstEvaluator, Value};
+use crate::ast::expression::Expression;
 use crate::ast::program::Program;
-use crate::ast::query::{LinearQuery, PrimitiveQueryStatement, Query};
+use crate::ast::query::{
+    LimitClause, LinearQuery, OffsetClause, PrimitiveQueryStatement, PrimitiveResultStatement,
+    Query,
+};
 use crate::diag::Diag;
 use crate::ir::TypeTable;
+use crate::semantic::diag::invalid_page_count;
 
 /// Main entry point for expression validation pass.
 ///
@@ -93,6 +100,67 @@ fn validate_linear_query_expressions(
             },
             _ => {}
         }
+        validate_page_counts(statement, diagnostics);
+    }
+
+    if let Some(PrimitiveResultStatement::Return(return_stmt)) =
+        linear_query.result_statement.as_deref()
+        && let Some(order_page) = &return_stmt.order_by_and_page
+    {
+        validate_offset_and_limit(
+            order_page.offset.as_ref(),
+            order_page.limit.as_ref(),
+            diagnostics,
+        );
+    }
+}
+
+/// Validates the LIMIT and OFFSET/SKIP counts of ORDER BY / SELECT clauses.
+fn validate_page_counts(statement: &PrimitiveQueryStatement, diagnostics: &mut Vec<Diag>) {
+    let (offset, limit) = match statement {
+        PrimitiveQueryStatement::OrderByAndPage(order_page) => {
+            (order_page.offset.as_ref(), order_page.limit.as_ref())
+        }
+        PrimitiveQueryStatement::Select(select) => (select.offset.as_ref(), select.limit.as_ref()),
+        _ => return,
+    };
+    validate_offset_and_limit(offset, limit, diagnostics);
+}
+
+fn validate_offset_and_limit(
+    offset: Option<&OffsetClause>,
+    limit: Option<&LimitClause>,
+    diagnostics: &mut Vec<Diag>,
+) {
+    if let Some(OffsetClause {
+        count,
+        use_skip_keyword,
+        ..
+    }) = offset
+    {
+        let clause = if *use_skip_keyword { "SKIP" } else { "OFFSET" };
+        validate_page_count(clause, count, diagnostics);
+    }
+    if let Some(LimitClause { count, .. }) = limit {
+        validate_page_count("LIMIT", count, diagnostics);
+    }
+}
+
+/// Evaluates a page count at compile time if it is constant.
+///
+/// Counts that depend on parameters are left to the engine.
+fn validate_page_count(clause: &str, count: &Expression, diagnostics: &mut Vec<Diag>) {
+    match ConstEvaluator::new().evaluate(count) {
+        Ok(Value::Integer(n)) if n >= 0 => {}
+        Ok(value) => {
+            let found = match value {
+                Value::Integer(_) | Value::Null => value.to_string(),
+                other => format!("{} of type {}", other, other.type_of().name()),
+            };
+            diagnostics.push(invalid_page_count(clause, &found, count.span()));
+        }
+        Err(error) if error.is_not_constant() => {}
+        Err(error) => diagnostics.push(error.to_diag()),
     }
 }
 
@@ -288,6 +356,7 @@ fn validate_mutation_expressions(
                     }
                     _ => {}
                 }
+                validate_page_counts(query_stmt, diagnostics);
             }
             SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Primitive(
                 primitive,
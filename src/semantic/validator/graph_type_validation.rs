@@ -0,0 +1,301 @@
+//! Pass 8: Graph Type Validation
+//!
+//! Checks the constraints declared in `CREATE GRAPH TYPE` bodies against the
+//! node or edge type they belong to:
+//! - KEY, UNIQUE and MANDATORY name declared (or inherited) properties
+//! - CHECK conditions, on the type or on a single property, only reference
+//!   declared properties, compare values of compatible types and evaluate to
+//!   a boolean
+//! - DEFAULT values can be assigned to their property
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use crate::ast::expression::Expression;
+use crate::ast::graph_type::{
+    DirectedArcType, EdgeTypePattern, ElementTypeSpecification, GraphTypeConstraint,
+    LabelSetPhrase, NestedGraphTypeSpecification, PropertyType, PropertyTypesSpecification,
+};
+use crate::ast::visit::{Visit, walk_expression};
+use crate::ast::{CatalogStatementKind, GraphTypeSource, Program, Statement};
+use crate::diag::Diag;
+use crate::ir::symbol_table::SymbolKind;
+use crate::ir::type_table::Type;
+use crate::ir::{SymbolTable, TypeTable};
+use crate::semantic::diag::{type_mismatch, unknown_constraint_property};
+
+use super::type_inference::{VariableScope, infer_expression_type};
+
+/// Run graph type validation pass.
+pub(super) fn run_graph_type_validation(
+    validator: &super::SemanticValidator,
+    program: &Program,
+    diagnostics: &mut Vec<Diag>,
+) {
+    for statement in &program.statements {
+        if let Statement::Catalog(catalog) = statement
+            && let CatalogStatementKind::CreateGraphType(create) = &catalog.kind
+            && let Some(GraphTypeSource::Detailed { specification, .. }) = &create.source
+        {
+            validate_graph_type(validator, specification, diagnostics);
+        }
+    }
+}
+
+/// A node or edge type of a graph type definition.
+struct ElementType<'a> {
+    /// Type name followed by its labels, used to resolve inheritance.
+    names: Vec<SmolStr>,
+    properties: Vec<&'a PropertyType>,
+    parents: Vec<SmolStr>,
+    constraints: &'a [GraphTypeConstraint],
+}
+
+impl ElementType<'_> {
+    fn describe(&self) -> String {
+        match self.names.first() {
+            Some(name) => format!("'{}'", name),
+            None => "the element type".to_string(),
+        }
+    }
+}
+
+fn validate_graph_type(
+    validator: &super::SemanticValidator,
+    specification: &NestedGraphTypeSpecification,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let elements: Vec<ElementType> = specification
+        .body
+        .element_types
+        .types
+        .iter()
+        .map(element_type)
+        .collect();
+
+    for element in &elements {
+        let has_property_rules = element
+            .properties
+            .iter()
+            .any(|property| property.default.is_some() || property.check.is_some());
+        if element.constraints.is_empty() && !has_property_rules {
+            continue;
+        }
+        let mut declared = Vec::new();
+        collect_properties(element, &elements, &mut HashSet::new(), &mut declared);
+
+        for property in &element.properties {
+            if let Some(default) = &property.default {
+                check_default(validator, default, property, diagnostics);
+            }
+            if let Some(check) = &property.check {
+                check_condition(validator, check, element, &declared, diagnostics);
+            }
+        }
+
+        for constraint in element.constraints {
+            for property in constraint.properties() {
+                if !declared.iter().any(|p| p.name.name == property.name) {
+                    diagnostics.push(unknown_constraint_property(
+                        constraint.kind_name(),
+                        &property.name,
+                        &element.describe(),
+                        property.span.clone(),
+                    ));
+                }
+            }
+            if let GraphTypeConstraint::Check { condition, .. } = constraint {
+                check_condition(validator, condition, element, &declared, diagnostics);
+            }
+        }
+    }
+}
+
+fn element_type(element: &ElementTypeSpecification) -> ElementType<'_> {
+    match element {
+        ElementTypeSpecification::Node(node) => {
+            let filler = node.pattern.phrase.filler.as_ref();
+            let labels = filler
+                .and_then(|filler| filler.label_set.as_ref())
+                .map(|set| labels(&set.label_set_phrase))
+                .unwrap_or_default();
+            ElementType {
+                names: node.name.iter().cloned().chain(labels).collect(),
+                properties: filler
+                    .and_then(|filler| filler.property_types.as_ref())
+                    .map(|types| property_types(&types.specification))
+                    .unwrap_or_default(),
+                parents: parent_names(node.inheritance.as_ref()),
+                constraints: filler.map_or(&[], |filler| &filler.constraints),
+            }
+        }
+        ElementTypeSpecification::Edge(edge) => {
+            let filler = match &edge.pattern {
+                EdgeTypePattern::Directed(directed) => match &directed.arc {
+                    DirectedArcType::PointingRight(arc) => arc.filler.as_ref(),
+                    DirectedArcType::PointingLeft(arc) => arc.filler.as_ref(),
+                },
+                EdgeTypePattern::Undirected(undirected) => undirected.arc.filler.as_ref(),
+            };
+            let content = filler.and_then(|filler| filler.phrase.filler_content.as_ref());
+            let labels = content
+                .and_then(|content| content.label_set.as_ref())
+                .map(|set| labels(&set.label_set_phrase))
+                .unwrap_or_default();
+            ElementType {
+                names: edge.name.iter().cloned().chain(labels).collect(),
+                properties: content
+                    .and_then(|content| content.property_types.as_ref())
+                    .map(|types| property_types(&types.specification))
+                    .unwrap_or_default(),
+                parents: parent_names(edge.inheritance.as_ref()),
+                constraints: filler.map_or(&[], |filler| &filler.constraints),
+            }
+        }
+    }
+}
+
+fn labels(phrase: &LabelSetPhrase) -> Vec<SmolStr> {
+    match phrase {
+        LabelSetPhrase::Label(label) => vec![label.name.clone()],
+        LabelSetPhrase::Labels(set) | LabelSetPhrase::IsLabelSet(set) => {
+            set.labels.iter().map(|label| label.name.clone()).collect()
+        }
+    }
+}
+
+fn property_types(specification: &PropertyTypesSpecification) -> Vec<&PropertyType> {
+    specification
+        .property_types
+        .iter()
+        .flat_map(|list| &list.types)
+        .collect()
+}
+
+fn parent_names(inheritance: Option<&crate::ast::graph_type::TypeInheritanceClause>) -> Vec<SmolStr> {
+    inheritance
+        .map(|clause| clause.parents.iter().map(|parent| parent.name.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// Collects the properties of `element` and of the parents defined in the
+/// same graph type. Parents defined elsewhere contribute nothing.
+fn collect_properties<'a>(
+    element: &ElementType<'a>,
+    elements: &[ElementType<'a>],
+    visited: &mut HashSet<SmolStr>,
+    declared: &mut Vec<&'a PropertyType>,
+) {
+    declared.extend(element.properties.iter().copied());
+    for parent in &element.parents {
+        if !visited.insert(parent.clone()) {
+            continue;
+        }
+        if let Some(parent_type) = elements.iter().find(|e| e.names.contains(parent)) {
+            collect_properties(parent_type, elements, visited, declared);
+        }
+    }
+}
+
+/// Checks that a DEFAULT value can be assigned to its property.
+fn check_default(
+    validator: &super::SemanticValidator,
+    value: &Expression,
+    property: &PropertyType,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let no_variables = SymbolTable::new();
+    let variables = VariableScope::new(&no_variables, no_variables.current_scope());
+    let value_type = infer_expression_type(validator, variables, value, &mut TypeTable::new());
+    let property_type = Type::from_value_type(&property.value_type.value_type);
+    if !value_type.can_coerce_to(&property_type) {
+        diagnostics.push(type_mismatch(
+            &property_type.name(),
+            &value_type.without_not_null().name(),
+            value.span(),
+        ));
+    }
+}
+
+/// Checks a CHECK condition, where properties are referenced by bare name.
+fn check_condition(
+    validator: &super::SemanticValidator,
+    condition: &Expression,
+    element: &ElementType,
+    declared: &[&PropertyType],
+    diagnostics: &mut Vec<Diag>,
+) {
+    let mut properties = SymbolTable::new();
+    for property in declared {
+        properties.define_typed(
+            property.name.name.to_string(),
+            SymbolKind::BindingVariable,
+            property.span.clone(),
+            Some(Type::from_value_type(&property.value_type.value_type)),
+        );
+    }
+    let variables = VariableScope::new(&properties, properties.current_scope());
+    let mut type_table = TypeTable::new();
+    let condition_type =
+        infer_expression_type(validator, variables, condition, &mut type_table);
+
+    let mut checker = ConditionChecker {
+        element,
+        declared,
+        type_table: &type_table,
+        diagnostics,
+    };
+    let _ = checker.visit_expression(condition);
+
+    let condition_type = condition_type.without_not_null();
+    if !matches!(condition_type, Type::Boolean | Type::Any | Type::Null) {
+        diagnostics.push(type_mismatch(
+            "Boolean",
+            &condition_type.name(),
+            condition.span(),
+        ));
+    }
+}
+
+struct ConditionChecker<'a, 'd> {
+    element: &'a ElementType<'a>,
+    declared: &'a [&'a PropertyType],
+    type_table: &'a TypeTable,
+    diagnostics: &'d mut Vec<Diag>,
+}
+
+impl Visit for ConditionChecker<'_, '_> {
+    type Break = ();
+
+    fn visit_expression(&mut self, expression: &Expression) -> ControlFlow<Self::Break> {
+        match expression {
+            Expression::VariableReference(name, span)
+                if !self.declared.iter().any(|p| p.name.name == *name) =>
+            {
+                self.diagnostics.push(unknown_constraint_property(
+                    "CHECK",
+                    name,
+                    &self.element.describe(),
+                    span.clone(),
+                ));
+            }
+            Expression::Comparison(_, left, right, _) => {
+                if let (Some(left_type), Some(right_type)) = (
+                    self.type_table.get_type_by_span(&left.span()),
+                    self.type_table.get_type_by_span(&right.span()),
+                ) && !left_type.is_compatible_with(right_type)
+                {
+                    self.diagnostics.push(type_mismatch(
+                        &left_type.without_not_null().name(),
+                        &right_type.without_not_null().name(),
+                        right.span(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+        walk_expression(self, expression)
+    }
+}
@@ -0,0 +1,229 @@
+//! Group variable validation pass for semantic validation.
+//!
+//! Element variables declared inside a quantified path primary are group
+//! variables: a reference inside the quantifier sees the element bound by the
+//! current iteration, while a reference anywhere else sees the list of
+//! elements bound across all iterations. This pass tracks the degree of
+//! reference at each use and reports:
+//! - property access on a group variable outside an aggregate, e.g. `e.since`
+//!   in the graph pattern WHERE clause (`sum(e.km)` is fine)
+//! - group variables compared with `=` or `<>`, once per comparison
+//!
+//! Names declared both as group and singleton variables are reported by
+//! scope analysis.
+
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use crate::ast::expression::{ComparisonOperator, Expression};
+use crate::ast::mutation::{LinearDataModifyingStatement, SimpleDataAccessingStatement};
+use crate::ast::query::{
+    EdgePattern, GraphPattern, GraphPatternQuantifier, LinearQuery, NodePattern, PathFactor,
+};
+use crate::ast::visit::{
+    Visit, VisitResult, walk_edge_pattern, walk_expression, walk_linear_query, walk_node_pattern,
+    walk_path_factor,
+};
+use crate::ast::{Program, Span, Statement};
+use crate::diag::Diag;
+use crate::semantic::diag::{group_variable_as_singleton, group_variable_comparison};
+
+/// Main entry point for group variable validation pass.
+pub(super) fn run_group_variable_validation(
+    _validator: &super::SemanticValidator,
+    program: &Program,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let mut checker = GroupChecker {
+        groups: HashMap::new(),
+        quantifiers: Vec::new(),
+        declaring: false,
+        aggregate_depth: 0,
+        diagnostics,
+    };
+
+    for statement in &program.statements {
+        match statement {
+            Statement::Query(query_stmt) => {
+                let _ = checker.visit_query_statement(query_stmt);
+            }
+            Statement::Mutation(mutation_stmt) => {
+                checker.mutation(&mutation_stmt.statement);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Where a group variable was declared.
+#[derive(Clone)]
+struct GroupDeclaration {
+    /// Spans of the quantifiers enclosing the declaration, outermost first.
+    quantifiers: Vec<Span>,
+    span: Span,
+}
+
+struct GroupChecker<'d> {
+    /// Group variables visible at the current position.
+    groups: HashMap<SmolStr, GroupDeclaration>,
+    /// Spans of the quantifiers enclosing the current position, outermost
+    /// first.
+    quantifiers: Vec<Span>,
+    /// Whether element patterns are visited to collect declarations rather
+    /// than to check their expressions.
+    declaring: bool,
+    aggregate_depth: usize,
+    diagnostics: &'d mut Vec<Diag>,
+}
+
+impl GroupChecker<'_> {
+    fn mutation(&mut self, mutation: &LinearDataModifyingStatement) {
+        let outer = self.groups.clone();
+        for statement in &mutation.statements {
+            if let SimpleDataAccessingStatement::Query(query) = statement {
+                let _ = self.visit_primitive_query_statement(query);
+            }
+        }
+        self.groups = outer;
+    }
+
+    fn declare(&mut self, variable: Option<&SmolStr>, span: Option<&Span>) {
+        if self.quantifiers.is_empty() {
+            return;
+        }
+        if let (Some(variable), Some(span)) = (variable, span) {
+            self.groups.insert(
+                variable.clone(),
+                GroupDeclaration {
+                    quantifiers: self.quantifiers.clone(),
+                    span: span.clone(),
+                },
+            );
+        }
+    }
+
+    /// Returns the declaration of `expression` if it refers to a group
+    /// variable with group degree at the current position.
+    fn group_reference<'e>(
+        &self,
+        expression: &'e Expression,
+    ) -> Option<(&'e SmolStr, &'e Span, &GroupDeclaration)> {
+        let Expression::VariableReference(name, span) = expression else {
+            return None;
+        };
+        let declaration = self.groups.get(name)?;
+        (!self.quantifiers.starts_with(&declaration.quantifiers)).then_some((
+            name,
+            span,
+            declaration,
+        ))
+    }
+}
+
+impl Visit for GroupChecker<'_> {
+    type Break = ();
+
+    fn visit_linear_query(&mut self, query: &LinearQuery) -> VisitResult<()> {
+        // Group variables of one linear query are not visible in its siblings.
+        let outer = self.groups.clone();
+        let result = walk_linear_query(self, query);
+        self.groups = outer;
+        result
+    }
+
+    fn visit_graph_pattern(&mut self, pattern: &GraphPattern) -> VisitResult<()> {
+        // Element WHERE clauses may refer to variables declared later in the
+        // pattern, so all declarations are collected before any check.
+        self.declaring = true;
+        for path in &pattern.paths.patterns {
+            self.visit_path_pattern(path)?;
+        }
+        self.declaring = false;
+        for path in &pattern.paths.patterns {
+            self.visit_path_pattern(path)?;
+        }
+
+        if let Some(where_clause) = &pattern.where_clause {
+            self.visit_expression(&where_clause.condition)?;
+        }
+        if let Some(yield_clause) = &pattern.yield_clause {
+            for item in &yield_clause.items {
+                self.visit_expression(&item.expression)?;
+            }
+        }
+        VisitResult::Continue(())
+    }
+
+    fn visit_path_factor(&mut self, factor: &PathFactor) -> VisitResult<()> {
+        let repeats = factor
+            .quantifier
+            .as_ref()
+            .filter(|q| !matches!(q, GraphPatternQuantifier::QuestionMark { .. }));
+        let Some(quantifier) = repeats else {
+            return walk_path_factor(self, factor);
+        };
+
+        self.quantifiers.push(quantifier.span().clone());
+        let result = walk_path_factor(self, factor);
+        self.quantifiers.pop();
+        result
+    }
+
+    fn visit_node_pattern(&mut self, pattern: &NodePattern) -> VisitResult<()> {
+        if self.declaring {
+            let variable = pattern.variable.as_ref();
+            self.declare(variable.map(|v| &v.variable), variable.map(|v| &v.span));
+            return VisitResult::Continue(());
+        }
+        walk_node_pattern(self, pattern)
+    }
+
+    fn visit_edge_pattern(&mut self, pattern: &EdgePattern) -> VisitResult<()> {
+        if self.declaring {
+            if let EdgePattern::Full(full) = pattern {
+                let variable = full.filler.variable.as_ref();
+                self.declare(variable.map(|v| &v.variable), variable.map(|v| &v.span));
+            }
+            return VisitResult::Continue(());
+        }
+        walk_edge_pattern(self, pattern)
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> VisitResult<()> {
+        match expression {
+            Expression::AggregateFunction(_) => {
+                self.aggregate_depth += 1;
+                let result = walk_expression(self, expression);
+                self.aggregate_depth -= 1;
+                return result;
+            }
+            Expression::PropertyReference(object, _, _) if self.aggregate_depth == 0 => {
+                if let Some((name, span, declaration)) = self.group_reference(object) {
+                    let diag =
+                        group_variable_as_singleton(name, span.clone(), declaration.span.clone());
+                    self.diagnostics.push(diag);
+                }
+            }
+            Expression::Comparison(
+                ComparisonOperator::Eq | ComparisonOperator::NotEq,
+                left,
+                right,
+                _,
+            ) => {
+                // One report per comparison, even when both operands are
+                // group variables, as in `e = e`.
+                let group = [left, right]
+                    .into_iter()
+                    .find_map(|operand| self.group_reference(operand));
+                if let Some((name, span, declaration)) = group {
+                    let diag =
+                        group_variable_comparison(name, span.clone(), declaration.span.clone());
+                    self.diagnostics.push(diag);
+                }
+            }
+            _ => {}
+        }
+        walk_expression(self, expression)
+    }
+}
@@ -0,0 +1,573 @@
+// Pass 10: Label Narrowing
+//
+// This pass computes, for every element variable, the set of schema types it
+// may be bound to at each point of a linear query:
+// - Pattern label expressions: (n:A|B), (n:A&B), (n:!A), (n IS A)
+// - Predicates: WHERE n:A, FILTER n IS LABELED A, NOT / AND / OR combinations
+// - Inheritance: a type carries the labels of all of its ancestors
+//
+// Property references through a narrowed variable are then resolved against
+// the candidate types. A property that exists on none of them is an error;
+// otherwise the resolved type is recorded in the type table.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::ControlFlow;
+
+use smol_str::SmolStr;
+
+use crate::ast::expression::{LogicalOperator, Predicate, UnaryOperator};
+use crate::ast::query::{
+    EdgePattern, ElementPattern, GraphPattern, LabelExpression, MatchStatement, OptionalOperand,
+    PathPatternExpression, PathPrimary,
+};
+use crate::ast::visit::{Visit, VisitResult, walk_expression};
+use crate::ast::*;
+use crate::diag::Diag;
+use crate::ir::TypeTable;
+use crate::ir::type_table::Type;
+use crate::semantic::diag::{invalid_property_access, unsatisfiable_labels};
+use crate::semantic::schema_catalog::{SchemaSnapshot, TypeRef};
+
+/// Run label narrowing pass.
+pub(super) fn run_label_narrowing(
+    validator: &super::SemanticValidator,
+    statements: &[Statement],
+    type_table: &mut TypeTable,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let Some(metadata) = validator.metadata_provider else {
+        return;
+    };
+
+    for statement in statements {
+        if let Statement::Query(query_stmt) = statement {
+            let Some(snapshot) = super::statement_snapshot(metadata, statement) else {
+                continue;
+            };
+
+            let universe = Universe::new(&*snapshot);
+            if universe.nodes.is_empty() && universe.edges.is_empty() {
+                // Nothing to narrow against.
+                continue;
+            }
+
+            let mut narrowing = Narrowing {
+                snapshot: &*snapshot,
+                universe: &universe,
+                type_table,
+                diagnostics,
+                reported_empty: BTreeSet::new(),
+            };
+            narrowing.query(&query_stmt.query);
+        }
+    }
+}
+
+/// Kind of element a variable is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElementKind {
+    Node,
+    Edge,
+}
+
+/// Candidate types of an element variable.
+#[derive(Debug, Clone, PartialEq)]
+struct Candidates {
+    kind: ElementKind,
+    /// Names of the schema types the variable may be bound to.
+    types: BTreeSet<SmolStr>,
+}
+
+type Env = HashMap<SmolStr, Candidates>;
+
+/// Every node and edge type in the schema, with its inherited label set.
+struct Universe {
+    nodes: Vec<(SmolStr, BTreeSet<SmolStr>)>,
+    edges: Vec<(SmolStr, BTreeSet<SmolStr>)>,
+}
+
+impl Universe {
+    fn new(snapshot: &dyn SchemaSnapshot) -> Self {
+        let nodes = snapshot
+            .node_type_names()
+            .into_iter()
+            .map(|name| {
+                let labels = inherited_labels(snapshot, TypeRef::NodeType(name.clone()));
+                (name, labels)
+            })
+            .collect();
+        let edges = snapshot
+            .edge_type_names()
+            .into_iter()
+            .map(|name| {
+                let labels = inherited_labels(snapshot, TypeRef::EdgeType(name.clone()));
+                (name, labels)
+            })
+            .collect();
+        Self { nodes, edges }
+    }
+
+    fn types(&self, kind: ElementKind) -> &[(SmolStr, BTreeSet<SmolStr>)] {
+        match kind {
+            ElementKind::Node => &self.nodes,
+            ElementKind::Edge => &self.edges,
+        }
+    }
+
+    fn all(&self, kind: ElementKind) -> BTreeSet<SmolStr> {
+        self.types(kind).iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Types of the given kind whose label set satisfies `expr`.
+    fn matching(&self, kind: ElementKind, expr: &LabelExpression) -> BTreeSet<SmolStr> {
+        self.types(kind)
+            .iter()
+            .filter(|(_, labels)| satisfies(labels, expr))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Collects a type's own name and the names of all of its ancestors.
+fn inherited_labels(snapshot: &dyn SchemaSnapshot, owner: TypeRef) -> BTreeSet<SmolStr> {
+    let mut labels = BTreeSet::new();
+    let mut pending = vec![owner];
+    while let Some(current) = pending.pop() {
+        let name = match &current {
+            TypeRef::NodeType(name) | TypeRef::EdgeType(name) => name.clone(),
+        };
+        if labels.insert(name) {
+            pending.extend(snapshot.parents(current).iter().cloned());
+        }
+    }
+    labels
+}
+
+/// Evaluates a label expression against a concrete label set.
+fn satisfies(labels: &BTreeSet<SmolStr>, expr: &LabelExpression) -> bool {
+    match expr {
+        LabelExpression::LabelName { name, .. } => labels.contains(name),
+        LabelExpression::Wildcard { .. } => true,
+        LabelExpression::Negation { operand, .. } => !satisfies(labels, operand),
+        LabelExpression::Conjunction { left, right, .. } => {
+            satisfies(labels, left) && satisfies(labels, right)
+        }
+        LabelExpression::Disjunction { left, right, .. } => {
+            satisfies(labels, left) || satisfies(labels, right)
+        }
+        LabelExpression::Parenthesized { expression, .. } => satisfies(labels, expression),
+    }
+}
+
+/// Returns true if every label named in `expr` is known to the schema.
+///
+/// Unknown labels are reported by schema validation; an empty candidate set
+/// caused by one is not worth a second diagnostic.
+fn labels_known(universe: &Universe, kind: ElementKind, expr: &LabelExpression) -> bool {
+    match expr {
+        LabelExpression::LabelName { name, .. } => universe
+            .types(kind)
+            .iter()
+            .any(|(_, labels)| labels.contains(name)),
+        LabelExpression::Wildcard { .. } => true,
+        LabelExpression::Negation { operand, .. } => labels_known(universe, kind, operand),
+        LabelExpression::Conjunction { left, right, .. }
+        | LabelExpression::Disjunction { left, right, .. } => {
+            labels_known(universe, kind, left) && labels_known(universe, kind, right)
+        }
+        LabelExpression::Parenthesized { expression, .. } => {
+            labels_known(universe, kind, expression)
+        }
+    }
+}
+
+struct Narrowing<'a> {
+    snapshot: &'a dyn SchemaSnapshot,
+    universe: &'a Universe,
+    type_table: &'a mut TypeTable,
+    diagnostics: &'a mut Vec<Diag>,
+    /// Variables already reported as unsatisfiable, to avoid cascades.
+    reported_empty: BTreeSet<SmolStr>,
+}
+
+impl Narrowing<'_> {
+    fn query(&mut self, query: &Query) {
+        match query {
+            Query::Linear(linear) => {
+                let mut env = Env::new();
+                self.linear_query(linear, &mut env);
+            }
+            Query::Composite(composite) => {
+                self.query(&composite.left);
+                self.query(&composite.right);
+            }
+            Query::Parenthesized(query, _) => self.query(query),
+        }
+    }
+
+    fn linear_query(&mut self, linear: &LinearQuery, env: &mut Env) {
+        for statement in &linear.primitive_statements {
+            match statement {
+                PrimitiveQueryStatement::Match(match_stmt) => self.match_statement(match_stmt, env),
+                PrimitiveQueryStatement::Filter(filter) => {
+                    self.narrow(&filter.condition, env);
+                    self.check(&filter.condition, env);
+                }
+                PrimitiveQueryStatement::Let(let_stmt) => {
+                    for binding in &let_stmt.bindings {
+                        self.check(&binding.value, env);
+                    }
+                    for binding in &let_stmt.bindings {
+                        env.remove(&binding.variable.name);
+                    }
+                }
+                PrimitiveQueryStatement::For(for_stmt) => {
+                    self.check(&for_stmt.item.collection, env);
+                    env.remove(&for_stmt.item.binding_variable.name);
+                }
+                PrimitiveQueryStatement::OrderByAndPage(_) | PrimitiveQueryStatement::Select(_) => {
+                    self.check_statement(statement, env);
+                }
+                PrimitiveQueryStatement::Call(_) => {}
+            }
+        }
+
+        if let Some(result) = &linear.result_statement {
+            let mut checker = PropertyChecker { narrowing: self, env };
+            let _ = checker.visit_primitive_result_statement(result);
+        }
+    }
+
+    fn match_statement(&mut self, statement: &MatchStatement, env: &mut Env) {
+        match statement {
+            MatchStatement::Simple(simple) => self.graph_pattern(&simple.pattern, env),
+            MatchStatement::Optional(optional) => {
+                // Bindings introduced by the optional part may be null, but
+                // narrowing applied inside it must not leak to outer variables.
+                let mut inner = env.clone();
+                match &optional.operand {
+                    OptionalOperand::Match { pattern } => self.graph_pattern(pattern, &mut inner),
+                    OptionalOperand::Block { statements }
+                    | OptionalOperand::ParenthesizedBlock { statements } => {
+                        for statement in statements {
+                            self.match_statement(statement, &mut inner);
+                        }
+                    }
+                }
+                for (name, candidates) in inner {
+                    env.entry(name).or_insert(candidates);
+                }
+            }
+        }
+    }
+
+    fn graph_pattern(&mut self, pattern: &GraphPattern, env: &mut Env) {
+        for path in &pattern.paths.patterns {
+            self.path_expression(&path.expression, env);
+        }
+        if let Some(where_clause) = &pattern.where_clause {
+            self.narrow(&where_clause.condition, env);
+            self.check(&where_clause.condition, env);
+        }
+    }
+
+    fn path_expression(&mut self, expr: &PathPatternExpression, env: &mut Env) {
+        match expr {
+            PathPatternExpression::Term(term) => {
+                for factor in &term.factors {
+                    match &factor.primary {
+                        PathPrimary::ElementPattern(element) => self.element(element, env),
+                        PathPrimary::ParenthesizedExpression(inner) => {
+                            self.path_expression(inner, env)
+                        }
+                        PathPrimary::SimplifiedExpression(_) => {}
+                    }
+                }
+            }
+            PathPatternExpression::Union { left, right, .. } => {
+                self.alternatives([left.as_ref(), right.as_ref()], env);
+            }
+            PathPatternExpression::Alternation { alternatives, .. } => {
+                self.alternatives(alternatives.iter(), env);
+            }
+        }
+    }
+
+    /// Processes each alternative against its own copy of the environment and
+    /// merges the results: a variable may have any type it has in any branch.
+    fn alternatives<'p>(
+        &mut self,
+        branches: impl IntoIterator<Item = &'p PathPatternExpression>,
+        env: &mut Env,
+    ) {
+        let mut merged: Env = Env::new();
+        for branch in branches {
+            let mut branch_env = env.clone();
+            self.path_expression(branch, &mut branch_env);
+            for (name, candidates) in branch_env {
+                merged
+                    .entry(name)
+                    .and_modify(|existing| existing.types.extend(candidates.types.iter().cloned()))
+                    .or_insert(candidates);
+            }
+        }
+        *env = merged;
+    }
+
+    fn element(&mut self, element: &ElementPattern, env: &mut Env) {
+        let (kind, variable, label_expression, properties, where_clause) = match element {
+            ElementPattern::Node(node) => (
+                ElementKind::Node,
+                node.variable.as_ref(),
+                node.label_expression.as_ref(),
+                node.properties.as_ref(),
+                node.where_clause.as_ref(),
+            ),
+            ElementPattern::Edge(EdgePattern::Full(full)) => (
+                ElementKind::Edge,
+                full.filler.variable.as_ref(),
+                full.filler.label_expression.as_ref(),
+                full.filler.properties.as_ref(),
+                full.filler.where_clause.as_ref(),
+            ),
+            ElementPattern::Edge(EdgePattern::Abbreviated(_)) => return,
+        };
+
+        if self.universe.types(kind).is_empty() {
+            return;
+        }
+
+        let types = match label_expression {
+            Some(expr) => self.universe.matching(kind, expr),
+            None => self.universe.all(kind),
+        };
+        let mut candidates = Candidates { kind, types };
+
+        if let Some(variable) = variable {
+            // A variable repeated across patterns must satisfy every occurrence.
+            if let Some(previous) = env.get(&variable.variable)
+                && previous.kind == kind
+            {
+                candidates.types = candidates
+                    .types
+                    .intersection(&previous.types)
+                    .cloned()
+                    .collect();
+            }
+            if candidates.types.is_empty()
+                && label_expression.is_none_or(|expr| labels_known(self.universe, kind, expr))
+            {
+                self.report_empty(&variable.variable, variable.span.clone());
+            }
+            env.insert(variable.variable.clone(), candidates.clone());
+        }
+
+        if let Some(properties) = properties {
+            for pair in &properties.properties {
+                self.check_property(
+                    variable.map_or("element", |v| v.variable.as_str()),
+                    &candidates,
+                    &pair.key,
+                    pair.span.clone(),
+                );
+                self.check(&pair.value, env);
+            }
+        }
+
+        if let Some(where_clause) = where_clause {
+            self.narrow(&where_clause.condition, env);
+            self.check(&where_clause.condition, env);
+        }
+    }
+
+    /// Applies the label constraints implied by a boolean condition holding.
+    fn narrow(&mut self, condition: &Expression, env: &mut Env) {
+        for (name, types) in self.constraints(condition, false, env) {
+            let Some(candidates) = env.get_mut(&name) else {
+                continue;
+            };
+            let before = candidates.types.len();
+            candidates.types = candidates.types.intersection(&types).cloned().collect();
+            if candidates.types.is_empty() && before > 0 {
+                let span = condition.span();
+                self.report_empty(&name, span);
+            }
+        }
+    }
+
+    /// Derives per-variable type sets from `condition` (or its negation).
+    ///
+    /// Variables absent from the result are unconstrained.
+    fn constraints(
+        &self,
+        condition: &Expression,
+        negated: bool,
+        env: &Env,
+    ) -> HashMap<SmolStr, BTreeSet<SmolStr>> {
+        match condition {
+            Expression::Predicate(Predicate::IsLabeled(operand, Some(label), not, _)) => {
+                let Expression::VariableReference(name, _) = operand.as_ref() else {
+                    return HashMap::new();
+                };
+                let Some(candidates) = env.get(name) else {
+                    return HashMap::new();
+                };
+                if !labels_known(self.universe, candidates.kind, label) {
+                    return HashMap::new();
+                }
+                let mut types = self.universe.matching(candidates.kind, label);
+                if *not != negated {
+                    types = self
+                        .universe
+                        .all(candidates.kind)
+                        .difference(&types)
+                        .cloned()
+                        .collect();
+                }
+                HashMap::from([(name.clone(), types)])
+            }
+            Expression::Unary(UnaryOperator::Not, operand, _) => {
+                self.constraints(operand, !negated, env)
+            }
+            Expression::Parenthesized(inner, _) => self.constraints(inner, negated, env),
+            Expression::Logical(op @ (LogicalOperator::And | LogicalOperator::Or), left, right, _) => {
+                let left = self.constraints(left, negated, env);
+                let right = self.constraints(right, negated, env);
+                // De Morgan: a negated conjunction behaves like a disjunction.
+                if (*op == LogicalOperator::And) != negated {
+                    conjoin(left, right)
+                } else {
+                    disjoin(left, right)
+                }
+            }
+            _ => HashMap::new(),
+        }
+    }
+
+    fn report_empty(&mut self, name: &SmolStr, span: Span) {
+        if self.reported_empty.insert(name.clone()) {
+            self.diagnostics.push(unsatisfiable_labels(name, span));
+        }
+    }
+
+    fn check(&mut self, expr: &Expression, env: &Env) {
+        let mut checker = PropertyChecker { narrowing: self, env };
+        let _ = checker.visit_expression(expr);
+    }
+
+    fn check_statement(&mut self, statement: &PrimitiveQueryStatement, env: &Env) {
+        let mut checker = PropertyChecker { narrowing: self, env };
+        let _ = checker.visit_primitive_query_statement(statement);
+    }
+
+    /// Resolves `property` against the candidate types and reports it if none
+    /// declares it. Returns the resolved property type.
+    fn check_property(
+        &mut self,
+        variable: &str,
+        candidates: &Candidates,
+        property: &str,
+        span: Span,
+    ) -> Option<Type> {
+        if candidates.types.is_empty() {
+            return None;
+        }
+
+        let mut types = Vec::new();
+        let mut required = true;
+        for name in &candidates.types {
+            let owner = match candidates.kind {
+                ElementKind::Node => TypeRef::NodeType(name.clone()),
+                ElementKind::Edge => TypeRef::EdgeType(name.clone()),
+            };
+            match self.snapshot.property(owner, property) {
+                Some(meta) => {
+                    types.push(Type::from_value_type(&meta.value_type));
+                    required &= meta.required;
+                }
+                None => required = false,
+            }
+        }
+
+        if types.is_empty() {
+            let names: Vec<&str> = candidates.types.iter().map(SmolStr::as_str).collect();
+            self.diagnostics
+                .push(invalid_property_access(variable, property, &names, span));
+            return None;
+        }
+
+        let ty = Type::common_supertype(&types).unwrap_or(Type::Union(types));
+        Some(if required { Type::not_null(ty) } else { ty })
+    }
+}
+
+/// Both constraint sets hold: intersect shared variables, keep the rest.
+fn conjoin(
+    mut left: HashMap<SmolStr, BTreeSet<SmolStr>>,
+    right: HashMap<SmolStr, BTreeSet<SmolStr>>,
+) -> HashMap<SmolStr, BTreeSet<SmolStr>> {
+    for (name, types) in right {
+        left.entry(name)
+            .and_modify(|existing| *existing = existing.intersection(&types).cloned().collect())
+            .or_insert(types);
+    }
+    left
+}
+
+/// Either constraint set holds: only variables constrained on both sides are
+/// narrowed, to the union of their sets.
+fn disjoin(
+    left: HashMap<SmolStr, BTreeSet<SmolStr>>,
+    mut right: HashMap<SmolStr, BTreeSet<SmolStr>>,
+) -> HashMap<SmolStr, BTreeSet<SmolStr>> {
+    left.into_iter()
+        .filter_map(|(name, mut types)| {
+            let other = right.remove(&name)?;
+            types.extend(other);
+            Some((name, types))
+        })
+        .collect()
+}
+
+/// Visits expressions under a fixed environment, checking property accesses
+/// and recording narrowed element types.
+struct PropertyChecker<'n, 'a, 'e> {
+    narrowing: &'n mut Narrowing<'a>,
+    env: &'e Env,
+}
+
+impl Visit for PropertyChecker<'_, '_, '_> {
+    type Break = ();
+
+    fn visit_expression(&mut self, expr: &Expression) -> VisitResult<()> {
+        match expr {
+            Expression::VariableReference(name, span) => {
+                if let Some(candidates) = self.env.get(name)
+                    && !candidates.types.is_empty()
+                {
+                    let names = Some(candidates.types.iter().map(SmolStr::to_string).collect());
+                    let ty = match candidates.kind {
+                        ElementKind::Node => Type::Node(names),
+                        ElementKind::Edge => Type::Edge(names),
+                    };
+                    self.narrowing.type_table.set_type_by_span(span, ty);
+                }
+                ControlFlow::Continue(())
+            }
+            Expression::PropertyReference(object, property, span) => {
+                if let Expression::VariableReference(name, _) = object.as_ref()
+                    && let Some(candidates) = self.env.get(name)
+                    && let Some(ty) =
+                        self.narrowing
+                            .check_property(name, candidates, property, span.clone())
+                {
+                    self.narrowing.type_table.set_type_by_span(span, ty);
+                }
+                self.visit_expression(object)
+            }
+            // Nested queries bind their own variables.
+            Expression::SubqueryExpression(..) | Expression::Exists(_) => ControlFlow::Continue(()),
+            _ => walk_expression(self, expr),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Pass 0: Literal Validation
+//
+// This pass decodes every scalar literal (see `Literal::value`) and reports
+// literals that are malformed or out of range:
+// - DATE '2024-13-45'       -> month out of range
+// - DURATION 'P1Q'          -> invalid designator
+// - 340282366920938463463374607431768211456 -> fits no integer type
+//
+// Diagnostics point at the offending component where it can be located in
+// the source, otherwise at the whole literal.
+
+use crate::ast::literal::LiteralErrorKind;
+use crate::ast::visit::{Visit, VisitResult, walk_expression};
+use crate::ast::*;
+use crate::diag::Diag;
+use crate::semantic::diag::invalid_literal;
+
+/// Run literal validation pass.
+pub(super) fn run_literal_validation(
+    _validator: &super::SemanticValidator,
+    program: &Program,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let mut checker = LiteralChecker { diagnostics };
+    let _ = checker.visit_program(program);
+}
+
+struct LiteralChecker<'d> {
+    diagnostics: &'d mut Vec<Diag>,
+}
+
+impl LiteralChecker<'_> {
+    fn check(&mut self, literal: &Literal, span: &Span) {
+        let Err(error) = literal.value() else {
+            return;
+        };
+        if error.kind == LiteralErrorKind::NotScalar {
+            return;
+        }
+
+        let (kind, text, content_start) = match literal {
+            Literal::Integer(text) => ("integer", text, Some(span.start)),
+            Literal::Float(text) => ("numeric", text, Some(span.start)),
+            // X'...'
+            Literal::ByteString(text) => ("byte string", text, Some(span.start + 2)),
+            // KEYWORD '...': the content ends just before the closing quote.
+            Literal::Date(text) => ("date", text, temporal_content_start(text, span)),
+            Literal::Time(text) => ("time", text, temporal_content_start(text, span)),
+            Literal::Datetime(text) => ("datetime", text, temporal_content_start(text, span)),
+            Literal::Duration(text) => ("duration", text, temporal_content_start(text, span)),
+            _ => return,
+        };
+
+        let precise = match (content_start, &error.range) {
+            (Some(start), Some(range)) if range.end <= text.len() => {
+                let end = (start + range.end.max(range.start + 1)).min(span.end);
+                Some(start + range.start..end)
+            }
+            _ => None,
+        };
+        let span = precise
+            .filter(|s| s.start >= span.start && s.end <= span.end && s.start < s.end)
+            .unwrap_or_else(|| span.clone());
+
+        self.diagnostics
+            .push(invalid_literal(kind, &error.message, span));
+    }
+}
+
+/// Source offset of a temporal literal's string content, if its stored text
+/// can be mapped back onto the source one-to-one.
+fn temporal_content_start(text: &str, span: &Span) -> Option<usize> {
+    let start = span.end.checked_sub(text.len() + 1)?;
+    (start > span.start).then_some(start)
+}
+
+impl Visit for LiteralChecker<'_> {
+    type Break = ();
+
+    fn visit_expression(&mut self, expr: &Expression) -> VisitResult<()> {
+        if let Expression::Literal(literal, span) = expr {
+            self.check(literal, span);
+        }
+        walk_expression(self, expr)
+    }
+}
@@ -3,20 +3,32 @@
 mod callable_validation;
 mod context_validation;
 mod expression_validation;
-mod pattern_validation;
+mod graph_type_validation;
+mod group_variable_validation;
+mod label_narrowing;
+mod literal_validation;
+pub(crate) mod pattern_validation;
+mod quantifier_validation;
 mod reference_validation;
 mod schema_validation;
 mod scope_analysis;
+mod transaction_validation;
 mod type_checking;
 mod type_inference;
 mod variable_validation;
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use crate::ast::program::Program;
-use crate::diag::DiagSeverity;
+use crate::ast::expression::Expression;
+use crate::ast::program::{Program, Statement};
+use crate::ast::query::Query;
+use crate::diag::{Diag, DiagSeverity};
 use crate::ir::symbol_table::ScopeId;
-use crate::ir::{IR, ValidationOutcome};
+use crate::ir::{IR, TypeTable, ValidationOutcome};
+use crate::semantic::catalog_overlay::CatalogOverlay;
+use crate::semantic::metadata_provider::MetadataProvider;
+use crate::semantic::schema_catalog::{SchemaSnapshot, SessionContext};
 
 /// Tracks the scope context where an expression is evaluated.
 #[derive(Debug, Clone, Copy)]
@@ -142,6 +154,7 @@ impl<'m> SemanticValidator<'m> {
     /// # Multi-Pass Validation
     ///
     /// The validator runs multiple passes in sequence:
+    /// 0. Literal Validation - Check literals decode to valid values
     /// 1. Scope Analysis - Build symbol table
     /// 2. Type Inference - Infer expression types
     /// 3. Variable Validation - Check undefined variables
@@ -149,7 +162,14 @@ impl<'m> SemanticValidator<'m> {
     /// 5. Context Validation - Check clause usage
     /// 6. Type Checking - Check type compatibility
     /// 7. Expression Validation - Check expressions
-    /// 8. Metadata Validation (optional) - Check references, labels, callables
+    /// 8. Graph Type Validation - Check graph-type constraints, property
+    ///    defaults and checks against the properties of their element types
+    /// 9. Transaction Validation - Check each statement against the transaction
+    ///    state (READ ONLY, COMMIT/ROLLBACK without START, session commands
+    ///    inside a transaction)
+    /// 10. Metadata Validation (optional) - Check references, labels, properties
+    /// 11. Callable Validation - Check arity and argument types of calls; unknown
+    ///     callables are only reported with metadata validation enabled
     ///
     /// # Error Recovery
     ///
@@ -159,13 +179,21 @@ impl<'m> SemanticValidator<'m> {
     pub fn validate(&self, program: &Program) -> ValidationOutcome {
         let mut diagnostics = Vec::new();
 
+        // Pass 0: Literal Validation - Malformed or out-of-range literals
+        literal_validation::run_literal_validation(self, program, &mut diagnostics);
+
         // Pass 1: Scope Analysis - Builds symbol table and tracks expression contexts
         let (symbol_table, scope_metadata) =
             scope_analysis::run_scope_analysis(self, program, &mut diagnostics);
 
         // Pass 2: Type Inference
-        let type_table =
-            type_inference::run_type_inference(self, program, &symbol_table, &mut diagnostics);
+        let mut type_table = type_inference::run_type_inference(
+            self,
+            program,
+            &symbol_table,
+            &scope_metadata,
+            &mut diagnostics,
+        );
 
         // Pass 3: Variable Validation - Now uses scope metadata for reference-site-aware lookups
         variable_validation::run_variable_validation(
@@ -179,6 +207,12 @@ impl<'m> SemanticValidator<'m> {
         // Pass 4: Pattern Validation
         pattern_validation::run_pattern_validation(self, program, &mut diagnostics);
 
+        // Quantifier Validation - path patterns whose result may be infinite
+        quantifier_validation::run_quantifier_validation(self, program, &mut diagnostics);
+
+        // Group Variable Validation - group variables used as single elements
+        group_variable_validation::run_group_variable_validation(self, program, &mut diagnostics);
+
         // Pass 5: Context Validation
         context_validation::run_context_validation(self, program, &mut diagnostics);
 
@@ -188,16 +222,36 @@ impl<'m> SemanticValidator<'m> {
         // Pass 7: Expression Validation
         expression_validation::run_expression_validation(self, program, &type_table, &mut diagnostics);
 
-        // Pass 8: Metadata Validation (optional) - includes references, schema, callables
-        if self.config.metadata_validation {
-            // Reference validation (USE GRAPH)
-            reference_validation::run_reference_validation(self, program, &mut diagnostics);
+        // Pass 8: Graph Type Validation - constraints, defaults and checks in CREATE GRAPH TYPE
+        graph_type_validation::run_graph_type_validation(self, program, &mut diagnostics);
 
-            // Schema validation (labels, properties)
-            schema_validation::run_schema_validation(self, program, &mut diagnostics);
+        // Pass 9: Transaction Validation - statements the transaction state does not allow
+        transaction_validation::run_transaction_validation(self, program, &mut diagnostics);
 
-            // Callable validation (functions, procedures)
-            callable_validation::run_callable_validation(self, program, &mut diagnostics);
+        // Passes 10 and 11 see catalog changes made by earlier statements
+        let has_catalog_statements = program
+            .statements
+            .iter()
+            .any(|statement| matches!(statement, Statement::Catalog(_)));
+        match self.metadata_provider {
+            Some(base) if self.config.metadata_validation && has_catalog_statements => {
+                let mut overlay = CatalogOverlay::new(base);
+                for statement in &program.statements {
+                    let focused = SemanticValidator {
+                        config: self.config.clone(),
+                        metadata_provider: Some(&overlay),
+                    };
+                    focused.run_catalog_passes(
+                        std::slice::from_ref(statement),
+                        &mut type_table,
+                        &mut diagnostics,
+                    );
+                    if let Statement::Catalog(catalog) = statement {
+                        diagnostics.extend(overlay.apply(&catalog.kind));
+                    }
+                }
+            }
+            _ => self.run_catalog_passes(&program.statements, &mut type_table, &mut diagnostics),
         }
 
         // Return IR or diagnostics
@@ -216,6 +270,64 @@ impl<'m> SemanticValidator<'m> {
     }
 }
 
+impl SemanticValidator<'_> {
+    /// Runs the passes that consult the catalog.
+    fn run_catalog_passes(
+        &self,
+        statements: &[Statement],
+        type_table: &mut TypeTable,
+        diagnostics: &mut Vec<Diag>,
+    ) {
+        // Pass 10: Metadata Validation (optional) - includes references, schema, label narrowing
+        if self.config.metadata_validation {
+            // Reference validation (USE GRAPH)
+            reference_validation::run_reference_validation(self, statements, diagnostics);
+
+            // Schema validation (labels, properties)
+            schema_validation::run_schema_validation(self, statements, diagnostics);
+
+            // Label narrowing (candidate types of element variables, properties)
+            label_narrowing::run_label_narrowing(self, statements, type_table, diagnostics);
+        }
+
+        // Pass 11: Callable Validation (functions, procedures, aggregates)
+        callable_validation::run_callable_validation(self, statements, type_table, diagnostics);
+    }
+}
+
+/// Returns the schema snapshot of the graph a statement runs against.
+///
+/// A `USE g` clause focuses the statement on `g`; otherwise the provider's
+/// active graph is used. Returns `None` if the snapshot cannot be resolved.
+pub(super) fn statement_snapshot(
+    metadata: &dyn MetadataProvider,
+    statement: &Statement,
+) -> Option<Arc<dyn SchemaSnapshot>> {
+    let use_graph = match statement {
+        Statement::Query(query_stmt) => query_use_graph(&query_stmt.query),
+        Statement::Mutation(mutation_stmt) => mutation_stmt.statement.use_graph_clause.as_ref(),
+        _ => None,
+    };
+    let session = SessionContext {
+        active_graph: use_graph.and_then(|clause| match &clause.graph {
+            Expression::VariableReference(name, _) => Some(name.clone()),
+            _ => None,
+        }),
+        ..SessionContext::new()
+    };
+    let graph = metadata.resolve_active_graph(&session).ok()?;
+    let schema = metadata.resolve_active_schema(&graph).ok()?;
+    metadata.get_schema_snapshot(&graph, Some(&schema)).ok()
+}
+
+fn query_use_graph(query: &Query) -> Option<&crate::ast::query::UseGraphClause> {
+    match query {
+        Query::Linear(linear) => linear.use_graph.as_ref(),
+        Query::Composite(composite) => query_use_graph(&composite.left),
+        Query::Parenthesized(query, _) => query_use_graph(query),
+    }
+}
+
 impl<'m> Default for SemanticValidator<'m> {
     fn default() -> Self {
         Self::new()
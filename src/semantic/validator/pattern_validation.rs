@@ -95,17 +95,7 @@ fn validate_match_pattern_connectivity(
 
         // Build connectivity graph
         // Each variable is a node, and edges connect variables that appear together
-        let mut adjacency: HashMap<String, HashSet<String>> = HashMap::new();
-        let mut all_variables = HashSet::new();
-
-        for path_pattern in &pattern.paths.patterns {
-            extract_connectivity_from_path_pattern(
-                validator,
-                path_pattern,
-                &mut adjacency,
-                &mut all_variables,
-            );
-        }
+        let (adjacency, all_variables) = pattern_connectivity(pattern);
 
         // If no variables or only one variable, skip connectivity check
         if all_variables.len() <= 1 {
@@ -147,9 +137,25 @@ fn validate_match_pattern_connectivity(
     }
 }
 
+/// Builds the connectivity graph of a graph pattern.
+///
+/// Returns an adjacency list connecting variables that appear next to each
+/// other in a path, and the set of all node, edge and path variables.
+pub(crate) fn pattern_connectivity(
+    pattern: &crate::ast::query::GraphPattern,
+) -> (HashMap<String, HashSet<String>>, HashSet<String>) {
+    let mut adjacency: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut all_variables = HashSet::new();
+
+    for path_pattern in &pattern.paths.patterns {
+        extract_connectivity_from_path_pattern(path_pattern, &mut adjacency, &mut all_variables);
+    }
+
+    (adjacency, all_variables)
+}
+
 /// Extracts connectivity information from a path pattern.
 fn extract_connectivity_from_path_pattern(
-    validator: &super::SemanticValidator,
     path_pattern: &PathPattern,
     adjacency: &mut HashMap<String, HashSet<String>>,
     all_variables: &mut HashSet<String>,
@@ -159,32 +165,26 @@ fn extract_connectivity_from_path_pattern(
         all_variables.insert(var_decl.variable.to_string());
     }
 
-    extract_connectivity_from_expression(
-        validator,
-        &path_pattern.expression,
-        adjacency,
-        all_variables,
-    );
+    extract_connectivity_from_expression(&path_pattern.expression, adjacency, all_variables);
 }
 
 /// Extracts connectivity from a path pattern expression.
 fn extract_connectivity_from_expression(
-    validator: &super::SemanticValidator,
     expr: &PathPatternExpression,
     adjacency: &mut HashMap<String, HashSet<String>>,
     all_variables: &mut HashSet<String>,
 ) {
     match expr {
         PathPatternExpression::Term(term) => {
-            extract_connectivity_from_term(validator, term, adjacency, all_variables);
+            extract_connectivity_from_term(term, adjacency, all_variables);
         }
         PathPatternExpression::Union { left, right, .. } => {
-            extract_connectivity_from_expression(validator, left, adjacency, all_variables);
-            extract_connectivity_from_expression(validator, right, adjacency, all_variables);
+            extract_connectivity_from_expression(left, adjacency, all_variables);
+            extract_connectivity_from_expression(right, adjacency, all_variables);
         }
         PathPatternExpression::Alternation { alternatives, .. } => {
             for alt in alternatives {
-                extract_connectivity_from_expression(validator, alt, adjacency, all_variables);
+                extract_connectivity_from_expression(alt, adjacency, all_variables);
             }
         }
     }
@@ -192,7 +192,6 @@ fn extract_connectivity_from_expression(
 
 /// Extracts connectivity from a path term (sequence of elements).
 fn extract_connectivity_from_term(
-    validator: &super::SemanticValidator,
     term: &PathTerm,
     adjacency: &mut HashMap<String, HashSet<String>>,
     all_variables: &mut HashSet<String>,
@@ -241,7 +240,7 @@ fn extract_connectivity_from_term(
                 }
             }
         } else if let PathPrimary::ParenthesizedExpression(nested_expr) = &factor.primary {
-            extract_connectivity_from_expression(validator, nested_expr, adjacency, all_variables);
+            extract_connectivity_from_expression(nested_expr, adjacency, all_variables);
         }
     }
 }
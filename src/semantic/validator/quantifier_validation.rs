@@ -0,0 +1,294 @@
+//! Quantifier validation pass for semantic validation.
+//!
+//! A path pattern with an unbounded quantifier (`*`, `+`, `{n,}`) matches
+//! infinitely many paths in a cyclic graph unless something limits the
+//! result. ISO GQL accepts such a pattern only when it is:
+//! - restrictive: TRAIL, SIMPLE or ACYCLIC path mode, or DIFFERENT EDGES
+//! - selective: ANY, ANY SHORTEST, ALL SHORTEST, SHORTEST k [GROUPS]
+//!
+//! Nested quantifiers where either side is unbounded additionally need a
+//! restrictive mode, since a selective search alone does not bound the
+//! iterations of the inner subpattern. A KEEP clause lends its prefix to
+//! every path pattern of its graph pattern, and cannot be combined with a
+//! path pattern that already selects paths itself.
+//!
+//! Finiteness diagnostics are warnings unless strict mode is enabled; the
+//! KEEP conflict is always an error.
+
+use crate::ast::mutation::{LinearDataModifyingStatement, SimpleDataAccessingStatement};
+use crate::ast::query::{
+    GraphPattern, GraphPatternQuantifier, MatchMode, PathMode, PathPatternExpression,
+    PathPatternPrefix, PathPrimary, PathSearch, ShortestPathSearch,
+    SimplifiedPathPatternExpression,
+};
+use crate::ast::visit::{Visit, VisitResult, walk_graph_pattern};
+use crate::ast::{Program, Span, Statement};
+use crate::diag::{Diag, DiagSeverity};
+use crate::semantic::diag::{
+    nested_path_quantifier, selective_path_with_keep, unbounded_path_quantifier,
+};
+
+/// Main entry point for quantifier validation pass.
+pub(super) fn run_quantifier_validation(
+    validator: &super::SemanticValidator,
+    program: &Program,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let mut checker = QuantifierChecker {
+        strict: validator.config.strict_mode,
+        diagnostics,
+    };
+
+    for statement in &program.statements {
+        match statement {
+            Statement::Query(query_stmt) => {
+                let _ = checker.visit_query_statement(query_stmt);
+            }
+            Statement::Mutation(mutation_stmt) => {
+                checker.mutation(&mutation_stmt.statement);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What limits the paths matched by one path pattern.
+#[derive(Clone, Copy)]
+struct PathLimits {
+    /// Repeated edges (or nodes) are excluded.
+    restrictive: bool,
+    /// Only a finite selection of the matching paths is returned.
+    selective: bool,
+}
+
+struct QuantifierChecker<'d> {
+    strict: bool,
+    diagnostics: &'d mut Vec<Diag>,
+}
+
+impl QuantifierChecker<'_> {
+    fn mutation(&mut self, mutation: &LinearDataModifyingStatement) {
+        for statement in &mutation.statements {
+            if let SimpleDataAccessingStatement::Query(query) = statement {
+                let _ = self.visit_primitive_query_statement(query);
+            }
+        }
+    }
+
+    /// Reports a finiteness problem as an error in strict mode, otherwise as
+    /// a warning.
+    fn report(&mut self, mut diag: Diag) {
+        if !self.strict {
+            diag.severity = DiagSeverity::Warning;
+        }
+        self.diagnostics.push(diag);
+    }
+
+    fn graph_pattern(&mut self, pattern: &GraphPattern) {
+        let different_edges = pattern.match_mode == Some(MatchMode::DifferentEdges);
+        let keep_prefix = pattern.keep_clause.as_ref().map(|keep| &keep.prefix);
+
+        for path in &pattern.paths.patterns {
+            if let (Some(keep), Some(PathPatternPrefix::PathSearch(search))) =
+                (&pattern.keep_clause, &path.prefix)
+                && is_selective(search)
+            {
+                self.diagnostics.push(selective_path_with_keep(
+                    path_search_span(search).clone(),
+                    keep.span.clone(),
+                ));
+            }
+
+            let prefixes = || path.prefix.iter().chain(keep_prefix);
+            let limits = PathLimits {
+                restrictive: different_edges || prefixes().any(is_restrictive_prefix),
+                selective: prefixes().any(|prefix| match prefix {
+                    PathPatternPrefix::PathSearch(search) => is_selective(search),
+                    PathPatternPrefix::PathMode(_) => false,
+                }),
+            };
+            self.expression(&path.expression, limits, None);
+        }
+    }
+
+    /// Checks the quantifiers of a path pattern expression. `outer` is the
+    /// closest enclosing quantifier, if any.
+    fn expression(
+        &mut self,
+        expression: &PathPatternExpression,
+        limits: PathLimits,
+        outer: Option<&GraphPatternQuantifier>,
+    ) {
+        match expression {
+            PathPatternExpression::Term(term) => {
+                for factor in &term.factors {
+                    if let Some(quantifier) = &factor.quantifier {
+                        self.quantifier(quantifier, limits, outer);
+                    }
+                    let inner_outer = factor.quantifier.as_ref().or(outer);
+                    match &factor.primary {
+                        PathPrimary::ElementPattern(_) => {}
+                        PathPrimary::ParenthesizedExpression(inner) => {
+                            self.expression(inner, limits, inner_outer);
+                        }
+                        PathPrimary::SimplifiedExpression(inner) => {
+                            self.simplified(inner, limits, inner_outer);
+                        }
+                    }
+                }
+            }
+            PathPatternExpression::Union { left, right, .. } => {
+                self.expression(left, limits, outer);
+                self.expression(right, limits, outer);
+            }
+            PathPatternExpression::Alternation { alternatives, .. } => {
+                for alternative in alternatives {
+                    self.expression(alternative, limits, outer);
+                }
+            }
+        }
+    }
+
+    fn simplified(
+        &mut self,
+        expression: &SimplifiedPathPatternExpression,
+        limits: PathLimits,
+        outer: Option<&GraphPatternQuantifier>,
+    ) {
+        match expression {
+            SimplifiedPathPatternExpression::Contents(_) => {}
+            SimplifiedPathPatternExpression::Union(union) => {
+                self.simplified(&union.left, limits, outer);
+                self.simplified(&union.right, limits, outer);
+            }
+            SimplifiedPathPatternExpression::MultisetAlternation(alternation) => {
+                for alternative in &alternation.alternatives {
+                    self.simplified(alternative, limits, outer);
+                }
+            }
+            SimplifiedPathPatternExpression::Conjunction(conjunction) => {
+                self.simplified(&conjunction.left, limits, outer);
+                self.simplified(&conjunction.right, limits, outer);
+            }
+            SimplifiedPathPatternExpression::Concatenation(concatenation) => {
+                for part in &concatenation.parts {
+                    self.simplified(part, limits, outer);
+                }
+            }
+            SimplifiedPathPatternExpression::Quantified(quantified) => {
+                self.quantifier(&quantified.quantifier, limits, outer);
+                self.simplified(&quantified.pattern, limits, Some(&quantified.quantifier));
+            }
+            SimplifiedPathPatternExpression::Questioned(questioned) => {
+                self.simplified(&questioned.pattern, limits, outer);
+            }
+            SimplifiedPathPatternExpression::DirectionOverride(direction) => {
+                self.simplified(&direction.pattern, limits, outer);
+            }
+            SimplifiedPathPatternExpression::Negation(negation) => {
+                self.simplified(&negation.pattern, limits, outer);
+            }
+        }
+    }
+
+    fn quantifier(
+        &mut self,
+        quantifier: &GraphPatternQuantifier,
+        limits: PathLimits,
+        outer: Option<&GraphPatternQuantifier>,
+    ) {
+        if let Some(outer) = outer
+            && (is_unbounded(quantifier) || is_unbounded(outer))
+            && !limits.restrictive
+        {
+            self.report(nested_path_quantifier(
+                quantifier.span().clone(),
+                outer.span().clone(),
+            ));
+        } else if is_unbounded(quantifier) && !limits.restrictive && !limits.selective {
+            self.report(unbounded_path_quantifier(quantifier.span().clone()));
+        }
+    }
+}
+
+impl Visit for QuantifierChecker<'_> {
+    type Break = ();
+
+    fn visit_graph_pattern(&mut self, pattern: &GraphPattern) -> VisitResult<()> {
+        self.graph_pattern(pattern);
+        walk_graph_pattern(self, pattern)
+    }
+}
+
+fn is_unbounded(quantifier: &GraphPatternQuantifier) -> bool {
+    matches!(
+        quantifier,
+        GraphPatternQuantifier::Star { .. }
+            | GraphPatternQuantifier::Plus { .. }
+            | GraphPatternQuantifier::General { max: None, .. }
+    )
+}
+
+fn is_restrictive_mode(mode: Option<PathMode>) -> bool {
+    matches!(
+        mode,
+        Some(PathMode::Trail | PathMode::Simple | PathMode::Acyclic)
+    )
+}
+
+fn is_restrictive_prefix(prefix: &PathPatternPrefix) -> bool {
+    match prefix {
+        PathPatternPrefix::PathMode(mode) => is_restrictive_mode(Some(*mode)),
+        PathPatternPrefix::PathSearch(PathSearch::All(search)) => is_restrictive_mode(search.mode),
+        PathPatternPrefix::PathSearch(PathSearch::Any(search)) => is_restrictive_mode(search.mode),
+        PathPatternPrefix::PathSearch(PathSearch::Shortest(search)) => match search {
+            ShortestPathSearch::AllShortest { mode, .. }
+            | ShortestPathSearch::AnyShortest { mode, .. }
+            | ShortestPathSearch::CountedShortest { mode, .. }
+            | ShortestPathSearch::CountedShortestGroups { mode, .. } => is_restrictive_mode(*mode),
+        },
+    }
+}
+
+/// Returns true for the searches that return finitely many paths: every
+/// search except ALL.
+fn is_selective(search: &PathSearch) -> bool {
+    !matches!(search, PathSearch::All(_))
+}
+
+fn path_search_span(search: &PathSearch) -> &Span {
+    match search {
+        PathSearch::All(search) => &search.span,
+        PathSearch::Any(search) => &search.span,
+        PathSearch::Shortest(search) => search.span(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::semantic::validator::SemanticValidator;
+
+    fn messages(source: &str, strict: bool) -> Vec<String> {
+        let program = parse(source).ast.expect("parse");
+        SemanticValidator::new()
+            .with_strict_mode(strict)
+            .validate(&program)
+            .diagnostics
+            .into_iter()
+            .map(|diag| diag.message)
+            .collect()
+    }
+
+    #[test]
+    fn bounded_and_restricted_quantifiers_are_accepted() {
+        for source in [
+            "MATCH (a)-[e]->{1,5}(b) RETURN a",
+            "MATCH TRAIL (a)-[e]->+(b) RETURN a",
+            "MATCH ANY SHORTEST (a)-[e]->*(b) RETURN a",
+            "MATCH DIFFERENT EDGES (a)-[e]->{2,}(b) RETURN a",
+        ] {
+            assert!(messages(source, true).is_empty(), "{source}");
+        }
+    }
+}
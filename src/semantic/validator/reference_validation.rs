@@ -1,14 +1,14 @@
-//! Pass 8: Reference Validation
+//! Pass 10: Reference Validation
 //!
 //! Validates that references to catalog entities (e.g., graphs) exist.
 
-use crate::ast::{Program, Query, Statement};
+use crate::ast::{Query, Statement};
 use crate::diag::Diag;
 
-/// Pass 8: Reference Validation - Validates that references to metadata entities exist.
+/// Pass 10: Reference Validation - Validates that references to metadata entities exist.
 pub(super) fn run_reference_validation(
     validator: &super::SemanticValidator,
-    program: &Program,
+    statements: &[Statement],
     diagnostics: &mut Vec<Diag>,
 ) {
     // This pass checks:
@@ -22,7 +22,7 @@ pub(super) fn run_reference_validation(
         return;
     };
 
-    for statement in &program.statements {
+    for statement in statements {
         match statement {
             Statement::Catalog(_catalog_stmt) => {
                 // Validate catalog statement references
@@ -10,12 +10,11 @@ use crate::ast::query::{
 };
 use crate::ast::*;
 use crate::diag::Diag;
-use crate::semantic::schema_catalog::SessionContext;
 
 /// Run schema validation pass.
 pub(super) fn run_schema_validation(
     validator: &super::SemanticValidator,
-    program: &Program,
+    statements: &[Statement],
     diagnostics: &mut Vec<Diag>,
 ) {
     // This pass checks:
@@ -29,34 +28,13 @@ pub(super) fn run_schema_validation(
         return;
     };
 
-    // Get schema snapshot
-    let session = SessionContext::new();
-    let graph = match metadata.resolve_active_graph(&session) {
-        Ok(g) => g,
-        Err(_) => {
-            // Can't resolve graph, skip schema validation
-            return;
-        }
-    };
-
-    let schema = match metadata.resolve_active_schema(&graph) {
-        Ok(s) => s,
-        Err(_) => {
-            // Can't resolve schema, skip validation
-            return;
-        }
-    };
-
-    let snapshot = match metadata.get_schema_snapshot(&graph, Some(&schema)) {
-        Ok(snap) => snap,
-        Err(_) => {
-            // Can't get snapshot, skip validation
-            return;
-        }
-    };
-
-    for statement in &program.statements {
+    for statement in statements {
         if let Statement::Query(query_stmt) = statement {
+            // Skip statements whose graph has no known schema
+            let Some(snapshot) = super::statement_snapshot(metadata, statement) else {
+                continue;
+            };
+
             // Validate:
             // - Node labels: (n:Person) -> check if 'Person' exists in schema
             // - Edge labels: -[e:KNOWS]-> -> check if 'KNOWS' exists in schema
@@ -5,7 +5,10 @@
 //! - Extracts variable definitions from MATCH, LET, FOR, and INSERT statements
 //! - Builds a symbol table with proper scoping
 //! - Tracks statement boundaries for variable isolation
+//! - Binds the columns a statement returns in the statement after `NEXT`
 //! - Detects variable shadowing when configured
+//! - Marks element variables declared inside quantified path primaries as
+//!   group variables, and rejects names used both as group and singleton
 //!
 //! The scope analysis phase produces:
 //! - A `SymbolTable` containing all declared variables with their scopes
@@ -13,16 +16,21 @@
 
 use std::collections::HashMap;
 
-use crate::ast::program::Program;
+use crate::ast::expression::Expression;
+use crate::ast::procedure::{CallProcedureStatement, ProcedureCall};
+use crate::ast::program::{Program, Statement};
 use crate::ast::query::{
-    EdgePattern, ElementPattern, ForStatement, LetStatement, LinearQuery, MatchStatement,
-    PathPattern, PathPatternExpression, PathPrimary, PathTerm, PrimitiveQueryStatement, Query,
+    EdgePattern, ElementPattern, ElementVariableDeclaration, ForStatement, GraphPatternQuantifier,
+    LetStatement, LinearQuery, MatchStatement, PathPattern, PathPatternExpression, PathPrimary,
+    PathTerm, PrimitiveQueryStatement, PrimitiveResultStatement, Query, ReturnItemList,
 };
 use crate::diag::Diag;
 use crate::ir::SymbolTable;
-use crate::ir::symbol_table::{ScopeId, ScopeKind, SymbolKind};
+use crate::ir::symbol_table::{ScopeId, ScopeKind, Symbol, SymbolKind};
+use crate::ir::type_table::Type;
 
 use super::ScopeMetadata;
+use super::callable_validation::{resolve_procedure, yield_bindings};
 
 /// Main entry point for scope analysis pass.
 ///
@@ -45,11 +53,17 @@ pub(super) fn run_scope_analysis(
 
     // Walk all statements in the program, tracking statement boundaries
     let mut next_statement_id = 0usize;
+    // Columns returned by the previous statement, bound after NEXT
+    let mut columns = Vec::new();
     for statement in &program.statements {
         match statement {
-            crate::ast::program::Statement::Query(query_stmt) => {
+            Statement::Query(query_stmt) => {
                 let statement_id = next_statement_id;
                 next_statement_id += 1;
+                let incoming = query_stmt
+                    .follows_next
+                    .then(|| std::mem::take(&mut columns));
+                bind_columns(incoming.as_deref(), &mut symbol_table);
                 analyze_query(
                     validator,
                     &query_stmt.query,
@@ -59,10 +73,20 @@ pub(super) fn run_scope_analysis(
                     &mut next_statement_id,
                     diagnostics,
                 );
+                unbind_columns(incoming.as_deref(), &mut symbol_table);
+                columns = result_columns(
+                    &symbol_table,
+                    scope_metadata.statement_scopes[statement_id],
+                    query_result(&query_stmt.query),
+                );
             }
-            crate::ast::program::Statement::Mutation(mutation_stmt) => {
+            Statement::Mutation(mutation_stmt) => {
                 let statement_id = next_statement_id;
                 next_statement_id += 1;
+                let incoming = mutation_stmt
+                    .follows_next
+                    .then(|| std::mem::take(&mut columns));
+                bind_columns(incoming.as_deref(), &mut symbol_table);
                 analyze_mutation_with_scope(
                     validator,
                     &mutation_stmt.statement,
@@ -71,11 +95,17 @@ pub(super) fn run_scope_analysis(
                     statement_id,
                     diagnostics,
                 );
+                unbind_columns(incoming.as_deref(), &mut symbol_table);
+                columns = result_columns(
+                    &symbol_table,
+                    scope_metadata.statement_scopes[statement_id],
+                    mutation_stmt.statement.primitive_result_statement.as_ref(),
+                );
             }
-            crate::ast::program::Statement::Session(_)
-            | crate::ast::program::Statement::Transaction(_)
-            | crate::ast::program::Statement::Catalog(_)
-            | crate::ast::program::Statement::Empty(_) => {
+            Statement::Session(_)
+            | Statement::Transaction(_)
+            | Statement::Catalog(_)
+            | Statement::Empty(_) => {
                 // These don't introduce variables or scopes
             }
         }
@@ -163,6 +193,92 @@ fn analyze_mutation_with_scope(
     scope_metadata.statement_scopes[statement_id] = statement_scope_id;
 }
 
+/// Returns the result statement that produces the columns of `query`.
+///
+/// The branches of a composite query return the same columns, so the left
+/// branch stands for the whole query.
+fn query_result(query: &Query) -> Option<&PrimitiveResultStatement> {
+    match query {
+        Query::Linear(linear_query) => linear_query.result_statement.as_deref(),
+        Query::Composite(composite_query) => query_result(&composite_query.left),
+        Query::Parenthesized(query, _) => query_result(query),
+    }
+}
+
+/// Collects the columns of a result statement as symbols.
+///
+/// A column that returns a variable keeps that variable's binding, so an
+/// element can still be matched or updated after `NEXT`; any other named
+/// column becomes a [`SymbolKind::ResultColumn`].
+fn result_columns(
+    symbol_table: &SymbolTable,
+    scope: ScopeId,
+    result: Option<&PrimitiveResultStatement>,
+) -> Vec<Symbol> {
+    let Some(PrimitiveResultStatement::Return(return_stmt)) = result else {
+        return Vec::new();
+    };
+
+    match &return_stmt.items {
+        ReturnItemList::Star => {
+            // The statement's own bindings and those carried into it
+            let mut names = Vec::new();
+            let mut scope_id = Some(scope);
+            while let Some(scope_data) = scope_id.and_then(|id| symbol_table.get_scope(id)) {
+                names.extend(scope_data.symbols());
+                scope_id = scope_data.parent;
+            }
+            names.sort();
+            names.dedup();
+            names
+                .into_iter()
+                .filter_map(|name| symbol_table.lookup_from(scope, name))
+                .cloned()
+                .collect()
+        }
+        ReturnItemList::Items { items } => items
+            .iter()
+            .filter_map(|item| {
+                let variable = match &item.expression {
+                    Expression::VariableReference(name, _) => Some(name),
+                    _ => None,
+                };
+                let name = item.alias.as_ref().or(variable)?.to_string();
+                let mut column = variable
+                    .and_then(|variable| symbol_table.lookup_from(scope, variable))
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        Symbol::new(name.clone(), SymbolKind::ResultColumn, 0..0, scope)
+                    });
+                column.name = name;
+                column.declared_at = item.span.clone();
+                Some(column)
+            })
+            .collect(),
+    }
+}
+
+/// Opens a scope holding the columns of the statement before `NEXT`, so the
+/// scope of the statement after it sees them as outer bindings.
+fn bind_columns(columns: Option<&[Symbol]>, symbol_table: &mut SymbolTable) {
+    let Some(columns) = columns else {
+        return;
+    };
+    symbol_table.push_scope(ScopeKind::Query);
+    for column in columns {
+        if !symbol_table.is_defined_in_current_scope(&column.name) {
+            symbol_table.define_alias(column, column.name.clone(), column.declared_at.clone());
+        }
+    }
+}
+
+/// Closes the scope opened by [`bind_columns`].
+fn unbind_columns(columns: Option<&[Symbol]>, symbol_table: &mut SymbolTable) {
+    if columns.is_some() {
+        symbol_table.pop_scope();
+    }
+}
+
 /// Analyzes a linear query and extracts variables from clauses.
 fn analyze_linear_query(
     validator: &super::SemanticValidator,
@@ -213,9 +329,8 @@ fn analyze_primitive_statement(
         PrimitiveQueryStatement::For(for_stmt) => {
             analyze_for_statement(validator, for_stmt, symbol_table, diagnostics);
         }
-        PrimitiveQueryStatement::Call(_) => {
-            // CALL statements are handled separately - they may reference variables
-            // but don't introduce new binding variables in the scope analysis phase
+        PrimitiveQueryStatement::Call(call_stmt) => {
+            analyze_call_statement(validator, call_stmt, symbol_table, diagnostics);
         }
         PrimitiveQueryStatement::Filter(_) => {
             // FILTER statements reference existing variables in their condition
@@ -298,6 +413,7 @@ fn extract_pattern_variables(
         extract_expression_variables(
             validator,
             &path_pattern.expression,
+            false,
             symbol_table,
             diagnostics,
         );
@@ -305,23 +421,33 @@ fn extract_pattern_variables(
 }
 
 /// Extracts variables from a path pattern expression.
+///
+/// `group` is set inside a quantified path primary, where element variables
+/// are group variables.
 fn extract_expression_variables(
     validator: &super::SemanticValidator,
     expression: &PathPatternExpression,
+    group: bool,
     symbol_table: &mut SymbolTable,
     diagnostics: &mut Vec<Diag>,
 ) {
     match expression {
         PathPatternExpression::Term(term) => {
-            extract_term_variables(validator, term, symbol_table, diagnostics);
+            extract_term_variables(validator, term, group, symbol_table, diagnostics);
         }
         PathPatternExpression::Union { left, right, .. } => {
-            extract_expression_variables(validator, left, symbol_table, diagnostics);
-            extract_expression_variables(validator, right, symbol_table, diagnostics);
+            extract_expression_variables(validator, left, group, symbol_table, diagnostics);
+            extract_expression_variables(validator, right, group, symbol_table, diagnostics);
         }
         PathPatternExpression::Alternation { alternatives, .. } => {
             for alternative in alternatives {
-                extract_expression_variables(validator, alternative, symbol_table, diagnostics);
+                extract_expression_variables(
+                    validator,
+                    alternative,
+                    group,
+                    symbol_table,
+                    diagnostics,
+                );
             }
         }
     }
@@ -331,11 +457,19 @@ fn extract_expression_variables(
 fn extract_term_variables(
     validator: &super::SemanticValidator,
     term: &PathTerm,
+    group: bool,
     symbol_table: &mut SymbolTable,
     diagnostics: &mut Vec<Diag>,
 ) {
     for factor in &term.factors {
-        extract_primary_variables(validator, &factor.primary, symbol_table, diagnostics);
+        // Every quantifier except `?` repeats its primary, making the element
+        // variables declared inside it group variables.
+        let group = group
+            || factor
+                .quantifier
+                .as_ref()
+                .is_some_and(|q| !matches!(q, GraphPatternQuantifier::QuestionMark { .. }));
+        extract_primary_variables(validator, &factor.primary, group, symbol_table, diagnostics);
     }
 }
 
@@ -343,15 +477,16 @@ fn extract_term_variables(
 fn extract_primary_variables(
     validator: &super::SemanticValidator,
     primary: &PathPrimary,
+    group: bool,
     symbol_table: &mut SymbolTable,
     diagnostics: &mut Vec<Diag>,
 ) {
     match primary {
         PathPrimary::ElementPattern(element) => {
-            extract_element_variables(validator, element, symbol_table, diagnostics);
+            extract_element_variables(validator, element, group, symbol_table, diagnostics);
         }
         PathPrimary::ParenthesizedExpression(expr) => {
-            extract_expression_variables(validator, expr, symbol_table, diagnostics);
+            extract_expression_variables(validator, expr, group, symbol_table, diagnostics);
         }
         PathPrimary::SimplifiedExpression(_) => {
             // Simplified expressions don't have explicit variables
@@ -363,49 +498,77 @@ fn extract_primary_variables(
 fn extract_element_variables(
     validator: &super::SemanticValidator,
     element: &ElementPattern,
+    group: bool,
     symbol_table: &mut SymbolTable,
     diagnostics: &mut Vec<Diag>,
 ) {
     match element {
         ElementPattern::Node(node_pattern) => {
             if let Some(var_decl) = &node_pattern.variable {
-                let var_name = var_decl.variable.to_string();
-                let span = var_decl.span.clone();
-
-                if validator.config.warn_on_shadowing
-                    && let Some(existing) = symbol_table.lookup(&var_name)
-                {
-                    use crate::semantic::diag::variable_shadowing;
-                    let diag =
-                        variable_shadowing(&var_name, span.clone(), existing.declared_at.clone());
-                    diagnostics.push(diag);
-                }
-
-                symbol_table.define(var_name, SymbolKind::BindingVariable, span);
+                define_element_variable(
+                    validator,
+                    var_decl,
+                    group.then_some(Type::Node(None)),
+                    symbol_table,
+                    diagnostics,
+                );
             }
         }
         ElementPattern::Edge(edge_pattern) => {
             if let EdgePattern::Full(full_edge) = edge_pattern
                 && let Some(var_decl) = &full_edge.filler.variable
             {
-                let var_name = var_decl.variable.to_string();
-                let span = var_decl.span.clone();
-
-                if validator.config.warn_on_shadowing
-                    && let Some(existing) = symbol_table.lookup(&var_name)
-                {
-                    use crate::semantic::diag::variable_shadowing;
-                    let diag =
-                        variable_shadowing(&var_name, span.clone(), existing.declared_at.clone());
-                    diagnostics.push(diag);
-                }
-
-                symbol_table.define(var_name, SymbolKind::BindingVariable, span);
+                define_element_variable(
+                    validator,
+                    var_decl,
+                    group.then_some(Type::Edge(None)),
+                    symbol_table,
+                    diagnostics,
+                );
             }
         }
     }
 }
 
+/// Defines an element variable from a MATCH pattern. `group_element` is the
+/// element type of a group variable, or `None` for a singleton.
+fn define_element_variable(
+    validator: &super::SemanticValidator,
+    var_decl: &ElementVariableDeclaration,
+    group_element: Option<Type>,
+    symbol_table: &mut SymbolTable,
+    diagnostics: &mut Vec<Diag>,
+) {
+    use crate::semantic::diag::{group_singleton_mix, variable_shadowing};
+
+    let var_name = var_decl.variable.to_string();
+    let span = var_decl.span.clone();
+
+    if let Some(existing) = symbol_table.lookup(&var_name) {
+        if existing.kind == SymbolKind::BindingVariable
+            && existing.is_group() != group_element.is_some()
+        {
+            diagnostics.push(group_singleton_mix(
+                &var_name,
+                span.clone(),
+                existing.declared_at.clone(),
+            ));
+        } else if validator.config.warn_on_shadowing {
+            let diag = variable_shadowing(&var_name, span.clone(), existing.declared_at.clone());
+            diagnostics.push(diag);
+        }
+    }
+
+    match group_element {
+        Some(element_type) => {
+            symbol_table.define_group(var_name, span, element_type);
+        }
+        None => {
+            symbol_table.define(var_name, SymbolKind::BindingVariable, span);
+        }
+    }
+}
+
 /// Analyzes a LET statement and extracts variable definitions.
 fn analyze_let_statement(
     validator: &super::SemanticValidator,
@@ -431,6 +594,48 @@ fn analyze_let_statement(
     }
 }
 
+/// Analyzes a CALL statement and defines the variables it yields.
+///
+/// Inline procedure calls bind nothing in the enclosing scope; named calls
+/// bind their YIELD items, or all result columns when there is no YIELD.
+fn analyze_call_statement(
+    validator: &super::SemanticValidator,
+    call_stmt: &CallProcedureStatement,
+    symbol_table: &mut SymbolTable,
+    diagnostics: &mut Vec<Diag>,
+) {
+    use crate::semantic::diag::variable_shadowing;
+
+    let ProcedureCall::Named(call) = &call_stmt.call else {
+        return;
+    };
+
+    // Argument types are not inferred yet, so overloads resolve on arity
+    let arg_count = call.arguments.as_ref().map_or(0, |a| a.arguments.len());
+    let signature = resolve_procedure(validator, call, &vec![Type::Any; arg_count]);
+    for binding in yield_bindings(signature.as_ref(), call, call_stmt.optional) {
+        let var_name = binding.name.to_string();
+
+        if validator.config.warn_on_shadowing
+            && let Some(existing) = symbol_table.lookup(&var_name)
+        {
+            let diag = variable_shadowing(
+                &var_name,
+                binding.span.clone(),
+                existing.declared_at.clone(),
+            );
+            diagnostics.push(diag);
+        }
+
+        symbol_table.define_typed(
+            var_name,
+            SymbolKind::YieldVariable,
+            binding.span,
+            Some(binding.ty),
+        );
+    }
+}
+
 /// Analyzes a FOR statement and extracts loop variable.
 fn analyze_for_statement(
     validator: &super::SemanticValidator,
@@ -532,9 +737,8 @@ fn analyze_modifying_statement(
         SimpleDataModifyingStatement::Primitive(primitive) => {
             analyze_primitive_modifying_statement(validator, primitive, symbol_table, diagnostics);
         }
-        SimpleDataModifyingStatement::Call(_call_stmt) => {
-            // CALL statements may yield variables - would need to analyze YIELD clause
-            // For now, this is a placeholder for future implementation
+        SimpleDataModifyingStatement::Call(call_stmt) => {
+            analyze_call_statement(validator, &call_stmt.call, symbol_table, diagnostics);
         }
     }
 }
@@ -0,0 +1,96 @@
+//! Transaction state validation pass.
+//!
+//! Follows the transaction state through the statements of a program and
+//! reports statements the state does not allow: data or catalog changes in
+//! a READ ONLY transaction, COMMIT or ROLLBACK with no transaction started,
+//! a nested START TRANSACTION, and session commands while a transaction is
+//! active.
+
+use crate::analysis::{StatementCategory, StatementClass};
+use crate::ast::{
+    Program, Span, Statement, TransactionAccessMode, TransactionCommand, TransactionMode,
+};
+use crate::diag::Diag;
+use crate::semantic::diag::{
+    inactive_transaction, nested_transaction, read_only_transaction_violation,
+    session_command_in_transaction,
+};
+
+/// An explicitly started transaction.
+struct ActiveTransaction {
+    span: Span,
+    read_only: bool,
+}
+
+/// Runs transaction state validation on the AST.
+pub(super) fn run_transaction_validation(
+    validator: &super::SemanticValidator,
+    program: &Program,
+    diagnostics: &mut Vec<Diag>,
+) {
+    let mut active: Option<ActiveTransaction> = None;
+
+    for statement in &program.statements {
+        match statement {
+            Statement::Transaction(transaction) => match &transaction.command {
+                TransactionCommand::Start(start) => {
+                    if let Some(outer) = &active {
+                        diagnostics.push(nested_transaction(
+                            start.span.clone(),
+                            outer.span.clone(),
+                        ));
+                    }
+                    let read_only = start.characteristics.iter().flat_map(|c| &c.modes).any(
+                        |mode| {
+                            matches!(
+                                mode,
+                                TransactionMode::AccessMode(TransactionAccessMode::ReadOnly)
+                            )
+                        },
+                    );
+                    active = Some(ActiveTransaction {
+                        span: start.span.clone(),
+                        read_only,
+                    });
+                }
+                TransactionCommand::Commit(commit) => {
+                    if active.take().is_none() {
+                        diagnostics.push(inactive_transaction("COMMIT", commit.span.clone()));
+                    }
+                }
+                TransactionCommand::Rollback(rollback) => {
+                    if active.take().is_none() {
+                        diagnostics.push(inactive_transaction("ROLLBACK", rollback.span.clone()));
+                    }
+                }
+            },
+            Statement::Session(session) => {
+                if let Some(transaction) = &active {
+                    diagnostics.push(session_command_in_transaction(
+                        session.span.clone(),
+                        transaction.span.clone(),
+                    ));
+                }
+            }
+            _ => {
+                let Some(transaction) = active.as_ref().filter(|t| t.read_only) else {
+                    continue;
+                };
+                let class = match validator.metadata_provider {
+                    Some(provider) => StatementClass::with_metadata(statement, provider),
+                    None => StatementClass::from_ast(statement),
+                };
+                let what = match class.category {
+                    StatementCategory::DataModifying => "data-modifying",
+                    StatementCategory::CatalogModifying => "catalog-modifying",
+                    _ => continue,
+                };
+                diagnostics.push(read_only_transaction_violation(
+                    what,
+                    statement.span(),
+                    transaction.span.clone(),
+                ));
+            }
+        }
+    }
+}
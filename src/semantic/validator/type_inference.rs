@@ -1,10 +1,12 @@
 //! Type inference pass - infers types for expressions and builds the type table.
 
 use crate::ast::expression::{BinaryOperator, FunctionName, Literal, UnaryOperator};
+use crate::ast::literal::LiteralValue;
 use crate::ast::program::{Program, Statement};
 use crate::ast::query::{LinearQuery, PrimitiveQueryStatement, Query};
 use crate::diag::Diag;
 use crate::ir::type_table::Type;
+use crate::ir::symbol_table::ScopeId;
 use crate::ir::{SymbolTable, TypeTable};
 use crate::semantic::schema_catalog::TypeRef;
 
@@ -23,19 +25,42 @@ use crate::semantic::schema_catalog::TypeRef;
 pub(super) fn run_type_inference(
     validator: &super::SemanticValidator,
     program: &Program,
-    _symbol_table: &SymbolTable,
+    symbol_table: &SymbolTable,
+    scope_metadata: &super::ScopeMetadata,
     _diagnostics: &mut Vec<Diag>,
 ) -> TypeTable {
     let mut type_table = TypeTable::new();
+    let scopes = StatementScopes {
+        symbol_table,
+        scope_metadata,
+    };
 
-    // Walk all statements and infer types for expressions
+    // Walk all statements and infer types for expressions, numbering them as
+    // scope analysis does
+    let mut next_statement_id = 0usize;
     for statement in &program.statements {
         match statement {
             Statement::Query(query_stmt) => {
-                infer_query_types(validator, &query_stmt.query, &mut type_table);
+                let statement_id = next_statement_id;
+                next_statement_id += 1;
+                infer_query_types(
+                    validator,
+                    &query_stmt.query,
+                    &scopes,
+                    statement_id,
+                    &mut next_statement_id,
+                    &mut type_table,
+                );
             }
             Statement::Mutation(mutation_stmt) => {
-                infer_mutation_types(validator, &mutation_stmt.statement, &mut type_table);
+                let statement_id = next_statement_id;
+                next_statement_id += 1;
+                infer_mutation_types(
+                    validator,
+                    &mutation_stmt.statement,
+                    scopes.variables(statement_id),
+                    &mut type_table,
+                );
             }
             _ => {}
         }
@@ -44,22 +69,91 @@ pub(super) fn run_type_inference(
     type_table
 }
 
+/// The variables visible in an expression, for typing references to them.
+#[derive(Clone, Copy)]
+pub(super) struct VariableScope<'a> {
+    symbol_table: &'a SymbolTable,
+    scope: ScopeId,
+}
+
+impl<'a> VariableScope<'a> {
+    pub(super) fn new(symbol_table: &'a SymbolTable, scope: ScopeId) -> Self {
+        Self {
+            symbol_table,
+            scope,
+        }
+    }
+
+    /// Returns the type declared for the variable a reference resolves to.
+    fn declared_type(self, name: &str) -> Option<&'a Type> {
+        self.symbol_table
+            .lookup_from(self.scope, name)?
+            .declared_type
+            .as_ref()
+    }
+}
+
+/// Root scopes of the statements, as recorded by scope analysis.
+struct StatementScopes<'a> {
+    symbol_table: &'a SymbolTable,
+    scope_metadata: &'a super::ScopeMetadata,
+}
+
+impl<'a> StatementScopes<'a> {
+    fn variables(&self, statement_id: usize) -> VariableScope<'a> {
+        let scope = super::variable_validation::statement_scope_id(
+            self.symbol_table,
+            self.scope_metadata,
+            statement_id,
+        );
+        VariableScope::new(self.symbol_table, scope)
+    }
+}
+
 /// Infers types in a query.
 fn infer_query_types(
     validator: &super::SemanticValidator,
     query: &Query,
+    scopes: &StatementScopes<'_>,
+    statement_id: usize,
+    next_statement_id: &mut usize,
     type_table: &mut TypeTable,
 ) {
     match query {
         Query::Linear(linear_query) => {
-            infer_linear_query_types(validator, linear_query, type_table);
+            let variables = scopes.variables(statement_id);
+            infer_linear_query_types(validator, linear_query, variables, type_table);
         }
         Query::Composite(composite) => {
-            infer_query_types(validator, &composite.left, type_table);
-            infer_query_types(validator, &composite.right, type_table);
+            infer_query_types(
+                validator,
+                &composite.left,
+                scopes,
+                statement_id,
+                next_statement_id,
+                type_table,
+            );
+
+            let right_statement_id = *next_statement_id;
+            *next_statement_id += 1;
+            infer_query_types(
+                validator,
+                &composite.right,
+                scopes,
+                right_statement_id,
+                next_statement_id,
+                type_table,
+            );
         }
         Query::Parenthesized(query, _) => {
-            infer_query_types(validator, query, type_table);
+            infer_query_types(
+                validator,
+                query,
+                scopes,
+                statement_id,
+                next_statement_id,
+                type_table,
+            );
         }
     }
 }
@@ -68,6 +162,7 @@ fn infer_query_types(
 fn infer_linear_query_types(
     validator: &super::SemanticValidator,
     linear_query: &LinearQuery,
+    variables: VariableScope<'_>,
     type_table: &mut TypeTable,
 ) {
     let primitive_statements = &linear_query.primitive_statements;
@@ -82,23 +177,23 @@ fn infer_linear_query_types(
             PrimitiveQueryStatement::Let(let_stmt) => {
                 // Infer types of LET variable definitions
                 for binding in &let_stmt.bindings {
-                    infer_expression_type(validator, &binding.value, type_table);
+                    infer_expression_type(validator, variables, &binding.value, type_table);
                 }
             }
             PrimitiveQueryStatement::For(for_stmt) => {
                 // Infer type of FOR collection expression
-                infer_expression_type(validator, &for_stmt.item.collection, type_table);
+                infer_expression_type(validator, variables, &for_stmt.item.collection, type_table);
             }
             PrimitiveQueryStatement::Filter(filter) => {
                 // Infer type of filter condition (should be boolean)
-                infer_expression_type(validator, &filter.condition, type_table);
+                infer_expression_type(validator, variables, &filter.condition, type_table);
             }
             PrimitiveQueryStatement::Select(select) => {
                 // Infer types of select items
                 match &select.select_items {
                     crate::ast::query::SelectItemList::Items { items } => {
                         for item in items {
-                            infer_expression_type(validator, &item.expression, type_table);
+                            infer_expression_type(validator, variables, &item.expression, type_table);
                         }
                     }
                     crate::ast::query::SelectItemList::Star => {
@@ -109,12 +204,23 @@ fn infer_linear_query_types(
             _ => {}
         }
     }
+
+    // Infer types of RETURN items
+    if let Some(result) = &linear_query.result_statement
+        && let crate::ast::query::PrimitiveResultStatement::Return(ret) = result.as_ref()
+        && let crate::ast::query::ReturnItemList::Items { items } = &ret.items
+    {
+        for item in items {
+            infer_expression_type(validator, variables, &item.expression, type_table);
+        }
+    }
 }
 
 /// Infers types in a mutation statement.
 fn infer_mutation_types(
     validator: &super::SemanticValidator,
     mutation: &crate::ast::mutation::LinearDataModifyingStatement,
+    variables: VariableScope<'_>,
     type_table: &mut TypeTable,
 ) {
     
@@ -127,10 +233,10 @@ fn infer_mutation_types(
         match stmt {
             SimpleDataAccessingStatement::Query(query_stmt) => {
                 // Infer types in the query part
-                infer_primitive_query_statement_types(validator, query_stmt, type_table);
+                infer_primitive_query_statement_types(validator, query_stmt, variables, type_table);
             }
             SimpleDataAccessingStatement::Modifying(modifying) => {
-                infer_modifying_statement_types(validator, modifying, type_table);
+                infer_modifying_statement_types(validator, modifying, variables, type_table);
             }
         }
     }
@@ -140,6 +246,7 @@ fn infer_mutation_types(
 fn infer_primitive_query_statement_types(
     validator: &super::SemanticValidator,
     stmt: &PrimitiveQueryStatement,
+    variables: VariableScope<'_>,
     type_table: &mut TypeTable,
 ) {
     match stmt {
@@ -148,19 +255,19 @@ fn infer_primitive_query_statement_types(
         }
         PrimitiveQueryStatement::Let(let_stmt) => {
             for binding in &let_stmt.bindings {
-                infer_expression_type(validator, &binding.value, type_table);
+                infer_expression_type(validator, variables, &binding.value, type_table);
             }
         }
         PrimitiveQueryStatement::For(for_stmt) => {
-            infer_expression_type(validator, &for_stmt.item.collection, type_table);
+            infer_expression_type(validator, variables, &for_stmt.item.collection, type_table);
         }
         PrimitiveQueryStatement::Filter(filter) => {
-            infer_expression_type(validator, &filter.condition, type_table);
+            infer_expression_type(validator, variables, &filter.condition, type_table);
         }
         PrimitiveQueryStatement::Select(select) => match &select.select_items {
             crate::ast::query::SelectItemList::Items { items } => {
                 for item in items {
-                    infer_expression_type(validator, &item.expression, type_table);
+                    infer_expression_type(validator, variables, &item.expression, type_table);
                 }
             }
             crate::ast::query::SelectItemList::Star => {}
@@ -173,6 +280,7 @@ fn infer_primitive_query_statement_types(
 fn infer_modifying_statement_types(
     validator: &super::SemanticValidator,
     stmt: &crate::ast::mutation::SimpleDataModifyingStatement,
+    variables: VariableScope<'_>,
     type_table: &mut TypeTable,
 ) {
     use crate::ast::mutation::{PrimitiveDataModifyingStatement, SimpleDataModifyingStatement};
@@ -207,7 +315,7 @@ fn infer_modifying_statement_types(
 
                         if let Some(properties) = properties_opt {
                             for pair in &properties.properties {
-                                infer_expression_type(validator, &pair.value, type_table);
+                                infer_expression_type(validator, variables, &pair.value, type_table);
                             }
                         }
                     }
@@ -220,11 +328,11 @@ fn infer_modifying_statement_types(
 
                     match item {
                         SetItem::Property(prop) => {
-                            infer_expression_type(validator, &prop.value, type_table);
+                            infer_expression_type(validator, variables, &prop.value, type_table);
                         }
                         SetItem::AllProperties(all_props) => {
                             for pair in &all_props.properties.properties {
-                                infer_expression_type(validator, &pair.value, type_table);
+                                infer_expression_type(validator, variables, &pair.value, type_table);
                             }
                         }
                         SetItem::Label(_) => {
@@ -239,7 +347,7 @@ fn infer_modifying_statement_types(
             PrimitiveDataModifyingStatement::Delete(delete_stmt) => {
                 // Infer types in DELETE expressions
                 for item in &delete_stmt.items.items {
-                    infer_expression_type(validator, &item.expression, type_table);
+                    infer_expression_type(validator, variables, &item.expression, type_table);
                 }
             }
         },
@@ -261,8 +369,9 @@ fn infer_modifying_statement_types(
 /// - Query property types instead of defaulting to Type::Any
 /// - Query callable return types for functions
 /// - Respect inference policy for fallback behavior
-fn infer_expression_type(
+pub(super) fn infer_expression_type(
     validator: &super::SemanticValidator,
+    variables: VariableScope<'_>,
     expr: &crate::ast::expression::Expression,
     type_table: &mut TypeTable,
 ) -> Type {
@@ -274,10 +383,9 @@ fn infer_expression_type(
             Literal::Integer(_) => Type::Int,
             Literal::Float(_) => Type::Float,
             Literal::String(_) => Type::String,
-            Literal::ByteString(_) => Type::String, // Treat as string type
+            Literal::ByteString(_) => Type::Bytes,
             Literal::Date(_) => Type::Date,
-            Literal::Time(_) => Type::Time,
-            Literal::Datetime(_) => Type::Timestamp,
+            Literal::Time(_) | Literal::Datetime(_) => time_literal_type(lit),
             Literal::Duration(_) => Type::Duration,
             Literal::List(exprs) => {
                 // Infer element types recursively and find common type
@@ -286,7 +394,7 @@ fn infer_expression_type(
                 } else {
                     let elem_types: Vec<Type> = exprs
                         .iter()
-                        .map(|e| infer_expression_type(validator, e, type_table))
+                        .map(|e| infer_expression_type(validator, variables, e, type_table))
                         .collect();
 
                     // Find common type
@@ -294,15 +402,21 @@ fn infer_expression_type(
                     Type::List(Box::new(common_type))
                 }
             }
-            Literal::Record(_) => {
-                // For now, use Record with empty fields
-                Type::Record(vec![])
+            Literal::Record(fields) => {
+                let field_types: Vec<(String, Type)> = fields
+                    .iter()
+                    .map(|field| {
+                        let ty = infer_expression_type(validator, variables, &field.value, type_table);
+                        (field.name.to_string(), ty)
+                    })
+                    .collect();
+                Type::Record(field_types)
             }
         },
 
         // Unary operations
         crate::ast::expression::Expression::Unary(op, operand, _) => {
-            let operand_type = infer_expression_type(validator, operand, type_table);
+            let operand_type = infer_expression_type(validator, variables, operand, type_table);
             match op {
                 UnaryOperator::Plus | UnaryOperator::Minus => {
                     // Preserve the numeric type: +5 is Int, +5.0 is Float
@@ -318,8 +432,8 @@ fn infer_expression_type(
 
         // Binary operations
         crate::ast::expression::Expression::Binary(op, left, right, _) => {
-            let left_type = infer_expression_type(validator, left, type_table);
-            let right_type = infer_expression_type(validator, right, type_table);
+            let left_type = infer_expression_type(validator, variables, left, type_table);
+            let right_type = infer_expression_type(validator, variables, right, type_table);
 
             match op {
                 BinaryOperator::Add
@@ -327,11 +441,15 @@ fn infer_expression_type(
                 | BinaryOperator::Multiply
                 | BinaryOperator::Divide
                 | BinaryOperator::Modulo => {
-                    // Preserve Int if both operands are Int, otherwise Float
-                    match (&left_type, &right_type) {
-                        (Type::Int, Type::Int) if *op != BinaryOperator::Divide => Type::Int,
-                        (Type::Int, Type::Float) | (Type::Float, Type::Int) | (Type::Float, Type::Float) => Type::Float,
-                        _ if left_type.is_numeric() && right_type.is_numeric() => Type::Float,
+                    // Division of integers yields Float; otherwise the result is the
+                    // numeric supertype of the operands (Int stays Int, DECIMAL widens).
+                    let both_integral = matches!(
+                        (left_type.without_not_null(), right_type.without_not_null()),
+                        (Type::Int | Type::SignedInt(_) | Type::UnsignedInt(_), Type::Int | Type::SignedInt(_) | Type::UnsignedInt(_))
+                    );
+                    match Type::supertype(left_type.without_not_null(), right_type.without_not_null()) {
+                        Some(_) if *op == BinaryOperator::Divide && both_integral => Type::Float,
+                        Some(result) if result.is_numeric() => result,
                         _ => Type::Float, // Fallback for Any or unknown types
                     }
                 }
@@ -341,26 +459,26 @@ fn infer_expression_type(
 
         // Comparison operations always produce boolean
         crate::ast::expression::Expression::Comparison(_, left, right, _) => {
-            infer_expression_type(validator, left, type_table);
-            infer_expression_type(validator, right, type_table);
+            infer_expression_type(validator, variables, left, type_table);
+            infer_expression_type(validator, variables, right, type_table);
             Type::Boolean
         }
 
         // Logical operations produce boolean
         crate::ast::expression::Expression::Logical(_, left, right, _) => {
-            infer_expression_type(validator, left, type_table);
-            infer_expression_type(validator, right, type_table);
+            infer_expression_type(validator, variables, left, type_table);
+            infer_expression_type(validator, variables, right, type_table);
             Type::Boolean
         }
 
         // Parenthesized expression has same type as inner expression
         crate::ast::expression::Expression::Parenthesized(inner, _) => {
-            return infer_expression_type(validator, inner, type_table);
+            return infer_expression_type(validator, variables, inner, type_table);
         }
 
         // Property reference - query from metadata provider
         crate::ast::expression::Expression::PropertyReference(object, prop_name, _) => {
-            let object_type = infer_expression_type(validator, object, type_table);
+            let object_type = infer_expression_type(validator, variables, object, type_table);
 
             // Try to query property type from metadata provider
             if let Some(metadata) = validator.metadata_provider {
@@ -375,10 +493,13 @@ fn infer_expression_type(
             fallback_type(validator)
         }
 
-        // Variable reference - type should be looked up in symbol table
-        crate::ast::expression::Expression::VariableReference(_, _) => {
-            // TODO: Lookup in symbol table once integrated
-            fallback_type(validator)
+        // Variable reference - typed by the symbol it resolves to, when the
+        // declaration carries a type
+        crate::ast::expression::Expression::VariableReference(name, _) => {
+            match variables.declared_type(name) {
+                Some(ty) => ty.clone(),
+                None => fallback_type(validator),
+            }
         }
 
         // Parameter reference
@@ -390,16 +511,35 @@ fn infer_expression_type(
         // Function calls - query from metadata provider
         crate::ast::expression::Expression::FunctionCall(func_call) => {
             // Infer argument types
-            for arg in &func_call.arguments {
-                infer_expression_type(validator, arg, type_table);
-            }
+            let arg_types: Vec<Type> = func_call
+                .arguments
+                .iter()
+                .map(|arg| infer_expression_type(validator, variables, arg, type_table))
+                .collect();
+            let name_str = function_name_to_string(&func_call.name);
 
             // Try to query return type from metadata provider
-            if let Some(metadata) = validator.metadata_provider {
-                let name_str = function_name_to_string(&func_call.name);
-                if let Some(return_type) = metadata.get_callable_return_type_metadata(name_str) {
-                    return map_value_type_to_type(&return_type);
-                }
+            if let Some(metadata) = validator.metadata_provider
+                && let Some(return_type) = metadata.get_callable_return_type_metadata(name_str)
+            {
+                return map_value_type_to_type(&return_type);
+            }
+
+            // Instantiate the return type of the best matching overload
+            use crate::semantic::callable::{CallableKind, resolve_builtin_signatures, resolve_overload};
+            let signatures = resolve_builtin_signatures(name_str, CallableKind::Function)
+                .or_else(|| {
+                    validator
+                        .metadata_provider
+                        .and_then(|m| m.lookup_callable(name_str))
+                        .map(|sig| vec![sig])
+                });
+            if let Some(signatures) = signatures
+                && let Ok(resolved) = resolve_overload(&signatures, &arg_types)
+                && let Some(return_type) = resolved.return_type
+                && return_type != Type::Any
+            {
+                return return_type;
             }
 
             // Fallback based on policy
@@ -415,29 +555,29 @@ fn infer_expression_type(
                 crate::ast::expression::CaseExpression::Searched(searched) => {
                     // Collect types from all THEN clauses
                     for when_clause in &searched.when_clauses {
-                        let then_type = infer_expression_type(validator, &when_clause.then_result, type_table);
+                        let then_type = infer_expression_type(validator, variables, &when_clause.then_result, type_table);
                         result_types.push(then_type);
                     }
 
                     // ELSE clause if present
                     if let Some(else_expr) = &searched.else_clause {
-                        let else_type = infer_expression_type(validator, else_expr, type_table);
+                        let else_type = infer_expression_type(validator, variables, else_expr, type_table);
                         result_types.push(else_type);
                     }
                 }
                 crate::ast::expression::CaseExpression::Simple(simple) => {
                     // Infer operand type
-                    infer_expression_type(validator, &simple.operand, type_table);
+                    infer_expression_type(validator, variables, &simple.operand, type_table);
 
                     // Collect types from all THEN clauses
                     for when_clause in &simple.when_clauses {
-                        let then_type = infer_expression_type(validator, &when_clause.then_result, type_table);
+                        let then_type = infer_expression_type(validator, variables, &when_clause.then_result, type_table);
                         result_types.push(then_type);
                     }
 
                     // ELSE clause if present
                     if let Some(else_expr) = &simple.else_clause {
-                        let else_type = infer_expression_type(validator, else_expr, type_table);
+                        let else_type = infer_expression_type(validator, variables, else_expr, type_table);
                         result_types.push(else_type);
                     }
                 }
@@ -453,7 +593,7 @@ fn infer_expression_type(
 
         // Cast expression - type is the target type
         crate::ast::expression::Expression::Cast(cast) => {
-            infer_expression_type(validator, &cast.operand, type_table);
+            infer_expression_type(validator, variables, &cast.operand, type_table);
             // Map ValueType to Type
             map_value_type_to_type(&cast.target_type)
         }
@@ -464,14 +604,14 @@ fn infer_expression_type(
             match &**agg {
                 AggregateFunction::CountStar { .. } => Type::Int,
                 AggregateFunction::GeneralSetFunction(gsf) => {
-                    let expr_type = infer_expression_type(validator, &gsf.expression, type_table);
+                    let expr_type = infer_expression_type(validator, variables, &gsf.expression, type_table);
                     match gsf.function_type {
                         GeneralSetFunctionType::Count => Type::Int,
                         GeneralSetFunctionType::Avg => Type::Float,
                         GeneralSetFunctionType::Sum => {
-                            // SUM preserves type: SUM(int) = Int, SUM(float) = Float
-                            if expr_type == Type::Int {
-                                Type::Int
+                            // SUM preserves exact types: SUM(int) = Int, SUM(decimal) = Decimal
+                            if expr_type.is_exact_numeric() {
+                                expr_type.without_not_null().clone()
                             } else {
                                 Type::Float
                             }
@@ -490,7 +630,7 @@ fn infer_expression_type(
 
         // Type annotation - use the annotated type
         crate::ast::expression::Expression::TypeAnnotation(inner, annotation, _) => {
-            infer_expression_type(validator, inner, type_table);
+            infer_expression_type(validator, variables, inner, type_table);
             map_value_type_to_type(&annotation.type_ref)
         }
 
@@ -501,7 +641,7 @@ fn infer_expression_type(
             } else {
                 let elem_types: Vec<Type> = elements
                     .iter()
-                    .map(|e| infer_expression_type(validator, e, type_table))
+                    .map(|e| infer_expression_type(validator, variables, e, type_table))
                     .collect();
                 let common_type = infer_common_type(&elem_types);
                 Type::List(Box::new(common_type))
@@ -513,7 +653,7 @@ fn infer_expression_type(
             let field_types: Vec<(String, Type)> = fields
                 .iter()
                 .map(|field| {
-                    let ty = infer_expression_type(validator, &field.value, type_table);
+                    let ty = infer_expression_type(validator, variables, &field.value, type_table);
                     (field.name.to_string(), ty)
                 })
                 .collect();
@@ -523,7 +663,7 @@ fn infer_expression_type(
         // Path constructor
         crate::ast::expression::Expression::PathConstructor(elements, _) => {
             for elem in elements {
-                infer_expression_type(validator, elem, type_table);
+                infer_expression_type(validator, variables, elem, type_table);
             }
             Type::Path
         }
@@ -536,12 +676,12 @@ fn infer_expression_type(
 
         // Graph expressions
         crate::ast::expression::Expression::GraphExpression(inner, _) => {
-            infer_expression_type(validator, inner, type_table)
+            infer_expression_type(validator, variables, inner, type_table)
         }
 
         // Binding table expressions
         crate::ast::expression::Expression::BindingTableExpression(inner, _) => {
-            infer_expression_type(validator, inner, type_table)
+            infer_expression_type(validator, variables, inner, type_table)
         }
 
         // Subquery expressions
@@ -577,88 +717,33 @@ fn type_to_type_ref(ty: &Type) -> Option<TypeRef> {
 
 /// Infers a common type from a list of types.
 ///
-/// Returns the most specific type that all input types can be converted to:
-/// - If all types are the same, return that type
-/// - If all types are numeric, return Float (widest numeric type)
-/// - If types include Any, return Any
-/// - Otherwise, return a Union type or Any
+/// Returns the common supertype of the inputs (see [`Type::supertype`]), so
+/// mixed numerics widen, NULL is absorbed and closed element types merge their
+/// labels. Types without a common supertype produce a Union.
 fn infer_common_type(types: &[Type]) -> Type {
     if types.is_empty() {
         return Type::Any;
     }
 
-    // If all types are the same, return that type
-    let first = &types[0];
-    if types.iter().all(|t| t == first) {
-        return first.clone();
-    }
-
-    // If any type is Any, return Any
-    if types.iter().any(|t| matches!(t, Type::Any)) {
-        return Type::Any;
-    }
-
-    // If all types are numeric, return Float (widest)
-    if types.iter().all(|t| t.is_numeric()) {
-        return Type::Float;
-    }
-
-    // If all types are compatible, return the first one
-    if types.iter().all(|t| first.is_compatible_with(t)) {
-        return first.clone();
-    }
-
-    // Otherwise, return a union type (or Any as fallback)
-    Type::Union(types.to_vec())
+    Type::common_supertype(types).unwrap_or_else(|| Type::Union(types.to_vec()))
 }
 
 /// Maps a ValueType from the AST to a Type.
 fn map_value_type_to_type(value_type: &crate::ast::types::ValueType) -> Type {
-    use crate::ast::types::{PredefinedType, ValueType};
-
-    match value_type {
-        ValueType::Predefined(ptype, _) => match ptype {
-            PredefinedType::Boolean(_) => Type::Boolean,
-            PredefinedType::CharacterString(_) => Type::String,
-            PredefinedType::ByteString(_) => Type::String,
-            PredefinedType::Numeric(ntype) => {
-                use crate::ast::types::NumericType;
-                match ntype {
-                    NumericType::Exact(_) => Type::Int,
-                    NumericType::Approximate(_) => Type::Float,
-                }
-            }
-            PredefinedType::Temporal(ttype) => {
-                use crate::ast::types::{TemporalInstantType, TemporalType};
-                match ttype {
-                    TemporalType::Instant(itype) => match itype {
-                        TemporalInstantType::Date => Type::Date,
-                        TemporalInstantType::LocalTime | TemporalInstantType::ZonedTime => {
-                            Type::Time
-                        }
-                        TemporalInstantType::LocalDatetime | TemporalInstantType::ZonedDatetime => {
-                            Type::Timestamp
-                        }
-                    },
-                    TemporalType::Duration(_) => Type::Duration,
-                }
-            }
-            PredefinedType::ReferenceValue(rtype) => {
-                use crate::ast::types::ReferenceValueType;
-                match rtype {
-                    ReferenceValueType::Node(_) => Type::Node(None),
-                    ReferenceValueType::Edge(_) => Type::Edge(None),
-                    ReferenceValueType::Graph(_) => Type::Any, // No direct graph type
-                    ReferenceValueType::BindingTable(_) => Type::Any,
-                }
-            }
-            PredefinedType::Immaterial(_) => Type::Any,
-        },
-        ValueType::Path(_) => Type::Path,
-        ValueType::List(list_type) => {
-            Type::List(Box::new(map_value_type_to_type(&list_type.element_type)))
-        }
-        ValueType::Record(_) => Type::Any,
+    Type::from_value_type(value_type)
+}
+
+/// Types a TIME or DATETIME literal as zoned if its decoded value has a UTC offset.
+///
+/// Malformed literals are reported by literal validation and typed as unzoned.
+fn time_literal_type(lit: &Literal) -> Type {
+    match lit.value() {
+        Ok(LiteralValue::Time(time)) if time.offset.is_some() => Type::ZonedTime,
+        Ok(LiteralValue::Datetime(datetime)) if datetime.time.offset.is_some() => {
+            Type::ZonedDatetime
+        }
+        _ if matches!(lit, Literal::Time(_)) => Type::Time,
+        _ => Type::Timestamp,
     }
 }
 
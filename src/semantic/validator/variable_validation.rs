@@ -6,6 +6,7 @@
 //! - ISO GQL aggregation rules (WHERE, HAVING, RETURN)
 //! - Nested aggregation detection
 
+use crate::ast::expression::Expression;
 use crate::ast::program::{Program, Statement};
 use crate::ast::query::{MatchStatement, PrimitiveQueryStatement, Query};
 use crate::diag::Diag;
@@ -56,7 +57,7 @@ pub(super) fn run_variable_validation(
     }
 }
 
-fn statement_scope_id(
+pub(super) fn statement_scope_id(
     symbol_table: &SymbolTable,
     scope_metadata: &super::ScopeMetadata,
     statement_id: usize,
@@ -933,6 +934,43 @@ fn validate_result_statement_variables(
             }
         }
 
+        // Sort keys may name a return item alias in addition to the
+        // variables in scope.
+        if let Some(order_by_page) = &return_stmt.order_by_and_page {
+            let aliases: Vec<&str> = match &return_stmt.items {
+                ReturnItemList::Star => Vec::new(),
+                ReturnItemList::Items { items } => items
+                    .iter()
+                    .filter_map(|item| item.alias.as_deref())
+                    .collect(),
+            };
+            let sort_keys = order_by_page
+                .order_by
+                .iter()
+                .flat_map(|order_by| &order_by.sort_specifications)
+                .map(|sort_spec| &sort_spec.key)
+                .filter(|key| {
+                    !matches!(
+                        key,
+                        Expression::VariableReference(name, _) if aliases.contains(&name.as_str())
+                    )
+                });
+            let counts = order_by_page
+                .offset
+                .iter()
+                .map(|offset| &offset.count)
+                .chain(order_by_page.limit.iter().map(|limit| &limit.count));
+            for expr in sort_keys.chain(counts) {
+                validate_expression_variables(
+                    expr,
+                    symbol_table,
+                    scope_metadata,
+                    statement_id,
+                    diagnostics,
+                );
+            }
+        }
+
         // Validate aggregation rules for RETURN (ISO GQL compliance)
         validate_return_aggregation(validator, return_stmt, diagnostics);
     }
@@ -1748,10 +1786,10 @@ fn collect_non_aggregated_expressions_recursive<'a>(
                 }
             }
         }
-        Expression::VariableReference(_, _) | Expression::PropertyReference(_, _, _) => {
-            if !in_aggregate {
-                result.push(expr);
-            }
+        Expression::VariableReference(_, _) | Expression::PropertyReference(_, _, _)
+            if !in_aggregate =>
+        {
+            result.push(expr);
         }
         Expression::Binary(_, left, right, _) => {
             collect_non_aggregated_expressions_recursive(left, in_aggregate, result);
@@ -0,0 +1,189 @@
+//! Cardinality estimation tests
+//!
+//! Queries are estimated against the example statistics: 1000 `Person`
+//! nodes with 5000 `KNOWS` edges among them, and 50 `City` nodes with one
+//! `LIVES_IN` edge per person.
+
+use gql_parser::QueryInfo;
+use gql_parser::analysis::cardinality::{CardinalityEstimator, QueryEstimate};
+use gql_parser::parse;
+use gql_parser::semantic::statistics_provider::MockStatisticsProvider;
+
+fn estimate_with(statistics: &MockStatisticsProvider, source: &str) -> QueryEstimate {
+    let program = parse(source)
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    CardinalityEstimator::new(statistics)
+        .estimate_statement(&program.statements[0])
+        .expect("query statement")
+}
+
+fn estimate_of(source: &str) -> QueryEstimate {
+    estimate_with(&MockStatisticsProvider::example(), source)
+}
+
+fn rows(source: &str) -> f64 {
+    estimate_of(source).rows
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6 * expected.abs().max(1.0),
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn test_node_scans_use_label_counts_and_property_statistics() {
+    assert_close(rows("MATCH (p:Person) RETURN p"), 1000.0);
+    assert_close(rows("MATCH (n) RETURN n"), 1050.0);
+    assert_close(
+        rows("MATCH (n:Person|City) RETURN n"),
+        1050.0 - 1000.0 * 50.0 / 1050.0,
+    );
+    assert_close(rows("MATCH (p:Person {name: 'Ann'}) RETURN p"), 1.0);
+    assert_close(rows("MATCH (c:City {name: 'Paris'}) RETURN c"), 10.0);
+    assert_close(
+        rows("MATCH (c:City {name: 'Rome'}) RETURN c"),
+        50.0 * 0.8 / 49.0,
+    );
+
+    let estimate = estimate_of("MATCH (p:Person WHERE p.age >= 60) RETURN p");
+    let node = &estimate.clauses[0].patterns[0].nodes[0];
+    assert_eq!(node.variable.as_deref(), Some("p"));
+    assert_close(node.label_rows, 1000.0);
+    assert_close(node.selectivity, 0.2 * 0.9);
+}
+
+#[test]
+fn test_expansions_use_edge_counts_per_endpoint_labels() {
+    let estimate = estimate_of("MATCH (a:Person)-[k:KNOWS]->(b:Person) RETURN b");
+    let expansion = &estimate.clauses[0].patterns[0].expansions[0];
+    assert_eq!(expansion.variable.as_deref(), Some("k"));
+    assert_close(expansion.fan_out, 5.0);
+    assert_close(expansion.reverse_fan_out, 5.0);
+    assert_close(estimate.rows, 5000.0);
+
+    let reversed = estimate_of("MATCH (c:City)<-[:LIVES_IN]-(p:Person) RETURN p");
+    let expansion = &reversed.clauses[0].patterns[0].expansions[0];
+    assert_close(expansion.fan_out, 20.0);
+    assert_close(expansion.reverse_fan_out, 1.0);
+    assert_close(reversed.rows, 1000.0);
+
+    assert_close(rows("MATCH (c:City)-[:LIVES_IN]->(p:Person) RETURN p"), 0.0);
+    assert_close(
+        rows("MATCH (a:Person)-[:KNOWS]-(b:Person) RETURN b"),
+        10000.0,
+    );
+    assert_close(
+        rows("MATCH (a:Person {name: 'Ann'})-[:KNOWS]->(b)-[:LIVES_IN]->(c) RETURN c"),
+        5.0 * 1000.0 / 1050.0,
+    );
+}
+
+#[test]
+fn test_where_predicates_are_estimated_per_conjunct() {
+    let estimate = estimate_of("MATCH (p:Person) WHERE p.age < 25 AND p.name = 'Ann' RETURN p");
+    let predicates = &estimate.clauses[0].patterns[0].predicates;
+    assert_eq!(predicates.len(), 2);
+    assert!(predicates[0].info.variable_references.contains("p"));
+    assert_eq!(predicates[0].info.property_references[0].property, "age");
+    assert_close(predicates[0].selectivity, 0.3 * 0.9);
+    assert_close(predicates[1].selectivity, 0.001);
+    assert_close(estimate.rows, 1000.0 * 0.27 * 0.001);
+
+    assert_close(rows("MATCH (p:Person) WHERE 25 > p.age RETURN p"), 270.0);
+    assert_close(rows("MATCH (p:Person) WHERE p.age IS NULL RETURN p"), 100.0);
+    assert_close(
+        rows("MATCH (p:Person) WHERE p.age IS NULL OR p.age >= 60 RETURN p"),
+        1000.0 * (0.1 + 0.18 - 0.1 * 0.18),
+    );
+    assert_close(rows("MATCH (p:Person) FILTER p.name = $name RETURN p"), 1.0);
+    // Unknown properties fall back to default selectivities.
+    assert_close(rows("MATCH (p:Person) WHERE p.email = 'x' RETURN p"), 100.0);
+}
+
+#[test]
+fn test_quantified_expansions_sum_over_repetitions() {
+    assert_close(
+        rows("MATCH (a:Person {name: 'Ann'})-[:KNOWS]->{1,2}(b) RETURN b"),
+        5.0 + 25.0,
+    );
+    assert_close(
+        rows("MATCH (a:Person {name: 'Ann'}) ((x)-[:KNOWS]->(y)){2} (b) RETURN b"),
+        25.0,
+    );
+    assert_close(
+        rows(
+            "MATCH p = ANY SHORTEST (a:Person {name: 'Ann'})-[:KNOWS]->+(b:Person {name: 'Bob'}) \
+             RETURN p",
+        ),
+        1.0,
+    );
+}
+
+#[test]
+fn test_clauses_join_and_aggregate() {
+    let estimate = estimate_of(
+        "MATCH (a:Person) MATCH (a)-[:LIVES_IN]->(c:City) \
+         RETURN c.name AS city, count(*) AS residents",
+    );
+    let rows: Vec<f64> = estimate.clauses.iter().map(|clause| clause.rows).collect();
+    assert_eq!(rows.len(), 3);
+    assert_close(rows[0], 1000.0);
+    assert_close(rows[1], 1000.0);
+    assert_close(rows[2], 50.0);
+
+    assert_close(self::rows("MATCH (p:Person) RETURN count(*)"), 1.0);
+    assert_close(
+        self::rows("MATCH (p:Person) RETURN p ORDER BY p.age LIMIT 10"),
+        10.0,
+    );
+    assert_close(self::rows("MATCH (p:Person) RETURN DISTINCT p.age"), 80.0);
+    assert_close(self::rows("FOR x IN [1, 2, 3] RETURN x"), 3.0);
+    assert_close(
+        self::rows("MATCH (p:Person) OPTIONAL MATCH (p)-[:LIVES_IN]->(c:City) RETURN p, c"),
+        1000.0,
+    );
+    assert_close(
+        self::rows("MATCH (p:Person) RETURN p UNION ALL MATCH (c:City) RETURN c AS p"),
+        1050.0,
+    );
+}
+
+#[test]
+fn test_clause_ids_match_query_info() {
+    let source = "MATCH (a:Person) FILTER a.age > 30 RETURN a \
+                  UNION MATCH (c:City) LET n = c.name RETURN c AS a";
+    let program = parse(source).ast.unwrap();
+    let info = QueryInfo::from_ast(&program.statements[0]);
+    let estimated: Vec<_> = estimate_of(source)
+        .clauses
+        .into_iter()
+        .map(|clause| (clause.clause_id, clause.kind))
+        .collect();
+    let analyzed: Vec<_> = info
+        .clause_sequence
+        .into_iter()
+        .map(|clause| (clause.clause_id, clause.kind))
+        .collect();
+    assert_eq!(estimated, analyzed);
+}
+
+#[test]
+fn test_missing_statistics_use_defaults() {
+    let mut statistics = MockStatisticsProvider::new();
+    statistics.set_counts(100, 400);
+    assert_close(
+        estimate_with(&statistics, "MATCH (n:Robot) RETURN n").rows,
+        10.0,
+    );
+    assert_close(
+        estimate_with(&statistics, "MATCH (n)-[:OWNS]->(m) RETURN m").rows,
+        40.0,
+    );
+    assert_close(
+        estimate_with(&statistics, "MATCH (n)-[]->(m) WHERE n.x < m.y RETURN m").rows,
+        400.0 / 3.0,
+    );
+}
@@ -0,0 +1,231 @@
+//! openCypher emitter tests
+//!
+//! Validated programs are rendered as Cypher text for Neo4j-compatible
+//! stores; constructs Cypher cannot express are reported instead.
+
+use gql_parser::emit::EmitResult;
+use gql_parser::emit::cypher;
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn emit(source: &str) -> EmitResult {
+    let result = parse(source);
+    let program = result
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+    cypher::program(&program)
+}
+
+fn emit_errors(source: &str) -> Vec<String> {
+    emit(source)
+        .expect_err("program should not be expressible")
+        .into_iter()
+        .map(|diag| diag.message)
+        .collect()
+}
+
+#[test]
+fn test_match_return_renders_cypher() {
+    let cypher = emit(
+        "MATCH (a:Person|Robot {name: 'Ann'})-[e:KNOWS WHERE e.since > 2000]->(b) \
+         WHERE a.age > 30 OR b.age > 30 \
+         RETURN DISTINCT b.name AS friend ORDER BY friend DESC OFFSET 2 LIMIT 3",
+    )
+    .unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a:Person|Robot {name: 'Ann'})-[e:KNOWS]->(b)\n\
+         WHERE e.since > 2000 AND (a.age > 30 OR b.age > 30)\n\
+         RETURN DISTINCT b.name AS friend\n\
+         ORDER BY friend DESC\n\
+         SKIP 2\n\
+         LIMIT 3"
+    );
+}
+
+#[test]
+fn test_quantified_path_patterns_keep_element_conditions() {
+    let cypher = emit(
+        "MATCH DIFFERENT EDGES (a) ((x)-[r:ROAD WHERE r.km < 100]->(y)){1,3} (b:City) \
+         RETURN a.name",
+    )
+    .unwrap();
+    assert!(
+        cypher.starts_with("MATCH (a) ((x)-[r:ROAD WHERE r.km < 100]->(y)){1,3} (b:City)\n"),
+        "{cypher}"
+    );
+
+    let cypher = emit("MATCH DIFFERENT EDGES (a)-[e:A&B]->+(b) RETURN b.name").unwrap();
+    assert!(
+        cypher.starts_with("MATCH (a) (()-[e:A&B]->())+ (b)\n"),
+        "{cypher}"
+    );
+}
+
+#[test]
+fn test_let_filter_and_for_become_with_and_unwind() {
+    let cypher = emit(
+        "MATCH (a:Account) LET total = a.balance * 2 FILTER total > $min \
+         FOR tag IN a.tags RETURN a.id, tag ORDER BY total DESC LIMIT 5",
+    )
+    .unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a:Account)\n\
+         WITH *, a.balance * 2 AS total\n\
+         WITH * WHERE total > $min\n\
+         UNWIND a.tags AS tag\n\
+         RETURN a.id, tag\n\
+         ORDER BY total DESC\n\
+         LIMIT 5"
+    );
+}
+
+#[test]
+fn test_mutations_become_updating_clauses() {
+    let cypher = emit(
+        "MATCH (a:Person {id: $id}) \
+         INSERT (a)-[:OWNS {since: $year}]->(c:Car&Vehicle {model: 'T'}) \
+         SET a.updated = TRUE, a:Owner REMOVE a.tmp, a:Temp",
+    )
+    .unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a:Person {id: $id})\n\
+         CREATE (a)-[:OWNS {since: $year}]->(c:Car:Vehicle {model: 'T'})\n\
+         SET a.updated = TRUE, a:Owner\n\
+         REMOVE a.tmp, a:Temp"
+    );
+}
+
+#[test]
+fn test_delete_keeps_detach_and_statements_are_separated() {
+    let cypher =
+        emit("MATCH (a:Temp) DETACH DELETE a NEXT MATCH (b:Old) NODETACH DELETE b").unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a:Temp)\nDETACH DELETE a;\nMATCH (b:Old)\nDELETE b"
+    );
+}
+
+#[test]
+fn test_next_continues_with_the_returned_columns() {
+    let cypher = emit("MATCH (n) RETURN n.x AS x NEXT FILTER x > 1 RETURN x").unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (n)\nWITH n.x AS x\nWITH * WHERE x > 1\nRETURN x"
+    );
+
+    let cypher = emit(
+        "MATCH (a:Person) RETURN a, count(*) AS n ORDER BY n DESC LIMIT 3 \
+         NEXT MATCH (a)-[:OWNS]->(c) RETURN a.name, c.model",
+    )
+    .unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a:Person)\n\
+         WITH a, count(*) AS n\n\
+         ORDER BY n DESC\n\
+         LIMIT 3\n\
+         MATCH (a)-[:OWNS]->(c)\n\
+         RETURN a.name, c.model"
+    );
+
+    assert_eq!(
+        emit_errors("MATCH (n) RETURN n.x NEXT MATCH (m) RETURN m"),
+        vec!["Unnamed column before NEXT cannot be rendered as openCypher"]
+    );
+    assert_eq!(
+        emit_errors(
+            "MATCH (a) RETURN a UNION MATCH (b) RETURN b AS a NEXT FILTER a.x > 1 RETURN a"
+        ),
+        vec![
+            "Set operation before NEXT cannot be rendered as openCypher",
+            "FILTER before any variable is bound cannot be rendered as openCypher",
+        ]
+    );
+}
+
+#[test]
+fn test_reading_after_an_update_is_separated_by_with() {
+    let cypher = emit(
+        "MATCH (a) INSERT (a)-[:SEEN]->(:Visit) \
+         MATCH DIFFERENT EDGES (a)-[:SEEN]->(v)-[:LOCATED]->(p) RETURN count(v)",
+    )
+    .unwrap();
+    assert!(
+        cypher.contains("CREATE (a)-[:SEEN]->(:Visit)\nWITH *\nMATCH (a)-[:SEEN]->(v)"),
+        "{cypher}"
+    );
+}
+
+#[test]
+fn test_shortest_path_searches_use_cypher_functions() {
+    let cypher = emit("MATCH p = ANY SHORTEST (a)-[e:ROAD]->+(b) RETURN p").unwrap();
+    assert!(
+        cypher.starts_with("MATCH p = shortestPath((a)-[e:ROAD*1..]->(b))\n"),
+        "{cypher}"
+    );
+    let cypher = emit("MATCH p = ALL SHORTEST (a)-[e]->{1,4}(b) RETURN p").unwrap();
+    assert!(
+        cypher.starts_with("MATCH p = allShortestPaths((a)-[e*1..4]->(b))\n"),
+        "{cypher}"
+    );
+}
+
+#[test]
+fn test_repeatable_elements_becomes_a_plain_match_and_path_modes_are_reported() {
+    let cypher = emit("MATCH (a)-[e]->(b)-[f]->(c) RETURN c.name").unwrap();
+    assert_eq!(cypher, "MATCH (a)-[e]->(b)-[f]->(c)\nRETURN c.name");
+
+    assert_eq!(
+        emit_errors("MATCH TRAIL (a)-[e]->(b) RETURN b.name"),
+        vec!["Path mode cannot be rendered as openCypher"]
+    );
+    assert_eq!(
+        emit_errors("MATCH DIFFERENT EDGES ((a)-[e]->(b) |+| (a)<-[e]-(b)) RETURN a.name"),
+        vec!["Multiset alternation cannot be rendered as openCypher"]
+    );
+}
+
+#[test]
+fn test_set_operators_and_grouping() {
+    let cypher = emit("MATCH (a) RETURN a.x UNION ALL MATCH (b) RETURN b.x").unwrap();
+    assert_eq!(
+        cypher,
+        "MATCH (a)\nRETURN a.x\nUNION ALL\nMATCH (b)\nRETURN b.x"
+    );
+    assert_eq!(
+        emit_errors("MATCH (a) RETURN a.x EXCEPT MATCH (b) RETURN b.x"),
+        vec!["EXCEPT cannot be rendered as openCypher"]
+    );
+
+    let cypher = emit(
+        "MATCH (a:Person) MATCH (a)-[:OWNS]->(c) \
+         RETURN a.name AS name, count(c) AS cars GROUP BY name",
+    )
+    .unwrap();
+    assert!(
+        cypher.ends_with("RETURN a.name AS name, count(c) AS cars"),
+        "{cypher}"
+    );
+}
+
+#[test]
+fn test_functions_and_predicates_use_cypher_spellings() {
+    let cypher = emit(
+        "MATCH (a) RETURN upper(a.name), collect_list(a.x), char_length(a.name), \
+         a.s || 'x', a.flag IS TRUE, a IS LABELED Person|Robot, CAST(a.x AS INT), \
+         DATE '2020-01-01'",
+    )
+    .unwrap();
+    assert!(
+        cypher.ends_with(
+            "RETURN toUpper(a.name), collect(a.x), size(a.name), a.s + 'x', \
+             coalesce(a.flag, false), a:Person|Robot, toInteger(a.x), date('2020-01-01')"
+        ),
+        "{cypher}"
+    );
+}
@@ -0,0 +1,398 @@
+//! Reference interpreter tests
+//!
+//! Programs are validated and then executed against a small social graph:
+//! Ann knows Bob and Cat, Bob knows Cat and Cat knows Ann, and Ann lives in
+//! Paris.
+
+use gql_parser::interpreter::{
+    BindingTable, Interpreter, NodeId, PropertyGraph, RuntimeErrorKind, Value,
+};
+use gql_parser::ir::type_table::Type;
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn social() -> PropertyGraph {
+    let mut graph = PropertyGraph::new();
+    let person = |name: &str, age: Option<i64>| {
+        let mut properties = vec![("name", Value::from(name))];
+        properties.extend(age.map(|age| ("age", Value::from(age))));
+        properties
+    };
+    let ann = graph.add_node(["Person"], person("Ann", Some(30)));
+    let bob = graph.add_node(["Person"], person("Bob", Some(25)));
+    let cat = graph.add_node(["Person"], person("Cat", Some(35)));
+    graph.add_node(["Person"], person("Dan", None));
+    let paris = graph.add_node(["City"], [("name", Value::from("Paris"))]);
+
+    let since = |year: i64| [("since", Value::from(year))];
+    graph.add_edge(ann, bob, ["KNOWS"], since(2010));
+    graph.add_edge(bob, cat, ["KNOWS"], since(2015));
+    graph.add_edge(cat, ann, ["KNOWS"], since(2020));
+    graph.add_edge(ann, cat, ["KNOWS"], since(2012));
+    graph.add_edge(ann, paris, ["LIVES_IN"], Vec::<(&str, Value)>::new());
+    graph
+}
+
+fn execute(graph: &mut PropertyGraph, source: &str) -> Vec<BindingTable> {
+    try_execute(graph, source).unwrap_or_else(|error| panic!("{source}: {error}"))
+}
+
+fn try_execute(
+    graph: &mut PropertyGraph,
+    source: &str,
+) -> Result<Vec<BindingTable>, gql_parser::interpreter::RuntimeError> {
+    let program = parse(source)
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    let ir = SemanticValidator::new()
+        .validate(&program)
+        .ir
+        .unwrap_or_else(|| panic!("failed to validate: {source}"));
+    Interpreter::new(graph).execute(&ir)
+}
+
+/// Runs a single query and returns its rows.
+fn rows(source: &str) -> Vec<Vec<Value>> {
+    let mut graph = social();
+    execute(&mut graph, source).remove(0).rows
+}
+
+/// Runs a single query returning one column and returns its values.
+fn column(source: &str) -> Vec<Value> {
+    rows(source)
+        .into_iter()
+        .map(|mut row| row.remove(0))
+        .collect()
+}
+
+fn rows_of(graph: &mut PropertyGraph, source: &str) -> Vec<Vec<Value>> {
+    execute(graph, source).remove(0).rows
+}
+
+fn strings(values: &[&str]) -> Vec<Value> {
+    values.iter().map(|value| Value::from(*value)).collect()
+}
+
+fn integers(values: &[i64]) -> Vec<Value> {
+    values.iter().map(|value| Value::from(*value)).collect()
+}
+
+#[test]
+fn test_match_filter_and_order() {
+    assert_eq!(
+        column("MATCH (p:Person) WHERE p.age > 26 RETURN p.name ORDER BY p.name DESC"),
+        strings(&["Cat", "Ann"])
+    );
+    assert_eq!(
+        column("MATCH (a {name: 'Ann'})-[:KNOWS]->(b) RETURN b.name ORDER BY b.name"),
+        strings(&["Bob", "Cat"])
+    );
+    assert_eq!(
+        column("MATCH (a)<-[e:KNOWS WHERE e.since > 2014]-(b) RETURN a.name ORDER BY a.name"),
+        strings(&["Ann", "Cat"])
+    );
+}
+
+#[test]
+fn test_nulls_sort_last_and_paging() {
+    assert_eq!(
+        column("MATCH (p:Person) RETURN p.age ORDER BY p.age"),
+        vec![25.into(), 30.into(), 35.into(), Value::Null]
+    );
+    assert_eq!(
+        column("MATCH (p:Person) RETURN p.age ORDER BY p.age DESC NULLS LAST OFFSET 1 LIMIT 2"),
+        integers(&[30, 25])
+    );
+}
+
+#[test]
+fn test_optional_match_pads_with_null() {
+    assert_eq!(
+        rows(
+            "MATCH (p:Person) OPTIONAL MATCH (p)-[:LIVES_IN]->(c:City) \
+             RETURN p.name, c.name ORDER BY p.name"
+        ),
+        vec![
+            strings(&["Ann", "Paris"]),
+            vec!["Bob".into(), Value::Null],
+            vec!["Cat".into(), Value::Null],
+            vec!["Dan".into(), Value::Null],
+        ]
+    );
+}
+
+#[test]
+fn test_let_and_for() {
+    assert_eq!(
+        rows("FOR x IN [10, 20] WITH ORDINALITY i LET y = x + i RETURN x, i, y"),
+        vec![integers(&[10, 1, 11]), integers(&[20, 2, 22])]
+    );
+    assert_eq!(
+        column("FOR x IN [3, 1, 2] WITH OFFSET i FILTER i > 0 RETURN x"),
+        integers(&[1, 2])
+    );
+}
+
+#[test]
+fn test_next_starts_from_the_returned_rows() {
+    let mut graph = social();
+    let results = execute(
+        &mut graph,
+        "MATCH (n:Person) RETURN n.name AS x NEXT FILTER x <> 'Ann' RETURN x ORDER BY x",
+    );
+    assert_eq!(
+        results[1].rows,
+        vec![strings(&["Bob"]), strings(&["Cat"]), strings(&["Dan"])]
+    );
+
+    let results = execute(
+        &mut graph,
+        "MATCH (p {name: 'Dan'}) RETURN p \
+         NEXT MATCH (c:City) INSERT (p)-[:LIVES_IN]->(c) \
+         NEXT MATCH (p)-[:LIVES_IN]->(:City) RETURN p.name ORDER BY p.name",
+    );
+    assert_eq!(results[2].rows, vec![strings(&["Ann"]), strings(&["Dan"])]);
+}
+
+#[test]
+fn test_grouping_and_aggregates() {
+    assert_eq!(
+        rows(
+            "MATCH (a:Person)-[:KNOWS]->(b) \
+             RETURN a.name AS name, count(*) AS friends, max(b.age) AS oldest \
+             GROUP BY name ORDER BY friends DESC, name"
+        ),
+        vec![
+            vec!["Ann".into(), 2.into(), 35.into()],
+            vec!["Bob".into(), 1.into(), 35.into()],
+            vec!["Cat".into(), 1.into(), 30.into()],
+        ]
+    );
+    assert_eq!(
+        rows("MATCH (p:Person) RETURN count(p.age), sum(p.age), avg(p.age), min(p.name)"),
+        vec![vec![3.into(), 90.into(), Value::Float(30.0), "Ann".into()]]
+    );
+    assert_eq!(
+        rows("MATCH (p:Nobody) RETURN count(*) AS n, sum(p.age) AS total"),
+        vec![vec![0.into(), Value::Null]]
+    );
+    assert_eq!(
+        column("MATCH (p:Person)-[:KNOWS]->(q) RETURN count(DISTINCT q) AS known"),
+        integers(&[3])
+    );
+}
+
+#[test]
+fn test_distinct_and_set_operations() {
+    assert_eq!(
+        column("MATCH (a)-[:KNOWS]->(b) RETURN DISTINCT b.name ORDER BY b.name"),
+        strings(&["Ann", "Bob", "Cat"])
+    );
+    let names = |source: &str| {
+        let mut names = column(source);
+        names.sort_by_key(|name| name.to_string());
+        names
+    };
+    assert_eq!(
+        names(
+            "MATCH (p:Person) WHERE p.age < 31 RETURN p.name AS n \
+             UNION MATCH (p:Person) WHERE p.age > 29 RETURN p.name AS n"
+        ),
+        strings(&["Ann", "Bob", "Cat"])
+    );
+    assert_eq!(
+        names(
+            "MATCH (p:Person) RETURN p.name AS n \
+             EXCEPT MATCH (p)-[:LIVES_IN]->() RETURN p.name AS n"
+        ),
+        strings(&["Bob", "Cat", "Dan"])
+    );
+    assert_eq!(
+        names(
+            "MATCH (p)-[:KNOWS]->() RETURN p.name AS n \
+             INTERSECT ALL MATCH (p)-[:KNOWS]->() RETURN p.name AS n"
+        ),
+        strings(&["Ann", "Ann", "Bob", "Cat"])
+    );
+    assert_eq!(
+        names(
+            "MATCH (p:Robot) RETURN p.name AS n \
+             OTHERWISE MATCH (c:City) RETURN c.name AS n"
+        ),
+        strings(&["Paris"])
+    );
+}
+
+#[test]
+fn test_path_modes() {
+    let count = |source: &str| column(source).remove(0);
+    // Walks from Ann over one to three KNOWS edges.
+    assert_eq!(
+        count("MATCH (a {name: 'Ann'})-[:KNOWS]->{1,3}(b) RETURN count(*) AS n"),
+        Value::from(7)
+    );
+    assert_eq!(
+        count("MATCH TRAIL (a {name: 'Ann'})-[:KNOWS]->{1,3}(b) RETURN count(*) AS n"),
+        Value::from(6)
+    );
+    assert_eq!(
+        count("MATCH ACYCLIC (a {name: 'Ann'})-[:KNOWS]->{1,3}(b) RETURN count(*) AS n"),
+        Value::from(3)
+    );
+    // SIMPLE also admits the two cycles back to Ann.
+    assert_eq!(
+        count("MATCH SIMPLE (a {name: 'Ann'})-[:KNOWS]->{1,3}(b) RETURN count(*) AS n"),
+        Value::from(5)
+    );
+}
+
+#[test]
+fn test_path_searches() {
+    let lengths = |source: &str| {
+        let mut lengths = column(source);
+        lengths.sort_by_key(|length| length.to_string());
+        lengths
+    };
+    assert_eq!(
+        lengths(
+            "MATCH p = ANY SHORTEST (a {name: 'Ann'})-[:KNOWS]->+(b {name: 'Ann'}) \
+             RETURN path_length(p)"
+        ),
+        integers(&[2])
+    );
+    assert_eq!(
+        lengths(
+            "MATCH p = ALL SHORTEST (a {name: 'Bob'})-[:KNOWS]->+(b {name: 'Bob'}) \
+             RETURN path_length(p)"
+        ),
+        integers(&[3])
+    );
+    assert_eq!(
+        lengths(
+            "MATCH p = SHORTEST 3 (a {name: 'Ann'})-[:KNOWS]->+(b {name: 'Cat'}) \
+             RETURN path_length(p)"
+        ),
+        integers(&[1, 2, 3])
+    );
+    assert_eq!(
+        lengths(
+            "MATCH p = SHORTEST 2 GROUPS (a {name: 'Ann'})-[:KNOWS]->+(b {name: 'Cat'}) \
+             RETURN path_length(p)"
+        ),
+        integers(&[1, 2])
+    );
+    assert_eq!(
+        lengths(
+            "MATCH p = ANY (a {name: 'Ann'})-[:KNOWS]->+(b) \
+             RETURN path_length(p)"
+        ),
+        integers(&[1, 1, 2])
+    );
+}
+
+#[test]
+fn test_unbounded_walk_is_reported() {
+    let mut graph = social();
+    let error = try_execute(&mut graph, "MATCH (a)-[:KNOWS]->*(b) RETURN b").unwrap_err();
+    assert_eq!(error.kind, RuntimeErrorKind::InfinitePaths);
+}
+
+#[test]
+fn test_group_variables_aggregate_horizontally() {
+    assert_eq!(
+        rows(
+            "MATCH TRAIL (a {name: 'Ann'}) (()-[e:KNOWS]->()){2} (b) \
+             RETURN b.name, sum(e.since) AS total ORDER BY total"
+        ),
+        vec![
+            vec!["Cat".into(), 4025.into()],
+            vec!["Ann".into(), 4032.into()]
+        ]
+    );
+}
+
+#[test]
+fn test_path_values() {
+    let mut graph = social();
+    let ann = NodeId::clone(&graph.nodes().next().unwrap().0);
+    let table = execute(
+        &mut graph,
+        "MATCH p = (a {name: 'Ann'})-[:LIVES_IN]->(c) RETURN p, elements(p) AS e",
+    )
+    .remove(0);
+    let Some(Value::Path(path)) = table.get(0, "p") else {
+        panic!("expected a path: {table:?}");
+    };
+    assert_eq!(path.nodes()[0], ann);
+    assert_eq!(path.len(), 1);
+    assert_eq!(table.columns[0].ty, Type::Path);
+    assert!(matches!(table.get(0, "e"), Some(Value::List(items)) if items.len() == 3));
+}
+
+#[test]
+fn test_insert_set_remove() {
+    let mut graph = social();
+    execute(
+        &mut graph,
+        "MATCH (a {name: 'Dan'}), (c:City) \
+         INSERT (a)-[:LIVES_IN {since: 2021}]->(c), (:Person {name: 'Eve', age: 41})",
+    );
+    assert_eq!((graph.node_count(), graph.edge_count()), (6, 6));
+    execute(
+        &mut graph,
+        "MATCH (p {name: 'Eve'}) SET p.age = p.age + 1, p:Admin REMOVE p.name",
+    );
+    assert_eq!(
+        rows_of(
+            &mut graph,
+            "MATCH (p:Admin) RETURN p.age AS age, p.name AS name"
+        ),
+        vec![vec![42.into(), Value::Null]]
+    );
+}
+
+#[test]
+fn test_delete_requires_detach() {
+    let mut graph = social();
+    let before = graph.clone();
+    let error = try_execute(&mut graph, "MATCH (p {name: 'Cat'}) DELETE p").unwrap_err();
+    assert_eq!(error.kind, RuntimeErrorKind::ConstraintViolation);
+    assert_eq!(graph, before);
+
+    execute(&mut graph, "MATCH (p {name: 'Cat'}) DETACH DELETE p");
+    assert_eq!((graph.node_count(), graph.edge_count()), (4, 2));
+    execute(&mut graph, "MATCH (p {name: 'Dan'}) DELETE p");
+    assert_eq!(graph.node_count(), 3);
+}
+
+#[test]
+fn test_rollback_restores_the_graph() {
+    let mut graph = social();
+    let before = graph.clone();
+    execute(
+        &mut graph,
+        "START TRANSACTION; INSERT (:Person {name: 'Eve'}); ROLLBACK",
+    );
+    assert_eq!(graph, before);
+}
+
+#[test]
+fn test_parameters() {
+    let program = parse("MATCH (p:Person) WHERE p.age >= $min RETURN p.name ORDER BY p.name")
+        .ast
+        .unwrap();
+    let ir = SemanticValidator::new().validate(&program).ir.unwrap();
+    let mut graph = social();
+    let tables = Interpreter::new(&mut graph)
+        .with_parameter("min", 30)
+        .execute(&ir)
+        .unwrap();
+    assert_eq!(
+        tables[0]
+            .rows
+            .iter()
+            .map(|row| row[0].clone())
+            .collect::<Vec<_>>(),
+        strings(&["Ann", "Cat"])
+    );
+}
@@ -0,0 +1,290 @@
+//! Join order planning tests
+//!
+//! Patterns are planned with heuristics only, against the social graph
+//! fixture schema (where `Person` is keyed by `name`), and against the
+//! example statistics.
+
+use gql_parser::analysis::cardinality::CardinalityEstimator;
+use gql_parser::analysis::join_order::{
+    JoinEdgeKind, JoinGraph, JoinOrderPlanner, MatchPlan, PlanStep, StartReason,
+};
+use gql_parser::ast::query::{
+    EdgeDirection, GraphPattern, LinearQuery, MatchStatement, PrimitiveQueryStatement, Query,
+};
+use gql_parser::ast::{Program, Statement};
+use gql_parser::parse;
+use gql_parser::semantic::metadata_provider::{MetadataProvider, MockMetadataProvider};
+use gql_parser::semantic::schema_catalog::GraphRef;
+use gql_parser::semantic::statistics_provider::MockStatisticsProvider;
+
+fn parse_program(source: &str) -> Program {
+    parse(source)
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"))
+}
+
+fn first_pattern(program: &Program) -> &GraphPattern {
+    let Statement::Query(statement) = &program.statements[0] else {
+        panic!("expected a query");
+    };
+    let Query::Linear(LinearQuery {
+        primitive_statements,
+        ..
+    }) = &statement.query
+    else {
+        panic!("expected a linear query");
+    };
+    match &primitive_statements[0] {
+        PrimitiveQueryStatement::Match(MatchStatement::Simple(statement)) => &statement.pattern,
+        _ => panic!("expected a MATCH statement"),
+    }
+}
+
+fn plan_with(planner: &JoinOrderPlanner<'_>, source: &str) -> MatchPlan {
+    planner.plan(first_pattern(&parse_program(source)))
+}
+
+/// Renders plan steps with variable names, `_` for anonymous nodes.
+fn describe(plan: &MatchPlan) -> Vec<String> {
+    let name = |node: usize| {
+        plan.graph.nodes[node]
+            .variable
+            .as_deref()
+            .unwrap_or("_")
+            .to_string()
+    };
+    plan.steps
+        .iter()
+        .map(|step| match step {
+            PlanStep::Scan { node, .. } => format!("scan {}", name(*node)),
+            PlanStep::Expand { from, to, into, .. } => {
+                let arrow = if *into { "=>" } else { "->" };
+                format!("expand {} {arrow} {}", name(*from), name(*to))
+            }
+            PlanStep::Join { component } => format!("join {component}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_join_graph_merges_shared_variables_and_subpaths() {
+    let program = parse_program(
+        "MATCH (a:Person)-[k:KNOWS]->(b), (b)-[:KNOWS]->{1,3}(c), (d)-[]-() RETURN a",
+    );
+    let graph = JoinGraph::from_pattern(first_pattern(&program));
+    let variables: Vec<_> = graph
+        .nodes
+        .iter()
+        .map(|node| node.variable.as_deref())
+        .collect();
+    assert_eq!(
+        variables,
+        [Some("a"), Some("b"), Some("c"), Some("d"), None]
+    );
+    assert_eq!(graph.nodes[1].spans.len(), 2);
+    assert_eq!(graph.nodes[0].label.as_deref(), Some("Person"));
+
+    assert_eq!(graph.edges.len(), 3);
+    assert_eq!(graph.edges[0].variable.as_deref(), Some("k"));
+    assert_eq!(graph.edges[0].kind, JoinEdgeKind::Edge);
+    assert_eq!((graph.edges[1].source, graph.edges[1].target), (1, 2));
+    assert_eq!(graph.edges[1].kind, JoinEdgeKind::Subpath);
+    assert_eq!(graph.edges[1].direction, Some(EdgeDirection::PointingRight));
+    assert_eq!(graph.edges[2].direction, Some(EdgeDirection::AnyDirection));
+
+    assert_eq!(graph.components, vec![vec![0, 1, 2], vec![3, 4]]);
+    assert_eq!(graph.info.connected_component_count, 2);
+    assert_eq!(graph.component_of(4), Some(1));
+}
+
+#[test]
+fn test_heuristics_start_from_constrained_nodes() {
+    let planner = JoinOrderPlanner::new();
+    let plan = plan_with(
+        &planner,
+        "MATCH (a)-[:KNOWS]->(b:Person {name: 'Ann'})-[:LIVES_IN]->(c:City) RETURN a",
+    );
+    assert_eq!(
+        describe(&plan),
+        ["scan b", "expand b -> c", "expand b -> a"]
+    );
+    assert_eq!(
+        plan.steps[0],
+        PlanStep::Scan {
+            node: 1,
+            reason: StartReason::Label
+        }
+    );
+    assert!(matches!(
+        plan.steps[2],
+        PlanStep::Expand {
+            edge: 0,
+            direction: Some(EdgeDirection::PointingLeft),
+            reversed: true,
+            into: false,
+            ..
+        }
+    ));
+    assert_eq!(plan.rows, None);
+    assert_eq!(plan.cost, None);
+
+    // Equalities in the WHERE clause count as well; a value depending on
+    // another variable does not.
+    let plan = plan_with(
+        &planner,
+        "MATCH (a:Person)-[:KNOWS]->(b:Person) WHERE b.age = 30 AND a.name = b.name RETURN a",
+    );
+    assert_eq!(describe(&plan), ["scan b", "expand b -> a"]);
+    assert_eq!(plan.graph.nodes[1].equalities, ["age"]);
+
+    let plan = plan_with(&planner, "MATCH (a)<-[e]-(b) RETURN a");
+    assert_eq!(describe(&plan), ["scan a", "expand a -> b"]);
+    assert_eq!(
+        plan.steps[0],
+        PlanStep::Scan {
+            node: 0,
+            reason: StartReason::AllNodes
+        }
+    );
+}
+
+#[test]
+fn test_schema_keys_select_lookups() {
+    let provider = MockMetadataProvider::with_standard_fixtures();
+    let schema = provider
+        .get_schema_snapshot(
+            &GraphRef {
+                name: "social_graph".into(),
+            },
+            None,
+        )
+        .unwrap();
+    let planner = JoinOrderPlanner::new().with_schema(&*schema);
+
+    // A key lookup beats a node with more, but non-key, equalities.
+    let plan = plan_with(
+        &planner,
+        "MATCH (a:Person {age: 30, email: 'a@example.com'})-[:KNOWS]->(b:Person) \
+         WHERE b.name = 'Bob' RETURN a",
+    );
+    assert_eq!(describe(&plan), ["scan b", "expand b -> a"]);
+    assert_eq!(
+        plan.steps[0],
+        PlanStep::Scan {
+            node: 1,
+            reason: StartReason::KeyLookup {
+                label: "Person".into(),
+                properties: vec!["name".into()],
+            }
+        }
+    );
+
+    let plan = plan_with(
+        &JoinOrderPlanner::new(),
+        "MATCH (a:Person {age: 30, email: 'a@example.com'})-[:KNOWS]->(b:Person) \
+         WHERE b.name = 'Bob' RETURN a",
+    );
+    assert_eq!(describe(&plan), ["scan a", "expand a -> b"]);
+}
+
+#[test]
+fn test_bound_variables_start_and_close_expansions() {
+    let planner = JoinOrderPlanner::new().with_bound_variables(["c"]);
+    let plan = plan_with(
+        &planner,
+        "MATCH (a:Person {name: 'Ann'})-[:KNOWS]->(b)-[:KNOWS]->(c) RETURN a",
+    );
+    assert_eq!(
+        describe(&plan),
+        ["scan c", "expand c -> b", "expand b -> a"]
+    );
+    assert_eq!(
+        plan.steps[0],
+        PlanStep::Scan {
+            node: 2,
+            reason: StartReason::Bound
+        }
+    );
+
+    // Expansions reaching a bound node only check the edge.
+    let plan = plan_with(
+        &JoinOrderPlanner::new().with_bound_variables(["a", "c"]),
+        "MATCH (a)-[:KNOWS]->(b)-[:KNOWS]->(c) RETURN a",
+    );
+    assert_eq!(
+        describe(&plan),
+        ["scan a", "expand a -> b", "expand b => c"]
+    );
+
+    // Cycles are closed as soon as both ends are matched.
+    let plan = plan_with(
+        &JoinOrderPlanner::new(),
+        "MATCH (a:Person {name: 'Ann'})-[:KNOWS]->(b)-[:KNOWS]->(c)-[:KNOWS]->(a), \
+         (c)-[:KNOWS]->(d) RETURN a",
+    );
+    assert_eq!(
+        describe(&plan),
+        [
+            "scan a",
+            "expand a -> b",
+            "expand b -> c",
+            "expand c => a",
+            "expand c -> d"
+        ]
+    );
+}
+
+#[test]
+fn test_estimates_choose_start_and_direction() {
+    let statistics = MockStatisticsProvider::example();
+    let estimator = CardinalityEstimator::new(&statistics);
+    let planner = JoinOrderPlanner::new().with_estimator(&estimator);
+
+    let plan = plan_with(
+        &planner,
+        "MATCH (p:Person)-[:LIVES_IN]->(c:City {name: 'Paris'}) RETURN p",
+    );
+    assert_eq!(describe(&plan), ["scan c", "expand c -> p"]);
+    assert_eq!(
+        plan.steps[0],
+        PlanStep::Scan {
+            node: 1,
+            reason: StartReason::Estimated { rows: 10.0 }
+        }
+    );
+    assert_eq!(plan.rows, Some(200.0));
+    assert_eq!(plan.cost, Some(210.0));
+
+    // Heuristics alone cannot tell which equality is more selective.
+    let source = "MATCH (p:Person {age: 30})-[:LIVES_IN]->(c:City {name: 'Rome'}) RETURN p";
+    assert_eq!(
+        describe(&plan_with(&planner, source)),
+        ["scan c", "expand c -> p"]
+    );
+    assert_eq!(
+        describe(&plan_with(&JoinOrderPlanner::new(), source)),
+        ["scan p", "expand p -> c"]
+    );
+}
+
+#[test]
+fn test_components_are_joined_smallest_first() {
+    let statistics = MockStatisticsProvider::example();
+    let estimator = CardinalityEstimator::new(&statistics);
+    let plan = plan_with(
+        &JoinOrderPlanner::new().with_estimator(&estimator),
+        "MATCH (p:Person), (c:City) RETURN p",
+    );
+    assert_eq!(describe(&plan), ["scan c", "scan p", "join 0"]);
+    assert_eq!(plan.rows, Some(50_000.0));
+    assert_eq!(plan.cost, Some(50.0 + 1000.0 + 50_000.0));
+
+    let plan = plan_with(
+        &JoinOrderPlanner::new().with_bound_variables(["p"]),
+        "MATCH (c:City), (p)-[:LIVES_IN]->(x) RETURN p",
+    );
+    assert_eq!(
+        describe(&plan),
+        ["scan p", "expand p -> x", "scan c", "join 0"]
+    );
+}
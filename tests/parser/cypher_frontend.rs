@@ -0,0 +1,218 @@
+//! openCypher front-end tests
+//!
+//! `parse_cypher` lowers Cypher to the GQL AST where GQL has the same
+//! construct, and reports the Cypher-only constructs it cannot lower.
+
+use crate::common::*;
+use gql_parser::ast::query::{
+    GraphPatternQuantifier, LinearQuery, MatchMode, MatchStatement, PathPrimary,
+    PrimitiveQueryStatement, Query,
+};
+use gql_parser::ast::{
+    PrimitiveDataModifyingStatement, Program, SimpleDataAccessingStatement,
+    SimpleDataModifyingStatement, Statement,
+};
+use gql_parser::{SemanticValidator, parse_cypher};
+
+fn parse_cypher_cleanly(source: &str) -> Program {
+    let result = parse_cypher(source);
+    assert_no_parse_errors(&result, source);
+    result.ast.expect("expected AST")
+}
+
+fn linear_query(statement: &Statement) -> &LinearQuery {
+    let Statement::Query(query) = statement else {
+        panic!("expected query statement, got {statement:?}");
+    };
+    let Query::Linear(linear) = &query.query else {
+        panic!("expected linear query");
+    };
+    linear
+}
+
+fn cypher_errors(source: &str) -> Vec<String> {
+    parse_cypher(source)
+        .diagnostics
+        .iter()
+        .map(|report| report.to_string())
+        .collect()
+}
+
+#[test]
+fn variable_length_relationship_becomes_quantified_edge() {
+    let program = parse_cypher_cleanly("MATCH (a:Person)-[:KNOWS*1..3]->(b) RETURN b");
+    let linear = linear_query(&program.statements[0]);
+    let PrimitiveQueryStatement::Match(MatchStatement::Simple(statement)) =
+        &linear.primitive_statements[0]
+    else {
+        panic!("expected MATCH");
+    };
+    assert!(matches!(
+        statement.pattern.match_mode,
+        Some(MatchMode::DifferentEdges)
+    ));
+
+    let expression = &statement.pattern.paths.patterns[0].expression;
+    let gql_parser::ast::query::PathPatternExpression::Term(term) = expression else {
+        panic!("expected path term");
+    };
+    let edge = &term.factors[1];
+    assert!(matches!(edge.primary, PathPrimary::ElementPattern(_)));
+    assert!(matches!(
+        edge.quantifier,
+        Some(GraphPatternQuantifier::General {
+            min: Some(1),
+            max: Some(3),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn single_relationship_keeps_default_match_mode() {
+    let program = parse_cypher_cleanly("MATCH (a)-[:KNOWS]->(b) RETURN b");
+    let linear = linear_query(&program.statements[0]);
+    let PrimitiveQueryStatement::Match(MatchStatement::Simple(statement)) =
+        &linear.primitive_statements[0]
+    else {
+        panic!("expected MATCH");
+    };
+    assert!(statement.pattern.match_mode.is_none());
+}
+
+#[test]
+fn unwind_becomes_for() {
+    let program = parse_cypher_cleanly("UNWIND [1, 2, 3] AS x RETURN x");
+    let linear = linear_query(&program.statements[0]);
+    let PrimitiveQueryStatement::For(statement) = &linear.primitive_statements[0] else {
+        panic!("expected FOR");
+    };
+    assert_eq!(statement.item.binding_variable.name, "x");
+}
+
+#[test]
+fn with_becomes_return_and_next() {
+    let source = "MATCH (n:Person) WITH n.city AS city, count(n) AS people \
+                  WHERE people > 10 RETURN city";
+    let program = parse_cypher_cleanly(source);
+    assert_eq!(program.statements.len(), 2);
+
+    let first = linear_query(&program.statements[0]);
+    assert!(first.result_statement.is_some());
+    assert!(matches!(&program.statements[1], Statement::Query(query) if query.follows_next));
+    let second = linear_query(&program.statements[1]);
+    assert!(matches!(
+        second.primitive_statements[0],
+        PrimitiveQueryStatement::Filter(_)
+    ));
+}
+
+#[test]
+fn create_becomes_insert() {
+    let program = parse_cypher_cleanly("MATCH (a), (b) CREATE (a)-[:KNOWS {since: 2020}]->(b)");
+    let Statement::Mutation(mutation) = &program.statements[0] else {
+        panic!("expected mutation");
+    };
+    assert!(
+        mutation
+            .statement
+            .statements
+            .iter()
+            .any(|statement| matches!(
+                statement,
+                SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Primitive(
+                    PrimitiveDataModifyingStatement::Insert(_)
+                ))
+            ))
+    );
+}
+
+#[test]
+fn updates_followed_by_with() {
+    let program = parse_cypher_cleanly("MATCH (n) SET n.seen = true WITH n RETURN n");
+    assert_eq!(program.statements.len(), 2);
+    assert!(matches!(program.statements[0], Statement::Mutation(_)));
+}
+
+#[test]
+fn lowered_query_validates() {
+    let source = "MATCH (a:Person)-[:KNOWS]->(b) WHERE exists(b.name) \
+                  RETURN toUpper(b.name) AS name, collect(a) AS friends";
+    let program = parse_cypher_cleanly(source);
+    let outcome = SemanticValidator::new().validate(&program);
+    assert_no_validation_errors(&outcome);
+}
+
+#[test]
+fn with_bindings_validate_after_next() {
+    for source in [
+        "MATCH (n) WITH n, count(*) AS c WHERE c > 1 RETURN n, c",
+        "MATCH (n) WITH n MATCH (n)-->(m) RETURN m",
+        "MATCH (n) WITH n WHERE n.age > 30 SET n.senior = true",
+    ] {
+        let program = parse_cypher_cleanly(source);
+        let outcome = SemanticValidator::new().validate(&program);
+        assert_no_validation_errors(&outcome);
+    }
+
+    let program = parse_cypher_cleanly("MATCH (n) WITH n AS m RETURN n");
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(
+        outcome
+            .diagnostics
+            .iter()
+            .any(|diag| diag.message == "Undefined variable 'n'")
+    );
+}
+
+#[test]
+fn constructs_without_gql_equivalent_are_reported() {
+    let cases = [
+        ("MERGE (n:Person {id: 1})", "MERGE has no GQL equivalent"),
+        (
+            "MATCH (n) FOREACH (x IN [1] | SET n.x = x)",
+            "FOREACH has no GQL equivalent",
+        ),
+        (
+            "MATCH (n) WHERE n.name STARTS WITH 'A' RETURN n",
+            "STARTS WITH has no GQL equivalent",
+        ),
+        (
+            "MATCH (n) RETURN [x IN n.scores | x * 2]",
+            "a list comprehension has no GQL equivalent",
+        ),
+        (
+            "MATCH (n) RETURN n {.name}",
+            "a map projection has no GQL equivalent",
+        ),
+        (
+            "MATCH (n) SET n += {name: 'x'}",
+            "SET += has no GQL equivalent",
+        ),
+        (
+            "CREATE INDEX FOR (n:Person) ON (n.name)",
+            "CREATE INDEX has no GQL equivalent",
+        ),
+    ];
+    for (source, message) in cases {
+        let errors = cypher_errors(source);
+        assert_eq!(errors, vec![message.to_string()], "{source}");
+    }
+}
+
+#[test]
+fn unsupported_statement_does_not_hide_the_rest_of_the_program() {
+    let result = parse_cypher("MERGE (n:Person); MATCH (n) RETURN n");
+    assert_eq!(result.diagnostics.len(), 1);
+    let program = result.ast.expect("expected AST");
+    assert_eq!(program.statements.len(), 1);
+    assert!(matches!(program.statements[0], Statement::Query(_)));
+}
+
+#[test]
+fn merge_actions_are_reported_once() {
+    let errors = cypher_errors(
+        "MERGE (n:Person {id: 1}) ON CREATE SET n.created = 1 ON MATCH SET n.seen = 1 RETURN n",
+    );
+    assert_eq!(errors, vec!["MERGE has no GQL equivalent".to_string()]);
+}
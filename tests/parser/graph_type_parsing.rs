@@ -435,3 +435,53 @@ fn test_complex_graph_type_with_multiple_features() {
     assert!(result.ast.is_some(), "Complex graph type should parse");
     // Test focuses on successful parsing
 }
+
+#[test]
+fn test_delimited_identifiers_in_graph_type() {
+    let source = r#"
+        CREATE GRAPH TYPE audit AS {
+            NODE TYPE `Event` LABEL `Event` { `timestamp` :: LOCAL DATETIME NOT NULL }
+                CONSTRAINT KEY (`timestamp`),
+            DIRECTED EDGE TYPE `NEXT` LABEL `NEXT` CONNECTING (`Event` TO `Event`)
+        }
+    "#;
+
+    let result = gql_parser::parse(source);
+    let program = result.ast.expect("delimited identifiers should parse");
+    let gql_parser::ast::Statement::Catalog(catalog) = &program.statements[0] else {
+        panic!("expected catalog statement");
+    };
+    let gql_parser::ast::CatalogStatementKind::CreateGraphType(create) = &catalog.kind else {
+        panic!("expected CREATE GRAPH TYPE");
+    };
+    let Some(gql_parser::ast::GraphTypeSource::Detailed { specification, .. }) = &create.source
+    else {
+        panic!("expected detailed graph type");
+    };
+    let gql_parser::ast::graph_type::ElementTypeSpecification::Node(node) =
+        &specification.body.element_types.types[0]
+    else {
+        panic!("expected node type");
+    };
+    assert_eq!(node.name.as_deref(), Some("Event"));
+    let properties = node.pattern.phrase.filler.as_ref().unwrap().property_types.as_ref().unwrap();
+    let list = properties.specification.property_types.as_ref().unwrap();
+    assert_eq!(list.types[0].name.name, "timestamp");
+}
+
+#[test]
+fn test_delimited_identifiers_may_spell_reserved_words_and_spaces() {
+    let source = r#"
+        CREATE GRAPH TYPE audit AS {
+            (`audit event` :`Audit Event` { `MATCH` :: STRING }),
+            (`audit event`)-[:`FOLLOWS`]->(`audit event`)
+        }
+    "#;
+
+    let result = gql_parser::parse(source);
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let ddl = format!("{:?}", result.ast.expect("graph type should parse"));
+    assert!(ddl.contains("\"Audit Event\""), "{ddl}");
+    assert!(ddl.contains("\"MATCH\""), "{ddl}");
+    assert!(ddl.contains("\"audit event\""), "{ddl}");
+}
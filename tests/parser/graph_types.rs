@@ -510,6 +510,21 @@ fn test_graph_type_with_multiple_constraints() {
 
     let filler = node.pattern.phrase.filler.as_ref().unwrap();
     assert_eq!(filler.constraints.len(), 3, "Should have 3 constraints");
+
+    use gql_parser::ast::graph_type::GraphTypeConstraint;
+    let names: Vec<_> = filler.constraints[0]
+        .properties()
+        .iter()
+        .map(|property| property.name.as_str())
+        .collect();
+    assert_eq!(names, ["id"]);
+    let GraphTypeConstraint::Check { condition, .. } = &filler.constraints[1] else {
+        panic!("Expected CHECK constraint");
+    };
+    assert!(matches!(
+        condition.as_ref(),
+        gql_parser::ast::Expression::Comparison(..)
+    ));
 }
 
 #[test]
@@ -545,3 +560,104 @@ fn test_graph_type_with_inheritance_chain() {
     // NOTE: Semantic validation (not parser) should check inheritance chain correctness
 }
 
+#[test]
+fn test_graph_type_constraint_property_lists() {
+    use gql_parser::ast::graph_type::{ElementTypeSpecification, GraphTypeConstraint};
+    use gql_parser::parse;
+
+    let source = r#"
+        CREATE GRAPH TYPE keyed AS {
+            NODE TYPE Person { id :: INT, email :: STRING }
+            CONSTRAINT KEY (id)
+            CONSTRAINT UNIQUE (id, email)
+            CONSTRAINT MANDATORY (email)
+        }
+    "#;
+
+    let result = parse(source);
+    assert!(result.diagnostics.is_empty(), "unexpected diagnostics: {:?}", result.diagnostics);
+
+    let program = result.ast.unwrap();
+    let gql_parser::ast::Statement::Catalog(cat) = &program.statements[0] else {
+        panic!("Expected catalog statement");
+    };
+    let gql_parser::ast::CatalogStatementKind::CreateGraphType(create) = &cat.kind else {
+        panic!("Expected CREATE GRAPH TYPE");
+    };
+    let Some(gql_parser::ast::GraphTypeSource::Detailed { specification, .. }) = &create.source else {
+        panic!("Expected detailed source");
+    };
+    let ElementTypeSpecification::Node(node) = &specification.body.element_types.types[0] else {
+        panic!("Expected node type");
+    };
+    let constraints = &node.pattern.phrase.filler.as_ref().unwrap().constraints;
+
+    let properties = |constraint: &GraphTypeConstraint| -> Vec<String> {
+        constraint.properties().iter().map(|p| p.name.to_string()).collect()
+    };
+    assert!(matches!(constraints[0], GraphTypeConstraint::Key { .. }));
+    assert_eq!(properties(&constraints[0]), ["id"]);
+    assert_eq!(properties(&constraints[1]), ["id", "email"]);
+    assert!(matches!(constraints[2], GraphTypeConstraint::Mandatory { .. }));
+    assert_eq!(properties(&constraints[2]), ["email"]);
+}
+
+#[test]
+fn test_graph_type_constraint_rejects_malformed_arguments() {
+    use gql_parser::parse;
+
+    let result = parse("CREATE GRAPH TYPE t AS { NODE TYPE Person { id :: INT } CONSTRAINT UNIQUE (id + 1) }");
+    assert!(!result.diagnostics.is_empty(), "UNIQUE takes property names only");
+
+    let result = parse("CREATE GRAPH TYPE t AS { NODE TYPE Person { id :: INT } CONSTRAINT CHECK (id > 0, id < 9) }");
+    assert!(!result.diagnostics.is_empty(), "CHECK takes a single condition");
+}
+
+#[test]
+fn test_property_default_and_check() {
+    use gql_parser::ast::graph_type::ElementTypeSpecification;
+    use gql_parser::parse;
+
+    let source = "CREATE GRAPH TYPE t AS { NODE TYPE Person { \
+                  age :: INT NOT NULL DEFAULT 18 CHECK (age >= 0), \
+                  tags :: LIST<STRING> DEFAULT [ 'a', 'b' ], \
+                  name :: STRING } }";
+    let result = parse(source);
+    assert!(result.diagnostics.is_empty(), "unexpected diagnostics: {:?}", result.diagnostics);
+
+    let program = result.ast.unwrap();
+    let gql_parser::ast::Statement::Catalog(cat) = &program.statements[0] else {
+        panic!("Expected catalog statement");
+    };
+    let gql_parser::ast::CatalogStatementKind::CreateGraphType(create) = &cat.kind else {
+        panic!("Expected CREATE GRAPH TYPE");
+    };
+    let Some(gql_parser::ast::GraphTypeSource::Detailed { specification, .. }) = &create.source else {
+        panic!("Expected detailed source");
+    };
+    let ElementTypeSpecification::Node(node) = &specification.body.element_types.types[0] else {
+        panic!("Expected node type");
+    };
+    let filler = node.pattern.phrase.filler.as_ref().unwrap();
+    let types = &filler.property_types.as_ref().unwrap().specification.property_types;
+    let types = &types.as_ref().unwrap().types;
+
+    let age = &types[0];
+    assert!(age.not_null);
+    assert_eq!(&source[age.default.as_ref().unwrap().span()], "18");
+    assert_eq!(&source[age.check.as_ref().unwrap().span()], "age >= 0");
+    assert_eq!(&source[age.span.clone()], "age :: INT NOT NULL DEFAULT 18 CHECK (age >= 0)");
+    assert_eq!(&source[types[1].default.as_ref().unwrap().span()], "[ 'a', 'b' ]");
+    assert!(types[2].default.is_none() && types[2].check.is_none());
+}
+
+#[test]
+fn test_property_default_and_check_reject_malformed_input() {
+    use gql_parser::parse;
+
+    let result = parse("CREATE GRAPH TYPE t AS { NODE TYPE Person { age :: INT DEFAULT } }");
+    assert!(!result.diagnostics.is_empty(), "DEFAULT needs a value");
+
+    let result = parse("CREATE GRAPH TYPE t AS { NODE TYPE Person { age :: INT CHECK age > 0 } }");
+    assert!(!result.diagnostics.is_empty(), "CHECK needs parentheses");
+}
@@ -27,3 +27,4 @@ mod procedures_advanced;
 mod graph_type_parsing;
 mod pagination_ordering;
 mod path_pattern_parsing;
+mod cypher_frontend;
@@ -210,3 +210,12 @@ fn parse_match_followed_by_remove_is_single_mutation_statement() {
     );
     assert!(matches!(program.statements[0], Statement::Mutation(_)));
 }
+
+#[test]
+fn parse_next_ends_mutation_statement() {
+    let program = parse_cleanly("MATCH (n) SET n.age = 1 RETURN n NEXT RETURN 1");
+
+    assert_eq!(program.statements.len(), 2);
+    assert!(matches!(program.statements[0], Statement::Mutation(_)));
+    assert!(matches!(program.statements[1], Statement::Query(_)));
+}
@@ -430,3 +430,87 @@ fn test_complete_pagination_pipeline() {
 
     assert!(result.ast.is_some(), "Complete pagination pipeline should parse");
 }
+
+#[test]
+fn test_order_by_after_return_is_kept() {
+    use gql_parser::ast::{PrimitiveResultStatement, Query, Statement};
+
+    let source = "MATCH (n:Person) RETURN n.name AS name ORDER BY name DESC OFFSET 5 LIMIT 10";
+
+    let lexer = Lexer::new(source);
+    let lex_result = lexer.tokenize();
+    let parser = Parser::new(lex_result.tokens, source);
+    let result = parser.parse();
+
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let program = result.ast.expect("query should parse");
+    let Statement::Query(statement) = &program.statements[0] else {
+        panic!("expected query statement");
+    };
+    let Query::Linear(query) = &statement.query else {
+        panic!("expected linear query");
+    };
+    let Some(PrimitiveResultStatement::Return(ret)) = query.result_statement.as_deref() else {
+        panic!("expected RETURN");
+    };
+    let page = ret
+        .order_by_and_page
+        .as_ref()
+        .expect("ORDER BY belongs to the RETURN statement");
+    assert_eq!(page.order_by.as_ref().unwrap().sort_specifications.len(), 1);
+    assert!(page.offset.is_some() && page.limit.is_some());
+    assert_eq!(ret.span.end, source.len());
+}
+
+fn return_order_by_and_page(source: &str) -> Option<gql_parser::ast::OrderByAndPageStatement> {
+    use gql_parser::ast::{PrimitiveResultStatement, Query, Statement};
+
+    let result = gql_parser::parse(source);
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let program = result.ast.expect("query should parse");
+    let Statement::Query(statement) = &program.statements[0] else {
+        panic!("expected query statement");
+    };
+    let Query::Linear(query) = &statement.query else {
+        panic!("expected linear query");
+    };
+    let Some(PrimitiveResultStatement::Return(ret)) = query.result_statement.as_deref() else {
+        panic!("expected RETURN");
+    };
+    ret.order_by_and_page.as_deref().cloned()
+}
+
+#[test]
+fn test_return_without_order_by_has_no_page() {
+    assert!(return_order_by_and_page("MATCH (n) RETURN n").is_none());
+}
+
+#[test]
+fn test_return_with_order_by_only() {
+    let page = return_order_by_and_page("MATCH (n) RETURN n ORDER BY n.name, n.age DESC")
+        .expect("ORDER BY belongs to the RETURN statement");
+    assert_eq!(page.order_by.unwrap().sort_specifications.len(), 2);
+    assert!(page.offset.is_none() && page.limit.is_none());
+}
+
+#[test]
+fn test_return_with_limit_or_skip_only() {
+    let page = return_order_by_and_page("MATCH (n) RETURN n LIMIT 3").expect("LIMIT is kept");
+    assert!(page.order_by.is_none() && page.offset.is_none());
+    assert!(page.limit.is_some());
+
+    let page = return_order_by_and_page("MATCH (n) RETURN n SKIP 2").expect("SKIP is kept");
+    assert!(page.offset.unwrap().use_skip_keyword);
+}
+
+#[test]
+fn test_return_with_limit_before_offset() {
+    let page = return_order_by_and_page("MATCH (n) RETURN n ORDER BY n.name LIMIT 10 OFFSET 20")
+        .expect("LIMIT and OFFSET are kept");
+    assert!(page.order_by.is_some() && page.limit.is_some());
+    assert!(!page.offset.unwrap().use_skip_keyword);
+
+    let page = return_order_by_and_page("MATCH (n) RETURN n LIMIT 10 SKIP 20")
+        .expect("LIMIT and SKIP are kept");
+    assert!(page.limit.is_some() && page.offset.is_some());
+}
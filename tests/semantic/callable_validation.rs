@@ -3,8 +3,9 @@
 use gql_parser::semantic::callable::{
     resolve_builtin_signatures,
     CallableKind, CallableSignature, CallableValidator, DefaultCallableValidator,
-    ParameterSignature,
+    ParameterSignature, SignatureType,
 };
+use gql_parser::ir::type_table::Type;
 use gql_parser::semantic::{SemanticValidator, ValidationConfig};
 
 #[test]
@@ -128,8 +129,8 @@ fn test_default_callable_validator_correct_arity() {
     let sig = CallableSignature::new(
         "test",
         CallableKind::Function,
-        vec![ParameterSignature::required("x", "INT")],
-        Some("INT"),
+        vec![ParameterSignature::required("x", Type::Int)],
+        Some(Type::Int),
     );
 
     // Correct arity
@@ -154,8 +155,8 @@ fn test_default_callable_validator_wrong_arity() {
     let sig = CallableSignature::new(
         "test",
         CallableKind::Function,
-        vec![ParameterSignature::required("x", "INT")],
-        Some("INT"),
+        vec![ParameterSignature::required("x", Type::Int)],
+        Some(Type::Int),
     );
 
     // Wrong arity: too many arguments
@@ -214,8 +215,8 @@ fn test_default_callable_validator_variadic() {
     let sig = CallableSignature::new(
         "concat",
         CallableKind::Function,
-        vec![ParameterSignature::variadic("args", "STRING")],
-        Some("STRING"),
+        vec![ParameterSignature::variadic("args", Type::String)],
+        Some(Type::String),
     );
 
     // Variadic accepts any number of arguments >= min_arity
@@ -254,10 +255,10 @@ fn test_signature_arity_helpers() {
         "test",
         CallableKind::Function,
         vec![
-            ParameterSignature::required("a", "INT"),
-            ParameterSignature::required("b", "INT"),
+            ParameterSignature::required("a", Type::Int),
+            ParameterSignature::required("b", Type::Int),
         ],
-        Some("INT"),
+        Some(Type::Int),
     );
     assert_eq!(sig.min_arity(), 2);
     assert_eq!(sig.max_arity(), Some(2));
@@ -270,10 +271,10 @@ fn test_signature_arity_helpers() {
         "test",
         CallableKind::Function,
         vec![
-            ParameterSignature::required("a", "INT"),
-            ParameterSignature::optional("b", "INT"),
+            ParameterSignature::required("a", Type::Int),
+            ParameterSignature::optional("b", Type::Int),
         ],
-        Some("INT"),
+        Some(Type::Int),
     );
     assert_eq!(sig.min_arity(), 1);
     assert_eq!(sig.max_arity(), Some(2));
@@ -286,8 +287,8 @@ fn test_signature_arity_helpers() {
     let sig = CallableSignature::new(
         "test",
         CallableKind::Function,
-        vec![ParameterSignature::variadic("args", "ANY")],
-        Some("ANY"),
+        vec![ParameterSignature::variadic("args", SignatureType::Any)],
+        Some(SignatureType::Any),
     );
     assert_eq!(sig.min_arity(), 0);
     assert_eq!(sig.max_arity(), None);
@@ -300,10 +301,10 @@ fn test_signature_arity_helpers() {
         "test",
         CallableKind::Function,
         vec![
-            ParameterSignature::required("first", "INT"),
-            ParameterSignature::variadic("rest", "INT"),
+            ParameterSignature::required("first", Type::Int),
+            ParameterSignature::variadic("rest", Type::Int),
         ],
-        Some("INT"),
+        Some(Type::Int),
     );
     assert_eq!(sig.min_arity(), 1);
     assert_eq!(sig.max_arity(), None);
@@ -314,17 +315,17 @@ fn test_signature_arity_helpers() {
 
 #[test]
 fn test_parameter_signature_constructors() {
-    let param = ParameterSignature::required("x", "INT");
+    let param = ParameterSignature::required("x", Type::Int);
     assert!(!param.optional);
     assert!(!param.variadic);
     assert_eq!(param.name, "x");
-    assert_eq!(param.param_type, "INT");
+    assert_eq!(param.param_type, SignatureType::Exact(Type::Int));
 
-    let param = ParameterSignature::optional("y", "STRING");
+    let param = ParameterSignature::optional("y", Type::String);
     assert!(param.optional);
     assert!(!param.variadic);
 
-    let param = ParameterSignature::variadic("args", "ANY");
+    let param = ParameterSignature::variadic("args", SignatureType::Any);
     assert!(!param.optional);
     assert!(param.variadic);
 }
@@ -336,8 +337,8 @@ fn test_callable_signature_builder() {
     let sig = CallableSignature::new(
         "test",
         CallableKind::Function,
-        vec![ParameterSignature::required("x", "INT")],
-        Some("INT"),
+        vec![ParameterSignature::required("x", Type::Int)],
+        Some(Type::Int),
     )
     .with_volatility(Volatility::Volatile)
     .with_nullability(Nullability::CalledOnNullInput);
@@ -359,3 +360,73 @@ fn test_builtin_catalog_case_insensitive() {
     assert_eq!(sigs_lower.len(), sigs_mixed.len());
 }
 
+fn callable_errors(source: &str) -> Vec<String> {
+    let program = gql_parser::parse(source).ast.expect("query should parse");
+    SemanticValidator::new()
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.message.contains("cannot be applied"))
+        .map(|d| d.message)
+        .collect()
+}
+
+#[test]
+fn test_argument_type_mismatch_is_reported() {
+    let errors = callable_errors("RETURN abs('x') AS a");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains("abs"));
+    assert!(errors[0].contains("String"));
+
+    assert!(callable_errors("RETURN abs(1) AS a").is_empty());
+    assert!(callable_errors("RETURN abs(-2.5) AS a").is_empty());
+}
+
+#[test]
+fn test_aggregate_argument_type_mismatch_is_reported() {
+    assert_eq!(callable_errors("MATCH (n) RETURN sum('x') AS s").len(), 1);
+    assert!(callable_errors("MATCH (n) RETURN sum(n.age) AS s").is_empty());
+}
+
+#[test]
+fn test_generic_parameters_require_common_supertype() {
+    assert!(callable_errors("RETURN coalesce(1, 2.5, NULL) AS c").is_empty());
+    assert_eq!(callable_errors("RETURN coalesce(1, 'a') AS c").len(), 1);
+}
+
+#[test]
+fn test_overloads_resolved_by_argument_type() {
+    // date(STRING) and date(INT, INT, INT) are both accepted
+    assert!(callable_errors("RETURN date('2024-01-01') AS d").is_empty());
+    assert!(callable_errors("RETURN date(2024, 1, 1) AS d").is_empty());
+    assert_eq!(callable_errors("RETURN date(TRUE) AS d").len(), 1);
+}
+
+#[test]
+fn test_resolve_overload_instantiates_return_type() {
+    use gql_parser::semantic::callable::resolve_overload;
+
+    let sigs = resolve_builtin_signatures("coalesce", CallableKind::Function).unwrap();
+    let resolved = resolve_overload(&sigs, &[Type::Int, Type::Float]).unwrap();
+    assert_eq!(resolved.return_type, Some(Type::Float));
+
+    let sigs = resolve_builtin_signatures("collect", CallableKind::AggregateFunction).unwrap();
+    let resolved = resolve_overload(&sigs, &[Type::String]).unwrap();
+    assert_eq!(resolved.return_type, Some(Type::List(Box::new(Type::String))));
+}
+
+#[test]
+fn test_typed_call_reports_candidates() {
+    use gql_parser::semantic::callable::CallSite;
+
+    let sigs = resolve_builtin_signatures("substring", CallableKind::Function).unwrap();
+    let call = CallSite {
+        name: "substring",
+        kind: CallableKind::Function,
+        arg_count: 2,
+        span: 0..10,
+    };
+    let diags = DefaultCallableValidator.validate_typed_call(&call, &[Type::Int, Type::Int], &sigs);
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].notes.len(), 2);
+}
@@ -0,0 +1,178 @@
+//! Catalog overlay tests
+//!
+//! Catalog statements earlier in a program change the catalog that later
+//! statements are validated against.
+
+use gql_parser::diag::DiagSeverity;
+use gql_parser::ir::ValidationOutcome;
+use gql_parser::parse;
+use gql_parser::semantic::callable::{CallableKind, CallableSignature, SignatureType};
+use gql_parser::semantic::metadata_provider::MockMetadataProvider;
+use gql_parser::semantic::schema_catalog::{PropertyMeta, SchemaSnapshotBuilder};
+use gql_parser::semantic::validator::SemanticValidator;
+
+const SOCIAL_TYPE: &str = "CREATE GRAPH TYPE social AS { \
+    NODE TYPE Person LABEL Person { name :: STRING NOT NULL, age :: INT }, \
+    DIRECTED EDGE TYPE KNOWS { since :: DATE } CONNECTING (Person TO Person) }";
+
+fn provider() -> MockMetadataProvider {
+    let snapshot = SchemaSnapshotBuilder::new()
+        .with_node_type("Account", |b| b.add_property(PropertyMeta::int("id", true)))
+        .build();
+    let mut provider = MockMetadataProvider::new();
+    provider.add_schema_snapshot("default", snapshot.clone());
+    provider.add_schema_snapshot("bank", snapshot.clone());
+    provider.add_graph_type("ledger", snapshot);
+    provider.add_schema("finance");
+    provider.add_callable(
+        "audit",
+        CallableSignature::new("audit", CallableKind::Procedure, vec![], None::<SignatureType>),
+    );
+    provider
+}
+
+fn validate(source: &str) -> ValidationOutcome {
+    let program = parse(source).ast.expect("parse should succeed");
+    let provider = provider();
+    SemanticValidator::new()
+        .with_metadata_provider(&provider)
+        .validate(&program)
+}
+
+fn errors(source: &str) -> Vec<String> {
+    validate(source)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.severity == DiagSeverity::Error)
+        .map(|d| d.message)
+        .collect()
+}
+
+fn assert_valid(source: &str) {
+    let errors = errors(source);
+    assert!(errors.is_empty(), "unexpected errors for {source}: {errors:?}");
+}
+
+#[test]
+fn test_created_graph_is_usable_by_later_statements() {
+    assert_valid(&format!(
+        "{SOCIAL_TYPE}; CREATE GRAPH g :: social; USE g MATCH (n:Person)-[e:KNOWS]->(m) RETURN n"
+    ));
+}
+
+#[test]
+fn test_created_graph_type_is_checked_by_schema_validation() {
+    let errors = errors(&format!(
+        "{SOCIAL_TYPE}; CREATE GRAPH g :: social; USE g MATCH (n:Company) RETURN n"
+    ));
+    assert!(errors.iter().any(|e| e.contains("Company")), "{errors:?}");
+}
+
+#[test]
+fn test_graph_is_unknown_before_it_is_created() {
+    let errors = errors(&format!(
+        "USE g MATCH (n) RETURN n; {SOCIAL_TYPE}; CREATE GRAPH g :: social"
+    ));
+    assert_eq!(errors, vec!["Unknown graph 'g'"]);
+}
+
+#[test]
+fn test_use_of_dropped_graph_is_reported() {
+    let errors = errors("DROP GRAPH bank; USE bank MATCH (n) RETURN n");
+    assert_eq!(errors, vec!["Unknown graph 'bank'"]);
+}
+
+#[test]
+fn test_duplicate_create_is_reported() {
+    assert_eq!(errors("CREATE GRAPH bank ANY"), vec!["Graph 'bank' already exists"]);
+    assert_eq!(
+        errors("CREATE GRAPH TYPE ledger AS { NODE TYPE X LABEL X }"),
+        vec!["Graph type 'ledger' already exists"]
+    );
+    assert_eq!(errors("CREATE SCHEMA /finance"), vec!["Schema 'finance' already exists"]);
+}
+
+#[test]
+fn test_if_not_exists_and_or_replace_allow_existing_objects() {
+    assert_valid("CREATE GRAPH IF NOT EXISTS bank ANY");
+    assert_valid("CREATE OR REPLACE GRAPH bank ANY");
+    assert_valid("CREATE SCHEMA IF NOT EXISTS /finance");
+}
+
+#[test]
+fn test_if_not_exists_keeps_existing_graph_type() {
+    let errors = errors(&format!(
+        "CREATE GRAPH TYPE IF NOT EXISTS ledger AS {{ NODE TYPE Person LABEL Person }}; \
+         CREATE GRAPH g :: ledger; USE g MATCH (n:Person) RETURN n; {SOCIAL_TYPE}"
+    ));
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains("Person"));
+}
+
+#[test]
+fn test_or_replace_replaces_graph_type() {
+    assert_valid(
+        "CREATE OR REPLACE GRAPH TYPE ledger AS { NODE TYPE Person LABEL Person }; \
+         CREATE GRAPH g :: ledger; USE g MATCH (n:Person) RETURN n",
+    );
+}
+
+#[test]
+fn test_drop_of_missing_object_is_reported() {
+    assert_eq!(errors("DROP GRAPH nowhere"), vec!["Unknown graph 'nowhere'"]);
+    assert_eq!(errors("DROP GRAPH TYPE absent"), vec!["Unknown graph type 'absent'"]);
+    assert_eq!(errors("DROP SCHEMA /nobody"), vec!["Unknown schema 'nobody'"]);
+    assert_eq!(errors("DROP PROCEDURE absent"), vec!["Unknown procedure 'absent'"]);
+}
+
+#[test]
+fn test_drop_if_exists_allows_missing_objects() {
+    assert_valid("DROP GRAPH IF EXISTS nowhere");
+    assert_valid("DROP GRAPH TYPE IF EXISTS absent");
+    assert_valid("DROP PROCEDURE IF EXISTS absent");
+}
+
+#[test]
+fn test_drop_twice_is_reported() {
+    let errors = errors("DROP GRAPH bank; DROP GRAPH bank");
+    assert_eq!(errors, vec!["Unknown graph 'bank'"]);
+}
+
+#[test]
+fn test_create_graph_of_unknown_graph_type_is_reported() {
+    let errors = errors("CREATE GRAPH g :: missing; USE g MATCH (n) RETURN n");
+    assert_eq!(errors, vec!["Unknown graph type 'missing'"]);
+}
+
+#[test]
+fn test_graph_created_like_another_graph_shares_its_schema() {
+    assert_valid("CREATE GRAPH mirror LIKE bank; USE mirror MATCH (n:Account) RETURN n.id");
+    let errors = errors("CREATE GRAPH mirror LIKE bank; USE mirror MATCH (n:Person) RETURN n");
+    assert!(errors.iter().any(|e| e.contains("Person")), "{errors:?}");
+}
+
+#[test]
+fn test_created_procedure_yields_its_return_columns() {
+    assert_valid(
+        "CREATE PROCEDURE names() { MATCH (n:Account) RETURN n.id AS id }; \
+         CALL names() YIELD id RETURN id",
+    );
+    let errors = errors(
+        "CREATE PROCEDURE names() { MATCH (n:Account) RETURN n.id AS id }; \
+         CALL names() YIELD name RETURN name",
+    );
+    assert_eq!(errors, vec!["Procedure 'names' has no result column 'name'"]);
+}
+
+#[test]
+fn test_duplicate_procedure_is_reported() {
+    let errors = errors("CREATE PROCEDURE audit() { MATCH (n) RETURN n }");
+    assert_eq!(errors, vec!["Procedure 'audit' already exists"]);
+}
+
+#[test]
+fn test_catalog_statements_are_ignored_without_metadata_validation() {
+    let program = parse("DROP GRAPH nowhere").ast.expect("parse should succeed");
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+}
@@ -335,8 +335,12 @@ fn test_session_set_schema_in_transaction() {
     let source = "START TRANSACTION; SESSION SET SCHEMA myschema; MATCH (n) RETURN n; COMMIT";
     let outcome = validate_catalog(source);
 
-    // Session commands can be used within transactions
-    assert!(outcome.is_success(), "SESSION SET SCHEMA in transaction should validate: {:?}", outcome.diagnostics);
+    // Session settings cannot change while a transaction is active
+    assert!(!outcome.is_success(), "SESSION SET SCHEMA in transaction should be rejected");
+    assert!(outcome
+        .diagnostics
+        .iter()
+        .any(|d| d.message == "Session command cannot run inside a transaction"));
 }
 
 // ===== Section E: Session Commands - SET GRAPH =====
@@ -382,8 +386,12 @@ fn test_session_set_graph_in_transaction() {
     let source = "START TRANSACTION; SESSION SET GRAPH mygraph; MATCH (n) RETURN n; COMMIT";
     let outcome = validate_catalog(source);
 
-    // Session commands can be used within transactions
-    assert!(outcome.is_success(), "SESSION SET GRAPH in transaction should validate: {:?}", outcome.diagnostics);
+    // Session settings cannot change while a transaction is active
+    assert!(!outcome.is_success(), "SESSION SET GRAPH in transaction should be rejected");
+    assert!(outcome
+        .diagnostics
+        .iter()
+        .any(|d| d.message == "Session command cannot run inside a transaction"));
 }
 
 // ===== Section E: Session Commands - SET TIME ZONE =====
@@ -0,0 +1,150 @@
+//! Graph-type constraint validation tests
+//!
+//! KEY, UNIQUE, MANDATORY and CHECK constraints are checked against the
+//! properties declared by (or inherited into) their node or edge type, and
+//! carried into the catalog as `ConstraintMeta`.
+
+use gql_parser::ast::Expression;
+use gql_parser::diag::DiagSeverity;
+use gql_parser::parse;
+use gql_parser::semantic::metadata_provider::{MetadataProvider, MockMetadataProvider};
+use gql_parser::semantic::schema_catalog::{ConstraintMeta, TypeRef};
+use gql_parser::semantic::validator::SemanticValidator;
+use gql_parser::semantic::CatalogOverlay;
+
+fn errors(source: &str) -> Vec<String> {
+    let program = parse(source).ast.expect("parse should succeed");
+    SemanticValidator::new()
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.severity == DiagSeverity::Error)
+        .map(|d| d.message)
+        .collect()
+}
+
+fn graph_type(body: &str) -> String {
+    format!("CREATE GRAPH TYPE t AS {{ {body} }}")
+}
+
+#[test]
+fn test_constraints_on_declared_properties_are_valid() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person { id :: INT NOT NULL, email :: STRING, age :: INT } \
+         CONSTRAINT KEY (id) CONSTRAINT UNIQUE (email) CONSTRAINT MANDATORY (email) \
+         CONSTRAINT CHECK (age >= 0 AND age < 150)",
+    ));
+    assert!(errors.is_empty(), "{errors:?}");
+}
+
+#[test]
+fn test_unknown_property_in_property_list_is_reported() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person { id :: INT } CONSTRAINT UNIQUE (id, email)",
+    ));
+    assert_eq!(
+        errors,
+        vec!["UNIQUE constraint refers to property 'email', which 'Person' does not declare"]
+    );
+}
+
+#[test]
+fn test_unknown_property_in_check_is_reported() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person { age :: INT } CONSTRAINT CHECK (height > 0)",
+    ));
+    assert_eq!(
+        errors,
+        vec!["CHECK constraint refers to property 'height', which 'Person' does not declare"]
+    );
+}
+
+#[test]
+fn test_check_comparing_incompatible_types_is_reported() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person { name :: STRING } CONSTRAINT CHECK (name > 0)",
+    ));
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].starts_with("Type mismatch"), "{errors:?}");
+}
+
+#[test]
+fn test_non_boolean_check_is_reported() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person { age :: INT } CONSTRAINT CHECK (age + 1)",
+    ));
+    assert_eq!(errors, vec!["Type mismatch: expected Boolean, found Int"]);
+}
+
+#[test]
+fn test_inherited_properties_can_be_constrained() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Entity { id :: INT }, \
+         NODE TYPE Person INHERITS Entity { name :: STRING } CONSTRAINT KEY (id)",
+    ));
+    assert!(errors.is_empty(), "{errors:?}");
+}
+
+#[test]
+fn test_edge_constraints_are_checked() {
+    let errors = errors(&graph_type(
+        "NODE TYPE Person, \
+         DIRECTED EDGE TYPE KNOWS { since :: DATE } CONSTRAINT CHECK (weight > 0) \
+         CONNECTING (Person TO Person)",
+    ));
+    assert_eq!(
+        errors,
+        vec!["CHECK constraint refers to property 'weight', which 'KNOWS' does not declare"]
+    );
+}
+
+#[test]
+fn test_constraints_are_carried_into_catalog() {
+    let program = parse(&graph_type(
+        "NODE TYPE Person LABEL Person { id :: INT, age :: INT } \
+         CONSTRAINT KEY (id) CONSTRAINT CHECK (age >= 0)",
+    ))
+    .ast
+    .expect("parse should succeed");
+    let gql_parser::ast::Statement::Catalog(catalog) = &program.statements[0] else {
+        panic!("expected catalog statement");
+    };
+
+    let base = MockMetadataProvider::new();
+    let mut overlay = CatalogOverlay::new(&base);
+    assert!(overlay.apply(&catalog.kind).is_empty());
+
+    let snapshot = overlay.get_graph_type_snapshot("t").unwrap();
+    let constraints = snapshot.constraints(TypeRef::NodeType("Person".into()));
+    assert_eq!(constraints.len(), 2);
+    assert_eq!(
+        constraints[0],
+        ConstraintMeta::PrimaryKey {
+            properties: vec!["id".into()]
+        }
+    );
+    let ConstraintMeta::Check { expression } = &constraints[1] else {
+        panic!("expected CHECK constraint, got {:?}", constraints[1]);
+    };
+    assert!(matches!(expression, Expression::Comparison(..)));
+}
+
+#[test]
+fn test_property_default_and_check_are_validated() {
+    let valid = errors(&graph_type(
+        "NODE TYPE Person { age :: INT DEFAULT 0 CHECK (age >= 0), name :: STRING DEFAULT 'x' }",
+    ));
+    assert!(valid.is_empty(), "{valid:?}");
+
+    let invalid = errors(&graph_type(
+        "NODE TYPE Person { age :: INT DEFAULT 'old' CHECK (height > 0) }",
+    ));
+    assert_eq!(
+        invalid,
+        vec![
+            "Type mismatch: expected Int, found String",
+            "CHECK constraint refers to property 'height', which 'Person' does not declare",
+        ]
+    );
+}
+
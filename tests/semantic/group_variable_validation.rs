@@ -0,0 +1,114 @@
+//! Group variable tests
+//!
+//! Element variables declared inside a quantified path primary denote lists
+//! outside the quantifier and cannot be used there as single elements.
+
+use gql_parser::ir::symbol_table::DegreeOfReference;
+use gql_parser::ir::type_table::Type;
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn errors(source: &str) -> Vec<String> {
+    let program = parse(source)
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    SemanticValidator::new()
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|diag| diag.severity == gql_parser::DiagSeverity::Error)
+        .map(|diag| diag.message)
+        .collect()
+}
+
+#[test]
+fn test_quantified_variables_are_group_variables() {
+    let program = parse("MATCH TRAIL (a) ((x)-[e:KNOWS]->(y)){1,5} (b) RETURN a, e")
+        .ast
+        .unwrap();
+    let outcome = SemanticValidator::new().validate(&program);
+    let ir = outcome.ir.expect("validation succeeds");
+
+    let e = ir.symbol_table().lookup_all("e").unwrap();
+    assert_eq!(e[0].degree, DegreeOfReference::Group);
+    let a = ir.symbol_table().lookup_all("a").unwrap();
+    assert_eq!(a[0].degree, DegreeOfReference::Singleton);
+
+    assert_eq!(e[0].declared_type, Some(Type::List(Box::new(Type::Edge(None)))));
+    let x = ir.symbol_table().lookup_all("x").unwrap();
+    assert_eq!(x[0].declared_type, Some(Type::List(Box::new(Type::Node(None)))));
+    assert_eq!(a[0].declared_type, None);
+}
+
+#[test]
+fn test_optional_quantifier_keeps_singletons() {
+    let program = parse("MATCH (a)-[e]->?(b) RETURN e.since").ast.unwrap();
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+    let ir = outcome.ir.unwrap();
+    assert!(!ir.symbol_table().lookup_all("e").unwrap()[0].is_group());
+}
+
+#[test]
+fn test_singleton_references_inside_the_quantifier_are_allowed() {
+    for source in [
+        "MATCH TRAIL (a) ((x)-[e:ROAD WHERE e.km < 100]->(y WHERE y.open)){1,5} (b) RETURN a",
+        "MATCH TRAIL (a)-[e]->+(b) RETURN sum(e.km) AS total, count(e) AS hops",
+        "MATCH TRAIL (a)-[e]->+(b) RETURN e",
+    ] {
+        assert!(errors(source).is_empty(), "{source}: {:?}", errors(source));
+    }
+}
+
+#[test]
+fn test_group_variable_used_as_singleton() {
+    let expected = vec!["Group variable 'e' cannot be used as a single element here"];
+    assert_eq!(
+        errors("MATCH TRAIL (a) ((x)-[e]->(y)){1,5} (b) WHERE e.since > 2000 RETURN a"),
+        expected
+    );
+    assert_eq!(errors("MATCH TRAIL (a)-[e]->+(b) RETURN e.since"), expected);
+    assert_eq!(
+        errors("MATCH TRAIL (a)-[e]->+(b) FILTER e.since > 2000 RETURN a"),
+        expected
+    );
+
+    // `y` is a group variable relative to the outer iteration that declares `x`.
+    assert_eq!(
+        errors("MATCH TRAIL ((x WHERE y.v > 1)-[f]->(m) ((y)-[e]->(z)){2}){1,3} RETURN x"),
+        vec!["Group variable 'y' cannot be used as a single element here"]
+    );
+}
+
+#[test]
+fn test_group_variable_compared_with_equals() {
+    assert_eq!(
+        errors("MATCH TRAIL (a)-[e]->{1,3}(b), (c)-[f]->(d) WHERE e = f RETURN a"),
+        vec!["Group variable 'e' cannot be compared for equality"]
+    );
+    assert!(errors("MATCH TRAIL (a)-[e]->{1,3}(b) FILTER size(e) = 2 RETURN a").is_empty());
+}
+
+#[test]
+fn test_group_variable_compared_with_itself_is_reported_once() {
+    assert_eq!(
+        errors("MATCH TRAIL (a) ((x)-[e]->(y)){1,3} (b) WHERE e = e RETURN a"),
+        vec!["Group variable 'e' cannot be compared for equality"]
+    );
+    assert_eq!(
+        errors("MATCH TRAIL (a)-[e]->{1,3}(b)-[f]->{1,3}(c) WHERE e <> f RETURN a"),
+        vec!["Group variable 'e' cannot be compared for equality"]
+    );
+}
+
+#[test]
+fn test_group_and_singleton_declarations_cannot_mix() {
+    assert_eq!(
+        errors("MATCH TRAIL (a) ((a)-[e]->(b))+ (c) RETURN c"),
+        vec!["Variable 'a' is declared both as a group variable and as a singleton"]
+    );
+    assert_eq!(
+        errors("MATCH TRAIL (a)-[e]->+(b) MATCH (c)-[e]->(d) RETURN c"),
+        vec!["Variable 'e' is declared both as a group variable and as a singleton"]
+    );
+}
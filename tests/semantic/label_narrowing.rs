@@ -0,0 +1,172 @@
+//! Label narrowing tests
+//!
+//! Element variables are narrowed to the schema types their label
+//! expressions, predicates and inherited labels allow; property references
+//! are then resolved against those candidate types.
+
+use gql_parser::diag::DiagSeverity;
+use gql_parser::ir::type_table::Type;
+use gql_parser::parse;
+use gql_parser::semantic::metadata_provider::MockMetadataProvider;
+use gql_parser::semantic::schema_catalog::{PropertyMeta, SchemaSnapshotBuilder, TypeRef};
+use gql_parser::semantic::validator::SemanticValidator;
+use gql_parser::ir::ValidationOutcome;
+
+/// Entity <- Person, Entity <- Company, plus an unrelated Robot.
+fn provider() -> MockMetadataProvider {
+    let snapshot = SchemaSnapshotBuilder::new()
+        .with_node_type("Entity", |b| b.add_property(PropertyMeta::int("id", true)))
+        .with_node_type("Person", |b| {
+            b.add_parent(TypeRef::NodeType("Entity".into()))
+                .add_property(PropertyMeta::string("name", true))
+                .add_property(PropertyMeta::int("age", false))
+        })
+        .with_node_type("Company", |b| {
+            b.add_parent(TypeRef::NodeType("Entity".into()))
+                .add_property(PropertyMeta::string("name", true))
+                .add_property(PropertyMeta::decimal("revenue", false, 18, 2))
+        })
+        .with_node_type("Robot", |b| b.add_property(PropertyMeta::string("serial", true)))
+        .with_edge_type("KNOWS", |b| b.add_property(PropertyMeta::int("since", false)))
+        .with_edge_type("WORKS_AT", |b| b.add_property(PropertyMeta::string("role", false)))
+        .build();
+
+    let mut provider = MockMetadataProvider::new();
+    provider.add_schema_snapshot("default", snapshot);
+    provider
+}
+
+fn validate(source: &str) -> ValidationOutcome {
+    let program = parse(source).ast.expect("parse should succeed");
+    let provider = provider();
+    SemanticValidator::new()
+        .with_metadata_provider(&provider)
+        .validate(&program)
+}
+
+fn property_errors(source: &str) -> Vec<String> {
+    validate(source)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.severity == DiagSeverity::Error && d.message.starts_with("Property"))
+        .map(|d| d.message)
+        .collect()
+}
+
+fn assert_valid(source: &str) {
+    let errors = property_errors(source);
+    assert!(errors.is_empty(), "unexpected errors for {source}: {errors:?}");
+}
+
+fn assert_invalid_property(source: &str, property: &str) {
+    let errors = property_errors(source);
+    assert!(
+        errors.iter().any(|m| m.contains(&format!("'{property}'"))),
+        "expected '{property}' to be rejected in {source}, got {errors:?}"
+    );
+}
+
+#[test]
+fn test_single_label_resolves_properties() {
+    assert_valid("MATCH (n:Person) RETURN n.name, n.age");
+    assert_invalid_property("MATCH (n:Person) RETURN n.revenue", "revenue");
+}
+
+#[test]
+fn test_disjunction_accepts_property_of_any_candidate() {
+    assert_valid("MATCH (n:Person|Company) RETURN n.name, n.age, n.revenue");
+    assert_invalid_property("MATCH (n:Person|Company) RETURN n.serial", "serial");
+}
+
+#[test]
+fn test_inherited_labels_and_properties() {
+    // Person and Company carry the Entity label and inherit `id`.
+    assert_valid("MATCH (n:Entity) RETURN n.id, n.name");
+    assert_valid("MATCH (n:Person) RETURN n.id");
+    assert_invalid_property("MATCH (n:Entity) RETURN n.serial", "serial");
+}
+
+#[test]
+fn test_negation_and_conjunction() {
+    assert_invalid_property("MATCH (n:!Entity) RETURN n.name", "name");
+    assert_valid("MATCH (n:!Entity) RETURN n.serial");
+    assert_valid("MATCH (n:Person&Entity) RETURN n.age");
+    assert_invalid_property("MATCH (n:Entity&!Person) RETURN n.age", "age");
+}
+
+#[test]
+fn test_is_label_syntax_in_pattern() {
+    assert_invalid_property("MATCH (n IS Robot) RETURN n.name", "name");
+}
+
+#[test]
+fn test_where_label_predicate_narrows() {
+    assert_valid("MATCH (n) WHERE n:Robot RETURN n.serial");
+    assert_invalid_property("MATCH (n:Person|Robot) WHERE n:Robot RETURN n.age", "age");
+    assert_invalid_property(
+        "MATCH (n:Person|Robot) WHERE NOT n:Person RETURN n.age",
+        "age",
+    );
+    assert_invalid_property(
+        "MATCH (n) WHERE n:Person OR n:Company RETURN n.serial",
+        "serial",
+    );
+}
+
+#[test]
+fn test_filter_is_labeled_narrows() {
+    assert_invalid_property(
+        "MATCH (n:Person|Robot) FILTER n IS LABELED Person RETURN n.serial",
+        "serial",
+    );
+    assert_valid("MATCH (n:Person|Robot) FILTER n IS NOT LABELED Person RETURN n.serial");
+}
+
+#[test]
+fn test_optional_match_narrowing_does_not_leak() {
+    assert_valid("MATCH (n:Person|Robot) OPTIONAL MATCH (n) WHERE n:Robot RETURN n.age");
+    assert_invalid_property(
+        "MATCH (n:Person|Robot) OPTIONAL MATCH (n) WHERE n:Robot AND n.age > 1 RETURN n",
+        "age",
+    );
+}
+
+#[test]
+fn test_edge_variables_and_property_maps() {
+    assert_valid("MATCH (a)-[e:KNOWS]->(b) RETURN e.since");
+    assert_invalid_property("MATCH (a)-[e:KNOWS]->(b) RETURN e.role", "role");
+    assert_invalid_property("MATCH (n:Robot {age: 3}) RETURN n", "age");
+}
+
+#[test]
+fn test_unsatisfiable_labels_warn() {
+    let outcome = validate("MATCH (n:Person&Robot) RETURN n");
+    assert!(outcome.is_success());
+    assert!(
+        outcome
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == DiagSeverity::Warning && d.message.contains("'n'"))
+    );
+}
+
+#[test]
+fn test_narrowed_types_recorded() {
+    let source = "MATCH (n:Person|Company) LET x = n.name, y = n.age RETURN x, y";
+    let outcome = validate(source);
+    let ir = outcome.ir.expect("validation should succeed");
+    let type_at = |text: &str| {
+        let start = source.find(text).unwrap();
+        ir.type_table()
+            .get_type_by_span(&(start..start + text.len()))
+            .cloned()
+    };
+
+    assert_eq!(type_at("n.name"), Some(Type::not_null(Type::String)));
+    assert_eq!(type_at("n.age"), Some(Type::Int));
+    let n = source.find("n.name").unwrap();
+    assert_eq!(
+        ir.type_table().get_type_by_span(&(n..n + 1)).cloned(),
+        Some(Type::Node(Some(vec!["Company".into(), "Person".into()])))
+    );
+}
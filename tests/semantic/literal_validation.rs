@@ -0,0 +1,95 @@
+//! Literal validation tests
+//!
+//! Malformed and out-of-range numeric, byte string and temporal literals are
+//! rejected with diagnostics pointing at the offending component.
+
+use gql_parser::diag::{Diag, DiagSeverity};
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn literal_errors(source: &str) -> Vec<Diag> {
+    let program = parse(source).ast.expect("parse should succeed");
+    SemanticValidator::new()
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.severity == DiagSeverity::Error && d.message.starts_with("Invalid"))
+        .collect()
+}
+
+/// Asserts exactly one literal error whose primary label covers `text`.
+fn assert_error_at(source: &str, text: &str) {
+    let errors = literal_errors(source);
+    assert_eq!(errors.len(), 1, "expected one literal error in {source}: {errors:?}");
+    let start = source.find(text).expect("text should be in source");
+    assert_eq!(
+        errors[0].labels[0].span,
+        start..start + text.len(),
+        "unexpected span for {source}: {}",
+        errors[0].message
+    );
+}
+
+#[test]
+fn test_valid_literals_pass() {
+    let source = "RETURN DATE '2024-02-29', TIME '23:59:59.999+01:00', \
+                  DATETIME '2024-01-01T00:00:00Z', DURATION 'P1Y2M3DT4H5M6.5S', \
+                  X'0AFF', 18446744073709551615, 1.5e10, 12.50";
+    let errors = literal_errors(source);
+    assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+}
+
+#[test]
+fn test_date_components_out_of_range() {
+    assert_error_at("RETURN DATE '2024-13-45'", "13");
+    assert_error_at("RETURN DATE '2023-02-29'", "29");
+}
+
+#[test]
+fn test_time_and_datetime_components() {
+    assert_error_at("RETURN TIME '25:00'", "25");
+    assert_error_at("RETURN DATETIME '2024-01-01T10:61:00'", "61");
+    assert_error_at("RETURN TIMESTAMP '2024-01-01X10:00'", "X");
+}
+
+#[test]
+fn test_duration_designators() {
+    assert_error_at("RETURN DURATION 'P1Q'", "Q");
+    assert_eq!(literal_errors("RETURN DURATION 'PT'").len(), 1);
+}
+
+#[test]
+fn test_integer_overflow() {
+    let big = "340282366920938463463374607431768211456";
+    assert_error_at(&format!("RETURN {big}"), big);
+    // Larger than any 64-bit type but still representable.
+    assert!(literal_errors("RETURN 99999999999999999999999").is_empty());
+}
+
+#[test]
+fn test_literals_in_nested_expressions_and_mutations() {
+    assert_error_at(
+        "MATCH (n) LET xs = [DATE '2024-01-01', DATE '2024-00-01'] RETURN xs",
+        "00",
+    );
+    assert_error_at("INSERT (:Event {starts: DATE '2024-04-31'})", "31");
+}
+
+#[test]
+fn test_duration_component_overflow() {
+    let errors = literal_errors("RETURN DURATION 'P99999999999999999999Y'");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("out of range"), "{}", errors[0].message);
+    assert_error_at(
+        "RETURN DURATION 'P99999999999999999999Y'",
+        "99999999999999999999",
+    );
+}
+
+#[test]
+fn test_literals_in_calls_procedures_and_sessions() {
+    assert_error_at("CALL my_proc(DATE '2024-13-01')", "13");
+    assert_error_at("MATCH (n) CALL { RETURN DATE '2024-02-30' AS d } RETURN n", "30");
+    assert_error_at("CREATE PROCEDURE p() { RETURN TIME '24:30' }", "24");
+    assert_error_at("SESSION SET VALUE $timeout = DURATION 'P1Q'", "Q");
+}
@@ -15,10 +15,20 @@ mod mutation_validation;
 mod procedure_validation;
 mod path_pattern_validation;
 mod label_expression_validation;
+mod label_narrowing;
+mod literal_validation;
+mod page_count_validation;
 mod subquery_exists_validation;
 mod type_system_validation;
 mod set_operations_validation;
 mod case_expression_validation;
 mod predicate_validation;
 mod catalog_session_validation;
+mod catalog_overlay;
+mod graph_type_constraints;
+mod schema_diff;
+mod schema_export;
+mod transaction_validation;
+mod quantifier_validation;
+mod group_variable_validation;
 mod edge_case_regression_validation;
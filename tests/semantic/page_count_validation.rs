@@ -0,0 +1,79 @@
+//! LIMIT / OFFSET / SKIP validation tests
+//!
+//! Constant page counts are evaluated at compile time and must be
+//! non-negative integers; counts built from parameters are left to the engine.
+
+use gql_parser::diag::{Diag, DiagSeverity};
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn errors(source: &str) -> Vec<Diag> {
+    let program = parse(source).ast.expect("parse should succeed");
+    SemanticValidator::new()
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.severity == DiagSeverity::Error)
+        .collect()
+}
+
+#[test]
+fn test_constant_counts_pass() {
+    assert!(errors("MATCH (n) ORDER BY n.x OFFSET 2 * 3 LIMIT 10 RETURN n").is_empty());
+    assert!(errors("MATCH (n) ORDER BY n.x SKIP 0 RETURN n").is_empty());
+    assert!(errors("MATCH (n) ORDER BY n.x LIMIT COALESCE(NULL, 5) RETURN n").is_empty());
+}
+
+#[test]
+fn test_parameter_counts_are_not_checked() {
+    assert!(errors("MATCH (n) ORDER BY n.x LIMIT $k RETURN n").is_empty());
+    assert!(errors("MATCH (n) ORDER BY n.x OFFSET $k - 1 RETURN n").is_empty());
+}
+
+#[test]
+fn test_negative_and_non_integer_counts_rejected() {
+    let source = "MATCH (n) ORDER BY n.x LIMIT -1 RETURN n";
+    let errors = errors(source);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("LIMIT"));
+    let start = source.find("-1").unwrap();
+    assert_eq!(errors[0].labels[0].span, start..start + 2);
+
+    let errors = self::errors("MATCH (n) ORDER BY n.x SKIP 1.5 RETURN n");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.starts_with("SKIP"));
+
+    assert_eq!(self::errors("MATCH (n) ORDER BY n.x OFFSET 'a' RETURN n").len(), 1);
+}
+
+#[test]
+fn test_evaluation_errors_reported() {
+    let errors = errors("MATCH (n) ORDER BY n.x SKIP 1 / 0 RETURN n");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("zero"), "{}", errors[0].message);
+}
+
+#[test]
+fn test_counts_in_select_and_mutations() {
+    assert_eq!(errors("SELECT 1 AS x LIMIT -2").len(), 1);
+    assert_eq!(
+        errors("MATCH (n) ORDER BY n.x LIMIT -1 SET n.seen = TRUE RETURN n").len(),
+        1
+    );
+}
+
+#[test]
+fn test_counts_after_return_are_checked() {
+    assert!(errors("MATCH (n) RETURN n.x AS x ORDER BY x OFFSET 1 LIMIT 10").is_empty());
+    let errors = errors("MATCH (n) RETURN n.x AS x ORDER BY x LIMIT -1");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("LIMIT"));
+}
+
+#[test]
+fn test_sort_keys_after_return_may_name_aliases() {
+    assert!(errors("MATCH (n) RETURN n.x AS x ORDER BY x, n.y").is_empty());
+    let errors = errors("MATCH (n) RETURN n.x AS x ORDER BY y");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("'y'"), "{errors:?}");
+}
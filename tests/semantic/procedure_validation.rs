@@ -8,8 +8,9 @@ use gql_parser::semantic::validator::{SemanticValidator, ValidationConfig};
 use gql_parser::ir::ValidationOutcome;
 use gql_parser::semantic::callable::{
     CallableSignature, CallableKind,
-    ParameterSignature, Volatility, Nullability,
+    ParameterSignature, ProcedureColumn, SignatureType, Volatility, Nullability,
 };
+use gql_parser::ir::type_table::Type;
 use gql_parser::semantic::metadata_provider::{MetadataProvider, MockMetadataProvider};
 use smol_str::SmolStr;
 
@@ -88,18 +89,19 @@ fn test_procedure_with_correct_arity_validates() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("arg1"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
             ParameterSignature {
                 name: SmolStr::new("arg2"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -120,12 +122,13 @@ fn test_procedure_with_wrong_arity_fails() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("arg1"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -161,7 +164,8 @@ fn test_yield_valid_field_validates() {
         name: SmolStr::new("my_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -180,7 +184,8 @@ fn test_yield_invalid_field_fails() {
         name: SmolStr::new("my_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -244,7 +249,8 @@ fn test_yield_with_alias_validates() {
         name: SmolStr::new("my_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -266,7 +272,8 @@ fn test_yield_multiple_fields_validates() {
         name: SmolStr::new("multi_result_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -287,7 +294,8 @@ fn test_yield_star_validates() {
         name: SmolStr::new("my_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -314,18 +322,19 @@ fn test_procedure_with_variadic_arguments() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("arg1"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
             ParameterSignature {
                 name: SmolStr::new("rest"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: true,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -348,18 +357,19 @@ fn test_procedure_with_optional_parameters() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("required"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
             ParameterSignature {
                 name: SmolStr::new("optional"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: true,
                 variadic: false,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -409,7 +419,8 @@ fn test_optional_call_variable_scope() {
         name: SmolStr::new("my_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -431,12 +442,13 @@ fn test_procedure_call_with_expression_arguments() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("x"),
-                param_type: SmolStr::new("INT64"),
+                param_type: Type::Int.into(),
                 optional: false,
                 variadic: false,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -479,7 +491,8 @@ fn test_procedure_call_with_chained_clauses() {
         name: SmolStr::new("filter_proc"),
         kind: CallableKind::Procedure,
         parameters: vec![],
-        return_type: Some(SmolStr::new("node")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("node", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -500,6 +513,7 @@ fn test_procedure_call_without_yield() {
         kind: CallableKind::Procedure,
         parameters: vec![],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -521,12 +535,13 @@ fn test_procedure_null_handling() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("value"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
         ],
-        return_type: Some(SmolStr::new("result")),
+        return_type: None,
+        columns: vec![ProcedureColumn::new("result", SignatureType::Any)],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -552,18 +567,19 @@ fn test_procedure_with_too_few_arguments_fails() {
         parameters: vec![
             ParameterSignature {
                 name: SmolStr::new("arg1"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
             ParameterSignature {
                 name: SmolStr::new("arg2"),
-                param_type: SmolStr::new("ANY"),
+                param_type: SignatureType::Any,
                 optional: false,
                 variadic: false,
             },
         ],
         return_type: None,
+        columns: vec![],
         volatility: Volatility::Volatile,
         nullability: Nullability::NullOnNullInput,
     });
@@ -611,3 +627,105 @@ fn test_optional_procedure_failure_handling() {
             ),
             "Expected diagnostic about undefined procedure, got: {:?}", outcome.diagnostics);
 }
+
+// ===== Result columns =====
+
+fn catalog_with_columns() -> MockMetadataProvider {
+
+    let mut catalog = MockMetadataProvider::new();
+    catalog.add_callable(
+        "top_users",
+        CallableSignature::new("top_users", CallableKind::Procedure, vec![], None::<SignatureType>)
+            .with_columns(vec![
+                ProcedureColumn::new("user_id", Type::Int).not_null(),
+                ProcedureColumn::new("score", Type::Float),
+            ]),
+    );
+    catalog
+}
+
+#[test]
+fn test_yield_unknown_column_fails() {
+    let catalog = catalog_with_columns();
+    let outcome = validate_with_procedures("CALL top_users() YIELD user_id, rank RETURN 1", &catalog);
+
+    let errors: Vec<_> = outcome
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("has no result column"))
+        .collect();
+    assert_eq!(errors.len(), 1, "Diagnostics: {:?}", outcome.diagnostics);
+    assert!(errors[0].message.contains("'rank'"));
+    assert!(errors[0].notes.iter().any(|n| n.contains("user_id, score")));
+}
+
+#[test]
+fn test_yield_duplicate_alias_fails() {
+    let catalog = catalog_with_columns();
+    let outcome = validate_with_procedures(
+        "CALL top_users() YIELD user_id AS x, score AS x RETURN x",
+        &catalog,
+    );
+
+    assert!(
+        outcome.diagnostics.iter().any(|d| d.message.contains("yielded more than once")),
+        "Diagnostics: {:?}",
+        outcome.diagnostics
+    );
+}
+
+#[test]
+fn test_yielded_variables_are_typed() {
+    use gql_parser::ir::symbol_table::SymbolKind;
+
+    let catalog = catalog_with_columns();
+    let outcome = validate_with_procedures(
+        "CALL top_users() YIELD user_id, score AS s RETURN user_id, s",
+        &catalog,
+    );
+    assert!(outcome.is_success(), "Diagnostics: {:?}", outcome.diagnostics);
+    let ir = outcome.unwrap();
+
+    let symbol = &ir.symbol_table().lookup_all("s").unwrap()[0];
+    assert_eq!(symbol.kind, SymbolKind::YieldVariable);
+    assert_eq!(symbol.declared_type, Some(Type::Float));
+    assert_eq!(
+        yielded_type(&ir, "user_id"),
+        Some(Type::not_null(Type::Int))
+    );
+}
+
+#[test]
+fn test_call_without_yield_binds_all_columns() {
+    let catalog = catalog_with_columns();
+    let outcome = validate_with_procedures("CALL top_users() RETURN user_id, score", &catalog);
+    assert!(outcome.is_success(), "Diagnostics: {:?}", outcome.diagnostics);
+    let ir = outcome.unwrap();
+    assert_eq!(yielded_type(&ir, "score"), Some(Type::Float));
+
+    // OPTIONAL CALL may bind nulls even for NOT NULL columns
+    let outcome = validate_with_procedures("OPTIONAL CALL top_users() RETURN user_id", &catalog);
+    assert!(outcome.is_success(), "Diagnostics: {:?}", outcome.diagnostics);
+    assert_eq!(yielded_type(&outcome.unwrap(), "user_id"), Some(Type::Int));
+}
+
+fn yielded_type(ir: &gql_parser::ir::IR, name: &str) -> Option<Type> {
+    ir.symbol_table().lookup_all(name)?[0].declared_type.clone()
+}
+#[test]
+fn test_same_name_in_union_branches_keeps_each_type() {
+    let catalog = catalog_with_columns();
+    let source = "CALL top_users() YIELD score AS v RETURN v \
+                  UNION ALL CALL top_users() YIELD user_id AS v RETURN v";
+    let outcome = validate_with_procedures(source, &catalog);
+    assert!(outcome.is_success(), "Diagnostics: {:?}", outcome.diagnostics);
+    let ir = outcome.unwrap();
+
+    let first = source.find("RETURN v").unwrap() + "RETURN ".len();
+    let second = source.rfind("RETURN v").unwrap() + "RETURN ".len();
+    assert_eq!(ir.type_table().get_type_by_span(&(first..first + 1)), Some(&Type::Float));
+    assert_eq!(
+        ir.type_table().get_type_by_span(&(second..second + 1)),
+        Some(&Type::not_null(Type::Int))
+    );
+}
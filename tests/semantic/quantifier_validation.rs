@@ -0,0 +1,120 @@
+//! Finiteness checks for quantified path patterns
+//!
+//! Unbounded quantifiers need a restrictive path mode or a selective path
+//! search; nested quantifiers need a restrictive mode, and KEEP clauses cannot
+//! be combined with path patterns that already select paths.
+
+use gql_parser::diag::{Diag, DiagSeverity, LabelRole};
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn diagnostics(source: &str, strict: bool) -> Vec<Diag> {
+    let program = parse(source)
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    SemanticValidator::new()
+        .with_strict_mode(strict)
+        .validate(&program)
+        .diagnostics
+        .into_iter()
+        .filter(|diag| diag.message.contains("quantifier") || diag.message.contains("KEEP"))
+        .collect()
+}
+
+fn primary_span(diag: &Diag) -> std::ops::Range<usize> {
+    diag.labels
+        .iter()
+        .find(|label| label.role == LabelRole::Primary)
+        .map(|label| label.span.clone())
+        .expect("primary label")
+}
+
+#[test]
+fn test_unbounded_walk_is_a_warning_by_default() {
+    let source = "MATCH (a:Person)-[e:KNOWS]->{2,}(b:Person) RETURN b";
+    let diags = diagnostics(source, false);
+    assert_eq!(diags.len(), 1, "{diags:?}");
+    assert_eq!(diags[0].severity, DiagSeverity::Warning);
+    assert_eq!(
+        diags[0].message,
+        "Unbounded quantifier may match infinitely many paths"
+    );
+    assert_eq!(&source[primary_span(&diags[0])], "{2,}");
+    assert!(diags[0].help.is_some());
+}
+
+#[test]
+fn test_unbounded_walk_is_an_error_in_strict_mode() {
+    for source in [
+        "MATCH (a)-[e]->*(b) RETURN b",
+        "MATCH WALK (a)-[e]->+(b) RETURN b",
+        "MATCH ALL PATHS (a)-[e]->+(b) RETURN b",
+        "MATCH (a) INSERT (a)-[:SEEN]->(:Visit) MATCH (a)-[e]->+(b) RETURN b",
+    ] {
+        let diags = diagnostics(source, true);
+        assert_eq!(diags.len(), 1, "{source}: {diags:?}");
+        assert_eq!(diags[0].severity, DiagSeverity::Error, "{source}");
+    }
+}
+
+#[test]
+fn test_restrictive_and_selective_patterns_are_finite() {
+    for source in [
+        "MATCH (a)-[e]->{1,5}(b) RETURN b",
+        "MATCH (a)-[e]->?(b) RETURN b",
+        "MATCH TRAIL (a)-[e]->+(b) RETURN b",
+        "MATCH ALL ACYCLIC (a)-[e]->*(b) RETURN b",
+        "MATCH ANY (a)-[e]->+(b) RETURN b",
+        "MATCH p = ALL SHORTEST (a)-[e]->+(b) RETURN p",
+        "MATCH SHORTEST 3 GROUPS (a)-[e]->+(b) RETURN b",
+        "MATCH DIFFERENT EDGES (a)-[e]->+(b) RETURN b",
+        "MATCH (a)-[e]->+(b) KEEP SIMPLE RETURN b",
+        "MATCH (a)-[e]->+(b) KEEP ANY SHORTEST RETURN b",
+    ] {
+        let diags = diagnostics(source, true);
+        assert!(diags.is_empty(), "{source}: {diags:?}");
+    }
+}
+
+#[test]
+fn test_nested_quantifiers_need_a_restrictive_mode() {
+    let source = "MATCH ANY SHORTEST (a) ((x)-[e]->+(y))* (b) RETURN a";
+    let diags = diagnostics(source, true);
+    assert_eq!(diags.len(), 1, "{diags:?}");
+    assert_eq!(
+        diags[0].message,
+        "Nested quantifier requires a restrictive path mode"
+    );
+    assert_eq!(primary_span(&diags[0]), 33..34);
+    assert!(
+        diags[0]
+            .labels
+            .iter()
+            .any(|label| label.role == LabelRole::Secondary && &source[label.span.clone()] == "*")
+    );
+
+    for source in [
+        "MATCH ANY SHORTEST TRAIL (a) ((x)-[e]->+(y))* (b) RETURN a",
+        "MATCH (a) ((x)-[e]->{1,2}(y)){2} (b) RETURN a",
+    ] {
+        assert!(diagnostics(source, true).is_empty(), "{source}");
+    }
+}
+
+#[test]
+fn test_keep_conflicts_with_selective_path_patterns() {
+    let source = "MATCH ANY (a)-[e]->+(b) KEEP TRAIL RETURN b";
+    let diags = diagnostics(source, false);
+    assert_eq!(diags.len(), 1, "{diags:?}");
+    assert_eq!(diags[0].severity, DiagSeverity::Error);
+    assert_eq!(
+        diags[0].message,
+        "Path search prefix cannot be combined with a KEEP clause"
+    );
+    assert_eq!(&source[primary_span(&diags[0])], "ANY");
+
+    // A KEEP clause that neither restricts nor selects leaves the path unbounded.
+    let diags = diagnostics("MATCH (a)-[e]->+(b) KEEP WALK RETURN b", false);
+    assert_eq!(diags.len(), 1, "{diags:?}");
+    assert_eq!(diags[0].severity, DiagSeverity::Warning);
+}
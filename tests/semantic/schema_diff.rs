@@ -0,0 +1,308 @@
+//! Schema diff tests
+//!
+//! Changes between two schema versions are listed per type and classified as
+//! compatible or breaking; saved queries are re-validated against the new
+//! schema.
+
+use std::sync::Arc;
+
+use gql_parser::ast::{CatalogStatementKind, GraphTypeSource, Statement};
+use gql_parser::ast::graph_type::NestedGraphTypeSpecification;
+use gql_parser::parse;
+use gql_parser::semantic::schema_catalog::{
+    ConstraintMeta, PropertyMeta, SchemaSnapshot, SchemaSnapshotBuilder, TypeRef,
+};
+use gql_parser::semantic::schema_diff::{
+    Compatibility, SchemaChange, TypeChange, affected_queries, graph_type_diff, schema_diff,
+};
+
+fn specification(body: &str) -> NestedGraphTypeSpecification {
+    let program = parse(&format!("CREATE GRAPH TYPE t AS {{ {body} }}"))
+        .ast
+        .expect("parse should succeed");
+    let Statement::Catalog(catalog) = &program.statements[0] else {
+        panic!("expected catalog statement");
+    };
+    let CatalogStatementKind::CreateGraphType(create) = &catalog.kind else {
+        panic!("expected CREATE GRAPH TYPE");
+    };
+    let Some(GraphTypeSource::Detailed { specification, .. }) = &create.source else {
+        panic!("expected detailed graph type");
+    };
+    specification.clone()
+}
+
+fn changes(old: &str, new: &str) -> Vec<SchemaChange> {
+    graph_type_diff(&specification(old), &specification(new)).changes
+}
+
+fn person() -> TypeRef {
+    TypeRef::NodeType("Person".into())
+}
+
+#[test]
+fn test_added_and_removed_types() {
+    let changes = changes(
+        "NODE TYPE Person LABEL Person, NODE TYPE City LABEL City",
+        "NODE TYPE Person LABEL Person, NODE TYPE Company LABEL Company",
+    );
+    assert_eq!(
+        changes,
+        vec![
+            SchemaChange::TypeRemoved {
+                owner: TypeRef::NodeType("City".into())
+            },
+            SchemaChange::TypeAdded {
+                owner: TypeRef::NodeType("Company".into())
+            },
+        ]
+    );
+    assert_eq!(changes[0].compatibility(), Compatibility::Breaking);
+    assert_eq!(changes[1].compatibility(), Compatibility::Compatible);
+}
+
+#[test]
+fn test_property_additions_depend_on_required_flag() {
+    let diff = graph_type_diff(
+        &specification("NODE TYPE Person LABEL Person { name :: STRING }"),
+        &specification(
+            "NODE TYPE Person LABEL Person { name :: STRING, nick :: STRING, id :: INT NOT NULL }",
+        ),
+    );
+    assert_eq!(diff.changes.len(), 2, "{:?}", diff.changes);
+    let breaking: Vec<_> = diff.breaking().collect();
+    assert_eq!(
+        breaking,
+        vec![&SchemaChange::PropertyAdded {
+            owner: person(),
+            property: "id".into(),
+            required: true,
+        }]
+    );
+    assert_eq!(diff.compatible().count(), 1);
+}
+
+#[test]
+fn test_property_removal_is_breaking() {
+    let changes = changes(
+        "NODE TYPE Person LABEL Person { name :: STRING, age :: INT }",
+        "NODE TYPE Person LABEL Person { name :: STRING }",
+    );
+    assert_eq!(
+        changes,
+        vec![SchemaChange::PropertyRemoved {
+            owner: person(),
+            property: "age".into()
+        }]
+    );
+    assert!(changes[0].is_breaking());
+}
+
+#[test]
+fn test_type_widening_and_narrowing() {
+    let widened = changes(
+        "NODE TYPE Person LABEL Person { age :: INT32 }",
+        "NODE TYPE Person LABEL Person { age :: INT64 }",
+    );
+    assert!(
+        matches!(
+            widened.as_slice(),
+            [SchemaChange::PropertyTypeChanged {
+                change: TypeChange::Widened,
+                ..
+            }]
+        ),
+        "{widened:?}"
+    );
+    assert!(!widened[0].is_breaking());
+
+    let narrowed = changes(
+        "NODE TYPE Person LABEL Person { age :: INT64 }",
+        "NODE TYPE Person LABEL Person { age :: INT32 }",
+    );
+    assert!(
+        matches!(
+            narrowed.as_slice(),
+            [SchemaChange::PropertyTypeChanged {
+                change: TypeChange::Narrowed,
+                ..
+            }]
+        ),
+        "{narrowed:?}"
+    );
+    assert!(narrowed[0].is_breaking());
+}
+
+#[test]
+fn test_incompatible_type_change_is_breaking() {
+    let changes = changes(
+        "NODE TYPE Person LABEL Person { age :: INT }",
+        "NODE TYPE Person LABEL Person { age :: STRING }",
+    );
+    assert!(
+        matches!(
+            changes.as_slice(),
+            [SchemaChange::PropertyTypeChanged {
+                change: TypeChange::Incompatible,
+                ..
+            }]
+        ),
+        "{changes:?}"
+    );
+    assert_eq!(
+        changes[0].to_string(),
+        "property 'age' of node type 'Person' changed from Int to String"
+    );
+}
+
+#[test]
+fn test_required_flag_changes() {
+    let tightened = changes(
+        "NODE TYPE Person LABEL Person { name :: STRING }",
+        "NODE TYPE Person LABEL Person { name :: STRING NOT NULL }",
+    );
+    assert_eq!(
+        tightened,
+        vec![SchemaChange::RequiredChanged {
+            owner: person(),
+            property: "name".into(),
+            required: true,
+        }]
+    );
+    assert!(tightened[0].is_breaking());
+
+    let relaxed = changes(
+        "NODE TYPE Person LABEL Person { name :: STRING NOT NULL }",
+        "NODE TYPE Person LABEL Person { name :: STRING }",
+    );
+    assert!(!relaxed[0].is_breaking());
+}
+
+#[test]
+fn test_constraint_changes_ignore_source_positions() {
+    let unchanged = changes(
+        "NODE TYPE Person LABEL Person { age :: INT } CONSTRAINT CHECK (age >= 0)",
+        "NODE TYPE Person LABEL Person {  age :: INT  }  CONSTRAINT CHECK ( age >= 0 )",
+    );
+    assert!(unchanged.is_empty(), "{unchanged:?}");
+
+    let changes = changes(
+        "NODE TYPE Person LABEL Person { id :: INT, age :: INT } CONSTRAINT CHECK (age >= 0)",
+        "NODE TYPE Person LABEL Person { id :: INT, age :: INT } CONSTRAINT KEY (id)",
+    );
+    assert_eq!(changes.len(), 2, "{changes:?}");
+    assert!(matches!(
+        &changes[0],
+        SchemaChange::ConstraintRemoved {
+            constraint: ConstraintMeta::Check { .. },
+            ..
+        }
+    ));
+    assert!(!changes[0].is_breaking());
+    assert_eq!(
+        changes[1],
+        SchemaChange::ConstraintAdded {
+            owner: person(),
+            constraint: ConstraintMeta::PrimaryKey {
+                properties: vec!["id".into()]
+            },
+        }
+    );
+    assert!(changes[1].is_breaking());
+}
+
+#[test]
+fn test_inheritance_changes() {
+    let changes = changes(
+        "NODE TYPE Entity LABEL Entity, NODE TYPE Person LABEL Person",
+        "NODE TYPE Entity LABEL Entity, NODE TYPE Person INHERITS Entity LABEL Person",
+    );
+    assert_eq!(
+        changes,
+        vec![SchemaChange::ParentAdded {
+            owner: person(),
+            parent: TypeRef::NodeType("Entity".into()),
+        }]
+    );
+    assert!(!changes[0].is_breaking());
+}
+
+#[test]
+fn test_edge_type_rename() {
+    let old = SchemaSnapshotBuilder::new()
+        .with_edge_type("KNOWS", |b| b.add_property(PropertyMeta::date("since", false)))
+        .build();
+    let new = SchemaSnapshotBuilder::new()
+        .with_edge_type("FRIEND_OF", |b| b.add_property(PropertyMeta::date("since", false)))
+        .build();
+    let diff = schema_diff(&old, &new);
+    assert_eq!(
+        diff.changes,
+        vec![SchemaChange::TypeRenamed {
+            from: TypeRef::EdgeType("KNOWS".into()),
+            to: TypeRef::EdgeType("FRIEND_OF".into()),
+        }]
+    );
+    assert_eq!(
+        diff.changes[0].to_string(),
+        "edge type 'KNOWS' renamed to 'FRIEND_OF'"
+    );
+}
+
+#[test]
+fn test_affected_queries_reports_newly_failing_queries() {
+    let old: Arc<dyn SchemaSnapshot> = Arc::new(
+        SchemaSnapshotBuilder::new()
+            .with_node_type("Person", |b| {
+                b.add_property(PropertyMeta::string("name", true))
+                    .add_property(PropertyMeta::int("age", false))
+            })
+            .with_node_type("City", |b| b.add_property(PropertyMeta::string("name", true)))
+            .build(),
+    );
+    let new: Arc<dyn SchemaSnapshot> = Arc::new(
+        SchemaSnapshotBuilder::new()
+            .with_node_type("Person", |b| b.add_property(PropertyMeta::string("name", true)))
+            .build(),
+    );
+
+    let queries = [
+        "MATCH (p:Person) RETURN p.name",
+        "MATCH (p:Person) RETURN p.age",
+        "MATCH (c:City) RETURN c.name",
+        "MATCH (x:Unknown) RETURN x",
+        "MATCH (",
+    ];
+    let impacts = affected_queries(&queries, old, new);
+    let indexes: Vec<usize> = impacts.iter().map(|impact| impact.index).collect();
+    assert_eq!(indexes, vec![1, 2]);
+    assert!(impacts.iter().all(|impact| !impact.diagnostics.is_empty()));
+    assert_eq!(impacts[0].source, queries[1]);
+}
+
+#[test]
+fn test_affected_queries_tell_errors_apart_by_position() {
+    let old: Arc<dyn SchemaSnapshot> = Arc::new(
+        SchemaSnapshotBuilder::new()
+            .with_node_type("Person", |b| b.add_property(PropertyMeta::int("age", false)))
+            .with_node_type("City", |b| b.add_property(PropertyMeta::string("name", true)))
+            .build(),
+    );
+    let new: Arc<dyn SchemaSnapshot> = Arc::new(
+        SchemaSnapshotBuilder::new()
+            .with_node_type("Person", |b| b.add_property(PropertyMeta::string("name", true)))
+            .with_node_type("City", |b| b.add_property(PropertyMeta::string("name", true)))
+            .build(),
+    );
+    // The error on City's `p.age` is already there; the one on Person's is new
+    let query = "MATCH (p:Person) RETURN p.age NEXT MATCH (p:City) RETURN p.age";
+    let impacts = affected_queries(&[query], old, new);
+    assert_eq!(impacts.len(), 1);
+    let spans: Vec<_> = impacts[0]
+        .diagnostics
+        .iter()
+        .map(|diag| diag.labels[0].span.clone())
+        .collect();
+    let first = query.find("p.age").unwrap();
+    assert_eq!(spans, vec![first..first + "p.age".len()]);
+}
@@ -0,0 +1,208 @@
+//! Schema export tests
+//!
+//! Schema snapshots are written out as `CREATE GRAPH TYPE` statements that
+//! parse back into the same schema.
+
+use std::sync::Arc;
+
+use gql_parser::ast::Statement;
+use gql_parser::parse;
+use gql_parser::semantic::CatalogOverlay;
+use gql_parser::semantic::metadata_provider::{MetadataProvider, MockMetadataProvider};
+use gql_parser::semantic::schema_catalog::{
+    ConstraintMeta, EdgeEndpoints, GraphRef, InMemorySchemaSnapshot, PropertyConstraint,
+    PropertyMeta, SchemaSnapshot, SchemaSnapshotBuilder, TypeRef,
+};
+use gql_parser::semantic::schema_diff::{SchemaChange, schema_diff};
+use gql_parser::semantic::schema_export::{export_graph_type, graph_type_ddl};
+
+/// Parses `ddl` and applies it to an empty catalog.
+fn reimport(ddl: &str) -> Arc<dyn SchemaSnapshot> {
+    let result = parse(ddl);
+    let program = result.ast.unwrap_or_else(|| panic!("{ddl}\n{:?}", result.diagnostics));
+    let Statement::Catalog(catalog) = &program.statements[0] else {
+        panic!("expected catalog statement");
+    };
+    let base = MockMetadataProvider::new();
+    let mut overlay = CatalogOverlay::new(&base);
+    let diagnostics = overlay.apply(&catalog.kind);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    overlay.get_graph_type_snapshot("exported").unwrap()
+}
+
+fn assert_round_trips(snapshot: &dyn SchemaSnapshot) -> String {
+    let ddl = graph_type_ddl("exported", snapshot).expect("snapshot should export");
+    let reimported = reimport(&ddl);
+    let diff = schema_diff(snapshot, &*reimported);
+    assert!(diff.is_empty(), "{ddl}\n{:?}", diff.changes);
+    ddl
+}
+
+#[test]
+fn test_example_schema_exports_canonical_ddl() {
+    let ddl = assert_round_trips(&InMemorySchemaSnapshot::example());
+    assert_eq!(
+        ddl,
+        "CREATE GRAPH TYPE exported AS {\n  \
+         NODE TYPE Person LABEL Person { age :: INT, name :: STRING NOT NULL },\n  \
+         DIRECTED EDGE TYPE KNOWS LABEL KNOWS { since :: INT } CONNECTING (Person TO Person)\n}"
+    );
+}
+
+#[test]
+fn test_constraints_and_inheritance_round_trip() {
+    let snapshot = SchemaSnapshotBuilder::new()
+        .with_node_type("Entity", |node| {
+            node.add_property(PropertyMeta::int("id", true))
+                .add_constraint(ConstraintMeta::PrimaryKey {
+                    properties: vec!["id".into()],
+                })
+        })
+        .with_node_type("Person", |node| {
+            node.add_parent(TypeRef::NodeType("Entity".into()))
+                .add_property(PropertyMeta::string("email", false))
+                .add_property(PropertyMeta::int("age", false))
+                .add_constraint(ConstraintMeta::Unique {
+                    properties: vec!["email".into()],
+                })
+                .add_constraint(ConstraintMeta::Mandatory {
+                    properties: vec!["email".into()],
+                })
+        })
+        .build();
+    let ddl = assert_round_trips(&snapshot);
+    assert!(
+        ddl.contains(
+            "NODE TYPE Person INHERITS Entity LABEL Person { age :: INT, email :: STRING } \
+             CONSTRAINT UNIQUE (email) CONSTRAINT MANDATORY (email)"
+        ),
+        "{ddl}"
+    );
+}
+
+#[test]
+fn test_check_constraints_are_exported() {
+    let program = parse(
+        "CREATE GRAPH TYPE exported AS { \
+         NODE TYPE Person LABEL Person { age :: INT } CONSTRAINT CHECK (age >= 0 AND age < 150) }",
+    )
+    .ast
+    .unwrap();
+    let Statement::Catalog(catalog) = &program.statements[0] else {
+        panic!("expected catalog statement");
+    };
+    let base = MockMetadataProvider::new();
+    let mut overlay = CatalogOverlay::new(&base);
+    assert!(overlay.apply(&catalog.kind).is_empty());
+    let snapshot = overlay.get_graph_type_snapshot("exported").unwrap();
+
+    let ddl = assert_round_trips(&*snapshot);
+    assert!(
+        ddl.contains("CONSTRAINT CHECK (age >= 0 AND age < 150)"),
+        "{ddl}"
+    );
+}
+
+#[test]
+fn test_property_defaults_and_checks_are_exported() {
+    let snapshot = reimport(
+        "CREATE GRAPH TYPE exported AS { \
+         NODE TYPE Person LABEL Person { age :: INT DEFAULT 0 CHECK (age >= 0), name :: STRING } }",
+    );
+    let age = snapshot.property(TypeRef::NodeType("Person".into()), "age").unwrap();
+    assert!(matches!(
+        age.constraints[..],
+        [PropertyConstraint::Default { .. }, PropertyConstraint::Check { .. }]
+    ));
+
+    let ddl = assert_round_trips(&*snapshot);
+    assert!(
+        ddl.contains("{ age :: INT DEFAULT 0 CHECK (age >= 0), name :: STRING }"),
+        "{ddl}"
+    );
+}
+
+#[test]
+fn test_edge_endpoints_are_preserved() {
+    let snapshot = SchemaSnapshotBuilder::new()
+        .with_node_type("Person", |node| node)
+        .with_node_type("City", |node| node)
+        .with_edge_type("LIVES_IN", |edge| edge.connecting("Person", "City"))
+        .with_edge_type("MARRIED", |edge| {
+            edge.connecting_undirected("Person", "Person")
+        })
+        .with_edge_type("RELATED", |edge| edge)
+        .build();
+    let ddl = assert_round_trips(&snapshot);
+    assert!(ddl.contains("DIRECTED EDGE TYPE LIVES_IN LABEL LIVES_IN CONNECTING (Person TO City)"));
+    assert!(
+        ddl.contains("UNDIRECTED EDGE TYPE MARRIED LABEL MARRIED CONNECTING (Person TO Person)")
+    );
+    assert!(ddl.contains("DIRECTED EDGE TYPE RELATED LABEL RELATED CONNECTING (() TO ())"));
+
+    let reimported = reimport(&ddl);
+    assert_eq!(
+        reimported.edge_type("MARRIED").unwrap().endpoints,
+        Some(EdgeEndpoints {
+            source: "Person".into(),
+            destination: "Person".into(),
+            directed: false,
+        })
+    );
+    assert_eq!(reimported.edge_type("RELATED").unwrap().endpoints, None);
+}
+
+#[test]
+fn test_provider_fixtures_round_trip() {
+    let provider = MockMetadataProvider::with_extended_fixtures();
+    for name in ["social_graph", "financial", "ecommerce", "healthcare"] {
+        let graph = GraphRef { name: name.into() };
+        let snapshot = provider.get_schema_snapshot(&graph, None).unwrap();
+        let ddl = graph_type_ddl("exported", &*snapshot).expect("fixture should export");
+        let diff = schema_diff(&*snapshot, &*reimport(&ddl));
+        // Property-level constraints come back as constraints of their type.
+        assert!(
+            diff.changes
+                .iter()
+                .all(|change| matches!(change, SchemaChange::ConstraintAdded { .. })),
+            "{name}: {:?}",
+            diff.changes
+        );
+    }
+}
+
+#[test]
+fn test_constraints_without_graph_type_syntax_are_reported() {
+    let snapshot = SchemaSnapshotBuilder::new()
+        .with_node_type("Order", |node| {
+            let default = |value: &str| PropertyConstraint::Default {
+                value: gql_parser::ast::Expression::Literal(
+                    gql_parser::ast::Literal::Integer(value.into()),
+                    0..0,
+                ),
+            };
+            node.add_property(
+                PropertyMeta::int("customer", false)
+                    .with_constraint(default("0"))
+                    .with_constraint(default("1")),
+            )
+            .add_constraint(ConstraintMeta::ForeignKey {
+                properties: vec!["customer".into()],
+                references: TypeRef::NodeType("Customer".into()),
+                referenced_properties: vec!["id".into()],
+            })
+        })
+        .build();
+    let diagnostics = export_graph_type("exported", &snapshot).unwrap_err();
+    let messages: Vec<_> = diagnostics
+        .iter()
+        .map(|diag| diag.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "foreign key on node type 'Order' cannot be expressed in a graph type",
+            "property 'customer' of node type 'Order' has more than one default value",
+        ]
+    );
+}
@@ -64,6 +64,31 @@ fn test_scope_proper_linear_flow() {
     }
 }
 
+#[test]
+fn test_scope_columns_carried_across_next() {
+    // NEXT hands the returned columns, and only those, to the next statement
+    let source = "MATCH (n:Person)-[:KNOWS]->(f) RETURN n, count(f) AS friends \
+                  NEXT FILTER friends > 3 MATCH (n)-[:WORKS_AT]->(c) RETURN n, c, friends";
+    let program = parse(source).ast.expect("source should parse");
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(
+        outcome.is_success(),
+        "Should succeed: returned columns are visible after NEXT"
+    );
+
+    for source in [
+        "MATCH (n:Person)-[:KNOWS]->(f) RETURN n NEXT RETURN f",
+        "MATCH (n:Person) RETURN n NEXT RETURN n; RETURN n",
+    ] {
+        let program = parse(source).ast.expect("source should parse");
+        let outcome = SemanticValidator::new().validate(&program);
+        assert!(
+            !outcome.is_success(),
+            "Should fail: variable outside the returned columns in {source}"
+        );
+    }
+}
+
 #[test]
 fn test_composite_query_scope_isolation_union() {
     // UNION queries should have isolated scopes
@@ -0,0 +1,131 @@
+//! Transaction state validation tests
+//!
+//! Statements are checked against the transaction they run in: READ ONLY
+//! transactions reject data and catalog changes, COMMIT and ROLLBACK need an
+//! active transaction, and session settings cannot change mid-transaction.
+
+use gql_parser::ir::ValidationOutcome;
+use gql_parser::parse;
+use gql_parser::semantic::callable::{CallableKind, CallableSignature, SignatureType, Volatility};
+use gql_parser::semantic::metadata_provider::MockMetadataProvider;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn validate(source: &str) -> ValidationOutcome {
+    let result = parse(source);
+    let program = result
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    SemanticValidator::new().validate(&program)
+}
+
+fn error_messages(outcome: &ValidationOutcome) -> Vec<&str> {
+    outcome
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.severity == gql_parser::diag::DiagSeverity::Error)
+        .map(|diag| diag.message.as_str())
+        .collect()
+}
+
+#[test]
+fn test_read_only_transaction_rejects_data_modification() {
+    for statement in [
+        "INSERT (:Person {name: 'Alice'})",
+        "MATCH (n:Person) SET n.age = 30",
+        "MATCH (n:Person) REMOVE n.age",
+        "MATCH (n:Person) DETACH DELETE n",
+    ] {
+        let source = format!("START TRANSACTION READ ONLY; {statement}; COMMIT");
+        let outcome = validate(&source);
+        assert_eq!(
+            error_messages(&outcome),
+            vec!["Data-modifying statement cannot run in a READ ONLY transaction"],
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_read_only_transaction_rejects_catalog_modification() {
+    let outcome = validate("START TRANSACTION READ ONLY; CREATE GRAPH g ANY; COMMIT");
+    assert_eq!(
+        error_messages(&outcome),
+        vec!["Catalog-modifying statement cannot run in a READ ONLY transaction"]
+    );
+}
+
+#[test]
+fn test_read_only_transaction_allows_queries() {
+    let outcome = validate("START TRANSACTION READ ONLY; MATCH (n) RETURN n; ROLLBACK");
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+}
+
+#[test]
+fn test_read_write_transaction_allows_modification() {
+    let outcome = validate(
+        "START TRANSACTION READ WRITE; INSERT (:Person); CREATE GRAPH g ANY; COMMIT",
+    );
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+}
+
+#[test]
+fn test_modification_after_read_only_transaction_ends() {
+    let outcome = validate("START TRANSACTION READ ONLY; COMMIT; INSERT (:Person)");
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+}
+
+#[test]
+fn test_commit_and_rollback_require_active_transaction() {
+    let outcome = validate("COMMIT");
+    assert_eq!(
+        error_messages(&outcome),
+        vec!["COMMIT without an active transaction"]
+    );
+
+    let outcome = validate("START TRANSACTION; COMMIT; ROLLBACK");
+    assert_eq!(
+        error_messages(&outcome),
+        vec!["ROLLBACK without an active transaction"]
+    );
+}
+
+#[test]
+fn test_nested_start_transaction() {
+    let outcome = validate("START TRANSACTION; START TRANSACTION; COMMIT");
+    assert_eq!(
+        error_messages(&outcome),
+        vec!["START TRANSACTION inside an active transaction"]
+    );
+}
+
+#[test]
+fn test_session_command_inside_transaction() {
+    let outcome = validate("START TRANSACTION; SESSION RESET; COMMIT; SESSION RESET");
+    assert_eq!(
+        error_messages(&outcome),
+        vec!["Session command cannot run inside a transaction"]
+    );
+}
+
+#[test]
+fn test_volatile_procedure_call_is_allowed_in_read_only_transaction() {
+    let mut provider = MockMetadataProvider::new();
+    provider.add_callable(
+        "audit",
+        CallableSignature::new("audit", CallableKind::Procedure, vec![], None::<SignatureType>)
+            .with_volatility(Volatility::Volatile),
+    );
+    let program = parse("START TRANSACTION READ ONLY; CALL audit() RETURN 1; COMMIT")
+        .ast
+        .unwrap();
+    let outcome = SemanticValidator::new()
+        .with_metadata_provider(&provider)
+        .validate(&program);
+    assert!(
+        !error_messages(&outcome)
+            .iter()
+            .any(|message| message.contains("READ ONLY")),
+        "{:?}",
+        outcome.diagnostics
+    );
+}
@@ -74,8 +74,8 @@ fn test_default_cast_rules_numeric_widening() {
     // Numeric widening
     assert!(rules.can_cast(&Type::Int, &Type::Float));
 
-    // Not the reverse
-    assert!(!rules.can_cast(&Type::Float, &Type::Int));
+    // The reverse truncates explicitly
+    assert!(rules.can_cast(&Type::Float, &Type::Int));
 }
 
 #[test]
@@ -134,8 +134,12 @@ fn test_default_cast_rules_list() {
     // Integer list can cast to float list (element widening)
     assert!(rules.can_cast(&int_list, &float_list));
 
-    // Not the reverse
-    assert!(!rules.can_cast(&float_list, &int_list));
+    // The reverse converts each element
+    assert!(rules.can_cast(&float_list, &int_list));
+
+    // Elements must cast themselves
+    let date_list = Type::List(Box::new(Type::Date));
+    assert!(!rules.can_cast(&date_list, &int_list));
 
     // Integer list to string list (via toString)
     assert!(rules.can_cast(&int_list, &string_list));
@@ -157,7 +161,7 @@ fn test_cast_result_type() {
 
     // Invalid casts return Any
     assert_eq!(
-        rules.cast_result_type(&Type::Float, &Type::Int),
+        rules.cast_result_type(&Type::Date, &Type::Int),
         Type::Any
     );
 }
@@ -427,3 +431,65 @@ fn test_general_type_ref() {
         Some(Type::Int)
     );
 }
+
+/// Validates `source` and returns the type inferred for the first occurrence of `expr`.
+fn inferred_type(source: &str, expr: &str) -> Option<Type> {
+    let program = gql_parser::parse(source).ast.expect("parse should succeed");
+    let outcome = SemanticValidator::new().validate(&program);
+    let ir = outcome.ir.expect("validation should succeed");
+    let start = source.find(expr).expect("expression text should be in source");
+    ir.type_table()
+        .get_type_by_span(&(start..start + expr.len()))
+        .cloned()
+}
+
+#[test]
+fn test_cast_preserves_exact_numeric_precision() {
+    use gql_parser::ir::type_table::NumericWidth;
+
+    let source = "MATCH (n) LET a = CAST(n.x AS DECIMAL(10, 2)), b = CAST(n.y AS UINT16) RETURN a, b";
+    assert_eq!(
+        inferred_type(source, "CAST(n.x AS DECIMAL(10, 2))"),
+        Some(Type::Decimal {
+            precision: Some(10),
+            scale: Some(2)
+        })
+    );
+    assert_eq!(
+        inferred_type(source, "CAST(n.y AS UINT16)"),
+        Some(Type::UnsignedInt(NumericWidth::W16))
+    );
+}
+
+#[test]
+fn test_cast_preserves_temporal_and_bytes_kinds() {
+    let source = "MATCH (n) LET a = CAST(n.x AS ZONED DATETIME), b = CAST(n.y AS LOCAL TIME), c = CAST(n.z AS BYTES) RETURN a, b, c";
+    assert_eq!(
+        inferred_type(source, "CAST(n.x AS ZONED DATETIME)"),
+        Some(Type::ZonedDatetime)
+    );
+    assert_eq!(inferred_type(source, "CAST(n.y AS LOCAL TIME)"), Some(Type::Time));
+    assert_eq!(inferred_type(source, "CAST(n.z AS BYTES)"), Some(Type::Bytes));
+}
+
+#[test]
+fn test_zoned_literals_and_mixed_arithmetic() {
+    let source = "MATCH (n) LET a = DATETIME '2024-01-01T10:00:00+02:00', b = 1 + 2.5, c = 7 / 2 RETURN a, b, c";
+    assert_eq!(
+        inferred_type(source, "DATETIME '2024-01-01T10:00:00+02:00'"),
+        Some(Type::ZonedDatetime)
+    );
+    assert_eq!(inferred_type(source, "1 + 2.5"), Some(Type::Float));
+    assert_eq!(inferred_type(source, "7 / 2"), Some(Type::Float));
+}
+
+#[test]
+fn test_time_literals_are_zoned_by_their_offset() {
+    let source = "MATCH (n) LET a = TIME '10:00:00Z', b = TIME '10:00:00', c = DATETIME '2024-01-01 10:00:00' RETURN a, b, c";
+    assert_eq!(inferred_type(source, "TIME '10:00:00Z'"), Some(Type::ZonedTime));
+    assert_eq!(inferred_type(source, "TIME '10:00:00'"), Some(Type::Time));
+    assert_eq!(
+        inferred_type(source, "DATETIME '2024-01-01 10:00:00'"),
+        Some(Type::Timestamp)
+    );
+}
@@ -0,0 +1,170 @@
+//! SQL/PGQ emitter tests
+//!
+//! Validated linear queries are rendered as `SELECT ... FROM GRAPH_TABLE`
+//! text; constructs SQL/PGQ cannot express are reported instead.
+
+use gql_parser::ast::Statement;
+use gql_parser::emit::EmitResult;
+use gql_parser::emit::sql_pgq;
+use gql_parser::parse;
+use gql_parser::semantic::validator::SemanticValidator;
+
+fn emit(source: &str) -> EmitResult {
+    let result = parse(source);
+    let program = result
+        .ast
+        .unwrap_or_else(|| panic!("failed to parse: {source}"));
+    let outcome = SemanticValidator::new().validate(&program);
+    assert!(outcome.is_success(), "{:?}", outcome.diagnostics);
+    let Statement::Query(statement) = &program.statements[0] else {
+        panic!("expected query statement");
+    };
+    sql_pgq::query(&statement.query, "social")
+}
+
+fn emit_errors(source: &str) -> Vec<String> {
+    emit(source)
+        .expect_err("query should not be expressible")
+        .into_iter()
+        .map(|diag| diag.message)
+        .collect()
+}
+
+#[test]
+fn test_match_return_renders_graph_table() {
+    let sql = emit(
+        "MATCH (a:Person)-[e:KNOWS]->(b:Person) WHERE a.age > 30 \
+         RETURN a.name AS person, b.name AS friend",
+    )
+    .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT person, friend\n\
+         FROM GRAPH_TABLE (social\n  \
+         MATCH (a IS Person)-[e IS KNOWS]->(b IS Person)\n  \
+         WHERE a.age > 30\n  \
+         COLUMNS (a.name AS person, b.name AS friend)\n\
+         )"
+    );
+}
+
+#[test]
+fn test_aggregates_group_outside_the_graph_table() {
+    let sql = emit(
+        "MATCH (a:Person)-[:KNOWS]->{1,3}(b:Person) \
+         RETURN b.name AS name, count(*) AS n, avg(a.age) AS age \
+         ORDER BY n DESC, name OFFSET 5 LIMIT 10",
+    )
+    .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT name, COUNT(*) AS n, AVG(a_age) AS age\n\
+         FROM GRAPH_TABLE (social\n  \
+         MATCH (a IS Person)-[IS KNOWS]->{1,3}(b IS Person)\n  \
+         COLUMNS (b.name AS name, a.age AS a_age)\n\
+         )\n\
+         GROUP BY name\n\
+         ORDER BY n DESC, name\n\
+         OFFSET 5 ROWS\n\
+         FETCH FIRST 10 ROWS ONLY"
+    );
+}
+
+#[test]
+fn test_aggregate_only_return_keeps_a_graph_table_column() {
+    let sql = emit("MATCH (a:Person)-[:KNOWS]->(b) RETURN count(*) AS n, count(b) AS one").unwrap();
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) AS n, COUNT(*) AS one\n\
+         FROM GRAPH_TABLE (social\n  \
+         MATCH (a IS Person)-[IS KNOWS]->(b)\n  \
+         COLUMNS (1 AS one_2)\n\
+         )"
+    );
+
+    let sql = emit("MATCH (a:Person) RETURN count(a.age) AS c").unwrap();
+    assert!(sql.contains("COLUMNS (a.age AS a_age)"), "{sql}");
+}
+
+#[test]
+fn test_consecutive_matches_and_filters_share_one_graph_table() {
+    let sql = emit(
+        "MATCH (a:Person) MATCH (a)-[:LIVES_IN]->(c:City|Town) \
+         FILTER c.population > 1000 OR c.capital = TRUE \
+         RETURN DISTINCT c.name",
+    )
+    .unwrap();
+    assert!(sql.starts_with("SELECT DISTINCT name\n"), "{sql}");
+    assert!(
+        sql.contains(
+            "MATCH (a IS Person), (a)-[IS LIVES_IN]->(c IS City|Town)\n  \
+             WHERE c.population > 1000 OR c.capital = TRUE\n"
+        ),
+        "{sql}"
+    );
+}
+
+#[test]
+fn test_property_specifications_become_element_conditions() {
+    let sql = emit("MATCH (a:Person {name: 'O\\'Brien'})-[e]-(b) RETURN b.age").unwrap();
+    assert!(
+        sql.contains("MATCH (a IS Person WHERE a.name = 'O''Brien')-[e]-(b)"),
+        "{sql}"
+    );
+}
+
+#[test]
+fn test_path_search_prefixes_are_kept() {
+    let sql = emit("MATCH ANY SHORTEST TRAIL (a)-[e]->{1,5}(b) RETURN a.name, b.name").unwrap();
+    assert!(
+        sql.contains("MATCH ANY SHORTEST TRAIL (a)-[e]->{1,5}(b)"),
+        "{sql}"
+    );
+    assert!(
+        sql.contains("COLUMNS (a.name AS name, b.name AS name_2)"),
+        "{sql}"
+    );
+}
+
+#[test]
+fn test_use_clause_names_the_graph() {
+    let sql = emit("USE finance MATCH (n) RETURN n.id").unwrap();
+    assert!(sql.contains("FROM GRAPH_TABLE (finance\n"), "{sql}");
+}
+
+#[test]
+fn test_unbounded_quantifier_is_reported() {
+    let diagnostics = emit("MATCH (a)-[e]->+(b) RETURN b.name").unwrap_err();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "Unbounded quantifier cannot be rendered as SQL/PGQ"
+    );
+    assert!(diagnostics[0].help.is_some());
+}
+
+#[test]
+fn test_unsupported_statements_are_reported() {
+    assert_eq!(
+        emit_errors("MATCH (a) OPTIONAL MATCH (a)-[e]->(b) LET n = a.name RETURN n"),
+        vec![
+            "OPTIONAL MATCH cannot be rendered as SQL/PGQ",
+            "LET cannot be rendered as SQL/PGQ",
+        ]
+    );
+    assert_eq!(
+        emit_errors("MATCH (a) RETURN a.x UNION MATCH (b) RETURN b.x"),
+        vec!["Composite query cannot be rendered as SQL/PGQ"]
+    );
+}
+
+#[test]
+fn test_whole_elements_and_lists_are_reported() {
+    assert_eq!(
+        emit_errors("MATCH (a)-[e]->(b) RETURN b, [a.x, a.y] AS xs"),
+        vec![
+            "Graph element value cannot be rendered as SQL/PGQ",
+            "List value cannot be rendered as SQL/PGQ",
+        ]
+    );
+}